@@ -262,19 +262,15 @@ fn benchmark_ligero() -> LigeroResults {
     let mut verify_times = Vec::new();
     let mut proof_sizes = Vec::new();
     
-    // We need to use custom parameters that ensure FFT-friendly sizes
-    // The tableau width must be a power of 2 for FFT
-    let custom_params = LigeroParams {
-        block_size: 64,  // Power of 2
-        extension_factor: 2,  // Results in width = 64 * 2 = 128 (power of 2)
-        num_blinding_rows: 3,
+    // Security settings only; the tableau dimensions are fitted to each
+    // constraint system by `LigeroInstance::with_auto_params`
+    let base_params = LigeroParams {
         num_col_openings: 100,
         num_ldt_queries: 50,
-        security_bits: 80,
-        use_subfield: false,
+        ..LigeroParams::security_80()
     };
     
-    // Test different witness counts (must be <= block_size for single block)
+    // Test different witness counts
     for &witness_count in &[16, 32, 64] {
         println!("  Benchmarking Ligero with {} witnesses...", witness_count);
         
@@ -312,11 +308,11 @@ fn benchmark_ligero() -> LigeroResults {
         let mut last_proof = None;
         
         for _ in 0..iterations {
-            let instance = LigeroInstance::new(custom_params.clone(), cs.clone()).unwrap();
+            let instance = LigeroInstance::with_auto_params(base_params.clone(), cs.clone()).unwrap();
             let prover = LigeroProver::new(instance).unwrap();
             
             let start = Instant::now();
-            match prover.prove(&witness, &mut OsRng) {
+            match prover.prove(&witness, &[], &mut OsRng) {
                 Ok(proof) => {
                     total_prove_time += start.elapsed().as_secs_f64();
                     proof_size = estimate_proof_size(&proof, witness_count);
@@ -345,13 +341,13 @@ fn benchmark_ligero() -> LigeroResults {
         
         // Benchmark verification
         let proof = last_proof.unwrap();
-        let instance = LigeroInstance::new(custom_params.clone(), cs).unwrap();
+        let instance = LigeroInstance::with_auto_params(base_params.clone(), cs).unwrap();
         let verifier = LigeroVerifier::new(instance).unwrap();
         
         let verify_iterations = iterations * 10;
         let start = Instant::now();
         for _ in 0..verify_iterations {
-            match verifier.verify(&proof) {
+            match verifier.verify(&proof, &[]) {
                 Ok(_) => {},
                 Err(e) => {
                     println!("    Warning: Verification failed: {}", e);
@@ -487,24 +483,19 @@ fn benchmark_full_system() -> SystemResults {
         );
     }
     
-    // Custom params for proper FFT
     let params = LigeroParams {
-        block_size: 64,
-        extension_factor: 2,
-        num_blinding_rows: 3,
         num_col_openings: 100,
         num_ldt_queries: 50,
-        security_bits: 80,
-        use_subfield: false,
+        ..LigeroParams::security_80()
     };
     
-    let instance = LigeroInstance::new(params, cs).unwrap();
+    let instance = LigeroInstance::with_auto_params(params, cs).unwrap();
     let witness: Vec<Fp128> = (0..witness_count).map(|i| Fp128::from_u64(i as u64)).collect();
     
     // Time full proof generation
     let prover = LigeroProver::new(instance.clone()).unwrap();
     let start = Instant::now();
-    let ligero_proof = match prover.prove(&witness, &mut OsRng) {
+    let ligero_proof = match prover.prove(&witness, &[], &mut OsRng) {
         Ok(p) => p,
         Err(_) => {
             // Return dummy results if proof fails
@@ -524,7 +515,7 @@ fn benchmark_full_system() -> SystemResults {
     // Time full verification
     let verifier = LigeroVerifier::new(instance).unwrap();
     let start = Instant::now();
-    let _ = verifier.verify(&ligero_proof).unwrap();
+    let _ = verifier.verify(&ligero_proof, &[]).unwrap();
     let full_verify_time_ms = start.elapsed().as_millis() as f64;
     
     // Estimate proof sizes
@@ -563,12 +554,12 @@ fn benchmark_full_system() -> SystemResults {
 
 fn estimate_proof_size<F: Field>(proof: &longfellow_ligero::LigeroProof<F>, witness_size: usize) -> usize {
     // Estimate based on proof structure:
-    // - Column roots: 32 bytes each
+    // - Column root: 32 bytes
     // - Linear responses: witness_size * 16 bytes
     // - Quadratic responses: similar
     // - Column openings: depends on parameters
     
-    let roots_size = proof.column_roots.len() * 32;
+    let roots_size = proof.column_root.len();
     let field_element_size = 16; // Fp128 is roughly 16 bytes
     let linear_responses_size = proof.linear_responses.len() * field_element_size;
     let quadratic_responses_size = proof.quadratic_responses.len() * field_element_size;
@@ -455,17 +455,15 @@ fn generate_ligero_proof() -> Result<ComprehensiveProof> {
     info!("Checking w[0] * w[1] = 1 * 1 = 1 = w[2]");
     
     // Debug: Check constraint satisfaction manually BEFORE creating prover
-    let satisfied = instance.constraints.is_satisfied(&witness)?;
+    let satisfied = instance.constraints.is_satisfied(&witness, &[])?;
     info!("Manual constraint check: {}", satisfied);
     
     // Debug each constraint individually
-    for i in 0..instance.constraints.linear_constraints.num_constraints {
+    for (i, row) in instance.constraints.linear_constraints.matrix.rows().enumerate() {
         let mut sum = Fp128::zero();
-        for &(row, col, ref value) in &instance.constraints.linear_constraints.matrix {
-            if row == i {
-                sum += *value * witness[col];
-                info!("  constraint {} term: w[{}] * {:?} = {:?}", i, col, value, *value * witness[col]);
-            }
+        for (col, value) in row.iter() {
+            sum += value * witness[col];
+            info!("  constraint {} term: w[{}] * {:?} = {:?}", i, col, value, value * witness[col]);
         }
         let rhs = instance.constraints.linear_constraints.rhs[i];
         info!("  constraint {}: sum = {:?}, rhs = {:?}, equal = {}", i, sum, rhs, sum == rhs);
@@ -510,7 +508,7 @@ fn generate_ligero_proof() -> Result<ComprehensiveProof> {
     let prover = LigeroProver::new(instance)?;
     
     // Generate actual proof
-    let proof = prover.prove(&witness, &mut OsRng)?;
+    let proof = prover.prove(&witness, &[], &mut OsRng)?;
     
     // Serialize proof data (manually since Fp128 doesn't implement Serialize)
     let proof_summary = format!("{} column openings, {} ldt responses", 
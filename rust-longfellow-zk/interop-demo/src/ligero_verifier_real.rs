@@ -157,6 +157,17 @@ pub fn reconstruct_constraint_system<F: Field>(
                 wire_count += 2;
             }
             
+            Predicate::NotRevoked { .. } | Predicate::StatusEquals { .. } => {
+                // The sparse Merkle path is checked against the published
+                // list root; one wire carries the path check result
+                cs.add_linear_constraint(
+                    vec![(wire_index, F::one())],
+                    F::one(),
+                );
+                wire_index += 1;
+                wire_count += 1;
+            }
+            
             Predicate::Custom { id, params } => {
                 // Custom predicates would have their own constraint patterns
                 // For now, add a simple constraint
@@ -1,17 +1,10 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use longfellow_algebra::{fft::FFT, traits::Field, Fp128};
+use longfellow_algebra::{fft::FFT, fft_planner::FftPlanner, traits::Field, Fp128};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
 fn get_root_of_unity(n: usize) -> Fp128 {
-    // For benchmarking, use a simple value that works
-    // In production, this would be a proper nth root of unity
-    match n {
-        4 => Fp128::from_u64(17166008163159356379u64),
-        8 => Fp128::from_u64(123456789u64),
-        16 => Fp128::from_u64(987654321u64),
-        _ => Fp128::from_u64(35u64),
-    }
+    Fp128::get_root_of_unity(n.trailing_zeros()).unwrap()
 }
 
 fn bench_fft_forward(c: &mut Criterion) {
@@ -109,10 +102,93 @@ fn bench_polynomial_multiplication(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_repeated_encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Repeated Encoding (16 rows)");
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    const ROWS: usize = 16;
+    
+    for log_size in [8, 10, 12].iter() {
+        let size = 1 << log_size;
+        let omega = Fp128::get_root_of_unity(size.trailing_zeros()).unwrap();
+        
+        let rows: Vec<Vec<Fp128>> = (0..ROWS)
+            .map(|_| (0..size).map(|_| Fp128::from_u64(rng.gen::<u64>())).collect())
+            .collect();
+        
+        // Baseline: every encode rebuilds twiddles and bit-reversal tables
+        group.bench_with_input(
+            BenchmarkId::new("Rebuild", size),
+            &size,
+            |b, _| {
+                b.iter(|| {
+                    for row in &rows {
+                        let fft = FFT::new(size, omega).unwrap();
+                        let mut work = row.clone();
+                        fft.forward(&mut work).unwrap();
+                        black_box(work);
+                    }
+                })
+            },
+        );
+        
+        let planner = FftPlanner::<Fp128>::new();
+        group.bench_with_input(
+            BenchmarkId::new("Planner", size),
+            &size,
+            |b, _| {
+                b.iter(|| {
+                    for row in &rows {
+                        let fft = planner.plan(size, omega).unwrap();
+                        let mut work = row.clone();
+                        fft.forward(&mut work).unwrap();
+                        black_box(work);
+                    }
+                })
+            },
+        );
+    }
+    
+    group.finish();
+}
+
+fn bench_fft_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("FFT Forward (serial vs parallel)");
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    
+    for log_size in [14, 16, 18].iter() {
+        let size = 1 << log_size;
+        let omega = Fp128::get_root_of_unity(size.trailing_zeros()).unwrap();
+        let serial = FFT::new(size, omega).unwrap().with_parallel_threshold(usize::MAX);
+        let parallel = FFT::new(size, omega).unwrap().with_parallel_threshold(0);
+        
+        let data: Vec<Fp128> = (0..size)
+            .map(|_| Fp128::from_u64(rng.gen::<u64>()))
+            .collect();
+        
+        for (name, fft) in [("Serial", &serial), ("Parallel", &parallel)] {
+            group.bench_with_input(
+                BenchmarkId::new(name, size),
+                &size,
+                |b, _| {
+                    b.iter(|| {
+                        let mut work = data.clone();
+                        fft.forward(&mut work).unwrap();
+                        black_box(work)
+                    })
+                },
+            );
+        }
+    }
+    
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_fft_forward,
     bench_fft_inverse,
-    bench_polynomial_multiplication
+    bench_polynomial_multiplication,
+    bench_repeated_encoding,
+    bench_fft_parallel
 );
 criterion_main!(benches);
\ No newline at end of file
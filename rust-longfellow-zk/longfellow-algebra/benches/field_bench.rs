@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use longfellow_algebra::Fp128;
+use longfellow_algebra::{BabyBear, BabyBear4, Field, Fp128, Mersenne31};
 
 fn bench_field_operations(c: &mut Criterion) {
     let mut group = c.benchmark_group("Fp128");
@@ -28,5 +28,46 @@ fn bench_field_operations(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_field_operations);
+fn bench_small_field_operations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("SmallField");
+    
+    let a = BabyBear::from_u64(0x12345678);
+    let b = BabyBear::from_u64(0x0fedcba9);
+    group.bench_function("babybear_multiplication", |bench| {
+        bench.iter(|| {
+            black_box(a) * black_box(b)
+        });
+    });
+    
+    let a = Mersenne31::from_u64(0x12345678);
+    let b = Mersenne31::from_u64(0x0fedcba9);
+    group.bench_function("mersenne31_multiplication", |bench| {
+        bench.iter(|| {
+            black_box(a) * black_box(b)
+        });
+    });
+    
+    let a = BabyBear4::from_u64(0x12345678) + BabyBear4::from_u64(3).square();
+    let b = BabyBear4::from_u64(0x0fedcba9);
+    group.bench_function("babybear4_multiplication", |bench| {
+        bench.iter(|| {
+            black_box(a) * black_box(b)
+        });
+    });
+    
+    for &size in &[1024usize, 65536] {
+        let xs: Vec<BabyBear> = (0..size as u64).map(BabyBear::from_u64).collect();
+        let ys: Vec<BabyBear> = (0..size as u64).map(|i| BabyBear::from_u64(i + 17)).collect();
+        group.bench_with_input(BenchmarkId::new("babybear_mul_slice", size), &size, |bench, _| {
+            let mut out = xs.clone();
+            bench.iter(|| {
+                BabyBear::mul_assign_slice(black_box(&mut out), black_box(&ys));
+            });
+        });
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, bench_field_operations, bench_small_field_operations);
 criterion_main!(benches);
\ No newline at end of file
@@ -1,5 +1,9 @@
+use crate::fft::FFT;
+use crate::fft_planner::shared_plan;
 use crate::traits::Field;
+use longfellow_core::{LongfellowError, Result};
 use rayon::prelude::*;
+use std::sync::Arc;
 
 pub fn dot_product<F: Field>(a: &[F], b: &[F]) -> F {
     assert_eq!(a.len(), b.len(), "Vectors must have the same length");
@@ -37,6 +41,274 @@ pub fn matrix_vector_multiply<F: Field>(matrix: &[Vec<F>], vector: &[F]) -> Vec<
         .collect()
 }
 
+/// Rows accumulated together by the portable blocked kernel
+const ROW_BLOCK: usize = 4;
+
+/// Columns per tile, sized so a tile of the vector stays in L1 while every
+/// row block of the matrix streams past it
+const COL_TILE: usize = 512;
+
+/// Rows handed to each rayon task by `par_mat_vec_mul`
+const PAR_ROW_CHUNK: usize = 64;
+
+fn check_mat_vec_dims<F>(matrix: &[F], cols: usize, vector: &[F]) -> Result<usize> {
+    if cols == 0 {
+        return Err(LongfellowError::InvalidParameter(
+            "Matrix must have at least one column".to_string(),
+        ));
+    }
+    if !matrix.len().is_multiple_of(cols) {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Matrix length {} is not a multiple of {} columns",
+            matrix.len(),
+            cols
+        )));
+    }
+    if vector.len() != cols {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Vector length {} does not match {} matrix columns",
+            vector.len(),
+            cols
+        )));
+    }
+    Ok(matrix.len() / cols)
+}
+
+/// Multiply a row-major `rows x cols` matrix by `vector`
+///
+/// On CPUs with AVX2 or NEON this runs the blocked kernel compiled with
+/// that target feature enabled, otherwise the default build of it.
+pub fn mat_vec_mul<F: Field>(matrix: &[F], cols: usize, vector: &[F]) -> Result<Vec<F>> {
+    let rows = check_mat_vec_dims(matrix, cols, vector)?;
+    let mut out = vec![F::zero(); rows];
+    mat_vec_dispatch(matrix, cols, vector, &mut out);
+    Ok(out)
+}
+
+/// Portable cache-blocked matrix-vector product
+pub fn mat_vec_mul_blocked<F: Field>(matrix: &[F], cols: usize, vector: &[F]) -> Result<Vec<F>> {
+    let rows = check_mat_vec_dims(matrix, cols, vector)?;
+    let mut out = vec![F::zero(); rows];
+    mat_vec_kernel::<F, ROW_BLOCK>(matrix, cols, vector, &mut out);
+    Ok(out)
+}
+
+/// Rayon-parallel matrix-vector product over chunks of rows
+pub fn par_mat_vec_mul<F: Field>(matrix: &[F], cols: usize, vector: &[F]) -> Result<Vec<F>> {
+    let rows = check_mat_vec_dims(matrix, cols, vector)?;
+    let mut out = vec![F::zero(); rows];
+
+    out.par_chunks_mut(PAR_ROW_CHUNK)
+        .zip(matrix.par_chunks(PAR_ROW_CHUNK * cols))
+        .for_each(|(out_chunk, rows_chunk)| {
+            mat_vec_dispatch(rows_chunk, cols, vector, out_chunk);
+        });
+
+    Ok(out)
+}
+
+fn mat_vec_dispatch<F: Field>(matrix: &[F], cols: usize, vector: &[F], out: &mut [F]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just checked
+            unsafe { mat_vec_avx2(matrix, cols, vector, out) };
+            return;
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            // SAFETY: NEON support was just checked
+            unsafe { mat_vec_neon(matrix, cols, vector, out) };
+            return;
+        }
+    }
+
+    mat_vec_kernel::<F, ROW_BLOCK>(matrix, cols, vector, out);
+}
+
+/// The blocked kernel compiled with AVX2 enabled, four rows per block
+///
+/// There are no hand-written intrinsics: any vectorization of the
+/// independent accumulators is left to the compiler.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn mat_vec_avx2<F: Field>(matrix: &[F], cols: usize, vector: &[F], out: &mut [F]) {
+    mat_vec_kernel::<F, 4>(matrix, cols, vector, out);
+}
+
+/// The blocked kernel compiled with NEON enabled, two rows per block;
+/// as with AVX2, vectorization is left to the compiler
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn mat_vec_neon<F: Field>(matrix: &[F], cols: usize, vector: &[F], out: &mut [F]) {
+    mat_vec_kernel::<F, 2>(matrix, cols, vector, out);
+}
+
+/// Blocked kernel: for each column tile, accumulate `LANES` rows at a time
+/// in registers, then fold the partial sums into `out`
+#[inline(always)]
+fn mat_vec_kernel<F: Field, const LANES: usize>(
+    matrix: &[F],
+    cols: usize,
+    vector: &[F],
+    out: &mut [F],
+) {
+    let rows = out.len();
+    let full = rows - rows % LANES;
+    out.iter_mut().for_each(|o| *o = F::zero());
+
+    for c0 in (0..cols).step_by(COL_TILE) {
+        let c1 = (c0 + COL_TILE).min(cols);
+        let tile = &vector[c0..c1];
+
+        for r0 in (0..full).step_by(LANES) {
+            let mut acc = [F::zero(); LANES];
+            for (k, &v) in tile.iter().enumerate() {
+                for (l, a) in acc.iter_mut().enumerate() {
+                    *a += matrix[(r0 + l) * cols + c0 + k] * v;
+                }
+            }
+            for (o, a) in out[r0..r0 + LANES].iter_mut().zip(acc.iter()) {
+                *o += *a;
+            }
+        }
+
+        for (r, o) in out.iter_mut().enumerate().skip(full) {
+            *o += dot_product(&matrix[r * cols + c0..r * cols + c1], tile);
+        }
+    }
+}
+
+/// Below this many diagonals the direct product beats three FFTs
+const TOEPLITZ_FFT_THRESHOLD: usize = 64;
+
+/// Toeplitz matrix T[i][j] = t(i - j), constant along every diagonal
+///
+/// Stored by its `rows + cols - 1` diagonals. When the field has a large
+/// enough two-adic subgroup, the transform of the diagonals is computed once
+/// and each product is a convolution costing O(n log n); otherwise products
+/// fall back to the direct O(rows * cols) sum.
+#[derive(Clone)]
+pub struct ToeplitzMatrix<F: Field> {
+    rows: usize,
+    cols: usize,
+    /// diagonals[k] = t(k - (cols - 1))
+    diagonals: Vec<F>,
+    spectrum: Option<(Arc<FFT<F>>, Vec<F>)>,
+}
+
+impl<F: Field> ToeplitzMatrix<F> {
+    /// Build from the first column t(0), t(1), ... and the first row
+    /// t(0), t(-1), ...; both must start with the same entry
+    pub fn new(first_column: &[F], first_row: &[F]) -> Self {
+        assert!(
+            !first_column.is_empty() && !first_row.is_empty(),
+            "Matrix must have at least one row and column"
+        );
+        assert_eq!(first_column[0], first_row[0], "First row and column must share t(0)");
+
+        let diagonals = first_row
+            .iter()
+            .skip(1)
+            .rev()
+            .chain(first_column.iter())
+            .copied()
+            .collect();
+        Self::from_diagonals(first_column.len(), first_row.len(), diagonals)
+    }
+
+    /// Square circulant matrix C[i][j] = c[(i - j) mod n], the Toeplitz
+    /// matrix whose diagonals wrap around
+    pub fn circulant(first_column: &[F]) -> Self {
+        let n = first_column.len();
+        assert!(n > 0, "Matrix must have at least one row and column");
+
+        let diagonals = first_column[1..]
+            .iter()
+            .chain(first_column.iter())
+            .copied()
+            .collect();
+        Self::from_diagonals(n, n, diagonals)
+    }
+
+    fn from_diagonals(rows: usize, cols: usize, diagonals: Vec<F>) -> Self {
+        // A cyclic convolution of this length leaves outputs cols - 1 ..
+        // rows + cols - 2 free of wrap-around
+        let fft_size = (rows + cols - 1).next_power_of_two();
+        let spectrum = F::get_root_of_unity(fft_size.trailing_zeros())
+            .filter(|_| diagonals.len() >= TOEPLITZ_FFT_THRESHOLD)
+            .and_then(|omega| shared_plan(fft_size, omega).ok())
+            .map(|plan| {
+                let mut spectrum = diagonals.clone();
+                spectrum.resize(fft_size, F::zero());
+                plan.forward(&mut spectrum).expect("buffer sized to the plan");
+                (plan, spectrum)
+            });
+
+        Self {
+            rows,
+            cols,
+            diagonals,
+            spectrum,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Entry (row, col)
+    pub fn get(&self, row: usize, col: usize) -> F {
+        self.diagonals[row + self.cols - 1 - col]
+    }
+
+    /// Multiply by `vector`, which must have `cols` entries
+    pub fn mul_vec(&self, vector: &[F]) -> Vec<F> {
+        assert_eq!(vector.len(), self.cols, "Vector length must match matrix columns");
+
+        match &self.spectrum {
+            Some((plan, spectrum)) => {
+                let mut work = vector.to_vec();
+                work.resize(plan.size(), F::zero());
+                plan.forward(&mut work).expect("buffer sized to the plan");
+                hadamard_product(&mut work, spectrum);
+                plan.inverse(&mut work).expect("buffer sized to the plan");
+
+                work.truncate(self.rows + self.cols - 1);
+                work.drain(..self.cols - 1);
+                work
+            }
+            None => (0..self.rows)
+                .into_par_iter()
+                .map(|i| dot_product(&self.diagonals_for_row(i), vector))
+                .collect(),
+        }
+    }
+
+    /// Row i of the matrix, read from the diagonals right to left
+    fn diagonals_for_row(&self, row: usize) -> Vec<F> {
+        self.diagonals[row..row + self.cols].iter().rev().copied().collect()
+    }
+}
+
+/// Toeplitz matrix-vector product without keeping the matrix around
+pub fn toeplitz_mat_vec_mul<F: Field>(first_column: &[F], first_row: &[F], vector: &[F]) -> Vec<F> {
+    ToeplitzMatrix::new(first_column, first_row).mul_vec(vector)
+}
+
+/// Circulant matrix-vector product, i.e. the cyclic convolution of
+/// `first_column` with `vector`
+pub fn circulant_mat_vec_mul<F: Field>(first_column: &[F], vector: &[F]) -> Vec<F> {
+    ToeplitzMatrix::circulant(first_column).mul_vec(vector)
+}
+
 pub fn transpose<F: Field>(matrix: &[Vec<F>]) -> Vec<Vec<F>> {
     if matrix.is_empty() || matrix[0].is_empty() {
         return vec![];
@@ -237,4 +509,80 @@ mod tests {
             ]
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mat_vec_mul_kernels_agree() {
+        // Odd row count and more columns than one tile exercise both the
+        // remainder rows and the tile boundaries
+        let rows = 70;
+        let cols = COL_TILE + 37;
+        let matrix: Vec<TestField> = (0..rows * cols)
+            .map(|i| TestField::from_u64((i * 7 + 3) as u64))
+            .collect();
+        let vector: Vec<TestField> = (0..cols).map(|i| TestField::from_u64(i as u64 + 1)).collect();
+
+        let nested: Vec<Vec<TestField>> = matrix.chunks(cols).map(|r| r.to_vec()).collect();
+        let expected = matrix_vector_multiply(&nested, &vector);
+
+        assert_eq!(mat_vec_mul(&matrix, cols, &vector).unwrap(), expected);
+        assert_eq!(mat_vec_mul_blocked(&matrix, cols, &vector).unwrap(), expected);
+        assert_eq!(par_mat_vec_mul(&matrix, cols, &vector).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_mat_vec_mul_dimension_mismatch() {
+        let matrix = vec![TestField(1); 6];
+        assert!(mat_vec_mul(&matrix, 3, &[TestField(1); 2]).is_err());
+        assert!(mat_vec_mul(&matrix, 4, &[TestField(1); 4]).is_err());
+        assert!(par_mat_vec_mul(&matrix, 0, &[]).is_err());
+    }
+
+    fn dense_toeplitz<F: Field>(m: &ToeplitzMatrix<F>) -> Vec<Vec<F>> {
+        (0..m.rows())
+            .map(|i| (0..m.cols()).map(|j| m.get(i, j)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_toeplitz_matches_dense() {
+        use crate::Fp128;
+
+        // Tall, wide and square shapes, above and below the FFT threshold
+        for (rows, cols) in [(3, 5), (100, 70), (45, 130), (64, 64)] {
+            let column: Vec<Fp128> = (0..rows).map(|i| Fp128::from_u64(i as u64 * 31 + 7)).collect();
+            let mut row: Vec<Fp128> = (0..cols).map(|j| Fp128::from_u64(j as u64 * 17 + 2)).collect();
+            row[0] = column[0];
+            let vector: Vec<Fp128> = (0..cols).map(|j| Fp128::from_u64(j as u64 * j as u64 + 1)).collect();
+
+            let m = ToeplitzMatrix::new(&column, &row);
+            assert_eq!(m.spectrum.is_some(), rows + cols - 1 >= TOEPLITZ_FFT_THRESHOLD);
+            let expected = matrix_vector_multiply(&dense_toeplitz(&m), &vector);
+            assert_eq!(m.mul_vec(&vector), expected);
+            assert_eq!(toeplitz_mat_vec_mul(&column, &row, &vector), expected);
+        }
+    }
+
+    #[test]
+    fn test_circulant_is_cyclic_convolution() {
+        use crate::Fp128;
+
+        for n in [1, 7, 50, 128] {
+            let column: Vec<Fp128> = (0..n).map(|i| Fp128::from_u64(i as u64 * 5 + 1)).collect();
+            let vector: Vec<Fp128> = (0..n).map(|i| Fp128::from_u64(i as u64 + 9)).collect();
+
+            let expected: Vec<Fp128> = (0..n)
+                .map(|i| {
+                    (0..n).fold(Fp128::zero(), |acc, j| acc + column[(i + n - j) % n] * vector[j])
+                })
+                .collect();
+            assert_eq!(circulant_mat_vec_mul(&column, &vector), expected);
+        }
+
+        // Fields without two-adic roots use the direct product
+        let column: Vec<TestField> = (0..80).map(|i| TestField::from_u64(i * 3)).collect();
+        let vector: Vec<TestField> = (0..80).map(|i| TestField::from_u64(i + 1)).collect();
+        let m = ToeplitzMatrix::circulant(&column);
+        assert!(m.spectrum.is_none());
+        assert_eq!(m.mul_vec(&vector), matrix_vector_multiply(&dense_toeplitz(&m), &vector));
+    }
+}
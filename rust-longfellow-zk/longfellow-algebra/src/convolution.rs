@@ -109,24 +109,33 @@ pub trait ConvolutionFactory<F: Field> {
 
 /// FFT-based convolution factory
 pub struct FftConvolutionFactory<F: Field> {
-    omega_provider: Box<dyn Fn(usize) -> F>,
+    omega_provider: Box<dyn Fn(usize) -> Result<F>>,
 }
 
 impl<F: Field> FftConvolutionFactory<F> {
     /// Create a new factory with an omega provider function
     pub fn new<P: Fn(usize) -> F + 'static>(omega_provider: P) -> Self {
         Self {
-            omega_provider: Box::new(omega_provider),
+            omega_provider: Box::new(move |size| Ok(omega_provider(size))),
         }
     }
     
-    /// Create with default omega computation
+    /// Create with the field's own two-adic roots of unity
+    ///
+    /// `make` fails for FFT sizes beyond `2^F::TWO_ADICITY`; callers can
+    /// fall back to `DirectConvolutionFactory` then.
     pub fn default() -> Self {
-        Self::new(|size| {
-            // This is a placeholder - in practice, we'd compute proper root of unity
-            // based on the field and required size
-            F::from_u64(3).pow(&[((F::characteristic() - 1) / size as u64)])
-        })
+        Self {
+            omega_provider: Box::new(|size: usize| {
+                F::get_root_of_unity(size.trailing_zeros()).ok_or_else(|| {
+                    LongfellowError::InvalidParameter(format!(
+                        "FFT size {} exceeds the field's two-adicity {}",
+                        size,
+                        F::TWO_ADICITY
+                    ))
+                })
+            }),
+        }
     }
 }
 
@@ -135,11 +144,24 @@ impl<F: Field> ConvolutionFactory<F> for FftConvolutionFactory<F> {
     
     fn make(&self, n: usize, m: usize, inverses: &[F]) -> Result<Self::Convolver> {
         let fft_size = (n + m - 1).next_power_of_two();
-        let omega = (self.omega_provider)(fft_size);
+        let omega = (self.omega_provider)(fft_size)?;
         FftConvolver::new(n, m, inverses, omega)
     }
 }
 
+/// Factory for `DirectConvolver`, for fields without large enough
+/// two-adic subgroups
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DirectConvolutionFactory;
+
+impl<F: Field> ConvolutionFactory<F> for DirectConvolutionFactory {
+    type Convolver = DirectConvolver<F>;
+    
+    fn make(&self, n: usize, m: usize, inverses: &[F]) -> Result<Self::Convolver> {
+        DirectConvolver::new(n, m, inverses)
+    }
+}
+
 /// Direct convolution for small sizes or testing
 pub struct DirectConvolver<F: Field> {
     inverse_sequence: Vec<F>,
@@ -215,4 +237,14 @@ mod tests {
         // Verify output is non-zero
         assert!(!output.iter().all(|x| *x == Fp128::zero()));
     }
+    
+    #[test]
+    fn test_fft_factory_rejects_sizes_beyond_two_adicity() {
+        use crate::field::Mersenne31;
+        
+        // Mersenne31 has two-adicity 1, so only sizes up to 2 have roots
+        let inverses = vec![Mersenne31::zero(); 8];
+        assert!(FftConvolutionFactory::<Mersenne31>::default().make(4, 8, &inverses).is_err());
+        assert!(DirectConvolutionFactory.make(4, 8, &inverses).is_ok());
+    }
 }
\ No newline at end of file
@@ -1,8 +1,14 @@
-use crate::permutations::bit_reverse_inplace;
+use crate::permutations::{bit_reverse, bit_reverse_inplace};
 use crate::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use rayon::prelude::*;
 
+/// Default size at and above which `FFT` switches to `fft_parallel`
+pub const DEFAULT_PARALLEL_THRESHOLD: usize = 1 << 14;
+
+/// Butterflies handed to one rayon task when a level is split within a block
+const PARALLEL_BUTTERFLY_CHUNK: usize = 1 << 10;
+
 pub struct FFT<F: Field> {
     size: usize,
     log_size: usize,
@@ -10,6 +16,8 @@ pub struct FFT<F: Field> {
     omega_inv: F,
     twiddle_factors: Vec<F>,
     inv_twiddle_factors: Vec<F>,
+    bit_reverse_swaps: Vec<(usize, usize)>,
+    parallel_threshold: usize,
 }
 
 impl<F: Field> FFT<F> {
@@ -27,6 +35,7 @@ impl<F: Field> FFT<F> {
 
         let twiddle_factors = compute_twiddle_factors(&omega, size);
         let inv_twiddle_factors = compute_twiddle_factors(&omega_inv, size);
+        let bit_reverse_swaps = compute_bit_reverse_swaps(size);
 
         Ok(Self {
             size,
@@ -35,9 +44,21 @@ impl<F: Field> FFT<F> {
             omega_inv,
             twiddle_factors,
             inv_twiddle_factors,
+            bit_reverse_swaps,
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
         })
     }
 
+    /// Set the size at and above which transforms run on all cores
+    pub fn with_parallel_threshold(mut self, threshold: usize) -> Self {
+        self.parallel_threshold = threshold;
+        self
+    }
+
+    pub fn parallel_threshold(&self) -> usize {
+        self.parallel_threshold
+    }
+
     pub fn forward(&self, coeffs: &mut [F]) -> Result<()> {
         self.transform(coeffs, &self.twiddle_factors)
     }
@@ -62,14 +83,25 @@ impl<F: Field> FFT<F> {
             )));
         }
 
+        if self.size >= self.parallel_threshold {
+            apply_swaps(data, &self.bit_reverse_swaps);
+            parallel_butterflies(data, twiddles, self.log_size);
+            return Ok(());
+        }
+
         // Use SIMD version if available and size is large enough
         #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
         if self.size >= 1024 {
-            crate::fft_simd::fft_vectorized(data, twiddles, self.log_size);
+            crate::fft_simd::fft_vectorized_with_swaps(
+                data,
+                twiddles,
+                self.log_size,
+                &self.bit_reverse_swaps,
+            );
             return Ok(());
         }
 
-        bit_reverse_inplace(data);
+        apply_swaps(data, &self.bit_reverse_swaps);
 
         let mut stride = 1;
         for level in 0..self.log_size {
@@ -111,6 +143,19 @@ impl<F: Field> FFT<F> {
     pub fn log_size(&self) -> usize {
         self.log_size
     }
+
+    pub fn omega(&self) -> F {
+        self.omega
+    }
+
+    pub fn twiddle_factors(&self) -> &[F] {
+        &self.twiddle_factors
+    }
+
+    /// Index pairs (i, j) with i < j swapped by the bit-reversal permutation
+    pub fn bit_reverse_swaps(&self) -> &[(usize, usize)] {
+        &self.bit_reverse_swaps
+    }
 }
 
 fn compute_twiddle_factors<F: Field>(omega: &F, size: usize) -> Vec<F> {
@@ -125,6 +170,73 @@ fn compute_twiddle_factors<F: Field>(omega: &F, size: usize) -> Vec<F> {
     twiddles
 }
 
+/// Multi-threaded radix-2 FFT over a power-of-two slice.
+///
+/// Early levels have many small blocks and are split block-wise; the last
+/// levels have only a few large blocks, so their butterflies are split
+/// within each block instead of running serially.
+pub fn fft_parallel<F: Field>(data: &mut [F], twiddles: &[F], log_n: usize) {
+    bit_reverse_inplace(data);
+    parallel_butterflies(data, twiddles, log_n);
+}
+
+fn parallel_butterflies<F: Field>(data: &mut [F], twiddles: &[F], log_n: usize) {
+    let n = data.len();
+    let threads = rayon::current_num_threads();
+
+    let mut stride = 1;
+    for level in 0..log_n {
+        let half_stride = stride;
+        stride <<= 1;
+        let shift = log_n - level - 1;
+
+        if n / stride >= threads {
+            data.par_chunks_mut(stride).for_each(|block| {
+                for j in 0..half_stride {
+                    let twiddle = twiddles[j << shift];
+                    let a = block[j];
+                    let b = block[j + half_stride] * twiddle;
+                    block[j] = a + b;
+                    block[j + half_stride] = a - b;
+                }
+            });
+        } else {
+            for block in data.chunks_mut(stride) {
+                let (lo, hi) = block.split_at_mut(half_stride);
+                lo.par_chunks_mut(PARALLEL_BUTTERFLY_CHUNK)
+                    .zip(hi.par_chunks_mut(PARALLEL_BUTTERFLY_CHUNK))
+                    .enumerate()
+                    .for_each(|(chunk, (lo, hi))| {
+                        let base = chunk * PARALLEL_BUTTERFLY_CHUNK;
+                        for (j, (x, y)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                            let twiddle = twiddles[(base + j) << shift];
+                            let a = *x;
+                            let b = *y * twiddle;
+                            *x = a + b;
+                            *y = a - b;
+                        }
+                    });
+            }
+        }
+    }
+}
+
+fn compute_bit_reverse_swaps(size: usize) -> Vec<(usize, usize)> {
+    let bits = size.trailing_zeros() as usize;
+    (0..size)
+        .filter_map(|i| {
+            let j = bit_reverse(i, bits);
+            (i < j).then_some((i, j))
+        })
+        .collect()
+}
+
+pub(crate) fn apply_swaps<T>(data: &mut [T], swaps: &[(usize, usize)]) {
+    for &(i, j) in swaps {
+        data.swap(i, j);
+    }
+}
+
 pub struct RFFT<F: Field> {
     fft: FFT<F>,
 }
@@ -151,7 +263,7 @@ impl<F: Field> RFFT<F> {
 
         self.fft.forward(&mut complex)?;
 
-        let omega_n = self.fft.omega.pow(&[self.fft.size as u64 / 2]);
+        let omega_n = self.fft.omega.pow_vartime(&[self.fft.size as u64 / 2]);
         let mut omega_power = F::one();
         
         for k in 0..self.fft.size / 2 {
@@ -178,7 +290,7 @@ impl<F: Field> RFFT<F> {
             ));
         }
 
-        let omega_n_inv = self.fft.omega_inv.pow(&[self.fft.size as u64 / 2]);
+        let omega_n_inv = self.fft.omega_inv.pow_vartime(&[self.fft.size as u64 / 2]);
         let mut omega_power = F::one();
         
         let mut complex = vec![F::zero(); self.fft.size];
@@ -207,6 +319,171 @@ impl<F: Field> RFFT<F> {
     }
 }
 
+/// FFT over arbitrary sizes using mixed-radix Cooley-Tukey.
+///
+/// The size is factored into radix-2, radix-3 and radix-5 stages; any other
+/// prime factor p falls back to a direct O(p^2) butterfly, so sizes with
+/// large prime factors still work but lose the n log n bound. `omega` must be
+/// a primitive root of unity of order `size`.
+pub struct MixedRadixFFT<F: Field> {
+    size: usize,
+    factors: Vec<usize>,
+    twiddle_factors: Vec<F>,
+    inv_twiddle_factors: Vec<F>,
+}
+
+impl<F: Field> MixedRadixFFT<F> {
+    pub fn new(size: usize, omega: F) -> Result<Self> {
+        if size == 0 {
+            return Err(LongfellowError::InvalidParameter(
+                "FFT size must be positive".to_string(),
+            ));
+        }
+
+        let omega_inv = omega.invert().ok_or_else(|| {
+            LongfellowError::InvalidParameter("Root of unity is not invertible".to_string())
+        })?;
+
+        Ok(Self {
+            size,
+            factors: radix_factors(size),
+            twiddle_factors: compute_twiddle_factors(&omega, size),
+            inv_twiddle_factors: compute_twiddle_factors(&omega_inv, size),
+        })
+    }
+
+    pub fn forward(&self, coeffs: &mut [F]) -> Result<()> {
+        self.transform(coeffs, &self.twiddle_factors)
+    }
+
+    pub fn inverse(&self, values: &mut [F]) -> Result<()> {
+        self.transform(values, &self.inv_twiddle_factors)?;
+
+        let inv_size = F::from_u64(self.size as u64)
+            .invert()
+            .ok_or_else(|| LongfellowError::ArithmeticError("Cannot invert size".to_string()))?;
+
+        values.par_iter_mut().for_each(|v| *v *= inv_size);
+        Ok(())
+    }
+
+    fn transform(&self, data: &mut [F], twiddles: &[F]) -> Result<()> {
+        if data.len() != self.size {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Input size {} does not match FFT size {}",
+                data.len(),
+                self.size
+            )));
+        }
+
+        let input = data.to_vec();
+        mixed_radix_recursive(&input, 1, data, &self.factors, twiddles, 1);
+        Ok(())
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Radices used by each stage, outermost first
+    pub fn factors(&self) -> &[usize] {
+        &self.factors
+    }
+}
+
+/// Whether `n` factors entirely into radix-2/3/5 stages
+pub fn is_smooth_fft_size(n: usize) -> bool {
+    n > 0 && radix_factors(n).iter().all(|&r| r <= 5)
+}
+
+fn radix_factors(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    for radix in [5, 3, 2] {
+        while n.is_multiple_of(radix) {
+            factors.push(radix);
+            n /= radix;
+        }
+    }
+
+    let mut p = 7;
+    while p * p <= n {
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+        p += 2;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+
+    factors
+}
+
+/// Decimation-in-time step: `out` receives the DFT of
+/// `input[0], input[stride], ..., input[(n-1)*stride]` where n = out.len().
+/// `tw_stride` maps this level's root powers into the full twiddle table.
+fn mixed_radix_recursive<F: Field>(
+    input: &[F],
+    stride: usize,
+    out: &mut [F],
+    factors: &[usize],
+    twiddles: &[F],
+    tw_stride: usize,
+) {
+    let n = out.len();
+    if n == 1 {
+        out[0] = input[0];
+        return;
+    }
+
+    let radix = factors[0];
+    let m = n / radix;
+
+    for j in 0..radix {
+        mixed_radix_recursive(
+            &input[j * stride..],
+            stride * radix,
+            &mut out[j * m..(j + 1) * m],
+            &factors[1..],
+            twiddles,
+            tw_stride * radix,
+        );
+    }
+
+    let total = twiddles.len();
+    match radix {
+        2 => {
+            for k in 0..m {
+                let a = out[k];
+                let b = out[k + m] * twiddles[k * tw_stride];
+                out[k] = a + b;
+                out[k + m] = a - b;
+            }
+        }
+        _ => {
+            // Powers of the primitive radix-th root: w_r^i = omega^(i * m * tw_stride)
+            let roots: Vec<F> = (0..radix)
+                .map(|i| twiddles[(i * m * tw_stride) % total])
+                .collect();
+            let mut scratch = vec![F::zero(); radix];
+
+            for k in 0..m {
+                for j in 0..radix {
+                    scratch[j] = out[j * m + k] * twiddles[(j * k * tw_stride) % total];
+                }
+                for q in 0..radix {
+                    let mut acc = scratch[0];
+                    for j in 1..radix {
+                        acc += scratch[j] * roots[(j * q) % radix];
+                    }
+                    out[q * m + k] = acc;
+                }
+            }
+        }
+    }
+}
+
 pub fn polynomial_multiplication<F: Field>(a: &[F], b: &[F], omega: F) -> Result<Vec<F>> {
     let output_size = a.len() + b.len() - 1;
     let fft_size = output_size.next_power_of_two();
@@ -427,4 +704,101 @@ mod tests {
         
         assert_eq!(data, original);
     }
-}
\ No newline at end of file
+
+    fn naive_dft(data: &[TestField], omega: TestField) -> Vec<TestField> {
+        let n = data.len();
+        (0..n)
+            .map(|k| {
+                let mut acc = TestField::zero();
+                for (j, &x) in data.iter().enumerate() {
+                    acc += x * omega.pow(&[(j * k % n) as u64]);
+                }
+                acc
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mixed_radix_fft_matches_dft() {
+        // 5 generates the multiplicative group of order 96 = 2^5 * 3
+        let generator = TestField(5);
+        for size in [3, 6, 12, 24, 32, 48, 96] {
+            let omega = generator.pow(&[96 / size as u64]);
+            let fft = MixedRadixFFT::new(size, omega).unwrap();
+
+            let data: Vec<TestField> = (0..size).map(|i| TestField::from_u64(i as u64 * 7 + 1)).collect();
+            let mut transformed = data.clone();
+            fft.forward(&mut transformed).unwrap();
+            assert_eq!(transformed, naive_dft(&data, omega));
+
+            fft.inverse(&mut transformed).unwrap();
+            assert_eq!(transformed, data);
+        }
+    }
+
+    #[test]
+    fn test_mixed_radix_fft_radix5_fp128() {
+        use crate::Fp128;
+
+        // p - 1 = 2^108 * (2^20 - 1) and 59 is a primitive root, so
+        // 59^((2^20 - 1) / 15) squared 108 times has order exactly 15
+        let mut omega_15 = Fp128::from_u64(59).pow(&[((1u64 << 20) - 1) / 15]);
+        for _ in 0..108 {
+            omega_15 = omega_15.square();
+        }
+
+        let fft = MixedRadixFFT::new(15, omega_15).unwrap();
+        assert_eq!(fft.factors(), &[5, 3]);
+
+        let data: Vec<Fp128> = (0..15).map(|i| Fp128::from_u64(i * i + 2)).collect();
+        let mut values = data.clone();
+        fft.forward(&mut values).unwrap();
+
+        // Spot-check against direct evaluation at omega^k
+        for k in [0u64, 1, 7, 14] {
+            let point = omega_15.pow(&[k]);
+            let mut expected = Fp128::zero();
+            let mut power = Fp128::one();
+            for &c in &data {
+                expected += c * power;
+                power *= point;
+            }
+            assert_eq!(values[k as usize], expected);
+        }
+
+        fft.inverse(&mut values).unwrap();
+        assert_eq!(values, data);
+    }
+
+    #[test]
+    fn test_smooth_fft_sizes() {
+        assert!(is_smooth_fft_size(1));
+        assert!(is_smooth_fft_size(600));
+        assert!(!is_smooth_fft_size(0));
+        assert!(!is_smooth_fft_size(639));
+    }
+
+    #[test]
+    fn test_parallel_fft_matches_serial() {
+        use crate::Fp128;
+
+        let size: usize = 1 << 12;
+        let omega = Fp128::get_root_of_unity(size.trailing_zeros()).unwrap();
+        let serial = FFT::new(size, omega).unwrap().with_parallel_threshold(usize::MAX);
+        let parallel = FFT::new(size, omega).unwrap().with_parallel_threshold(1);
+
+        let data: Vec<Fp128> = (0..size as u64).map(|i| Fp128::from_u64(i * i + 5)).collect();
+        let mut a = data.clone();
+        let mut b = data.clone();
+        serial.forward(&mut a).unwrap();
+        parallel.forward(&mut b).unwrap();
+        assert_eq!(a, b);
+
+        let mut c = data.clone();
+        fft_parallel(&mut c, serial.twiddle_factors(), serial.log_size());
+        assert_eq!(a, c);
+
+        parallel.inverse(&mut b).unwrap();
+        assert_eq!(b, data);
+    }
+}
@@ -0,0 +1,151 @@
+//! Cached FFT plans
+//!
+//! Building an `FFT` computes the forward and inverse twiddle tables and the
+//! bit-reversal swap table, which costs about as much as a transform. Proving
+//! repeatedly encodes rows of the same size, so plans are built once per
+//! (field, size, root of unity) and shared through `Arc`.
+
+use crate::fft::FFT;
+use crate::traits::Field;
+use longfellow_core::Result;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Planner that caches FFT plans for a single field
+pub struct FftPlanner<F: Field> {
+    plans: RwLock<HashMap<usize, Vec<Arc<FFT<F>>>>>,
+}
+
+impl<F: Field> FftPlanner<F> {
+    /// Create an empty planner
+    pub fn new() -> Self {
+        Self {
+            plans: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the plan for `size` and `omega`, building it on first use
+    pub fn plan(&self, size: usize, omega: F) -> Result<Arc<FFT<F>>> {
+        if let Some(plan) = self.lookup(size, omega) {
+            return Ok(plan);
+        }
+
+        let plan = Arc::new(FFT::new(size, omega)?);
+        let mut plans = self.plans.write().unwrap();
+        let entry = plans.entry(size).or_default();
+
+        // Another thread may have built the same plan meanwhile
+        if let Some(existing) = entry.iter().find(|p| p.omega() == omega) {
+            return Ok(existing.clone());
+        }
+        entry.push(plan.clone());
+        Ok(plan)
+    }
+
+    fn lookup(&self, size: usize, omega: F) -> Option<Arc<FFT<F>>> {
+        let plans = self.plans.read().unwrap();
+        plans
+            .get(&size)?
+            .iter()
+            .find(|p| p.omega() == omega)
+            .cloned()
+    }
+
+    /// Number of cached plans
+    pub fn len(&self) -> usize {
+        self.plans.read().unwrap().values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached plans
+    pub fn clear(&self) {
+        self.plans.write().unwrap().clear();
+    }
+}
+
+impl<F: Field> Default for FftPlanner<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type SharedPlanners = RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+fn shared_planners() -> &'static SharedPlanners {
+    static PLANNERS: OnceLock<SharedPlanners> = OnceLock::new();
+    PLANNERS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Process-wide planner for field `F`
+pub fn shared_planner<F: Field>() -> Arc<FftPlanner<F>> {
+    let key = TypeId::of::<F>();
+
+    if let Some(planner) = shared_planners().read().unwrap().get(&key) {
+        return planner
+            .clone()
+            .downcast::<FftPlanner<F>>()
+            .expect("planner registered under its own TypeId");
+    }
+
+    let mut planners = shared_planners().write().unwrap();
+    planners
+        .entry(key)
+        .or_insert_with(|| Arc::new(FftPlanner::<F>::new()))
+        .clone()
+        .downcast::<FftPlanner<F>>()
+        .expect("planner registered under its own TypeId")
+}
+
+/// Get a plan from the process-wide cache
+pub fn shared_plan<F: Field>(size: usize, omega: F) -> Result<Arc<FFT<F>>> {
+    shared_planner::<F>().plan(size, omega)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fp128;
+
+    #[test]
+    fn test_planner_reuses_plans() {
+        let planner = FftPlanner::<Fp128>::new();
+        let omega = Fp128::get_root_of_unity(4).unwrap();
+
+        let a = planner.plan(16, omega).unwrap();
+        let b = planner.plan(16, omega).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(planner.len(), 1);
+
+        let omega_8 = Fp128::get_root_of_unity(3).unwrap();
+        let c = planner.plan(8, omega_8).unwrap();
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(planner.len(), 2);
+
+        planner.clear();
+        assert!(planner.is_empty());
+    }
+
+    #[test]
+    fn test_planned_fft_roundtrip() {
+        let omega = Fp128::get_root_of_unity(5).unwrap();
+        let plan = shared_plan(32, omega).unwrap();
+        assert!(Arc::ptr_eq(&plan, &shared_plan(32, omega).unwrap()));
+
+        let data: Vec<Fp128> = (0..32).map(|i| Fp128::from_u64(i * 3 + 1)).collect();
+        let mut work = data.clone();
+        plan.forward(&mut work).unwrap();
+        plan.inverse(&mut work).unwrap();
+        assert_eq!(work, data);
+    }
+
+    #[test]
+    fn test_rejects_non_power_of_two() {
+        let planner = FftPlanner::<Fp128>::new();
+        assert!(planner.plan(12, Fp128::one()).is_err());
+        assert!(planner.is_empty());
+    }
+}
@@ -29,11 +29,37 @@ pub unsafe fn butterfly_simd_avx2<F: Field>(
 
 /// Vectorized FFT implementation using SIMD instructions
 pub fn fft_vectorized<F: Field>(data: &mut [F], twiddles: &[F], log_n: usize) {
-    let n = data.len();
-    
     // Bit reversal permutation
     bit_reverse_simd(data);
     
+    butterfly_passes(data, twiddles, log_n);
+}
+
+/// Vectorized FFT using a precomputed bit-reversal swap table, as cached by
+/// `FFT` plans from the `FftPlanner`
+pub fn fft_vectorized_with_swaps<F: Field>(
+    data: &mut [F],
+    twiddles: &[F],
+    log_n: usize,
+    swaps: &[(usize, usize)],
+) {
+    crate::fft::apply_swaps(data, swaps);
+    butterfly_passes(data, twiddles, log_n);
+}
+
+/// Vectorized FFT driven entirely by a cached plan
+pub fn fft_vectorized_planned<F: Field>(data: &mut [F], plan: &crate::fft::FFT<F>) {
+    fft_vectorized_with_swaps(
+        data,
+        plan.twiddle_factors(),
+        plan.log_size(),
+        plan.bit_reverse_swaps(),
+    );
+}
+
+fn butterfly_passes<F: Field>(data: &mut [F], twiddles: &[F], log_n: usize) {
+    let n = data.len();
+    
     // FFT passes
     let mut stride = 1;
     for level in 0..log_n {
@@ -65,14 +65,15 @@ pub fn sub_with_borrow_asm(a: u64, b: u64, borrow_in: u8) -> (u64, u8) {
         let mut diff: u64;
         let mut borrow_out: u8;
         unsafe {
+            // Load the incoming borrow into CF so sbb subtracts b + borrow
             std::arch::asm!(
-                "sub {}, {}",
-                "sbb {}, 0",
-                "setc {}",
-                inlateout(reg) a => diff,
-                in(reg) b,
-                inlateout(reg) borrow_in as u64 => _,
-                lateout(reg_byte) borrow_out,
+                "bt {borrow}, 0",
+                "sbb {diff}, {b}",
+                "setc {out}",
+                diff = inlateout(reg) a => diff,
+                b = in(reg) b,
+                borrow = in(reg) borrow_in as u64,
+                out = lateout(reg_byte) borrow_out,
                 options(pure, nomem, nostack)
             );
         }
@@ -93,12 +94,17 @@ pub fn mac_with_carry_asm(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
         let mut lo: u64;
         let mut hi: u64;
         unsafe {
+            // rdx:rax = b * c, then add a and carry into the low word,
+            // rippling each carry into the high word. rdx is a plain `out`
+            // so no input can live in it across the mul.
             std::arch::asm!(
                 "mul {c}",
                 "add rax, {a}",
-                "adc rdx, {carry}",
+                "adc rdx, 0",
+                "add rax, {carry}",
+                "adc rdx, 0",
                 inlateout("rax") b => lo,
-                lateout("rdx") hi,
+                out("rdx") hi,
                 c = in(reg) c,
                 a = in(reg) a,
                 carry = in(reg) carry,
@@ -272,6 +278,37 @@ mod tests {
         assert_eq!((lo1, hi1), (lo2, hi2));
     }
 
+    #[test]
+    fn test_mac_with_carry_asm() {
+        let test_cases = [
+            (u64::MAX, u64::MAX, u64::MAX, u64::MAX),
+            (u64::MAX, 1, 1, u64::MAX),
+            (1234567890, 0xFEDCBA9876543210, 0x123456789ABCDEF0, 42),
+        ];
+
+        for (a, b, c, carry) in test_cases {
+            let wide = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+            assert_eq!(mac_with_carry_asm(a, b, c, carry), (wide as u64, (wide >> 64) as u64));
+        }
+    }
+
+    #[test]
+    fn test_sub_with_borrow_asm() {
+        let test_cases = [
+            (0u64, 0, 1u8),
+            (0, u64::MAX, 1),
+            (5, 5, 0),
+            (5, 4, 1),
+            (9876543210, 1234567890, 0),
+        ];
+
+        for (a, b, borrow_in) in test_cases {
+            let (diff1, b1) = a.overflowing_sub(b);
+            let (diff2, b2) = diff1.overflowing_sub(borrow_in as u64);
+            assert_eq!(sub_with_borrow_asm(a, b, borrow_in), (diff2, (b1 | b2) as u8));
+        }
+    }
+
     #[test]
     #[ignore = "Assembly version has bugs - using pure Rust implementation"]
     fn test_add_with_carry_asm() {
@@ -0,0 +1,128 @@
+use crate::field::fp_generic::{FieldReduction, FpGeneric};
+use crate::nat::{self, Limb, Nat};
+
+/// Scalar field of BLS12-381:
+/// r = 0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001
+pub type Bls12_381Fr = FpGeneric<4, Bls12_381FrReduce>;
+
+#[derive(Clone, Copy)]
+pub struct Bls12_381FrReduce;
+
+impl FieldReduction<4> for Bls12_381FrReduce {
+    const MODULUS: Nat<4> = Nat {
+        limbs: [
+            0xffffffff00000001,
+            0x53bda402fffe5bfe,
+            0x3339d80809a1d805,
+            0x73eda753299d7d48,
+        ]
+    };
+    
+    const MODULUS_STR: &'static str =
+        "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+    const MODULUS_BITS: u32 = 255;
+    
+    // 2^256 mod r
+    const R: Nat<4> = Nat {
+        limbs: [
+            0x00000001fffffffe,
+            0x5884b7fa00034802,
+            0x998c4fefecbc4ff5,
+            0x1824b159acc5056f,
+        ]
+    };
+    
+    // 2^512 mod r
+    const R2: Nat<4> = Nat {
+        limbs: [
+            0xc999e990f3f29c6d,
+            0x2b6cedcb87925c23,
+            0x05d314967254398f,
+            0x0748d9d99f59ff11,
+        ]
+    };
+    
+    // -r^(-1) mod 2^64
+    const INV: Limb = 0xfffffffeffffffff;
+    
+    // 7^t where r - 1 = 2^32 * t
+    const TWO_ADICITY: u32 = 32;
+    const ROOT_OF_UNITY: Nat<4> = Nat {
+        limbs: [
+            0x3829971f439f0d2b,
+            0xb63683508c2280b9,
+            0xd09b681922c813b4,
+            0x16a2a19edfe81f20,
+        ]
+    };
+    
+    fn reduction_step(a: &mut [Limb], mprime: Limb, modulus: &Nat<4>) {
+        let k = a[0].wrapping_mul(mprime);
+        let mut carry = 0u64;
+        
+        for (limb, &m) in a.iter_mut().zip(&modulus.limbs) {
+            let (lo, hi) = nat::mul_wide(m, k);
+            let (sum, c) = nat::add_with_carry(*limb, lo, carry);
+            *limb = sum;
+            carry = hi + c;
+        }
+        
+        a[4] = a[4].wrapping_add(carry);
+        
+        // Shift right by one limb
+        for i in 0..4 {
+            a[i] = a[i + 1];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Field;
+
+    #[test]
+    fn test_bls12_381_fr_basic_operations() {
+        let a = Bls12_381Fr::from_u64(5);
+        let b = Bls12_381Fr::from_u64(7);
+        
+        assert_eq!(a + b, Bls12_381Fr::from_u64(12));
+        assert_eq!(b - a, Bls12_381Fr::from_u64(2));
+        assert_eq!(a * b, Bls12_381Fr::from_u64(35));
+        assert_eq!(a + (-a), Bls12_381Fr::zero());
+        
+        // (-2) * (-3) = 6 exercises reduction across all four limbs
+        let minus_two = -Bls12_381Fr::from_u64(2);
+        let minus_three = -Bls12_381Fr::from_u64(3);
+        assert_eq!(minus_two * minus_three, Bls12_381Fr::from_u64(6));
+    }
+
+    #[test]
+    fn test_bls12_381_fr_wide_multiplication() {
+        // x = 2^250 + 12345
+        let mut x_bytes = [0u8; 32];
+        x_bytes[0] = 0x39;
+        x_bytes[1] = 0x30;
+        x_bytes[31] = 0x04;
+        let x = <Bls12_381Fr as Field>::from_bytes_le(&x_bytes).unwrap();
+        
+        let expected = <Bls12_381Fr as Field>::from_bytes_le(&[
+            0x87, 0xa8, 0xf4, 0x68, 0xf2, 0x9c, 0x0c, 0xc9,
+            0xa6, 0xee, 0xbd, 0x4d, 0xe6, 0xc9, 0x71, 0x96,
+            0xfa, 0x0a, 0x6b, 0x86, 0x63, 0xde, 0x7e, 0x05,
+            0xc2, 0xdf, 0x50, 0x01, 0x98, 0x3a, 0xf5, 0x0e,
+        ]).unwrap();
+        
+        assert_eq!(x * x, expected);
+    }
+
+    #[test]
+    fn test_bls12_381_fr_inversion_and_range() {
+        let a = Bls12_381Fr::from_u64(123456789);
+        assert_eq!(a * a.invert().unwrap(), Bls12_381Fr::one());
+        assert!(Bls12_381Fr::zero().invert().is_none());
+        
+        let modulus_bytes = Bls12_381FrReduce::MODULUS.to_bytes_le();
+        assert!(<Bls12_381Fr as Field>::from_bytes_le(&modulus_bytes).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::field::fp_generic::{FieldReduction, FpGeneric};
+use crate::nat::{self, Limb, Nat};
+
+/// Scalar field of BN254 (alt_bn128):
+/// r = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001
+pub type Bn254Fr = FpGeneric<4, Bn254FrReduce>;
+
+#[derive(Clone, Copy)]
+pub struct Bn254FrReduce;
+
+impl FieldReduction<4> for Bn254FrReduce {
+    const MODULUS: Nat<4> = Nat {
+        limbs: [
+            0x43e1f593f0000001,
+            0x2833e84879b97091,
+            0xb85045b68181585d,
+            0x30644e72e131a029,
+        ]
+    };
+    
+    const MODULUS_STR: &'static str =
+        "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    const MODULUS_BITS: u32 = 254;
+    
+    // 2^256 mod r
+    const R: Nat<4> = Nat {
+        limbs: [
+            0xac96341c4ffffffb,
+            0x36fc76959f60cd29,
+            0x666ea36f7879462e,
+            0x0e0a77c19a07df2f,
+        ]
+    };
+    
+    // 2^512 mod r
+    const R2: Nat<4> = Nat {
+        limbs: [
+            0x1bb8e645ae216da7,
+            0x53fe3ab1e35c59e3,
+            0x8c49833d53bb8085,
+            0x0216d0b17f4e44a5,
+        ]
+    };
+    
+    // -r^(-1) mod 2^64
+    const INV: Limb = 0xc2e1f593efffffff;
+    
+    // 5^t where r - 1 = 2^28 * t
+    const TWO_ADICITY: u32 = 28;
+    const ROOT_OF_UNITY: Nat<4> = Nat {
+        limbs: [
+            0x9bd61b6e725b19f0,
+            0x402d111e41112ed4,
+            0x00e0a7eb8ef62abc,
+            0x2a3c09f0a58a7e85,
+        ]
+    };
+    
+    fn reduction_step(a: &mut [Limb], mprime: Limb, modulus: &Nat<4>) {
+        let k = a[0].wrapping_mul(mprime);
+        let mut carry = 0u64;
+        
+        for (limb, &m) in a.iter_mut().zip(&modulus.limbs) {
+            let (lo, hi) = nat::mul_wide(m, k);
+            let (sum, c) = nat::add_with_carry(*limb, lo, carry);
+            *limb = sum;
+            carry = hi + c;
+        }
+        
+        a[4] = a[4].wrapping_add(carry);
+        
+        // Shift right by one limb
+        for i in 0..4 {
+            a[i] = a[i + 1];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Field;
+
+    #[test]
+    fn test_bn254_fr_basic_operations() {
+        let a = Bn254Fr::from_u64(5);
+        let b = Bn254Fr::from_u64(7);
+        
+        assert_eq!(a + b, Bn254Fr::from_u64(12));
+        assert_eq!(b - a, Bn254Fr::from_u64(2));
+        assert_eq!(a * b, Bn254Fr::from_u64(35));
+        assert_eq!(a + (-a), Bn254Fr::zero());
+        
+        // (-2) * (-3) = 6 exercises reduction across all four limbs
+        let minus_two = -Bn254Fr::from_u64(2);
+        let minus_three = -Bn254Fr::from_u64(3);
+        assert_eq!(minus_two * minus_three, Bn254Fr::from_u64(6));
+    }
+
+    #[test]
+    fn test_bn254_fr_wide_multiplication() {
+        // x = 2^250 + 12345
+        let mut x_bytes = [0u8; 32];
+        x_bytes[0] = 0x39;
+        x_bytes[1] = 0x30;
+        x_bytes[31] = 0x04;
+        let x = <Bn254Fr as Field>::from_bytes_le(&x_bytes).unwrap();
+        
+        let expected = <Bn254Fr as Field>::from_bytes_le(&[
+            0xd0, 0x46, 0x57, 0x72, 0x46, 0xa0, 0x1a, 0xfe,
+            0xc4, 0x75, 0xc1, 0xcf, 0x1e, 0x5c, 0xe8, 0xd5,
+            0xe6, 0x9f, 0x7d, 0xcc, 0x7a, 0x85, 0xc8, 0xa9,
+            0x7f, 0x80, 0xce, 0x42, 0xbd, 0xb5, 0xc8, 0x2f,
+        ]).unwrap();
+        
+        assert_eq!(x * x, expected);
+    }
+
+    #[test]
+    fn test_bn254_fr_inversion_and_range() {
+        let a = Bn254Fr::from_u64(123456789);
+        assert_eq!(a * a.invert().unwrap(), Bn254Fr::one());
+        assert!(Bn254Fr::zero().invert().is_none());
+        
+        let modulus_bytes = Bn254FrReduce::MODULUS.to_bytes_le();
+        assert!(<Bn254Fr as Field>::from_bytes_le(&modulus_bytes).is_err());
+    }
+
+    #[test]
+    fn test_bn254_fr_root_of_unity() {
+        // Order exactly 2^28
+        let mut root = Bn254Fr::root_of_unity();
+        for _ in 0..27 {
+            root = root.square();
+        }
+        assert_eq!(root, -Bn254Fr::one());
+        assert_eq!(Bn254Fr::get_root_of_unity(1), Some(-Bn254Fr::one()));
+        assert!(Bn254Fr::get_root_of_unity(29).is_none());
+    }
+}
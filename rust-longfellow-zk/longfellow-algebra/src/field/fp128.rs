@@ -1,6 +1,5 @@
 use crate::field::fp_generic::{FieldReduction, FpGeneric};
 use crate::nat::{self, Limb, Nat};
-use crate::traits::Field;
 
 pub type Fp128 = FpGeneric<2, Fp128Reduce>;
 
@@ -16,7 +15,7 @@ impl FieldReduction<2> for Fp128Reduce {
         ]
     };
     
-    const MODULUS_STR: &'static str = "0xFFFFF00000000000000000000000001";
+    const MODULUS_STR: &'static str = "0xFFFFF000000000000000000000000001";
     const MODULUS_BITS: u32 = 128;
     
     const R: Nat<2> = Nat {
@@ -34,6 +33,15 @@ impl FieldReduction<2> for Fp128Reduce {
     };
     
     const INV: Limb = 0xFFFFFFFFFFFFFFFF;
+
+    // p - 1 = 2^108 * (2^20 - 1); 59^(2^20 - 1) for the generator 59
+    const TWO_ADICITY: u32 = 108;
+    const ROOT_OF_UNITY: Nat<2> = Nat {
+        limbs: [
+            0x9b0e401163b0e5dc,
+            0xb903f4cfb9c26b3c,
+        ]
+    };
     
     fn reduction_step(a: &mut [Limb], mprime: Limb, modulus: &Nat<2>) {
         // Montgomery reduction step for p = 2^128 - 2^108 + 1
@@ -57,49 +65,6 @@ impl FieldReduction<2> for Fp128Reduce {
     }
 }
 
-impl Fp128 {
-    /// Get a primitive root of unity for the given order
-    /// Returns None if no such root exists
-    pub fn get_root_of_unity(n: usize) -> Option<Self> {
-        // For Fp128 with p = 2^128 - 2^108 + 1, we have p - 1 = 2^108 * (2^20 - 1)
-        // The C++ implementation uses omega of order 2^32 from the comments in fp_p128.h
-        // This is: 164956748514267535023998284330560247862
-        
-        // Check if n is a power of 2
-        if !n.is_power_of_two() {
-            return None;
-        }
-        
-        // For powers of 2 up to 2^108, we can compute roots
-        // Start with the root of unity of order 2^32 from C++
-        const OMEGA_32_ORDER: u32 = 32;
-        
-        if n == 1 {
-            return Some(Self::one());
-        }
-        
-        // Use the mathematically correct omega_32 value: 99753660205281253039813454253403999101
-        // This is a primitive 2^32 root of unity computed from primitive root 59
-        let omega_32_bytes = [
-            0x7d, 0x0b, 0x89, 0x6c, 0x63, 0xd7, 0x3c, 0xbd,
-            0x95, 0x20, 0xb3, 0x04, 0x2b, 0xdb, 0x0b, 0x4b,
-        ];
-        let omega_32 = Self::from_bytes_le(&omega_32_bytes).ok()?;
-        
-        // For the requested size n, we need to compute omega_32^(2^(32-log2(n)))
-        let log_n = n.trailing_zeros();
-        if log_n > OMEGA_32_ORDER {
-            return None;
-        }
-        
-        // Compute omega_n = omega_32^(2^(32-log_n))
-        let exponent = 1u64 << (OMEGA_32_ORDER - log_n);
-        let omega_n = omega_32.pow(&[exponent]);
-        
-        Some(omega_n)
-    }
-}
-
 // Field trait is already implemented by FpGeneric<N, R> in fp_generic.rs
 
 #[cfg(test)]
@@ -137,21 +102,157 @@ mod tests {
         let zero = Fp128::zero();
         assert!(zero.invert().is_none());
     }
+
+    #[test]
+    fn test_fp128_batch_invert() {
+        let mut elements: Vec<Fp128> = (0..20).map(|i| Fp128::from_u64(i * i + 3)).collect();
+        elements[5] = Fp128::zero();
+        let original = elements.clone();
+
+        Fp128::batch_invert(&mut elements);
+
+        for (inv, orig) in elements.iter().zip(original.iter()) {
+            match orig.invert() {
+                Some(expected) => assert_eq!(*inv, expected),
+                None => assert_eq!(*inv, Fp128::zero()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_fp128_batch_invert_parallel() {
+        // Large enough to take the chunked parallel path
+        let mut elements: Vec<Fp128> = (1..=5000).map(Fp128::from_u64).collect();
+        let original = elements.clone();
+
+        Fp128::batch_invert(&mut elements);
+
+        for (inv, orig) in elements.iter().zip(original.iter()) {
+            assert_eq!(*inv * orig, Fp128::one());
+        }
+    }
     
     #[test]
-    #[ignore = "Root of unity computation needs investigation"]
-    fn test_root_of_unity() {
-        // Test that roots of unity have the correct order
-        if let Some(omega_2) = Fp128::get_root_of_unity(2) {
-            assert_eq!(omega_2.square(), Fp128::one());
-            assert_ne!(omega_2, Fp128::one());
+    fn test_fp128_montgomery_batch() {
+        let mut values: Vec<u64> = (0..20_000u64).map(|i| i.wrapping_mul(0x9E3779B97F4A7C15)).collect();
+        values[3] = u64::MAX;
+        let small = &values[..100];
+
+        // Serial and parallel paths agree with per-element conversion
+        for batch in [small, &values[..]] {
+            let elements = Fp128::from_u64_batch(batch);
+            for (e, &v) in elements.iter().zip(batch.iter()) {
+                assert_eq!(*e, Fp128::from_u64(v));
+            }
+
+            let nats = Fp128::from_montgomery_batch(&elements);
+            for (n, &v) in nats.iter().zip(batch.iter()) {
+                assert_eq!(*n, Nat::from_u64(v));
+            }
+            assert_eq!(Fp128::to_montgomery_batch(&nats), elements);
         }
-        
-        if let Some(omega_4) = Fp128::get_root_of_unity(4) {
-            let omega_4_squared = omega_4.square();
-            assert_ne!(omega_4_squared, Fp128::one());
-            assert_eq!(omega_4_squared.square(), Fp128::one());
+
+        // Values near the modulus survive the round trip
+        let minus_one = -Fp128::one();
+        let nats = Fp128::from_montgomery_batch(&[minus_one]);
+        assert_eq!(nats[0], minus_one.from_montgomery());
+        assert_eq!(Fp128::to_montgomery_batch(&nats), vec![minus_one]);
+    }
+
+    #[test]
+    fn test_fp128_pow_multi_limb() {
+        // p - 1 and p - 2 as little-endian limbs
+        let p_minus_1 = [0u64, 0xFFFFF00000000000];
+        let p_minus_2 = [u64::MAX, 0xFFFFEFFFFFFFFFFF];
+        let a = Fp128::from_u64(987654321);
+
+        assert_eq!(a.pow(&p_minus_1), Fp128::one());
+        assert_eq!(a.pow_vartime(&p_minus_1), Fp128::one());
+        assert_eq!(a.pow(&p_minus_2), a.invert().unwrap());
+        assert_eq!(a.pow_vartime(&p_minus_2), a.invert().unwrap());
+
+        for e in [0u64, 1, 2, 5, 64, u64::MAX] {
+            assert_eq!(a.pow(&[e]), a.pow_vartime(&[e]));
         }
+        assert_eq!(a.pow(&[]), Fp128::one());
+        assert_eq!(a.pow_vartime(&[0, 0]), Fp128::one());
+    }
+
+    #[test]
+    fn test_fp128_canonical_bytes() {
+        let a = Fp128::from_u64(0x1234_5678);
+        let bytes = a.to_canonical_bytes();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(Fp128::from_canonical_bytes(&bytes).unwrap(), a);
+
+        // Short encodings are not canonical even if they decode
+        assert!(Fp128::from_canonical_bytes(&bytes[..4]).is_err());
+
+        // The modulus itself is the smallest non-reduced encoding
+        let mut modulus = 1u64.to_le_bytes().to_vec();
+        modulus.extend_from_slice(&0xFFFFF00000000000u64.to_le_bytes());
+        assert!(Fp128::from_canonical_bytes(&modulus).is_err());
+
+        let minus_one = -Fp128::one();
+        assert_eq!(
+            Fp128::from_canonical_bytes(&minus_one.to_canonical_bytes()).unwrap(),
+            minus_one
+        );
+    }
+
+    #[test]
+    fn test_fp128_constant_time_ops() {
+        use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+        let a = Fp128::from_u64(11);
+        let b = Fp128::from_u64(13);
+        assert!(bool::from(a.ct_eq(&a)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_eq!(Fp128::conditional_select(&a, &b, Choice::from(0)), a);
+        assert_eq!(Fp128::conditional_select(&a, &b, Choice::from(1)), b);
+
+        assert!(bool::from(Fp128::zero().is_zero_ct()));
+        assert!(!bool::from(a.is_zero_ct()));
+        assert_eq!(Fp128::from_choice(Choice::from(1)), Fp128::one());
+
+        // 11 = 0b1011
+        let bits = a.to_bits_le_ct(6);
+        let expected: Vec<Fp128> = [1, 1, 0, 1, 0, 0].iter().map(|&b| Fp128::from_u64(b)).collect();
+        assert_eq!(bits, expected);
+
+        // Recomposition of the full decomposition gives the value back
+        let x = -Fp128::from_u64(5);
+        let recomposed = x
+            .to_bits_le_ct(128)
+            .iter()
+            .rev()
+            .fold(Fp128::zero(), |acc, bit| acc.double() + bit);
+        assert_eq!(recomposed, x);
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        // The full two-adic root has order exactly 2^108
+        let root = Fp128::root_of_unity();
+        assert_eq!(root.pow_vartime(&[0, 1 << 44]), Fp128::one());
+        assert_eq!(root.pow_vartime(&[0, 1 << 43]), -Fp128::one());
+
+        // omega_32 = 99753660205281253039813454253403999101, as used by the
+        // C++ implementation
+        let omega_32_bytes = [
+            0x7d, 0x0b, 0x89, 0x6c, 0x63, 0xd7, 0x3c, 0xbd,
+            0x95, 0x20, 0xb3, 0x04, 0x2b, 0xdb, 0x0b, 0x4b,
+        ];
+        assert_eq!(
+            Fp128::get_root_of_unity(32),
+            Fp128::from_bytes_le(&omega_32_bytes).ok()
+        );
+
+        let omega_4 = Fp128::get_root_of_unity(2).unwrap();
+        assert_ne!(omega_4.square(), Fp128::one());
+        assert_eq!(omega_4.square().square(), Fp128::one());
+        assert_eq!(Fp128::get_root_of_unity(0), Some(Fp128::one()));
+        assert!(Fp128::get_root_of_unity(109).is_none());
     }
 }
 
@@ -0,0 +1,236 @@
+//! Quadratic extension Fp2 = F[u] / (u^2 - β)
+//!
+//! The base of the Fp4/Fp6 towers. Multiplication uses Karatsuba (3 base
+//! multiplications instead of 4).
+
+use crate::traits::{Field, FieldExtension};
+use longfellow_core::{LongfellowError, Result};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+pub trait Fp2Config<F: Field>: Copy + Send + Sync + 'static {
+    /// Quadratic non-residue β in F
+    fn fp2_nonresidue() -> F;
+}
+
+#[derive(Clone, Copy, Zeroize)]
+pub struct Fp2<F: Field, C: Fp2Config<F>> {
+    pub c0: F,
+    pub c1: F,
+    _phantom: PhantomData<C>,
+}
+
+impl<F: Field, C: Fp2Config<F>> Fp2<F, C> {
+    pub const ZERO: Self = Self {
+        c0: F::ZERO,
+        c1: F::ZERO,
+        _phantom: PhantomData,
+    };
+
+    pub const ONE: Self = Self {
+        c0: F::ONE,
+        c1: F::ZERO,
+        _phantom: PhantomData,
+    };
+
+    pub fn new(c0: F, c1: F) -> Self {
+        Self {
+            c0,
+            c1,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Conjugate a0 - a1*u
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    /// Norm a0^2 - β*a1^2 down to the base field
+    pub fn norm(&self) -> F {
+        self.c0.square() - C::fp2_nonresidue() * self.c1.square()
+    }
+
+    /// Multiply by a base field element
+    pub fn mul_by_base(&self, scalar: &F) -> Self {
+        Self::new(self.c0 * scalar, self.c1 * scalar)
+    }
+
+    fn add_impl(&self, rhs: &Self) -> Self {
+        Self::new(self.c0 + rhs.c0, self.c1 + rhs.c1)
+    }
+
+    fn sub_impl(&self, rhs: &Self) -> Self {
+        Self::new(self.c0 - rhs.c0, self.c1 - rhs.c1)
+    }
+
+    fn mul_impl(&self, rhs: &Self) -> Self {
+        // Karatsuba: v0 = a0*b0, v1 = a1*b1
+        // c0 = v0 + β*v1, c1 = (a0 + a1)(b0 + b1) - v0 - v1
+        let v0 = self.c0 * rhs.c0;
+        let v1 = self.c1 * rhs.c1;
+        let c1 = (self.c0 + self.c1) * (rhs.c0 + rhs.c1) - v0 - v1;
+        Self::new(v0 + C::fp2_nonresidue() * v1, c1)
+    }
+
+    fn neg_impl(&self) -> Self {
+        Self::new(-self.c0, -self.c1)
+    }
+}
+
+impl<F: Field, C: Fp2Config<F>> Field for Fp2<F, C> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const MODULUS: &'static str = F::MODULUS;
+    const MODULUS_BITS: u32 = 2 * F::MODULUS_BITS;
+    const CHAR_IS_TWO: bool = F::CHAR_IS_TWO;
+    // FFT domains live in the base field's two-adic subgroup
+    const TWO_ADICITY: u32 = F::TWO_ADICITY;
+
+    fn from_u64(val: u64) -> Self {
+        Self::new(F::from_u64(val), F::zero())
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(LongfellowError::InvalidParameter(
+                "Fp2 encoding must have even length".to_string(),
+            ));
+        }
+        let (lo, hi) = bytes.split_at(bytes.len() / 2);
+        Ok(Self::new(F::from_bytes_le(lo)?, F::from_bytes_le(hi)?))
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.c0.to_bytes_le();
+        bytes.extend_from_slice(&self.c1.to_bytes_le());
+        bytes
+    }
+
+    fn canonical_byte_len() -> usize {
+        2 * F::canonical_byte_len()
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.c0.to_canonical_bytes();
+        bytes.extend_from_slice(&self.c1.to_canonical_bytes());
+        bytes
+    }
+
+    fn uniform_bytes_len() -> usize {
+        2 * F::uniform_bytes_len()
+    }
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let (lo, hi) = bytes.split_at(bytes.len() / 2);
+        Self::new(F::from_uniform_bytes(lo), F::from_uniform_bytes(hi))
+    }
+
+    fn invert(&self) -> Option<Self> {
+        // (a0 - a1*u) / (a0^2 - β*a1^2)
+        let norm_inv = self.norm().invert()?;
+        Some(self.conjugate().mul_by_base(&norm_inv))
+    }
+
+    fn order_limbs() -> Vec<u64> {
+        crate::sqrt::mul_limbs(&F::order_limbs(), &F::order_limbs())
+    }
+
+    fn root_of_unity() -> Self {
+        Self::new(F::root_of_unity(), F::ZERO)
+    }
+
+    fn characteristic() -> u64 {
+        F::characteristic()
+    }
+}
+
+impl<F: Field, C: Fp2Config<F>> FieldExtension for Fp2<F, C> {
+    type BaseField = F;
+
+    const DEGREE: usize = 2;
+
+    fn from_base_elements(elements: &[F]) -> Self {
+        let c0 = elements.first().copied().unwrap_or(F::ZERO);
+        let c1 = elements.get(1).copied().unwrap_or(F::ZERO);
+        Self::new(c0, c1)
+    }
+
+    fn to_base_elements(&self) -> Vec<F> {
+        vec![self.c0, self.c1]
+    }
+}
+
+impl<F: Field, C: Fp2Config<F>> ConstantTimeEq for Fp2<F, C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
+    }
+}
+
+impl<F: Field, C: Fp2Config<F>> ConditionallySelectable for Fp2<F, C> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self::new(
+            F::conditional_select(&a.c0, &b.c0, choice),
+            F::conditional_select(&a.c1, &b.c1, choice),
+        )
+    }
+}
+
+impl<F: Field, C: Fp2Config<F>> std::fmt::Debug for Fp2<F, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fp2({:?} + {:?}*u)", self.c0, self.c1)
+    }
+}
+
+impl_extension_ops!(Fp2, Fp2Config);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fp31::{Mersenne31, Mersenne31Fp2};
+
+    #[test]
+    fn test_fp2_complex_multiplication() {
+        // Mersenne31 has u^2 = -1
+        let i = Mersenne31Fp2::new(Mersenne31::zero(), Mersenne31::one());
+        assert_eq!(i * i, -Mersenne31Fp2::one());
+
+        // (3 + 4i)(1 - 2i) = 11 - 2i
+        let a = Mersenne31Fp2::new(Mersenne31::from_u64(3), Mersenne31::from_u64(4));
+        let b = Mersenne31Fp2::new(Mersenne31::from_u64(1), -Mersenne31::from_u64(2));
+        assert_eq!(
+            a * b,
+            Mersenne31Fp2::new(Mersenne31::from_u64(11), -Mersenne31::from_u64(2))
+        );
+    }
+
+    #[test]
+    fn test_fp2_inversion() {
+        let a = Mersenne31Fp2::new(Mersenne31::from_u64(12345), Mersenne31::from_u64(678));
+        assert_eq!(a * a.invert().unwrap(), Mersenne31Fp2::one());
+        assert!(Mersenne31Fp2::zero().invert().is_none());
+    }
+
+    #[test]
+    fn test_fp2_bytes_roundtrip() {
+        let a = Mersenne31Fp2::new(Mersenne31::from_u64(1), Mersenne31::from_u64(2));
+        let bytes = a.to_bytes_le();
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(Mersenne31Fp2::from_bytes_le(&bytes).unwrap(), a);
+    }
+
+    #[test]
+    fn test_fp2_canonical_bytes() {
+        let a = Mersenne31Fp2::new(Mersenne31::from_u64(7), -Mersenne31::one());
+        let bytes = a.to_canonical_bytes();
+        assert_eq!(bytes.len(), Mersenne31Fp2::canonical_byte_len());
+        assert_eq!(Mersenne31Fp2::from_canonical_bytes(&bytes).unwrap(), a);
+
+        // 2^31 - 1 in the second coordinate is not reduced
+        let mut bad = bytes.clone();
+        bad[4..].copy_from_slice(&0x7fffffffu32.to_le_bytes());
+        assert!(Mersenne31Fp2::from_canonical_bytes(&bad).is_err());
+    }
+}
@@ -0,0 +1,837 @@
+//! 31-bit prime fields (BabyBear, Mersenne31), their binomial extensions and
+//! tower configurations
+//!
+//! Elements are kept in Montgomery form with R = 2^32 so that a product of two
+//! elements fits in a single u64 and reduces with one multiply and one
+//! conditional correction. The slice helpers process fixed-width lanes of
+//! packed u32 values, which lets the compiler emit AVX2/NEON vector code.
+
+use crate::field::fp2::{Fp2, Fp2Config};
+use crate::field::fp4::{Fp4, Fp4Config};
+use crate::field::fp6::{Fp6, Fp6Config};
+use crate::nat::{self, Nat};
+use crate::traits::{Field, FieldExtension};
+use longfellow_core::{LongfellowError, Result};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+/// Number of elements processed together by the slice helpers
+pub const FP31_LANES: usize = 8;
+
+pub trait Fp31Params: Copy + Send + Sync + 'static {
+    /// The prime modulus, p < 2^31
+    const P: u32;
+    /// p^(-1) mod 2^32
+    const MU: u32;
+    const MODULUS_STR: &'static str;
+    const MODULUS_BITS: u32;
+    /// s such that p - 1 = 2^s * t with t odd
+    const TWO_ADICITY: u32;
+    /// Primitive 2^TWO_ADICITY-th root of unity, canonical form
+    const ROOT_OF_UNITY: u32;
+}
+
+/// Parameters for a binomial extension Fp[x] / (x^D - W)
+pub trait Fp31ExtParams<const D: usize>: Fp31Params {
+    /// Non-residue W such that x^D - W is irreducible over Fp
+    const W: u32;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BabyBearParams;
+
+impl Fp31Params for BabyBearParams {
+    // p = 15 * 2^27 + 1
+    const P: u32 = 0x78000001;
+    const MU: u32 = 0x88000001;
+    const MODULUS_STR: &'static str = "0x78000001";
+    const MODULUS_BITS: u32 = 31;
+    // 31^15
+    const TWO_ADICITY: u32 = 27;
+    const ROOT_OF_UNITY: u32 = 0x1a427a41;
+}
+
+impl Fp31ExtParams<4> for BabyBearParams {
+    const W: u32 = 11;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mersenne31Params;
+
+impl Fp31Params for Mersenne31Params {
+    // p = 2^31 - 1
+    const P: u32 = 0x7fffffff;
+    const MU: u32 = 0x7fffffff;
+    const MODULUS_STR: &'static str = "0x7fffffff";
+    const MODULUS_BITS: u32 = 31;
+    // p - 1 = 2 * (2^30 - 1), so only -1
+    const TWO_ADICITY: u32 = 1;
+    const ROOT_OF_UNITY: u32 = 0x7ffffffe;
+}
+
+// 4 does not divide p - 1 for Mersenne31, so no quartic binomial is
+// irreducible; the cubic extension gives ~93 bits instead.
+impl Fp31ExtParams<3> for Mersenne31Params {
+    const W: u32 = 5;
+}
+
+pub type BabyBear = Fp31<BabyBearParams>;
+pub type Mersenne31 = Fp31<Mersenne31Params>;
+
+/// Quartic extension of BabyBear, ~124 bits
+pub type BabyBear4 = Fp31Ext<BabyBearParams, 4>;
+/// Cubic extension of Mersenne31, ~93 bits
+pub type Mersenne31Ext3 = Fp31Ext<Mersenne31Params, 3>;
+
+/// Tower BabyBear < Fp2 (u^2 = 11) < Fp4 (v^2 = u) / Fp6 (w^3 = 1 + u)
+#[derive(Clone, Copy, Debug)]
+pub struct BabyBearTower;
+
+impl Fp2Config<BabyBear> for BabyBearTower {
+    fn fp2_nonresidue() -> BabyBear {
+        BabyBear::new(11)
+    }
+}
+
+impl Fp4Config<BabyBear> for BabyBearTower {
+    fn fp4_nonresidue() -> Fp2<BabyBear, Self> {
+        Fp2::new(BabyBear::ZERO, BabyBear::ONE)
+    }
+}
+
+impl Fp6Config<BabyBear> for BabyBearTower {
+    fn fp6_nonresidue() -> Fp2<BabyBear, Self> {
+        Fp2::new(BabyBear::ONE, BabyBear::ONE)
+    }
+}
+
+/// Tower Mersenne31 < Fp2 (u^2 = -1) < Fp4 (v^2 = 2 + u) / Fp6 (w^3 = 2 + u)
+#[derive(Clone, Copy, Debug)]
+pub struct Mersenne31Tower;
+
+impl Fp2Config<Mersenne31> for Mersenne31Tower {
+    fn fp2_nonresidue() -> Mersenne31 {
+        -Mersenne31::ONE
+    }
+}
+
+impl Fp4Config<Mersenne31> for Mersenne31Tower {
+    fn fp4_nonresidue() -> Fp2<Mersenne31, Self> {
+        Fp2::new(Mersenne31::new(2), Mersenne31::ONE)
+    }
+}
+
+impl Fp6Config<Mersenne31> for Mersenne31Tower {
+    fn fp6_nonresidue() -> Fp2<Mersenne31, Self> {
+        Fp2::new(Mersenne31::new(2), Mersenne31::ONE)
+    }
+}
+
+/// ~124 bits
+pub type BabyBearFp4 = Fp4<BabyBear, BabyBearTower>;
+/// ~186 bits
+pub type BabyBearFp6 = Fp6<BabyBear, BabyBearTower>;
+/// Complex extension of Mersenne31, ~62 bits
+pub type Mersenne31Fp2 = Fp2<Mersenne31, Mersenne31Tower>;
+/// ~124 bits
+pub type Mersenne31Fp4 = Fp4<Mersenne31, Mersenne31Tower>;
+/// ~186 bits
+pub type Mersenne31Fp6 = Fp6<Mersenne31, Mersenne31Tower>;
+
+#[derive(Clone, Copy, Zeroize)]
+#[repr(transparent)]
+pub struct Fp31<P: Fp31Params> {
+    value: u32,
+    _phantom: PhantomData<P>,
+}
+
+#[inline(always)]
+fn monty_reduce<P: Fp31Params>(x: u64) -> u32 {
+    let t = x.wrapping_mul(P::MU as u64) & 0xffff_ffff;
+    let u = t * P::P as u64;
+    let (diff, borrow) = x.overflowing_sub(u);
+    let hi = (diff >> 32) as u32;
+    hi.wrapping_add(if borrow { P::P } else { 0 })
+}
+
+impl<P: Fp31Params> Fp31<P> {
+    pub const ZERO: Self = Self {
+        value: 0,
+        _phantom: PhantomData,
+    };
+
+    pub const ONE: Self = Self {
+        value: ((1u64 << 32) % P::P as u64) as u32,
+        _phantom: PhantomData,
+    };
+
+    /// Create an element from its canonical representative, reducing mod p
+    #[inline]
+    pub fn new(value: u32) -> Self {
+        Self {
+            value: (((value as u64) << 32) % P::P as u64) as u32,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Canonical representative in [0, p)
+    #[inline]
+    pub fn as_canonical_u32(&self) -> u32 {
+        monty_reduce::<P>(self.value as u64)
+    }
+
+    /// Element-wise `a[i] += b[i]`
+    pub fn add_assign_slice(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "Vectors must have the same length");
+
+        let mut a_chunks = a.chunks_exact_mut(FP31_LANES);
+        let mut b_chunks = b.chunks_exact(FP31_LANES);
+        for (ac, bc) in (&mut a_chunks).zip(&mut b_chunks) {
+            for j in 0..FP31_LANES {
+                ac[j] += bc[j];
+            }
+        }
+        for (x, y) in a_chunks.into_remainder().iter_mut().zip(b_chunks.remainder()) {
+            *x += *y;
+        }
+    }
+
+    /// Element-wise `a[i] *= b[i]`
+    pub fn mul_assign_slice(a: &mut [Self], b: &[Self]) {
+        assert_eq!(a.len(), b.len(), "Vectors must have the same length");
+
+        let mut a_chunks = a.chunks_exact_mut(FP31_LANES);
+        let mut b_chunks = b.chunks_exact(FP31_LANES);
+        for (ac, bc) in (&mut a_chunks).zip(&mut b_chunks) {
+            for j in 0..FP31_LANES {
+                ac[j] *= bc[j];
+            }
+        }
+        for (x, y) in a_chunks.into_remainder().iter_mut().zip(b_chunks.remainder()) {
+            *x *= *y;
+        }
+    }
+
+    /// Element-wise `a[i] *= scalar`
+    pub fn scale_slice(a: &mut [Self], scalar: Self) {
+        let mut chunks = a.chunks_exact_mut(FP31_LANES);
+        for chunk in &mut chunks {
+            for x in chunk.iter_mut() {
+                *x *= scalar;
+            }
+        }
+        for x in chunks.into_remainder() {
+            *x *= scalar;
+        }
+    }
+
+    #[inline]
+    fn add_reduce(&mut self, other: &Self) {
+        // Both operands are < 2^31, so the sum cannot overflow a u32
+        let sum = self.value + other.value;
+        let (reduced, borrow) = sum.overflowing_sub(P::P);
+        self.value = if borrow { sum } else { reduced };
+    }
+
+    #[inline]
+    fn sub_reduce(&mut self, other: &Self) {
+        let (diff, borrow) = self.value.overflowing_sub(other.value);
+        self.value = if borrow { diff.wrapping_add(P::P) } else { diff };
+    }
+
+    #[inline]
+    fn mul_montgomery(&mut self, other: &Self) {
+        self.value = monty_reduce::<P>(self.value as u64 * other.value as u64);
+    }
+}
+
+impl<P: Fp31Params> Field for Fp31<P> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const MODULUS: &'static str = P::MODULUS_STR;
+    const MODULUS_BITS: u32 = P::MODULUS_BITS;
+    const TWO_ADICITY: u32 = P::TWO_ADICITY;
+
+    fn from_u64(val: u64) -> Self {
+        Self {
+            value: (((val % P::P as u64) << 32) % P::P as u64) as u32,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > 4 {
+            return Err(LongfellowError::InvalidParameter(
+                "Byte array too long for field element".to_string(),
+            ));
+        }
+
+        let mut buf = [0u8; 4];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        let value = u32::from_le_bytes(buf);
+        if value >= P::P {
+            return Err(LongfellowError::InvalidParameter(
+                "Value exceeds modulus".to_string(),
+            ));
+        }
+
+        Ok(Self::new(value))
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        self.as_canonical_u32().to_le_bytes().to_vec()
+    }
+
+    fn invert(&self) -> Option<Self> {
+        if self.value == 0 {
+            return None;
+        }
+        // Fermat: a^(p-2)
+        Some(self.pow(&[(P::P - 2) as u64]))
+    }
+
+    fn order_limbs() -> Vec<u64> {
+        vec![P::P as u64]
+    }
+
+    fn root_of_unity() -> Self {
+        Self::new(P::ROOT_OF_UNITY)
+    }
+
+    fn characteristic() -> u64 {
+        P::P as u64
+    }
+}
+
+impl<P: Fp31Params> ConstantTimeEq for Fp31<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.value.ct_eq(&other.value)
+    }
+}
+
+impl<P: Fp31Params> ConditionallySelectable for Fp31<P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            value: u32::conditional_select(&a.value, &b.value, choice),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: Fp31Params> Default for Fp31<P> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<P: Fp31Params> PartialEq for Fp31<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<P: Fp31Params> Eq for Fp31<P> {}
+
+impl<P: Fp31Params> Add for Fp31<P> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self.add_reduce(&rhs);
+        self
+    }
+}
+
+impl<P: Fp31Params> AddAssign for Fp31<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.add_reduce(&rhs);
+    }
+}
+
+impl<P: Fp31Params> Sub for Fp31<P> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self.sub_reduce(&rhs);
+        self
+    }
+}
+
+impl<P: Fp31Params> SubAssign for Fp31<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.sub_reduce(&rhs);
+    }
+}
+
+impl<P: Fp31Params> Mul for Fp31<P> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Self) -> Self {
+        self.mul_montgomery(&rhs);
+        self
+    }
+}
+
+impl<P: Fp31Params> MulAssign for Fp31<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.mul_montgomery(&rhs);
+    }
+}
+
+impl<P: Fp31Params> Neg for Fp31<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<P: Fp31Params> Add<&Self> for Fp31<P> {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self {
+        self + *rhs
+    }
+}
+
+impl<P: Fp31Params> AddAssign<&Self> for Fp31<P> {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self += *rhs;
+    }
+}
+
+impl<P: Fp31Params> Sub<&Self> for Fp31<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self {
+        self - *rhs
+    }
+}
+
+impl<P: Fp31Params> SubAssign<&Self> for Fp31<P> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self -= *rhs;
+    }
+}
+
+impl<P: Fp31Params> Mul<&Self> for Fp31<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: &Self) -> Self {
+        self * *rhs
+    }
+}
+
+impl<P: Fp31Params> MulAssign<&Self> for Fp31<P> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self *= *rhs;
+    }
+}
+
+impl<P: Fp31Params> std::fmt::Debug for Fp31<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fp31({})", self.as_canonical_u32())
+    }
+}
+
+/// Binomial extension Fp[x] / (x^D - W), coefficients in ascending order
+#[derive(Clone, Copy, Zeroize)]
+pub struct Fp31Ext<P: Fp31ExtParams<D>, const D: usize> {
+    coeffs: [Fp31<P>; D],
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Fp31Ext<P, D> {
+    pub const ZERO: Self = Self {
+        coeffs: [Fp31::<P>::ZERO; D],
+    };
+
+    pub const ONE: Self = {
+        let mut coeffs = [Fp31::<P>::ZERO; D];
+        coeffs[0] = Fp31::<P>::ONE;
+        Self { coeffs }
+    };
+
+    pub fn new(coeffs: [Fp31<P>; D]) -> Self {
+        Self { coeffs }
+    }
+
+    pub fn from_base(value: Fp31<P>) -> Self {
+        let mut coeffs = [Fp31::ZERO; D];
+        coeffs[0] = value;
+        Self { coeffs }
+    }
+
+    pub fn coeffs(&self) -> &[Fp31<P>; D] {
+        &self.coeffs
+    }
+
+    /// The field order p^D; p < 2^32, so it always fits in D limbs
+    fn order() -> Nat<D> {
+        let mut order = Nat::<D>::from_u64(1);
+        for _ in 0..D {
+            let mut carry = 0;
+            for limb in order.limbs.iter_mut() {
+                (*limb, carry) = nat::mac_with_carry(0, *limb, P::P as u64, carry);
+            }
+        }
+        order
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Field for Fp31Ext<P, D> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const MODULUS: &'static str = P::MODULUS_STR;
+    const MODULUS_BITS: u32 = P::MODULUS_BITS * D as u32;
+    // Only the base field's two-adic subgroup is used for FFT domains
+    const TWO_ADICITY: u32 = P::TWO_ADICITY;
+
+    fn from_u64(val: u64) -> Self {
+        Self::from_base(Fp31::from_u64(val))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 4 * D {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Extension element requires exactly {} bytes",
+                4 * D
+            )));
+        }
+
+        let mut coeffs = [Fp31::ZERO; D];
+        for (coeff, chunk) in coeffs.iter_mut().zip(bytes.chunks(4)) {
+            *coeff = Fp31::from_bytes_le(chunk)?;
+        }
+        Ok(Self { coeffs })
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        self.coeffs.iter().flat_map(|c| c.to_bytes_le()).collect()
+    }
+
+    fn canonical_byte_len() -> usize {
+        4 * D
+    }
+
+    fn uniform_bytes_len() -> usize {
+        D * Fp31::<P>::uniform_bytes_len()
+    }
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let mut coeffs = [Fp31::ZERO; D];
+        for (coeff, chunk) in coeffs.iter_mut().zip(bytes.chunks(bytes.len().div_ceil(D).max(1))) {
+            *coeff = Fp31::from_uniform_bytes(chunk);
+        }
+        Self { coeffs }
+    }
+
+    fn invert(&self) -> Option<Self> {
+        if *self == Self::ZERO {
+            return None;
+        }
+        // Fermat in the extension: a^(p^D - 2)
+        let mut exp = Self::order();
+        exp.sub_with_borrow(&Nat::from_u64(2));
+        Some(self.pow(&exp.limbs))
+    }
+
+    fn order_limbs() -> Vec<u64> {
+        Self::order().limbs.to_vec()
+    }
+
+    fn root_of_unity() -> Self {
+        Self::from_base(Fp31::root_of_unity())
+    }
+
+    fn characteristic() -> u64 {
+        P::P as u64
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> FieldExtension for Fp31Ext<P, D> {
+    type BaseField = Fp31<P>;
+
+    const DEGREE: usize = D;
+
+    fn from_base_elements(elements: &[Self::BaseField]) -> Self {
+        let mut coeffs = [Fp31::ZERO; D];
+        for (coeff, elem) in coeffs.iter_mut().zip(elements) {
+            *coeff = *elem;
+        }
+        Self { coeffs }
+    }
+
+    fn to_base_elements(&self) -> Vec<Self::BaseField> {
+        self.coeffs.to_vec()
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> ConstantTimeEq for Fp31Ext<P, D> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.coeffs
+            .iter()
+            .zip(other.coeffs.iter())
+            .fold(Choice::from(1), |acc, (a, b)| acc & a.ct_eq(b))
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> ConditionallySelectable for Fp31Ext<P, D> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut coeffs = [Fp31::ZERO; D];
+        for (coeff, (x, y)) in coeffs.iter_mut().zip(a.coeffs.iter().zip(&b.coeffs)) {
+            *coeff = Fp31::conditional_select(x, y, choice);
+        }
+        Self { coeffs }
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Default for Fp31Ext<P, D> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> PartialEq for Fp31Ext<P, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Eq for Fp31Ext<P, D> {}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Add for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> AddAssign for Fp31Ext<P, D> {
+    fn add_assign(&mut self, rhs: Self) {
+        for i in 0..D {
+            self.coeffs[i] += rhs.coeffs[i];
+        }
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Sub for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> SubAssign for Fp31Ext<P, D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for i in 0..D {
+            self.coeffs[i] -= rhs.coeffs[i];
+        }
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Mul for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // Schoolbook product, folding x^(D+i) = W * x^i
+        let w = Fp31::<P>::new(P::W);
+        let mut coeffs = [Fp31::ZERO; D];
+        for i in 0..D {
+            for j in 0..D {
+                let prod = self.coeffs[i] * rhs.coeffs[j];
+                if i + j < D {
+                    coeffs[i + j] += prod;
+                } else {
+                    coeffs[i + j - D] += prod * w;
+                }
+            }
+        }
+        Self { coeffs }
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> MulAssign for Fp31Ext<P, D> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Neg for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Add<&Self> for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self {
+        self + *rhs
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> AddAssign<&Self> for Fp31Ext<P, D> {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self += *rhs;
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Sub<&Self> for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self {
+        self - *rhs
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> SubAssign<&Self> for Fp31Ext<P, D> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self -= *rhs;
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> Mul<&Self> for Fp31Ext<P, D> {
+    type Output = Self;
+
+    fn mul(self, rhs: &Self) -> Self {
+        self * *rhs
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> MulAssign<&Self> for Fp31Ext<P, D> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self *= *rhs;
+    }
+}
+
+impl<P: Fp31ExtParams<D>, const D: usize> std::fmt::Debug for Fp31Ext<P, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fp31Ext({:?})", self.coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_babybear_arithmetic() {
+        let a = BabyBear::from_u64(5);
+        let b = BabyBear::from_u64(7);
+
+        assert_eq!(a + b, BabyBear::from_u64(12));
+        assert_eq!(b - a, BabyBear::from_u64(2));
+        assert_eq!(a * b, BabyBear::from_u64(35));
+        assert_eq!(a + (-a), BabyBear::zero());
+
+        // p - 1 = -1
+        let minus_one = BabyBear::from_u64(0x78000000);
+        assert_eq!(minus_one, -BabyBear::one());
+        assert_eq!(minus_one * minus_one, BabyBear::one());
+    }
+
+    #[test]
+    fn test_mersenne31_arithmetic() {
+        let a = Mersenne31::from_u64((1 << 31) - 2);
+        assert_eq!(a + Mersenne31::one(), Mersenne31::zero());
+        assert_eq!(Mersenne31::from_u64(1 << 31), Mersenne31::one());
+
+        let b = Mersenne31::from_u64(123456789);
+        assert_eq!(b.as_canonical_u32(), 123456789);
+        assert_eq!(b * b.invert().unwrap(), Mersenne31::one());
+    }
+
+    #[test]
+    fn test_fp31_root_of_unity() {
+        let omega = BabyBear::get_root_of_unity(27).unwrap();
+        assert_eq!(omega.pow(&[1 << 26]), -BabyBear::one());
+        assert_eq!(omega.pow(&[1 << 27]), BabyBear::one());
+        assert_eq!(BabyBear::get_root_of_unity(3), Some(omega.pow(&[1 << 24])));
+
+        assert_eq!(Mersenne31::get_root_of_unity(1), Some(-Mersenne31::one()));
+        assert!(Mersenne31::get_root_of_unity(2).is_none());
+
+        // Extensions reuse the base field's roots
+        assert_eq!(
+            BabyBear4::get_root_of_unity(5),
+            BabyBear::get_root_of_unity(5).map(BabyBear4::from_base)
+        );
+    }
+
+    #[test]
+    fn test_fp31_bytes_roundtrip() {
+        let a = BabyBear::from_u64(0x12345678);
+        let bytes = a.to_bytes_le();
+        assert_eq!(bytes.len(), 4);
+        assert_eq!(BabyBear::from_bytes_le(&bytes).unwrap(), a);
+
+        assert!(BabyBear::from_bytes_le(&0x78000001u32.to_le_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_slice_ops_match_scalar() {
+        let a: Vec<BabyBear> = (0..21).map(|i| BabyBear::from_u64(i * 1000003 + 7)).collect();
+        let b: Vec<BabyBear> = (0..21).map(|i| BabyBear::from_u64(i * 7919 + 1)).collect();
+
+        let mut sum = a.clone();
+        BabyBear::add_assign_slice(&mut sum, &b);
+        let mut prod = a.clone();
+        BabyBear::mul_assign_slice(&mut prod, &b);
+
+        for i in 0..a.len() {
+            assert_eq!(sum[i], a[i] + b[i]);
+            assert_eq!(prod[i], a[i] * b[i]);
+        }
+    }
+
+    #[test]
+    fn test_babybear4_inversion() {
+        let a = BabyBear4::new([
+            BabyBear::from_u64(1),
+            BabyBear::from_u64(2),
+            BabyBear::from_u64(3),
+            BabyBear::from_u64(4),
+        ]);
+        let a_inv = a.invert().unwrap();
+        assert_eq!(a * a_inv, BabyBear4::one());
+        assert!(BabyBear4::zero().invert().is_none());
+    }
+
+    #[test]
+    fn test_extension_reduces_by_w() {
+        // x^3 = W in Mersenne31[x] / (x^3 - 5)
+        let x = Mersenne31Ext3::new([Mersenne31::zero(), Mersenne31::one(), Mersenne31::zero()]);
+        assert_eq!(x * x * x, Mersenne31Ext3::from_u64(5));
+
+        let y = Mersenne31Ext3::new([
+            Mersenne31::from_u64(9),
+            Mersenne31::from_u64(8),
+            Mersenne31::from_u64(7),
+        ]);
+        assert_eq!(y * y.invert().unwrap(), Mersenne31Ext3::one());
+    }
+
+    // 11 is a non-square and p = 1 mod 4, so x^8 - 11 is irreducible too
+    impl Fp31ExtParams<8> for BabyBearParams {
+        const W: u32 = 11;
+    }
+
+    #[test]
+    fn test_order_beyond_128_bits() {
+        type BabyBear8 = Fp31Ext<BabyBearParams, 8>;
+
+        let p = [BabyBearParams::P as u64];
+        let order = (0..8).fold(vec![1u64], |acc, _| crate::sqrt::mul_limbs(&acc, &p));
+        assert_eq!(BabyBear8::order_limbs()[..order.len()], order[..]);
+
+        let a = BabyBear8::new(core::array::from_fn(|i| BabyBear::from_u64(i as u64 + 1)));
+        assert_eq!(a * a.invert().unwrap(), BabyBear8::one());
+    }
+}
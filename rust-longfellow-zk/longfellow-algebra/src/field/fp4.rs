@@ -0,0 +1,224 @@
+//! Quartic extension Fp4 = Fp2[v] / (v^2 - ξ)
+//!
+//! Built as a quadratic tower over `Fp2`, so a multiplication costs three
+//! Fp2 multiplications (nine base multiplications) via Karatsuba.
+
+use crate::field::fp2::{Fp2, Fp2Config};
+use crate::traits::{Field, FieldExtension};
+use longfellow_core::{LongfellowError, Result};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+pub trait Fp4Config<F: Field>: Fp2Config<F> {
+    /// Quadratic non-residue ξ in Fp2
+    fn fp4_nonresidue() -> Fp2<F, Self>;
+}
+
+#[derive(Clone, Copy, Zeroize)]
+pub struct Fp4<F: Field, C: Fp4Config<F>> {
+    pub c0: Fp2<F, C>,
+    pub c1: Fp2<F, C>,
+    _phantom: PhantomData<C>,
+}
+
+impl<F: Field, C: Fp4Config<F>> Fp4<F, C> {
+    pub const ZERO: Self = Self {
+        c0: Fp2::ZERO,
+        c1: Fp2::ZERO,
+        _phantom: PhantomData,
+    };
+
+    pub const ONE: Self = Self {
+        c0: Fp2::ONE,
+        c1: Fp2::ZERO,
+        _phantom: PhantomData,
+    };
+
+    pub fn new(c0: Fp2<F, C>, c1: Fp2<F, C>) -> Self {
+        Self {
+            c0,
+            c1,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Conjugate over Fp2: c0 - c1*v
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    /// Norm c0^2 - ξ*c1^2 down to Fp2
+    pub fn norm(&self) -> Fp2<F, C> {
+        self.c0.square() - C::fp4_nonresidue() * self.c1.square()
+    }
+
+    fn add_impl(&self, rhs: &Self) -> Self {
+        Self::new(self.c0 + rhs.c0, self.c1 + rhs.c1)
+    }
+
+    fn sub_impl(&self, rhs: &Self) -> Self {
+        Self::new(self.c0 - rhs.c0, self.c1 - rhs.c1)
+    }
+
+    fn mul_impl(&self, rhs: &Self) -> Self {
+        let v0 = self.c0 * rhs.c0;
+        let v1 = self.c1 * rhs.c1;
+        let c1 = (self.c0 + self.c1) * (rhs.c0 + rhs.c1) - v0 - v1;
+        Self::new(v0 + C::fp4_nonresidue() * v1, c1)
+    }
+
+    fn neg_impl(&self) -> Self {
+        Self::new(-self.c0, -self.c1)
+    }
+}
+
+impl<F: Field, C: Fp4Config<F>> Field for Fp4<F, C> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const MODULUS: &'static str = F::MODULUS;
+    const MODULUS_BITS: u32 = 4 * F::MODULUS_BITS;
+    const CHAR_IS_TWO: bool = F::CHAR_IS_TWO;
+    // FFT domains live in the base field's two-adic subgroup
+    const TWO_ADICITY: u32 = F::TWO_ADICITY;
+
+    fn from_u64(val: u64) -> Self {
+        Self::new(Fp2::from_u64(val), Fp2::ZERO)
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(LongfellowError::InvalidParameter(
+                "Fp4 encoding length must be a multiple of 4".to_string(),
+            ));
+        }
+        let (lo, hi) = bytes.split_at(bytes.len() / 2);
+        Ok(Self::new(Fp2::from_bytes_le(lo)?, Fp2::from_bytes_le(hi)?))
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.c0.to_bytes_le();
+        bytes.extend_from_slice(&self.c1.to_bytes_le());
+        bytes
+    }
+
+    fn canonical_byte_len() -> usize {
+        2 * Fp2::<F, C>::canonical_byte_len()
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.c0.to_canonical_bytes();
+        bytes.extend_from_slice(&self.c1.to_canonical_bytes());
+        bytes
+    }
+
+    fn uniform_bytes_len() -> usize {
+        2 * Fp2::<F, C>::uniform_bytes_len()
+    }
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let (lo, hi) = bytes.split_at(bytes.len() / 2);
+        Self::new(Fp2::from_uniform_bytes(lo), Fp2::from_uniform_bytes(hi))
+    }
+
+    fn invert(&self) -> Option<Self> {
+        let norm_inv = self.norm().invert()?;
+        let conj = self.conjugate();
+        Some(Self::new(conj.c0 * norm_inv, conj.c1 * norm_inv))
+    }
+
+    fn order_limbs() -> Vec<u64> {
+        crate::sqrt::mul_limbs(&Fp2::<F, C>::order_limbs(), &Fp2::<F, C>::order_limbs())
+    }
+
+    fn root_of_unity() -> Self {
+        Self::new(Fp2::root_of_unity(), Fp2::ZERO)
+    }
+
+    fn characteristic() -> u64 {
+        F::characteristic()
+    }
+}
+
+impl<F: Field, C: Fp4Config<F>> FieldExtension for Fp4<F, C> {
+    type BaseField = Fp2<F, C>;
+
+    const DEGREE: usize = 2;
+
+    fn from_base_elements(elements: &[Fp2<F, C>]) -> Self {
+        let c0 = elements.first().copied().unwrap_or(Fp2::ZERO);
+        let c1 = elements.get(1).copied().unwrap_or(Fp2::ZERO);
+        Self::new(c0, c1)
+    }
+
+    fn to_base_elements(&self) -> Vec<Fp2<F, C>> {
+        vec![self.c0, self.c1]
+    }
+}
+
+impl<F: Field, C: Fp4Config<F>> ConstantTimeEq for Fp4<F, C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
+    }
+}
+
+impl<F: Field, C: Fp4Config<F>> ConditionallySelectable for Fp4<F, C> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self::new(
+            Fp2::conditional_select(&a.c0, &b.c0, choice),
+            Fp2::conditional_select(&a.c1, &b.c1, choice),
+        )
+    }
+}
+
+impl<F: Field, C: Fp4Config<F>> std::fmt::Debug for Fp4<F, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fp4({:?} + {:?}*v)", self.c0, self.c1)
+    }
+}
+
+impl_extension_ops!(Fp4, Fp4Config);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fp31::{BabyBear, BabyBear4, BabyBearFp4, Mersenne31, Mersenne31Fp4};
+
+    /// The tower Fp2[v]/(v^2 - u) with u^2 = 11 is BabyBear[x]/(x^4 - 11)
+    /// with basis {1, u, v, uv} = {1, x^2, x, x^3}.
+    fn to_tower(a: &BabyBear4) -> BabyBearFp4 {
+        let c = a.coeffs();
+        Fp4::new(Fp2::new(c[0], c[2]), Fp2::new(c[1], c[3]))
+    }
+
+    #[test]
+    fn test_fp4_tower_matches_binomial_extension() {
+        let a = BabyBear4::new([
+            BabyBear::from_u64(1),
+            BabyBear::from_u64(2),
+            BabyBear::from_u64(3),
+            BabyBear::from_u64(4),
+        ]);
+        let b = BabyBear4::new([
+            BabyBear::from_u64(1000),
+            BabyBear::from_u64(0x7000_0000),
+            BabyBear::from_u64(5),
+            BabyBear::from_u64(99),
+        ]);
+
+        assert_eq!(to_tower(&(a * b)), to_tower(&a) * to_tower(&b));
+        assert_eq!(to_tower(&a.invert().unwrap()), to_tower(&a).invert().unwrap());
+    }
+
+    #[test]
+    fn test_fp4_inversion() {
+        let a = Mersenne31Fp4::new(
+            Fp2::new(Mersenne31::from_u64(7), Mersenne31::from_u64(0)),
+            Fp2::new(Mersenne31::from_u64(11), Mersenne31::from_u64(13)),
+        );
+        assert_eq!(a * a.invert().unwrap(), Mersenne31Fp4::one());
+        assert!(Mersenne31Fp4::zero().invert().is_none());
+        assert_eq!(Mersenne31Fp4::MODULUS_BITS, 124);
+    }
+}
@@ -0,0 +1,249 @@
+//! Sextic extension Fp6 = Fp2[w] / (w^3 - ξ)
+//!
+//! Built as a cubic tower over `Fp2`. Multiplication uses the Karatsuba
+//! variant for cubic extensions (six Fp2 multiplications instead of nine).
+
+use crate::field::fp2::{Fp2, Fp2Config};
+use crate::traits::{Field, FieldExtension};
+use longfellow_core::{LongfellowError, Result};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroize;
+
+pub trait Fp6Config<F: Field>: Fp2Config<F> {
+    /// Cubic non-residue ξ in Fp2
+    fn fp6_nonresidue() -> Fp2<F, Self>;
+}
+
+#[derive(Clone, Copy, Zeroize)]
+pub struct Fp6<F: Field, C: Fp6Config<F>> {
+    pub c0: Fp2<F, C>,
+    pub c1: Fp2<F, C>,
+    pub c2: Fp2<F, C>,
+    _phantom: PhantomData<C>,
+}
+
+impl<F: Field, C: Fp6Config<F>> Fp6<F, C> {
+    pub const ZERO: Self = Self {
+        c0: Fp2::ZERO,
+        c1: Fp2::ZERO,
+        c2: Fp2::ZERO,
+        _phantom: PhantomData,
+    };
+
+    pub const ONE: Self = Self {
+        c0: Fp2::ONE,
+        c1: Fp2::ZERO,
+        c2: Fp2::ZERO,
+        _phantom: PhantomData,
+    };
+
+    pub fn new(c0: Fp2<F, C>, c1: Fp2<F, C>, c2: Fp2<F, C>) -> Self {
+        Self {
+            c0,
+            c1,
+            c2,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn add_impl(&self, rhs: &Self) -> Self {
+        Self::new(self.c0 + rhs.c0, self.c1 + rhs.c1, self.c2 + rhs.c2)
+    }
+
+    fn sub_impl(&self, rhs: &Self) -> Self {
+        Self::new(self.c0 - rhs.c0, self.c1 - rhs.c1, self.c2 - rhs.c2)
+    }
+
+    fn mul_impl(&self, rhs: &Self) -> Self {
+        let xi = C::fp6_nonresidue();
+        let (a0, a1, a2) = (self.c0, self.c1, self.c2);
+        let (b0, b1, b2) = (rhs.c0, rhs.c1, rhs.c2);
+
+        let v0 = a0 * b0;
+        let v1 = a1 * b1;
+        let v2 = a2 * b2;
+
+        let c0 = v0 + xi * ((a1 + a2) * (b1 + b2) - v1 - v2);
+        let c1 = (a0 + a1) * (b0 + b1) - v0 - v1 + xi * v2;
+        let c2 = (a0 + a2) * (b0 + b2) - v0 + v1 - v2;
+
+        Self::new(c0, c1, c2)
+    }
+
+    fn neg_impl(&self) -> Self {
+        Self::new(-self.c0, -self.c1, -self.c2)
+    }
+}
+
+impl<F: Field, C: Fp6Config<F>> Field for Fp6<F, C> {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    const MODULUS: &'static str = F::MODULUS;
+    const MODULUS_BITS: u32 = 6 * F::MODULUS_BITS;
+    const CHAR_IS_TWO: bool = F::CHAR_IS_TWO;
+    // FFT domains live in the base field's two-adic subgroup
+    const TWO_ADICITY: u32 = F::TWO_ADICITY;
+
+    fn from_u64(val: u64) -> Self {
+        Self::new(Fp2::from_u64(val), Fp2::ZERO, Fp2::ZERO)
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        if !bytes.len().is_multiple_of(6) {
+            return Err(LongfellowError::InvalidParameter(
+                "Fp6 encoding length must be a multiple of 6".to_string(),
+            ));
+        }
+        let width = bytes.len() / 3;
+        Ok(Self::new(
+            Fp2::from_bytes_le(&bytes[..width])?,
+            Fp2::from_bytes_le(&bytes[width..2 * width])?,
+            Fp2::from_bytes_le(&bytes[2 * width..])?,
+        ))
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.c0.to_bytes_le();
+        bytes.extend_from_slice(&self.c1.to_bytes_le());
+        bytes.extend_from_slice(&self.c2.to_bytes_le());
+        bytes
+    }
+
+    fn canonical_byte_len() -> usize {
+        3 * Fp2::<F, C>::canonical_byte_len()
+    }
+
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.c0.to_canonical_bytes();
+        bytes.extend_from_slice(&self.c1.to_canonical_bytes());
+        bytes.extend_from_slice(&self.c2.to_canonical_bytes());
+        bytes
+    }
+
+    fn uniform_bytes_len() -> usize {
+        3 * Fp2::<F, C>::uniform_bytes_len()
+    }
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let width = bytes.len() / 3;
+        Self::new(
+            Fp2::from_uniform_bytes(&bytes[..width]),
+            Fp2::from_uniform_bytes(&bytes[width..2 * width]),
+            Fp2::from_uniform_bytes(&bytes[2 * width..]),
+        )
+    }
+
+    fn invert(&self) -> Option<Self> {
+        let xi = C::fp6_nonresidue();
+        let (a0, a1, a2) = (self.c0, self.c1, self.c2);
+
+        let t0 = a0.square() - xi * (a1 * a2);
+        let t1 = xi * a2.square() - a0 * a1;
+        let t2 = a1.square() - a0 * a2;
+
+        let det = a0 * t0 + xi * (a2 * t1 + a1 * t2);
+        let det_inv = det.invert()?;
+
+        Some(Self::new(t0 * det_inv, t1 * det_inv, t2 * det_inv))
+    }
+
+    fn order_limbs() -> Vec<u64> {
+        let q = Fp2::<F, C>::order_limbs();
+        crate::sqrt::mul_limbs(&crate::sqrt::mul_limbs(&q, &q), &q)
+    }
+
+    fn root_of_unity() -> Self {
+        Self::new(Fp2::root_of_unity(), Fp2::ZERO, Fp2::ZERO)
+    }
+
+    fn characteristic() -> u64 {
+        F::characteristic()
+    }
+}
+
+impl<F: Field, C: Fp6Config<F>> FieldExtension for Fp6<F, C> {
+    type BaseField = Fp2<F, C>;
+
+    const DEGREE: usize = 3;
+
+    fn from_base_elements(elements: &[Fp2<F, C>]) -> Self {
+        let c0 = elements.first().copied().unwrap_or(Fp2::ZERO);
+        let c1 = elements.get(1).copied().unwrap_or(Fp2::ZERO);
+        let c2 = elements.get(2).copied().unwrap_or(Fp2::ZERO);
+        Self::new(c0, c1, c2)
+    }
+
+    fn to_base_elements(&self) -> Vec<Fp2<F, C>> {
+        vec![self.c0, self.c1, self.c2]
+    }
+}
+
+impl<F: Field, C: Fp6Config<F>> ConstantTimeEq for Fp6<F, C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1) & self.c2.ct_eq(&other.c2)
+    }
+}
+
+impl<F: Field, C: Fp6Config<F>> ConditionallySelectable for Fp6<F, C> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self::new(
+            Fp2::conditional_select(&a.c0, &b.c0, choice),
+            Fp2::conditional_select(&a.c1, &b.c1, choice),
+            Fp2::conditional_select(&a.c2, &b.c2, choice),
+        )
+    }
+}
+
+impl<F: Field, C: Fp6Config<F>> std::fmt::Debug for Fp6<F, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fp6({:?} + {:?}*w + {:?}*w^2)", self.c0, self.c1, self.c2)
+    }
+}
+
+impl_extension_ops!(Fp6, Fp6Config);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fp31::{BabyBear, BabyBearFp6, BabyBearTower, Mersenne31, Mersenne31Fp6};
+
+    fn sample_babybear() -> BabyBearFp6 {
+        Fp6::new(
+            Fp2::new(BabyBear::from_u64(3), BabyBear::from_u64(1)),
+            Fp2::new(BabyBear::from_u64(4), BabyBear::from_u64(1)),
+            Fp2::new(BabyBear::from_u64(5), BabyBear::from_u64(9)),
+        )
+    }
+
+    #[test]
+    fn test_fp6_cube_of_w_is_nonresidue() {
+        let w = BabyBearFp6::new(Fp2::ZERO, Fp2::ONE, Fp2::ZERO);
+        let xi = <BabyBearTower as Fp6Config<BabyBear>>::fp6_nonresidue();
+        assert_eq!(w * w * w, BabyBearFp6::new(xi, Fp2::ZERO, Fp2::ZERO));
+    }
+
+    #[test]
+    fn test_fp6_distributivity() {
+        let a = sample_babybear();
+        let b = a.square() + BabyBearFp6::from_u64(17);
+        let c = b * a - BabyBearFp6::one();
+        assert_eq!(a * (b + c), a * b + a * c);
+        assert_eq!((a * b) * c, a * (b * c));
+    }
+
+    #[test]
+    fn test_fp6_inversion() {
+        let a = sample_babybear();
+        assert_eq!(a * a.invert().unwrap(), BabyBearFp6::one());
+
+        let b = Mersenne31Fp6::new(
+            Fp2::new(Mersenne31::from_u64(2), Mersenne31::from_u64(0)),
+            Fp2::ZERO,
+            Fp2::new(Mersenne31::from_u64(1), Mersenne31::from_u64(1)),
+        );
+        assert_eq!(b * b.invert().unwrap(), Mersenne31Fp6::one());
+        assert!(Mersenne31Fp6::zero().invert().is_none());
+    }
+}
@@ -1,11 +1,38 @@
 use crate::nat::{self, Limb, Nat};
 use crate::traits::Field;
 use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
 use std::marker::PhantomData;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroize;
 
+/// Batches at least this long are inverted in parallel chunks
+const BATCH_INVERT_PARALLEL_THRESHOLD: usize = 1 << 12;
+const BATCH_INVERT_CHUNK_SIZE: usize = 1 << 10;
+
+/// Montgomery conversions of at least this many elements run in parallel
+const BATCH_CONVERT_PARALLEL_THRESHOLD: usize = 1 << 14;
+const BATCH_CONVERT_CHUNK_SIZE: usize = 1 << 12;
+
+/// Apply `convert` to every value, splitting long slices into chunks
+/// converted on all cores
+fn convert_batch<T, U, C>(values: &[T], convert: C) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    C: Fn(&T) -> U + Send + Sync,
+{
+    if values.len() < BATCH_CONVERT_PARALLEL_THRESHOLD {
+        values.iter().map(convert).collect()
+    } else {
+        values
+            .par_chunks(BATCH_CONVERT_CHUNK_SIZE)
+            .flat_map_iter(|chunk| chunk.iter().map(&convert))
+            .collect()
+    }
+}
+
 pub trait FieldReduction<const N: usize>: Copy + Send + Sync + 'static {
     const MODULUS: Nat<N>;
     const MODULUS_STR: &'static str;
@@ -13,6 +40,11 @@ pub trait FieldReduction<const N: usize>: Copy + Send + Sync + 'static {
     const R: Nat<N>;
     const R2: Nat<N>;
     const INV: Limb;
+    /// s such that p - 1 = 2^s * t with t odd
+    const TWO_ADICITY: u32;
+    /// Primitive 2^TWO_ADICITY-th root of unity, in canonical (not
+    /// Montgomery) form
+    const ROOT_OF_UNITY: Nat<N>;
     
     fn reduction_step(a: &mut [Limb], mprime: Limb, modulus: &Nat<N>);
 }
@@ -128,8 +160,9 @@ impl<const N: usize, R: FieldReduction<N>> FpGeneric<N, R> {
         // Multiply a * b to get full 2N-limb result
         let (wide_result, _) = self.value.mul_wide(&other.value);
         
-        // Convert to working array - make sure we have 2*N limbs
-        let mut t = vec![0u64; 2 * N];
+        // Convert to working array, with a limb above the 2N for the carry
+        // out of moduli that fill their top limb
+        let mut t = vec![0u64; 2 * N + 1];
         for i in 0..wide_result.len().min(2 * N) {
             t[i] = wide_result[i];
         }
@@ -165,7 +198,7 @@ impl<const N: usize, R: FieldReduction<N>> FpGeneric<N, R> {
         }
         
         // Final conditional subtraction
-        if self.value >= R::MODULUS {
+        if t[2 * N] != 0 || self.value >= R::MODULUS {
             self.value.sub_with_borrow(&R::MODULUS);
         }
     }
@@ -212,6 +245,94 @@ impl<const N: usize, R: FieldReduction<N>> FpGeneric<N, R> {
         }
     }
 
+    /// Montgomery's trick over one slice. Prefix products are kept in a
+    /// scratch buffer so the backward pass needs no extra inversions, and
+    /// zeros are skipped so they stay zero.
+    fn batch_invert_serial(elements: &mut [Self]) {
+        let mut prefix = Vec::with_capacity(elements.len());
+        let mut acc = Self::ONE;
+
+        for elem in elements.iter() {
+            prefix.push(acc);
+            if !bool::from(elem.value.is_zero()) {
+                acc.mul_montgomery(elem);
+            }
+        }
+
+        let mut inv_acc = match acc.invert() {
+            Some(inv) => inv,
+            None => return,
+        };
+
+        for (elem, prod) in elements.iter_mut().zip(prefix.iter()).rev() {
+            if bool::from(elem.value.is_zero()) {
+                continue;
+            }
+            let mut next = inv_acc;
+            next.mul_montgomery(elem);
+            elem.value = prod.value;
+            elem.mul_montgomery(&inv_acc);
+            inv_acc = next;
+        }
+    }
+
+    /// Montgomery product a * b * R^-1 with the CIOS method, on a stack
+    /// buffer so tight batch loops avoid the allocations of `mul_montgomery`
+    ///
+    /// The accumulator is N limbs plus the two limbs above them, kept in
+    /// `hi` and `top`.
+    #[inline]
+    fn mont_mul_nat(a: &Nat<N>, b: &Nat<N>) -> Nat<N> {
+        let mut t = [0 as Limb; N];
+        let mut hi: Limb = 0;
+
+        for &ai in &a.limbs {
+            let mut carry = 0;
+            for (tj, &bj) in t.iter_mut().zip(&b.limbs) {
+                (*tj, carry) = nat::mac_with_carry(*tj, ai, bj, carry);
+            }
+            let (sum, top) = nat::add_with_carry(hi, carry, 0);
+            hi = sum;
+
+            let m = t[0].wrapping_mul(R::INV);
+            let (_, mut carry) = nat::mac_with_carry(t[0], m, R::MODULUS.limbs[0], 0);
+            for j in 1..N {
+                (t[j - 1], carry) = nat::mac_with_carry(t[j], m, R::MODULUS.limbs[j], carry);
+            }
+            let (sum, c) = nat::add_with_carry(hi, carry, 0);
+            t[N - 1] = sum;
+            hi = top + c;
+        }
+
+        let mut result = Nat { limbs: t };
+        if hi != 0 || result >= R::MODULUS {
+            result.sub_with_borrow(&R::MODULUS);
+        }
+        result
+    }
+
+    /// Convert canonical values into Montgomery form. Long slices are split
+    /// into chunks converted on all cores.
+    pub fn to_montgomery_batch(values: &[Nat<N>]) -> Vec<Self> {
+        convert_batch(values, |v| Self {
+            value: Self::mont_mul_nat(v, &Self::R2),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Convert Montgomery-form elements back to canonical values
+    pub fn from_montgomery_batch(elements: &[Self]) -> Vec<Nat<N>> {
+        convert_batch(elements, |e| Self::mont_mul_nat(&e.value, &Nat::ONE))
+    }
+
+    /// Batch `from_u64`, for ingesting large numbers of small claims
+    pub fn from_u64_batch(values: &[u64]) -> Vec<Self> {
+        convert_batch(values, |&v| Self {
+            value: Self::mont_mul_nat(&Nat::from_u64(v), &Self::R2),
+            _phantom: PhantomData,
+        })
+    }
+
     /// Convert from little-endian bytes
     pub fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
         if bytes.len() > N * 8 {
@@ -263,6 +384,7 @@ impl<const N: usize, R: FieldReduction<N>> Field for FpGeneric<N, R> {
     const ONE: Self = Self::ONE;
     const MODULUS: &'static str = R::MODULUS_STR;
     const MODULUS_BITS: u32 = R::MODULUS_BITS;
+    const TWO_ADICITY: u32 = R::TWO_ADICITY;
 
     fn zero() -> Self {
         Self::ZERO
@@ -294,6 +416,26 @@ impl<const N: usize, R: FieldReduction<N>> Field for FpGeneric<N, R> {
     fn invert(&self) -> Option<Self> {
         self.invert()
     }
+
+    fn order_limbs() -> Vec<u64> {
+        R::MODULUS.limbs.to_vec()
+    }
+
+    fn root_of_unity() -> Self {
+        Self::to_montgomery(R::ROOT_OF_UNITY)
+    }
+
+    fn batch_invert(elements: &mut [Self]) {
+        // One inversion per chunk is cheap next to the chunk's 3(n-1)
+        // multiplications, and lets long vectors spread over all cores.
+        if elements.len() < BATCH_INVERT_PARALLEL_THRESHOLD {
+            Self::batch_invert_serial(elements);
+        } else {
+            elements
+                .par_chunks_mut(BATCH_INVERT_CHUNK_SIZE)
+                .for_each(Self::batch_invert_serial);
+        }
+    }
 }
 
 impl<const N: usize, R: FieldReduction<N>> ConstantTimeEq for FpGeneric<N, R> {
@@ -370,6 +512,21 @@ impl<const N: usize, R: FieldReduction<N>> MulAssign for FpGeneric<N, R> {
     }
 }
 
+impl<const N: usize, R: FieldReduction<N>> Div for FpGeneric<N, R> {
+    type Output = Self;
+
+    /// Panics if `rhs` is zero
+    fn div(self, rhs: Self) -> Self {
+        self.mul(rhs.invert().expect("division by zero"))
+    }
+}
+
+impl<const N: usize, R: FieldReduction<N>> From<u64> for FpGeneric<N, R> {
+    fn from(val: u64) -> Self {
+        <Self as Field>::from_u64(val)
+    }
+}
+
 impl<const N: usize, R: FieldReduction<N>> Neg for FpGeneric<N, R> {
     type Output = Self;
 
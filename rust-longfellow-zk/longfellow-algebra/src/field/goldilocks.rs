@@ -0,0 +1,72 @@
+use crate::field::fp_generic::{FieldReduction, FpGeneric};
+use crate::nat::{self, Limb, Nat};
+
+/// Goldilocks field: p = 2^64 - 2^32 + 1
+pub type Goldilocks = FpGeneric<1, GoldilocksReduce>;
+
+#[derive(Clone, Copy)]
+pub struct GoldilocksReduce;
+
+impl FieldReduction<1> for GoldilocksReduce {
+    const MODULUS: Nat<1> = Nat { limbs: [0xffffffff00000001] };
+    
+    const MODULUS_STR: &'static str = "0xffffffff00000001";
+    const MODULUS_BITS: u32 = 64;
+    
+    // 2^64 mod p
+    const R: Nat<1> = Nat { limbs: [0x00000000ffffffff] };
+    
+    // 2^128 mod p
+    const R2: Nat<1> = Nat { limbs: [0xfffffffe00000001] };
+    
+    // -p^(-1) mod 2^64
+    const INV: Limb = 0xfffffffeffffffff;
+    
+    // 7^t where p - 1 = 2^32 * t
+    const TWO_ADICITY: u32 = 32;
+    const ROOT_OF_UNITY: Nat<1> = Nat { limbs: [0x185629dcda58878c] };
+    
+    fn reduction_step(a: &mut [Limb], mprime: Limb, modulus: &Nat<1>) {
+        let k = a[0].wrapping_mul(mprime);
+        let (lo, hi) = nat::mul_wide(modulus.limbs[0], k);
+        let (_, c) = nat::add_with_carry(a[0], lo, 0);
+        a[0] = a[1].wrapping_add(hi + c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Field;
+
+    #[test]
+    fn test_goldilocks_arithmetic() {
+        let a = Goldilocks::from_u64(5);
+        let b = Goldilocks::from_u64(7);
+        assert_eq!(a * b, Goldilocks::from_u64(35));
+        assert_eq!(a - b + b, a);
+        
+        // Products near p^2 overflow the limb before the final subtraction
+        let minus_one = -Goldilocks::one();
+        assert_eq!(minus_one * minus_one, Goldilocks::one());
+        let big = Goldilocks::from_u64(0xffffffff00000000);
+        assert_eq!(big, minus_one);
+        let x = Goldilocks::from_u64(0xfedcba9876543210);
+        let expected = (0xfedcba9876543210u128 * 0xfedcba9876543210u128 % 0xffffffff00000001u128) as u64;
+        assert_eq!(x * x, Goldilocks::from_u64(expected));
+        
+        assert_eq!(x * x.invert().unwrap(), Goldilocks::one());
+        assert!(Goldilocks::zero().invert().is_none());
+    }
+
+    #[test]
+    fn test_goldilocks_root_of_unity() {
+        // Order exactly 2^32
+        let mut root = Goldilocks::root_of_unity();
+        for _ in 0..31 {
+            root = root.square();
+        }
+        assert_eq!(root, -Goldilocks::one());
+        assert!(Goldilocks::get_root_of_unity(33).is_none());
+    }
+}
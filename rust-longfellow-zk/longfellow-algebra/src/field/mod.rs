@@ -1,9 +1,138 @@
+/// Operator and comparison impls shared by the Fp2/Fp4/Fp6 tower types, which
+/// provide `add_impl`, `sub_impl`, `mul_impl` and `neg_impl`.
+macro_rules! impl_extension_ops {
+    ($ty:ident, $config:ident) => {
+        impl<F: Field, C: $config<F>> Default for $ty<F, C> {
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+
+        impl<F: Field, C: $config<F>> PartialEq for $ty<F, C> {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other).into()
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Eq for $ty<F, C> {}
+
+        impl<F: Field, C: $config<F>> Add for $ty<F, C> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                self.add_impl(&rhs)
+            }
+        }
+
+        impl<F: Field, C: $config<F>> AddAssign for $ty<F, C> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = self.add_impl(&rhs);
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Sub for $ty<F, C> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                self.sub_impl(&rhs)
+            }
+        }
+
+        impl<F: Field, C: $config<F>> SubAssign for $ty<F, C> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = self.sub_impl(&rhs);
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Mul for $ty<F, C> {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                self.mul_impl(&rhs)
+            }
+        }
+
+        impl<F: Field, C: $config<F>> MulAssign for $ty<F, C> {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = self.mul_impl(&rhs);
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Neg for $ty<F, C> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                self.neg_impl()
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Add<&Self> for $ty<F, C> {
+            type Output = Self;
+
+            fn add(self, rhs: &Self) -> Self {
+                self.add_impl(rhs)
+            }
+        }
+
+        impl<F: Field, C: $config<F>> AddAssign<&Self> for $ty<F, C> {
+            fn add_assign(&mut self, rhs: &Self) {
+                *self = self.add_impl(rhs);
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Sub<&Self> for $ty<F, C> {
+            type Output = Self;
+
+            fn sub(self, rhs: &Self) -> Self {
+                self.sub_impl(rhs)
+            }
+        }
+
+        impl<F: Field, C: $config<F>> SubAssign<&Self> for $ty<F, C> {
+            fn sub_assign(&mut self, rhs: &Self) {
+                *self = self.sub_impl(rhs);
+            }
+        }
+
+        impl<F: Field, C: $config<F>> Mul<&Self> for $ty<F, C> {
+            type Output = Self;
+
+            fn mul(self, rhs: &Self) -> Self {
+                self.mul_impl(rhs)
+            }
+        }
+
+        impl<F: Field, C: $config<F>> MulAssign<&Self> for $ty<F, C> {
+            fn mul_assign(&mut self, rhs: &Self) {
+                *self = self.mul_impl(rhs);
+            }
+        }
+    };
+}
+
 pub mod asm_support;
+pub mod bls12_381_fr;
+pub mod bn254_fr;
 pub mod fp_generic;
+pub mod goldilocks;
 pub mod fp128;
+pub mod fp2;
+pub mod fp4;
+pub mod fp6;
+pub mod fp31;
 
+pub use bls12_381_fr::Bls12_381Fr;
+pub use bn254_fr::Bn254Fr;
 pub use fp_generic::FpGeneric;
+pub use goldilocks::Goldilocks;
 pub use fp128::Fp128;
+pub use fp2::{Fp2, Fp2Config};
+pub use fp4::{Fp4, Fp4Config};
+pub use fp6::{Fp6, Fp6Config};
+pub use fp31::{
+    BabyBear, BabyBear4, BabyBearFp4, BabyBearFp6, BabyBearTower, Fp31, Fp31Ext, Mersenne31,
+    Mersenne31Ext3, Mersenne31Fp2, Mersenne31Fp4, Mersenne31Fp6, Mersenne31Tower,
+};
 
 #[cfg(test)]
 mod test_montgomery;
\ No newline at end of file
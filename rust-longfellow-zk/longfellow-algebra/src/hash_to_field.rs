@@ -0,0 +1,119 @@
+//! Hashing arbitrary messages to field elements
+//!
+//! Follows RFC 9380: the message is expanded with `expand_message_xmd`
+//! over SHA-256 to `Field::uniform_bytes_len()` bytes, which are then
+//! reduced into the field by `Field::from_uniform_bytes`. The extra 16
+//! bytes beyond the canonical width keep the reduction bias below 2^-128.
+
+use sha2::{Digest, Sha256};
+
+/// Bytes of slack added to the canonical width before reducing
+pub const SECURITY_BYTES: usize = 16;
+
+const SHA256_OUTPUT: usize = 32;
+const SHA256_BLOCK: usize = 64;
+
+/// `expand_message_xmd` from RFC 9380 section 5.3.1, instantiated with
+/// SHA-256. Domain tags longer than 255 bytes are first hashed as the RFC
+/// prescribes.
+///
+/// Panics if `len` exceeds 255 * 32 bytes.
+pub fn expand_message_xmd(domain: &[u8], msg: &[u8], len: usize) -> Vec<u8> {
+    let ell = len.div_ceil(SHA256_OUTPUT);
+    assert!(
+        ell <= 255 && len <= u16::MAX as usize,
+        "expand_message_xmd output length too large"
+    );
+
+    let oversize;
+    let domain = if domain.len() > 255 {
+        oversize = Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(domain)
+            .finalize();
+        &oversize[..]
+    } else {
+        domain
+    };
+    let domain_len = [domain.len() as u8];
+
+    let b0 = Sha256::new()
+        .chain_update([0u8; SHA256_BLOCK])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(domain)
+        .chain_update(domain_len)
+        .finalize();
+
+    let mut out = Vec::with_capacity(ell * SHA256_OUTPUT);
+    let mut prev = [0u8; SHA256_OUTPUT];
+    for i in 1..=ell {
+        let mut input = [0u8; SHA256_OUTPUT];
+        for (x, (a, b)) in input.iter_mut().zip(b0.iter().zip(prev.iter())) {
+            *x = a ^ b;
+        }
+
+        let bi = Sha256::new()
+            .chain_update(input)
+            .chain_update([i as u8])
+            .chain_update(domain)
+            .chain_update(domain_len)
+            .finalize();
+
+        prev.copy_from_slice(&bi);
+        out.extend_from_slice(&bi);
+    }
+
+    out.truncate(len);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Field;
+    use crate::{BabyBear4, Fp128, Mersenne31Fp2};
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_expand_message_xmd_rfc_vectors() {
+        // RFC 9380 appendix K.1
+        assert_eq!(
+            hex(&expand_message_xmd(DST, b"", 0x20)),
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"
+        );
+        assert_eq!(
+            hex(&expand_message_xmd(DST, b"abc", 0x20)),
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"
+        );
+        assert_eq!(expand_message_xmd(DST, b"abc", 0x80).len(), 0x80);
+    }
+
+    #[test]
+    fn test_hash_to_field_is_deterministic_and_separated() {
+        let a = Fp128::hash_to_field(b"domain-a", b"message");
+        assert_eq!(a, Fp128::hash_to_field(b"domain-a", b"message"));
+        assert_ne!(a, Fp128::hash_to_field(b"domain-b", b"message"));
+        assert_ne!(a, Fp128::hash_to_field(b"domain-a", b"other"));
+
+        let e = BabyBear4::hash_to_field(b"domain-a", b"message");
+        assert_ne!(e, BabyBear4::from_u64(0));
+        let t = Mersenne31Fp2::hash_to_field(b"domain-a", b"message");
+        assert_eq!(t, Mersenne31Fp2::hash_to_field(b"domain-a", b"message"));
+    }
+
+    #[test]
+    fn test_from_uniform_bytes_reduces() {
+        // 2^128 = 2^108 - 1 mod p
+        let mut bytes = vec![0u8; 17];
+        bytes[0] = 1;
+        let expected = Fp128::from_u64(1 << 54).square() - Fp128::one();
+        assert_eq!(Fp128::from_uniform_bytes(&bytes), expected);
+    }
+}
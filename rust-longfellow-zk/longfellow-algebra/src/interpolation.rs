@@ -10,21 +10,32 @@ pub fn lagrange_interpolate<F: Field>(
     }
 
     let n = points.len();
+
+    // Denominators prod_{j != i} (x_i - x_j), inverted together
+    let mut denoms = vec![F::one(); n];
+    for i in 0..n {
+        for j in 0..n {
+            if i != j {
+                denoms[i] *= points[i].0 - points[j].0;
+            }
+        }
+        if denoms[i] == F::zero() {
+            return Err(LongfellowError::ArithmeticError(
+                "Division by zero in interpolation".to_string(),
+            ));
+        }
+    }
+    F::batch_invert(&mut denoms);
+
     let mut result = Polynomial::zero();
 
     for i in 0..n {
-        let mut term = Polynomial::constant(points[i].1);
+        let mut term = Polynomial::constant(points[i].1 * denoms[i]);
         
         for j in 0..n {
             if i != j {
                 let num = Polynomial::new(vec![-points[j].0, F::one()]);
-                let denom = points[i].0 - points[j].0;
-                let denom_inv = denom.invert().ok_or_else(|| {
-                    LongfellowError::ArithmeticError("Division by zero in interpolation".to_string())
-                })?;
-                
                 term = term * num;
-                term.scale(denom_inv);
             }
         }
         
@@ -49,14 +60,23 @@ pub fn newton_interpolate<F: Field>(
         divided_differences[i][0] = points[i].1;
     }
 
+    let mut denom_invs = Vec::with_capacity(n);
     for j in 1..n {
+        denom_invs.clear();
         for i in 0..n - j {
-            let num = divided_differences[i + 1][j - 1] - divided_differences[i][j - 1];
             let denom = evaluation_points[i + j] - evaluation_points[i];
-            let denom_inv = denom.invert().ok_or_else(|| {
-                LongfellowError::ArithmeticError("Division by zero in Newton interpolation".to_string())
-            })?;
-            divided_differences[i][j] = num * denom_inv;
+            if denom == F::zero() {
+                return Err(LongfellowError::ArithmeticError(
+                    "Division by zero in Newton interpolation".to_string(),
+                ));
+            }
+            denom_invs.push(denom);
+        }
+        F::batch_invert(&mut denom_invs);
+
+        for i in 0..n - j {
+            let num = divided_differences[i + 1][j - 1] - divided_differences[i][j - 1];
+            divided_differences[i][j] = num * denom_invs[i];
         }
     }
 
@@ -72,14 +92,16 @@ pub fn barycentric_weights<F: Field>(evaluation_points: &[F]) -> Result<Vec<F>>
     for i in 0..n {
         for j in 0..n {
             if i != j {
-                let diff = evaluation_points[i] - evaluation_points[j];
-                let diff_inv = diff.invert().ok_or_else(|| {
-                    LongfellowError::ArithmeticError("Duplicate evaluation points".to_string())
-                })?;
-                weights[i] *= diff_inv;
+                weights[i] *= evaluation_points[i] - evaluation_points[j];
             }
         }
+        if weights[i] == F::zero() {
+            return Err(LongfellowError::ArithmeticError(
+                "Duplicate evaluation points".to_string(),
+            ));
+        }
     }
+    F::batch_invert(&mut weights);
 
     Ok(weights)
 }
@@ -102,15 +124,15 @@ pub fn barycentric_interpolate<F: Field>(
         }
     }
 
+    // x differs from every node, so none of these are zero
+    let mut diff_invs: Vec<F> = evaluation_points.iter().map(|&xi| x - xi).collect();
+    F::batch_invert(&mut diff_invs);
+
     let mut numerator = F::zero();
     let mut denominator = F::zero();
 
     for i in 0..evaluation_points.len() {
-        let diff = x - evaluation_points[i];
-        let diff_inv = diff.invert().ok_or_else(|| {
-            LongfellowError::ArithmeticError("Division by zero".to_string())
-        })?;
-        let term = weights[i] * diff_inv;
+        let term = weights[i] * diff_invs[i];
         numerator += term * values[i];
         denominator += term;
     }
@@ -122,6 +144,93 @@ pub fn barycentric_interpolate<F: Field>(
     Ok(numerator * denom_inv)
 }
 
+/// Barycentric evaluator for the fixed domain {0, 1, ..., n-1}.
+///
+/// Sumcheck round polynomials are sent as evaluations on a small domain
+/// such as {0, 1, 2, 3}; the weights depend only on the domain, so they are
+/// computed once and each evaluation costs O(n) plus one batched inversion.
+#[derive(Clone, Debug)]
+pub struct FixedDomainInterpolator<F: Field> {
+    points: Vec<F>,
+    weights: Vec<F>,
+}
+
+impl<F: Field> FixedDomainInterpolator<F> {
+    /// Interpolator over {0, 1, ..., num_points - 1}
+    pub fn new(num_points: usize) -> Result<Self> {
+        if num_points == 0 {
+            return Err(LongfellowError::InvalidParameter(
+                "Interpolation domain must be non-empty".to_string(),
+            ));
+        }
+
+        let points: Vec<F> = (0..num_points).map(|i| F::from_u64(i as u64)).collect();
+        let weights = barycentric_weights(&points)?;
+
+        Ok(Self { points, weights })
+    }
+
+    /// Interpolator for cubic round polynomials, domain {0, 1, 2, 3}
+    ///
+    /// Fails in characteristic 2 or 3, where those points are not distinct.
+    pub fn cubic() -> Result<Self> {
+        Self::new(4)
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    /// Evaluate the polynomial given by `evals` on the domain at `x`
+    pub fn evaluate(&self, evals: &[F], x: F) -> Result<F> {
+        Ok(self.evaluate_at(evals, &[x])?[0])
+    }
+
+    /// Evaluate the polynomial given by `evals` on the domain at every
+    /// challenge, sharing one batched inversion across all of them
+    pub fn evaluate_at(&self, evals: &[F], challenges: &[F]) -> Result<Vec<F>> {
+        let n = self.points.len();
+        if evals.len() != n {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Expected {} evaluations, got {}",
+                n,
+                evals.len()
+            )));
+        }
+
+        // First barycentric form: p(x) = l(x) * sum_i w_i y_i / (x - x_i)
+        let mut diffs = Vec::with_capacity(n * challenges.len());
+        for &x in challenges {
+            diffs.extend(self.points.iter().map(|&xi| x - xi));
+        }
+        F::batch_invert(&mut diffs);
+
+        let results = challenges
+            .iter()
+            .zip(diffs.chunks(n))
+            .map(|(&x, diff_invs)| {
+                if let Some(i) = self.points.iter().position(|&xi| xi == x) {
+                    return evals[i];
+                }
+
+                let mut vanishing = F::one();
+                let mut sum = F::zero();
+                for i in 0..n {
+                    vanishing *= x - self.points[i];
+                    sum += self.weights[i] * evals[i] * diff_invs[i];
+                }
+                vanishing * sum
+            })
+            .collect();
+
+        Ok(results)
+    }
+}
+
 pub fn multipoint_evaluate<F: Field>(
     poly: &Polynomial<F>,
     points: &[F],
@@ -337,4 +446,39 @@ mod tests {
         assert_eq!(poly.evaluate(&TestField(1)), TestField(2));
         assert_eq!(poly.evaluate(&TestField(2)), TestField(5));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_barycentric_interpolation() {
+        let xs = vec![TestField(0), TestField(1), TestField(2), TestField(3)];
+        // f(x) = x^2 + 1
+        let ys = vec![TestField(1), TestField(2), TestField(5), TestField(10)];
+        let weights = barycentric_weights(&xs).unwrap();
+
+        assert_eq!(
+            barycentric_interpolate(&xs, &ys, &weights, TestField(7)).unwrap(),
+            TestField(50)
+        );
+        assert_eq!(
+            barycentric_interpolate(&xs, &ys, &weights, TestField(2)).unwrap(),
+            TestField(5)
+        );
+        assert!(barycentric_weights(&[TestField(4), TestField(4)]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_domain_interpolator() {
+        let interp = FixedDomainInterpolator::<TestField>::cubic().unwrap();
+        // f(x) = x^3 + 2x + 3 on {0, 1, 2, 3}
+        let f = |x: u64| TestField::from_u64(x * x * x + 2 * x + 3);
+        let evals: Vec<TestField> = (0..4).map(f).collect();
+
+        let challenges = [TestField(2), TestField(10), TestField(57), TestField(100)];
+        let results = interp.evaluate_at(&evals, &challenges).unwrap();
+        for (c, r) in challenges.iter().zip(results.iter()) {
+            assert_eq!(*r, f(c.0));
+        }
+
+        assert_eq!(interp.evaluate(&evals, TestField(5)).unwrap(), f(5));
+        assert!(interp.evaluate_at(&evals[..3], &challenges).is_err());
+    }
+}
@@ -1,8 +1,12 @@
 pub mod field;
 pub mod fft;
 pub mod fft_simd;
+pub mod fft_planner;
 pub mod polynomial;
+pub mod multilinear;
 pub mod interpolation;
+pub mod hash_to_field;
+pub mod sqrt;
 pub mod reed_solomon;
 pub mod convolution;
 pub mod reed_solomon_advanced;
@@ -17,4 +21,5 @@ pub mod traits;
 
 pub use field::*;
 pub use polynomial::*;
+pub use multilinear::*;
 pub use traits::*;
\ No newline at end of file
@@ -0,0 +1,362 @@
+//! Multilinear polynomials over the boolean hypercube
+//!
+//! A polynomial in `num_vars` variables is stored by its evaluations on
+//! {0,1}^num_vars. Variable 0 is the most significant bit of the hypercube
+//! index, so binding variable 0 folds the lower half of the table with the
+//! upper half, matching the layout used by sumcheck and `Dense::bind`.
+
+use crate::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use std::collections::BTreeMap;
+
+/// Dense table of all 2^num_vars evaluations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseMultilinear<F: Field> {
+    num_vars: usize,
+    evals: Vec<F>,
+}
+
+/// Non-zero evaluations only, keyed by hypercube index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseMultilinear<F: Field> {
+    num_vars: usize,
+    entries: BTreeMap<usize, F>,
+}
+
+/// Multilinear polynomial with a dense or sparse backend
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MultilinearPolynomial<F: Field> {
+    Dense(DenseMultilinear<F>),
+    Sparse(SparseMultilinear<F>),
+}
+
+fn check_var(var: usize, num_vars: usize) -> Result<()> {
+    if var >= num_vars {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Variable {} out of range for {} variables",
+            var, num_vars
+        )));
+    }
+    Ok(())
+}
+
+fn check_point<F>(point: &[F], num_vars: usize) -> Result<()> {
+    if point.len() != num_vars {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Point dimension {} doesn't match polynomial vars {}",
+            point.len(),
+            num_vars
+        )));
+    }
+    Ok(())
+}
+
+impl<F: Field> DenseMultilinear<F> {
+    pub fn new(evals: Vec<F>) -> Result<Self> {
+        let n = evals.len();
+        if n == 0 || !n.is_power_of_two() {
+            return Err(LongfellowError::InvalidParameter(
+                "Evaluation count must be a power of 2".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            num_vars: n.trailing_zeros() as usize,
+            evals,
+        })
+    }
+
+    /// eq(x, r) = prod_i (x_i r_i + (1 - x_i)(1 - r_i))
+    pub fn eq(r: &[F]) -> Self {
+        let mut evals = vec![F::one()];
+        for &r_i in r {
+            let one_minus = F::one() - r_i;
+            evals = evals
+                .iter()
+                .flat_map(|&e| [e * one_minus, e * r_i])
+                .collect();
+        }
+
+        Self {
+            num_vars: r.len(),
+            evals,
+        }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn evals(&self) -> &[F] {
+        &self.evals
+    }
+
+    pub fn into_evals(self) -> Vec<F> {
+        self.evals
+    }
+
+    /// Fix variable `var` to `r`, removing it
+    pub fn bind_var(&mut self, var: usize, r: F) -> Result<()> {
+        check_var(var, self.num_vars)?;
+
+        let stride = 1 << (self.num_vars - 1 - var);
+        let mut bound = Vec::with_capacity(self.evals.len() / 2);
+        for block in self.evals.chunks(2 * stride) {
+            let (lo, hi) = block.split_at(stride);
+            bound.extend(lo.iter().zip(hi.iter()).map(|(&l, &h)| l + r * (h - l)));
+        }
+
+        self.evals = bound;
+        self.num_vars -= 1;
+        Ok(())
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> Result<F> {
+        check_point(point, self.num_vars)?;
+
+        let mut table = self.evals.clone();
+        let mut len = table.len();
+        for &r in point {
+            len /= 2;
+            for k in 0..len {
+                let l = table[k];
+                table[k] = l + r * (table[k + len] - l);
+            }
+        }
+
+        Ok(table[0])
+    }
+
+    pub fn sum_over_hypercube(&self) -> F {
+        self.evals.iter().fold(F::zero(), |acc, &x| acc + x)
+    }
+}
+
+impl<F: Field> SparseMultilinear<F> {
+    /// Build from (index, value) pairs; repeated indices are summed
+    pub fn new(num_vars: usize, entries: impl IntoIterator<Item = (usize, F)>) -> Result<Self> {
+        let mut map = BTreeMap::new();
+        for (idx, v) in entries {
+            if idx >> num_vars != 0 {
+                return Err(LongfellowError::InvalidParameter(format!(
+                    "Index {} out of range for {} variables",
+                    idx, num_vars
+                )));
+            }
+            *map.entry(idx).or_insert_with(F::zero) += v;
+        }
+        map.retain(|_, v| *v != F::zero());
+
+        Ok(Self {
+            num_vars,
+            entries: map,
+        })
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Number of non-zero evaluations
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (usize, F)> + '_ {
+        self.entries.iter().map(|(&i, &v)| (i, v))
+    }
+
+    /// Fix variable `var` to `r`, removing it
+    pub fn bind_var(&mut self, var: usize, r: F) -> Result<()> {
+        check_var(var, self.num_vars)?;
+
+        let shift = self.num_vars - 1 - var;
+        let low_mask = (1 << shift) - 1;
+        let one_minus = F::one() - r;
+
+        let mut bound = BTreeMap::new();
+        for (&idx, &v) in &self.entries {
+            let new_idx = ((idx >> (shift + 1)) << shift) | (idx & low_mask);
+            let scaled = if (idx >> shift) & 1 == 1 { v * r } else { v * one_minus };
+            *bound.entry(new_idx).or_insert_with(F::zero) += scaled;
+        }
+        bound.retain(|_, v| *v != F::zero());
+
+        self.entries = bound;
+        self.num_vars -= 1;
+        Ok(())
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> Result<F> {
+        check_point(point, self.num_vars)?;
+
+        let mut result = F::zero();
+        for (&idx, &v) in &self.entries {
+            let mut term = v;
+            for (i, &r) in point.iter().enumerate() {
+                if (idx >> (self.num_vars - 1 - i)) & 1 == 1 {
+                    term *= r;
+                } else {
+                    term *= F::one() - r;
+                }
+            }
+            result += term;
+        }
+
+        Ok(result)
+    }
+
+    pub fn to_dense(&self) -> DenseMultilinear<F> {
+        let mut evals = vec![F::zero(); 1 << self.num_vars];
+        for (&idx, &v) in &self.entries {
+            evals[idx] = v;
+        }
+        DenseMultilinear {
+            num_vars: self.num_vars,
+            evals,
+        }
+    }
+
+    pub fn sum_over_hypercube(&self) -> F {
+        self.entries.values().fold(F::zero(), |acc, &x| acc + x)
+    }
+}
+
+impl<F: Field> MultilinearPolynomial<F> {
+    /// Dense polynomial from its 2^n evaluations
+    pub fn dense(evals: Vec<F>) -> Result<Self> {
+        Ok(Self::Dense(DenseMultilinear::new(evals)?))
+    }
+
+    /// Sparse polynomial from its non-zero evaluations
+    pub fn sparse(num_vars: usize, entries: impl IntoIterator<Item = (usize, F)>) -> Result<Self> {
+        Ok(Self::Sparse(SparseMultilinear::new(num_vars, entries)?))
+    }
+
+    /// The equality polynomial eq(., r)
+    pub fn eq(r: &[F]) -> Self {
+        Self::Dense(DenseMultilinear::eq(r))
+    }
+
+    pub fn num_vars(&self) -> usize {
+        match self {
+            Self::Dense(p) => p.num_vars(),
+            Self::Sparse(p) => p.num_vars(),
+        }
+    }
+
+    pub fn bind_var(&mut self, var: usize, r: F) -> Result<()> {
+        match self {
+            Self::Dense(p) => p.bind_var(var, r),
+            Self::Sparse(p) => p.bind_var(var, r),
+        }
+    }
+
+    /// Bind variables 0, 1, ... in order to the given values
+    pub fn bind_prefix(&mut self, values: &[F]) -> Result<()> {
+        for &r in values {
+            self.bind_var(0, r)?;
+        }
+        Ok(())
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> Result<F> {
+        match self {
+            Self::Dense(p) => p.evaluate(point),
+            Self::Sparse(p) => p.evaluate(point),
+        }
+    }
+
+    pub fn sum_over_hypercube(&self) -> F {
+        match self {
+            Self::Dense(p) => p.sum_over_hypercube(),
+            Self::Sparse(p) => p.sum_over_hypercube(),
+        }
+    }
+
+    pub fn to_dense(&self) -> DenseMultilinear<F> {
+        match self {
+            Self::Dense(p) => p.clone(),
+            Self::Sparse(p) => p.to_dense(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fp128;
+
+    fn f(v: u64) -> Fp128 {
+        Fp128::from_u64(v)
+    }
+
+    #[test]
+    fn test_dense_evaluate_on_hypercube() {
+        let evals: Vec<Fp128> = (0..8).map(|i| f(i * 3 + 1)).collect();
+        let poly = DenseMultilinear::new(evals.clone()).unwrap();
+
+        for idx in 0..8usize {
+            let point: Vec<Fp128> = (0..3).map(|i| f(((idx >> (2 - i)) & 1) as u64)).collect();
+            assert_eq!(poly.evaluate(&point).unwrap(), evals[idx]);
+        }
+        assert!(poly.evaluate(&[f(1)]).is_err());
+        assert!(DenseMultilinear::new(vec![f(1); 3]).is_err());
+    }
+
+    #[test]
+    fn test_bind_var_matches_evaluate() {
+        let evals: Vec<Fp128> = (0..16).map(|i| f(i * i + 7)).collect();
+        let point = [f(5), f(11), f(2), f(9)];
+        let expected = DenseMultilinear::new(evals.clone()).unwrap().evaluate(&point).unwrap();
+
+        // Bind a middle variable first, then the rest in order
+        for var in 0..4 {
+            let mut poly = MultilinearPolynomial::dense(evals.clone()).unwrap();
+            poly.bind_var(var, point[var]).unwrap();
+            let rest: Vec<Fp128> = (0..4).filter(|&i| i != var).map(|i| point[i]).collect();
+            assert_eq!(poly.evaluate(&rest).unwrap(), expected);
+        }
+
+        let mut poly = MultilinearPolynomial::dense(evals).unwrap();
+        poly.bind_prefix(&point).unwrap();
+        assert_eq!(poly.num_vars(), 0);
+        assert_eq!(poly.evaluate(&[]).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_sparse_matches_dense() {
+        let entries = vec![(1usize, f(4)), (6, f(9)), (13, f(2)), (6, f(1))];
+        let sparse = MultilinearPolynomial::sparse(4, entries).unwrap();
+        let dense = MultilinearPolynomial::Dense(sparse.to_dense());
+        assert_eq!(sparse.sum_over_hypercube(), f(16));
+
+        let point = [f(3), f(8), f(21), f(5)];
+        assert_eq!(sparse.evaluate(&point).unwrap(), dense.evaluate(&point).unwrap());
+
+        let mut sparse_bound = sparse.clone();
+        let mut dense_bound = dense.clone();
+        sparse_bound.bind_var(2, f(7)).unwrap();
+        dense_bound.bind_var(2, f(7)).unwrap();
+        assert_eq!(sparse_bound.to_dense(), dense_bound.to_dense());
+
+        assert!(MultilinearPolynomial::sparse(2, vec![(4usize, f(1))]).is_err());
+    }
+
+    #[test]
+    fn test_eq_polynomial() {
+        let r = [f(3), f(10), f(6)];
+        let eq = MultilinearPolynomial::eq(&r);
+        assert_eq!(eq.sum_over_hypercube(), Fp128::one());
+
+        // eq(r, r') = prod (r_i r'_i + (1 - r_i)(1 - r'_i))
+        let s = [f(4), f(1), f(12)];
+        let expected = r
+            .iter()
+            .zip(s.iter())
+            .fold(Fp128::one(), |acc, (&a, &b)| {
+                acc * (a * b + (Fp128::one() - a) * (Fp128::one() - b))
+            });
+        assert_eq!(eq.evaluate(&s).unwrap(), expected);
+    }
+}
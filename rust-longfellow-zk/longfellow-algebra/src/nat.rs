@@ -1,5 +1,8 @@
+use longfellow_core::{LongfellowError, Result};
 use std::cmp::Ordering;
 use std::fmt::{self, Debug};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::Arc;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroize;
 
@@ -119,10 +122,11 @@ impl<const N: usize> Nat<N> {
         for i in 0..N {
             let mut carry = 0;
             for j in 0..N {
-                let (lo, hi) = mul_wide(self.limbs[i], other.limbs[j]);
-                let (sum, c) = add_with_carry(result[i + j], lo, carry);
+                // result + a * b + carry < 2^(2 * LIMB_BITS), so one
+                // multiply-accumulate never loses a carry
+                let (sum, hi) = mac_with_carry(result[i + j], self.limbs[i], other.limbs[j], carry);
                 result[i + j] = sum;
-                carry = hi + c;
+                carry = hi;
             }
             result[i + N] = carry;
         }
@@ -283,4 +287,515 @@ pub fn mac_with_carry(a: Limb, b: Limb, c: Limb, carry: Limb) -> (Limb, Limb) {
         let wide = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
         (wide as Limb, (wide >> Limb::BITS) as Limb)
     }
-}
\ No newline at end of file
+}
+
+/// Montgomery parameters for an odd modulus chosen at runtime
+///
+/// `Nat<N>` and `FpGeneric` fix the modulus at compile time. RSA moduli,
+/// the P-384 base field and similar non-native fields are only known when
+/// a circuit is built, so `DynModulus` carries the same Montgomery
+/// constants as heap-allocated limbs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DynModulus {
+    modulus: Vec<Limb>,
+    /// -m^-1 mod 2^LIMB_BITS
+    inv: Limb,
+    /// R^2 mod m with R = 2^(LIMB_BITS * len)
+    r2: Vec<Limb>,
+}
+
+/// Element of Z/mZ for a runtime modulus, kept in Montgomery form
+#[derive(Clone)]
+pub struct DynFp {
+    value: Vec<Limb>,
+    modulus: Arc<DynModulus>,
+}
+
+impl DynModulus {
+    /// Modulus from little-endian limbs; it must be odd and greater than 1
+    pub fn new(limbs: &[Limb]) -> Result<Arc<Self>> {
+        let mut modulus = limbs.to_vec();
+        while modulus.len() > 1 && modulus.last() == Some(&0) {
+            modulus.pop();
+        }
+
+        if modulus.is_empty() || modulus[0] & 1 == 0 || (modulus.len() == 1 && modulus[0] == 1) {
+            return Err(LongfellowError::InvalidParameter(
+                "Modulus must be odd and greater than 1".to_string(),
+            ));
+        }
+
+        // Newton iteration doubles the correct low bits of m^-1 each step
+        let mut inv: Limb = 1;
+        for _ in 0..Limb::BITS.trailing_zeros() {
+            inv = inv.wrapping_mul((2 as Limb).wrapping_sub(modulus[0].wrapping_mul(inv)));
+        }
+
+        // R^2 mod m by doubling 1 modulo m, 2 * LIMB_BITS * len times
+        let n = modulus.len();
+        let mut r2 = vec![0 as Limb; n];
+        r2[0] = 1;
+        for _ in 0..2 * Limb::BITS as usize * n {
+            let carry = shl1_limbs(&mut r2);
+            if carry != 0 || !lt_limbs(&r2, &modulus) {
+                sub_limbs(&mut r2, &modulus);
+            }
+        }
+
+        Ok(Arc::new(Self {
+            modulus,
+            inv: inv.wrapping_neg(),
+            r2,
+        }))
+    }
+
+    /// Modulus from little-endian bytes
+    pub fn from_bytes_le(bytes: &[u8]) -> Result<Arc<Self>> {
+        Self::new(&limbs_from_bytes_le(bytes))
+    }
+
+    /// Modulus from big-endian bytes, the usual encoding of RSA moduli
+    pub fn from_bytes_be(bytes: &[u8]) -> Result<Arc<Self>> {
+        let le: Vec<u8> = bytes.iter().rev().copied().collect();
+        Self::from_bytes_le(&le)
+    }
+
+    pub fn limbs(&self) -> &[Limb] {
+        &self.modulus
+    }
+
+    pub fn num_limbs(&self) -> usize {
+        self.modulus.len()
+    }
+
+    pub fn bits(&self) -> usize {
+        let top = *self.modulus.last().unwrap();
+        (self.modulus.len() - 1) * Limb::BITS as usize + (Limb::BITS - top.leading_zeros()) as usize
+    }
+
+    /// CIOS Montgomery product a * b * R^-1 mod m, for a * b < m * R
+    fn mont_mul(&self, a: &[Limb], b: &[Limb]) -> Vec<Limb> {
+        let n = self.modulus.len();
+        let mut t = vec![0 as Limb; n + 2];
+
+        for &ai in &a[..n] {
+            let mut carry = 0;
+            for j in 0..n {
+                (t[j], carry) = mac_with_carry(t[j], ai, b[j], carry);
+            }
+            let (sum, c) = add_with_carry(t[n], carry, 0);
+            t[n] = sum;
+            t[n + 1] = c;
+
+            let m = t[0].wrapping_mul(self.inv);
+            let (_, mut carry) = mac_with_carry(t[0], m, self.modulus[0], 0);
+            for j in 1..n {
+                (t[j - 1], carry) = mac_with_carry(t[j], m, self.modulus[j], carry);
+            }
+            let (sum, c) = add_with_carry(t[n], carry, 0);
+            t[n - 1] = sum;
+            t[n] = t[n + 1] + c;
+        }
+
+        let overflow = t[n] != 0;
+        t.truncate(n);
+        if overflow || !lt_limbs(&t, &self.modulus) {
+            sub_limbs(&mut t, &self.modulus);
+        }
+        t
+    }
+}
+
+impl Debug for DynModulus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DynModulus({})", limbs_to_hex(&self.modulus))
+    }
+}
+
+impl DynFp {
+    /// Reduce a value of at most `num_limbs()` little-endian limbs
+    pub fn from_limbs(modulus: &Arc<DynModulus>, limbs: &[Limb]) -> Result<Self> {
+        let n = modulus.num_limbs();
+        if limbs.iter().skip(n).any(|&l| l != 0) {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Value has more than {} limbs",
+                n
+            )));
+        }
+
+        let mut value = limbs.to_vec();
+        value.resize(n, 0);
+        // value * R^2 * R^-1 is correct for any value below R
+        Ok(Self {
+            value: modulus.mont_mul(&value, &modulus.r2),
+            modulus: modulus.clone(),
+        })
+    }
+
+    pub fn from_bytes_le(modulus: &Arc<DynModulus>, bytes: &[u8]) -> Result<Self> {
+        Self::from_limbs(modulus, &limbs_from_bytes_le(bytes))
+    }
+
+    pub fn from_bytes_be(modulus: &Arc<DynModulus>, bytes: &[u8]) -> Result<Self> {
+        let le: Vec<u8> = bytes.iter().rev().copied().collect();
+        Self::from_bytes_le(modulus, &le)
+    }
+
+    pub fn from_u64(modulus: &Arc<DynModulus>, val: u64) -> Self {
+        let limbs = limbs_from_bytes_le(&val.to_le_bytes());
+        if limbs.len() <= modulus.num_limbs() {
+            return Self::from_limbs(modulus, &limbs).unwrap();
+        }
+
+        // Only reachable with 32-bit limbs and a single-limb modulus
+        let hi = Self::from_limbs(modulus, &limbs[1..]).unwrap();
+        let lo = Self::from_limbs(modulus, &limbs[..1]).unwrap();
+        (0..Limb::BITS).fold(hi, |acc, _| &acc + &acc) + &lo
+    }
+
+    pub fn zero(modulus: &Arc<DynModulus>) -> Self {
+        Self {
+            value: vec![0; modulus.num_limbs()],
+            modulus: modulus.clone(),
+        }
+    }
+
+    pub fn one(modulus: &Arc<DynModulus>) -> Self {
+        Self::from_limbs(modulus, &[1]).unwrap()
+    }
+
+    pub fn modulus(&self) -> &Arc<DynModulus> {
+        &self.modulus
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.iter().all(|&l| l == 0)
+    }
+
+    /// Canonical value as little-endian limbs
+    pub fn to_limbs(&self) -> Vec<Limb> {
+        let mut one = vec![0 as Limb; self.modulus.num_limbs()];
+        one[0] = 1;
+        self.modulus.mont_mul(&self.value, &one)
+    }
+
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        self.to_limbs().iter().flat_map(|l| l.to_le_bytes()).collect()
+    }
+
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    /// Exponentiation by a public little-endian exponent, e.g. an RSA
+    /// public exponent. Variable time.
+    pub fn pow_vartime(&self, exp: &[Limb]) -> Self {
+        let mut result = Self::one(&self.modulus);
+        for &limb in exp.iter().rev() {
+            for bit in (0..Limb::BITS).rev() {
+                result = result.square();
+                if (limb >> bit) & 1 == 1 {
+                    result = &result * self;
+                }
+            }
+        }
+        result
+    }
+
+    /// Inverse by the binary extended Euclidean algorithm, so composite
+    /// moduli work too. None if the value shares a factor with the modulus.
+    pub fn invert(&self) -> Option<Self> {
+        let m = &self.modulus.modulus;
+        let n = m.len();
+
+        let mut a = self.to_limbs();
+        let mut b = m.clone();
+        let mut u = vec![0 as Limb; n];
+        u[0] = 1;
+        let mut v = vec![0 as Limb; n];
+
+        while a.iter().any(|&l| l != 0) {
+            if a[0] & 1 == 0 {
+                shr1_limbs(&mut a, 0);
+                let carry = if u[0] & 1 == 1 { add_limbs(&mut u, m) } else { 0 };
+                shr1_limbs(&mut u, carry);
+            } else {
+                if lt_limbs(&a, &b) {
+                    std::mem::swap(&mut a, &mut b);
+                    std::mem::swap(&mut u, &mut v);
+                }
+                sub_limbs(&mut a, &b);
+                if sub_limbs(&mut u, &v) != 0 {
+                    add_limbs(&mut u, m);
+                }
+            }
+        }
+
+        if b[0] != 1 || b[1..].iter().any(|&l| l != 0) {
+            return None;
+        }
+        Some(Self::from_limbs(&self.modulus, &v).unwrap())
+    }
+
+    fn check_modulus(&self, other: &Self) {
+        assert!(
+            Arc::ptr_eq(&self.modulus, &other.modulus) || self.modulus == other.modulus,
+            "DynFp operands have different moduli"
+        );
+    }
+}
+
+impl PartialEq for DynFp {
+    fn eq(&self, other: &Self) -> bool {
+        self.modulus == other.modulus && self.value == other.value
+    }
+}
+
+impl Eq for DynFp {}
+
+impl Debug for DynFp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DynFp({})", limbs_to_hex(&self.to_limbs()))
+    }
+}
+
+impl Add<&DynFp> for &DynFp {
+    type Output = DynFp;
+
+    fn add(self, rhs: &DynFp) -> DynFp {
+        self.check_modulus(rhs);
+        let mut value = self.value.clone();
+        let carry = add_limbs(&mut value, &rhs.value);
+        if carry != 0 || !lt_limbs(&value, &self.modulus.modulus) {
+            sub_limbs(&mut value, &self.modulus.modulus);
+        }
+        DynFp {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+impl Sub<&DynFp> for &DynFp {
+    type Output = DynFp;
+
+    fn sub(self, rhs: &DynFp) -> DynFp {
+        self.check_modulus(rhs);
+        let mut value = self.value.clone();
+        if sub_limbs(&mut value, &rhs.value) != 0 {
+            add_limbs(&mut value, &self.modulus.modulus);
+        }
+        DynFp {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+impl Mul<&DynFp> for &DynFp {
+    type Output = DynFp;
+
+    fn mul(self, rhs: &DynFp) -> DynFp {
+        self.check_modulus(rhs);
+        DynFp {
+            value: self.modulus.mont_mul(&self.value, &rhs.value),
+            modulus: self.modulus.clone(),
+        }
+    }
+}
+
+impl Neg for &DynFp {
+    type Output = DynFp;
+
+    fn neg(self) -> DynFp {
+        &DynFp::zero(&self.modulus) - self
+    }
+}
+
+impl Add<&DynFp> for DynFp {
+    type Output = DynFp;
+
+    fn add(self, rhs: &DynFp) -> DynFp {
+        &self + rhs
+    }
+}
+
+impl Sub<&DynFp> for DynFp {
+    type Output = DynFp;
+
+    fn sub(self, rhs: &DynFp) -> DynFp {
+        &self - rhs
+    }
+}
+
+impl Mul<&DynFp> for DynFp {
+    type Output = DynFp;
+
+    fn mul(self, rhs: &DynFp) -> DynFp {
+        &self * rhs
+    }
+}
+
+impl Neg for DynFp {
+    type Output = DynFp;
+
+    fn neg(self) -> DynFp {
+        -&self
+    }
+}
+
+fn limbs_from_bytes_le(bytes: &[u8]) -> Vec<Limb> {
+    const LIMB_BYTES: usize = std::mem::size_of::<Limb>();
+    bytes
+        .chunks(LIMB_BYTES)
+        .map(|chunk| {
+            let mut buf = [0u8; LIMB_BYTES];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Limb::from_le_bytes(buf)
+        })
+        .collect()
+}
+
+fn limbs_to_hex(limbs: &[Limb]) -> String {
+    let mut s = String::from("0x");
+    for limb in limbs.iter().rev() {
+        s.push_str(&format!("{:0width$x}", limb, width = Limb::BITS as usize / 4));
+    }
+    s
+}
+
+fn lt_limbs(a: &[Limb], b: &[Limb]) -> bool {
+    for (x, y) in a.iter().zip(b.iter()).rev() {
+        if x != y {
+            return x < y;
+        }
+    }
+    false
+}
+
+fn add_limbs(a: &mut [Limb], b: &[Limb]) -> Limb {
+    let mut carry = 0;
+    for (x, &y) in a.iter_mut().zip(b.iter()) {
+        (*x, carry) = add_with_carry(*x, y, carry);
+    }
+    carry
+}
+
+fn sub_limbs(a: &mut [Limb], b: &[Limb]) -> Limb {
+    let mut borrow = 0;
+    for (x, &y) in a.iter_mut().zip(b.iter()) {
+        let (d1, b1) = x.overflowing_sub(y);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        *x = d2;
+        borrow = (b1 as Limb) | (b2 as Limb);
+    }
+    borrow
+}
+
+fn shl1_limbs(a: &mut [Limb]) -> Limb {
+    let mut carry = 0;
+    for x in a.iter_mut() {
+        let next = *x >> (Limb::BITS - 1);
+        *x = (*x << 1) | carry;
+        carry = next;
+    }
+    carry
+}
+
+/// Shift right by one, shifting `top` in as the new most significant bit
+fn shr1_limbs(a: &mut [Limb], top: Limb) {
+    let mut carry = top;
+    for x in a.iter_mut().rev() {
+        let next = *x & 1;
+        *x = (*x >> 1) | (carry << (Limb::BITS - 1));
+        carry = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_wide_keeps_top_carry() {
+        let a = Nat::<2>::new([Limb::MAX; 2]);
+        let (wide, _) = a.mul_wide(&a);
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        assert_eq!(wide, vec![1, 0, Limb::MAX - 1, Limb::MAX]);
+
+        // (2^128 - 1) y = (y - 1) 2^128 + (2^128 - y)
+        let y = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+        let (wide, _) = a.mul_wide(&Nat::<2>::new([y as Limb, (y >> 64) as Limb]));
+        let (lo, hi) = (y.wrapping_neg(), y - 1);
+        assert_eq!(wide, vec![lo as Limb, (lo >> 64) as Limb, hi as Limb, (hi >> 64) as Limb]);
+    }
+
+    #[test]
+    fn test_dyn_fp_matches_u128_arithmetic() {
+        // Composite, like an RSA modulus
+        let m: u128 = 1_000_000_007 * 998_244_353;
+        let modulus = DynModulus::new(&[m as Limb]).unwrap();
+        assert_eq!(modulus.bits(), 128 - m.leading_zeros() as usize);
+
+        let (x, y) = (123_456_789_012_345u128 % m, 987_654_321_098_765u128 % m);
+        let a = DynFp::from_u64(&modulus, x as u64);
+        let b = DynFp::from_u64(&modulus, y as u64);
+        let canon = |v: &DynFp| v.to_limbs()[0] as u128;
+
+        assert_eq!(canon(&(&a + &b)), (x + y) % m);
+        assert_eq!(canon(&(&a - &b)), (x + m - y) % m);
+        assert_eq!(canon(&(&a * &b)), (x * y) % m);
+        assert_eq!(canon(&-&a), m - x);
+
+        let inv = a.invert().unwrap();
+        assert_eq!(&inv * &a, DynFp::one(&modulus));
+
+        // Shares the factor 1_000_000_007 with m
+        assert!(DynFp::from_u64(&modulus, 1_000_000_007).invert().is_none());
+    }
+
+    #[test]
+    fn test_dyn_fp_p384() {
+        // p = 2^384 - 2^128 - 2^96 + 2^32 - 1
+        let p: [Limb; 6] = [
+            0x00000000ffffffff,
+            0xffffffff00000000,
+            0xfffffffffffffffe,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+        ];
+        let modulus = DynModulus::new(&p).unwrap();
+        assert_eq!(modulus.bits(), 384);
+
+        let a = DynFp::from_u64(&modulus, 0xdeadbeef);
+        let mut p_minus_1 = p;
+        p_minus_1[0] -= 1;
+        assert_eq!(a.pow_vartime(&p_minus_1), DynFp::one(&modulus));
+        assert_eq!(&a.invert().unwrap() * &a, DynFp::one(&modulus));
+
+        // p itself reduces to zero, p - 1 to -1
+        assert!(DynFp::from_limbs(&modulus, &p).unwrap().is_zero());
+        assert_eq!(
+            DynFp::from_limbs(&modulus, &p_minus_1).unwrap(),
+            -DynFp::one(&modulus)
+        );
+
+        let bytes = a.to_bytes_be();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(DynFp::from_bytes_be(&modulus, &bytes).unwrap(), a);
+    }
+
+    #[test]
+    fn test_dyn_modulus_rejects_even() {
+        assert!(DynModulus::new(&[10]).is_err());
+        assert!(DynModulus::new(&[1]).is_err());
+        assert!(DynModulus::new(&[]).is_err());
+    }
+}
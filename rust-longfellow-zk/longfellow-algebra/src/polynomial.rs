@@ -1,5 +1,7 @@
 use crate::traits::Field;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use longfellow_core::{LongfellowError, Result};
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Polynomial<F: Field> {
@@ -81,6 +83,100 @@ impl<F: Field> Polynomial<F> {
         }
         self.trim();
     }
+
+    /// Vanishing polynomial prod_i (x - points[i])
+    pub fn vanishing(points: &[F]) -> Self {
+        let mut coeffs = vec![F::one()];
+        for &point in points {
+            coeffs.push(F::zero());
+            for k in (1..coeffs.len()).rev() {
+                let lower = coeffs[k - 1];
+                coeffs[k] = lower - point * coeffs[k];
+            }
+            coeffs[0] = -point * coeffs[0];
+        }
+        Self::new(coeffs)
+    }
+
+    pub fn leading_coefficient(&self) -> Option<F> {
+        self.coefficients.last().copied()
+    }
+
+    /// Scale so the leading coefficient is one; zero stays zero
+    pub fn monic(&self) -> Self {
+        match self.leading_coefficient().and_then(|lead| lead.invert()) {
+            Some(lead_inv) => {
+                let mut result = self.clone();
+                result.scale(lead_inv);
+                result
+            }
+            None => self.clone(),
+        }
+    }
+
+    /// Euclidean division: returns (q, r) with self = q * divisor + r and
+    /// deg(r) < deg(divisor)
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self)> {
+        let divisor_degree = divisor.degree().ok_or_else(|| {
+            LongfellowError::ArithmeticError("Division by zero polynomial".to_string())
+        })?;
+        let lead_inv = divisor.coefficients[divisor_degree].invert().ok_or_else(|| {
+            LongfellowError::ArithmeticError("Cannot invert leading coefficient".to_string())
+        })?;
+
+        let mut remainder = self.clone();
+        remainder.trim();
+
+        let quotient_len = remainder
+            .degree()
+            .map_or(0, |d| (d + 1).saturating_sub(divisor_degree));
+        let mut quotient = vec![F::zero(); quotient_len];
+
+        while let Some(rem_degree) = remainder.degree() {
+            if rem_degree < divisor_degree {
+                break;
+            }
+
+            let coeff = remainder.coefficients[rem_degree] * lead_inv;
+            let shift = rem_degree - divisor_degree;
+            quotient[shift] = coeff;
+
+            for (i, d) in divisor.coefficients.iter().enumerate() {
+                remainder.coefficients[shift + i] -= coeff * d;
+            }
+
+            remainder.trim();
+        }
+
+        Ok((Self::new(quotient), remainder))
+    }
+
+    /// Monic greatest common divisor; gcd(0, 0) = 0
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        a.trim();
+        b.trim();
+
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b).expect("divisor is non-zero");
+            a = std::mem::replace(&mut b, r);
+        }
+
+        a.monic()
+    }
+
+    /// Formal derivative
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| c * F::from_u64(i as u64))
+                .collect(),
+        )
+    }
 }
 
 impl<F: Field> Add for Polynomial<F> {
@@ -171,6 +267,175 @@ impl<F: Field> MulAssign<F> for Polynomial<F> {
     }
 }
 
+/// Sparse polynomial stored as (coefficient, exponent) terms.
+///
+/// Terms are kept sorted by exponent with duplicates merged and zero
+/// coefficients dropped, so equal polynomials compare equal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparsePoly<F: Field> {
+    terms: Vec<(F, usize)>,
+}
+
+impl<F: Field> SparsePoly<F> {
+    pub fn new(terms: Vec<(F, usize)>) -> Self {
+        let mut merged: BTreeMap<usize, F> = BTreeMap::new();
+        for (coeff, exp) in terms {
+            *merged.entry(exp).or_insert_with(F::zero) += coeff;
+        }
+
+        Self {
+            terms: merged
+                .into_iter()
+                .filter(|(_, c)| *c != F::zero())
+                .map(|(e, c)| (c, e))
+                .collect(),
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self { terms: vec![] }
+    }
+
+    pub fn monomial(coeff: F, exp: usize) -> Self {
+        Self::new(vec![(coeff, exp)])
+    }
+
+    pub fn terms(&self) -> &[(F, usize)] {
+        &self.terms
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.last().map(|&(_, e)| e)
+    }
+
+    /// Evaluate by stepping x^e between consecutive exponents, so the cost
+    /// is O(t log(degree)) rather than O(degree)
+    ///
+    /// The exponents are part of the polynomial's public shape, so the
+    /// variable-time `pow_vartime` is used for the steps.
+    pub fn evaluate(&self, x: &F) -> F {
+        let mut result = F::zero();
+        let mut power = F::one();
+        let mut prev_exp = 0;
+
+        for &(coeff, exp) in &self.terms {
+            power *= x.pow_vartime(&[(exp - prev_exp) as u64]);
+            prev_exp = exp;
+            result += coeff * power;
+        }
+
+        result
+    }
+
+    pub fn from_dense(poly: &Polynomial<F>) -> Self {
+        Self {
+            terms: poly
+                .coefficients
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c != F::zero())
+                .map(|(e, &c)| (c, e))
+                .collect(),
+        }
+    }
+
+    pub fn to_dense(&self) -> Polynomial<F> {
+        let mut coeffs = vec![F::zero(); self.degree().map_or(0, |d| d + 1)];
+        for &(coeff, exp) in &self.terms {
+            coeffs[exp] = coeff;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<F: Field> From<&Polynomial<F>> for SparsePoly<F> {
+    fn from(poly: &Polynomial<F>) -> Self {
+        Self::from_dense(poly)
+    }
+}
+
+impl<F: Field> From<&SparsePoly<F>> for Polynomial<F> {
+    fn from(poly: &SparsePoly<F>) -> Self {
+        poly.to_dense()
+    }
+}
+
+impl<F: Field> Add for SparsePoly<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self + &rhs
+    }
+}
+
+impl<F: Field> Add<&Self> for SparsePoly<F> {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self {
+        let mut terms = self.terms;
+        terms.extend_from_slice(&rhs.terms);
+        Self::new(terms)
+    }
+}
+
+impl<F: Field> Sub for SparsePoly<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self - &rhs
+    }
+}
+
+impl<F: Field> Sub<&Self> for SparsePoly<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self {
+        let mut terms = self.terms;
+        terms.extend(rhs.terms.iter().map(|&(c, e)| (-c, e)));
+        Self::new(terms)
+    }
+}
+
+impl<F: Field> Neg for SparsePoly<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            terms: self.terms.into_iter().map(|(c, e)| (-c, e)).collect(),
+        }
+    }
+}
+
+impl<F: Field> Mul for SparsePoly<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self * &rhs
+    }
+}
+
+impl<F: Field> Mul<&Self> for SparsePoly<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: &Self) -> Self {
+        let mut terms = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+        for &(a, i) in &self.terms {
+            for &(b, j) in &rhs.terms {
+                terms.push((a * b, i + j));
+            }
+        }
+        Self::new(terms)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PolynomialBasis {
     Monomial,
@@ -325,7 +590,8 @@ mod tests {
             if self.0 == 0 {
                 None
             } else {
-                Some(TestField(1))
+                // Fermat: x^(p - 2) = x^-1 mod 101
+                Some(self.pow_vartime(&[99]))
             }
         }
     }
@@ -464,4 +730,84 @@ mod tests {
         let result = poly.evaluate(&x);
         assert_eq!(result, TestField(86));
     }
-}
\ No newline at end of file
+
+    fn poly(coeffs: &[u64]) -> Polynomial<TestField> {
+        Polynomial::new(coeffs.iter().map(|&c| TestField::from_u64(c)).collect())
+    }
+
+    #[test]
+    fn test_polynomial_div_rem() {
+        // (x^3 + 2x + 5) = (x^2 + x + 3)(x - 1) + 8
+        let a = poly(&[5, 2, 0, 1]);
+        let b = poly(&[100, 1]);
+        let (q, r) = a.div_rem(&b).unwrap();
+        assert_eq!(q, poly(&[3, 1, 1]));
+        assert_eq!(r, poly(&[8]));
+        assert_eq!(q * &b + r, a);
+
+        assert!(a.div_rem(&Polynomial::zero()).is_err());
+        let (q, r) = b.div_rem(&a).unwrap();
+        assert!(q.is_zero());
+        assert_eq!(r, b);
+    }
+
+    #[test]
+    fn test_polynomial_gcd() {
+        // (x - 1)(x - 2) and (x - 1)(x - 3) share x - 1
+        let a = Polynomial::vanishing(&[TestField(1), TestField(2)]);
+        let b = Polynomial::vanishing(&[TestField(1), TestField(3)]);
+        assert_eq!(a.gcd(&b), poly(&[100, 1]));
+
+        let scaled = a.clone() * poly(&[7]);
+        assert_eq!(scaled.gcd(&Polynomial::zero()), a);
+        assert!(Polynomial::<TestField>::zero().gcd(&Polynomial::zero()).is_zero());
+    }
+
+    #[test]
+    fn test_polynomial_derivative_and_vanishing() {
+        // d/dx (4x^3 + 3x + 1) = 12x^2 + 3
+        assert_eq!(poly(&[1, 3, 0, 4]).derivative(), poly(&[3, 0, 12]));
+        assert!(poly(&[9]).derivative().is_zero());
+
+        let points = [TestField(2), TestField(5), TestField(7)];
+        let z = Polynomial::vanishing(&points);
+        assert_eq!(z.degree(), Some(3));
+        for p in &points {
+            assert_eq!(z.evaluate(p), TestField::zero());
+        }
+        assert_ne!(z.evaluate(&TestField(3)), TestField::zero());
+    }
+
+    #[test]
+    fn test_sparse_poly_arithmetic() {
+        // a = 3x^1000 + 2, b = x^1000 - x^3
+        let a = SparsePoly::new(vec![(TestField(3), 1000), (TestField(2), 0)]);
+        let b = SparsePoly::new(vec![(TestField(1), 1000), (TestField(100), 3)]);
+
+        let sum = a.clone() + &b;
+        assert_eq!(sum.terms(), &[(TestField(2), 0), (TestField(100), 3), (TestField(4), 1000)]);
+        assert!((a.clone() - &a).is_zero());
+
+        // a * b = 3x^2000 - 3x^1003 + 2x^1000 - 2x^3
+        let prod = a.clone() * &b;
+        assert_eq!(prod.num_terms(), 4);
+        assert_eq!(prod.degree(), Some(2000));
+
+        let x = TestField(7);
+        assert_eq!(prod.evaluate(&x), a.evaluate(&x) * b.evaluate(&x));
+        assert_eq!((-a.clone()).evaluate(&x), -a.evaluate(&x));
+    }
+
+    #[test]
+    fn test_sparse_dense_conversion() {
+        let dense = poly(&[4, 0, 0, 9, 0, 1]);
+        let sparse = SparsePoly::from(&dense);
+        assert_eq!(sparse.num_terms(), 3);
+        assert_eq!(Polynomial::from(&sparse), dense);
+        assert_eq!(sparse.evaluate(&TestField(6)), dense.evaluate(&TestField(6)));
+
+        let merged = SparsePoly::new(vec![(TestField(50), 2), (TestField(51), 2)]);
+        assert!(merged.is_zero());
+        assert!(merged.to_dense().is_zero());
+    }
+}
@@ -1,13 +1,15 @@
 use crate::fft::FFT;
+use crate::fft_planner::shared_plan;
 use crate::polynomial::Polynomial;
 use crate::traits::Field;
 use longfellow_core::{LongfellowError, Result};
+use std::sync::Arc;
 
 pub struct ReedSolomon<F: Field> {
     n: usize,
     k: usize,
     omega: F,
-    fft: FFT<F>,
+    fft: Arc<FFT<F>>,
 }
 
 impl<F: Field> ReedSolomon<F> {
@@ -24,7 +26,8 @@ impl<F: Field> ReedSolomon<F> {
             ));
         }
 
-        let fft = FFT::new(n, omega)?;
+        // Twiddles are shared with every other code of the same length
+        let fft = shared_plan(n, omega)?;
 
         Ok(Self { n, k, omega, fft })
     }
@@ -62,7 +65,7 @@ impl<F: Field> ReedSolomon<F> {
             systematic[idx] = encoded[idx];
             
             for j in 0..self.k {
-                systematic[idx] -= data[j] * omega_power.pow(&[j as u64]);
+                systematic[idx] -= data[j] * omega_power.pow_vartime(&[j as u64]);
             }
             
             omega_power *= omega_inv;
@@ -71,6 +74,12 @@ impl<F: Field> ReedSolomon<F> {
         Ok(systematic)
     }
 
+    /// Recover the message from a codeword with known erased positions.
+    ///
+    /// With erasure locator E(x) = prod_{e} (x - omega^e), the product
+    /// E(x) * m(x) has degree < n and vanishes on the erasures, so its
+    /// evaluations are known everywhere. One inverse FFT gives its
+    /// coefficients, and dividing out E recovers m.
     pub fn decode(&self, codeword: &[F], erasures: &[usize]) -> Result<Vec<F>> {
         if codeword.len() != self.n {
             return Err(LongfellowError::InvalidParameter(format!(
@@ -80,21 +89,101 @@ impl<F: Field> ReedSolomon<F> {
             )));
         }
 
-        if erasures.len() > self.n - self.k {
+        let mut erased = vec![false; self.n];
+        for &i in erasures {
+            if i >= self.n {
+                return Err(LongfellowError::InvalidParameter(format!(
+                    "Erasure position {} out of range for n={}",
+                    i, self.n
+                )));
+            }
+            erased[i] = true;
+        }
+        let erased_positions: Vec<usize> = (0..self.n).filter(|&i| erased[i]).collect();
+
+        if erased_positions.len() > self.n - self.k {
             return Err(LongfellowError::InvalidParameter(
                 "Too many erasures for recovery".to_string(),
             ));
         }
 
-        let mut received = codeword.to_vec();
-        for &i in erasures {
-            received[i] = F::zero();
+        let domain = self.fft.twiddle_factors();
+
+        let mut locator = Polynomial::one();
+        for &e in &erased_positions {
+            locator = locator * Polynomial::new(vec![-domain[e], F::one()]);
         }
 
-        let mut values = received.clone();
-        self.fft.inverse(&mut values)?;
+        let mut locator_evals = locator.coefficients.clone();
+        locator_evals.resize(self.n, F::zero());
+        self.fft.forward(&mut locator_evals)?;
 
-        Ok(values[..self.k].to_vec())
+        let mut product: Vec<F> = (0..self.n)
+            .map(|i| {
+                if erased[i] {
+                    F::zero()
+                } else {
+                    codeword[i] * locator_evals[i]
+                }
+            })
+            .collect();
+        self.fft.inverse(&mut product)?;
+
+        let mut message = product;
+        for &e in &erased_positions {
+            message = divide_by_linear(&message, domain[e]);
+        }
+        message.resize(self.k, F::zero());
+
+        Ok(message)
+    }
+
+    /// Recover the message from a codeword with up to (n - k) / 2 corrupted
+    /// positions at unknown locations, using Gao's decoder.
+    pub fn correct_errors(&self, received: &[F]) -> Result<Vec<F>> {
+        if received.len() != self.n {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Codeword length {} does not match n={}",
+                received.len(),
+                self.n
+            )));
+        }
+
+        // g0 = prod_i (x - omega^i) = x^n - 1
+        let mut vanishing = vec![F::zero(); self.n + 1];
+        vanishing[0] = -F::one();
+        vanishing[self.n] = F::one();
+
+        // g1 interpolates the received word over the domain
+        let mut interpolant = received.to_vec();
+        self.fft.inverse(&mut interpolant)?;
+
+        // Partial extended Euclid on (g0, g1), stopping once deg(r) < (n + k) / 2
+        let mut r_prev = Polynomial::new(vanishing);
+        let mut r = Polynomial::new(interpolant);
+        let mut v_prev = Polynomial::zero();
+        let mut v = Polynomial::one();
+
+        while let Some(deg) = r.degree() {
+            if 2 * deg < self.n + self.k {
+                break;
+            }
+            let (q, rem) = r_prev.div_rem(&r)?;
+            r_prev = std::mem::replace(&mut r, rem);
+            let next_v = v_prev - q * &v;
+            v_prev = std::mem::replace(&mut v, next_v);
+        }
+
+        let (message, rem) = r.div_rem(&v)?;
+        if !rem.is_zero() || message.degree().is_some_and(|d| d >= self.k) {
+            return Err(LongfellowError::ArithmeticError(
+                "Too many errors to decode".to_string(),
+            ));
+        }
+
+        let mut message = message.coefficients;
+        message.resize(self.k, F::zero());
+        Ok(message)
     }
 
     pub fn verify(&self, codeword: &[F]) -> Result<bool> {
@@ -189,35 +278,23 @@ fn polynomial_division<F: Field>(
     dividend: &Polynomial<F>,
     divisor: &Polynomial<F>,
 ) -> Result<Polynomial<F>> {
-    if divisor.is_zero() {
-        return Err(LongfellowError::ArithmeticError(
-            "Division by zero polynomial".to_string(),
-        ));
-    }
-
-    let mut remainder = dividend.clone();
-    let divisor_degree = divisor.degree().unwrap();
-    let divisor_lead = divisor.coefficients.last().unwrap();
-    let divisor_lead_inv = divisor_lead.invert().ok_or_else(|| {
-        LongfellowError::ArithmeticError("Cannot invert leading coefficient".to_string())
-    })?;
-
-    while let Some(rem_degree) = remainder.degree() {
-        if rem_degree < divisor_degree {
-            break;
-        }
-
-        let coeff = *remainder.coefficients.last().unwrap() * divisor_lead_inv;
-        let shift = rem_degree - divisor_degree;
-
-        for i in 0..=divisor_degree {
-            remainder.coefficients[shift + i] -= coeff * divisor.coefficients[i];
-        }
+    dividend.div_rem(divisor).map(|(_, remainder)| remainder)
+}
 
-        remainder.trim();
+/// Exact division of a coefficient vector by (x - root), dropping the
+/// (zero) remainder
+fn divide_by_linear<F: Field>(coeffs: &[F], root: F) -> Vec<F> {
+    if coeffs.len() <= 1 {
+        return vec![];
     }
 
-    Ok(remainder)
+    let mut quotient = vec![F::zero(); coeffs.len() - 1];
+    let mut carry = F::zero();
+    for i in (1..coeffs.len()).rev() {
+        carry = coeffs[i] + root * carry;
+        quotient[i - 1] = carry;
+    }
+    quotient
 }
 
 #[cfg(test)]
@@ -403,4 +480,38 @@ mod tests {
         let remainder = polynomial_division(&dividend, &divisor).unwrap();
         assert_eq!(remainder.coefficients.len(), 1);
     }
-}
\ No newline at end of file
+
+    fn test_code() -> ReedSolomon<TestField> {
+        // 64 = 5^12 has order 8 mod 97
+        ReedSolomon::new(8, 4, TestField(64)).unwrap()
+    }
+
+    #[test]
+    fn test_erasure_decoding() {
+        let rs = test_code();
+        let data = vec![TestField(3), TestField(1), TestField(4), TestField(1)];
+        let mut codeword = rs.encode(&data).unwrap();
+
+        let erasures = [0, 2, 5, 7];
+        for &i in &erasures {
+            codeword[i] = TestField(42);
+        }
+
+        assert_eq!(rs.decode(&codeword, &erasures).unwrap(), data);
+        assert!(rs.decode(&codeword, &[0, 1, 2, 3, 4]).is_err());
+        assert!(rs.decode(&codeword, &[8]).is_err());
+    }
+
+    #[test]
+    fn test_error_correction() {
+        let rs = test_code();
+        let data = vec![TestField(9), TestField(2), TestField(6), TestField(5)];
+        let codeword = rs.encode(&data).unwrap();
+        assert_eq!(rs.correct_errors(&codeword).unwrap(), data);
+
+        let mut corrupted = codeword.clone();
+        corrupted[1] = corrupted[1] + TestField(1);
+        corrupted[6] = corrupted[6] + TestField(50);
+        assert_eq!(rs.correct_errors(&corrupted).unwrap(), data);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::traits::Field;
-use crate::convolution::{FftConvolutionFactory, ConvolutionFactory};
+use crate::convolution::{ConvolutionFactory, DirectConvolutionFactory, FftConvolutionFactory};
 use crate::reed_solomon_advanced::{ConvolutionReedSolomon, ConvolutionReedSolomonFactory};
 use crate::reed_solomon_lch14::{LCH14ReedSolomon, LCH14ReedSolomonFactory};
 use longfellow_core::{LongfellowError, Result};
@@ -8,6 +8,9 @@ use longfellow_core::{LongfellowError, Result};
 pub enum UnifiedReedSolomon<F: Field> {
     /// Convolution-based implementation for prime fields
     Convolution(Box<ConvolutionReedSolomon<F, <FftConvolutionFactory<F> as ConvolutionFactory<F>>::Convolver>>),
+    /// Quadratic-time convolution for prime fields whose two-adicity is
+    /// too small for the FFT size
+    Direct(Box<ConvolutionReedSolomon<F, <DirectConvolutionFactory as ConvolutionFactory<F>>::Convolver>>),
     /// LCH14 implementation for binary fields
     Lch14(Box<LCH14ReedSolomon<F>>),
 }
@@ -22,16 +25,20 @@ impl<F: Field> UnifiedReedSolomon<F> {
             Ok(UnifiedReedSolomon::Lch14(Box::new(rs)))
         } else {
             // Use convolution-based for prime fields
-            let omega_provider = |size: usize| {
-                // This should compute proper root of unity for the field
-                // For now, use a placeholder
-                Self::compute_root_of_unity(size)
-            };
-            
-            let conv_factory = FftConvolutionFactory::new(omega_provider);
-            let rs_factory = ConvolutionReedSolomonFactory::new(conv_factory);
-            let rs = rs_factory.make(n, m)?;
-            Ok(UnifiedReedSolomon::Convolution(Box::new(rs)))
+            Self::convolution(n, m)
+        }
+    }
+    
+    /// FFT convolution when the field has a large enough two-adic
+    /// subgroup, direct convolution otherwise
+    fn convolution(n: usize, m: usize) -> Result<Self> {
+        let fft_factory = ConvolutionReedSolomonFactory::new(FftConvolutionFactory::default());
+        match fft_factory.make(n, m) {
+            Ok(rs) => Ok(UnifiedReedSolomon::Convolution(Box::new(rs))),
+            Err(_) => {
+                let direct_factory = ConvolutionReedSolomonFactory::new(DirectConvolutionFactory);
+                Ok(UnifiedReedSolomon::Direct(Box::new(direct_factory.make(n, m)?)))
+            }
         }
     }
     
@@ -39,6 +46,7 @@ impl<F: Field> UnifiedReedSolomon<F> {
     pub fn interpolate(&self, y: &mut [F]) -> Result<()> {
         match self {
             UnifiedReedSolomon::Convolution(rs) => rs.interpolate(y),
+            UnifiedReedSolomon::Direct(rs) => rs.interpolate(y),
             UnifiedReedSolomon::Lch14(rs) => rs.interpolate(y),
         }
     }
@@ -47,16 +55,7 @@ impl<F: Field> UnifiedReedSolomon<F> {
     fn is_binary_field() -> bool {
         F::one() + F::one() == F::zero()
     }
-    
-    /// Compute primitive root of unity for given size
-    fn compute_root_of_unity(size: usize) -> F {
-        // This is field-specific and should be properly implemented
-        // For now, return a placeholder
-        // In practice, this would:
-        // 1. Find a generator of the multiplicative group
-        // 2. Raise it to the power (p-1)/size to get a size-th root of unity
-        F::from_u64(3).pow(&[((F::characteristic() - 1) / size as u64)])
-    }
+
 }
 
 /// Configuration for Reed-Solomon encoding
@@ -105,16 +104,7 @@ impl<F: Field> ReedSolomonFactory<F> {
         let impl_type = self.config.force_implementation.unwrap_or(ReedSolomonImpl::Auto);
         
         match impl_type {
-            ReedSolomonImpl::Convolution => {
-                let omega_provider = |size: usize| {
-                    UnifiedReedSolomon::<F>::compute_root_of_unity(size)
-                };
-                
-                let conv_factory = FftConvolutionFactory::new(omega_provider);
-                let rs_factory = ConvolutionReedSolomonFactory::new(conv_factory);
-                let rs = rs_factory.make(n, m)?;
-                Ok(UnifiedReedSolomon::Convolution(Box::new(rs)))
-            }
+            ReedSolomonImpl::Convolution => UnifiedReedSolomon::convolution(n, m),
             ReedSolomonImpl::Lch14 => {
                 if !UnifiedReedSolomon::<F>::is_binary_field() {
                     return Err(LongfellowError::InvalidParameter(
@@ -158,6 +148,25 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_falls_back_to_direct_convolution() {
+        use crate::field::Mersenne31;
+        
+        // Mersenne31 has two-adicity 1, too small for an FFT of size 16
+        let rs = UnifiedReedSolomon::<Mersenne31>::new(4, 8).unwrap();
+        assert!(matches!(rs, UnifiedReedSolomon::Direct(_)));
+        
+        // p(x) = 2x + 1 extends to p(i) at every point
+        let mut y = vec![Mersenne31::zero(); 8];
+        for (i, v) in y.iter_mut().enumerate().take(4) {
+            *v = Mersenne31::from_u64(2 * i as u64 + 1);
+        }
+        rs.interpolate(&mut y).unwrap();
+        for (i, v) in y.iter().enumerate() {
+            assert_eq!(*v, Mersenne31::from_u64(2 * i as u64 + 1));
+        }
+    }
+    
     #[test]
     fn test_factory_with_config() {
         let config = ReedSolomonConfig {
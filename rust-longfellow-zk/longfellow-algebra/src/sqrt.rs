@@ -0,0 +1,323 @@
+//! Square roots and quadratic residuosity
+//!
+//! Tonelli-Shanks needs the decomposition q - 1 = 2^s * t of the field
+//! order and a fixed non-residue. These are derived once per field from
+//! `Field::order_limbs` and cached process-wide, the same way FFT plans are
+//! shared. Both operations are variable time and must not be applied to
+//! secret values.
+
+use crate::traits::Field;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Precomputed Tonelli-Shanks constants for a field of order q
+#[derive(Clone, Debug)]
+pub struct SqrtConstants<F: Field> {
+    /// s such that q - 1 = 2^s * t with t odd; zero in characteristic two
+    two_adicity: u32,
+    /// (q - 1) / 2, the Euler criterion exponent
+    euler_exp: Vec<u64>,
+    /// (t - 1) / 2
+    trace_exp: Vec<u64>,
+    /// c = z^t for a non-residue z, a primitive 2^s-th root of unity
+    root_of_unity: F,
+    /// q / 2, the inverse of Frobenius, used in characteristic two
+    half_order: Vec<u64>,
+}
+
+impl<F: Field> SqrtConstants<F> {
+    /// Derive the constants from the field order
+    pub fn new() -> Self {
+        let order = F::order_limbs();
+
+        if order[0] & 1 == 0 {
+            // Binary field: squaring is a bijection
+            return Self {
+                two_adicity: 0,
+                euler_exp: vec![],
+                trace_exp: vec![],
+                root_of_unity: F::one(),
+                half_order: shr(&order, 1),
+            };
+        }
+
+        let q_minus_one = sub_one(&order);
+        let two_adicity = trailing_zeros(&q_minus_one);
+        let t = shr(&q_minus_one, two_adicity);
+        let euler_exp = shr(&q_minus_one, 1);
+        let trace_exp = shr(&sub_one(&t), 1);
+
+        // Half of all non-zero elements are non-residues, so hashing a
+        // counter finds one quickly and deterministically, including in
+        // extension fields where every base-field element is a square.
+        let minus_one = -F::one();
+        let non_residue = (0u64..)
+            .map(|i| F::hash_to_field(b"longfellow-sqrt-non-residue", &i.to_le_bytes()))
+            .find(|z| z.pow_vartime(&euler_exp) == minus_one)
+            .expect("field of odd order has non-residues");
+
+        Self {
+            two_adicity,
+            euler_exp,
+            trace_exp,
+            root_of_unity: non_residue.pow_vartime(&t),
+            half_order: vec![],
+        }
+    }
+
+    pub fn two_adicity(&self) -> u32 {
+        self.two_adicity
+    }
+
+    /// The primitive 2^s-th root of unity z^t
+    pub fn root_of_unity(&self) -> F {
+        self.root_of_unity
+    }
+
+    /// 1 for non-zero squares, -1 for non-squares and 0 for zero
+    pub fn legendre(&self, x: &F) -> i8 {
+        if *x == F::zero() {
+            return 0;
+        }
+        if self.two_adicity == 0 {
+            return 1;
+        }
+
+        if x.pow_vartime(&self.euler_exp) == F::one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// A square root of `x`, or None if `x` is not a square
+    pub fn sqrt(&self, x: &F) -> Option<F> {
+        if *x == F::zero() {
+            return Some(F::zero());
+        }
+        if self.two_adicity == 0 {
+            return Some(x.pow_vartime(&self.half_order));
+        }
+
+        // w = x^((t-1)/2), so r = x^((t+1)/2) and b = x^t
+        let w = x.pow_vartime(&self.trace_exp);
+        let mut r = *x * w;
+        let mut b = r * w;
+        let mut c = self.root_of_unity;
+        let mut m = self.two_adicity;
+
+        while b != F::one() {
+            // Least i with b^(2^i) = 1; reaching m means b has order 2^m
+            // and x is a non-residue
+            let mut i = 0;
+            let mut b2 = b;
+            while b2 != F::one() {
+                b2 = b2.square();
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+
+            let mut g = c;
+            for _ in 0..m - i - 1 {
+                g = g.square();
+            }
+            r *= g;
+            c = g.square();
+            b *= c;
+            m = i;
+        }
+
+        Some(r)
+    }
+}
+
+impl<F: Field> Default for SqrtConstants<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type SharedConstants = RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+fn shared_constants() -> &'static SharedConstants {
+    static CONSTANTS: OnceLock<SharedConstants> = OnceLock::new();
+    CONSTANTS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Process-wide square root constants for field `F`
+pub fn sqrt_constants<F: Field>() -> Arc<SqrtConstants<F>> {
+    let key = TypeId::of::<F>();
+
+    if let Some(constants) = shared_constants().read().unwrap().get(&key) {
+        return constants
+            .clone()
+            .downcast::<SqrtConstants<F>>()
+            .expect("constants registered under their own TypeId");
+    }
+
+    // Computed outside the lock: deriving the constants hashes to the field,
+    // and a racing thread at worst computes the same values twice
+    let constants: Arc<dyn Any + Send + Sync> = Arc::new(SqrtConstants::<F>::new());
+    shared_constants()
+        .write()
+        .unwrap()
+        .entry(key)
+        .or_insert(constants)
+        .clone()
+        .downcast::<SqrtConstants<F>>()
+        .expect("constants registered under their own TypeId")
+}
+
+/// Parse a decimal or 0x-prefixed hexadecimal modulus into little-endian
+/// limbs
+pub fn parse_modulus(s: &str) -> Vec<u64> {
+    let (digits, radix) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+
+    let mut limbs = vec![0u64];
+    for ch in digits.chars().filter(|c| !c.is_whitespace() && *c != '_') {
+        let digit = ch.to_digit(radix).expect("invalid digit in field modulus") as u128;
+        let mut carry = digit;
+        for limb in limbs.iter_mut() {
+            let v = (*limb as u128) * radix as u128 + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+        if carry != 0 {
+            limbs.push(carry as u64);
+        }
+    }
+    limbs
+}
+
+/// Schoolbook product of little-endian limb vectors
+pub fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut out = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            let v = (x as u128) * (y as u128) + out[i + j] as u128 + carry;
+            out[i + j] = v as u64;
+            carry = v >> 64;
+        }
+        out[i + b.len()] = carry as u64;
+    }
+    while out.len() > 1 && out.last() == Some(&0) {
+        out.pop();
+    }
+    out
+}
+
+fn sub_one(a: &[u64]) -> Vec<u64> {
+    let mut out = a.to_vec();
+    for limb in out.iter_mut() {
+        let (v, borrow) = limb.overflowing_sub(1);
+        *limb = v;
+        if !borrow {
+            break;
+        }
+    }
+    out
+}
+
+fn trailing_zeros(a: &[u64]) -> u32 {
+    let mut count = 0;
+    for &limb in a {
+        if limb != 0 {
+            return count + limb.trailing_zeros();
+        }
+        count += 64;
+    }
+    count
+}
+
+fn shr(a: &[u64], shift: u32) -> Vec<u64> {
+    let limb_shift = (shift / 64) as usize;
+    let bit_shift = shift % 64;
+    let mut out = vec![0u64; a.len().saturating_sub(limb_shift).max(1)];
+
+    for (i, o) in out.iter_mut().enumerate() {
+        let lo = a.get(i + limb_shift).copied().unwrap_or(0);
+        let hi = a.get(i + limb_shift + 1).copied().unwrap_or(0);
+        *o = if bit_shift == 0 {
+            lo
+        } else {
+            (lo >> bit_shift) | (hi << (64 - bit_shift))
+        };
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BabyBear, Bn254Fr, Fp128, Mersenne31Fp2};
+
+    fn check_sqrt<F: Field>(count: u64) {
+        for i in 1..=count {
+            let x = F::from_u64(i * 7919 + 3);
+            let sq = x.square();
+            assert_eq!(sq.legendre(), 1);
+
+            let root = sq.sqrt().unwrap();
+            assert!(root == x || root == -x);
+        }
+        assert_eq!(F::zero().legendre(), 0);
+        assert_eq!(F::zero().sqrt(), Some(F::zero()));
+    }
+
+    #[test]
+    fn test_parse_modulus() {
+        assert_eq!(parse_modulus("101"), vec![101]);
+        assert_eq!(parse_modulus("0x78000001"), vec![0x78000001]);
+        assert_eq!(
+            parse_modulus("340282366920938463463374607431768211456"),
+            vec![0, 0, 1]
+        );
+        assert_eq!(mul_limbs(&[u64::MAX], &[u64::MAX]), vec![1, u64::MAX - 1]);
+    }
+
+    #[test]
+    fn test_sqrt_prime_fields() {
+        check_sqrt::<BabyBear>(32);
+        check_sqrt::<Fp128>(8);
+        check_sqrt::<Bn254Fr>(4);
+        assert_eq!(sqrt_constants::<Fp128>().two_adicity(), 108);
+        assert_eq!(sqrt_constants::<BabyBear>().two_adicity(), 27);
+    }
+
+    #[test]
+    fn test_derived_root_of_unity_order() {
+        // The Tonelli-Shanks root z^t has the same order as the
+        // precomputed one, though it need not be the same root
+        let c = sqrt_constants::<BabyBear>().root_of_unity();
+        assert_eq!(c.pow_vartime(&[1 << 26]), -BabyBear::one());
+        assert_eq!(BabyBear::root_of_unity().pow_vartime(&[1 << 26]), -BabyBear::one());
+    }
+
+    #[test]
+    fn test_non_residues() {
+        // 59 generates Fp128^*, so it cannot be a square
+        let g = Fp128::from_u64(59);
+        assert_eq!(g.legendre(), -1);
+        assert!(g.sqrt().is_none());
+
+        // Exactly one of x and x * g is a square
+        let x = Fp128::from_u64(1234567);
+        assert_eq!(x.legendre() * (x * g).legendre(), -1);
+    }
+
+    #[test]
+    fn test_sqrt_extension_field() {
+        // -1 is a non-residue in Mersenne31 but a square in its Fp2
+        check_sqrt::<Mersenne31Fp2>(8);
+        let minus_one = -Mersenne31Fp2::one();
+        let i = minus_one.sqrt().unwrap();
+        assert_eq!(i * i, minus_one);
+    }
+}
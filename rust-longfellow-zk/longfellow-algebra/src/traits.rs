@@ -1,9 +1,12 @@
-use longfellow_core::Result;
+use longfellow_core::{LongfellowError, Result};
 use std::fmt::Debug;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroize;
 
+/// Field elements. The `ConstantTimeEq` and `ConditionallySelectable`
+/// bounds let prover code that touches witness values compare and select
+/// without secret-dependent branches; `PartialEq` is for public data.
 pub trait Field:
     Sized
     + Clone
@@ -37,6 +40,12 @@ pub trait Field:
     const MODULUS: &'static str;
     const MODULUS_BITS: u32;
     const CHAR_IS_TWO: bool = false;
+    /// Largest s such that `root_of_unity()` has order 2^s. Zero for
+    /// fields without a precomputed root; the FFT then has no radix-2 domain.
+    const TWO_ADICITY: u32 = 0;
+    /// Bit width of a subfield whose elements have a compact encoding,
+    /// e.g. 16 for GF(2^16) inside GF(2^128). Zero if the field has none.
+    const SUBFIELD_BITS: u32 = 0;
 
     fn zero() -> Self {
         Self::ZERO
@@ -47,13 +56,147 @@ pub trait Field:
     }
 
     fn from_u64(val: u64) -> Self;
+
+    /// The subfield element with the `SUBFIELD_BITS`-bit encoding `bits`
+    fn from_subfield(bits: u64) -> Self {
+        Self::from_u64(bits)
+    }
+
+    /// The compact encoding of a subfield element, or None if the element
+    /// lies outside the subfield
+    fn to_subfield(&self) -> Option<u64> {
+        None
+    }
     
     fn from_bytes_le(bytes: &[u8]) -> Result<Self>;
     
     fn to_bytes_le(&self) -> Vec<u8>;
 
+    /// Width in bytes of the canonical encoding
+    fn canonical_byte_len() -> usize {
+        (Self::MODULUS_BITS as usize).div_ceil(8)
+    }
+
+    /// Fixed-width little-endian encoding of the reduced value. This is the
+    /// encoding to use whenever field elements are hashed or sent over the
+    /// wire, so that every module produces identical bytes.
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.resize(Self::canonical_byte_len(), 0);
+        bytes
+    }
+
+    /// Inverse of `to_canonical_bytes`, rejecting inputs of the wrong width
+    /// and non-reduced encodings
+    fn from_canonical_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::canonical_byte_len() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Canonical encoding must be {} bytes, got {}",
+                Self::canonical_byte_len(),
+                bytes.len()
+            )));
+        }
+
+        let elem = Self::from_bytes_le(bytes)?;
+        if elem.to_canonical_bytes() != bytes {
+            return Err(LongfellowError::InvalidParameter(
+                "Non-canonical field element encoding".to_string(),
+            ));
+        }
+        Ok(elem)
+    }
+
+    /// Number of uniform bytes consumed by `from_uniform_bytes`
+    fn uniform_bytes_len() -> usize {
+        Self::canonical_byte_len() + crate::hash_to_field::SECURITY_BYTES
+    }
+
+    /// Reduce a big-endian byte string into the field. Given
+    /// `uniform_bytes_len()` uniform bytes the result is statistically
+    /// close to uniform. Extension fields reduce each coordinate separately.
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let radix = Self::from_u64(256);
+        bytes.iter().fold(Self::zero(), |acc, &b| {
+            acc * radix + Self::from_u64(b as u64)
+        })
+    }
+
+    /// Hash `msg` to a field element under the domain separation tag
+    /// `domain` (RFC 9380 hash_to_field with expand_message_xmd/SHA-256)
+    fn hash_to_field(domain: &[u8], msg: &[u8]) -> Self {
+        let bytes =
+            crate::hash_to_field::expand_message_xmd(domain, msg, Self::uniform_bytes_len());
+        Self::from_uniform_bytes(&bytes)
+    }
+
     fn invert(&self) -> Option<Self>;
 
+    /// Number of field elements as little-endian limbs. Parsed from
+    /// `MODULUS` by default; extension fields return p^k.
+    fn order_limbs() -> Vec<u64> {
+        crate::sqrt::parse_modulus(Self::MODULUS)
+    }
+
+    /// Legendre symbol: 1 for non-zero squares, -1 for non-squares and 0
+    /// for zero. Variable time.
+    fn legendre(&self) -> i8 {
+        crate::sqrt::sqrt_constants::<Self>().legendre(self)
+    }
+
+    /// Tonelli-Shanks square root, or None for non-squares. Which of the
+    /// two roots is returned is unspecified. Variable time.
+    fn sqrt(&self) -> Option<Self> {
+        crate::sqrt::sqrt_constants::<Self>().sqrt(self)
+    }
+
+    /// A primitive 2^TWO_ADICITY-th root of unity. By default derived from
+    /// the cached Tonelli-Shanks constants; prime fields override it with a
+    /// precomputed value.
+    fn root_of_unity() -> Self {
+        let constants = crate::sqrt::sqrt_constants::<Self>();
+        let mut root = constants.root_of_unity();
+        for _ in Self::TWO_ADICITY..constants.two_adicity() {
+            root = root.square();
+        }
+        root
+    }
+
+    /// A primitive 2^log_size-th root of unity, or None if the field's
+    /// two-adic subgroup is too small
+    fn get_root_of_unity(log_size: u32) -> Option<Self> {
+        if log_size > Self::TWO_ADICITY {
+            return None;
+        }
+        let mut root = Self::root_of_unity();
+        for _ in log_size..Self::TWO_ADICITY {
+            root = root.square();
+        }
+        Some(root)
+    }
+
+    /// Constant-time zero test
+    fn is_zero_ct(&self) -> Choice {
+        self.ct_eq(&Self::ZERO)
+    }
+
+    /// Zero or one selected by `choice` without branching on it
+    fn from_choice(choice: Choice) -> Self {
+        Self::conditional_select(&Self::ZERO, &Self::ONE, choice)
+    }
+
+    /// The low `num_bits` bits of the canonical encoding as zero/one field
+    /// elements, computed without branching on the value. Intended for
+    /// filling witness bit decompositions over prime fields.
+    fn to_bits_le_ct(&self, num_bits: usize) -> Vec<Self> {
+        let bytes = self.to_canonical_bytes();
+        (0..num_bits)
+            .map(|i| {
+                let byte = bytes.get(i / 8).copied().unwrap_or(0);
+                Self::from_choice(Choice::from((byte >> (i % 8)) & 1))
+            })
+            .collect()
+    }
+
     fn square(&self) -> Self {
         *self * self
     }
@@ -62,40 +205,66 @@ pub trait Field:
         *self + self
     }
 
+    /// Exponentiation by a little-endian multi-limb exponent in constant
+    /// time: every bit of every limb costs one squaring and one
+    /// multiplication, and the product is kept with a conditional select.
+    /// Use this whenever the base or exponent may be witness data.
     fn pow(&self, exp: &[u64]) -> Self {
-        if exp.is_empty() {
-            return Self::one();
+        let mut result = Self::one();
+
+        for &limb in exp.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.square();
+                let product = result * self;
+                let choice = Choice::from(((limb >> bit) & 1) as u8);
+                result = Self::conditional_select(&result, &product, choice);
+            }
         }
-        
+
+        result
+    }
+
+    /// Square-and-multiply that skips leading zero bits and only multiplies
+    /// on set bits. Only for public exponents and bases, e.g. roots of
+    /// unity and verifier-side evaluation points.
+    fn pow_vartime(&self, exp: &[u64]) -> Self {
         let mut result = Self::one();
-        let mut base = *self;
-        
-        for &limb in exp {
-            let mut remaining = limb;
-            while remaining > 0 {
-                if remaining & 1 == 1 {
-                    result *= &base;
+        let mut started = false;
+
+        for &limb in exp.iter().rev() {
+            for bit in (0..64).rev() {
+                if started {
+                    result = result.square();
+                }
+                if (limb >> bit) & 1 == 1 {
+                    result *= self;
+                    started = true;
                 }
-                base = base.square();
-                remaining >>= 1;
             }
         }
-        
+
         result
     }
 
+    /// Invert every element in place using a single field inversion
+    /// (Montgomery's trick). Zero elements are left as zero.
     fn batch_invert(elements: &mut [Self]) {
         let mut products = Vec::with_capacity(elements.len());
         let mut acc = Self::one();
 
         for elem in elements.iter() {
             products.push(acc);
-            acc *= elem;
+            if *elem != Self::ZERO {
+                acc *= elem;
+            }
         }
 
         if let Some(inv) = acc.invert() {
             let mut inv_acc = inv;
             for (elem, prod) in elements.iter_mut().zip(products.iter()).rev() {
+                if *elem == Self::ZERO {
+                    continue;
+                }
                 let tmp = *elem * inv_acc;
                 *elem = *prod * inv_acc;
                 inv_acc = tmp;
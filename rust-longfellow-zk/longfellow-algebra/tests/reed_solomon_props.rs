@@ -0,0 +1,59 @@
+//! Property tests for Reed-Solomon erasure and error decoding
+
+use longfellow_algebra::reed_solomon::ReedSolomon;
+use longfellow_algebra::{Field, Fp128};
+use proptest::prelude::*;
+
+const N: usize = 16;
+const K: usize = 8;
+
+fn code() -> ReedSolomon<Fp128> {
+    ReedSolomon::new(N, K, Fp128::get_root_of_unity(N.trailing_zeros()).unwrap()).unwrap()
+}
+
+fn message() -> impl Strategy<Value = Vec<Fp128>> {
+    prop::collection::vec(any::<u64>().prop_map(Fp128::from_u64), K)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn erasures_roundtrip(
+        data in message(),
+        erasures in prop::sample::subsequence((0..N).collect::<Vec<_>>(), 0..=N - K),
+        garbage in any::<u64>(),
+    ) {
+        let rs = code();
+        let mut codeword = rs.encode(&data).unwrap();
+        for &i in &erasures {
+            codeword[i] = Fp128::from_u64(garbage);
+        }
+
+        prop_assert_eq!(rs.decode(&codeword, &erasures).unwrap(), data);
+    }
+
+    #[test]
+    fn errors_roundtrip(
+        data in message(),
+        positions in prop::sample::subsequence((0..N).collect::<Vec<_>>(), 0..=(N - K) / 2),
+        offset in 1u64..,
+    ) {
+        let rs = code();
+        let mut codeword = rs.encode(&data).unwrap();
+        for &i in &positions {
+            codeword[i] += Fp128::from_u64(offset);
+        }
+
+        prop_assert_eq!(rs.correct_errors(&codeword).unwrap(), data);
+    }
+
+    #[test]
+    fn too_many_erasures_rejected(data in message()) {
+        let rs = code();
+        let codeword = rs.encode(&data).unwrap();
+        let erasures: Vec<usize> = (0..=N - K).collect();
+
+        prop_assert!(rs.decode(&codeword, &erasures).is_err());
+    }
+}
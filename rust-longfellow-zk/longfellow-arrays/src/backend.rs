@@ -0,0 +1,128 @@
+use crate::affine::affine_interpolation_conditional;
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Length from which the CPU kernels split work across rayon tasks
+pub const BACKEND_PARALLEL_THRESHOLD: usize = 1024;
+
+/// Bulk array kernels used by the provers
+///
+/// Dense binding and elementwise products dominate large sumcheck and
+/// Ligero instances. They go through this trait so a device backend can
+/// take them over; implementations decide per call whether an input is
+/// large enough to be worth offloading and otherwise run on the CPU.
+pub trait ArrayBackend<F: Field>: Send + Sync {
+    /// Short name for logs and benchmarks
+    fn name(&self) -> &'static str;
+
+    /// lo[i] <- lo[i] + r * (hi[i] - lo[i]) over the common length
+    fn fold(&self, lo: &mut [F], hi: &[F], r: F);
+
+    /// out[i] <- a[i] * b[i]
+    fn mul_batch(&self, a: &[F], b: &[F], out: &mut [F]) -> Result<()>;
+}
+
+/// The rayon kernels, always available
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuBackend;
+
+impl<F: Field> ArrayBackend<F> for CpuBackend {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn fold(&self, lo: &mut [F], hi: &[F], r: F) {
+        let n = lo.len().min(hi.len());
+        let (lo, hi) = (&mut lo[..n], &hi[..n]);
+
+        if n >= BACKEND_PARALLEL_THRESHOLD {
+            lo.par_iter_mut()
+                .zip(hi.par_iter())
+                .for_each(|(f0, &f1)| {
+                    *f0 = affine_interpolation_conditional(*f0, f1, r);
+                });
+        } else {
+            for (f0, &f1) in lo.iter_mut().zip(hi.iter()) {
+                *f0 = affine_interpolation_conditional(*f0, f1, r);
+            }
+        }
+    }
+
+    fn mul_batch(&self, a: &[F], b: &[F], out: &mut [F]) -> Result<()> {
+        check_batch_lengths(a, b, out)?;
+
+        if out.len() >= BACKEND_PARALLEL_THRESHOLD {
+            out.par_iter_mut()
+                .zip(a.par_iter().zip(b.par_iter()))
+                .for_each(|(o, (&x, &y))| *o = x * y);
+        } else {
+            for (o, (&x, &y)) in out.iter_mut().zip(a.iter().zip(b.iter())) {
+                *o = x * y;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn check_batch_lengths<F>(a: &[F], b: &[F], out: &[F]) -> Result<()> {
+    if a.len() != b.len() || a.len() != out.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Batch length mismatch: {} * {} -> {}",
+            a.len(),
+            b.len(),
+            out.len()
+        )));
+    }
+    Ok(())
+}
+
+/// The preferred backend for `F`
+///
+/// With the `wgpu` feature this is the GPU backend when an adapter is
+/// present and `F` is a prime field it can represent; otherwise the CPU.
+pub fn default_backend<F: Field>() -> Arc<dyn ArrayBackend<F>> {
+    #[cfg(feature = "wgpu")]
+    if let Some(gpu) = crate::gpu::WgpuBackend::<F>::new() {
+        return Arc::new(gpu);
+    }
+
+    Arc::new(CpuBackend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dense;
+    use longfellow_algebra::Fp128;
+
+    #[test]
+    fn test_cpu_backend_matches_dense_bind() {
+        let n1 = 3;
+        let values: Vec<Fp128> = (0..8 * n1).map(|i| Fp128::from_u64(i as u64 * 7 + 1)).collect();
+        let r = Fp128::from_u64(12345);
+
+        let mut expected = Dense::from_vec(8, n1, values.clone()).unwrap();
+        expected.bind(r);
+
+        let mut dense = Dense::from_vec(8, n1, values).unwrap();
+        dense.bind_with(r, &CpuBackend);
+        assert_eq!(dense.as_slice(), expected.as_slice());
+        assert_eq!(dense.n0(), 4);
+    }
+
+    #[test]
+    fn test_mul_batch() {
+        let backend = default_backend::<Fp128>();
+        let a: Vec<Fp128> = (0..2000).map(|i| Fp128::from_u64(i + 3)).collect();
+        let b: Vec<Fp128> = (0..2000).map(|i| Fp128::from_u64(5 * i + 1)).collect();
+        let mut out = vec![Fp128::zero(); a.len()];
+
+        backend.mul_batch(&a, &b, &mut out).unwrap();
+        for ((x, y), o) in a.iter().zip(b.iter()).zip(out.iter()) {
+            assert_eq!(*x * *y, *o);
+        }
+        assert!(backend.mul_batch(&a, &b[1..], &mut out).is_err());
+    }
+}
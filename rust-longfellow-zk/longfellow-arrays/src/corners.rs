@@ -0,0 +1,201 @@
+use crate::affine::affine_interpolation;
+use crate::CornerIndex;
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// Lazy iterator over the corners of {0,1}^log_n
+///
+/// Yields `(i, gen(i))` for i = 0, 1, ..., 2^log_n - 1 without storing the
+/// table, so a multilinear extension given by a closure (e.g. a wiring
+/// predicate) can be summed or evaluated in O(log_n) memory. Bit k of the
+/// corner index pairs with coordinate k of an evaluation point, as in
+/// `compute_eq_polynomial`.
+pub struct HypercubeCorners<F: Field, G: FnMut(CornerIndex) -> F> {
+    log_n: usize,
+    range: Range<CornerIndex>,
+    generator: G,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: Field, G: FnMut(CornerIndex) -> F> HypercubeCorners<F, G> {
+    pub fn new(log_n: usize, generator: G) -> Self {
+        Self {
+            log_n,
+            range: 0..1 << log_n,
+            generator,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn log_n(&self) -> usize {
+        self.log_n
+    }
+
+    /// Skip corners whose value is zero
+    pub fn nonzero(self) -> impl Iterator<Item = (CornerIndex, F)> {
+        self.filter(|(_, v)| *v != F::zero())
+    }
+
+    /// Evaluate the multilinear extension at `point` by folding the stream
+    ///
+    /// Consecutive corners differ first in bit 0, so pairs are folded with
+    /// point[0] as they arrive and carried upwards like a binary counter,
+    /// keeping one pending value per level. The stream must not have been
+    /// advanced.
+    pub fn evaluate(self, point: &[F]) -> Result<F> {
+        if point.len() != self.log_n {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Expected {} points, got {}",
+                self.log_n,
+                point.len()
+            )));
+        }
+        if self.range.start != 0 {
+            return Err(LongfellowError::InvalidParameter(
+                "Cannot fold a partially consumed corner stream".to_string(),
+            ));
+        }
+
+        let log_n = self.log_n;
+        let mut pending: Vec<Option<F>> = vec![None; log_n];
+        for (_, v) in self {
+            let mut carry = v;
+            let mut level = 0;
+            while level < log_n {
+                match pending[level].take() {
+                    Some(f0) => {
+                        carry = affine_interpolation(f0, carry, point[level]);
+                        level += 1;
+                    }
+                    None => break,
+                }
+            }
+            if level == log_n {
+                return Ok(carry);
+            }
+            pending[level] = Some(carry);
+        }
+
+        unreachable!("a full hypercube stream always folds to one value")
+    }
+}
+
+impl<F: Field, G: FnMut(CornerIndex) -> F> Iterator for HypercubeCorners<F, G> {
+    type Item = (CornerIndex, F);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.range.next()?;
+        Some((i, (self.generator)(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<F: Field, G: FnMut(CornerIndex) -> F> ExactSizeIterator for HypercubeCorners<F, G> {}
+
+/// Stream the corners of {0,1}^log_n through `generator`
+pub fn hypercube_corners<F: Field, G: FnMut(CornerIndex) -> F>(
+    log_n: usize,
+    generator: G,
+) -> HypercubeCorners<F, G> {
+    HypercubeCorners::new(log_n, generator)
+}
+
+/// Multilinear extension at `point` of a sparse stream of corners
+///
+/// Corners may come in any order and repeat (values are summed); absent
+/// corners are zero. Costs O(log_n) per corner and no table.
+pub fn evaluate_corners<F: Field>(
+    log_n: usize,
+    corners: impl IntoIterator<Item = (CornerIndex, F)>,
+    point: &[F],
+) -> Result<F> {
+    if point.len() != log_n {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Expected {} points, got {}",
+            log_n,
+            point.len()
+        )));
+    }
+
+    let mut result = F::zero();
+    for (i, v) in corners {
+        if i >> log_n != 0 {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Corner {} out of bounds for 2^{} corners",
+                i, log_n
+            )));
+        }
+
+        let mut term = v;
+        for (k, &r) in point.iter().enumerate() {
+            if (i >> k) & 1 == 1 {
+                term *= r;
+            } else {
+                term *= F::one() - r;
+            }
+        }
+        result += term;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute_eq_polynomial;
+    use longfellow_algebra::Fp128;
+
+    fn table_mle(values: &[Fp128], point: &[Fp128]) -> Fp128 {
+        let eq = compute_eq_polynomial(point.len(), point).unwrap();
+        values.iter().zip(eq.iter()).fold(Fp128::zero(), |acc, (&v, &e)| acc + v * e)
+    }
+
+    #[test]
+    fn test_streaming_evaluation_matches_table() {
+        let log_n = 6;
+        let generator = |i: CornerIndex| Fp128::from_u64((i * i + 3) as u64);
+        let values: Vec<Fp128> = (0..1 << log_n).map(generator).collect();
+        let point: Vec<Fp128> = (0..log_n).map(|k| Fp128::from_u64(k as u64 * 11 + 2)).collect();
+
+        let corners = hypercube_corners(log_n, generator);
+        assert_eq!(corners.len(), 64);
+        assert_eq!(corners.evaluate(&point).unwrap(), table_mle(&values, &point));
+
+        // A single corner is its own extension
+        let constant = hypercube_corners(0, |_| Fp128::from_u64(9));
+        assert_eq!(constant.evaluate(&[]).unwrap(), Fp128::from_u64(9));
+        assert!(hypercube_corners(2, generator).evaluate(&point).is_err());
+    }
+
+    #[test]
+    fn test_sparse_stream_matches_table() {
+        // Wiring-style predicate: non-zero only where the high half of the
+        // index equals the low half
+        let log_n = 8;
+        let predicate = |i: CornerIndex| {
+            if i >> 4 == i & 0xf {
+                Fp128::one()
+            } else {
+                Fp128::zero()
+            }
+        };
+        let point: Vec<Fp128> = (0..log_n).map(|k| Fp128::from_u64(k as u64 + 5)).collect();
+
+        let nonzero: Vec<_> = hypercube_corners(log_n, predicate).nonzero().collect();
+        assert_eq!(nonzero.len(), 16);
+
+        let values: Vec<Fp128> = (0..1 << log_n).map(predicate).collect();
+        let expected = table_mle(&values, &point);
+        let reversed = nonzero.iter().rev().copied();
+        assert_eq!(evaluate_corners(log_n, reversed, &point).unwrap(), expected);
+        assert_eq!(hypercube_corners(log_n, predicate).evaluate(&point).unwrap(), expected);
+
+        assert!(evaluate_corners(2, vec![(4, Fp128::one())], &point[..2]).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 use crate::affine::affine_interpolation_conditional;
+use crate::backend::{ArrayBackend, CpuBackend};
 use crate::CornerIndex;
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use rayon::prelude::*;
 use std::fmt::Debug;
+use std::ops::Range;
 
 #[derive(Clone, Debug)]
 pub struct Dense<F: Field> {
@@ -58,6 +60,39 @@ impl<F: Field> Dense<F> {
         &mut self.v
     }
 
+    pub fn into_vec(self) -> Vec<F> {
+        self.v
+    }
+
+    /// Borrow the whole array as a view
+    pub fn view(&self) -> DenseView<'_, F> {
+        DenseView {
+            data: &self.v,
+            n0: self.n0,
+            n1: self.n1,
+            stride: self.n1,
+        }
+    }
+
+    pub fn view_mut(&mut self) -> DenseViewMut<'_, F> {
+        DenseViewMut {
+            data: &mut self.v,
+            n0: self.n0,
+            n1: self.n1,
+            stride: self.n1,
+        }
+    }
+
+    /// View of rows `rows` without copying
+    pub fn slice_rows(&self, rows: Range<CornerIndex>) -> DenseView<'_, F> {
+        self.view().slice_rows(rows)
+    }
+
+    /// View of columns `cols` of every row without copying
+    pub fn slice_cols(&self, cols: Range<CornerIndex>) -> DenseView<'_, F> {
+        self.view().slice_cols(cols)
+    }
+
     pub fn get(&self, i0: CornerIndex, i1: CornerIndex) -> Option<&F> {
         if i0 < self.n0 && i1 < self.n1 {
             Some(&self.v[i0 * self.n1 + i1])
@@ -85,39 +120,61 @@ impl<F: Field> Dense<F> {
         Ok(())
     }
 
+    /// Bind the leading variable to `r`
+    ///
+    /// Same as `bind_in_place`: the table is folded into its own first half.
     pub fn bind(&mut self, r: F) {
+        self.bind_in_place(r);
+    }
+
+    /// Fold the upper half of the rows into the lower half and truncate.
+    /// No allocation happens; the vector keeps its capacity, so a sequence
+    /// of binds touches only the original buffer.
+    pub fn bind_in_place(&mut self, r: F) {
+        self.bind_with(r, &CpuBackend);
+    }
+
+    /// Bind the leading variable with the fold running on `backend`
+    pub fn bind_with(&mut self, r: F, backend: &dyn ArrayBackend<F>) {
         assert!(self.n0 > 1, "Cannot bind dimension of size 1");
-        
-        let new_n0 = self.n0 / 2;
-        let n1 = self.n1;
-
-        if new_n0 * n1 >= 1024 {
-            // Process rows in parallel, but we need to be careful about aliasing
-            // Since we're reading from the second half and writing to the first half,
-            // we can split the array
-            let (first_half, second_half) = self.v.split_at_mut(new_n0 * n1);
-            
-            first_half.par_chunks_mut(n1)
-                .zip(second_half.par_chunks(n1))
-                .for_each(|(row, other_row)| {
-                    for j in 0..n1 {
-                        let f0 = row[j];
-                        let f1 = other_row[j];
-                        row[j] = affine_interpolation_conditional(f0, f1, r);
-                    }
+
+        let half = (self.n0 / 2) * self.n1;
+        let (lo, hi) = self.v.split_at_mut(half);
+        backend.fold(lo, hi, r);
+
+        self.n0 /= 2;
+        self.v.truncate(half);
+    }
+
+    /// Bind the leading variable into `scratch`, leaving `self` untouched
+    ///
+    /// `scratch` is overwritten and resized; its buffer is reused, so a
+    /// prover that needs the folded table for several challenges (or
+    /// alternates between two tables) allocates at most once.
+    pub fn bind_into(&self, r: F, scratch: &mut Dense<F>) {
+        assert!(self.n0 > 1, "Cannot bind dimension of size 1");
+
+        let half = (self.n0 / 2) * self.n1;
+        let (lo, hi) = self.v.split_at(half);
+        scratch.v.clear();
+        scratch.v.resize(half, F::zero());
+
+        if half >= 1024 {
+            scratch
+                .v
+                .par_iter_mut()
+                .zip(lo.par_iter().zip(hi.par_iter()))
+                .for_each(|(out, (&f0, &f1))| {
+                    *out = affine_interpolation_conditional(f0, f1, r);
                 });
         } else {
-            for i in 0..new_n0 {
-                for j in 0..n1 {
-                    let f0 = self.v[i * n1 + j];
-                    let f1 = self.v[(i + new_n0) * n1 + j];
-                    self.v[i * n1 + j] = affine_interpolation_conditional(f0, f1, r);
-                }
+            for (out, (&f0, &f1)) in scratch.v.iter_mut().zip(lo.iter().zip(hi.iter())) {
+                *out = affine_interpolation_conditional(f0, f1, r);
             }
         }
 
-        self.n0 = new_n0;
-        self.v.truncate(new_n0 * n1);
+        scratch.n0 = self.n0 / 2;
+        scratch.n1 = self.n1;
     }
 
     pub fn bind_all(&mut self, log_v: usize, r: &[F]) {
@@ -131,7 +188,7 @@ impl<F: Field> Dense<F> {
         );
 
         for &ri in r {
-            self.bind(ri);
+            self.bind_in_place(ri);
         }
 
         assert_eq!(self.n0, 1);
@@ -213,6 +270,268 @@ impl<F: Field> Dense<F> {
     }
 }
 
+/// Elements of `data` spanned by an n0 x n1 window with the given row stride
+fn window_len(n0: CornerIndex, n1: CornerIndex, stride: usize) -> usize {
+    if n0 == 0 || n1 == 0 {
+        0
+    } else {
+        (n0 - 1) * stride + n1
+    }
+}
+
+fn check_range(range: &Range<CornerIndex>, bound: CornerIndex, what: &str) {
+    assert!(
+        range.start <= range.end && range.end <= bound,
+        "{} range {:?} out of bounds for {}",
+        what,
+        range,
+        bound
+    );
+}
+
+/// Borrowed rectangular window into a `Dense` array
+///
+/// Row i of the window is `data[i * stride..i * stride + n1]`, so row ranges
+/// and column ranges can both be taken without copying. Rows are contiguous;
+/// the window as a whole is contiguous only when it spans full rows.
+#[derive(Clone, Copy, Debug)]
+pub struct DenseView<'a, F: Field> {
+    data: &'a [F],
+    n0: CornerIndex,
+    n1: CornerIndex,
+    stride: usize,
+}
+
+impl<'a, F: Field> DenseView<'a, F> {
+    /// View a row-major n0 x n1 slice
+    pub fn new(data: &'a [F], n0: CornerIndex, n1: CornerIndex) -> Result<Self> {
+        if data.len() != n0 * n1 {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Slice length {} does not match dimensions {}x{}",
+                data.len(),
+                n0,
+                n1
+            )));
+        }
+        Ok(Self {
+            data,
+            n0,
+            n1,
+            stride: n1,
+        })
+    }
+
+    pub fn n0(&self) -> CornerIndex {
+        self.n0
+    }
+
+    pub fn n1(&self) -> CornerIndex {
+        self.n1
+    }
+
+    pub fn len(&self) -> usize {
+        self.n0 * self.n1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, i0: CornerIndex, i1: CornerIndex) -> Option<&'a F> {
+        if i0 < self.n0 && i1 < self.n1 {
+            Some(&self.data[i0 * self.stride + i1])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, i0: CornerIndex) -> &'a [F] {
+        assert!(i0 < self.n0, "Row {} out of bounds for {} rows", i0, self.n0);
+        &self.data[i0 * self.stride..i0 * self.stride + self.n1]
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &'a [F]> + 'a {
+        let view = *self;
+        (0..self.n0).map(move |i0| view.row(i0))
+    }
+
+    /// The elements as one row-major slice, if the window is contiguous
+    pub fn as_slice(&self) -> Option<&'a [F]> {
+        if self.n0 <= 1 || self.stride == self.n1 {
+            Some(self.data)
+        } else {
+            None
+        }
+    }
+
+    pub fn slice_rows(&self, rows: Range<CornerIndex>) -> Self {
+        check_range(&rows, self.n0, "Row");
+        let n0 = rows.end - rows.start;
+        let start = (rows.start * self.stride).min(self.data.len());
+        Self {
+            data: &self.data[start..start + window_len(n0, self.n1, self.stride)],
+            n0,
+            n1: self.n1,
+            stride: self.stride,
+        }
+    }
+
+    pub fn slice_cols(&self, cols: Range<CornerIndex>) -> Self {
+        check_range(&cols, self.n1, "Column");
+        let n1 = cols.end - cols.start;
+        let start = cols.start.min(self.data.len());
+        Self {
+            data: &self.data[start..start + window_len(self.n0, n1, self.stride)],
+            n0: self.n0,
+            n1,
+            stride: self.stride,
+        }
+    }
+
+    /// Copy the window into an owned array
+    pub fn to_dense(&self) -> Dense<F> {
+        let mut v = Vec::with_capacity(self.len());
+        for row in self.rows() {
+            v.extend_from_slice(row);
+        }
+        Dense {
+            n0: self.n0,
+            n1: self.n1,
+            v,
+        }
+    }
+}
+
+/// Mutable counterpart of `DenseView`
+///
+/// Slicing consumes the view so that disjoint pieces obtained with
+/// `split_rows_at` can be handed to different threads.
+#[derive(Debug)]
+pub struct DenseViewMut<'a, F: Field> {
+    data: &'a mut [F],
+    n0: CornerIndex,
+    n1: CornerIndex,
+    stride: usize,
+}
+
+impl<'a, F: Field> DenseViewMut<'a, F> {
+    pub fn n0(&self) -> CornerIndex {
+        self.n0
+    }
+
+    pub fn n1(&self) -> CornerIndex {
+        self.n1
+    }
+
+    pub fn len(&self) -> usize {
+        self.n0 * self.n1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reborrow as a shared view
+    pub fn as_view(&self) -> DenseView<'_, F> {
+        DenseView {
+            data: &*self.data,
+            n0: self.n0,
+            n1: self.n1,
+            stride: self.stride,
+        }
+    }
+
+    pub fn get(&self, i0: CornerIndex, i1: CornerIndex) -> Option<&F> {
+        if i0 < self.n0 && i1 < self.n1 {
+            Some(&self.data[i0 * self.stride + i1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, i0: CornerIndex, i1: CornerIndex) -> Option<&mut F> {
+        if i0 < self.n0 && i1 < self.n1 {
+            Some(&mut self.data[i0 * self.stride + i1])
+        } else {
+            None
+        }
+    }
+
+    pub fn row_mut(&mut self, i0: CornerIndex) -> &mut [F] {
+        assert!(i0 < self.n0, "Row {} out of bounds for {} rows", i0, self.n0);
+        &mut self.data[i0 * self.stride..i0 * self.stride + self.n1]
+    }
+
+    pub fn fill(&mut self, value: F) {
+        for i0 in 0..self.n0 {
+            self.row_mut(i0).fill(value);
+        }
+    }
+
+    /// Overwrite the window with `src`, which must have the same shape
+    pub fn copy_from(&mut self, src: &DenseView<'_, F>) -> Result<()> {
+        if src.n0 != self.n0 || src.n1 != self.n1 {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Cannot copy {}x{} view into {}x{} view",
+                src.n0, src.n1, self.n0, self.n1
+            )));
+        }
+        for (i0, row) in src.rows().enumerate() {
+            self.row_mut(i0).copy_from_slice(row);
+        }
+        Ok(())
+    }
+
+    pub fn slice_rows_mut(self, rows: Range<CornerIndex>) -> Self {
+        check_range(&rows, self.n0, "Row");
+        let n0 = rows.end - rows.start;
+        let start = (rows.start * self.stride).min(self.data.len());
+        let len = window_len(n0, self.n1, self.stride);
+        let data = self.data;
+        Self {
+            data: &mut data[start..start + len],
+            n0,
+            n1: self.n1,
+            stride: self.stride,
+        }
+    }
+
+    pub fn slice_cols_mut(self, cols: Range<CornerIndex>) -> Self {
+        check_range(&cols, self.n1, "Column");
+        let n1 = cols.end - cols.start;
+        let start = cols.start.min(self.data.len());
+        let len = window_len(self.n0, n1, self.stride);
+        let data = self.data;
+        Self {
+            data: &mut data[start..start + len],
+            n0: self.n0,
+            n1,
+            stride: self.stride,
+        }
+    }
+
+    /// Split into rows [0, mid) and [mid, n0)
+    pub fn split_rows_at(self, mid: CornerIndex) -> (Self, Self) {
+        assert!(mid <= self.n0, "Split row {} out of bounds for {} rows", mid, self.n0);
+        let split = (mid * self.stride).min(self.data.len());
+        let (top, bottom) = self.data.split_at_mut(split);
+        (
+            Self {
+                data: top,
+                n0: mid,
+                n1: self.n1,
+                stride: self.stride,
+            },
+            Self {
+                data: bottom,
+                n0: self.n0 - mid,
+                n1: self.n1,
+                stride: self.stride,
+            },
+        )
+    }
+}
+
 pub struct DenseFiller<F: Field> {
     dense: Dense<F>,
     index: usize,
@@ -442,6 +761,39 @@ mod tests {
         assert_eq!(dense.len(), 4);
     }
 
+    #[test]
+    fn test_dense_bind_in_place_and_scratch() {
+        // 2^11 x 1 crosses the parallel threshold on the first bind
+        let values: Vec<TestField> =
+            (0..1 << 11).map(|i| TestField::from_u64(i * 7 + 1)).collect();
+        let original = Dense::from_vec(1 << 11, 1, values).unwrap();
+        let r = [TestField(50), TestField(3), TestField(91)];
+
+        let mut in_place = original.clone();
+        let capacity = in_place.v.capacity();
+        let ptr = in_place.as_slice().as_ptr();
+
+        let mut scratch = Dense::new(1, 1);
+        let mut current = original.clone();
+        for &ri in &r {
+            in_place.bind_in_place(ri);
+            current.bind_into(ri, &mut scratch);
+            std::mem::swap(&mut current, &mut scratch);
+            assert_eq!(in_place.as_slice(), current.as_slice());
+            assert_eq!(in_place.n0(), current.n0());
+        }
+
+        // Still the original buffer
+        assert_eq!(in_place.v.capacity(), capacity);
+        assert_eq!(in_place.as_slice().as_ptr(), ptr);
+
+        // bind_into does not modify its source
+        let mut out = Dense::new(1, 1);
+        original.bind_into(r[0], &mut out);
+        assert_eq!(original.n0(), 1 << 11);
+        assert_eq!(out.len(), 1 << 10);
+    }
+
     #[test]
     fn test_dense_filler() {
         let mut filler = DenseFiller::new(2, 2);
@@ -456,4 +808,35 @@ mod tests {
         assert_eq!(dense.get(1, 0), Some(&TestField(3)));
         assert_eq!(dense.get(1, 1), Some(&TestField(4)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_dense_views() {
+        let v: Vec<TestField> = (0..12).map(TestField::from_u64).collect();
+        let mut dense = Dense::from_vec(3, 4, v).unwrap();
+
+        let window = dense.slice_rows(1..3).slice_cols(1..3);
+        assert_eq!((window.n0(), window.n1()), (2, 2));
+        assert_eq!(window.get(0, 0), Some(&TestField(5)));
+        assert_eq!(window.row(1), &[TestField(9), TestField(10)]);
+        assert!(window.as_slice().is_none());
+        assert_eq!(dense.slice_rows(1..2).as_slice().unwrap().len(), 4);
+        assert_eq!(
+            window.to_dense().as_slice(),
+            &[TestField(5), TestField(6), TestField(9), TestField(10)]
+        );
+        assert!(dense.slice_cols(2..2).is_empty());
+
+        // Writes through disjoint mutable views land in the parent
+        let (mut top, bottom) = dense.view_mut().split_rows_at(1);
+        let mut right = bottom.slice_cols_mut(2..4);
+        top.fill(TestField(0));
+        right.copy_from(&Dense::new(2, 2).view()).unwrap();
+        *right.get_mut(1, 1).unwrap() = TestField(99);
+        assert!(top.copy_from(&Dense::new(2, 2).view()).is_err());
+
+        assert_eq!(dense.get(0, 3), Some(&TestField(0)));
+        assert_eq!(dense.get(1, 1), Some(&TestField(5)));
+        assert_eq!(dense.get(1, 2), Some(&TestField(0)));
+        assert_eq!(dense.get(2, 3), Some(&TestField(99)));
+    }
+}
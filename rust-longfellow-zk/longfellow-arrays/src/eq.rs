@@ -1,6 +1,11 @@
 use crate::CornerIndex;
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
+
+/// Table size from which `extend_eq_table` splits the doubling across
+/// rayon tasks
+pub const EQ_PARALLEL_THRESHOLD: usize = 1 << 12;
 
 #[derive(Clone, Debug)]
 pub struct Eq<F: Field> {
@@ -117,24 +122,38 @@ pub fn compute_eq_polynomial<F: Field>(log_n: usize, r: &[F]) -> Result<Vec<F>>
         )));
     }
 
-    let n = 1 << log_n;
-    let mut eq_values = vec![F::one(); n];
-
-    for (k, &r_k) in r.iter().enumerate() {
-        let stride = 1 << k;
-        let one_minus_r = F::one() - r_k;
-
-        for i in (0..n).step_by(2 * stride) {
-            for j in 0..stride {
-                let idx0 = i + j;
-                let idx1 = i + j + stride;
-                eq_values[idx0] *= one_minus_r;
-                eq_values[idx1] *= r_k;
-            }
-        }
+    // eq_values[i] pairs bit k of i with r[k]: appending r[k] as the new
+    // most significant bit keeps the earlier bits in place
+    let mut table = Vec::with_capacity(1 << log_n);
+    table.push(F::one());
+    for &ri in r {
+        extend_eq_table(&mut table, ri);
     }
+    Ok(table)
+}
 
-    Ok(eq_values)
+/// Extend an EQ(r, .) table by one coordinate in place
+///
+/// `table` holds EQ(r, x) for x over n = 2^k points with bit j of x paired
+/// with r[j]. Appending r_k doubles it: the lower half is scaled by
+/// (1 - r_k) and the upper half is the old table scaled by r_k, one
+/// multiplication per entry instead of rebuilding all k + 1 levels.
+pub fn extend_eq_table<F: Field>(table: &mut Vec<F>, r: F) {
+    let n = table.len();
+    assert!(n.is_power_of_two(), "EQ table size must be a power of 2");
+
+    table.resize(2 * n, F::zero());
+    let (lo, hi) = table.split_at_mut(n);
+
+    let step = |(l, h): (&mut F, &mut F)| {
+        *h = *l * r;
+        *l -= *h;
+    };
+    if n >= EQ_PARALLEL_THRESHOLD {
+        lo.par_iter_mut().zip(hi.par_iter_mut()).for_each(step);
+    } else {
+        lo.iter_mut().zip(hi.iter_mut()).for_each(step);
+    }
 }
 
 #[cfg(test)]
@@ -327,4 +346,24 @@ mod tests {
         let sum = eq_poly.iter().fold(TestField(0), |acc, &x| acc + x);
         assert_eq!(sum, TestField(1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_eq_table_matches_pointwise() {
+        // Large enough to take the parallel path on the last extensions
+        let log_n = 14;
+        let r: Vec<TestField> = (0..log_n).map(|i| TestField::from_u64(i as u64 * 13 + 5)).collect();
+        let table = compute_eq_polynomial(log_n, &r).unwrap();
+        let eq = Eq::<TestField>::new(log_n);
+
+        for i in (0..1 << log_n).step_by(97) {
+            assert_eq!(table[i], eq.evaluate_at_points(i, &r).unwrap());
+        }
+
+        // Extending by one more coordinate equals rebuilding from scratch
+        let mut extended = table;
+        extend_eq_table(&mut extended, TestField(77));
+        let mut r_ext = r.clone();
+        r_ext.push(TestField(77));
+        assert_eq!(extended, compute_eq_polynomial(log_n + 1, &r_ext).unwrap());
+    }
+}
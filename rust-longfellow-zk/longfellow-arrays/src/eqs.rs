@@ -1,4 +1,4 @@
-use crate::{compute_eq_polynomial, Dense};
+use crate::{compute_eq_polynomial, extend_eq_table, Dense};
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 
@@ -55,6 +55,24 @@ impl<F: Field> Eqs<F> {
         }
     }
 
+    /// Append a coordinate to the fixed point, doubling the table in place
+    /// rather than recomputing it. Only valid before any `bind`.
+    pub fn extend(&mut self, r: F) -> Result<()> {
+        if self.dense.n0() != self.n() {
+            return Err(LongfellowError::InvalidParameter(
+                "Cannot extend an EQ table after binding".to_string(),
+            ));
+        }
+
+        let dense = std::mem::replace(&mut self.dense, Dense::new(1, 1));
+        let mut table = dense.into_vec();
+        extend_eq_table(&mut table, r);
+
+        self.dense = Dense::from_vec(table.len(), 1, table)?;
+        self.fixed_point.push(r);
+        Ok(())
+    }
+
     pub fn scalar(&self) -> Result<F> {
         self.dense.scalar()
     }
@@ -279,6 +297,21 @@ mod tests {
         assert_eq!(eqs.as_dense().n0(), 2);
     }
 
+    #[test]
+    fn test_eqs_extend() {
+        let mut eqs = Eqs::new(vec![TestField(20)]).unwrap();
+        eqs.extend(TestField(30)).unwrap();
+        eqs.extend(TestField(45)).unwrap();
+
+        assert_eq!(eqs.n(), 8);
+        assert!(eqs.verify().is_ok());
+        let rebuilt = Eqs::new(vec![TestField(20), TestField(30), TestField(45)]).unwrap();
+        assert_eq!(eqs.as_dense().as_slice(), rebuilt.as_dense().as_slice());
+
+        eqs.bind(TestField(3));
+        assert!(eqs.extend(TestField(1)).is_err());
+    }
+
     #[test]
     fn test_eqs_verify() {
         let fixed_point = vec![TestField(20), TestField(30)];
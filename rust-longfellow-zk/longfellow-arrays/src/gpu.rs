@@ -0,0 +1,341 @@
+//! WGPU backend for 31-bit prime fields
+//!
+//! Folds and elementwise products run as compute shaders (`gpu.wgsl`) on
+//! canonical u32 values. Fields wider than 31 bits, extension fields and
+//! inputs below `GPU_THRESHOLD` go to the CPU kernels, as does any call
+//! that fails on the device, so the backend is always safe to select.
+
+use crate::backend::{check_batch_lengths, ArrayBackend, CpuBackend};
+use bytemuck::{Pod, Zeroable};
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
+use std::marker::PhantomData;
+use std::sync::{mpsc, OnceLock};
+use wgpu::util::DeviceExt;
+
+/// Shortest input worth the upload and readback
+pub const GPU_THRESHOLD: usize = 1 << 16;
+
+/// Elements per dispatch: 2^23 / 256 workgroups stays below the 65535
+/// dispatch limit and the 32 MiB buffers below the default binding limit
+const GPU_CHUNK: usize = 1 << 23;
+const WORKGROUP_SIZE: usize = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct Params {
+    modulus: u32,
+    neg_inv: u32,
+    r2: u32,
+    len: u32,
+    challenge: u32,
+    _pad: [u32; 3],
+}
+
+/// Montgomery constants for an odd modulus below 2^31
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Modulus {
+    p: u32,
+    neg_inv: u32,
+    r2: u32,
+}
+
+impl Modulus {
+    fn of<F: Field>() -> Option<Self> {
+        let order = F::order_limbs();
+        if F::CHAR_IS_TWO || order.len() != 1 || order[0] >= 1 << 31 || order[0] & 1 == 0 {
+            return None;
+        }
+        let p = order[0] as u32;
+
+        // Newton iteration doubles the correct low bits; p * p = 1 mod 8
+        let mut inv = p;
+        for _ in 0..4 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(p.wrapping_mul(inv)));
+        }
+
+        Some(Self {
+            p,
+            neg_inv: inv.wrapping_neg(),
+            r2: ((1u128 << 64) % p as u128) as u32,
+        })
+    }
+
+    fn params(&self, len: usize, challenge: u32) -> Params {
+        Params {
+            modulus: self.p,
+            neg_inv: self.neg_inv,
+            r2: self.r2,
+            len: len as u32,
+            challenge,
+            _pad: [0; 3],
+        }
+    }
+}
+
+struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    fold: wgpu::ComputePipeline,
+    mul: wgpu::ComputePipeline,
+}
+
+impl GpuContext {
+    fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("longfellow-arrays"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::default(),
+            },
+            None,
+        ))
+        .ok()?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("longfellow-arrays kernels"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gpu.wgsl").into()),
+        });
+        let pipeline = |entry_point: &str| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: None,
+                module: &module,
+                entry_point,
+            })
+        };
+        let fold = pipeline("fold");
+        let mul = pipeline("mul");
+
+        Some(Self {
+            device,
+            queue,
+            fold,
+            mul,
+        })
+    }
+
+    /// Run `pipeline` over lhs/rhs in place, one chunk at a time
+    fn run(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        modulus: &Modulus,
+        challenge: u32,
+        lhs: &mut [u32],
+        rhs: &[u32],
+    ) -> Result<()> {
+        for (lhs, rhs) in lhs.chunks_mut(GPU_CHUNK).zip(rhs.chunks(GPU_CHUNK)) {
+            self.run_chunk(pipeline, modulus.params(lhs.len(), challenge), lhs, rhs)?;
+        }
+        Ok(())
+    }
+
+    fn run_chunk(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        params: Params,
+        lhs: &mut [u32],
+        rhs: &[u32],
+    ) -> Result<()> {
+        let bytes = std::mem::size_of_val(lhs) as wgpu::BufferAddress;
+        let uniform = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("params"),
+            contents: bytemuck::bytes_of(&params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let lhs_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("lhs"),
+            contents: bytemuck::cast_slice(lhs),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        });
+        let rhs_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("rhs"),
+            contents: bytemuck::cast_slice(rhs),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: lhs_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rhs_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(lhs.len().div_ceil(WORKGROUP_SIZE) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&lhs_buffer, 0, &staging, 0, bytes);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = staging.slice(..);
+        let (tx, rx) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|_| LongfellowError::InvalidParameter("GPU readback dropped".to_string()))?
+            .map_err(|e| LongfellowError::InvalidParameter(format!("GPU readback failed: {}", e)))?;
+
+        {
+            let mapped = slice.get_mapped_range();
+            lhs.copy_from_slice(bytemuck::cast_slice(&mapped));
+        }
+        staging.unmap();
+        Ok(())
+    }
+}
+
+/// The process-wide device, or None without a usable adapter
+fn context() -> Option<&'static GpuContext> {
+    static CONTEXT: OnceLock<Option<GpuContext>> = OnceLock::new();
+    CONTEXT.get_or_init(GpuContext::new).as_ref()
+}
+
+fn to_u32<F: Field>(x: &F) -> u32 {
+    let bytes = x.to_canonical_bytes();
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn upload<F: Field>(values: &[F]) -> Vec<u32> {
+    values.par_iter().map(to_u32).collect()
+}
+
+fn download<F: Field>(values: &[u32], out: &mut [F]) {
+    out.par_iter_mut()
+        .zip(values.par_iter())
+        .for_each(|(o, &v)| *o = F::from_u64(v as u64));
+}
+
+/// GPU kernels for a 31-bit prime field `F`
+pub struct WgpuBackend<F: Field> {
+    context: &'static GpuContext,
+    modulus: Modulus,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: Field> WgpuBackend<F> {
+    /// None if `F` is not a prime field below 2^31 or no adapter is found
+    pub fn new() -> Option<Self> {
+        let modulus = Modulus::of::<F>()?;
+        Some(Self {
+            context: context()?,
+            modulus,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn try_fold(&self, lo: &mut [F], hi: &[F], r: F) -> Result<()> {
+        let mut lhs = upload(lo);
+        let rhs = upload(hi);
+        self.context
+            .run(&self.context.fold, &self.modulus, to_u32(&r), &mut lhs, &rhs)?;
+        download(&lhs, lo);
+        Ok(())
+    }
+
+    fn try_mul_batch(&self, a: &[F], b: &[F], out: &mut [F]) -> Result<()> {
+        let mut lhs = upload(a);
+        let rhs = upload(b);
+        self.context
+            .run(&self.context.mul, &self.modulus, 0, &mut lhs, &rhs)?;
+        download(&lhs, out);
+        Ok(())
+    }
+}
+
+impl<F: Field> ArrayBackend<F> for WgpuBackend<F> {
+    fn name(&self) -> &'static str {
+        "wgpu"
+    }
+
+    fn fold(&self, lo: &mut [F], hi: &[F], r: F) {
+        let n = lo.len().min(hi.len());
+        if n < GPU_THRESHOLD || self.try_fold(&mut lo[..n], &hi[..n], r).is_err() {
+            CpuBackend.fold(lo, hi, r);
+        }
+    }
+
+    fn mul_batch(&self, a: &[F], b: &[F], out: &mut [F]) -> Result<()> {
+        check_batch_lengths(a, b, out)?;
+        if out.len() < GPU_THRESHOLD || self.try_mul_batch(a, b, out).is_err() {
+            return CpuBackend.mul_batch(a, b, out);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use longfellow_algebra::{BabyBear, BabyBear4, Fp128, Mersenne31};
+
+    #[test]
+    fn test_montgomery_constants() {
+        for m in [Modulus::of::<BabyBear>().unwrap(), Modulus::of::<Mersenne31>().unwrap()] {
+            assert_eq!(m.p.wrapping_mul(m.neg_inv), u32::MAX);
+            assert_eq!(m.r2 as u128, (1u128 << 64) % m.p as u128);
+        }
+        assert!(Modulus::of::<Fp128>().is_none());
+        assert!(Modulus::of::<BabyBear4>().is_none());
+    }
+
+    #[test]
+    fn test_gpu_kernels_match_cpu() {
+        // Machines without an adapter only exercise the constants above
+        let Some(gpu) = WgpuBackend::<BabyBear>::new() else {
+            return;
+        };
+
+        let n = GPU_THRESHOLD + 17;
+        let a: Vec<BabyBear> = (0..n as u64).map(|i| BabyBear::from_u64(i * i + 7)).collect();
+        let b: Vec<BabyBear> = (0..n as u64).map(|i| -BabyBear::from_u64(3 * i + 1)).collect();
+
+        let mut expected = vec![BabyBear::zero(); n];
+        let mut out = vec![BabyBear::zero(); n];
+        CpuBackend.mul_batch(&a, &b, &mut expected).unwrap();
+        gpu.mul_batch(&a, &b, &mut out).unwrap();
+        assert_eq!(out, expected);
+
+        let r = BabyBear::from_u64(0x1234567);
+        let mut expected = a.clone();
+        let mut folded = a;
+        CpuBackend.fold(&mut expected, &b, r);
+        gpu.fold(&mut folded, &b, r);
+        assert_eq!(folded, expected);
+    }
+}
@@ -0,0 +1,97 @@
+// Array kernels over a prime field p < 2^31
+//
+// Elements travel as canonical u32. Products use Montgomery reduction
+// with R = 2^32; WGSL has no 64-bit integers, so the 32x32 -> 64 bit
+// product is assembled from 16-bit halves.
+
+struct Params {
+    modulus: u32,
+    // -p^-1 mod 2^32
+    neg_inv: u32,
+    // R^2 mod p, to turn a Montgomery product back into a plain one
+    r2: u32,
+    len: u32,
+    challenge: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> lhs: array<u32>;
+@group(0) @binding(2) var<storage, read> rhs: array<u32>;
+
+// (lo, hi) of a * b
+fn mul_wide(a: u32, b: u32) -> vec2<u32> {
+    let a0 = a & 0xffffu;
+    let a1 = a >> 16u;
+    let b0 = b & 0xffffu;
+    let b1 = b >> 16u;
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = p01 + p10;
+    let mid_carry = select(0u, 0x10000u, mid < p01);
+    let lo = p00 + (mid << 16u);
+    let lo_carry = select(0u, 1u, lo < p00);
+    let hi = p11 + (mid >> 16u) + mid_carry + lo_carry;
+    return vec2<u32>(lo, hi);
+}
+
+// a * b * R^-1 mod p
+fn mont_mul(a: u32, b: u32) -> u32 {
+    let t = mul_wide(a, b);
+    let m = t.x * params.neg_inv;
+    let mp = mul_wide(m, params.modulus);
+    // t + m * p is divisible by R; only the carry out of the low word matters
+    let lo = t.x + mp.x;
+    let carry = select(0u, 1u, lo < t.x);
+    var r = t.y + mp.y + carry;
+    if (r >= params.modulus) {
+        r = r - params.modulus;
+    }
+    return r;
+}
+
+fn mul_mod(a: u32, b: u32) -> u32 {
+    return mont_mul(mont_mul(a, b), params.r2);
+}
+
+fn add_mod(a: u32, b: u32) -> u32 {
+    var r = a + b;
+    if (r >= params.modulus) {
+        r = r - params.modulus;
+    }
+    return r;
+}
+
+fn sub_mod(a: u32, b: u32) -> u32 {
+    if (a >= b) {
+        return a - b;
+    }
+    return a + params.modulus - b;
+}
+
+// lhs[i] <- lhs[i] + challenge * (rhs[i] - lhs[i])
+@compute @workgroup_size(256)
+fn fold(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let i = gid.x;
+    if (i >= params.len) {
+        return;
+    }
+    let f0 = lhs[i];
+    lhs[i] = add_mod(f0, mul_mod(params.challenge, sub_mod(rhs[i], f0)));
+}
+
+// lhs[i] <- lhs[i] * rhs[i]
+@compute @workgroup_size(256)
+fn mul(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let i = gid.x;
+    if (i >= params.len) {
+        return;
+    }
+    lhs[i] = mul_mod(lhs[i], rhs[i]);
+}
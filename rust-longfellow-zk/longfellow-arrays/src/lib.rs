@@ -1,13 +1,19 @@
 pub mod affine;
+pub mod backend;
 pub mod dense;
 pub mod sparse;
 pub mod eq;
 pub mod eqs;
+pub mod corners;
+#[cfg(feature = "wgpu")]
+pub mod gpu;
 
 pub use affine::*;
+pub use backend::*;
 pub use dense::*;
 pub use sparse::*;
 pub use eq::*;
 pub use eqs::*;
+pub use corners::*;
 
 pub type CornerIndex = usize;
\ No newline at end of file
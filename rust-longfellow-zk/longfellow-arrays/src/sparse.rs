@@ -2,6 +2,7 @@ use crate::affine::affine_interpolation_conditional;
 use crate::CornerIndex;
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
 use std::cmp::Ordering;
 use std::fmt::Debug;
 
@@ -220,6 +221,267 @@ impl<F: Field> Sparse<F> {
     }
 }
 
+/// Independent accumulators in `CsrRow::dot`, enough to keep the field
+/// multiplier pipeline busy and to let the compiler vectorize the gathers
+const CSR_DOT_LANES: usize = 4;
+
+/// Sparse matrix in compressed sparse row form
+///
+/// Column indices and values live in two flat arrays, and `row_ptr[i]..
+/// row_ptr[i + 1]` delimits row i in both, so a row is a pair of contiguous
+/// slices. Within a row columns are strictly increasing and values non-zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsrMatrix<F: Field> {
+    num_cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<F>,
+}
+
+/// Borrowed view of one row of a `CsrMatrix`
+#[derive(Clone, Copy, Debug)]
+pub struct CsrRow<'a, F: Field> {
+    cols: &'a [usize],
+    values: &'a [F],
+}
+
+impl<'a, F: Field> CsrRow<'a, F> {
+    pub fn cols(&self) -> &'a [usize] {
+        self.cols
+    }
+
+    pub fn values(&self) -> &'a [F] {
+        self.values
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cols.is_empty()
+    }
+
+    /// (column, value) pairs in increasing column order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, F)> + 'a {
+        self.cols.iter().copied().zip(self.values.iter().copied())
+    }
+
+    /// Inner product with a dense vector; panics if a column is out of range
+    pub fn dot(&self, vector: &[F]) -> F {
+        let mut acc = [F::zero(); CSR_DOT_LANES];
+        let cols = self.cols.chunks_exact(CSR_DOT_LANES);
+        let values = self.values.chunks_exact(CSR_DOT_LANES);
+        let (col_tail, value_tail) = (cols.remainder(), values.remainder());
+
+        for (c, v) in cols.zip(values) {
+            for ((a, &col), &val) in acc.iter_mut().zip(c).zip(v) {
+                *a += val * vector[col];
+            }
+        }
+        for (&c, &v) in col_tail.iter().zip(value_tail.iter()) {
+            acc[0] += v * vector[c];
+        }
+
+        acc.iter().fold(F::zero(), |sum, &a| sum + a)
+    }
+}
+
+impl<F: Field> CsrMatrix<F> {
+    /// Empty matrix with no rows
+    pub fn new(num_cols: usize) -> Self {
+        Self {
+            num_cols,
+            row_ptr: vec![0],
+            col_idx: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Build from (row, col, value) triples in any order; repeated
+    /// positions are summed and zeros dropped
+    pub fn from_triplets(
+        num_rows: usize,
+        num_cols: usize,
+        triplets: impl IntoIterator<Item = (usize, usize, F)>,
+    ) -> Result<Self> {
+        let mut triplets: Vec<(usize, usize, F)> = triplets.into_iter().collect();
+        if let Some(&(row, col, _)) = triplets
+            .iter()
+            .find(|&&(row, col, _)| row >= num_rows || col >= num_cols)
+        {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Entry ({}, {}) out of bounds for {}x{} matrix",
+                row, col, num_rows, num_cols
+            )));
+        }
+        triplets.sort_by_key(|&(row, col, _)| (row, col));
+
+        let mut matrix = Self::new(num_cols);
+        let mut start = 0;
+        for row in 0..num_rows {
+            let end = start + triplets[start..].partition_point(|&(r, _, _)| r == row);
+            matrix.push_row(triplets[start..end].iter().map(|&(_, col, v)| (col, v)));
+            start = end;
+        }
+        Ok(matrix)
+    }
+
+    /// Append a row given as (column, value) pairs in any order. Repeated
+    /// columns are summed and zeros dropped; the column count grows to fit.
+    pub fn push_row(&mut self, entries: impl IntoIterator<Item = (usize, F)>) {
+        let mut entries: Vec<(usize, F)> = entries.into_iter().collect();
+        entries.sort_by_key(|&(col, _)| col);
+
+        let row_start = self.col_idx.len();
+        for (col, v) in entries {
+            if self.col_idx.len() > row_start && self.col_idx.last() == Some(&col) {
+                *self.values.last_mut().unwrap() += v;
+            } else {
+                self.col_idx.push(col);
+                self.values.push(v);
+            }
+        }
+
+        // Compact away entries that cancelled or were zero to begin with
+        let mut write = row_start;
+        for read in row_start..self.col_idx.len() {
+            if self.values[read] != F::zero() {
+                self.col_idx[write] = self.col_idx[read];
+                self.values[write] = self.values[read];
+                write += 1;
+            }
+        }
+        self.col_idx.truncate(write);
+        self.values.truncate(write);
+
+        if let Some(&last) = self.col_idx[row_start..].last() {
+            self.num_cols = self.num_cols.max(last + 1);
+        }
+        self.row_ptr.push(self.col_idx.len());
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.row_ptr.len() - 1
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Number of stored non-zero entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn row_ptr(&self) -> &[usize] {
+        &self.row_ptr
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_idx
+    }
+
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    pub fn row(&self, row: usize) -> CsrRow<'_, F> {
+        let range = self.row_ptr[row]..self.row_ptr[row + 1];
+        CsrRow {
+            cols: &self.col_idx[range.clone()],
+            values: &self.values[range],
+        }
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = CsrRow<'_, F>> + '_ {
+        (0..self.num_rows()).map(move |row| self.row(row))
+    }
+
+    /// All entries as (row, col, value), row-major
+    pub fn triplets(&self) -> impl Iterator<Item = (usize, usize, F)> + '_ {
+        self.rows()
+            .enumerate()
+            .flat_map(|(row, r)| r.iter().map(move |(col, v)| (row, col, v)))
+    }
+
+    /// Entry (row, col), zero if not stored
+    pub fn get(&self, row: usize, col: usize) -> F {
+        let r = self.row(row);
+        r.cols
+            .binary_search(&col)
+            .map(|idx| r.values[idx])
+            .unwrap_or(F::zero())
+    }
+
+    /// A * x, one rayon task per row
+    pub fn mul_vec(&self, vector: &[F]) -> Result<Vec<F>> {
+        if vector.len() != self.num_cols {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Vector length {} doesn't match {} columns",
+                vector.len(),
+                self.num_cols
+            )));
+        }
+
+        Ok((0..self.num_rows())
+            .into_par_iter()
+            .map(|row| self.row(row).dot(vector))
+            .collect())
+    }
+
+    /// A^T in CSR form, so that repeated A^T * y products become row-wise
+    /// dot products
+    pub fn transpose(&self) -> Self {
+        let mut row_ptr = vec![0usize; self.num_cols + 1];
+        for &col in &self.col_idx {
+            row_ptr[col + 1] += 1;
+        }
+        for i in 0..self.num_cols {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        // Rows of A are visited in order, so each row of A^T comes out
+        // with increasing columns
+        let mut next = row_ptr[..self.num_cols].to_vec();
+        let mut col_idx = vec![0usize; self.nnz()];
+        let mut values = vec![F::zero(); self.nnz()];
+        for (row, r) in self.rows().enumerate() {
+            for (col, v) in r.iter() {
+                col_idx[next[col]] = row;
+                values[next[col]] = v;
+                next[col] += 1;
+            }
+        }
+
+        Self {
+            num_cols: self.num_rows(),
+            row_ptr,
+            col_idx,
+            values,
+        }
+    }
+
+    /// A^T * y, the combination of rows weighted by `vector`
+    pub fn transpose_mul_vec(&self, vector: &[F]) -> Result<Vec<F>> {
+        if vector.len() != self.num_rows() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Vector length {} doesn't match {} rows",
+                vector.len(),
+                self.num_rows()
+            )));
+        }
+
+        let mut out = vec![F::zero(); self.num_cols];
+        for (r, &y) in self.rows().zip(vector.iter()) {
+            for (col, v) in r.iter() {
+                out[col] += v * y;
+            }
+        }
+        Ok(out)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,4 +682,67 @@ mod tests {
         assert_eq!(sparse.n(), 2);
         assert_eq!(sparse.len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_csr_from_triplets() {
+        let triplets = vec![
+            (2, 1, TestField(5)),
+            (0, 3, TestField(7)),
+            (0, 0, TestField(1)),
+            (2, 1, TestField(96)), // cancels the first entry
+            (0, 3, TestField(2)),
+            (1, 2, TestField(0)),
+        ];
+        let m = CsrMatrix::from_triplets(3, 4, triplets).unwrap();
+
+        assert_eq!(m.num_rows(), 3);
+        assert_eq!(m.nnz(), 2);
+        assert_eq!(m.row_ptr(), &[0, 2, 2, 2]);
+        assert_eq!(m.get(0, 3), TestField(9));
+        assert_eq!(m.get(2, 1), TestField(0));
+        assert!(m.row(1).is_empty());
+        assert_eq!(
+            m.triplets().collect::<Vec<_>>(),
+            vec![(0, 0, TestField(1)), (0, 3, TestField(9))]
+        );
+
+        assert!(CsrMatrix::from_triplets(2, 2, vec![(0, 2, TestField(1))]).is_err());
+    }
+
+    #[test]
+    fn test_csr_products_match_dense() {
+        let (rows, cols) = (9, 13);
+        let mut m = CsrMatrix::new(cols);
+        for i in 0..rows {
+            // Rows of varying density, longer than the dot product lanes
+            m.push_row(
+                (0..cols)
+                    .filter(|j| (i + j) % 3 != 0)
+                    .map(|j| (j, TestField::from_u64((i * cols + j) as u64))),
+            );
+        }
+        let x: Vec<TestField> = (0..cols).map(|j| TestField::from_u64(j as u64 + 3)).collect();
+        let y: Vec<TestField> = (0..rows).map(|i| TestField::from_u64(2 * i as u64 + 1)).collect();
+
+        let expected_ax: Vec<TestField> = (0..rows)
+            .map(|i| (0..cols).fold(TestField::zero(), |acc, j| acc + m.get(i, j) * x[j]))
+            .collect();
+        let expected_aty: Vec<TestField> = (0..cols)
+            .map(|j| (0..rows).fold(TestField::zero(), |acc, i| acc + m.get(i, j) * y[i]))
+            .collect();
+
+        assert_eq!(m.mul_vec(&x).unwrap(), expected_ax);
+        assert_eq!(m.transpose_mul_vec(&y).unwrap(), expected_aty);
+        assert!(m.mul_vec(&y).is_err());
+
+        let t = m.transpose();
+        assert_eq!((t.num_rows(), t.num_cols(), t.nnz()), (cols, rows, m.nnz()));
+        assert_eq!(t.mul_vec(&y).unwrap(), expected_aty);
+        assert_eq!(t.transpose(), m);
+
+        // Rows pushed out of order are sorted, and widen the matrix
+        m.push_row(vec![(20, TestField(4)), (2, TestField(1))]);
+        assert_eq!(m.num_cols(), 21);
+        assert_eq!(m.row(rows).cols(), &[2, 20]);
+    }
+}
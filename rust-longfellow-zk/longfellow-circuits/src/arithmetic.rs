@@ -2,7 +2,9 @@
 
 use crate::{CircuitBuilder, Constraint, utils};
 use longfellow_algebra::traits::Field;
-use longfellow_core::Result;
+use longfellow_core::{LongfellowError, Result};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Zero};
 
 /// Integer arithmetic circuit
 pub struct IntegerArithmeticCircuit<F: Field, C: CircuitBuilder<F>> {
@@ -304,6 +306,418 @@ impl<F: Field, C: CircuitBuilder<F>> VectorCircuit<F, C> {
     }
 }
 
+/// Element of a `NonNativeField` as limb variables, least significant first
+#[derive(Clone, Debug)]
+pub struct NonNativeElement {
+    pub limbs: Vec<usize>,
+}
+
+/// Arithmetic modulo an integer other than the circuit field's modulus
+///
+/// Elements are `num_limbs` limbs of `limb_bits` bits, each range checked,
+/// so values lie below 2^(limb_bits * num_limbs) but need not be reduced.
+/// A relation is checked as an integer identity against a witnessed
+/// multiple of the modulus: limb products are summed per column and the
+/// columns carried into each other through range-checked carries, so no
+/// column wraps around the circuit field.
+///
+/// Operations take the circuit as an argument, so one field serves any
+/// number of gadgets sharing a builder.
+#[derive(Clone, Debug)]
+pub struct NonNativeField {
+    modulus: BigUint,
+    limb_bits: usize,
+    num_limbs: usize,
+}
+
+impl NonNativeField {
+    /// Limbs sized for circuit field `F`
+    pub fn new<F: Field>(modulus: BigUint) -> Result<Self> {
+        // Two limbs multiplied, summed over a column and carried must stay
+        // well inside the circuit field
+        let limb_bits = ((F::MODULUS_BITS as usize).saturating_sub(16) / 2).clamp(8, 52);
+        Self::with_limb_bits(modulus, limb_bits)
+    }
+    
+    /// Limbs of `limb_bits` bits; relations too wide for the circuit
+    /// field are rejected when they are added
+    pub fn with_limb_bits(modulus: BigUint, limb_bits: usize) -> Result<Self> {
+        if modulus < BigUint::from(2u32) {
+            return Err(LongfellowError::InvalidParameter(
+                "Non-native modulus must be at least 2".to_string()
+            ));
+        }
+        if limb_bits == 0 || limb_bits > 62 {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Invalid limb size: {} bits", limb_bits)
+            ));
+        }
+        
+        let num_limbs = (modulus.bits() as usize).div_ceil(limb_bits);
+        Ok(Self {
+            modulus,
+            limb_bits,
+            num_limbs,
+        })
+    }
+    
+    /// The modulus
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+    
+    /// Bits per limb
+    pub fn limb_bits(&self) -> usize {
+        self.limb_bits
+    }
+    
+    /// Limbs per element
+    pub fn num_limbs(&self) -> usize {
+        self.num_limbs
+    }
+    
+    /// Limb `i` of an integer
+    pub fn limb(&self, value: &BigUint, i: usize) -> BigUint {
+        (value >> (i * self.limb_bits)) & ((BigUint::one() << self.limb_bits) - 1u32)
+    }
+    
+    /// A fresh element with range-checked limbs, holding `value` if given
+    pub fn alloc<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        value: Option<&BigUint>,
+    ) -> Result<NonNativeElement> {
+        let mut limbs = Vec::with_capacity(self.num_limbs);
+        for i in 0..self.num_limbs {
+            let var = circuit.alloc_var();
+            if let Some(value) = value {
+                circuit.assign(var, field_from_biguint(&self.limb(value, i)));
+            }
+            circuit.add_constraint(Constraint::Range { var, bits: self.limb_bits })?;
+            limbs.push(var);
+        }
+        Ok(NonNativeElement { limbs })
+    }
+    
+    /// A constant element
+    pub fn constant<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        value: &BigUint,
+    ) -> Result<NonNativeElement> {
+        let limbs = (0..self.num_limbs)
+            .map(|i| utils::const_gate(circuit, field_from_biguint(&self.limb(value, i))))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(NonNativeElement { limbs })
+    }
+    
+    /// The element held by boolean bits, most significant first
+    pub fn from_bits<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        bits: &[usize],
+    ) -> Result<NonNativeElement> {
+        if bits.len() > self.limb_bits * self.num_limbs {
+            return Err(LongfellowError::InvalidParameter(
+                format!("{} bits do not fit {} limbs", bits.len(), self.num_limbs)
+            ));
+        }
+        
+        let limbs = (0..self.num_limbs)
+            .map(|i| {
+                let terms: Vec<(usize, F)> = (i * self.limb_bits..((i + 1) * self.limb_bits).min(bits.len()))
+                    .map(|j| (bits[bits.len() - 1 - j], F::from_u64(1u64 << (j - i * self.limb_bits))))
+                    .collect();
+                crate::hash::affine(circuit, &terms, F::zero())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(NonNativeElement { limbs })
+    }
+    
+    /// The integer an element holds, not necessarily reduced
+    pub fn value<F: Field, C: CircuitBuilder<F>>(&self, circuit: &C, x: &NonNativeElement) -> Option<BigUint> {
+        x.limbs.iter().rev().try_fold(BigUint::zero(), |acc, &limb| {
+            Some((acc << self.limb_bits) + biguint_from_field(&circuit.value(limb)?))
+        })
+    }
+    
+    /// The value of an element reduced modulo the modulus
+    pub fn reduced<F: Field, C: CircuitBuilder<F>>(&self, circuit: &C, x: &NonNativeElement) -> Option<BigUint> {
+        Some(self.value(circuit, x)? % &self.modulus)
+    }
+    
+    /// a + b
+    pub fn add<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        a: &NonNativeElement,
+        b: &NonNativeElement,
+    ) -> Result<NonNativeElement> {
+        let sum = self.reduced(circuit, a).zip(self.reduced(circuit, b)).map(|(a, b)| (a + b) % &self.modulus);
+        let c = self.alloc(circuit, sum.as_ref())?;
+        self.assert_zero(circuit, &[], &[(1, a), (1, b), (-1, &c)], &BigInt::zero())?;
+        Ok(c)
+    }
+    
+    /// a - b
+    pub fn sub<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        a: &NonNativeElement,
+        b: &NonNativeElement,
+    ) -> Result<NonNativeElement> {
+        let difference = self
+            .reduced(circuit, a)
+            .zip(self.reduced(circuit, b))
+            .map(|(a, b)| (a + &self.modulus - b) % &self.modulus);
+        let c = self.alloc(circuit, difference.as_ref())?;
+        self.assert_zero(circuit, &[], &[(1, a), (-1, b), (-1, &c)], &BigInt::zero())?;
+        Ok(c)
+    }
+    
+    /// a * b
+    pub fn mul<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        a: &NonNativeElement,
+        b: &NonNativeElement,
+    ) -> Result<NonNativeElement> {
+        let product = self.reduced(circuit, a).zip(self.reduced(circuit, b)).map(|(a, b)| a * b % &self.modulus);
+        let c = self.alloc(circuit, product.as_ref())?;
+        self.assert_zero(circuit, &[(1, a, b)], &[(-1, &c)], &BigInt::zero())?;
+        Ok(c)
+    }
+    
+    /// Constrain a = b modulo the modulus
+    pub fn assert_equal<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        a: &NonNativeElement,
+        b: &NonNativeElement,
+    ) -> Result<()> {
+        self.assert_zero(circuit, &[], &[(1, a), (-1, b)], &BigInt::zero())
+    }
+    
+    /// Constrain an element to hold an integer below the modulus, so it
+    /// has a single encoding
+    pub fn assert_reduced<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C, x: &NonNativeElement) -> Result<()> {
+        // x + d = m - 1 with d range checked
+        let max = &self.modulus - 1u32;
+        let slack = self.value(circuit, x).map(|x| if x > max { BigUint::zero() } else { &max - x });
+        let slack = self.alloc(circuit, slack.as_ref())?;
+        self.assert_integer_zero(circuit, &[], &[(1, x), (1, &slack)], &-BigInt::from(max))
+    }
+    
+    /// Constrain sum(c a b) + sum(c x) + constant to be a multiple of the
+    /// modulus
+    pub fn assert_zero<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        products: &[(i64, &NonNativeElement, &NonNativeElement)],
+        terms: &[(i64, &NonNativeElement)],
+        constant: &BigInt,
+    ) -> Result<()> {
+        self.relation(circuit, products, terms, constant, false)
+    }
+    
+    /// Constrain sum(c a b) + sum(c x) + constant to be zero as an integer
+    pub fn assert_integer_zero<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        products: &[(i64, &NonNativeElement, &NonNativeElement)],
+        terms: &[(i64, &NonNativeElement)],
+        constant: &BigInt,
+    ) -> Result<()> {
+        self.relation(circuit, products, terms, constant, true)
+    }
+    
+    fn relation<F: Field, C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        products: &[(i64, &NonNativeElement, &NonNativeElement)],
+        terms: &[(i64, &NonNativeElement)],
+        constant: &BigInt,
+        exact: bool,
+    ) -> Result<()> {
+        let bits = self.limb_bits;
+        let limb_max = (BigUint::one() << bits) - 1u32;
+        let max = |x: &NonNativeElement| (BigUint::one() << (bits * x.limbs.len())) - 1u32;
+        
+        // Offset the sum by a multiple of the modulus covering its most
+        // negative value, so the quotient is non-negative
+        let (mut pos, mut neg) = (BigUint::zero(), BigUint::zero());
+        let mut add_bound = |coeff: i64, bound: BigUint| {
+            if coeff > 0 {
+                pos += bound * coeff.unsigned_abs();
+            } else {
+                neg += bound * coeff.unsigned_abs();
+            }
+        };
+        for &(c, a, b) in products {
+            add_bound(c, max(a) * max(b));
+        }
+        for &(c, x) in terms {
+            add_bound(c, max(x));
+        }
+        if constant.sign() == Sign::Minus {
+            neg += constant.magnitude();
+        } else {
+            pos += constant.magnitude();
+        }
+        let (offset, quotient_max) = if exact {
+            (BigUint::zero(), BigUint::zero())
+        } else {
+            let offset = (&neg + &self.modulus - 1u32) / &self.modulus * &self.modulus;
+            let quotient_max = (pos + &offset) / &self.modulus;
+            (offset, quotient_max)
+        };
+        let shifted = constant + BigInt::from(offset);
+        
+        // The integer value of the shifted sum, when every input has one
+        let total = (|| {
+            let mut total = shifted.clone();
+            for &(c, a, b) in products {
+                total += BigInt::from(self.value(circuit, a)? * self.value(circuit, b)?) * c;
+            }
+            for &(c, x) in terms {
+                total += BigInt::from(self.value(circuit, x)?) * c;
+            }
+            Some(total)
+        })();
+        
+        let quotient_bits = quotient_max.bits() as usize;
+        let num_quotient = quotient_bits.div_ceil(bits);
+        let quotient = match &total {
+            Some(total) if !exact => Some(total.to_biguint().unwrap_or_default() / &self.modulus),
+            _ => None,
+        };
+        let mut quotient_limbs = Vec::with_capacity(num_quotient);
+        for i in 0..num_quotient {
+            let var = circuit.alloc_var();
+            if let Some(q) = &quotient {
+                circuit.assign(var, field_from_biguint(&self.limb(q, i)));
+            }
+            let limb_bits = if i + 1 == num_quotient { quotient_bits - i * bits } else { bits };
+            circuit.add_constraint(Constraint::Range { var, bits: limb_bits })?;
+            quotient_limbs.push(var);
+        }
+        
+        // Columns as integer combinations of limb variables plus a constant,
+        // with a bound on their absolute value
+        let modulus_limbs: Vec<BigUint> = (0..self.num_limbs).map(|i| self.limb(&self.modulus, i)).collect();
+        let (sign, magnitude) = (shifted.sign(), shifted.magnitude().clone());
+        let constant_limbs = (magnitude.bits() as usize).div_ceil(bits);
+        let num_columns = products
+            .iter()
+            .map(|(_, a, b)| a.limbs.len() + b.limbs.len() - 1)
+            .chain(terms.iter().map(|(_, x)| x.limbs.len()))
+            .chain([if num_quotient == 0 { 0 } else { num_quotient + self.num_limbs - 1 }, constant_limbs, 1])
+            .max()
+            .unwrap_or(1);
+        let mut columns: Vec<Vec<(usize, BigInt)>> = vec![Vec::new(); num_columns];
+        let mut constants = vec![BigInt::zero(); num_columns];
+        let mut bounds = vec![BigUint::zero(); num_columns];
+        
+        for &(c, a, b) in products {
+            for (i, &x) in a.limbs.iter().enumerate() {
+                for (j, &y) in b.limbs.iter().enumerate() {
+                    let product = utils::mul_gate(circuit, x, y)?;
+                    columns[i + j].push((product, BigInt::from(c)));
+                    bounds[i + j] += &limb_max * &limb_max * c.unsigned_abs();
+                }
+            }
+        }
+        for &(c, x) in terms {
+            for (i, &limb) in x.limbs.iter().enumerate() {
+                columns[i].push((limb, BigInt::from(c)));
+                bounds[i] += &limb_max * c.unsigned_abs();
+            }
+        }
+        for (i, &q) in quotient_limbs.iter().enumerate() {
+            for (j, m) in modulus_limbs.iter().enumerate() {
+                columns[i + j].push((q, -BigInt::from(m.clone())));
+                bounds[i + j] += &limb_max * m;
+            }
+        }
+        for i in 0..constant_limbs {
+            let limb = self.limb(&magnitude, i);
+            bounds[i] += &limb;
+            constants[i] = BigInt::from_biguint(sign, limb);
+        }
+        
+        // Carries are signed; they are range checked shifted up by `carry_offset`
+        let column_max = bounds.iter().max().cloned().unwrap_or_default();
+        let carry_bits = (&column_max / &limb_max + 1u32).bits() as usize + 1;
+        let carry_offset = BigInt::one() << (carry_bits - 1);
+        let field_bits = F::MODULUS_BITS as usize;
+        if carry_bits > 63 || column_max.bits() as usize + 2 >= field_bits || bits + carry_bits + 2 >= field_bits {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Non-native relation with {}-bit columns does not fit the circuit field",
+                column_max.bits()
+            )));
+        }
+        
+        let base = BigInt::one() << bits;
+        let mut carry_in: Option<(usize, BigInt)> = None;
+        for k in 0..num_columns {
+            let column_value = total.as_ref().map(|_| {
+                columns[k].iter().fold(constants[k].clone(), |acc, (var, coeff)| {
+                    acc + coeff * BigInt::from(circuit.value(*var).map_or_else(BigUint::zero, |v| biguint_from_field(&v)))
+                })
+            });
+            
+            let mut coeffs: Vec<(usize, F)> = columns[k]
+                .iter()
+                .map(|(var, coeff)| (*var, field_from_bigint(coeff)))
+                .collect();
+            let mut rhs = -constants[k].clone();
+            let mut carry_value = column_value;
+            if let Some((var, value)) = &carry_in {
+                coeffs.push((*var, F::one()));
+                rhs += &carry_offset;
+                carry_value = carry_value.map(|v| v + value);
+            }
+            
+            if k + 1 < num_columns {
+                let carry = carry_value.map(|v| v / &base);
+                let var = circuit.alloc_var();
+                if let Some(carry) = &carry {
+                    circuit.assign(var, field_from_bigint(&(carry + &carry_offset)));
+                }
+                circuit.add_constraint(Constraint::Range { var, bits: carry_bits })?;
+                coeffs.push((var, -field_from_bigint::<F>(&base)));
+                rhs -= &base * &carry_offset;
+                carry_in = Some((var, carry.unwrap_or_default()));
+            }
+            
+            circuit.add_constraint(Constraint::Linear {
+                coeffs,
+                constant: field_from_bigint(&rhs),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// A circuit field element with the given integer value
+pub(crate) fn field_from_biguint<F: Field>(value: &BigUint) -> F {
+    F::from_bytes_le(&value.to_bytes_le()).expect("value below the circuit field's modulus")
+}
+
+fn field_from_bigint<F: Field>(value: &BigInt) -> F {
+    let magnitude = field_from_biguint::<F>(value.magnitude());
+    if value.sign() == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// The integer value of a circuit field element
+pub(crate) fn biguint_from_field<F: Field>(value: &F) -> BigUint {
+    BigUint::from_bytes_le(&value.to_canonical_bytes())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,4 +789,57 @@ mod tests {
         let dot = vec_circuit.dot_product(&a, &b).unwrap();
         assert!(dot >= 3); // New variable
     }
+    
+    #[test]
+    fn test_non_native_mul() {
+        // The P-256 base field
+        let p = BigUint::parse_bytes(b"ffffffff00000001000000000000000000000000ffffffffffffffffffffffff", 16).unwrap();
+        let field = NonNativeField::new::<Fp128>(p.clone()).unwrap();
+        let x = &p - 5u32;
+        let y = BigUint::parse_bytes(b"123456789abcdef0123456789abcdef0123456789abcdef", 16).unwrap();
+        let product = &x * &y % &p;
+        
+        for (claimed, ok) in [(product.clone(), true), (product + 1u32, false)] {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let a = field.alloc(&mut circuit, Some(&x)).unwrap();
+            let b = field.alloc(&mut circuit, Some(&y)).unwrap();
+            let c = field.alloc(&mut circuit, Some(&claimed)).unwrap();
+            field.assert_zero(&mut circuit, &[(1, &a, &b)], &[(-1, &c)], &BigInt::zero()).unwrap();
+            assert_eq!(circuit.is_satisfied().unwrap(), ok);
+        }
+    }
+    
+    #[test]
+    fn test_non_native_operations() {
+        // A modulus wider than the circuit field, with small limbs
+        let m = (BigUint::one() << 200u32) - 75u32;
+        let field = NonNativeField::with_limb_bits(m.clone(), 40).unwrap();
+        let x = &m - 1u32;
+        let y = BigUint::from(0xdeadbeefu64) << 150u32;
+        
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let a = field.alloc(&mut circuit, Some(&x)).unwrap();
+        let b = field.alloc(&mut circuit, Some(&y)).unwrap();
+        let sum = field.add(&mut circuit, &a, &b).unwrap();
+        let difference = field.sub(&mut circuit, &b, &a).unwrap();
+        let product = field.mul(&mut circuit, &a, &b).unwrap();
+        field.assert_reduced(&mut circuit, &a).unwrap();
+        assert_eq!(field.value(&circuit, &sum).unwrap(), (&x + &y) % &m);
+        assert_eq!(field.value(&circuit, &difference).unwrap(), &y + 1u32);
+        assert_eq!(field.value(&circuit, &product).unwrap(), &m - &y);
+        assert!(circuit.is_satisfied().unwrap());
+        
+        // m itself has limbs in range but is not reduced
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let a = field.alloc(&mut circuit, Some(&m)).unwrap();
+        field.assert_reduced(&mut circuit, &a).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+        
+        // Bits pack into limbs
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let bytes = y.to_bytes_be();
+        let bits = crate::hash::alloc_bytes(&mut circuit, &bytes).unwrap();
+        let packed = field.from_bits(&mut circuit, &bits).unwrap();
+        assert_eq!(field.value(&circuit, &packed).unwrap(), y);
+    }
 }
\ No newline at end of file
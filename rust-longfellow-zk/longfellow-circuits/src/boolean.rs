@@ -1,8 +1,9 @@
 /// Boolean circuits and logic operations
 
+use crate::arithmetic::biguint_from_field;
 use crate::{CircuitBuilder, Constraint, gadgets, utils};
 use longfellow_algebra::traits::Field;
-use longfellow_core::Result;
+use longfellow_core::{LongfellowError, Result};
 
 /// Boolean formula circuit
 pub struct BooleanFormulaCircuit<F: Field, C: CircuitBuilder<F>> {
@@ -249,6 +250,224 @@ impl<F: Field, C: CircuitBuilder<F>> BitwiseCircuit<F, C> {
     }
 }
 
+/// A machine word of `N` bit variables, least significant first
+///
+/// Words stay unpacked: bitwise operations act bit by bit, rotations and
+/// shifts only rewire, and `pack` and `unpack` convert to and from one
+/// variable holding the integer. XOR and AND cost one product per bit,
+/// NOT none, and `add` one linear constraint over the result and carry
+/// bits. Bits must be boolean, which `alloc`, `unpack` and every
+/// operation ensure for the words they return. `N` is at most 64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Word<const N: usize> {
+    /// Bit variables, least significant first
+    pub bits: [usize; N],
+}
+
+/// A 32-bit word, as SHA-256 uses
+pub type Word32 = Word<32>;
+
+/// A 64-bit word, as Keccak lanes are
+pub type Word64 = Word<64>;
+
+impl<const N: usize> Word<N> {
+    const FITS_U64: () = assert!(N <= 64, "words are at most 64 bits");
+    
+    /// Wrap bit variables that are already boolean
+    pub fn from_bits(bits: [usize; N]) -> Self {
+        let () = Self::FITS_U64;
+        Self { bits }
+    }
+    
+    /// The constant `value` wired from shared `zero` and `one` bits
+    pub fn from_constant(zero: usize, one: usize, value: u64) -> Self {
+        Self::from_bits(std::array::from_fn(|j| if value >> j & 1 == 1 { one } else { zero }))
+    }
+    
+    /// Allocate a word, with `value` if given, constrained boolean
+    pub fn alloc<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, value: Option<u64>) -> Result<Self> {
+        let word = Self::from_bits(std::array::from_fn(|_| circuit.alloc_var()));
+        for (j, &bit) in word.bits.iter().enumerate() {
+            if let Some(value) = value {
+                circuit.assign(bit, F::from_u64(value >> j & 1));
+            }
+            circuit.add_constraint(Constraint::Boolean { var: bit })?;
+        }
+        Ok(word)
+    }
+    
+    /// The constant `value`, over two fresh constant bits
+    pub fn constant<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, value: u64) -> Result<Self> {
+        let zero = utils::const_gate(circuit, F::zero())?;
+        let one = utils::const_gate(circuit, F::one())?;
+        Ok(Self::from_constant(zero, one, value))
+    }
+    
+    /// Integer value, once every bit has a boolean value
+    pub fn value<F: Field, C: CircuitBuilder<F>>(&self, circuit: &C) -> Option<u64> {
+        self.bits
+            .iter()
+            .rev()
+            .try_fold(0u64, |acc, &bit| Some(acc << 1 | bit_value(circuit, bit)? as u64))
+    }
+    
+    /// One variable equal to the word's integer value
+    pub fn pack<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C) -> Result<usize> {
+        let terms: Vec<(usize, F)> = self.bits.iter().copied().zip(powers_of_two()).collect();
+        crate::hash::affine(circuit, &terms, F::zero())
+    }
+    
+    /// The bits of `var`, which must hold an integer below 2^N
+    pub fn unpack<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, var: usize) -> Result<Self> {
+        check_width::<F>(N)?;
+        let value = circuit.value(var).map(|v| {
+            let bytes = v.to_canonical_bytes();
+            (0..N).fold(0u64, |acc, j| acc | ((bytes.get(j / 8).map_or(0, |b| b >> (j % 8) & 1) as u64) << j))
+        });
+        let word = Self::alloc(circuit, value)?;
+        let packed = word.pack(circuit)?;
+        utils::assert_equal(circuit, packed, var)?;
+        Ok(word)
+    }
+    
+    /// Bitwise XOR
+    pub fn xor<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C, other: &Self) -> Result<Self> {
+        let mut bits = [0; N];
+        for (j, bit) in bits.iter_mut().enumerate() {
+            *bit = xor_bits(circuit, self.bits[j], other.bits[j])?;
+        }
+        Ok(Self::from_bits(bits))
+    }
+    
+    /// Bitwise AND
+    pub fn and<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C, other: &Self) -> Result<Self> {
+        let mut bits = [0; N];
+        for (j, bit) in bits.iter_mut().enumerate() {
+            *bit = utils::mul_gate(circuit, self.bits[j], other.bits[j])?;
+        }
+        Ok(Self::from_bits(bits))
+    }
+    
+    /// NOT self AND other, at the cost of one AND
+    pub fn and_not<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C, other: &Self) -> Result<Self> {
+        let mut bits = [0; N];
+        for (j, bit) in bits.iter_mut().enumerate() {
+            // (1 - a) b = b - ab
+            let both = utils::mul_gate(circuit, self.bits[j], other.bits[j])?;
+            *bit = crate::hash::affine(circuit, &[(other.bits[j], F::one()), (both, -F::one())], F::zero())?;
+        }
+        Ok(Self::from_bits(bits))
+    }
+    
+    /// Bitwise NOT
+    pub fn not<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C) -> Result<Self> {
+        self.xor_constant(circuit, u64::MAX)
+    }
+    
+    /// XOR with a constant, which only flips the bits it has set
+    pub fn xor_constant<F: Field, C: CircuitBuilder<F>>(&self, circuit: &mut C, value: u64) -> Result<Self> {
+        let mut bits = self.bits;
+        for (j, bit) in bits.iter_mut().enumerate().filter(|&(j, _)| value >> j & 1 == 1) {
+            *bit = crate::hash::affine(circuit, &[(self.bits[j], -F::one())], F::one())?;
+        }
+        Ok(Self::from_bits(bits))
+    }
+    
+    /// Rotation towards the least significant bit
+    pub fn rotate_right(&self, n: usize) -> Self {
+        Self::from_bits(std::array::from_fn(|j| self.bits[(j + n) % N]))
+    }
+    
+    /// Rotation towards the most significant bit
+    pub fn rotate_left(&self, n: usize) -> Self {
+        self.rotate_right(N - n % N)
+    }
+    
+    /// Logical shift towards the least significant bit, filling with the
+    /// constant bit `zero`
+    pub fn shift_right(&self, zero: usize, n: usize) -> Self {
+        Self::from_bits(std::array::from_fn(|j| if j + n < N { self.bits[j + n] } else { zero }))
+    }
+    
+    /// Logical shift towards the most significant bit, filling with the
+    /// constant bit `zero`
+    pub fn shift_left(&self, zero: usize, n: usize) -> Self {
+        Self::from_bits(std::array::from_fn(|j| if j >= n { self.bits[j - n] } else { zero }))
+    }
+    
+    /// Sum of `words` and `constant` mod 2^N: the integer sum is
+    /// decomposed into N result bits and as many carry bits as it needs
+    pub fn add<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, words: &[Self], constant: u64) -> Result<Self> {
+        let constant = constant & u64::MAX >> (64 - N);
+        let terms = words.len() + (constant != 0) as usize;
+        let carry_bits = (usize::BITS - terms.saturating_sub(1).leading_zeros()) as usize;
+        check_width::<F>(N + carry_bits)?;
+        
+        let sum = words
+            .iter()
+            .map(|word| word.value(&*circuit).map(u128::from))
+            .sum::<Option<u128>>()
+            .map(|sum| sum + constant as u128);
+        let mut coeffs = Vec::with_capacity(N * (words.len() + 1) + carry_bits);
+        let mut bits = [0; N];
+        for (i, power) in powers_of_two::<F>().take(N + carry_bits).enumerate() {
+            let bit = circuit.alloc_var();
+            if let Some(sum) = sum {
+                circuit.assign(bit, F::from_u64((sum >> i & 1) as u64));
+            }
+            circuit.add_constraint(Constraint::Boolean { var: bit })?;
+            coeffs.push((bit, -power));
+            if i < N {
+                bits[i] = bit;
+            }
+        }
+        for word in words {
+            coeffs.extend(word.bits.iter().copied().zip(powers_of_two()));
+        }
+        
+        // sum(words) + constant - result - 2^N carry = 0
+        circuit.add_constraint(Constraint::Linear {
+            coeffs,
+            constant: -F::from_u64(constant),
+        })?;
+        Ok(Self::from_bits(bits))
+    }
+}
+
+/// 1, 2, 4, ... in the field
+fn powers_of_two<F: Field>() -> impl Iterator<Item = F> {
+    std::iter::successors(Some(F::one()), |&power| Some(power + power))
+}
+
+/// Integers of `bits` bits must not wrap around the field
+fn check_width<F: Field>(bits: usize) -> Result<()> {
+    if F::CHAR_IS_TWO || (F::MODULUS_BITS as usize) <= bits {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Word arithmetic needs a field of odd characteristic and more than 2^{} elements",
+            bits
+        )));
+    }
+    Ok(())
+}
+
+/// Value of a bit variable, if assigned and boolean
+pub(crate) fn bit_value<F: Field, C: CircuitBuilder<F>>(circuit: &C, bit: usize) -> Option<bool> {
+    let value = circuit.value(bit)?;
+    if value == F::zero() {
+        Some(false)
+    } else if value == F::one() {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// a XOR b = a + b - 2ab, for boolean a and b
+pub(crate) fn xor_bits<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, a: usize, b: usize) -> Result<usize> {
+    let product = utils::mul_gate(circuit, a, b)?;
+    crate::hash::affine(circuit, &[(a, F::one()), (b, F::one()), (product, -F::from_u64(2))], F::zero())
+}
+
 /// Lookup table circuit
 pub struct LookupTableCircuit<F: Field, C: CircuitBuilder<F>> {
     circuit: C,
@@ -378,6 +597,92 @@ impl<F: Field, C: CircuitBuilder<F>> MuxCircuit<F, C> {
         
         Ok(current_level[0])
     }
+    
+    /// N-to-1 multiplexer on a selector index rather than its bits
+    pub fn mux_index(&mut self, index: usize, inputs: &[usize]) -> Result<usize> {
+        array_get(&mut self.circuit, inputs, index)
+    }
+}
+
+/// One boolean selector per position in `0..len`, set only at the value
+/// of `index`
+///
+/// The selectors sum to one and their position-weighted sum is `index`,
+/// so the circuit is unsatisfiable unless `index` is below `len`.
+pub fn one_hot<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, index: usize, len: usize) -> Result<Vec<usize>> {
+    if len == 0 {
+        return Err(LongfellowError::InvalidParameter(
+            "Cannot index an empty array".to_string()
+        ));
+    }
+    
+    let position = circuit
+        .value(index)
+        .and_then(|value| usize::try_from(biguint_from_field(&value)).ok());
+    let mut selectors = Vec::with_capacity(len);
+    for k in 0..len {
+        let selector = circuit.alloc_var();
+        if let Some(position) = position {
+            circuit.assign(selector, F::from_u64((k == position) as u64));
+        }
+        circuit.add_constraint(Constraint::Boolean { var: selector })?;
+        selectors.push(selector);
+    }
+    circuit.add_constraint(Constraint::Linear {
+        coeffs: selectors.iter().map(|&s| (s, F::one())).collect(),
+        constant: F::one(),
+    })?;
+    let mut coeffs: Vec<(usize, F)> = selectors
+        .iter()
+        .enumerate()
+        .map(|(k, &s)| (s, F::from_u64(k as u64)))
+        .collect();
+    coeffs.push((index, -F::one()));
+    circuit.add_constraint(Constraint::Linear {
+        coeffs,
+        constant: F::zero(),
+    })?;
+    Ok(selectors)
+}
+
+/// `array[index]` for an index known only to the prover
+///
+/// A one-hot selector over the positions picks the element: one product
+/// per element, and unsatisfiable unless `index` is in bounds.
+pub fn array_get<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, array: &[usize], index: usize) -> Result<usize> {
+    Ok(array_window(circuit, array, index, 1)?[0])
+}
+
+/// `array[index..index + len]` for an offset known only to the prover
+///
+/// One selector per possible offset is shared by the whole window, so a
+/// window costs `len` products per offset and reading a claim of several
+/// bytes needs no more selectors than reading one. Unsatisfiable unless
+/// the window fits in the array.
+pub fn array_window<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    array: &[usize],
+    index: usize,
+    len: usize,
+) -> Result<Vec<usize>> {
+    if len > array.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "A window of {} elements does not fit in {}",
+            len,
+            array.len()
+        )));
+    }
+    
+    let selectors = one_hot(circuit, index, array.len() - len + 1)?;
+    let mut window = Vec::with_capacity(len);
+    for j in 0..len {
+        let mut terms = Vec::with_capacity(selectors.len());
+        for (k, &selector) in selectors.iter().enumerate() {
+            terms.push((utils::mul_gate(circuit, selector, array[k + j])?, F::one()));
+        }
+        window.push(crate::hash::affine(circuit, &terms, F::zero())?);
+    }
+    Ok(window)
 }
 
 #[cfg(test)]
@@ -422,6 +727,49 @@ mod tests {
         assert!(xor_result > or_result);
     }
     
+    #[test]
+    fn test_word_operations() {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let (x, y) = (0x9e3779b9u64, 0x7f4a7c15u64);
+        let a = Word32::alloc(&mut circuit, Some(x)).unwrap();
+        let b = Word32::alloc(&mut circuit, Some(y)).unwrap();
+        let zero = utils::const_gate(&mut circuit, Fp128::zero()).unwrap();
+        
+        let xor = a.xor(&mut circuit, &b).unwrap();
+        let and = a.and(&mut circuit, &b).unwrap();
+        let and_not = a.and_not(&mut circuit, &b).unwrap();
+        let not = a.not(&mut circuit).unwrap();
+        let sum = Word32::add(&mut circuit, &[a, b, a], 0xdeadbeef).unwrap();
+        assert_eq!(xor.value(&circuit), Some(x ^ y));
+        assert_eq!(and.value(&circuit), Some(x & y));
+        assert_eq!(and_not.value(&circuit), Some(!x & y & 0xffffffff));
+        assert_eq!(not.value(&circuit), Some(!x & 0xffffffff));
+        assert_eq!(sum.value(&circuit), Some((2 * x + y + 0xdeadbeef) % (1 << 32)));
+        assert_eq!(a.rotate_right(7).value(&circuit), Some((x as u32).rotate_right(7) as u64));
+        assert_eq!(a.rotate_left(7).value(&circuit), Some((x as u32).rotate_left(7) as u64));
+        assert_eq!(a.shift_right(zero, 3).value(&circuit), Some(x >> 3));
+        assert_eq!(a.shift_left(zero, 3).value(&circuit), Some(x << 3 & 0xffffffff));
+        
+        // Packing round-trips through one variable
+        let packed = sum.pack(&mut circuit).unwrap();
+        let unpacked = Word32::unpack(&mut circuit, packed).unwrap();
+        assert_eq!(unpacked.value(&circuit), sum.value(&circuit));
+        
+        // 64-bit sums wrap and XOR with a constant only flips bits
+        let c = Word64::constant(&mut circuit, u64::MAX).unwrap();
+        let d = Word64::alloc(&mut circuit, Some(5)).unwrap();
+        let wrapped = Word64::add(&mut circuit, &[c, d], 0).unwrap();
+        assert_eq!(wrapped.value(&circuit), Some(4));
+        assert_eq!(d.xor_constant(&mut circuit, 0b110).unwrap().value(&circuit), Some(3));
+        assert!(circuit.is_satisfied().unwrap());
+        
+        // A wrong sum bit is caught
+        let mut witness = circuit.witness().unwrap();
+        witness[wrapped.bits[0]] = Fp128::one() - witness[wrapped.bits[0]];
+        circuit.set_witness(witness).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+    }
+    
     #[test]
     fn test_lookup_table() {
         let circuit = StandardCircuit::<Fp128>::new();
@@ -439,6 +787,37 @@ mod tests {
         assert!(result > index);
     }
     
+    #[test]
+    fn test_array_indexing() {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let array: Vec<usize> = (0..10u64).map(|i| circuit.alloc_value(Fp128::from_u64(100 + i * i))).collect();
+        let index = circuit.alloc_value(Fp128::from_u64(7));
+        
+        let element = array_get(&mut circuit, &array, index).unwrap();
+        let window = array_window(&mut circuit, &array, index, 3).unwrap();
+        assert_eq!(circuit.value(element), Some(Fp128::from_u64(149)));
+        let values: Vec<_> = window.iter().map(|&v| circuit.value(v).unwrap()).collect();
+        assert_eq!(values, [149, 164, 181].map(Fp128::from_u64));
+        assert!(circuit.is_satisfied().unwrap());
+        
+        // A claimed element other than array[index] is caught
+        let mut witness = circuit.witness().unwrap();
+        witness[element] = Fp128::from_u64(136);
+        circuit.set_witness(witness).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+        
+        // Windows running past the end select nothing
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let array = circuit.alloc_vars(4);
+        for &var in &array {
+            circuit.assign(var, Fp128::one());
+        }
+        let index = circuit.alloc_value(Fp128::from_u64(2));
+        array_window(&mut circuit, &array, index, 3).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+        assert!(array_window(&mut circuit, &array, index, 5).is_err());
+    }
+    
     #[test]
     fn test_multiplexer() {
         let circuit = StandardCircuit::<Fp128>::new();
@@ -2,7 +2,188 @@
 
 use crate::{CircuitBuilder, Constraint, gadgets, utils};
 use longfellow_algebra::traits::Field;
-use longfellow_core::Result;
+use longfellow_core::{LongfellowError, Result};
+
+/// Range checks by lookups into the table of `chunk_bits`-bit values
+///
+/// A value is split into table-sized chunks, so a range check costs one
+/// lookup per chunk instead of one boolean constraint per bit. Lookups are
+/// collected until `finalize`, which proves them all with a log-derivative
+/// argument: with a random challenge a, the sum of 1 / (a - c) over the
+/// looked-up values c equals the sum of m_t / (a - t) over the table,
+/// where m_t counts the lookups of t. That costs one quadratic constraint
+/// per lookup; the table side is a single linear constraint whatever its
+/// size, with one multiplicity variable per entry.
+///
+/// # Soundness
+///
+/// The argument is only sound with a challenge drawn after the looked-up
+/// values are committed. `finalize` bakes the challenge into the constraint
+/// system as a constant, and Ligero hashes that system into its transcript
+/// before the witness is committed, so a prover who knows the challenge can
+/// fake a lookup of an out-of-range value. Until the proof system can draw
+/// a challenge from the committed witness, nothing in this crate builds on
+/// these lookups; `RangeProofCircuit` keeps to bit decomposition.
+#[derive(Clone, Debug)]
+pub struct RangeLookup {
+    chunk_bits: usize,
+    lookups: Vec<usize>,
+}
+
+impl RangeLookup {
+    /// Create a lookup table of `chunk_bits`-bit values, 1 to 16 bits
+    pub fn new(chunk_bits: usize) -> Result<Self> {
+        if chunk_bits == 0 || chunk_bits > 16 {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Lookup tables hold 1 to 16 bits, not {}", chunk_bits)
+            ));
+        }
+        
+        Ok(Self {
+            chunk_bits,
+            lookups: Vec::new(),
+        })
+    }
+    
+    /// Bits per table entry
+    pub fn chunk_bits(&self) -> usize {
+        self.chunk_bits
+    }
+    
+    /// Number of lookups so far
+    pub fn num_lookups(&self) -> usize {
+        self.lookups.len()
+    }
+    
+    /// Constrain var to lie in the table
+    pub fn lookup(&mut self, var: usize) {
+        self.lookups.push(var);
+    }
+    
+    /// Constrain var to lie in [0, 2^bits)
+    pub fn range_check<F: Field, C: CircuitBuilder<F>>(
+        &mut self,
+        circuit: &mut C,
+        var: usize,
+        bits: usize,
+    ) -> Result<()> {
+        if bits >= F::MODULUS_BITS as usize {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Cannot range check {} bits in a {}-bit field", bits, F::MODULUS_BITS)
+            ));
+        }
+        
+        // var = sum of chunk_i * 2^(k i), which is var = 0 for no bits
+        let k = self.chunk_bits;
+        let num_chunks = bits.div_ceil(k);
+        let bytes = circuit.value(var).map(|v| v.to_canonical_bytes());
+        let chunks = circuit.alloc_vars(num_chunks);
+        let mut coeffs = Vec::with_capacity(num_chunks + 1);
+        let mut scale = F::one();
+        for (i, &chunk) in chunks.iter().enumerate() {
+            if let Some(bytes) = &bytes {
+                circuit.assign(chunk, F::from_u64(bits_at(bytes, i * k, k.min(bits - i * k))));
+            }
+            coeffs.push((chunk, scale));
+            scale *= F::from_u64(1 << k);
+            self.lookup(chunk);
+        }
+        coeffs.push((var, -F::one()));
+        circuit.add_constraint(Constraint::Linear {
+            coeffs,
+            constant: F::zero(),
+        })?;
+        
+        // A narrower top chunk is also looked up shifted to the top of the
+        // table, which bounds it by 2^(bits mod k)
+        if let Some(&top) = chunks.last() {
+            let top_bits = bits - (num_chunks - 1) * k;
+            if top_bits < k {
+                let shifted = crate::hash::affine(circuit, &[(top, F::from_u64(1 << (k - top_bits)))], F::zero())?;
+                self.lookup(shifted);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Prove every lookup against the table
+    ///
+    /// The argument needs `challenge` to be unpredictable to the prover once
+    /// the looked-up values are fixed, and nothing in this crate can provide
+    /// that yet; see the soundness note on `RangeLookup`. The verifier
+    /// builds the same constraints from it, and it must not itself be a
+    /// table entry.
+    pub fn finalize<F: Field, C: CircuitBuilder<F>>(self, circuit: &mut C, challenge: F) -> Result<()> {
+        let table_size = 1usize << self.chunk_bits;
+        
+        // 1 / (a - t) for every entry t, as constants
+        let mut inverses: Vec<F> = (0..table_size as u64).map(|t| challenge - F::from_u64(t)).collect();
+        if inverses.iter().any(|&d| d == F::zero()) {
+            return Err(LongfellowError::InvalidParameter(
+                "Lookup challenge lies in the table".to_string()
+            ));
+        }
+        F::batch_invert(&mut inverses);
+        
+        let mut counts = vec![0u64; table_size];
+        let mut known = true;
+        let mut coeffs = Vec::with_capacity(self.lookups.len() + table_size);
+        for &var in &self.lookups {
+            // inv (a - c) = 1
+            let difference = crate::hash::affine(circuit, &[(var, -F::one())], challenge)?;
+            let inverse = circuit.alloc_var();
+            if let Some(value) = circuit.value(difference) {
+                circuit.assign(inverse, value.invert().unwrap_or_else(F::zero));
+            }
+            let product = utils::mul_gate(circuit, inverse, difference)?;
+            circuit.add_constraint(Constraint::Linear {
+                coeffs: vec![(product, F::one())],
+                constant: F::one(),
+            })?;
+            coeffs.push((inverse, F::one()));
+            
+            match circuit.value(var) {
+                Some(value) => {
+                    if let Some(t) = table_index(&value, table_size) {
+                        counts[t] += 1;
+                    }
+                }
+                None => known = false,
+            }
+        }
+        
+        // Multiplicities weighted by the table's inverses
+        for (t, inverse) in inverses.into_iter().enumerate() {
+            let count = circuit.alloc_var();
+            if known {
+                circuit.assign(count, F::from_u64(counts[t]));
+            }
+            coeffs.push((count, -inverse));
+        }
+        circuit.add_constraint(Constraint::Linear {
+            coeffs,
+            constant: F::zero(),
+        })
+    }
+}
+
+/// `width` bits of a little-endian byte string starting at bit `offset`
+fn bits_at(bytes: &[u8], offset: usize, width: usize) -> u64 {
+    (0..width)
+        .filter(|&i| bytes.get((offset + i) / 8).is_some_and(|b| b >> ((offset + i) % 8) & 1 == 1))
+        .fold(0, |acc, i| acc | 1 << i)
+}
+
+/// The table entry a value is, if any
+fn table_index<F: Field>(value: &F, table_size: usize) -> Option<usize> {
+    let bytes = value.to_canonical_bytes();
+    if bytes.iter().skip(8).any(|&b| b != 0) {
+        return None;
+    }
+    let index = bits_at(&bytes, 0, 64) as usize;
+    (index < table_size).then_some(index)
+}
 
 /// Range proof circuit
 pub struct RangeProofCircuit<F: Field, C: CircuitBuilder<F>> {
@@ -48,13 +229,163 @@ impl<F: Field, C: CircuitBuilder<F>> RangeProofCircuit<F, C> {
         // Prove both differences are non-negative
         // This requires bit decomposition
         let bits_needed = 64; // Adjust based on field size
-        gadgets::bit_decompose(&mut self.circuit, value_minus_min, bits_needed)?;
-        gadgets::bit_decompose(&mut self.circuit, max_minus_value, bits_needed)?;
+        self.prove_range(value_minus_min, bits_needed)?;
+        self.prove_range(max_minus_value, bits_needed)?;
         
         Ok(())
     }
 }
 
+/// 1 if a < b else 0, for a and b in [0, 2^bits)
+///
+/// a - b + 2^bits lies in [0, 2^(bits + 1)) and its top bit is clear
+/// exactly when a < b. Costs bits + 1 boolean constraints, one per bit of
+/// that difference, and three linear ones.
+pub fn less_than<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<usize> {
+    check_width::<F>(bits + 1)?;
+    let difference = crate::hash::affine(circuit, &[(a, F::one()), (b, -F::one())], pow2(bits))?;
+    let difference_bits = decompose(circuit, difference, bits + 1)?;
+    crate::hash::affine(circuit, &[(difference_bits[bits], -F::one())], F::one())
+}
+
+/// 1 if a <= b else 0, for a and b in [0, 2^bits); costs as much as
+/// `less_than`
+pub fn less_equal<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<usize> {
+    let greater = less_than(circuit, b, a, bits)?;
+    crate::hash::affine(circuit, &[(greater, -F::one())], F::one())
+}
+
+/// 1 if a < b else 0, for signed a and b in (-2^(bits - 1), 2^(bits - 1))
+/// held as field elements, negative values as p - |x|
+///
+/// The difference of two such values is below 2^bits in magnitude, which
+/// is all `less_than` needs, so the cost is the same.
+pub fn signed_less_than<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<usize> {
+    less_than(circuit, a, b, bits)
+}
+
+/// 1 if min <= value <= max else 0, for all three in [0, 2^bits)
+///
+/// Costs 2 (bits + 1) boolean constraints and one multiplication.
+pub fn in_range<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    value: usize,
+    min: usize,
+    max: usize,
+    bits: usize,
+) -> Result<usize> {
+    let above = less_equal(circuit, min, value, bits)?;
+    let below = less_equal(circuit, value, max, bits)?;
+    utils::mul_gate(circuit, above, below)
+}
+
+/// The larger of a and b, for both in [0, 2^bits)
+///
+/// Costs bits + 2 boolean constraints, the last for the selection, and
+/// one multiplication.
+pub fn select_max<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<usize> {
+    let less = less_than(circuit, a, b, bits)?;
+    gadgets::select(circuit, less, b, a)
+}
+
+/// The smaller of a and b, for both in [0, 2^bits); costs as much as
+/// `select_max`
+pub fn select_min<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<usize> {
+    let less = less_than(circuit, a, b, bits)?;
+    gadgets::select(circuit, less, a, b)
+}
+
+/// Constrain a < b, that is b - a - 1 in [0, 2^bits)
+///
+/// Bounds a and b no further than that, so it holds for b anywhere in
+/// [a + 1, a + 2^bits]. Costs bits boolean constraints and two linear ones.
+pub fn assert_less_than<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<()> {
+    check_width::<F>(bits)?;
+    let gap = crate::hash::affine(circuit, &[(b, F::one()), (a, -F::one())], -F::one())?;
+    decompose(circuit, gap, bits)?;
+    Ok(())
+}
+
+/// Constrain a <= b, that is b - a in [0, 2^bits); costs as much as
+/// `assert_less_than`
+pub fn assert_less_equal<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: usize,
+    b: usize,
+    bits: usize,
+) -> Result<()> {
+    check_width::<F>(bits)?;
+    let gap = crate::hash::affine(circuit, &[(b, F::one()), (a, -F::one())], F::zero())?;
+    decompose(circuit, gap, bits)?;
+    Ok(())
+}
+
+/// Differences of `bits` bits must not wrap around the field
+fn check_width<F: Field>(bits: usize) -> Result<()> {
+    if bits >= F::MODULUS_BITS as usize {
+        return Err(LongfellowError::InvalidParameter(
+            format!("Cannot compare {}-bit values in a {}-bit field", bits, F::MODULUS_BITS)
+        ));
+    }
+    Ok(())
+}
+
+fn pow2<F: Field>(bits: usize) -> F {
+    F::from_u64(2).pow(&[bits as u64])
+}
+
+/// Boolean bits of var, least significant first, with values
+fn decompose<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, var: usize, bits: usize) -> Result<Vec<usize>> {
+    let bytes = circuit.value(var).map(|v| v.to_canonical_bytes());
+    let bit_vars = circuit.alloc_vars(bits);
+    let mut coeffs = Vec::with_capacity(bits + 1);
+    let mut scale = F::one();
+    for (i, &bit) in bit_vars.iter().enumerate() {
+        if let Some(bytes) = &bytes {
+            circuit.assign(bit, F::from_u64(bits_at(bytes, i, 1)));
+        }
+        circuit.add_constraint(Constraint::Boolean { var: bit })?;
+        coeffs.push((bit, scale));
+        scale = scale.double();
+    }
+    coeffs.push((var, -F::one()));
+    circuit.add_constraint(Constraint::Linear {
+        coeffs,
+        constant: F::zero(),
+    })?;
+    Ok(bit_vars)
+}
+
 /// Comparison circuit for general comparisons
 pub struct ComparisonCircuit<F: Field, C: CircuitBuilder<F>> {
     circuit: C,
@@ -72,30 +403,12 @@ impl<F: Field, C: CircuitBuilder<F>> ComparisonCircuit<F, C> {
     
     /// Assert a < b
     pub fn assert_less_than(&mut self, a: usize, b: usize, bits: usize) -> Result<()> {
-        let is_less = gadgets::less_than(&mut self.circuit, a, b, bits)?;
-        
-        // Assert is_less = 1
-        self.circuit.add_constraint(Constraint::Linear {
-            coeffs: vec![(is_less, F::one())],
-            constant: -F::one(),
-        })?;
-        
-        Ok(())
+        assert_less_than(&mut self.circuit, a, b, bits)
     }
     
     /// Assert a <= b
     pub fn assert_less_equal(&mut self, a: usize, b: usize, bits: usize) -> Result<()> {
-        // a <= b is equivalent to !(b < a)
-        let b_less_a = gadgets::less_than(&mut self.circuit, b, a, bits)?;
-        let not_b_less_a = gadgets::not_gate(&mut self.circuit, b_less_a)?;
-        
-        // Assert not_b_less_a = 1
-        self.circuit.add_constraint(Constraint::Linear {
-            coeffs: vec![(not_b_less_a, F::one())],
-            constant: -F::one(),
-        })?;
-        
-        Ok(())
+        assert_less_equal(&mut self.circuit, a, b, bits)
     }
     
     /// Assert a == b
@@ -300,6 +613,41 @@ mod tests {
         range_proof.prove_range(value, 8).unwrap();
     }
     
+    #[test]
+    fn test_range_lookup() {
+        let challenge = Fp128::from_u64(0x1234_5678_9abc_def0);
+        for (value, bits, ok) in [(1000, 10, true), (1023, 10, true), (1024, 10, false), (u64::MAX, 64, true), (0, 0, true), (1, 0, false)] {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let var = circuit.alloc_value(Fp128::from_u64(value));
+            let mut lookup = RangeLookup::new(8).unwrap();
+            lookup.range_check(&mut circuit, var, bits).unwrap();
+            lookup.finalize(&mut circuit, challenge).unwrap();
+            assert_eq!(circuit.is_satisfied().unwrap(), ok, "{} in {} bits", value, bits);
+        }
+        
+        // One lookup per chunk, plus the shifted top chunk
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let mut lookup = RangeLookup::new(8).unwrap();
+        let var = circuit.alloc_var();
+        lookup.range_check(&mut circuit, var, 64).unwrap();
+        assert_eq!(lookup.num_lookups(), 8);
+        lookup.range_check(&mut circuit, var, 12).unwrap();
+        assert_eq!(lookup.num_lookups(), 11);
+        
+        // A value outside the table fails the argument itself
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let mut lookup = RangeLookup::new(8).unwrap();
+        let inside = circuit.alloc_value(Fp128::from_u64(255));
+        let outside = circuit.alloc_value(Fp128::from_u64(256));
+        lookup.lookup(inside);
+        lookup.lookup(outside);
+        lookup.finalize(&mut circuit, challenge).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+        
+        assert!(RangeLookup::new(17).is_err());
+        assert!(RangeLookup::new(8).unwrap().finalize(&mut StandardCircuit::<Fp128>::new(), Fp128::from_u64(3)).is_err());
+    }
+    
     #[test]
     fn test_comparison_circuit() {
         let circuit = StandardCircuit::<Fp128>::new();
@@ -311,6 +659,54 @@ mod tests {
         comp.assert_less_than(a, b, 8).unwrap();
     }
     
+    #[test]
+    fn test_comparison_suite() {
+        let value = |circuit: &StandardCircuit<Fp128>, var| circuit.value(var).unwrap();
+        for (x, y) in [(0u64, 0u64), (3, 5), (5, 3), (255, 255), (0, 255), (200, 17)] {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let a = circuit.alloc_value(Fp128::from_u64(x));
+            let b = circuit.alloc_value(Fp128::from_u64(y));
+            let lt = less_than(&mut circuit, a, b, 8).unwrap();
+            let le = less_equal(&mut circuit, a, b, 8).unwrap();
+            let max = select_max(&mut circuit, a, b, 8).unwrap();
+            let min = select_min(&mut circuit, a, b, 8).unwrap();
+            let between = in_range(&mut circuit, a, b, max, 8).unwrap();
+            assert_eq!(value(&circuit, lt), Fp128::from_u64((x < y) as u64));
+            assert_eq!(value(&circuit, le), Fp128::from_u64((x <= y) as u64));
+            assert_eq!(value(&circuit, max), Fp128::from_u64(x.max(y)));
+            assert_eq!(value(&circuit, min), Fp128::from_u64(x.min(y)));
+            assert_eq!(value(&circuit, between), Fp128::from_u64((y <= x) as u64));
+            assert!(circuit.is_satisfied().unwrap());
+        }
+        
+        // Signed values, negatives as field elements
+        let signed = |x: i64| if x < 0 { -Fp128::from_u64(x.unsigned_abs()) } else { Fp128::from_u64(x as u64) };
+        for (x, y) in [(-3i64, 2i64), (2, -3), (-100, -7), (-7, -100), (127, -127)] {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let a = circuit.alloc_value(signed(x));
+            let b = circuit.alloc_value(signed(y));
+            let lt = signed_less_than(&mut circuit, a, b, 8).unwrap();
+            assert_eq!(value(&circuit, lt), Fp128::from_u64((x < y) as u64));
+            assert!(circuit.is_satisfied().unwrap());
+        }
+        
+        // Assertions constrain rather than compute
+        for (x, y, strict, ok) in [(3u64, 4u64, true, true), (4, 4, true, false), (4, 4, false, true), (5, 4, false, false)] {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let a = circuit.alloc_value(Fp128::from_u64(x));
+            let b = circuit.alloc_value(Fp128::from_u64(y));
+            if strict {
+                assert_less_than(&mut circuit, a, b, 8).unwrap();
+            } else {
+                assert_less_equal(&mut circuit, a, b, 8).unwrap();
+            }
+            assert_eq!(circuit.is_satisfied().unwrap(), ok);
+        }
+        
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        assert!(less_than(&mut circuit, 0, 0, 127).is_err());
+    }
+    
     #[test]
     fn test_sorting_circuit() {
         let circuit = StandardCircuit::<Fp128>::new();
@@ -0,0 +1,551 @@
+//! ECDSA-P256 signature verification circuits
+//!
+//! `verify_ecdsa_p256` constrains a signature (r, s) on a message hash e
+//! under a public key Q. The prover supplies the point R = (e Q + r G) / s
+//! with x(R) = r mod n, and the circuit checks e G + r Q - s R = O in one
+//! double-and-add pass over the three scalars, adding one of eight
+//! precomputed points per bit. The pass starts from a fixed offset point
+//! whose discrete log nobody knows and every table entry carries it too,
+//! so the accumulator never meets the point at infinity and the result is
+//! compared against a constant.
+//!
+//! Coordinates live in the P-256 base field, so they are `NonNativeField`
+//! elements and every curve formula is a relation checked modulo p: seven per
+//! scalar bit, each with a range-checked quotient and carries.
+
+use crate::arithmetic::{biguint_from_field, field_from_biguint, NonNativeElement, NonNativeField};
+use crate::{hash, utils, CircuitBuilder, Constraint};
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use longfellow_ec::ecdsa::EcdsaSignature;
+use longfellow_ec::Point;
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// A P-256 point in affine coordinates
+#[derive(Clone, Debug)]
+pub struct PointVar {
+    pub x: NonNativeElement,
+    pub y: NonNativeElement,
+}
+
+/// An ECDSA signature as the bits of r and s, 256 each, most significant
+/// first as in the 64-byte r || s encoding
+#[derive(Clone, Debug)]
+pub struct SignatureVar {
+    pub r: Vec<usize>,
+    pub s: Vec<usize>,
+}
+
+type Affine = (BigUint, BigUint);
+
+/// P-256 parameters and generator, with the offset point A of the verification pass,
+/// A + G and the value (2^257 - 1) A the pass must end on
+struct Curve {
+    p: BigUint,
+    n: BigUint,
+    b: BigUint,
+    g: Affine,
+    offset: Affine,
+    offset_g: Affine,
+    end: Affine,
+}
+
+fn hex(digits: &str) -> BigUint {
+    BigUint::parse_bytes(digits.as_bytes(), 16).expect("valid hex constant")
+}
+
+fn curve() -> &'static Curve {
+    static CURVE: OnceLock<Curve> = OnceLock::new();
+    CURVE.get_or_init(|| {
+        let p = hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+        let n = hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+        let b = hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b");
+        let g = (
+            hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+            hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+        );
+
+        // Try-and-increment on hashed x coordinates; p = 3 mod 4, so a
+        // square root is a single exponentiation
+        let offset = (0u8..)
+            .find_map(|counter| {
+                let digest = Sha256::new()
+                    .chain_update(b"longfellow-ecdsa-p256-offset")
+                    .chain_update([counter])
+                    .finalize();
+                let x = BigUint::from_bytes_be(&digest) % &p;
+                let rhs = (x.modpow(&BigUint::from(3u32), &p) + &p * 3u32 - &x * 3u32 + &b) % &p;
+                let y = rhs.modpow(&((&p + 1u32) >> 2), &p);
+                (&y * &y % &p == rhs).then_some((x, y))
+            })
+            .expect("a hashed x coordinate lies on the curve");
+
+        let mut curve = Curve {
+            p,
+            n,
+            b,
+            g: g.clone(),
+            offset_g: offset.clone(),
+            end: offset.clone(),
+            offset,
+        };
+        curve.offset_g = curve.add(&Some(curve.offset.clone()), &Some(g)).expect("A + G is affine");
+        let end_scalar = (BigUint::one() << 257u32) - 1u32;
+        curve.end = curve.mul(&end_scalar, &curve.offset).expect("(2^257 - 1) A is affine");
+        curve
+    })
+}
+
+impl Curve {
+    fn inv(&self, x: &BigUint) -> BigUint {
+        x.modpow(&(&self.p - 2u32), &self.p)
+    }
+
+    /// Group law on affine points, `None` for the point at infinity
+    fn add(&self, a: &Option<Affine>, b: &Option<Affine>) -> Option<Affine> {
+        let (Some((x1, y1)), Some((x2, y2))) = (a, b) else {
+            return a.clone().or_else(|| b.clone());
+        };
+        let p = &self.p;
+        let lambda = if x1 == x2 {
+            if ((y1 + y2) % p).is_zero() {
+                return None;
+            }
+            (x1 * x1 * 3u32 + p * 3u32 - 3u32) * self.inv(&(y1 * 2u32 % p)) % p
+        } else {
+            (y2 + p - y1) * self.inv(&((x2 + p - x1) % p)) % p
+        };
+        let x3 = (&lambda * &lambda + p * 2u32 - x1 - x2) % p;
+        let y3 = (&lambda * ((x1 + p - &x3) % p) + p - y1) % p;
+        Some((x3, y3))
+    }
+
+    fn mul(&self, k: &BigUint, point: &Affine) -> Option<Affine> {
+        let point = Some(point.clone());
+        let mut acc = None;
+        for i in (0..k.bits()).rev() {
+            acc = self.add(&acc, &acc);
+            if k.bit(i) {
+                acc = self.add(&acc, &point);
+            }
+        }
+        acc
+    }
+}
+
+/// Affine coordinates of a public key
+fn key_coordinates(key: &Point) -> Result<Affine> {
+    if key.is_infinity {
+        return Err(LongfellowError::InvalidParameter(
+            "Public key cannot be point at infinity".to_string()
+        ));
+    }
+    Ok((
+        BigUint::from_bytes_be(&key.x.to_bytes()),
+        BigUint::from_bytes_be(&key.y.to_bytes()),
+    ))
+}
+
+/// Allocate a public key's coordinates, with values
+///
+/// The coordinates are free witnesses: nothing ties them to `key` once the
+/// circuit is built. Use `constant_public_key` for a key the verifier knows.
+pub fn alloc_public_key<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, key: &Point) -> Result<PointVar> {
+    let (x, y) = key_coordinates(key)?;
+    let fp = NonNativeField::new::<F>(curve().p.clone())?;
+    Ok(PointVar {
+        x: fp.alloc(circuit, Some(&x))?,
+        y: fp.alloc(circuit, Some(&y))?,
+    })
+}
+
+/// A public key fixed by the circuit, such as an issuer's, so that only
+/// signatures under that key verify
+pub fn constant_public_key<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, key: &Point) -> Result<PointVar> {
+    let (x, y) = key_coordinates(key)?;
+    let fp = NonNativeField::new::<F>(curve().p.clone())?;
+    Ok(PointVar {
+        x: fp.constant(circuit, &x)?,
+        y: fp.constant(circuit, &y)?,
+    })
+}
+
+/// Allocate the bits of a signature, with values, constrained boolean
+pub fn alloc_signature<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, signature: &EcdsaSignature) -> Result<SignatureVar> {
+    let bits = hash::alloc_bytes(circuit, &signature.to_bytes())?;
+    Ok(SignatureVar {
+        r: bits[..256].to_vec(),
+        s: bits[256..].to_vec(),
+    })
+}
+
+/// Constrain `sig` to be a valid ECDSA-P256 signature under `pubkey` of
+/// the 256-bit hash `msg_hash`
+///
+/// Hash and scalars are bits most significant first, as `Sha256Circuit`
+/// returns digests; they must already be constrained boolean. The hash is
+/// taken whole as e, which only matters modulo n.
+pub fn verify_ecdsa_p256<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    pubkey: &PointVar,
+    msg_hash: &[usize],
+    sig: &SignatureVar,
+) -> Result<()> {
+    if msg_hash.len() != 256 || sig.r.len() != 256 || sig.s.len() != 256 {
+        return Err(LongfellowError::InvalidParameter(
+            "ECDSA-P256 hash and scalars must be 256 bits".to_string()
+        ));
+    }
+
+    let curve = curve();
+    let fp = NonNativeField::new::<F>(curve.p.clone())?;
+    let zero = BigInt::zero();
+    assert_on_curve(circuit, &fp, pubkey)?;
+    assert_scalar(circuit, &sig.r, &curve.n)?;
+    assert_scalar(circuit, &sig.s, &curve.n)?;
+
+    // R = (e G + r Q) / s, from the values when the prover has them
+    let scalar = |circuit: &C, bits: &[usize]| {
+        bits.iter().try_fold(BigUint::zero(), |acc, &bit| {
+            Some((acc << 1u32) + biguint_from_field(&circuit.value(bit)?))
+        })
+    };
+    let inputs = (|| {
+        let q = (fp.reduced(circuit, &pubkey.x)?, fp.reduced(circuit, &pubkey.y)?);
+        Some((scalar(circuit, msg_hash)?, scalar(circuit, &sig.r)?, scalar(circuit, &sig.s)?, q))
+    })();
+    let r_point = inputs.as_ref().map(|(e, r, s, q)| {
+        let s_inv = s.modpow(&(&curve.n - 2u32), &curve.n);
+        let u1 = e * &s_inv % &curve.n;
+        let u2 = r * &s_inv % &curve.n;
+        curve
+            .add(&curve.mul(&u1, &curve.g), &curve.mul(&u2, q))
+            .unwrap_or_else(|| curve.g.clone())
+    });
+    let r_var = PointVar {
+        x: fp.alloc(circuit, r_point.as_ref().map(|pt| &pt.0))?,
+        y: fp.alloc(circuit, r_point.as_ref().map(|pt| &pt.1))?,
+    };
+    assert_on_curve(circuit, &fp, &r_var)?;
+
+    // x(R) is reduced and equals r or r + n
+    fp.assert_reduced(circuit, &r_var.x)?;
+
+    let wraps = circuit.alloc_var();
+    if let (Some(pt), Some((_, r, _, _))) = (&r_point, &inputs) {
+        circuit.assign(wraps, if &pt.0 > r { F::one() } else { F::zero() });
+    }
+    circuit.add_constraint(Constraint::Boolean { var: wraps })?;
+    let r_limbs = fp.from_bits(circuit, &sig.r)?;
+    let wraps_n = (0..fp.num_limbs())
+        .map(|i| hash::affine(circuit, &[(wraps, field_from_biguint(&fp.limb(&curve.n, i)))], F::zero()))
+        .collect::<Result<Vec<_>>>()?;
+    fp.assert_integer_zero(
+        circuit,
+        &[],
+        &[(1, &r_var.x), (-1, &r_limbs), (-1, &NonNativeElement { limbs: wraps_n })],
+        &zero,
+    )?;
+
+    let neg_y = fp.alloc(circuit, r_point.as_ref().map(|pt| (&curve.p - &pt.1) % &curve.p).as_ref())?;
+    fp.assert_zero(circuit, &[], &[(1, &r_var.y), (1, &neg_y)], &zero)?;
+    let neg_r = PointVar { x: r_var.x.clone(), y: neg_y };
+
+    // Entry b0 + 2 b1 + 4 b2 of the table is A + b0 G + b1 Q - b2 R
+    let constant_point = |circuit: &mut C, pt: &Affine| -> Result<PointVar> {
+        Ok(PointVar {
+            x: fp.constant(circuit, &pt.0)?,
+            y: fp.constant(circuit, &pt.1)?,
+        })
+    };
+    let t0 = constant_point(circuit, &curve.offset)?;
+    let t1 = constant_point(circuit, &curve.offset_g)?;
+    let t2 = add_points(circuit, &fp, &t0, pubkey)?;
+    let t3 = add_points(circuit, &fp, &t1, pubkey)?;
+    let t4 = add_points(circuit, &fp, &t0, &neg_r)?;
+    let t5 = add_points(circuit, &fp, &t1, &neg_r)?;
+    let t6 = add_points(circuit, &fp, &t2, &neg_r)?;
+    let t7 = add_points(circuit, &fp, &t3, &neg_r)?;
+    let table = [t0, t1, t2, t3, t4, t5, t6, t7];
+
+    // A (2^256) + sum over bits of 2^i (A + e_i G + r_i Q - s_i R)
+    let mut acc = table[0].clone();
+    for ((&e, &r), &s) in msg_hash.iter().zip(&sig.r).zip(&sig.s) {
+        acc = double_point(circuit, &fp, &acc)?;
+        let entry = select_point(circuit, &table, [e, r, s])?;
+        acc = add_points(circuit, &fp, &acc, &entry)?;
+    }
+
+    let (end_x, end_y) = &curve.end;
+    fp.assert_zero(circuit, &[], &[(1, &acc.x)], &-BigInt::from(end_x.clone()))?;
+    fp.assert_zero(circuit, &[], &[(1, &acc.y)], &-BigInt::from(end_y.clone()))
+}
+
+/// y^2 = x^3 - 3x + b
+fn assert_on_curve<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, fp: &NonNativeField, point: &PointVar) -> Result<()> {
+    let square = fp
+        .reduced(circuit, &point.x)
+        .map(|x| &x * &x % fp.modulus());
+    let x2 = fp.alloc(circuit, square.as_ref())?;
+    fp.assert_zero(circuit, &[(1, &point.x, &point.x)], &[(-1, &x2)], &BigInt::zero())?;
+    fp.assert_zero(
+        circuit,
+        &[(1, &point.y, &point.y), (-1, &x2, &point.x)],
+        &[(3, &point.x)],
+        &-BigInt::from(curve().b.clone()),
+    )
+}
+
+/// a + b for points with distinct x coordinates
+fn add_points<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    fp: &NonNativeField,
+    a: &PointVar,
+    b: &PointVar,
+) -> Result<PointVar> {
+    let p = fp.modulus();
+    let values = (|| {
+        Some((
+            fp.reduced(circuit, &a.x)?,
+            fp.reduced(circuit, &a.y)?,
+            fp.reduced(circuit, &b.x)?,
+            fp.reduced(circuit, &b.y)?,
+        ))
+    })();
+    let witness = values.map(|(x1, y1, x2, y2)| {
+        let inv = curve().inv(&((&x2 + p - &x1) % p));
+        let lambda = (&y2 + p - &y1) * &inv % p;
+        let x3 = (&lambda * &lambda + p * 2u32 - &x1 - &x2) % p;
+        let y3 = (&lambda * ((&x1 + p - &x3) % p) + p - &y1) % p;
+        [inv, lambda, x3, y3]
+    });
+    let [inv, lambda, x3, y3] = [0, 1, 2, 3].map(|i| witness.as_ref().map(|w| &w[i]));
+    let inv = fp.alloc(circuit, inv)?;
+    let lambda = fp.alloc(circuit, lambda)?;
+    let x3 = fp.alloc(circuit, x3)?;
+    let y3 = fp.alloc(circuit, y3)?;
+
+    let zero = BigInt::zero();
+    // (x2 - x1) inv = 1 rules out equal x, where any lambda would pass
+    fp.assert_zero(circuit, &[(1, &inv, &b.x), (-1, &inv, &a.x)], &[], &-BigInt::one())?;
+    // lambda (x2 - x1) = y2 - y1
+    fp.assert_zero(circuit, &[(1, &lambda, &b.x), (-1, &lambda, &a.x)], &[(-1, &b.y), (1, &a.y)], &zero)?;
+    // x3 = lambda^2 - x1 - x2
+    fp.assert_zero(circuit, &[(1, &lambda, &lambda)], &[(-1, &a.x), (-1, &b.x), (-1, &x3)], &zero)?;
+    // y3 = lambda (x1 - x3) - y1
+    fp.assert_zero(circuit, &[(1, &lambda, &a.x), (-1, &lambda, &x3)], &[(-1, &a.y), (-1, &y3)], &zero)?;
+    Ok(PointVar { x: x3, y: y3 })
+}
+
+/// 2a; P-256 has no points with y = 0
+fn double_point<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, fp: &NonNativeField, a: &PointVar) -> Result<PointVar> {
+    let p = fp.modulus();
+    let values = (|| Some((fp.reduced(circuit, &a.x)?, fp.reduced(circuit, &a.y)?)))();
+    let witness = values.map(|(x, y)| {
+        let lambda = (&x * &x * 3u32 + p * 3u32 - 3u32) * curve().inv(&(&y * 2u32 % p)) % p;
+        let x3 = (&lambda * &lambda + p * 2u32 - &x * 2u32) % p;
+        let y3 = (&lambda * ((&x + p - &x3) % p) + p - &y) % p;
+        [lambda, x3, y3]
+    });
+    let [lambda, x3, y3] = [0, 1, 2].map(|i| witness.as_ref().map(|w| &w[i]));
+    let lambda = fp.alloc(circuit, lambda)?;
+    let x3 = fp.alloc(circuit, x3)?;
+    let y3 = fp.alloc(circuit, y3)?;
+
+    let zero = BigInt::zero();
+    // 2 lambda y = 3 x^2 - 3
+    fp.assert_zero(circuit, &[(2, &lambda, &a.y), (-3, &a.x, &a.x)], &[], &BigInt::from(3))?;
+    // x3 = lambda^2 - 2 x
+    fp.assert_zero(circuit, &[(1, &lambda, &lambda)], &[(-2, &a.x), (-1, &x3)], &zero)?;
+    // y3 = lambda (x - x3) - y
+    fp.assert_zero(circuit, &[(1, &lambda, &a.x), (-1, &lambda, &x3)], &[(-1, &a.y), (-1, &y3)], &zero)?;
+    Ok(PointVar { x: x3, y: y3 })
+}
+
+/// table[b0 + 2 b1 + 4 b2], limb by limb
+fn select_point<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    table: &[PointVar; 8],
+    bits: [usize; 3],
+) -> Result<PointVar> {
+    let select_limb = |circuit: &mut C, limbs: [usize; 8]| -> Result<usize> {
+        let mut level = limbs.to_vec();
+        for &bit in &bits {
+            level = level
+                .chunks(2)
+                .map(|pair| crate::gadgets::select(circuit, bit, pair[1], pair[0]))
+                .collect::<Result<Vec<_>>>()?;
+        }
+        Ok(level[0])
+    };
+    let num_limbs = table[0].x.limbs.len();
+    let mut x = Vec::with_capacity(num_limbs);
+    let mut y = Vec::with_capacity(num_limbs);
+    for i in 0..num_limbs {
+        x.push(select_limb(circuit, std::array::from_fn(|t| table[t].x.limbs[i]))?);
+        y.push(select_limb(circuit, std::array::from_fn(|t| table[t].y.limbs[i]))?);
+    }
+    Ok(PointVar {
+        x: NonNativeElement { limbs: x },
+        y: NonNativeElement { limbs: y },
+    })
+}
+
+/// 0 < bits < bound, for boolean bits most significant first
+fn assert_scalar<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, bits: &[usize], bound: &BigUint) -> Result<()> {
+    // While the bits so far equal the bound's, `equal` is 1; `less` turns
+    // 1 at the first bit below it
+    let mut equal = utils::const_gate(circuit, F::one())?;
+    let mut less = utils::const_gate(circuit, F::zero())?;
+    for (j, &bit) in bits.iter().enumerate() {
+        let both = utils::mul_gate(circuit, equal, bit)?;
+        if bound.bit((bits.len() - 1 - j) as u64) {
+            less = hash::affine(circuit, &[(less, F::one()), (equal, F::one()), (both, -F::one())], F::zero())?;
+            equal = both;
+        } else {
+            equal = hash::affine(circuit, &[(equal, F::one()), (both, -F::one())], F::zero())?;
+        }
+    }
+    circuit.add_constraint(Constraint::Linear {
+        coeffs: vec![(less, F::one())],
+        constant: F::one(),
+    })?;
+
+    // A nonzero bit count has an inverse
+    let terms: Vec<(usize, F)> = bits.iter().map(|&bit| (bit, F::one())).collect();
+    let count = hash::affine(circuit, &terms, F::zero())?;
+    let inverse = circuit.alloc_var();
+    if let Some(count) = circuit.value(count) {
+        circuit.assign(inverse, count.invert().unwrap_or_else(F::zero));
+    }
+    let product = utils::mul_gate(circuit, count, inverse)?;
+    circuit.add_constraint(Constraint::Linear {
+        coeffs: vec![(product, F::one())],
+        constant: F::one(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardCircuit;
+    use longfellow_algebra::Fp128;
+    use longfellow_ec::ecdsa::hash_message;
+    use longfellow_ec::{FieldElement, ScalarElement};
+
+    fn bytes32(value: &BigUint) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        let be = value.to_bytes_be();
+        bytes[32 - be.len()..].copy_from_slice(&be);
+        bytes
+    }
+
+    fn point(x: &str, y: &str) -> Point {
+        let coord = |s: &str| FieldElement::from_bytes(&bytes32(&hex(s))).unwrap();
+        Point::new(coord(x), coord(y)).unwrap()
+    }
+
+    fn alloc_point(circuit: &mut StandardCircuit<Fp128>, fp: &NonNativeField, pt: &Affine) -> PointVar {
+        PointVar {
+            x: fp.alloc(circuit, Some(&pt.0)).unwrap(),
+            y: fp.alloc(circuit, Some(&pt.1)).unwrap(),
+        }
+    }
+
+    fn point_value(circuit: &StandardCircuit<Fp128>, fp: &NonNativeField, pt: &PointVar) -> Affine {
+        (fp.reduced(circuit, &pt.x).unwrap(), fp.reduced(circuit, &pt.y).unwrap())
+    }
+
+    #[test]
+    fn test_point_formulas_match_native() {
+        let curve = curve();
+        let fp = NonNativeField::new::<Fp128>(curve.p.clone()).unwrap();
+        let g = curve.g.clone();
+        let g2 = curve.add(&Some(g.clone()), &Some(g.clone())).unwrap();
+
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let a = alloc_point(&mut circuit, &fp, &curve.offset);
+        let b = alloc_point(&mut circuit, &fp, &g);
+        assert_on_curve(&mut circuit, &fp, &b).unwrap();
+        let sum = add_points(&mut circuit, &fp, &a, &b).unwrap();
+        let double = double_point(&mut circuit, &fp, &b).unwrap();
+        assert_eq!(point_value(&circuit, &fp, &sum), curve.offset_g);
+        assert_eq!(point_value(&circuit, &fp, &double), g2);
+        assert!(circuit.is_satisfied().unwrap());
+
+        // Adding a point to itself has no valid inverse witness
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let b = alloc_point(&mut circuit, &fp, &g);
+        add_points(&mut circuit, &fp, &b, &b).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+
+        // Nor is a point off the curve accepted
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let off = alloc_point(&mut circuit, &fp, &(g.0.clone(), &g.1 + 1u32));
+        assert_on_curve(&mut circuit, &fp, &off).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_scalar_range() {
+        let n = &curve().n;
+        for (value, ok) in [(BigUint::one(), true), (n - 1u32, true), (BigUint::zero(), false), (n.clone(), false)] {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let bits = hash::alloc_bytes(&mut circuit, &bytes32(&value)).unwrap();
+            assert_scalar(&mut circuit, &bits, n).unwrap();
+            assert_eq!(circuit.is_satisfied().unwrap(), ok);
+        }
+    }
+
+    /// RFC 6979 A.2.5, P-256 with SHA-256, message "sample"
+    fn rfc6979_sample() -> (Point, [u8; 32], EcdsaSignature) {
+        let key = point(
+            "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6",
+            "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299",
+        );
+        let scalar = |s: &str| ScalarElement::from_bytes(&bytes32(&hex(s))).unwrap();
+        let signature = EcdsaSignature::new(
+            scalar("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+            scalar("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+        );
+        (key, hash_message(b"sample"), signature)
+    }
+
+    fn signature_circuit(key: &Point, hash: &[u8; 32], signature: &EcdsaSignature) -> StandardCircuit<Fp128> {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let pubkey = constant_public_key(&mut circuit, key).unwrap();
+        let msg_hash = hash::alloc_bytes(&mut circuit, hash).unwrap();
+        let sig = alloc_signature(&mut circuit, signature).unwrap();
+        verify_ecdsa_p256(&mut circuit, &pubkey, &msg_hash, &sig).unwrap();
+        circuit
+    }
+
+    #[test]
+    #[ignore = "builds a circuit of several million variables; run with --ignored"]
+    fn test_verify_rfc6979_signature() {
+        let (key, hash, signature) = rfc6979_sample();
+        assert!(signature_circuit(&key, &hash, &signature).is_satisfied().unwrap());
+
+        // Another message under the same signature
+        let other = hash_message(b"test");
+        assert!(!signature_circuit(&key, &other, &signature).is_satisfied().unwrap());
+
+        // The same signature checked against another key
+        assert!(!signature_circuit(&Point::generator(), &hash, &signature).is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_constant_public_key_is_pinned() {
+        // A prover cannot swap in coordinates of their own
+        let (key, _, _) = rfc6979_sample();
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let pinned = constant_public_key(&mut circuit, &key).unwrap();
+        assert!(circuit.is_satisfied().unwrap());
+        let limb = pinned.x.limbs[0];
+        circuit.assign(limb, circuit.value(limb).unwrap() + Fp128::one());
+        assert!(!circuit.is_satisfied().unwrap());
+    }
+}
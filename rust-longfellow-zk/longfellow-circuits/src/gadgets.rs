@@ -1,8 +1,8 @@
 /// Common circuit gadgets
 
-use crate::{CircuitBuilder, Constraint, utils};
+use crate::{CircuitBuilder, Constraint, hash, utils};
 use longfellow_algebra::traits::Field;
-use longfellow_core::Result;
+use longfellow_core::{LongfellowError, Result};
 
 /// Conditional selection: if cond then a else b
 pub fn select<F: Field, C: CircuitBuilder<F>>(
@@ -23,6 +23,9 @@ pub fn select<F: Field, C: CircuitBuilder<F>>(
         coeffs: vec![(a, F::one()), (b, -F::one()), (a_minus_b, -F::one())],
         constant: F::zero(),
     })?;
+    if let (Some(x), Some(y)) = (circuit.value(a), circuit.value(b)) {
+        circuit.assign(a_minus_b, x - y);
+    }
     
     // Compute cond * (a - b)
     let cond_times_diff = utils::mul_gate(circuit, cond, a_minus_b)?;
@@ -31,6 +34,84 @@ pub fn select<F: Field, C: CircuitBuilder<F>>(
     utils::add_gate(circuit, cond_times_diff, b)
 }
 
+/// Element-wise selection between two vectors: if cond then a else b
+pub fn select_all<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    cond: usize,
+    a: &[usize],
+    b: &[usize],
+) -> Result<Vec<usize>> {
+    if a.len() != b.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Cannot select between {} and {} values",
+            a.len(),
+            b.len()
+        )));
+    }
+    
+    // One boolean constraint for the whole vector
+    circuit.add_constraint(Constraint::Boolean { var: cond })?;
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| {
+            let difference = hash::affine(circuit, &[(x, F::one()), (y, -F::one())], F::zero())?;
+            let chosen = utils::mul_gate(circuit, cond, difference)?;
+            utils::add_gate(circuit, chosen, y)
+        })
+        .collect()
+}
+
+/// Constrain `x` to be zero when `cond` is one: cond * x = 0
+///
+/// Guards leave `cond` unconstrained so that one condition can guard many
+/// constraints; it must be boolean, e.g. a flag already passed to `select`
+/// or produced by a comparison. When it is zero the guarded constraint
+/// holds whatever the values.
+pub fn assert_zero_if<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    cond: usize,
+    x: usize,
+) -> Result<()> {
+    let guarded = utils::mul_gate(circuit, cond, x)?;
+    circuit.add_constraint(Constraint::Linear {
+        coeffs: vec![(guarded, F::one())],
+        constant: F::zero(),
+    })
+}
+
+/// Constrain a = b when `cond` is one
+pub fn assert_equal_if<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    cond: usize,
+    a: usize,
+    b: usize,
+) -> Result<()> {
+    let difference = hash::affine(circuit, &[(a, F::one()), (b, -F::one())], F::zero())?;
+    assert_zero_if(circuit, cond, difference)
+}
+
+/// Constrain `a` to equal the constant `value` when `cond` is one
+pub fn assert_constant_if<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    cond: usize,
+    a: usize,
+    value: F,
+) -> Result<()> {
+    let difference = hash::affine(circuit, &[(a, F::one())], -value)?;
+    assert_zero_if(circuit, cond, difference)
+}
+
+/// Constrain the boolean `predicate` to hold when `cond` does:
+/// cond * (1 - predicate) = 0
+pub fn assert_implies<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    cond: usize,
+    predicate: usize,
+) -> Result<()> {
+    let failed = hash::affine(circuit, &[(predicate, -F::one())], F::one())?;
+    assert_zero_if(circuit, cond, failed)
+}
+
 /// Bitwise decomposition of a field element
 pub fn bit_decompose<F: Field, C: CircuitBuilder<F>>(
     circuit: &mut C,
@@ -238,6 +319,43 @@ mod tests {
         assert_eq!(result, 3); // Should be the 4th variable
     }
     
+    #[test]
+    fn test_guarded_constraints() {
+        // An optional claim: when present it must equal 42, else it is free
+        fn optional_claim(present: bool, claim: u64) -> StandardCircuit<Fp128> {
+            let mut circuit = StandardCircuit::<Fp128>::new();
+            let flag = circuit.alloc_value(Fp128::from_u64(present as u64));
+            circuit.add_constraint(Constraint::Boolean { var: flag }).unwrap();
+            let var = circuit.alloc_value(Fp128::from_u64(claim));
+            assert_constant_if(&mut circuit, flag, var, Fp128::from_u64(42)).unwrap();
+            
+            // Absent claims read as zero
+            let fallback = utils::const_gate(&mut circuit, Fp128::zero()).unwrap();
+            let value = select(&mut circuit, flag, var, fallback).unwrap();
+            let expected = if present { claim } else { 0 };
+            assert_eq!(circuit.value(value), Some(Fp128::from_u64(expected)));
+            circuit
+        }
+        
+        assert!(optional_claim(true, 42).is_satisfied().unwrap());
+        assert!(!optional_claim(true, 41).is_satisfied().unwrap());
+        assert!(optional_claim(false, 41).is_satisfied().unwrap());
+        
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let [cond, yes, no] = [1, 1, 0].map(|v| circuit.alloc_value(Fp128::from_u64(v)));
+        let a: Vec<usize> = (1..=3).map(|v| circuit.alloc_value(Fp128::from_u64(v))).collect();
+        let b: Vec<usize> = (4..=6).map(|v| circuit.alloc_value(Fp128::from_u64(v))).collect();
+        let chosen = select_all(&mut circuit, cond, &a, &b).unwrap();
+        let values: Vec<_> = chosen.iter().map(|&v| circuit.value(v).unwrap()).collect();
+        assert_eq!(values, [1, 2, 3].map(Fp128::from_u64));
+        assert_equal_if(&mut circuit, cond, chosen[0], a[0]).unwrap();
+        assert_implies(&mut circuit, cond, yes).unwrap();
+        assert!(circuit.is_satisfied().unwrap());
+        assert_implies(&mut circuit, cond, no).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+        assert!(select_all(&mut circuit, cond, &a, &b[1..]).is_err());
+    }
+    
     #[test]
     fn test_bit_decompose() {
         let mut circuit = StandardCircuit::<Fp128>::new();
@@ -8,6 +8,10 @@ pub mod hash;
 pub mod comparison;
 pub mod arithmetic;
 pub mod boolean;
+pub mod merkle;
+pub mod ecdsa;
+pub mod rsa;
+pub mod string;
 
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
@@ -27,6 +31,53 @@ pub trait CircuitBuilder<F: Field> {
     
     /// Allocate multiple variables
     fn alloc_vars(&mut self, count: usize) -> Vec<usize>;
+    
+    /// Record the witness value of a variable; builders that only collect
+    /// constraints ignore it
+    fn assign(&mut self, _var: usize, _value: F) {}
+    
+    /// Witness value of a variable, if one has been assigned
+    fn value(&self, _var: usize) -> Option<F> {
+        None
+    }
+    
+    /// Allocate a variable holding `value`
+    fn alloc_value(&mut self, value: F) -> usize {
+        let var = self.alloc_var();
+        self.assign(var, value);
+        var
+    }
+}
+
+/// Gadgets that own their builder can also borrow one
+impl<F: Field, C: CircuitBuilder<F> + ?Sized> CircuitBuilder<F> for &mut C {
+    fn add_constraint(&mut self, constraint: Constraint<F>) -> Result<()> {
+        (**self).add_constraint(constraint)
+    }
+    
+    fn num_vars(&self) -> usize {
+        (**self).num_vars()
+    }
+    
+    fn alloc_var(&mut self) -> usize {
+        (**self).alloc_var()
+    }
+    
+    fn alloc_vars(&mut self, count: usize) -> Vec<usize> {
+        (**self).alloc_vars(count)
+    }
+    
+    fn assign(&mut self, var: usize, value: F) {
+        (**self).assign(var, value)
+    }
+    
+    fn value(&self, var: usize) -> Option<F> {
+        (**self).value(var)
+    }
+    
+    fn alloc_value(&mut self, value: F) -> usize {
+        (**self).alloc_value(value)
+    }
 }
 
 /// Constraint types
@@ -63,14 +114,17 @@ pub struct StandardCircuit<F: Field> {
     pub constraints: ConstraintSystem<F>,
     /// Current number of variables
     num_vars: usize,
+    /// Witness values assigned so far, by variable
+    values: Vec<Option<F>>,
 }
 
 impl<F: Field> StandardCircuit<F> {
     /// Create a new circuit
     pub fn new() -> Self {
         Self {
-            constraints: ConstraintSystem::new(F::zero()),
+            constraints: ConstraintSystem::new(0),
             num_vars: 0,
+            values: Vec::new(),
         }
     }
     
@@ -82,11 +136,27 @@ impl<F: Field> StandardCircuit<F> {
             ));
         }
         
-        // Update constraint system
-        self.constraints = ConstraintSystem::new(witness.len());
-        
+        self.values = witness.into_iter().map(Some).collect();
         Ok(())
     }
+    
+    /// The full witness, once every variable has a value
+    pub fn witness(&self) -> Result<Vec<F>> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(var, value)| {
+                value.ok_or_else(|| {
+                    LongfellowError::InvalidParameter(format!("Variable {} has no value", var))
+                })
+            })
+            .collect()
+    }
+    
+    /// Check the assigned witness against the constraints
+    pub fn is_satisfied(&self) -> Result<bool> {
+        self.constraints.is_satisfied(&self.witness()?, &[])
+    }
 }
 
 impl<F: Field> CircuitBuilder<F> for StandardCircuit<F> {
@@ -107,10 +177,23 @@ impl<F: Field> CircuitBuilder<F> for StandardCircuit<F> {
                 );
                 let result_var = self.alloc_var();
                 self.constraints.add_quadratic_constraint(var, temp, result_var);
+                self.constraints.add_linear_constraint(vec![(result_var, F::one())], F::zero());
+                
+                if let Some(v) = self.value(var) {
+                    self.assign(temp, v - F::one());
+                    self.assign(result_var, v * (v - F::one()));
+                }
             }
             Constraint::Range { var, bits } => {
                 // Decompose into bits
                 let bit_vars = self.alloc_vars(bits);
+                if let Some(v) = self.value(var) {
+                    let bytes = v.to_canonical_bytes();
+                    for (i, &bit) in bit_vars.iter().enumerate() {
+                        let set = bytes.get(i / 8).is_some_and(|b| b >> (i % 8) & 1 == 1);
+                        self.assign(bit, if set { F::one() } else { F::zero() });
+                    }
+                }
                 
                 // Each bit is boolean
                 for &bit in &bit_vars {
@@ -137,14 +220,26 @@ impl<F: Field> CircuitBuilder<F> for StandardCircuit<F> {
     fn alloc_var(&mut self) -> usize {
         let var = self.num_vars;
         self.num_vars += 1;
+        self.values.push(None);
+        self.constraints.num_witnesses = self.num_vars;
         var
     }
     
     fn alloc_vars(&mut self, count: usize) -> Vec<usize> {
         let start = self.num_vars;
         self.num_vars += count;
+        self.values.resize(self.num_vars, None);
+        self.constraints.num_witnesses = self.num_vars;
         (start..self.num_vars).collect()
     }
+    
+    fn assign(&mut self, var: usize, value: F) {
+        self.values[var] = Some(value);
+    }
+    
+    fn value(&self, var: usize) -> Option<F> {
+        self.values.get(var).copied().flatten()
+    }
 }
 
 /// Layered circuit for Sumcheck
@@ -200,6 +295,9 @@ pub mod utils {
             coeffs: vec![(a, F::one()), (b, F::one()), (c, -F::one())],
             constant: F::zero(),
         })?;
+        if let (Some(x), Some(y)) = (circuit.value(a), circuit.value(b)) {
+            circuit.assign(c, x + y);
+        }
         Ok(c)
     }
     
@@ -211,6 +309,9 @@ pub mod utils {
     ) -> Result<usize> {
         let c = circuit.alloc_var();
         circuit.add_constraint(Constraint::Quadratic { x: a, y: b, z: c })?;
+        if let (Some(x), Some(y)) = (circuit.value(a), circuit.value(b)) {
+            circuit.assign(c, x * y);
+        }
         Ok(c)
     }
     
@@ -219,7 +320,7 @@ pub mod utils {
         circuit: &mut C,
         value: F,
     ) -> Result<usize> {
-        let c = circuit.alloc_var();
+        let c = circuit.alloc_value(value);
         circuit.add_constraint(Constraint::Linear {
             coeffs: vec![(c, F::one())],
             constant: value,
@@ -0,0 +1,234 @@
+//! Merkle path verification circuits
+//!
+//! `verify_merkle_path` checks inside the constraint system that a leaf
+//! digest and a list of siblings hash up to a root, with the leaf position
+//! given as index bits. The hash is supplied by a `MerkleHashGadget`, whose
+//! digests are lists of variables: one field element for Poseidon, 256 bit
+//! variables for a SHA-256 gadget. `PoseidonMerkleGadget` reproduces
+//! `longfellow_merkle::PoseidonHasher` exactly, so a native tree's root and
+//! proofs can be checked in the circuit unchanged.
+
+use crate::{gadgets, hash, utils, CircuitBuilder, Constraint};
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use longfellow_merkle::poseidon::{self, PoseidonConfig, PoseidonHasher, PoseidonParams};
+use longfellow_merkle::MerkleProof;
+use std::marker::PhantomData;
+
+/// A Merkle tree hash expressed as constraints
+pub trait MerkleHashGadget<F: Field> {
+    /// Variables per digest
+    fn digest_len(&self) -> usize;
+
+    /// Digest of a leaf from its encoding in the gadget's native form
+    fn hash_leaf<C: CircuitBuilder<F>>(&self, circuit: &mut C, leaf: &[usize]) -> Result<Vec<usize>>;
+
+    /// Digest of an internal node at `height` (0 above the leaves)
+    fn hash_node<C: CircuitBuilder<F>>(
+        &self,
+        circuit: &mut C,
+        height: usize,
+        left: &[usize],
+        right: &[usize],
+    ) -> Result<Vec<usize>>;
+}
+
+/// Constrain `leaf_digest` to sit at the position given by `index_bits`
+/// (least significant first, one per level) under `root`
+pub fn verify_merkle_path<F, C, G>(
+    circuit: &mut C,
+    gadget: &G,
+    leaf_digest: &[usize],
+    index_bits: &[usize],
+    siblings: &[Vec<usize>],
+    root: &[usize],
+) -> Result<()>
+where
+    F: Field,
+    C: CircuitBuilder<F>,
+    G: MerkleHashGadget<F>,
+{
+    if index_bits.len() != siblings.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Path has {} index bits but {} siblings",
+            index_bits.len(),
+            siblings.len()
+        )));
+    }
+    let width = gadget.digest_len();
+    if leaf_digest.len() != width || root.len() != width || siblings.iter().any(|s| s.len() != width) {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Merkle digests must be {} variables",
+            width
+        )));
+    }
+
+    let mut current = leaf_digest.to_vec();
+    for (height, (&bit, sibling)) in index_bits.iter().zip(siblings).enumerate() {
+        // bit = 1: the current node is the right child
+        let mut left = Vec::with_capacity(width);
+        let mut right = Vec::with_capacity(width);
+        for (&cur, &sib) in current.iter().zip(sibling) {
+            left.push(gadgets::select(circuit, bit, sib, cur)?);
+            right.push(gadgets::select(circuit, bit, cur, sib)?);
+        }
+        current = gadget.hash_node(circuit, height, &left, &right)?;
+    }
+
+    for (&computed, &expected) in current.iter().zip(root) {
+        utils::assert_equal(circuit, computed, expected)?;
+    }
+    Ok(())
+}
+
+/// Poseidon Merkle hashing matching `longfellow_merkle::PoseidonHasher<P>`
+///
+/// Leaves are given as field elements and hashed with the leaf sponge; for
+/// a byte leaf that is its length followed by
+/// `poseidon::bytes_to_elements(data)`, as `PoseidonHasher::hash_leaf`
+/// absorbs it.
+pub struct PoseidonMerkleGadget<P: PoseidonConfig> {
+    _config: PhantomData<P>,
+}
+
+impl<P: PoseidonConfig> PoseidonMerkleGadget<P> {
+    pub fn new() -> Self {
+        Self {
+            _config: PhantomData,
+        }
+    }
+
+    fn params(&self) -> &'static PoseidonParams<P::F> {
+        P::params()
+    }
+
+    /// Allocate the index bits and siblings of a native proof, with values
+    pub fn alloc_proof<C: CircuitBuilder<P::F>>(
+        &self,
+        circuit: &mut C,
+        proof: &MerkleProof<PoseidonHasher<P>>,
+    ) -> Result<(Vec<usize>, Vec<Vec<usize>>)> {
+        let index_bits = (0..proof.siblings.len())
+            .map(|h| {
+                let bit = circuit.alloc_value(P::F::from_u64((proof.leaf_index >> h) as u64 & 1));
+                circuit.add_constraint(Constraint::Boolean { var: bit })?;
+                Ok(bit)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let siblings = proof
+            .siblings
+            .iter()
+            .map(|s| vec![circuit.alloc_value(PoseidonHasher::<P>::digest_to_field(s))])
+            .collect();
+        Ok((index_bits, siblings))
+    }
+
+    /// The permutation on state variables, as `PoseidonParams::permute`
+    pub fn permute<C: CircuitBuilder<P::F>>(&self, circuit: &mut C, state: &mut [usize]) -> Result<()> {
+        hash::poseidon_permute(circuit, self.params(), state)
+    }
+
+    /// Sponge over `elements` with capacity `tag`, as
+    /// `PoseidonParams::hash_elements`
+    pub fn hash_elements<C: CircuitBuilder<P::F>>(
+        &self,
+        circuit: &mut C,
+        tag: u64,
+        elements: &[usize],
+    ) -> Result<usize> {
+        hash::poseidon_sponge(circuit, self.params(), tag, elements)
+    }
+}
+
+impl<P: PoseidonConfig> Default for PoseidonMerkleGadget<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: PoseidonConfig> MerkleHashGadget<P::F> for PoseidonMerkleGadget<P> {
+    fn digest_len(&self) -> usize {
+        1
+    }
+
+    fn hash_leaf<C: CircuitBuilder<P::F>>(&self, circuit: &mut C, leaf: &[usize]) -> Result<Vec<usize>> {
+        Ok(vec![self.hash_elements(circuit, poseidon::LEAF_TAG, leaf)?])
+    }
+
+    fn hash_node<C: CircuitBuilder<P::F>>(
+        &self,
+        circuit: &mut C,
+        height: usize,
+        left: &[usize],
+        right: &[usize],
+    ) -> Result<Vec<usize>> {
+        let width = self.params().width();
+        let mut state = vec![utils::const_gate(circuit, P::F::zero())?; width];
+        state[0] = utils::const_gate(circuit, P::F::from_u64(poseidon::node_tag(height)))?;
+        state[1] = left[0];
+        state[2] = right[0];
+        self.permute(circuit, &mut state)?;
+        Ok(vec![state[1]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardCircuit;
+    use longfellow_algebra::Fp128;
+    use longfellow_merkle::poseidon::{bytes_to_elements, Fp128Poseidon};
+    use longfellow_merkle::{Fp128PoseidonHasher, MerkleTree};
+
+    /// Circuit checking that `data[index]` is in `tree`, with `root` as given
+    fn path_circuit(
+        tree: &MerkleTree<Fp128PoseidonHasher>,
+        data: &[u8],
+        index: usize,
+        root: Fp128,
+    ) -> StandardCircuit<Fp128> {
+        let gadget = PoseidonMerkleGadget::<Fp128Poseidon>::new();
+        let mut circuit = StandardCircuit::<Fp128>::new();
+
+        let mut elements = vec![Fp128::from_u64(data.len() as u64)];
+        elements.extend(bytes_to_elements::<Fp128>(data));
+        let leaf: Vec<usize> = elements.into_iter().map(|e| circuit.alloc_value(e)).collect();
+        let root = vec![circuit.alloc_value(root)];
+
+        let proof = tree.prove(index).unwrap();
+        let (bits, siblings) = gadget.alloc_proof(&mut circuit, &proof).unwrap();
+        let digest = gadget.hash_leaf(&mut circuit, &leaf).unwrap();
+        verify_merkle_path(&mut circuit, &gadget, &digest, &bits, &siblings, &root).unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_poseidon_path_matches_native_tree() {
+        let data: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 20]).collect();
+        let tree = MerkleTree::<Fp128PoseidonHasher>::new(&data).unwrap();
+        let root = Fp128PoseidonHasher::digest_to_field(tree.root());
+
+        for i in [0, 3, 4] {
+            assert!(path_circuit(&tree, &data[i], i, root).is_satisfied().unwrap());
+        }
+
+        // Wrong leaf or wrong root leaves the constraints unsatisfied
+        assert!(!path_circuit(&tree, &data[1], 2, root).is_satisfied().unwrap());
+        assert!(!path_circuit(&tree, &data[2], 2, root + Fp128::one()).is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_permutation_matches_native() {
+        let gadget = PoseidonMerkleGadget::<Fp128Poseidon>::new();
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let input = [Fp128::from_u64(1), Fp128::from_u64(2), Fp128::from_u64(3)];
+        let mut state: Vec<usize> = input.iter().map(|&x| circuit.alloc_value(x)).collect();
+        gadget.permute(&mut circuit, &mut state).unwrap();
+
+        let mut expected = input.to_vec();
+        Fp128Poseidon::params().permute(&mut expected);
+        let got: Vec<Fp128> = state.iter().map(|&v| circuit.value(v).unwrap()).collect();
+        assert_eq!(got, expected);
+        assert!(circuit.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,165 @@
+//! RSA signature verification circuits
+//!
+//! `verify_rs256` constrains an RSASSA-PKCS1-v1_5 signature with SHA-256,
+//! the JWS `RS256` algorithm, under a public modulus n and the exponent
+//! 65537. The signature is a `NonNativeField` element modulo n raised to
+//! the exponent by sixteen squarings and one multiplication, and the
+//! result must be exactly the padded encoding of the message hash:
+//!
+//! 0x00 || 0x01 || 0xff .. 0xff || 0x00 || DigestInfo(SHA-256) || hash
+//!
+//! Everything but the hash is fixed by the modulus length, so it is a
+//! constant and only the hash bits are variables.
+
+use crate::arithmetic::{NonNativeElement, NonNativeField};
+use crate::CircuitBuilder;
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use num_bigint::{BigInt, BigUint};
+
+/// The public exponent `verify_rs256` checks against
+pub const RSA_EXPONENT: u32 = 65537;
+
+/// Smallest modulus accepted, as RS256 requires
+pub const MIN_MODULUS_BITS: u64 = 2048;
+
+/// DER encoding of the SHA-256 DigestInfo ahead of the hash
+const SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20,
+];
+
+/// The arithmetic modulo an RSA public modulus
+pub fn modulus_field<F: Field>(modulus: &[u8]) -> Result<NonNativeField> {
+    let modulus = BigUint::from_bytes_be(modulus);
+    if modulus.bits() < MIN_MODULUS_BITS {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "RSA modulus of {} bits is below {} bits",
+            modulus.bits(),
+            MIN_MODULUS_BITS
+        )));
+    }
+    NonNativeField::new::<F>(modulus)
+}
+
+/// Allocate a signature, big-endian as in a JWS, with values
+pub fn alloc_signature<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    field: &NonNativeField,
+    signature: &[u8],
+) -> Result<NonNativeElement> {
+    if signature.len() != modulus_len(field) {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "RSA signature must be {} bytes, got {}",
+            modulus_len(field),
+            signature.len()
+        )));
+    }
+    field.alloc(circuit, Some(&BigUint::from_bytes_be(signature)))
+}
+
+/// Constrain `signature` to be a valid RS256 signature on the 256-bit
+/// hash `msg_hash` under the modulus of `field`
+///
+/// The hash is bits most significant first, as `Sha256Circuit` returns
+/// digests, and must already be constrained boolean.
+pub fn verify_rs256<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    field: &NonNativeField,
+    msg_hash: &[usize],
+    signature: &NonNativeElement,
+) -> Result<()> {
+    if msg_hash.len() != 256 {
+        return Err(LongfellowError::InvalidParameter(
+            "RS256 hash must be 256 bits".to_string()
+        ));
+    }
+    if field.modulus().bits() < MIN_MODULUS_BITS {
+        return Err(LongfellowError::InvalidParameter(
+            "RSA modulus is below 2048 bits".to_string()
+        ));
+    }
+
+    // A signature is an integer below n, so it has one encoding
+    field.assert_reduced(circuit, signature)?;
+
+    // e = 2^16 + 1: sixteen squarings give s^(2^16), one more product s^e
+    let mut power = signature.clone();
+    for _ in 0..RSA_EXPONENT.ilog2() {
+        power = field.mul(circuit, &power, &power)?;
+    }
+    let message = field.mul(circuit, &power, signature)?;
+
+    // The encoded message is below n, so reduced it is an integer identity
+    field.assert_reduced(circuit, &message)?;
+    let hash = field.from_bits(circuit, msg_hash)?;
+    let prefix = BigInt::from(padding_prefix(modulus_len(field)));
+    field.assert_integer_zero(circuit, &[], &[(1, &message), (-1, &hash)], &-prefix)
+}
+
+fn modulus_len(field: &NonNativeField) -> usize {
+    (field.modulus().bits() as usize).div_ceil(8)
+}
+
+/// The PKCS#1 v1.5 encoding of a k-byte block with the hash bytes zero
+fn padding_prefix(k: usize) -> BigUint {
+    let mut encoded = vec![0x00, 0x01];
+    encoded.resize(k - SHA256_DIGEST_INFO.len() - 32 - 1, 0xff);
+    encoded.push(0x00);
+    encoded.extend_from_slice(&SHA256_DIGEST_INFO);
+    encoded.resize(k, 0x00);
+    BigUint::from_bytes_be(&encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hash, StandardCircuit};
+    use longfellow_algebra::Fp128;
+    use sha2::{Digest, Sha256};
+
+    fn hex(digits: &str) -> Vec<u8> {
+        BigUint::parse_bytes(digits.as_bytes(), 16).unwrap().to_bytes_be()
+    }
+
+    /// A 2048-bit key and its signature on "sample"
+    const MODULUS: &str = concat!(
+        "cf30dba9cf2d59a88d5a859b6213fd0ed06dd9d08467c86854ce3ca362073a580ac5e35b42fb7bea694705a487376bc1",
+        "d0728eb337df6b5455f0e67e7f2b1ef69ae1d2ca575c6e8ea87cd2c1cce4810eef3b26bf2befae2cb39bd813bec21cf6",
+        "d63d5b772f20dcc4a39f7de837015c821b6b4e06bd0bd7b3ffd8b6c76881e2dfac1440e864293e9b11c0dd91cb5cff81",
+        "a5ba222e4c4e78cd283c5dd28d61a42dda0845774bb57103ac87860428f84430d0375f543f49db17ceafbeff71c2f57e",
+        "5e56ef10c52b4835e798dc153ea1c944e43485aad26685985d5eb08f98a8012791aabeff876b50fe49a756a5b2baf8b9",
+        "5a331f755cc8740f528610dfe3afd8b1",
+    );
+    const SIGNATURE: &str = concat!(
+        "1211df39b689839677f24a6d1683ed94e210e1344e4511ffe1ab3df3166ac8bd076699d6c6d0ff8dbee37c0885a38cbb",
+        "9b24a895b04b7542e2844eea698e49b4b7d5480d07c26e2b0332a0a1afc130192733acafd5df0942f7c91e5a0c5e0d52",
+        "4e9a8e506f605a22bd1c16945e7f6c70780e1a80823a5883c18233a942eee74436afa4ae1dd75c314a53c4e4030b8a79",
+        "b64a86708acb4723231a02dd5ef582fade343425da5ce54d596734b5837b905e933ce9aeabb829ade291390d906270d1",
+        "4f6474459decb0b6c18ab24a7269b9b618e031bb38d70e0b639ceb2faf072fd0f12cf47305d614c68ac029f51c3c0b5a",
+        "5d93ebf061244e9d23cc849e463d8965",
+    );
+
+    fn signature_circuit(message: &[u8], signature: &[u8]) -> StandardCircuit<Fp128> {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let field = modulus_field::<Fp128>(&hex(MODULUS)).unwrap();
+        let msg_hash = hash::alloc_bytes(&mut circuit, &Sha256::digest(message)).unwrap();
+        let signature = alloc_signature(&mut circuit, &field, signature).unwrap();
+        verify_rs256(&mut circuit, &field, &msg_hash, &signature).unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_verify_rs256() {
+        let signature = hex(SIGNATURE);
+        assert!(signature_circuit(b"sample", &signature).is_satisfied().unwrap());
+        assert!(!signature_circuit(b"sample!", &signature).is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_rejects_short_keys() {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        assert!(modulus_field::<Fp128>(&[0xff; 128]).is_err());
+        let field = modulus_field::<Fp128>(&hex(MODULUS)).unwrap();
+        assert!(alloc_signature(&mut circuit, &field, &[0x01; 255]).is_err());
+    }
+}
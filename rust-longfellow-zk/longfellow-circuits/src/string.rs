@@ -0,0 +1,269 @@
+//! Byte-string circuits
+//!
+//! Strings are one variable per byte, each below 256: `alloc_string` range
+//! checks the bytes it allocates and `from_bits` packs message-order bits,
+//! as the hash gadgets take and return them, into bytes. Comparisons pack
+//! up to `chunk_len` bytes into one field element, exactly since no chunk
+//! wraps the modulus, so a check costs one constraint per chunk rather
+//! than per byte.
+//!
+//! `assert_substring_at` proves that a claimed string occurs in a longer
+//! one at a committed offset without revealing the rest, e.g. that a JWT
+//! payload holds `"iss":"https://issuer.example"` or that a certificate
+//! names a given subject DN.
+
+use crate::arithmetic::biguint_from_field;
+use crate::{CircuitBuilder, Constraint, boolean, hash, utils};
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+
+/// Bytes packed per field element, so that the difference of two packed
+/// chunks cannot wrap the modulus
+pub fn chunk_len<F: Field>() -> Result<usize> {
+    let len = (F::MODULUS_BITS as usize).saturating_sub(2) / 8;
+    if F::CHAR_IS_TWO || len == 0 {
+        return Err(LongfellowError::InvalidParameter(
+            "String comparisons need a field of odd characteristic above 2^9".to_string()
+        ));
+    }
+    Ok(len)
+}
+
+/// Allocate the bytes of `value`, with values, range checked to 8 bits
+pub fn alloc_string<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, value: &[u8]) -> Result<Vec<usize>> {
+    let mut bytes = Vec::with_capacity(value.len());
+    for &byte in value {
+        let var = circuit.alloc_value(F::from_u64(byte as u64));
+        circuit.add_constraint(Constraint::Range { var, bits: 8 })?;
+        bytes.push(var);
+    }
+    Ok(bytes)
+}
+
+/// The constant string `value`, one constant variable per byte
+pub fn constant_string<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, value: &[u8]) -> Result<Vec<usize>> {
+    value
+        .iter()
+        .map(|&byte| utils::const_gate(circuit, F::from_u64(byte as u64)))
+        .collect()
+}
+
+/// Bytes from bits in message order, each byte most significant bit
+/// first; the bits must already be constrained boolean
+pub fn from_bits<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, bits: &[usize]) -> Result<Vec<usize>> {
+    if !bits.len().is_multiple_of(8) {
+        return Err(LongfellowError::InvalidParameter(
+            "String bits must be byte-aligned".to_string()
+        ));
+    }
+    bits.chunks(8)
+        .map(|byte| {
+            let terms: Vec<(usize, F)> = byte
+                .iter()
+                .enumerate()
+                .map(|(i, &bit)| (bit, F::from_u64(1 << (7 - i))))
+                .collect();
+            hash::affine(circuit, &terms, F::zero())
+        })
+        .collect()
+}
+
+/// Bytes held by the string, once every byte has a value below 256
+pub fn string_value<F: Field, C: CircuitBuilder<F>>(circuit: &C, bytes: &[usize]) -> Option<Vec<u8>> {
+    bytes
+        .iter()
+        .map(|&var| u8::try_from(biguint_from_field(&circuit.value(var)?)).ok())
+        .collect()
+}
+
+/// Constrain two strings of the same length to be equal
+pub fn assert_equal<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, a: &[usize], b: &[usize]) -> Result<()> {
+    check_lengths(a, b)?;
+    for (&x, &y) in a.iter().zip(b) {
+        utils::assert_equal(circuit, x, y)?;
+    }
+    Ok(())
+}
+
+/// Constrain a string to equal the public constant `expected`, one linear
+/// constraint per byte
+pub fn assert_equal_constant<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    a: &[usize],
+    expected: &[u8],
+) -> Result<()> {
+    if a.len() != expected.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "String of {} bytes compared with {} bytes",
+            a.len(),
+            expected.len()
+        )));
+    }
+    for (&var, &byte) in a.iter().zip(expected) {
+        circuit.add_constraint(Constraint::Linear {
+            coeffs: vec![(var, F::one())],
+            constant: F::from_u64(byte as u64),
+        })?;
+    }
+    Ok(())
+}
+
+/// Boolean variable, one if two strings of the same length are equal
+///
+/// Each chunk's packed difference is tested for zero with its witnessed
+/// inverse, and the chunk results are multiplied together: three products
+/// per chunk.
+pub fn is_equal<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, a: &[usize], b: &[usize]) -> Result<usize> {
+    check_lengths(a, b)?;
+    let len = chunk_len::<F>()?;
+    let mut equal = utils::const_gate(circuit, F::one())?;
+    for (x, y) in a.chunks(len).zip(b.chunks(len)) {
+        let difference = packed_difference(circuit, x, y)?;
+        let zero = is_zero(circuit, difference)?;
+        equal = utils::mul_gate(circuit, equal, zero)?;
+    }
+    Ok(equal)
+}
+
+/// Constrain `needle` to occur in `haystack` at the byte offset held by
+/// `offset`, which stays private
+///
+/// A `boolean::one_hot` selector over the candidate offsets picks the
+/// window, and for each chunk of the needle the selector-weighted
+/// differences between window and needle sum to zero. That is one product
+/// per candidate offset and needle chunk, and fails unless `offset` is at
+/// most `haystack.len() - needle.len()`.
+pub fn assert_substring_at<F: Field, C: CircuitBuilder<F>>(
+    circuit: &mut C,
+    haystack: &[usize],
+    needle: &[usize],
+    offset: usize,
+) -> Result<()> {
+    if needle.len() > haystack.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "A {}-byte string cannot occur in {} bytes",
+            needle.len(),
+            haystack.len()
+        )));
+    }
+    let len = chunk_len::<F>()?;
+    let candidates = haystack.len() - needle.len() + 1;
+    let selectors = boolean::one_hot(circuit, offset, candidates)?;
+
+    for (c, chunk) in needle.chunks(len).enumerate() {
+        let mut selected = Vec::with_capacity(candidates);
+        for (k, &selector) in selectors.iter().enumerate() {
+            let start = k + c * len;
+            let difference = packed_difference(circuit, &haystack[start..start + chunk.len()], chunk)?;
+            let product = utils::mul_gate(circuit, selector, difference)?;
+            selected.push((product, F::one()));
+        }
+        circuit.add_constraint(Constraint::Linear {
+            coeffs: selected,
+            constant: F::zero(),
+        })?;
+    }
+    Ok(())
+}
+
+fn check_lengths(a: &[usize], b: &[usize]) -> Result<()> {
+    if a.len() != b.len() {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "Strings of {} and {} bytes cannot be compared",
+            a.len(),
+            b.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Variable equal to the chunk `a` minus the chunk `b`, each read as a
+/// little-endian integer
+fn packed_difference<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, a: &[usize], b: &[usize]) -> Result<usize> {
+    let mut terms = Vec::with_capacity(2 * a.len());
+    let mut power = F::one();
+    for (&x, &y) in a.iter().zip(b) {
+        terms.push((x, power));
+        terms.push((y, -power));
+        power *= F::from_u64(256);
+    }
+    hash::affine(circuit, &terms, F::zero())
+}
+
+/// Boolean variable, one if `x` is zero: with w the inverse of x, or zero,
+/// z = 1 - xw and xz = 0
+fn is_zero<F: Field, C: CircuitBuilder<F>>(circuit: &mut C, x: usize) -> Result<usize> {
+    let inverse = circuit.alloc_var();
+    if let Some(value) = circuit.value(x) {
+        circuit.assign(inverse, value.invert().unwrap_or_else(F::zero));
+    }
+    let product = utils::mul_gate(circuit, x, inverse)?;
+    let result = hash::affine(circuit, &[(product, -F::one())], F::one())?;
+    let check = utils::mul_gate(circuit, x, result)?;
+    circuit.add_constraint(Constraint::Linear {
+        coeffs: vec![(check, F::one())],
+        constant: F::zero(),
+    })?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StandardCircuit;
+    use longfellow_algebra::Fp128;
+
+    const PAYLOAD: &[u8] = br#"{"sub":"1234567890","iss":"https://issuer.example","exp":1893456000}"#;
+    const CLAIM: &[u8] = br#""iss":"https://issuer.example""#;
+
+    fn substring_circuit(claim: &[u8], position: u64) -> StandardCircuit<Fp128> {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let haystack = alloc_string(&mut circuit, PAYLOAD).unwrap();
+        let needle = constant_string(&mut circuit, claim).unwrap();
+        let offset = circuit.alloc_value(Fp128::from_u64(position));
+        assert_substring_at(&mut circuit, &haystack, &needle, offset).unwrap();
+        circuit
+    }
+
+    #[test]
+    fn test_string_equality() {
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let issuer = alloc_string(&mut circuit, b"https://issuer.example").unwrap();
+        let other = alloc_string(&mut circuit, b"https://issuer.exampl3").unwrap();
+        assert_eq!(string_value(&circuit, &issuer).unwrap(), b"https://issuer.example");
+
+        let same = is_equal(&mut circuit, &issuer, &issuer).unwrap();
+        let differs = is_equal(&mut circuit, &issuer, &other).unwrap();
+        assert_eq!(circuit.value(same), Some(Fp128::one()));
+        assert_eq!(circuit.value(differs), Some(Fp128::zero()));
+        assert_equal_constant(&mut circuit, &issuer, b"https://issuer.example").unwrap();
+        assert!(circuit.is_satisfied().unwrap());
+
+        assert_equal(&mut circuit, &issuer, &other).unwrap();
+        assert!(!circuit.is_satisfied().unwrap());
+        assert!(assert_equal_constant(&mut circuit, &issuer, b"https://").is_err());
+
+        // Bytes from hash-style bits
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let bits = hash::alloc_bytes(&mut circuit, b"iss").unwrap();
+        let bytes = from_bits(&mut circuit, &bits).unwrap();
+        assert_eq!(string_value(&circuit, &bytes).unwrap(), b"iss");
+    }
+
+    #[test]
+    fn test_substring_at_offset() {
+        let position = PAYLOAD.windows(CLAIM.len()).position(|w| w == CLAIM).unwrap() as u64;
+        assert!(substring_circuit(CLAIM, position).is_satisfied().unwrap());
+        assert!(!substring_circuit(CLAIM, position + 1).is_satisfied().unwrap());
+        assert!(!substring_circuit(br#""iss":"https://issuer.evil.co""#, position).is_satisfied().unwrap());
+
+        // Offsets past the last window select nothing
+        assert!(!substring_circuit(CLAIM, PAYLOAD.len() as u64).is_satisfied().unwrap());
+
+        let mut circuit = StandardCircuit::<Fp128>::new();
+        let short = alloc_string(&mut circuit, b"iss").unwrap();
+        let long = alloc_string(&mut circuit, CLAIM).unwrap();
+        let offset = circuit.alloc_value(Fp128::zero());
+        assert!(assert_substring_at(&mut circuit, &short, &long, offset).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use alloc::string::String;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,6 +18,7 @@ pub enum LongfellowError {
     #[error("Circuit error: {0}")]
     CircuitError(String),
     
+    #[cfg(feature = "std")]
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     
@@ -39,4 +41,4 @@ pub enum LongfellowError {
     Other(String),
 }
 
-pub type Result<T> = std::result::Result<T, LongfellowError>;
\ No newline at end of file
+pub type Result<T> = core::result::Result<T, LongfellowError>;
\ No newline at end of file
@@ -1,3 +1,4 @@
+use alloc::{vec, vec::Vec};
 use serde::{Deserialize, Serialize};
 
 pub type Field = u64;
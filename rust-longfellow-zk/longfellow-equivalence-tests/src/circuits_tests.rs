@@ -6,6 +6,7 @@ use longfellow_circuits::{
     utils::*,
 };
 use longfellow_algebra::Fp128;
+use longfellow_merkle::poseidon::Fp128Poseidon;
 use std::time::Instant;
 
 #[test]
@@ -212,11 +213,11 @@ fn test_hash_circuits() {
         circuit.alloc_var(),
     ];
     
-    let mut poseidon = PoseidonCircuit::new(&mut circuit, 8, 3);
+    let mut poseidon = PoseidonCircuit::<Fp128Poseidon, _>::new(&mut circuit);
     let hash = poseidon.hash(&inputs).unwrap();
     assert!(hash > inputs[2]);
     
-    // Test SHA-256 circuit (simplified)
+    // Test SHA-256 circuit
     let message_bits = circuit.alloc_vars(256); // 32 bytes
     let mut sha256 = Sha256Circuit::new(&mut circuit);
     let sha_hash = sha256.hash(&message_bits).unwrap();
@@ -399,7 +400,7 @@ mod benchmarks {
         let iterations = 100;
         
         for _ in 0..iterations {
-            let mut poseidon = PoseidonCircuit::new(&mut circuit, 8, 3);
+            let mut poseidon = PoseidonCircuit::<Fp128Poseidon, _>::new(&mut circuit);
             let _ = poseidon.hash(&inputs[..3]).unwrap();
         }
         
@@ -1,14 +1,24 @@
-/// GF(2^128) - Galois Field implementation
-/// 
-/// This module implements arithmetic in GF(2^128) using the irreducible polynomial
-/// x^128 + x^7 + x^2 + x + 1
-
+//! GF(2^128) - Galois Field implementation
+//! 
+//! This module implements arithmetic in GF(2^128) using the irreducible polynomial
+//! x^128 + x^7 + x^2 + x + 1
+//!
+//! GF(2^128) contains GF(2^16) as a subfield, spanned over GF(2) by the
+//! powers g^0..g^15 of g = x^((2^128 - 1) / (2^16 - 1)). Subfield elements
+//! have a 16-bit encoding, their coordinates in that basis, which is what
+//! `Field::from_subfield` and `Field::to_subfield` convert to and from.
+
+use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use std::fmt::{self, Debug, Display};
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::OnceLock;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroize;
 
+/// Bits of the GF(2^16) subfield encoding
+pub const SUBFIELD_BITS: u32 = 16;
+
 /// GF(2^128) element represented as two 64-bit words
 #[derive(Clone, Copy, Zeroize)]
 #[repr(C, align(16))]
@@ -112,11 +122,11 @@ impl Gf2_128 {
         // Reduction
         let mut result = [0u64; 4];
         _mm_storeu_si128(result.as_mut_ptr() as *mut __m128i, p0);
+        _mm_storeu_si128((result.as_mut_ptr() as *mut __m128i).add(1), p1);
         let t = _mm_extract_epi64(middle, 0) as u64;
         result[1] ^= t;
         let t = _mm_extract_epi64(middle, 1) as u64;
         result[2] ^= t;
-        _mm_storeu_si128((result.as_mut_ptr() as *mut __m128i).add(1), p1);
 
         reduce_gf2_128(&mut result[0], &mut result[1], &mut result[2], &mut result[3]);
 
@@ -155,65 +165,69 @@ impl Gf2_128 {
         }
     }
 
-    /// Inversion using extended binary GCD
+    /// Inversion as a^(2^128 - 2), in constant time
     pub fn invert(&self) -> Option<Self> {
         if self.is_zero().into() {
             return None;
         }
 
-        // Extended binary GCD for GF(2^128)
-        let mut u = *self;
-        let mut v = Self { lo: Self::REDUCTION_POLY, hi: 1u64 << 63 }; // x^128 + reduction poly
-        let mut g1 = Self::ONE;
-        let mut g2 = Self::ZERO;
-
-        while !bool::from(u.is_zero()) {
-            // Find the degree (position of highest bit)
-            let deg_u = u.degree();
-            let deg_v = v.degree();
-
-            if deg_u < deg_v {
-                std::mem::swap(&mut u, &mut v);
-                std::mem::swap(&mut g1, &mut g2);
-            } else {
-                let shift = deg_u - deg_v;
-                u = u + v.shift_left(shift);
-                g1 = g1 + g2.shift_left(shift);
-            }
+        // a^(2 + 4 + ... + 2^127)
+        let mut power = *self;
+        let mut result = Self::ONE;
+        for _ in 1..128 {
+            power = power.square();
+            result *= power;
         }
 
-        Some(g2)
+        Some(result)
     }
 
-    /// Get the degree (position of highest set bit)
-    fn degree(&self) -> usize {
-        if self.hi != 0 {
-            64 + (63 - self.hi.leading_zeros() as usize)
-        } else if self.lo != 0 {
-            63 - self.lo.leading_zeros() as usize
-        } else {
-            0
-        }
+    fn as_u128(&self) -> u128 {
+        (self.hi as u128) << 64 | self.lo as u128
     }
+}
 
-    /// Left shift by n bits (multiplication by x^n)
-    fn shift_left(&self, n: usize) -> Self {
-        if n >= 128 {
-            Self::ZERO
-        } else if n >= 64 {
-            Self {
-                lo: 0,
-                hi: self.lo << (n - 64),
+/// Basis of the GF(2^16) subfield and its echelon form for decoding
+struct Subfield {
+    /// basis[i] = g^i
+    basis: [Gf2_128; SUBFIELD_BITS as usize],
+    /// (pivot bit, reduced vector, basis vectors summed into it)
+    echelon: Vec<(u32, u128, u64)>,
+}
+
+fn subfield() -> &'static Subfield {
+    static SUBFIELD: OnceLock<Subfield> = OnceLock::new();
+    SUBFIELD.get_or_init(|| {
+        // g = x^(1 + 2^16 + 2^32 + ... + 2^112)
+        let mut g = Gf2_128::ONE;
+        let mut frobenius = Gf2_128::new(2, 0);
+        for _ in 0..128 / SUBFIELD_BITS {
+            g *= frobenius;
+            for _ in 0..SUBFIELD_BITS {
+                frobenius = frobenius.square();
             }
-        } else if n > 0 {
-            Self {
-                lo: self.lo << n,
-                hi: (self.hi << n) | (self.lo >> (64 - n)),
+        }
+
+        let mut basis = [Gf2_128::ONE; SUBFIELD_BITS as usize];
+        for i in 1..basis.len() {
+            basis[i] = basis[i - 1] * g;
+        }
+
+        let mut echelon: Vec<(u32, u128, u64)> = Vec::with_capacity(basis.len());
+        for (i, b) in basis.iter().enumerate() {
+            let (mut vector, mut combination) = (b.as_u128(), 1u64 << i);
+            for &(pivot, row, row_combination) in &echelon {
+                if (vector >> pivot) & 1 == 1 {
+                    vector ^= row;
+                    combination ^= row_combination;
+                }
             }
-        } else {
-            *self
+            debug_assert!(vector != 0, "subfield basis is linearly dependent");
+            echelon.push((127 - vector.leading_zeros(), vector, combination));
         }
-    }
+
+        Subfield { basis, echelon }
+    })
 }
 
 /// Portable 64-bit carryless multiplication
@@ -235,19 +249,18 @@ fn gf2_mul_64(a: u64, b: u64) -> (u64, u64) {
 
 /// Reduction modulo x^128 + x^7 + x^2 + x + 1
 fn reduce_gf2_128(r0: &mut u64, r1: &mut u64, r2: &mut u64, r3: &mut u64) {
-    // Reduce 256-bit to 128-bit
+    // x^128 = x^7 + x^2 + x + 1, so the word at x^192 folds into the words
+    // at x^64 and x^128, and then the word at x^128 into those below it
     let t3 = *r3;
+    *r2 ^= (t3 >> 57) ^ (t3 >> 62) ^ (t3 >> 63);
+    *r1 ^= (t3 << 7) ^ (t3 << 2) ^ (t3 << 1) ^ t3;
+
     let t2 = *r2;
+    *r1 ^= (t2 >> 57) ^ (t2 >> 62) ^ (t2 >> 63);
+    *r0 ^= (t2 << 7) ^ (t2 << 2) ^ (t2 << 1) ^ t2;
 
     *r2 = 0;
     *r3 = 0;
-
-    // Multiply high 128 bits by reduction polynomial and XOR into low 128 bits
-    *r1 ^= t3 ^ (t3 >> 57) ^ (t3 >> 62) ^ (t3 >> 63);
-    *r0 ^= (t3 << 7) ^ (t3 << 2) ^ (t3 << 1) ^ t3;
-    
-    *r1 ^= t2 ^ (t2 >> 57) ^ (t2 >> 62) ^ (t2 >> 63);
-    *r0 ^= (t2 << 7) ^ (t2 << 2) ^ (t2 << 1) ^ t2;
 }
 
 impl ConstantTimeEq for Gf2_128 {
@@ -327,6 +340,154 @@ impl MulAssign for Gf2_128 {
     }
 }
 
+impl Neg for Gf2_128 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl<'a> Add<&'a Gf2_128> for Gf2_128 {
+    type Output = Self;
+
+    fn add(self, rhs: &'a Self) -> Self {
+        self + *rhs
+    }
+}
+
+impl<'a> Sub<&'a Gf2_128> for Gf2_128 {
+    type Output = Self;
+
+    fn sub(self, rhs: &'a Self) -> Self {
+        self - *rhs
+    }
+}
+
+impl<'a> Mul<&'a Gf2_128> for Gf2_128 {
+    type Output = Self;
+
+    fn mul(self, rhs: &'a Self) -> Self {
+        self.mul_clmul(rhs)
+    }
+}
+
+impl<'a> AddAssign<&'a Gf2_128> for Gf2_128 {
+    fn add_assign(&mut self, rhs: &'a Self) {
+        *self += *rhs;
+    }
+}
+
+impl<'a> SubAssign<&'a Gf2_128> for Gf2_128 {
+    fn sub_assign(&mut self, rhs: &'a Self) {
+        *self -= *rhs;
+    }
+}
+
+impl<'a> MulAssign<&'a Gf2_128> for Gf2_128 {
+    fn mul_assign(&mut self, rhs: &'a Self) {
+        *self = self.mul_clmul(rhs);
+    }
+}
+
+impl Field for Gf2_128 {
+    const ZERO: Self = Self::ZERO;
+    const ONE: Self = Self::ONE;
+    /// The reduction polynomial; see `order_limbs` for the field order
+    const MODULUS: &'static str = "0x100000000000000000000000000000087";
+    const MODULUS_BITS: u32 = 128;
+    const CHAR_IS_TWO: bool = true;
+    const SUBFIELD_BITS: u32 = SUBFIELD_BITS;
+
+    /// Each 16-bit chunk k of `val` as a subfield element times x^k, so
+    /// the map is injective and values below 2^16 stay in the subfield,
+    /// as evaluation points must for subfield rows to encode into it
+    fn from_u64(val: u64) -> Self {
+        (0..64 / SUBFIELD_BITS).fold(Self::ZERO, |acc, k| {
+            let chunk = (val >> (k * SUBFIELD_BITS)) & 0xffff;
+            acc + Self::from_subfield(chunk) * Self::new(1 << k, 0)
+        })
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self> {
+        Gf2_128::from_bytes_le(bytes)
+    }
+
+    fn to_bytes_le(&self) -> Vec<u8> {
+        Gf2_128::to_bytes_le(self).to_vec()
+    }
+
+    fn from_subfield(bits: u64) -> Self {
+        let subfield = subfield();
+        subfield.basis.iter().enumerate().fold(Self::ZERO, |acc, (i, b)| {
+            let selected = Self::conditional_select(&Self::ZERO, b, Choice::from(((bits >> i) & 1) as u8));
+            acc + selected
+        })
+    }
+
+    /// Variable time
+    fn to_subfield(&self) -> Option<u64> {
+        let mut vector = self.as_u128();
+        let mut bits = 0;
+        for &(pivot, row, combination) in &subfield().echelon {
+            if (vector >> pivot) & 1 == 1 {
+                vector ^= row;
+                bits ^= combination;
+            }
+        }
+        (vector == 0).then_some(bits)
+    }
+
+    /// Every byte string of the right length is an element
+    fn uniform_bytes_len() -> usize {
+        16
+    }
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let mut padded = [0u8; 16];
+        let len = bytes.len().min(16);
+        padded[..len].copy_from_slice(&bytes[..len]);
+        Self::new(
+            u64::from_le_bytes(padded[..8].try_into().unwrap()),
+            u64::from_le_bytes(padded[8..].try_into().unwrap()),
+        )
+    }
+
+    fn invert(&self) -> Option<Self> {
+        Gf2_128::invert(self)
+    }
+
+    fn order_limbs() -> Vec<u64> {
+        vec![0, 0, 1]
+    }
+
+    /// Squaring is a bijection in characteristic two
+    fn legendre(&self) -> i8 {
+        if *self == Self::ZERO { 0 } else { 1 }
+    }
+
+    /// The unique root a^(2^127)
+    fn sqrt(&self) -> Option<Self> {
+        let mut root = *self;
+        for _ in 0..127 {
+            root = Gf2_128::square(&root);
+        }
+        Some(root)
+    }
+
+    fn root_of_unity() -> Self {
+        Self::ONE
+    }
+
+    fn square(&self) -> Self {
+        Gf2_128::square(self)
+    }
+
+    fn characteristic() -> u64 {
+        2
+    }
+}
+
 impl Debug for Gf2_128 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Gf2_128({:016x}{:016x})", self.hi, self.lo)
@@ -410,6 +571,61 @@ mod tests {
         assert_eq!(sq1, sq2);
     }
 
+    #[test]
+    fn test_mul_matches_reference() {
+        // Schoolbook multiplication with reduction after every shift
+        fn reference(a: Gf2_128, b: Gf2_128) -> Gf2_128 {
+            let (mut a, b) = (a.as_u128(), b.as_u128());
+            let mut result = 0u128;
+            for i in 0..128 {
+                if (b >> i) & 1 == 1 {
+                    result ^= a;
+                }
+                let carry = a >> 127;
+                a = (a << 1) ^ (carry * 0x87);
+            }
+            Gf2_128::new(result as u64, (result >> 64) as u64)
+        }
+
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        for _ in 0..100 {
+            let a = Gf2_128::new(rng.gen(), rng.gen());
+            let b = Gf2_128::new(rng.gen(), rng.gen());
+            assert_eq!(a * b, reference(a, b));
+            assert_eq!(a.mul_portable(&b), reference(a, b));
+            assert_eq!(a.square(), reference(a, a));
+        }
+    }
+
+    #[test]
+    fn test_subfield() {
+        let g = subfield().basis[1];
+        let mut frobenius = g;
+        for _ in 0..SUBFIELD_BITS {
+            frobenius = frobenius.square();
+        }
+        assert_eq!(frobenius, g);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+        for _ in 0..100 {
+            let (a, b): (u16, u16) = (rng.gen(), rng.gen());
+            let (x, y) = (Gf2_128::from_subfield(a as u64), Gf2_128::from_subfield(b as u64));
+            assert_eq!(x.to_subfield(), Some(a as u64));
+            assert_eq!((x + y).to_subfield(), Some((a ^ b) as u64));
+            assert!((x * y).to_subfield().is_some());
+        }
+        assert_eq!(Gf2_128::ONE.to_subfield(), Some(1));
+        assert_eq!(<Gf2_128 as Field>::from_u64(0x1234), Gf2_128::from_subfield(0x1234));
+        assert_eq!(Gf2_128::new(2, 0).to_subfield(), None);
+
+        // Distinct integers map to distinct elements
+        let points: std::collections::HashSet<_> = [0u64, 1, 0xffff, 0x10000, 0x10001, u64::MAX]
+            .iter()
+            .map(|&v| <Gf2_128 as Field>::from_u64(v).as_u128())
+            .collect();
+        assert_eq!(points.len(), 6);
+    }
+
     #[test]
     fn test_distributivity() {
         let mut rng = ChaCha20Rng::seed_from_u64(42);
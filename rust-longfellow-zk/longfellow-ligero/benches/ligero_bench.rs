@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use longfellow_ligero::*;
-use longfellow_algebra::Fp128;
+use longfellow_algebra::{BabyBear4, Fp128, Mersenne31Ext3};
 use rand::rngs::OsRng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
@@ -60,7 +60,7 @@ fn bench_ligero_prove(c: &mut Criterion) {
             |b, _| {
                 let mut rng = ChaCha20Rng::seed_from_u64(42);
                 b.iter(|| {
-                    let proof = prover.prove(&witness, &mut rng).unwrap();
+                    let proof = prover.prove(&witness, &[], &mut rng).unwrap();
                     black_box(proof);
                 });
             },
@@ -79,7 +79,7 @@ fn bench_ligero_verify(c: &mut Criterion) {
         let witness = create_satisfying_witness(num_witnesses);
         
         let prover = LigeroProver::new(instance.clone()).unwrap();
-        let proof = prover.prove(&witness, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+        let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
         
         let verifier = LigeroVerifier::new(instance).unwrap();
         
@@ -88,7 +88,7 @@ fn bench_ligero_verify(c: &mut Criterion) {
             &num_witnesses,
             |b, _| {
                 b.iter(|| {
-                    let result = verifier.verify(&proof).unwrap();
+                    let result = verifier.verify(&proof, &[]).unwrap();
                     black_box(result);
                 });
             },
@@ -126,6 +126,43 @@ fn bench_tableau_operations(c: &mut Criterion) {
         });
     });
     
+    group.bench_function("to_column_major", |b| {
+        b.iter(|| black_box(tableau.to_column_major()));
+    });
+    
+    group.finish();
+}
+
+fn bench_small_field_tableau(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small_field_tableau");
+    
+    let params = LigeroParams::security_128();
+    let height = 100;
+    
+    group.bench_function("encode_fp128", |b| {
+        b.iter(|| {
+            let mut tableau = tableau::Tableau::<Fp128>::new(params.clone(), height);
+            tableau.encode_rows().unwrap();
+            black_box(tableau);
+        });
+    });
+    
+    group.bench_function("encode_babybear4", |b| {
+        b.iter(|| {
+            let mut tableau = tableau::Tableau::<BabyBear4>::new(params.clone(), height);
+            tableau.encode_rows().unwrap();
+            black_box(tableau);
+        });
+    });
+    
+    group.bench_function("encode_mersenne31_ext3", |b| {
+        b.iter(|| {
+            let mut tableau = tableau::Tableau::<Mersenne31Ext3>::new(params.clone(), height);
+            tableau.encode_rows().unwrap();
+            black_box(tableau);
+        });
+    });
+    
     group.finish();
 }
 
@@ -147,14 +184,14 @@ fn bench_merkle_tree(c: &mut Criterion) {
             &num_columns,
             |b, _| {
                 b.iter(|| {
-                    let tree = merkle::MerkleTree::new(&columns).unwrap();
+                    let tree = merkle::MerkleTree::new(&columns, merkle::DIGEST_LEN).unwrap();
                     black_box(tree.root());
                 });
             },
         );
         
         // Benchmark proof generation
-        let tree = merkle::MerkleTree::new(&columns).unwrap();
+        let tree = merkle::MerkleTree::new(&columns, merkle::DIGEST_LEN).unwrap();
         
         group.bench_with_input(
             BenchmarkId::new("prove", num_columns),
@@ -181,6 +218,7 @@ fn bench_merkle_tree(c: &mut Criterion) {
                         num_columns / 2,
                         &columns[num_columns / 2],
                         &proof,
+                        merkle::DIGEST_LEN,
                     );
                     black_box(result);
                 });
@@ -216,7 +254,7 @@ fn bench_constraint_system(c: &mut Criterion) {
             &size,
             |b, _| {
                 b.iter(|| {
-                    let satisfied = cs.is_satisfied(&witness).unwrap();
+                    let satisfied = cs.is_satisfied(&witness, &[]).unwrap();
                     black_box(satisfied);
                 });
             },
@@ -231,6 +269,7 @@ criterion_group!(
     bench_ligero_prove,
     bench_ligero_verify,
     bench_tableau_operations,
+    bench_small_field_tableau,
     bench_merkle_tree,
     bench_constraint_system
 );
@@ -54,7 +54,7 @@ fn main() -> Result<()> {
     }
     
     // Check if witness satisfies constraints
-    let satisfied = cs.is_satisfied(&witness)?;
+    let satisfied = cs.is_satisfied(&witness, &[])?;
     println!("Witness satisfies constraints: {}", satisfied);
     
     // Create Ligero instance with 80-bit security
@@ -73,9 +73,9 @@ fn main() -> Result<()> {
     // Generate proof
     println!("\nGenerating proof...");
     let mut rng = thread_rng();
-    let proof = prover.prove(&witness, &mut rng)?;
+    let proof = prover.prove(&witness, &[], &mut rng)?;
     println!("Proof generated successfully!");
-    println!("  Column roots: {}", proof.column_roots.len());
+    println!("  Column openings: {}", proof.column_openings.len());
     println!("  LDT responses: {}", proof.ldt_responses.len());
     
     // Create verifier  
@@ -83,7 +83,7 @@ fn main() -> Result<()> {
     
     // Verify proof
     println!("\nVerifying proof...");
-    let valid = verifier.verify(&proof)?;
+    let valid = verifier.verify(&proof, &[])?;
     println!("Proof valid: {}", valid);
     
     Ok(())
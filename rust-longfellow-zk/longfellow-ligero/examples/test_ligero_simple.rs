@@ -32,7 +32,7 @@ fn main() -> Result<()> {
     let witness = vec![two, four, Fp128::one()];
     
     // Check if witness satisfies constraints
-    let satisfied = cs.is_satisfied(&witness)?;
+    let satisfied = cs.is_satisfied(&witness, &[])?;
     println!("Witness satisfies constraints: {}", satisfied);
     
     // Create Ligero instance with 80-bit security
@@ -46,7 +46,7 @@ fn main() -> Result<()> {
     // Generate proof
     println!("\nGenerating proof...");
     let mut rng = thread_rng();
-    let proof = prover.prove(&witness, &mut rng)?;
+    let proof = prover.prove(&witness, &[], &mut rng)?;
     println!("Proof generated successfully!");
     
     // Create verifier  
@@ -54,7 +54,7 @@ fn main() -> Result<()> {
     
     // Verify proof
     println!("\nVerifying proof...");
-    let valid = verifier.verify(&proof)?;
+    let valid = verifier.verify(&proof, &[])?;
     println!("Proof valid: {}", valid);
     
     Ok(())
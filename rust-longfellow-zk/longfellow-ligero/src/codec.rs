@@ -0,0 +1,524 @@
+//! Ligero proof codec in the C++ longfellow-zk wire layout
+//!
+//! The C++ implementation writes a Ligero proof without lengths or tags:
+//! every size follows from the instance, and the opened column indices are
+//! recomputed by the verifier from the transcript. The sections are, in
+//! order,
+//!
+//! - the 32-byte commitment root
+//! - the LDT response (`row_message_size` elements)
+//! - the linear response (`dot_message_size` elements)
+//! - the quadratic response past the witness block, where it is zero by
+//!   construction (`dot_message_size - block_size` elements, omitted when
+//!   there are no quadratic constraints)
+//! - the opened columns, row-major: row 0 of every opening, then row 1, ...;
+//!   entries of rows packed into the subfield use the compact subfield
+//!   encoding (`SUBFIELD_BITS / 8` bytes)
+//! - a little-endian u32 digest count followed by the Merkle siblings of
+//!   each opening in order
+//!
+//! Field elements use their fixed-width little-endian canonical encoding.
+//!
+//! The compact layout, for proofs made with `optimize_size`, keeps the
+//! responses and columns as above but
+//!
+//! - cuts the root and every Merkle node to the instance's `digest_len`
+//! - writes the opened column indices as LEB128 varints after the responses
+//! - sends each Merkle sibling once across all openings, level by level in
+//!   increasing position, skipping nodes the verifier can compute from the
+//!   opened columns themselves
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+
+use crate::{
+    ColumnOpening, LigeroInstance, LigeroProof,
+    merkle::{hash_leaf, hash_node, DIGEST_LEN},
+    transcript::compute_instance_digest,
+    verifier::replay_challenges,
+};
+
+impl<F: Field> LigeroProof<F> {
+    /// Serialize in the C++ longfellow wire layout for `instance`
+    pub fn to_cpp_bytes(&self, instance: &LigeroInstance<F>) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&self.column_root);
+        self.write_responses(&mut out, instance)?;
+        self.write_columns(&mut out, instance)?;
+
+        let num_digests: usize = self.column_openings.iter().map(|o| o.merkle_proof.len()).sum();
+        let num_digests = u32::try_from(num_digests).map_err(|_| {
+            LongfellowError::SerializationError("Too many Merkle digests".to_string())
+        })?;
+        out.extend_from_slice(&num_digests.to_le_bytes());
+        for opening in &self.column_openings {
+            for digest in &opening.merkle_proof {
+                out.extend_from_slice(digest);
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Parse a proof in the C++ longfellow wire layout for `instance` and
+    /// the public inputs it is bound to
+    pub fn from_cpp_bytes(bytes: &[u8], instance: &LigeroInstance<F>, public: &[F]) -> Result<Self> {
+        let num_openings = instance.params.num_col_openings;
+        let depth = tree_depth(instance);
+
+        let mut reader = Reader { bytes, pos: 0 };
+        let column_root: [u8; DIGEST_LEN] = reader.take(DIGEST_LEN)?.try_into().unwrap();
+        let mut proof = reader.responses(instance, column_root)?;
+        let values = reader.columns(instance)?;
+
+        let num_digests = u32::from_le_bytes(reader.take(4)?.try_into().unwrap()) as usize;
+        if num_digests != num_openings * depth {
+            return Err(LongfellowError::SerializationError(format!(
+                "Expected {} Merkle digests, got {}",
+                num_openings * depth,
+                num_digests
+            )));
+        }
+        let mut paths = Vec::with_capacity(num_openings);
+        for _ in 0..num_openings {
+            let path = (0..depth)
+                .map(|_| Ok(reader.take(DIGEST_LEN)?.try_into().unwrap()))
+                .collect::<Result<Vec<[u8; DIGEST_LEN]>>>()?;
+            paths.push(path);
+        }
+        reader.finish()?;
+
+        // Indices are not on the wire; re-derive them as the verifier does
+        let instance_digest = compute_instance_digest(&instance.params, &instance.constraints);
+        let indices = replay_challenges(instance, &instance_digest, public, &[], &proof).column_indices;
+        proof.column_openings = indices
+            .into_iter()
+            .zip(values.into_iter().zip(paths))
+            .map(|(index, (values, merkle_proof))| ColumnOpening { index, values, merkle_proof })
+            .collect();
+
+        Ok(proof)
+    }
+
+    /// Serialize in the compact layout for `instance`
+    pub fn to_compact_bytes(&self, instance: &LigeroInstance<F>) -> Result<Vec<u8>> {
+        let digest_len = instance.params.digest_len();
+        let depth = tree_depth(instance);
+        if self.column_root[digest_len..].iter().any(|&b| b != 0) {
+            return Err(LongfellowError::SerializationError(
+                "Commitment root is wider than the instance digest length".to_string(),
+            ));
+        }
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&self.column_root[..digest_len]);
+        self.write_responses(&mut out, instance)?;
+        for opening in &self.column_openings {
+            write_varint(&mut out, opening.index as u64);
+        }
+        self.write_columns(&mut out, instance)?;
+
+        // Each node on some path, keyed by level and position; a node that
+        // two paths share must agree between them
+        let mut nodes = BTreeMap::new();
+        for opening in &self.column_openings {
+            if opening.merkle_proof.len() != depth {
+                return Err(LongfellowError::SerializationError(format!(
+                    "Expected Merkle paths of length {}, got {}",
+                    depth,
+                    opening.merkle_proof.len()
+                )));
+            }
+            for (level, sibling) in opening.merkle_proof.iter().enumerate() {
+                if sibling[digest_len..].iter().any(|&b| b != 0) {
+                    return Err(LongfellowError::SerializationError(
+                        "Merkle node is wider than the instance digest length".to_string(),
+                    ));
+                }
+                let position = (opening.index >> level) ^ 1;
+                if *nodes.entry((level, position)).or_insert(sibling) != sibling {
+                    return Err(LongfellowError::SerializationError(
+                        "Merkle paths disagree on a shared node".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let indices = self.column_openings.iter().map(|o| o.index);
+        for (level, position) in sent_siblings(indices, depth) {
+            out.extend_from_slice(&nodes[&(level, position)][..digest_len]);
+        }
+
+        Ok(out)
+    }
+
+    /// Parse a proof in the compact layout for `instance`
+    pub fn from_compact_bytes(bytes: &[u8], instance: &LigeroInstance<F>) -> Result<Self> {
+        let digest_len = instance.params.digest_len();
+        let depth = tree_depth(instance);
+
+        let mut reader = Reader { bytes, pos: 0 };
+        let column_root = reader.digest(digest_len)?;
+        let mut proof = reader.responses(instance, column_root)?;
+        let indices = (0..instance.params.num_col_openings)
+            .map(|_| {
+                let index = reader.varint()?;
+                usize::try_from(index)
+                    .ok()
+                    .filter(|&i| i < instance.params.block_enc_size())
+                    .ok_or_else(|| {
+                        LongfellowError::SerializationError(format!("Column index {} out of range", index))
+                    })
+            })
+            .collect::<Result<Vec<usize>>>()?;
+        let values = reader.columns(instance)?;
+
+        // Rebuild every node the paths need: the opened leaves and their
+        // ancestors are recomputed, the remaining siblings are on the wire
+        let mut nodes = BTreeMap::new();
+        for (&index, column) in indices.iter().zip(&values) {
+            let leaf = hash_leaf(column, digest_len);
+            if *nodes.entry((0, index)).or_insert(leaf) != leaf {
+                return Err(LongfellowError::SerializationError(
+                    "Column opened twice with different values".to_string(),
+                ));
+            }
+        }
+        let mut sent = sent_siblings(indices.iter().copied(), depth).into_iter().peekable();
+        let mut known: BTreeSet<usize> = indices.iter().copied().collect();
+        for level in 0..depth {
+            while let Some(&(_, position)) = sent.peek().filter(|&&(l, _)| l == level) {
+                nodes.insert((level, position), reader.digest(digest_len)?);
+                sent.next();
+            }
+            for &position in &known {
+                let left = nodes[&(level, position & !1)];
+                let right = nodes[&(level, position | 1)];
+                nodes.insert((level + 1, position >> 1), hash_node(&left, &right, digest_len));
+            }
+            known = known.iter().map(|&position| position >> 1).collect();
+        }
+        reader.finish()?;
+
+        proof.column_openings = indices
+            .into_iter()
+            .zip(values)
+            .map(|(index, values)| ColumnOpening {
+                index,
+                values,
+                merkle_proof: (0..depth).map(|level| nodes[&(level, (index >> level) ^ 1)]).collect(),
+            })
+            .collect();
+
+        Ok(proof)
+    }
+
+    fn write_responses(&self, out: &mut Vec<u8>, instance: &LigeroInstance<F>) -> Result<()> {
+        if self.ldt_responses.len() != 1 {
+            return Err(LongfellowError::SerializationError(
+                "C++ layout holds exactly one LDT response".to_string(),
+            ));
+        }
+        write_elements(out, &self.ldt_responses[0]);
+        if self.linear_responses.len() != instance.params.dot_message_size() {
+            return Err(LongfellowError::SerializationError(
+                "C++ layout holds the full linear response".to_string(),
+            ));
+        }
+        write_elements(out, &self.linear_responses);
+
+        if !self.quadratic_responses.is_empty() {
+            let block_size = instance.params.block_size;
+            if self.quadratic_responses.len() < block_size
+                || self.quadratic_responses[..block_size].iter().any(|&x| x != F::zero()) {
+                return Err(LongfellowError::SerializationError(
+                    "Quadratic response does not vanish on the witness block".to_string(),
+                ));
+            }
+            write_elements(out, &self.quadratic_responses[block_size..]);
+        }
+        Ok(())
+    }
+
+    fn write_columns(&self, out: &mut Vec<u8>, instance: &LigeroInstance<F>) -> Result<()> {
+        let subfield_rows = instance.params.subfield_rows(instance.constraints.subfield_boundary);
+        let height = self.column_openings.first().map_or(0, |o| o.values.len());
+        if self.column_openings.iter().any(|o| o.values.len() != height) {
+            return Err(LongfellowError::SerializationError(
+                "Opened columns have different heights".to_string(),
+            ));
+        }
+        for row in 0..height {
+            for opening in &self.column_openings {
+                let value = &opening.values[row];
+                if subfield_rows.contains(&row) {
+                    let bits = value.to_subfield().ok_or_else(|| {
+                        LongfellowError::SerializationError(
+                            "Opened value of a packed row is outside the subfield".to_string(),
+                        )
+                    })?;
+                    out.extend_from_slice(&bits.to_le_bytes()[..subfield_byte_len::<F>()]);
+                } else {
+                    out.extend_from_slice(&value.to_canonical_bytes());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merkle siblings the compact layout sends for openings at `indices`, in
+/// wire order: by level, then position, leaving out any sibling that is
+/// itself an opened leaf or the ancestor of one
+fn sent_siblings(indices: impl Iterator<Item = usize>, depth: usize) -> Vec<(usize, usize)> {
+    let mut known: BTreeSet<usize> = indices.collect();
+    let mut sent = Vec::new();
+    for level in 0..depth {
+        sent.extend(
+            known.iter()
+                .map(|&position| position ^ 1)
+                .filter(|sibling| !known.contains(sibling))
+                .map(|sibling| (level, sibling)),
+        );
+        known = known.iter().map(|&position| position >> 1).collect();
+    }
+    sent
+}
+
+fn tree_depth<F: Field>(instance: &LigeroInstance<F>) -> usize {
+    instance.params.block_enc_size().next_power_of_two().trailing_zeros() as usize
+}
+
+fn subfield_byte_len<F: Field>() -> usize {
+    (F::SUBFIELD_BITS as usize).div_ceil(8)
+}
+
+fn write_elements<F: Field>(out: &mut Vec<u8>, elements: &[F]) {
+    for x in elements {
+        out.extend_from_slice(&x.to_canonical_bytes());
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.bytes.len()).ok_or_else(|| {
+            LongfellowError::SerializationError("Truncated Ligero proof".to_string())
+        })?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn finish(&self) -> Result<()> {
+        if self.pos != self.bytes.len() {
+            return Err(LongfellowError::SerializationError(format!(
+                "{} trailing bytes after Ligero proof",
+                self.bytes.len() - self.pos
+            )));
+        }
+        Ok(())
+    }
+
+    fn digest(&mut self, len: usize) -> Result<[u8; DIGEST_LEN]> {
+        let mut digest = [0u8; DIGEST_LEN];
+        digest[..len].copy_from_slice(self.take(len)?);
+        Ok(digest)
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            let bits = (byte & 0x7f) as u64;
+            if bits << shift >> shift != bits {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(LongfellowError::SerializationError("Varint overflows 64 bits".to_string()))
+    }
+
+    fn element<F: Field>(&mut self) -> Result<F> {
+        F::from_canonical_bytes(self.take(F::canonical_byte_len())?)
+    }
+
+    fn subfield_element<F: Field>(&mut self) -> Result<F> {
+        let bytes = self.take(subfield_byte_len::<F>())?;
+        let bits = bytes.iter().rev().fold(0u64, |acc, &b| acc << 8 | b as u64);
+        if bits >> F::SUBFIELD_BITS != 0 {
+            return Err(LongfellowError::SerializationError(
+                "Subfield encoding out of range".to_string(),
+            ));
+        }
+        Ok(F::from_subfield(bits))
+    }
+
+    fn elements<F: Field>(&mut self, n: usize) -> Result<Vec<F>> {
+        (0..n).map(|_| self.element()).collect()
+    }
+
+    /// The three responses, into a proof with no openings yet
+    fn responses<F: Field>(
+        &mut self,
+        instance: &LigeroInstance<F>,
+        column_root: [u8; DIGEST_LEN],
+    ) -> Result<LigeroProof<F>> {
+        let params = &instance.params;
+        let ldt_response = self.elements(params.row_message_size())?;
+        let linear_responses = self.elements(params.dot_message_size())?;
+
+        let quadratic_responses = if instance.constraints.quadratic_constraints.constraints.is_empty() {
+            Vec::new()
+        } else {
+            let mut response = vec![F::zero(); params.block_size];
+            response.extend(self.elements::<F>(params.dot_message_size() - params.block_size)?);
+            response
+        };
+
+        Ok(LigeroProof {
+            column_root,
+            ldt_responses: vec![ldt_response],
+            linear_responses,
+            quadratic_responses,
+            column_openings: Vec::new(),
+            fri: None,
+            linear_ipa: None,
+        })
+    }
+
+    /// The opened columns, written row-major
+    fn columns<F: Field>(&mut self, instance: &LigeroInstance<F>) -> Result<Vec<Vec<F>>> {
+        let params = &instance.params;
+        let constraints = &instance.constraints;
+        let height = params.tableau_height(
+            params.num_witness_blocks(constraints.num_witnesses),
+            params.num_quadratic_rows(constraints.quadratic_constraints.constraints.len()),
+        );
+        let subfield_rows = params.subfield_rows(constraints.subfield_boundary);
+
+        let mut values = vec![Vec::with_capacity(height); params.num_col_openings];
+        for row in 0..height {
+            for column in values.iter_mut() {
+                column.push(if subfield_rows.contains(&row) {
+                    self.subfield_element()?
+                } else {
+                    self.element()?
+                });
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintSystem, LigeroParams, LigeroProver, LigeroVerifier};
+    use longfellow_algebra::Fp128;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn instance() -> LigeroInstance<Fp128> {
+        let mut cs = ConstraintSystem::<Fp128>::new(4);
+        cs.add_linear_constraint(
+            vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 1, 3);
+        LigeroInstance::new(LigeroParams::security_80(), cs).unwrap()
+    }
+
+    #[test]
+    fn test_cpp_bytes_round_trip() {
+        let witness = vec![Fp128::from(2), Fp128::from(3), Fp128::from(5), Fp128::from(6)];
+        let prover = LigeroProver::new(instance()).unwrap();
+        let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(7)).unwrap();
+
+        let bytes = proof.to_cpp_bytes(&instance()).unwrap();
+        let decoded = LigeroProof::from_cpp_bytes(&bytes, &instance(), &[]).unwrap();
+        assert_eq!(decoded.to_cpp_bytes(&instance()).unwrap(), bytes);
+        assert_eq!(decoded.quadratic_responses, proof.quadratic_responses);
+        let indices: Vec<usize> = proof.column_openings.iter().map(|o| o.index).collect();
+        let decoded_indices: Vec<usize> = decoded.column_openings.iter().map(|o| o.index).collect();
+        assert_eq!(decoded_indices, indices);
+        assert!(LigeroVerifier::new(instance()).unwrap().verify(&decoded, &[]).unwrap());
+
+        // Sizes are implied by the instance, so any truncation or excess fails
+        assert!(LigeroProof::from_cpp_bytes(&bytes[..bytes.len() - 1], &instance(), &[]).is_err());
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(LigeroProof::from_cpp_bytes(&longer, &instance(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_compact_bytes() {
+        let mut params = LigeroParams::security_80();
+        params.optimize_size = true;
+        let instance = LigeroInstance::new(params, instance().constraints).unwrap();
+        let witness = vec![Fp128::from(2), Fp128::from(3), Fp128::from(5), Fp128::from(6)];
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(7)).unwrap();
+
+        let bytes = proof.to_compact_bytes(&instance).unwrap();
+        let decoded = LigeroProof::from_compact_bytes(&bytes, &instance).unwrap();
+        assert_eq!(decoded.to_compact_bytes(&instance).unwrap(), bytes);
+        for (a, b) in decoded.column_openings.iter().zip(&proof.column_openings) {
+            assert_eq!(a.index, b.index);
+            assert_eq!(a.merkle_proof, b.merkle_proof);
+        }
+        let verifier = LigeroVerifier::new(instance.clone()).unwrap();
+        assert!(verifier.verify(&decoded, &[]).unwrap());
+
+        // At least 30% smaller than the C++ layout of the full-width proof
+        let full = prover_proof_bytes(&witness);
+        assert!(bytes.len() * 10 <= full * 7, "{} vs {} bytes", bytes.len(), full);
+
+        assert!(LigeroProof::from_compact_bytes(&bytes[..bytes.len() - 1], &instance).is_err());
+        let mut tampered = bytes.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        let tampered = LigeroProof::from_compact_bytes(&tampered, &instance).unwrap();
+        assert!(!verifier.verify(&tampered, &[]).unwrap());
+    }
+
+    fn prover_proof_bytes(witness: &[Fp128]) -> usize {
+        let prover = LigeroProver::new(instance()).unwrap();
+        let proof = prover.prove(witness, &[], &mut ChaCha20Rng::seed_from_u64(7)).unwrap();
+        proof.to_cpp_bytes(&instance()).unwrap().len()
+    }
+
+    #[test]
+    fn test_varint() {
+        for value in [0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+            write_varint(&mut out, value);
+            let mut reader = Reader { bytes: &out, pos: 0 };
+            assert_eq!(reader.varint().unwrap(), value);
+            reader.finish().unwrap();
+        }
+        let overlong = [0xffu8; 10];
+        assert!(Reader { bytes: &overlong, pos: 0 }.varint().is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//! Witness extraction from rewound Ligero provers, for tests
+//!
+//! The knowledge extractor rewinds the prover after its column commitment
+//! and asks again: accepting proofs under one column root but different
+//! challenges open different columns of the same committed tableau. Once
+//! they open `row_message_size` distinct columns between them, each row is
+//! the unique message through those columns, and the witness is read off
+//! its first block. The property tests run this against every parameter
+//! or encoding change: extraction must recover the committed witness.
+
+use std::collections::BTreeMap;
+
+use longfellow_algebra::interpolation::barycentric_weights;
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+
+use crate::{LigeroInstance, LigeroProof, LigeroVerifier, parameters::row_indices};
+
+/// Extract the witness from accepting proofs of one commitment, each given
+/// with the statement randomness it answers
+pub(crate) fn extract<F: Field>(
+    instance: &LigeroInstance<F>,
+    transcripts: &[(LigeroProof<F>, Vec<u8>)],
+    public: &[F],
+) -> Result<Vec<F>> {
+    let params = &instance.params;
+    let verifier = LigeroVerifier::new(instance.clone())?;
+    
+    let Some(((first, _), _)) = transcripts.split_first() else {
+        return Err(LongfellowError::InvalidParameter(
+            "Extraction needs at least one transcript".to_string()
+        ));
+    };
+    
+    for (i, (proof, statement_randomness)) in transcripts.iter().enumerate() {
+        if proof.column_root != first.column_root {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Transcript {} answers a different commitment", i
+            )));
+        }
+        if !verifier.verify_with_statement(proof, public, statement_randomness)? {
+            return Err(LongfellowError::VerificationError(format!(
+                "Transcript {} is rejected", i
+            )));
+        }
+    }
+    
+    // Rewinding only helps if the challenges moved
+    for (i, (proof, _)) in transcripts.iter().enumerate() {
+        let indices: Vec<usize> = proof.column_openings.iter().map(|c| c.index).collect();
+        let repeated = transcripts[..i].iter().any(|(earlier, _)| {
+            earlier.column_openings.iter().map(|c| c.index).eq(indices.iter().copied())
+        });
+        if repeated {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Transcript {} repeats an earlier challenge", i
+            )));
+        }
+    }
+    
+    // Union of the opened columns. They all hash to one root, so a column
+    // opened twice with different values is a Merkle collision
+    let mut columns: BTreeMap<usize, &[F]> = BTreeMap::new();
+    for (proof, _) in transcripts {
+        for opening in &proof.column_openings {
+            if let Some(&seen) = columns.get(&opening.index) {
+                if seen != opening.values.as_slice() {
+                    return Err(LongfellowError::ProofError(format!(
+                        "Column {} opened to two different values", opening.index
+                    )));
+                }
+            }
+            columns.insert(opening.index, &opening.values);
+        }
+    }
+    
+    let k = params.row_message_size();
+    if columns.len() < k {
+        return Err(LongfellowError::ProofError(format!(
+            "Transcripts open {} distinct columns, extraction needs {}",
+            columns.len(),
+            k
+        )));
+    }
+    
+    // Rows are messages of length k, so the first k columns determine them
+    // and the rest must agree
+    let (basis, checks): (Vec<_>, Vec<_>) = columns
+        .into_iter()
+        .enumerate()
+        .partition(|&(i, _)| i < k);
+    let nodes: Vec<F> = basis.iter().map(|&(_, (j, _))| F::from_u64(j as u64)).collect();
+    let weights = barycentric_weights(&nodes)?;
+    
+    let block_size = params.block_size;
+    let num_blocks = params.num_witness_blocks(instance.constraints.num_witnesses);
+    let mut witness = Vec::with_capacity(num_blocks * block_size);
+    
+    for row in row_indices::WITNESS_START..row_indices::WITNESS_START + num_blocks {
+        let values: Vec<F> = basis.iter().map(|&(_, (_, column))| column[row]).collect();
+        
+        for &(_, (j, column)) in &checks {
+            let at = lagrange_at(&nodes, &weights, F::from_u64(j as u64));
+            if dot(&at, &values) != column[row] {
+                return Err(LongfellowError::ProofError(format!(
+                    "Row {} is not a codeword at column {}", row, j
+                )));
+            }
+        }
+        
+        witness.extend((0..block_size).map(|x| {
+            dot(&lagrange_at(&nodes, &weights, F::from_u64(x as u64)), &values)
+        }));
+    }
+    
+    witness.truncate(instance.constraints.num_witnesses);
+    Ok(witness)
+}
+
+/// Lagrange basis over `nodes` at `x`, which is not a node:
+/// L_j(x) = Z(x) * w_j / (x - x_j)
+fn lagrange_at<F: Field>(nodes: &[F], weights: &[F], x: F) -> Vec<F> {
+    let mut diffs: Vec<F> = nodes.iter().map(|&n| x - n).collect();
+    let vanishing = diffs.iter().fold(F::one(), |acc, &d| acc * d);
+    F::batch_invert(&mut diffs);
+    diffs
+        .iter()
+        .zip(weights)
+        .map(|(&inv, &w)| vanishing * w * inv)
+        .collect()
+}
+
+fn dot<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).fold(F::zero(), |acc, (&x, &y)| acc + x * y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintSystem, LigeroParams, LigeroProver};
+    use longfellow_algebra::Fp128;
+    use proptest::prelude::*;
+    use rand::rngs::OsRng;
+    
+    const FREE: usize = 18;
+    
+    /// Rows of 16 values encoded 16-fold: two proofs of 40 openings each
+    /// almost always open the 56 columns a row message needs
+    fn extraction_params() -> LigeroParams {
+        LigeroParams {
+            block_size: 16,
+            extension_factor: 16,
+            num_col_openings: 40,
+            ..LigeroParams::security_80()
+        }
+    }
+    
+    /// `FREE` free values, then w[0] * w[1] and that plus w[2]
+    fn instance() -> LigeroInstance<Fp128> {
+        let mut cs = ConstraintSystem::<Fp128>::new(FREE + 2);
+        cs.add_quadratic_constraint(0, 1, FREE);
+        cs.add_linear_constraint(
+            vec![(FREE, Fp128::one()), (2, Fp128::one()), (FREE + 1, -Fp128::one())],
+            Fp128::zero(),
+        );
+        LigeroInstance::new(extraction_params(), cs).unwrap()
+    }
+    
+    fn witness(free: &[u64]) -> Vec<Fp128> {
+        let mut witness: Vec<Fp128> = free.iter().map(|&v| Fp128::from_u64(v)).collect();
+        witness.push(witness[0] * witness[1]);
+        witness.push(witness[FREE] + witness[2]);
+        witness
+    }
+    
+    /// Proofs of one committed tableau, rewound after the column root and
+    /// answered once per statement
+    fn rewound_proofs(
+        instance: &LigeroInstance<Fp128>,
+        witness: &[Fp128],
+        statements: &[Vec<u8>],
+    ) -> Vec<(LigeroProof<Fp128>, Vec<u8>)> {
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let setup = prover.setup().unwrap();
+        let mut tableau = prover.commit_with(&setup, witness, &[], &mut OsRng).unwrap();
+        setup.blind(&mut tableau, &mut OsRng).unwrap();
+        
+        statements
+            .iter()
+            .map(|statement| {
+                let proof = prover.prove_tableau(&setup, &tableau, &[], statement).unwrap();
+                (proof, statement.clone())
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn test_one_transcript_is_not_enough() {
+        let instance = instance();
+        let witness = witness(&[3; FREE]);
+        let transcripts = rewound_proofs(&instance, &witness, &[b"only".to_vec()]);
+        
+        assert!(extract(&instance, &transcripts, &[]).is_err());
+    }
+    
+    #[test]
+    fn test_rejects_tampered_transcript() {
+        let instance = instance();
+        let witness = witness(&[5; FREE]);
+        let mut transcripts = rewound_proofs(
+            &instance,
+            &witness,
+            &[b"first".to_vec(), b"second".to_vec()],
+        );
+        transcripts[1].0.column_openings[0].values[row_indices::WITNESS_START] += Fp128::one();
+        
+        assert!(extract(&instance, &transcripts, &[]).is_err());
+    }
+    
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+        
+        #[test]
+        fn extracts_committed_witness(
+            free in prop::collection::vec(any::<u64>(), FREE),
+            first in any::<u64>(),
+            second in any::<u64>(),
+        ) {
+            prop_assume!(first != second);
+            let instance = instance();
+            let witness = witness(&free);
+            let statements = [first.to_le_bytes().to_vec(), second.to_le_bytes().to_vec()];
+            let transcripts = rewound_proofs(&instance, &witness, &statements);
+            
+            let extracted = extract(&instance, &transcripts, &[]).unwrap();
+            prop_assert!(instance.constraints.check(&extracted, &[]).unwrap().is_valid());
+            prop_assert_eq!(extracted, witness);
+        }
+    }
+}
@@ -0,0 +1,607 @@
+//! FRI low-degree test backend
+//!
+//! With `LdtBackend::Fri` the prover does not send the combined LDT row
+//! u = ILDT + sum_i r_i * W_i. It commits to u's polynomial p, of degree
+//! below `row_message_size`, on a two-adic coset D instead. Rows live on the
+//! integer points 0..n, which FRI cannot fold, so the link to the tableau
+//! is a quotient: once the column indices Q are drawn, FRI shows that
+//!
+//!   q = (p - I) / Z_Q
+//!
+//! has degree below `block_size`, where I interpolates the combined opened
+//! columns on Q and Z_Q vanishes there. Then p agrees with the committed
+//! rows at every opened column, which is what the direct test checks with
+//! p in the clear. The verifier evaluates q from openings of p itself, so
+//! the first FRI layer costs no extra commitment.
+//!
+//! Each round folds f into f'(x^2) = (f(x) + f(-x)) / 2 + beta (f(x) - f(-x)) / 2x
+//! over the squared coset; layers are committed as Merkle trees over the
+//! pairs {f(x), f(-x)}, and the last layer is sent as coefficients.
+
+use alloc::{format, string::ToString, vec, vec::Vec};
+use longfellow_algebra::fft::FFT;
+use longfellow_algebra::interpolation::barycentric_weights;
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    par::*,
+    LigeroParams,
+    merkle::MerkleTree,
+    report::{Checked, VerificationFailure},
+    tableau::evaluate_message,
+    transcript::LigeroTranscript,
+};
+
+/// FRI proof that the combined LDT row is low-degree
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FriProof<F: Field> {
+    /// Roots of the committed layers: p on D, then every folded layer but
+    /// the last
+    pub layer_roots: Vec<[u8; 32]>,
+    
+    /// Coefficients of the last folded layer
+    pub final_poly: Vec<F>,
+    
+    /// Openings of every committed layer, one set per query
+    pub queries: Vec<Vec<FriOpening<F>>>,
+}
+
+/// Opened pair {f(x), f(-x)} of one FRI layer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FriOpening<F: Field> {
+    /// Values at x and -x
+    pub values: [F; 2],
+    
+    /// Authentication path to the layer root
+    pub merkle_proof: Vec<[u8; 32]>,
+}
+
+/// Verifier challenges of the FRI rounds
+pub(crate) struct FriChallenges<F: Field> {
+    pub folds: Vec<F>,
+    pub queries: Vec<usize>,
+}
+
+/// The coset D = shift * <omega> and its squarings, one per round
+pub(crate) struct FriDomain<F: Field> {
+    size: usize,
+    shift: F,
+    omega: F,
+}
+
+impl<F: Field> FriDomain<F> {
+    /// Domain for `params`; fails for fields without a large enough
+    /// two-adic subgroup
+    pub(crate) fn new(params: &LigeroParams) -> Result<Self> {
+        Self::with_size(params, params.fri_domain_size())
+    }
+    
+    /// Domain of `size` points, away from the tableau columns of `params`
+    pub(crate) fn with_size(params: &LigeroParams, size: usize) -> Result<Self> {
+        let omega = F::get_root_of_unity(size.trailing_zeros()).ok_or_else(|| {
+            LongfellowError::InvalidParameter(format!(
+                "FRI needs a multiplicative subgroup of size {}", size
+            ))
+        })?;
+        let shift = F::hash_to_field(b"LigeroFriDomain", &(size as u64).to_le_bytes());
+        
+        // q is undefined where D meets an openable column
+        let shift_n = shift.pow_vartime(&[size as u64]);
+        let hits_column = params.opening_columns()
+            .into_par_iter()
+            .any(|j| F::from_u64(j as u64).pow_vartime(&[size as u64]) == shift_n);
+        if shift == F::zero() || hits_column {
+            return Err(LongfellowError::InvalidParameter(
+                "FRI coset meets the tableau columns".to_string()
+            ));
+        }
+        
+        Ok(Self { size, shift, omega })
+    }
+    
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+    
+    /// Point `position` of the domain after `round` squarings
+    pub(crate) fn point(&self, round: usize, position: usize) -> F {
+        let exp = 1u64 << round;
+        self.shift.pow_vartime(&[exp]) * self.omega.pow_vartime(&[exp * position as u64])
+    }
+    
+    /// Every point of the domain after `round` squarings
+    pub(crate) fn points(&self, round: usize) -> Vec<F> {
+        let exp = 1u64 << round;
+        let shift = self.shift.pow_vartime(&[exp]);
+        let omega = self.omega.pow_vartime(&[exp]);
+        let mut points = Vec::with_capacity(self.size >> round);
+        let mut x = shift;
+        for _ in 0..self.size >> round {
+            points.push(x);
+            x *= omega;
+        }
+        points
+    }
+    
+    /// Evaluate `coeffs` on the whole domain
+    pub(crate) fn evaluate(&self, coeffs: &[F]) -> Result<Vec<F>> {
+        let mut values = vec![F::zero(); self.size];
+        let mut power = F::one();
+        for (v, &c) in values.iter_mut().zip(coeffs) {
+            *v = c * power;
+            power *= self.shift;
+        }
+        FFT::new(self.size, self.omega)?.forward(&mut values)?;
+        Ok(values)
+    }
+    
+    /// The first `len` coefficients of the polynomial with `values` on the
+    /// domain after `round` squarings
+    pub(crate) fn interpolate(&self, round: usize, mut values: Vec<F>, len: usize) -> Result<Vec<F>> {
+        let exp = 1u64 << round;
+        FFT::new(values.len(), self.omega.pow_vartime(&[exp]))?.inverse(&mut values)?;
+        let shift_inv = self.shift.pow_vartime(&[exp]).invert().ok_or_else(|| {
+            LongfellowError::ArithmeticError("FRI coset shift is zero".to_string())
+        })?;
+        let mut power = F::one();
+        for c in values.iter_mut() {
+            *c *= power;
+            power *= shift_inv;
+        }
+        values.truncate(len);
+        Ok(values)
+    }
+}
+
+/// Prover state between committing to p and answering the FRI rounds
+pub(crate) struct FriCommitment<F: Field> {
+    message: Vec<F>,
+    evaluations: Vec<F>,
+    tree: MerkleTree,
+}
+
+impl<F: Field> FriCommitment<F> {
+    /// Commit to the polynomial with `message` on 0..message.len() over D
+    pub(crate) fn new(message: Vec<F>, domain: &FriDomain<F>, params: &LigeroParams) -> Result<Self> {
+        let evaluations = domain.evaluate(&monomial_coefficients(&message))?;
+        let tree = commit_layer(&evaluations, params)?;
+        Ok(Self { message, evaluations, tree })
+    }
+    
+    pub(crate) fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+    
+    /// Run FRI on the quotient by the opened columns `column_indices`,
+    /// continuing `transcript` after they were drawn
+    pub(crate) fn prove(
+        self,
+        domain: &FriDomain<F>,
+        params: &LigeroParams,
+        column_indices: &[usize],
+        transcript: &mut LigeroTranscript,
+    ) -> Result<FriProof<F>> {
+        let num_rounds = params.fri_num_rounds();
+        let at_columns = evaluate_message(&self.message, column_indices)?;
+        let quotient = Quotient::new(column_indices, &at_columns)?;
+        let first = quotient.apply(&domain.points(0), &self.evaluations);
+        let folded = fold_layers(
+            first,
+            domain,
+            num_rounds,
+            params.block_size >> num_rounds,
+            params,
+            transcript,
+        )?;
+        
+        // Layer 0 is opened on p, which the verifier turns into q itself
+        let half = domain.size / 2;
+        let positions = transcript.challenge_fri_queries(0..half, params.num_ldt_queries);
+        let queries = positions
+            .into_iter()
+            .map(|position| {
+                let mut openings = vec![FriOpening {
+                    values: [self.evaluations[position], self.evaluations[position + half]],
+                    merkle_proof: self.tree.prove(position)?,
+                }];
+                openings.extend(folded.open(position)?);
+                Ok(openings)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        let mut layer_roots = vec![self.tree.root()];
+        layer_roots.extend(folded.roots());
+        let final_poly = folded.final_poly;
+        Ok(FriProof { layer_roots, final_poly, queries })
+    }
+}
+
+/// Replay the FRI rounds of `proof` on `transcript`, which has absorbed
+/// everything up to the column indices; `None` if the proof has the wrong
+/// number of layers
+pub(crate) fn replay_challenges<F: Field>(
+    proof: &FriProof<F>,
+    params: &LigeroParams,
+    transcript: &mut LigeroTranscript,
+) -> Option<FriChallenges<F>> {
+    if proof.layer_roots.len() != params.fri_num_rounds() {
+        return None;
+    }
+    
+    let folds = replay_folds(&proof.layer_roots[1..], &proof.final_poly, transcript);
+    let queries = transcript.challenge_fri_queries(
+        0..params.fri_domain_size() / 2,
+        params.num_ldt_queries,
+    );
+    
+    Some(FriChallenges { folds, queries })
+}
+
+/// Check `proof` for the combined row whose values at the opened columns
+/// `column_indices` are `at_columns`, naming the first query that fails
+pub(crate) fn verify<F: Field>(
+    proof: &FriProof<F>,
+    challenges: &FriChallenges<F>,
+    domain: &FriDomain<F>,
+    params: &LigeroParams,
+    column_indices: &[usize],
+    at_columns: &[F],
+) -> Result<Checked<()>> {
+    let num_rounds = params.fri_num_rounds();
+    if proof.final_poly.len() != params.block_size >> num_rounds
+        || proof.queries.len() != challenges.queries.len()
+        || proof.queries.iter().any(|openings| openings.len() != num_rounds)
+    {
+        return Ok(Err(VerificationFailure::LdtShape));
+    }
+    
+    let quotient = Quotient::new(column_indices, at_columns)?;
+    let digest_len = params.digest_len();
+    
+    let valid: Vec<bool> = proof.queries
+        .par_iter()
+        .zip(challenges.queries.par_iter())
+        .map(|(openings, &start)| {
+            // Layer 0 holds p, folded as q
+            let pair = start % (domain.size / 2);
+            let opening = &openings[0];
+            if !MerkleTree::verify(
+                &proof.layer_roots[0],
+                pair,
+                &opening.values,
+                &opening.merkle_proof,
+                digest_len,
+            ) {
+                return false;
+            }
+            let x = domain.point(0, pair);
+            let q = quotient.apply(&[x, -x], &opening.values);
+            
+            verify_folds(
+                domain,
+                start,
+                [q[0], q[1]],
+                &openings[1..],
+                &proof.layer_roots[1..],
+                &challenges.folds,
+                &proof.final_poly,
+                digest_len,
+            )
+        })
+        .collect();
+    
+    Ok(match valid.iter().position(|&ok| !ok) {
+        Some(query) => Err(VerificationFailure::LdtQuery { query }),
+        None => Ok(()),
+    })
+}
+
+/// Folded layers after the first, committed, and the last layer's
+/// coefficients
+pub(crate) struct FoldedLayers<F: Field> {
+    layers: Vec<Vec<F>>,
+    trees: Vec<MerkleTree>,
+    pub(crate) final_poly: Vec<F>,
+}
+
+impl<F: Field> FoldedLayers<F> {
+    /// Roots of the committed folded layers, in round order
+    pub(crate) fn roots(&self) -> Vec<[u8; 32]> {
+        self.trees.iter().map(|tree| tree.root()).collect()
+    }
+    
+    /// Openings of every committed folded layer for the query at `position`
+    pub(crate) fn open(&self, position: usize) -> Result<Vec<FriOpening<F>>> {
+        self.layers
+            .iter()
+            .zip(&self.trees)
+            .map(|(values, tree)| {
+                let half = values.len() / 2;
+                let pair = position % half;
+                Ok(FriOpening {
+                    values: [values[pair], values[pair + half]],
+                    merkle_proof: tree.prove(pair)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Fold `first`, on `domain`, `num_rounds` times down to `final_len`
+/// coefficients, committing to every layer in between
+///
+/// Draws a fold challenge per round and absorbs the layer roots and the
+/// final coefficients as it goes; the caller has already committed to
+/// `first` and answers its openings.
+pub(crate) fn fold_layers<F: Field>(
+    first: Vec<F>,
+    domain: &FriDomain<F>,
+    num_rounds: usize,
+    final_len: usize,
+    params: &LigeroParams,
+    transcript: &mut LigeroTranscript,
+) -> Result<FoldedLayers<F>> {
+    let mut layers = Vec::with_capacity(num_rounds);
+    let mut trees = Vec::with_capacity(num_rounds);
+    let mut last = first;
+    for round in 0..num_rounds {
+        let beta = transcript.challenge_fri_fold(round);
+        let next = fold(&last, &domain.points(round), beta);
+        if round + 1 < num_rounds {
+            let tree = commit_layer(&next, params)?;
+            transcript.append_fri_root(round + 1, &tree.root());
+            trees.push(tree);
+        }
+        let folded = core::mem::replace(&mut last, next);
+        if round > 0 {
+            layers.push(folded);
+        }
+    }
+    
+    let final_poly = domain.interpolate(num_rounds, last, final_len)?;
+    transcript.append_fri_final(&final_poly);
+    
+    Ok(FoldedLayers { layers, trees, final_poly })
+}
+
+/// Replay `fold_layers` from the roots of the folded layers and the final
+/// coefficients, returning the fold challenges
+pub(crate) fn replay_folds<F: Field>(
+    roots: &[[u8; 32]],
+    final_poly: &[F],
+    transcript: &mut LigeroTranscript,
+) -> Vec<F> {
+    let num_rounds = roots.len() + 1;
+    let mut folds = Vec::with_capacity(num_rounds);
+    for round in 0..num_rounds {
+        folds.push(transcript.challenge_fri_fold(round));
+        if let Some(root) = roots.get(round) {
+            transcript.append_fri_root(round + 1, root);
+        }
+    }
+    transcript.append_fri_final(final_poly);
+    folds
+}
+
+/// Check one query through the folded layers, starting from the pair
+/// `first` of layer 0 at position `start`
+///
+/// `openings` and `roots` cover the committed folded layers, one fewer than
+/// there are `folds`; the last fold must land on `final_poly`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_folds<F: Field>(
+    domain: &FriDomain<F>,
+    start: usize,
+    first: [F; 2],
+    openings: &[FriOpening<F>],
+    roots: &[[u8; 32]],
+    folds: &[F],
+    final_poly: &[F],
+    digest_len: usize,
+) -> bool {
+    let num_rounds = folds.len();
+    if num_rounds == 0 || openings.len() + 1 != num_rounds || roots.len() + 1 != num_rounds {
+        return false;
+    }
+    
+    let mut values = first;
+    for (round, &beta) in folds.iter().enumerate() {
+        let size = domain.size >> round;
+        let pair = start % (size / 2);
+        let folded = fold_pair(values, domain.point(round, pair), beta);
+        
+        if round + 1 == num_rounds {
+            let x = domain.point(num_rounds, start % (domain.size >> num_rounds));
+            let last = final_poly.iter().rev().fold(F::zero(), |acc, &c| acc * x + c);
+            return folded == last;
+        }
+        
+        // The fold lands at position start % (size / 2) of the next layer
+        let opening = &openings[round];
+        let next_pair = start % (size / 4);
+        if !MerkleTree::verify(&roots[round], next_pair, &opening.values, &opening.merkle_proof, digest_len)
+            || opening.values[(start % (size / 2)) / (size / 4)] != folded
+        {
+            return false;
+        }
+        values = opening.values;
+    }
+    false
+}
+
+/// q = (f - I) / Z_Q, with I the interpolant of the values at the opened
+/// columns Q
+///
+/// With barycentric weights w_j over Q, I = Z_Q * sum_j w_j u_j / (x - j),
+/// so q(x) = f(x) / Z_Q(x) - sum_j w_j u_j / (x - j).
+pub(crate) struct Quotient<F: Field> {
+    columns: Vec<F>,
+    weighted: Vec<F>,
+}
+
+impl<F: Field> Quotient<F> {
+    pub(crate) fn new(column_indices: &[usize], at_columns: &[F]) -> Result<Self> {
+        if column_indices.len() != at_columns.len() {
+            return Err(LongfellowError::InvalidParameter(
+                "Expected one combined value per opened column".to_string()
+            ));
+        }
+        let columns: Vec<F> = column_indices.iter().map(|&j| F::from_u64(j as u64)).collect();
+        let weights = barycentric_weights(&columns)?;
+        let weighted = weights.iter().zip(at_columns).map(|(&w, &u)| w * u).collect();
+        Ok(Self { columns, weighted })
+    }
+    
+    /// q at each of `points`, given f there
+    pub(crate) fn apply(&self, points: &[F], values: &[F]) -> Vec<F> {
+        points
+            .par_iter()
+            .zip(values.par_iter())
+            .map(|(&x, &f)| {
+                let mut inverses: Vec<F> = self.columns.iter().map(|&j| x - j).collect();
+                F::batch_invert(&mut inverses);
+                let vanishing_inv = inverses.iter().fold(F::one(), |acc, &d| acc * d);
+                let interpolant = inverses
+                    .iter()
+                    .zip(&self.weighted)
+                    .fold(F::zero(), |acc, (&d, &wu)| acc + d * wu);
+                f * vanishing_inv - interpolant
+            })
+            .collect()
+    }
+}
+
+/// Fold one pair {f(x), f(-x)} with challenge `beta`
+fn fold_pair<F: Field>(values: [F; 2], x: F, beta: F) -> F {
+    let [a, b] = values;
+    let two_inv = F::from_u64(2).invert().expect("FRI needs odd characteristic");
+    let x_inv = x.invert().expect("FRI domain avoids zero");
+    (a + b + beta * (a - b) * x_inv) * two_inv
+}
+
+/// Fold a whole layer on `points` with challenge `beta`
+fn fold<F: Field>(values: &[F], points: &[F], beta: F) -> Vec<F> {
+    let half = values.len() / 2;
+    let two_inv = F::from_u64(2).invert().expect("FRI needs odd characteristic");
+    let mut x_inv = points[..half].to_vec();
+    F::batch_invert(&mut x_inv);
+    (0..half)
+        .into_par_iter()
+        .map(|i| {
+            let (a, b) = (values[i], values[i + half]);
+            (a + b + beta * (a - b) * x_inv[i]) * two_inv
+        })
+        .collect()
+}
+
+/// Merkle tree over the pairs {f(x), f(-x)} of a layer
+pub(crate) fn commit_layer<F: Field>(values: &[F], params: &LigeroParams) -> Result<MerkleTree> {
+    let half = values.len() / 2;
+    let pairs: Vec<Vec<F>> = (0..half).map(|i| vec![values[i], values[i + half]]).collect();
+    MerkleTree::new(&pairs, params.digest_len())
+}
+
+/// Monomial coefficients of the polynomial with `message` on
+/// {0, .., message.len() - 1}
+///
+/// Divided differences on consecutive integers divide by j at level j, so
+/// they run in place; the Newton form is then expanded by Horner's rule.
+pub(crate) fn monomial_coefficients<F: Field>(message: &[F]) -> Vec<F> {
+    let n = message.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    
+    let mut inverses: Vec<F> = (0..n).map(|j| F::from_u64(j as u64)).collect();
+    F::batch_invert(&mut inverses);
+    
+    let mut diffs = message.to_vec();
+    for (j, &inverse) in inverses.iter().enumerate().skip(1) {
+        for i in (j..n).rev() {
+            diffs[i] = (diffs[i] - diffs[i - 1]) * inverse;
+        }
+    }
+    
+    let mut coeffs = vec![F::zero(); n];
+    coeffs[0] = diffs[n - 1];
+    for i in (0..n - 1).rev() {
+        // coeffs <- coeffs * (x - i) + diffs[i]
+        let xi = F::from_u64(i as u64);
+        for k in (1..n - i).rev() {
+            coeffs[k] = coeffs[k - 1] - xi * coeffs[k];
+        }
+        coeffs[0] = diffs[i] - xi * coeffs[0];
+    }
+    coeffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintSystem, LdtBackend, LigeroInstance, LigeroProver, LigeroVerifier, VerificationFailure};
+    use longfellow_algebra::Fp128;
+    use rand::rngs::OsRng;
+    
+    fn fri_params() -> LigeroParams {
+        LigeroParams {
+            block_size: 1024,
+            extension_factor: 2,
+            ldt: LdtBackend::Fri,
+            ..LigeroParams::security_80()
+        }
+    }
+    
+    #[test]
+    fn test_monomial_coefficients() {
+        let message: Vec<Fp128> = (0..20u64).map(|i| Fp128::from(i * i * 7 + 3)).collect();
+        let coeffs = monomial_coefficients(&message);
+        let points: Vec<usize> = (0..40).collect();
+        let expected = evaluate_message(&message, &points).unwrap();
+        for (x, e) in points.into_iter().zip(expected) {
+            let x = Fp128::from(x as u64);
+            assert_eq!(coeffs.iter().rev().fold(Fp128::zero(), |acc, &c| acc * x + c), e);
+        }
+    }
+    
+    #[test]
+    fn test_fri_proof() {
+        let mut cs = ConstraintSystem::<Fp128>::new(3);
+        cs.add_linear_constraint(
+            vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 0, 1);
+        let instance = LigeroInstance::new(fri_params(), cs).unwrap();
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let verifier = LigeroVerifier::new(instance).unwrap();
+        
+        let witness = vec![Fp128::from(3), Fp128::from(9), Fp128::from(12)];
+        let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
+        assert!(proof.ldt_responses.is_empty());
+        let fri = proof.fri.as_ref().unwrap();
+        assert_eq!(fri.layer_roots.len(), 6);
+        assert_eq!(fri.final_poly.len(), 16);
+        assert!(verifier.verify(&proof, &[]).unwrap());
+        assert!(verifier.verify_batch(&[proof.clone(), proof.clone()], &[vec![], vec![]]).unwrap());
+        
+        let mut bad = proof.clone();
+        bad.fri.as_mut().unwrap().final_poly[3] += Fp128::one();
+        assert!(!verifier.verify(&bad, &[]).unwrap());
+        
+        let mut bad = proof.clone();
+        bad.fri.as_mut().unwrap().queries[5][2].values[1] += Fp128::one();
+        assert!(!verifier.verify(&bad, &[]).unwrap());
+        let report = verifier.verify_with_report(&bad, &[], &[]).unwrap();
+        assert_eq!(report.failures(), &[VerificationFailure::LdtQuery { query: 5 }]);
+        
+        let mut bad = proof.clone();
+        bad.column_openings[0].values[0] += Fp128::one();
+        assert!(!verifier.verify(&bad, &[]).unwrap());
+        
+        let mut bad = proof;
+        bad.fri = None;
+        assert!(!verifier.verify(&bad, &[]).unwrap());
+    }
+}
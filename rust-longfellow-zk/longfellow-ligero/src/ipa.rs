@@ -0,0 +1,495 @@
+//! Ligero++ inner-product argument for the linear test
+//!
+//! With `LinearTest::InnerProduct` the prover does not send the product
+//! row q = IDOT + sum_i A_i * W_i, of degree below d = `dot_message_size`.
+//! It commits to q on a two-adic coset D and proves the two facts the
+//! direct test reads off q in the clear:
+//!
+//! - q agrees with the committed rows at the opened columns Q, as the
+//!   quotient c = (q - I) / Z_Q has degree below d - t, like the FRI LDT;
+//! - q sums to the claimed value r . b over the witness block H = {0, .., B - 1}.
+//!
+//! A sum over H is not a polynomial identity, but a weighted one is: with
+//! lambda_c = 1 / Z_H'(c), sum_c lambda_c q(c) is the X^{B-1} coefficient
+//! of q mod Z_H. So the blocks of A are scaled by mu_c = Z_H'(c) and IDOT
+//! is balanced against lambda, which keeps that weighted sum equal to the
+//! plain one. The prover commits to h = q div Z_H next to q, and the
+//! verifier derives
+//!
+//!   g = q - Z_H h - claimed X^{B-1}
+//!
+//! which has degree below B - 1 exactly when the sum is right. One FRI run
+//! then tests F = sum_f (alpha_f + beta_f X^{N - D_f}) f over f in {c, h, g},
+//! with D_f each term's degree bound and N = `ipa_degree_bound`.
+//!
+//! Everything opened is a function of q, which the direct test reveals in
+//! full, so the argument needs no extra blinding. The proof grows with the
+//! log of the product message instead of linearly, so it pays off once
+//! that message outweighs the FRI queries, i.e. for large blocks.
+
+use alloc::{vec, vec::Vec};
+use longfellow_algebra::traits::Field;
+use longfellow_core::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    par::*,
+    LigeroParams, LinearTest,
+    fri::{
+        FriDomain, FriOpening, Quotient,
+        fold_layers, monomial_coefficients, replay_folds, verify_folds,
+    },
+    merkle::MerkleTree,
+    report::{Checked, VerificationFailure},
+    tableau::evaluate_message,
+    transcript::LigeroTranscript,
+};
+
+/// Inner-product proof for the linear test
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerProductProof<F: Field> {
+    /// Root of the tree over the pairs {q(x), q(-x), h(x), h(-x)} on D
+    pub oracle_root: [u8; 32],
+    
+    /// Roots of the folded layers of F, all but the last
+    pub layer_roots: Vec<[u8; 32]>,
+    
+    /// Coefficients of the last folded layer
+    pub final_poly: Vec<F>,
+    
+    /// Openings for each query
+    pub queries: Vec<InnerProductQuery<F>>,
+}
+
+/// Openings of one inner-product query
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerProductQuery<F: Field> {
+    /// q(x), q(-x), h(x), h(-x)
+    pub oracle: [F; 4],
+    
+    /// Authentication path to the oracle root
+    pub oracle_proof: Vec<[u8; 32]>,
+    
+    /// Openings of the committed folded layers
+    pub layers: Vec<FriOpening<F>>,
+}
+
+/// Verifier challenges of the inner-product argument
+pub(crate) struct InnerProductChallenges<F: Field> {
+    pub batch: Vec<F>,
+    pub folds: Vec<F>,
+    pub queries: Vec<usize>,
+}
+
+/// Prover state between committing to q and answering the queries
+pub(crate) struct InnerProductCommitment<F: Field> {
+    message: Vec<F>,
+    q: Vec<F>,
+    h: Vec<F>,
+    g: Vec<F>,
+    tree: MerkleTree,
+}
+
+impl<F: Field> InnerProductCommitment<F> {
+    /// Commit to q, given by its `message` on 0..message.len(), and to
+    /// h = q div Z_H over D
+    pub(crate) fn new(message: Vec<F>, domain: &FriDomain<F>, params: &LigeroParams) -> Result<Self> {
+        let coeffs = monomial_coefficients(&message);
+        let (h_coeffs, mut g_coeffs) = divide(&coeffs, &vanishing_coefficients(params.block_size));
+        
+        // The top coefficient is the claimed sum, which g leaves out
+        g_coeffs[params.block_size - 1] = F::zero();
+        
+        let q = domain.evaluate(&coeffs)?;
+        let h = domain.evaluate(&h_coeffs)?;
+        let g = domain.evaluate(&g_coeffs)?;
+        
+        let half = domain.size() / 2;
+        let leaves: Vec<Vec<F>> = (0..half)
+            .map(|i| vec![q[i], q[i + half], h[i], h[i + half]])
+            .collect();
+        let tree = MerkleTree::new(&leaves, params.digest_len())?;
+        
+        Ok(Self { message, q, h, g, tree })
+    }
+    
+    pub(crate) fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+    
+    /// Run the batched FRI for the opened columns `column_indices`,
+    /// continuing `transcript` after they were drawn
+    pub(crate) fn prove(
+        self,
+        domain: &FriDomain<F>,
+        params: &LigeroParams,
+        column_indices: &[usize],
+        transcript: &mut LigeroTranscript,
+    ) -> Result<InnerProductProof<F>> {
+        let at_columns = evaluate_message(&self.message, column_indices)?;
+        let quotient = Quotient::new(column_indices, &at_columns)?;
+        let points = domain.points(0);
+        let c = quotient.apply(&points, &self.q);
+        
+        let batch = transcript.challenge_ipa_batch(BATCH_SIZE);
+        let bounds = degree_bounds(params);
+        let degree_bound = params.ipa_degree_bound();
+        let first = points
+            .par_iter()
+            .enumerate()
+            .map(|(i, &x)| batch_terms(x, [c[i], self.h[i], self.g[i]], bounds, &batch, degree_bound))
+            .collect();
+        
+        let num_rounds = params.ipa_num_rounds();
+        let folded = fold_layers(
+            first,
+            domain,
+            num_rounds,
+            degree_bound >> num_rounds,
+            params,
+            transcript,
+        )?;
+        
+        let half = domain.size() / 2;
+        let positions = transcript.challenge_fri_queries(0..half, params.num_ldt_queries);
+        let queries = positions
+            .into_iter()
+            .map(|position| {
+                Ok(InnerProductQuery {
+                    oracle: [
+                        self.q[position],
+                        self.q[position + half],
+                        self.h[position],
+                        self.h[position + half],
+                    ],
+                    oracle_proof: self.tree.prove(position)?,
+                    layers: folded.open(position)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        Ok(InnerProductProof {
+            oracle_root: self.tree.root(),
+            layer_roots: folded.roots(),
+            final_poly: folded.final_poly,
+            queries,
+        })
+    }
+}
+
+/// Replay the batched FRI of `proof` on `transcript`, which has absorbed
+/// everything up to the column indices and the LDT; `None` if the proof
+/// has the wrong number of layers
+pub(crate) fn replay_challenges<F: Field>(
+    proof: &InnerProductProof<F>,
+    params: &LigeroParams,
+    transcript: &mut LigeroTranscript,
+) -> Option<InnerProductChallenges<F>> {
+    if proof.layer_roots.len() + 1 != params.ipa_num_rounds() {
+        return None;
+    }
+    
+    let batch = transcript.challenge_ipa_batch(BATCH_SIZE);
+    let folds = replay_folds(&proof.layer_roots, &proof.final_poly, transcript);
+    let queries = transcript.challenge_fri_queries(
+        0..params.ipa_domain_size() / 2,
+        params.num_ldt_queries,
+    );
+    
+    Some(InnerProductChallenges { batch, folds, queries })
+}
+
+/// Check `proof` for the product row whose values at the opened columns
+/// `column_indices` are `at_columns` and whose weighted sum over the
+/// witness block is `claimed`, naming the first query that fails
+pub(crate) fn verify<F: Field>(
+    proof: &InnerProductProof<F>,
+    challenges: &InnerProductChallenges<F>,
+    domain: &FriDomain<F>,
+    params: &LigeroParams,
+    column_indices: &[usize],
+    at_columns: &[F],
+    claimed: F,
+) -> Result<Checked<()>> {
+    let num_rounds = params.ipa_num_rounds();
+    let degree_bound = params.ipa_degree_bound();
+    if proof.final_poly.len() != degree_bound >> num_rounds
+        || proof.queries.len() != challenges.queries.len()
+        || proof.queries.iter().any(|query| query.layers.len() + 1 != num_rounds)
+    {
+        return Ok(Err(VerificationFailure::LinearShape));
+    }
+    
+    let quotient = Quotient::new(column_indices, at_columns)?;
+    let bounds = degree_bounds(params);
+    let block_size = params.block_size;
+    let digest_len = params.digest_len();
+    
+    let valid: Vec<bool> = proof.queries
+        .par_iter()
+        .zip(challenges.queries.par_iter())
+        .map(|(query, &start)| {
+            if !MerkleTree::verify(
+                &proof.oracle_root,
+                start,
+                &query.oracle,
+                &query.oracle_proof,
+                digest_len,
+            ) {
+                return false;
+            }
+            
+            let x = domain.point(0, start);
+            let [q_x, q_neg, h_x, h_neg] = query.oracle;
+            let c = quotient.apply(&[x, -x], &[q_x, q_neg]);
+            let first = [(x, q_x, h_x, c[0]), (-x, q_neg, h_neg, c[1])].map(|(x, q, h, c)| {
+                let g = q - vanishing(x, block_size) * h - claimed * x.pow_vartime(&[block_size as u64 - 1]);
+                batch_terms(x, [c, h, g], bounds, &challenges.batch, degree_bound)
+            });
+            
+            verify_folds(
+                domain,
+                start,
+                first,
+                &query.layers,
+                &proof.layer_roots,
+                &challenges.folds,
+                &proof.final_poly,
+                digest_len,
+            )
+        })
+        .collect();
+    
+    Ok(match valid.iter().position(|&ok| !ok) {
+        Some(query) => Err(VerificationFailure::LinearQuery { query }),
+        None => Ok(()),
+    })
+}
+
+/// Batching coefficients: alpha and beta for each of c, h and g
+const BATCH_SIZE: usize = 6;
+
+/// Degree bounds D_f of c, h and g
+fn degree_bounds(params: &LigeroParams) -> [usize; 3] {
+    let dot_size = params.dot_message_size();
+    [
+        dot_size - params.num_col_openings,
+        dot_size - params.block_size,
+        params.block_size - 1,
+    ]
+}
+
+/// sum_f (alpha_f + beta_f x^{N - D_f}) f(x), lifting every term to degree
+/// bound N
+fn batch_terms<F: Field>(x: F, values: [F; 3], bounds: [usize; 3], batch: &[F], degree_bound: usize) -> F {
+    values
+        .iter()
+        .zip(bounds)
+        .zip(batch.chunks(2))
+        .fold(F::zero(), |acc, ((&f, bound), coeffs)| {
+            let shift = x.pow_vartime(&[(degree_bound - bound) as u64]);
+            acc + (coeffs[0] + coeffs[1] * shift) * f
+        })
+}
+
+/// Z_H(x) = (x - 0)(x - 1)..(x - (n - 1))
+fn vanishing<F: Field>(x: F, n: usize) -> F {
+    (0..n as u64).fold(F::one(), |acc, j| acc * (x - F::from_u64(j)))
+}
+
+/// Monomial coefficients of Z_H for H = {0, .., n - 1}, lowest first
+fn vanishing_coefficients<F: Field>(n: usize) -> Vec<F> {
+    let mut coeffs = vec![F::one()];
+    for j in 0..n as u64 {
+        // coeffs <- coeffs * (x - j)
+        let j = F::from_u64(j);
+        coeffs.push(F::zero());
+        for k in (1..coeffs.len()).rev() {
+            coeffs[k] = coeffs[k - 1] - j * coeffs[k];
+        }
+        coeffs[0] = -j * coeffs[0];
+    }
+    coeffs
+}
+
+/// Quotient and remainder of `coeffs` by the monic `divisor`
+fn divide<F: Field>(coeffs: &[F], divisor: &[F]) -> (Vec<F>, Vec<F>) {
+    let degree = divisor.len() - 1;
+    let mut remainder = coeffs.to_vec();
+    if remainder.len() < degree {
+        remainder.resize(degree, F::zero());
+        return (Vec::new(), remainder);
+    }
+    
+    let mut quotient = vec![F::zero(); remainder.len() - degree];
+    for k in (0..quotient.len()).rev() {
+        let lead = remainder[k + degree];
+        quotient[k] = lead;
+        for (r, &z) in remainder[k..k + degree].iter_mut().zip(divisor) {
+            *r -= lead * z;
+        }
+    }
+    remainder.truncate(degree);
+    (quotient, remainder)
+}
+
+/// mu_c = Z_H'(c) = (-1)^{n-1-c} c! (n-1-c)! for every c in H = {0, .., n - 1}
+fn vanishing_derivatives<F: Field>(n: usize) -> Vec<F> {
+    let mut factorials = vec![F::one(); n.max(1)];
+    for i in 1..n {
+        factorials[i] = factorials[i - 1] * F::from_u64(i as u64);
+    }
+    (0..n)
+        .map(|c| {
+            let mu = factorials[c] * factorials[n - 1 - c];
+            if (n - 1 - c).is_multiple_of(2) { mu } else { -mu }
+        })
+        .collect()
+}
+
+/// Scale each block of the linear coefficients A^T r by mu_c, so that
+/// the lambda-weighted sum of the product row is the plain inner product;
+/// no-op for the direct test
+pub(crate) fn weigh_coefficients<F: Field>(params: &LigeroParams, coeffs: &mut [F]) {
+    if params.linear_test != LinearTest::InnerProduct {
+        return;
+    }
+    let mu = vanishing_derivatives::<F>(params.block_size);
+    coeffs
+        .par_chunks_mut(params.block_size)
+        .for_each(|block| {
+            for (a, &m) in block.iter_mut().zip(&mu) {
+                *a *= m;
+            }
+        });
+}
+
+/// Set `mask[0]` so that the IDOT message sums to zero over the witness
+/// block, weighted by lambda for the inner-product argument
+pub(crate) fn balance_dot_mask<F: Field>(params: &LigeroParams, mask: &mut [F]) {
+    let block_size = params.block_size;
+    if params.linear_test != LinearTest::InnerProduct {
+        mask[0] = -mask[1..block_size].iter().fold(F::zero(), |acc, &x| acc + x);
+        return;
+    }
+    
+    let mu = vanishing_derivatives::<F>(block_size);
+    let mut lambda = mu.clone();
+    F::batch_invert(&mut lambda);
+    let sum = mask[1..block_size]
+        .iter()
+        .zip(&lambda[1..])
+        .fold(F::zero(), |acc, (&x, &l)| acc + l * x);
+    mask[0] = -mu[0] * sum;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintSystem, LdtBackend, LigeroInstance, LigeroProver, LigeroVerifier, VerificationFailure};
+    use longfellow_algebra::Fp128;
+    use rand::rngs::OsRng;
+    
+    #[test]
+    fn test_weighted_sum() {
+        // sum_c lambda_c q(c) is the top coefficient of q mod Z_H
+        let n = 6;
+        let message: Vec<Fp128> = (0..17u64).map(|i| Fp128::from(i * i * 3 + 5 * i + 1)).collect();
+        let (quotient, remainder) = divide(
+            &monomial_coefficients(&message),
+            &vanishing_coefficients(n),
+        );
+        assert_eq!(quotient.len(), 17 - n);
+        
+        let mut lambda = vanishing_derivatives::<Fp128>(n);
+        Fp128::batch_invert(&mut lambda);
+        let weighted = message[..n]
+            .iter()
+            .zip(&lambda)
+            .fold(Fp128::zero(), |acc, (&q, &l)| acc + l * q);
+        assert_eq!(remainder[n - 1], weighted);
+        
+        // And q = Z_H * quotient + remainder off H as well
+        let x = Fp128::from(40);
+        let eval = |coeffs: &[Fp128]| coeffs.iter().rev().fold(Fp128::zero(), |acc, &c| acc * x + c);
+        let expected = evaluate_message(&message, &[40]).unwrap()[0];
+        assert_eq!(vanishing(x, n) * eval(&quotient) + eval(&remainder), expected);
+    }
+    
+    #[test]
+    fn test_inner_product_proof() {
+        let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(3, 1);
+        cs.add_linear_constraint_with_public(
+            vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+            vec![(0, Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 0, 1);
+        let params = LigeroParams {
+            linear_test: LinearTest::InnerProduct,
+            ..LigeroParams::security_80()
+        };
+        let instance = LigeroInstance::new(params, cs).unwrap();
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let verifier = LigeroVerifier::new(instance).unwrap();
+        
+        // 3 + 9 - 13 + 1 = 0
+        let witness = vec![Fp128::from(3), Fp128::from(9), Fp128::from(13)];
+        let public = vec![Fp128::one()];
+        let proof = prover.prove(&witness, &public, &mut OsRng).unwrap();
+        assert!(proof.linear_responses.is_empty());
+        let ipa = proof.linear_ipa.as_ref().unwrap();
+        assert_eq!(ipa.layer_roots.len(), 4);
+        assert_eq!(ipa.final_poly.len(), 16);
+        assert!(verifier.verify(&proof, &public).unwrap());
+        assert!(!verifier.verify(&proof, &[Fp128::from(2)]).unwrap());
+        
+        let mut bad = proof.clone();
+        bad.linear_ipa.as_mut().unwrap().final_poly[0] += Fp128::one();
+        assert!(!verifier.verify(&bad, &public).unwrap());
+        
+        let mut bad = proof.clone();
+        bad.linear_ipa.as_mut().unwrap().queries[3].oracle[2] += Fp128::one();
+        assert!(!verifier.verify(&bad, &public).unwrap());
+        
+        let mut bad = proof.clone();
+        bad.linear_ipa.as_mut().unwrap().queries[7].layers[1].values[0] += Fp128::one();
+        assert!(!verifier.verify(&bad, &public).unwrap());
+        let report = verifier.verify_with_report(&bad, &public, &[]).unwrap();
+        assert_eq!(report.failures(), &[VerificationFailure::LinearQuery { query: 7 }]);
+        
+        let mut bad = proof.clone();
+        bad.column_openings[4].values[1] += Fp128::one();
+        assert!(!verifier.verify(&bad, &public).unwrap());
+        
+        let mut bad = proof;
+        bad.linear_ipa = None;
+        assert!(!verifier.verify(&bad, &public).unwrap());
+    }
+    
+    #[test]
+    fn test_with_fri_ldt() {
+        let mut cs = ConstraintSystem::<Fp128>::new(2);
+        cs.add_linear_constraint(vec![(0, Fp128::from(2)), (1, -Fp128::one())], Fp128::zero());
+        cs.add_quadratic_constraint(0, 0, 1);
+        let params = LigeroParams {
+            block_size: 1024,
+            extension_factor: 2,
+            ldt: LdtBackend::Fri,
+            linear_test: LinearTest::InnerProduct,
+            ..LigeroParams::security_80()
+        };
+        let instance = LigeroInstance::new(params, cs).unwrap();
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let verifier = LigeroVerifier::new(instance).unwrap();
+        
+        // Both responses are replaced: only the quadratic one is sent
+        let proof = prover.prove(&[Fp128::from(2), Fp128::from(4)], &[], &mut OsRng).unwrap();
+        assert!(proof.ldt_responses.is_empty() && proof.linear_responses.is_empty());
+        assert!(verifier.verify(&proof, &[]).unwrap());
+        
+        let mut bad = proof;
+        bad.fri.as_mut().unwrap().final_poly[1] += Fp128::one();
+        assert!(!verifier.verify(&bad, &[]).unwrap());
+    }
+}
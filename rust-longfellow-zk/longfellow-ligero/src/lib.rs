@@ -1,29 +1,52 @@
-/// Ligero: Lightweight Sublinear Arguments Without a Trusted Setup
-/// 
-/// This module implements the Ligero proof system for proving satisfiability
-/// of arithmetic circuits with linear and quadratic constraints.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Ligero: Lightweight Sublinear Arguments Without a Trusted Setup
+//! 
+//! This module implements the Ligero proof system for proving satisfiability
+//! of arithmetic circuits with linear and quadratic constraints.
+//! 
+//! Without the default `std` feature only verification is built, on
+//! `core` + `alloc`.
 
 pub mod tableau;
+#[cfg(feature = "std")]
 pub mod prover;
 pub mod verifier;
 pub mod transcript;
 pub mod merkle;
 pub mod parameters;
+pub mod codec;
+#[cfg(feature = "std")]
+pub mod streaming;
+pub mod fri;
+pub mod ipa;
+pub mod report;
+#[cfg(all(test, feature = "std"))]
+mod extraction;
+mod par;
+
+extern crate alloc;
+
+use alloc::{format, string::ToString, vec::Vec};
 
 use longfellow_algebra::traits::Field;
+use longfellow_arrays::CsrMatrix;
 use longfellow_core::{LongfellowError, Result};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 
-pub use prover::LigeroProver;
+#[cfg(feature = "std")]
+pub use prover::{LigeroProver, WitnessCommitment};
 pub use verifier::LigeroVerifier;
 pub use transcript::LigeroTranscript;
-pub use parameters::LigeroParams;
+pub use parameters::{LdtBackend, LigeroParams, LinearTest, TableauLayout};
+pub use report::{VerificationFailure, VerificationReport};
 
 /// Ligero proof structure
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LigeroProof<F: Field> {
-    /// Merkle root of column commitments
-    pub column_roots: Vec<[u8; 32]>,
+    /// Root of the Merkle tree over all tableau columns
+    pub column_root: [u8; 32],
     
     /// Low-degree test responses
     pub ldt_responses: Vec<Vec<F>>,
@@ -36,6 +59,16 @@ pub struct LigeroProof<F: Field> {
     
     /// Column opening proofs
     pub column_openings: Vec<ColumnOpening<F>>,
+    
+    /// FRI low-degree proof, in place of `ldt_responses` with
+    /// `LdtBackend::Fri`
+    #[serde(default)]
+    pub fri: Option<fri::FriProof<F>>,
+    
+    /// Inner-product argument, in place of `linear_responses` with
+    /// `LinearTest::InnerProduct`
+    #[serde(default)]
+    pub linear_ipa: Option<ipa::InnerProductProof<F>>,
 }
 
 /// Column opening with Merkle proof
@@ -47,7 +80,7 @@ pub struct ColumnOpening<F: Field> {
     /// Column values
     pub values: Vec<F>,
     
-    /// Merkle proof
+    /// Authentication path from the column hash to `column_root`
     pub merkle_proof: Vec<[u8; 32]>,
 }
 
@@ -57,18 +90,27 @@ pub struct ConstraintSystem<F: Field> {
     /// Number of witness variables
     pub num_witnesses: usize,
     
-    /// Linear constraints: A * w = b
+    /// Number of public inputs, supplied by both prover and verifier
+    pub num_public: usize,
+    
+    /// Witnesses below this index lie in the subfield of `F`
+    pub subfield_boundary: usize,
+    
+    /// Linear constraints: A * w + P * x = b
     pub linear_constraints: LinearConstraints<F>,
     
     /// Quadratic constraints: w[x] * w[y] = w[z]
     pub quadratic_constraints: QuadraticConstraints,
 }
 
-/// Linear constraints: A * w = b
+/// Linear constraints: A * w + P * x = b, with x the public inputs
 #[derive(Clone, Debug)]
 pub struct LinearConstraints<F: Field> {
-    /// Constraint matrix A (sparse representation)
-    pub matrix: Vec<(usize, usize, F)>, // (row, col, value)
+    /// Constraint matrix A, one row per constraint
+    pub matrix: CsrMatrix<F>,
+    
+    /// Public input matrix P, one row per constraint
+    pub public_matrix: CsrMatrix<F>,
     
     /// Right-hand side vector b
     pub rhs: Vec<F>,
@@ -86,10 +128,19 @@ pub struct QuadraticConstraints {
 
 impl<F: Field> ConstraintSystem<F> {
     pub fn new(num_witnesses: usize) -> Self {
+        Self::with_public_inputs(num_witnesses, 0)
+    }
+    
+    /// Constraint system over `num_witnesses` private and `num_public`
+    /// public variables
+    pub fn with_public_inputs(num_witnesses: usize, num_public: usize) -> Self {
         Self {
             num_witnesses,
+            num_public,
+            subfield_boundary: 0,
             linear_constraints: LinearConstraints {
-                matrix: Vec::new(),
+                matrix: CsrMatrix::new(num_witnesses),
+                public_matrix: CsrMatrix::new(num_public),
                 rhs: Vec::new(),
                 num_constraints: 0,
             },
@@ -99,19 +150,50 @@ impl<F: Field> ConstraintSystem<F> {
         }
     }
     
+    /// Declare that witnesses `0..boundary` lie in the subfield of `F`.
+    /// With `use_subfield` their full rows are packed and the verifier
+    /// checks membership at every opened column.
+    pub fn set_subfield_boundary(&mut self, boundary: usize) {
+        assert!(boundary <= self.num_witnesses);
+        self.subfield_boundary = boundary;
+    }
+    
     /// Add a linear constraint
     pub fn add_linear_constraint(&mut self, row: Vec<(usize, F)>, rhs: F) {
-        for (col, value) in row {
-            self.linear_constraints.matrix.push((
-                self.linear_constraints.num_constraints,
-                col,
-                value,
-            ));
-        }
+        self.add_linear_constraint_with_public(row, Vec::new(), rhs);
+    }
+    
+    /// Add a linear constraint sum(row) + sum(public_row) = rhs, where
+    /// `public_row` indexes the public inputs
+    pub fn add_linear_constraint_with_public(
+        &mut self,
+        row: Vec<(usize, F)>,
+        public_row: Vec<(usize, F)>,
+        rhs: F,
+    ) {
+        assert!(public_row.iter().all(|&(i, _)| i < self.num_public));
+        self.linear_constraints.matrix.push_row(row);
+        self.linear_constraints.public_matrix.push_row(public_row);
         self.linear_constraints.rhs.push(rhs);
         self.linear_constraints.num_constraints += 1;
     }
     
+    /// Right-hand side of the witness-only system A * w = b - P * x
+    pub fn effective_rhs(&self, public: &[F]) -> Result<Vec<F>> {
+        if public.len() != self.num_public {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Expected {} public inputs, got {}", self.num_public, public.len())
+            ));
+        }
+        
+        let public_terms = self.linear_constraints.public_matrix.mul_vec(public)?;
+        Ok(self.linear_constraints.rhs
+            .iter()
+            .zip(public_terms)
+            .map(|(&b, p)| b - p)
+            .collect())
+    }
+    
     /// Add a quadratic constraint: w[x] * w[y] = w[z]
     pub fn add_quadratic_constraint(&mut self, x: usize, y: usize, z: usize) {
         assert!(x < self.num_witnesses);
@@ -120,39 +202,103 @@ impl<F: Field> ConstraintSystem<F> {
         self.quadratic_constraints.constraints.push((x, y, z));
     }
     
-    /// Check if a witness satisfies all constraints
-    pub fn is_satisfied(&self, witness: &[F]) -> Result<bool> {
+    /// Hash of every constraint, coefficient and right-hand side
+    ///
+    /// Two systems of the same shape that differ in any coefficient have
+    /// different digests, so a transcript seeded with it binds a proof to
+    /// this exact statement.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"LigeroConstraintSystem");
+        hasher.update((self.num_witnesses as u64).to_le_bytes());
+        hasher.update((self.num_public as u64).to_le_bytes());
+        hasher.update((self.subfield_boundary as u64).to_le_bytes());
+        
+        let linear = &self.linear_constraints;
+        hasher.update((linear.num_constraints as u64).to_le_bytes());
+        hash_matrix(&mut hasher, &linear.matrix);
+        hash_matrix(&mut hasher, &linear.public_matrix);
+        hasher.update((linear.rhs.len() as u64).to_le_bytes());
+        for b in &linear.rhs {
+            hasher.update(b.to_canonical_bytes());
+        }
+        
+        let quadratic = &self.quadratic_constraints.constraints;
+        hasher.update((quadratic.len() as u64).to_le_bytes());
+        for &(x, y, z) in quadratic {
+            hasher.update((x as u64).to_le_bytes());
+            hasher.update((y as u64).to_le_bytes());
+            hasher.update((z as u64).to_le_bytes());
+        }
+        
+        hasher.finalize().into()
+    }
+    
+    /// Check if a witness satisfies all constraints for the given public
+    /// inputs
+    pub fn is_satisfied(&self, witness: &[F], public: &[F]) -> Result<bool> {
+        Ok(self.check(witness, public)?.is_valid())
+    }
+    
+    /// Every constraint a witness fails for the given public inputs:
+    /// subfield witnesses, linear rows and quadratic triples
+    pub fn check(&self, witness: &[F], public: &[F]) -> Result<VerificationReport> {
         if witness.len() != self.num_witnesses {
             return Err(LongfellowError::InvalidParameter(
                 format!("Expected {} witnesses, got {}", self.num_witnesses, witness.len())
             ));
         }
         
+        let mut failures: Vec<VerificationFailure> = witness[..self.subfield_boundary]
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.to_subfield().is_none())
+            .map(|(index, _)| VerificationFailure::SubfieldWitness { index })
+            .collect();
+        
         // Check linear constraints
-        for i in 0..self.linear_constraints.num_constraints {
-            let mut sum = F::zero();
-            for &(row, col, ref value) in &self.linear_constraints.matrix {
-                if row == i {
-                    sum += *value * witness[col];
-                }
-            }
-            if sum != self.linear_constraints.rhs[i] {
-                return Ok(false);
+        let matrix = &self.linear_constraints.matrix;
+        if matrix.num_cols() > witness.len() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Linear constraints reference {} witnesses, got {}",
+                matrix.num_cols(),
+                witness.len()
+            )));
+        }
+        let rhs = self.effective_rhs(public)?;
+        for (row, (constraint, rhs)) in matrix.rows().zip(rhs.iter()).enumerate() {
+            if constraint.dot(witness) != *rhs {
+                failures.push(VerificationFailure::LinearConstraint { row });
             }
         }
         
         // Check quadratic constraints
-        for &(x, y, z) in &self.quadratic_constraints.constraints {
+        for (triple, &(x, y, z)) in self.quadratic_constraints.constraints.iter().enumerate() {
             if witness[x] * witness[y] != witness[z] {
-                return Ok(false);
+                failures.push(VerificationFailure::QuadraticConstraint { triple });
             }
         }
         
-        Ok(true)
+        Ok(VerificationReport::new(failures))
+    }
+}
+
+/// Absorb a CSR matrix: its dimensions, then each row's columns and
+/// canonical values
+fn hash_matrix<F: Field>(hasher: &mut Sha3_256, matrix: &CsrMatrix<F>) {
+    hasher.update((matrix.num_rows() as u64).to_le_bytes());
+    hasher.update((matrix.num_cols() as u64).to_le_bytes());
+    for &ptr in matrix.row_ptr() {
+        hasher.update((ptr as u64).to_le_bytes());
+    }
+    for (&col, value) in matrix.col_indices().iter().zip(matrix.values()) {
+        hasher.update((col as u64).to_le_bytes());
+        hasher.update(value.to_canonical_bytes());
     }
 }
 
 /// Ligero instance combining parameters and constraint system
+#[derive(Clone, Debug)]
 pub struct LigeroInstance<F: Field> {
     pub params: LigeroParams,
     pub constraints: ConstraintSystem<F>,
@@ -169,8 +315,46 @@ impl<F: Field> LigeroInstance<F> {
             ));
         }
         
+        if params.use_subfield {
+            if F::SUBFIELD_BITS == 0 {
+                return Err(LongfellowError::InvalidParameter(
+                    "Field has no subfield to pack rows into".to_string()
+                ));
+            }
+            // Evaluation points 0..block_enc_size must be subfield elements
+            // for packed rows to encode into the subfield
+            if params.block_enc_size() as u64 > 1u64 << F::SUBFIELD_BITS {
+                return Err(LongfellowError::InvalidParameter(format!(
+                    "Encoded rows of {} columns exceed the {}-bit subfield",
+                    params.block_enc_size(),
+                    F::SUBFIELD_BITS
+                )));
+            }
+        } else if constraints.subfield_boundary > 0 {
+            return Err(LongfellowError::InvalidParameter(
+                "Subfield witnesses require use_subfield".to_string()
+            ));
+        }
+        
         Ok(Self { params, constraints })
     }
+    
+    /// Instance with the tableau dimensions of `params` chosen for
+    /// `constraints` by `LigeroParams::fit`, keeping its security settings
+    pub fn with_auto_params(params: LigeroParams, constraints: ConstraintSystem<F>) -> Result<Self> {
+        let max_width = if params.use_subfield && F::SUBFIELD_BITS > 0 {
+            // Packed rows evaluate on subfield points only
+            1usize.checked_shl(F::SUBFIELD_BITS).unwrap_or(usize::MAX)
+        } else {
+            usize::MAX
+        };
+        let params = params.fit_within(
+            constraints.num_witnesses,
+            constraints.quadratic_constraints.constraints.len(),
+            max_width,
+        )?;
+        Self::new(params, constraints)
+    }
 }
 
 #[cfg(test)]
@@ -199,7 +383,7 @@ mod tests {
             Fp128::zero(),
         ];
         
-        assert!(cs.is_satisfied(&witness).unwrap());
+        assert!(cs.is_satisfied(&witness, &[]).unwrap());
         
         // Test non-satisfying witness
         let bad_witness = vec![
@@ -209,6 +393,75 @@ mod tests {
             Fp128::zero(),
         ];
         
-        assert!(!cs.is_satisfied(&bad_witness).unwrap());
+        assert!(!cs.is_satisfied(&bad_witness, &[]).unwrap());
+    }
+    
+    #[test]
+    fn test_check_names_constraints() {
+        // w[0] + w[1] = w[2], w[1] + w[2] = 6, w[0] * w[1] = w[2]
+        let mut cs = ConstraintSystem::<Fp128>::new(3);
+        cs.add_linear_constraint(
+            vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_linear_constraint(vec![(1, Fp128::one()), (2, Fp128::one())], Fp128::from(6));
+        cs.add_quadratic_constraint(0, 1, 2);
+        
+        let good = [2u64, 2, 4].map(Fp128::from);
+        assert!(cs.check(&good, &[]).unwrap().failures().is_empty());
+        
+        // 0 + 0 = 0 and 0 * 0 = 0, but 0 + 0 != 6
+        let bad = [0u64, 0, 0].map(Fp128::from);
+        let report = cs.check(&bad, &[]).unwrap();
+        assert_eq!(report.failures(), &[VerificationFailure::LinearConstraint { row: 1 }]);
+        assert_eq!(report.to_string(), "linear constraint 1 is not satisfied");
+        
+        let bad = [3u64, 3, 3].map(Fp128::from);
+        let report = cs.check(&bad, &[]).unwrap();
+        assert_eq!(report.failures(), &[
+            VerificationFailure::LinearConstraint { row: 0 },
+            VerificationFailure::QuadraticConstraint { triple: 0 },
+        ]);
+        assert!(!report.is_valid());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_public_inputs() {
+        // w[0] + x[0] = 5 and w[0] * w[0] = w[1]
+        let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(2, 1);
+        cs.add_linear_constraint_with_public(
+            vec![(0, Fp128::one())],
+            vec![(0, Fp128::one())],
+            Fp128::from(5),
+        );
+        cs.add_quadratic_constraint(0, 0, 1);
+        
+        let witness = vec![Fp128::from(3), Fp128::from(9)];
+        assert!(cs.is_satisfied(&witness, &[Fp128::from(2)]).unwrap());
+        assert!(!cs.is_satisfied(&witness, &[Fp128::from(3)]).unwrap());
+        assert!(cs.is_satisfied(&witness, &[]).is_err());
+    }
+    
+    #[test]
+    fn test_digest_binds_coefficients() {
+        let build = |coeff: u64, rhs: u64| {
+            let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(3, 1);
+            cs.add_linear_constraint_with_public(
+                vec![(0, Fp128::one()), (1, Fp128::from(coeff))],
+                vec![(0, Fp128::one())],
+                Fp128::from(rhs),
+            );
+            cs.add_quadratic_constraint(0, 1, 2);
+            cs
+        };
+        
+        assert_eq!(build(2, 3).digest(), build(2, 3).digest());
+        // Same shape, one coefficient or right-hand side apart
+        assert_ne!(build(2, 3).digest(), build(4, 3).digest());
+        assert_ne!(build(2, 3).digest(), build(2, 5).digest());
+        
+        let mut other = build(2, 3);
+        other.quadratic_constraints.constraints[0] = (1, 0, 2);
+        assert_ne!(build(2, 3).digest(), other.digest());
+    }
+}
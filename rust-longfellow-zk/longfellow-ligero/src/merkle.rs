@@ -1,11 +1,18 @@
 /// Merkle tree implementation for column commitments
 
+use alloc::{collections::BTreeMap, format, string::ToString, vec, vec::Vec};
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use sha3::{Digest, Sha3_256};
-use std::collections::HashMap;
+use crate::par::*;
+
+/// Length of an untruncated digest
+pub const DIGEST_LEN: usize = 32;
 
 /// Merkle tree for committing to columns
+///
+/// Every node may be truncated to its first `digest_len` bytes, with the
+/// rest zeroed, so nodes keep one fixed-size type whatever the length.
 pub struct MerkleTree {
     /// Tree nodes (level -> nodes at that level)
     nodes: Vec<Vec<[u8; 32]>>,
@@ -16,21 +23,29 @@ pub struct MerkleTree {
 
 impl MerkleTree {
     /// Create a new Merkle tree from field element columns
-    pub fn new<F: Field>(columns: &[Vec<F>]) -> Result<Self> {
-        if columns.is_empty() {
+    pub fn new<F: Field>(columns: &[Vec<F>], digest_len: usize) -> Result<Self> {
+        Self::from_leaf_hashes(columns.iter().map(|c| hash_column(c)).collect(), digest_len)
+    }
+    
+    /// Build the tree over already-hashed columns, e.g. from a
+    /// `ColumnHasher`
+    pub fn from_leaf_hashes(mut leaf_hashes: Vec<[u8; 32]>, digest_len: usize) -> Result<Self> {
+        if leaf_hashes.is_empty() {
             return Err(LongfellowError::InvalidParameter(
                 "Cannot create Merkle tree with no columns".to_string()
             ));
         }
+        if digest_len == 0 || digest_len > DIGEST_LEN {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Digest length must be 1 to {} bytes, got {}", DIGEST_LEN, digest_len)
+            ));
+        }
         
-        let num_leaves = columns.len();
-        let mut nodes = Vec::new();
-        
-        // Compute leaf hashes
-        let mut leaf_hashes = Vec::with_capacity(num_leaves);
-        for column in columns {
-            leaf_hashes.push(hash_column(column));
+        let num_leaves = leaf_hashes.len();
+        for leaf in leaf_hashes.iter_mut() {
+            *leaf = truncate_digest(*leaf, digest_len);
         }
+        let mut nodes = Vec::new();
         
         // Pad to next power of 2
         let tree_size = num_leaves.next_power_of_two();
@@ -48,7 +63,7 @@ impl MerkleTree {
             for i in 0..current_size {
                 let left = &prev_level[2 * i];
                 let right = &prev_level[2 * i + 1];
-                level.push(hash_pair(left, right));
+                level.push(hash_node(left, right, digest_len));
             }
             
             nodes.push(level);
@@ -90,17 +105,18 @@ impl MerkleTree {
         index: usize,
         column: &[F],
         proof: &[[u8; 32]],
+        digest_len: usize,
     ) -> bool {
-        let mut current_hash = hash_column(column);
+        let mut current_hash = hash_leaf(column, digest_len);
         let mut current_index = index;
         
         for sibling in proof {
             if current_index & 1 == 0 {
                 // Current node is left child
-                current_hash = hash_pair(&current_hash, sibling);
+                current_hash = hash_node(&current_hash, sibling, digest_len);
             } else {
                 // Current node is right child
-                current_hash = hash_pair(sibling, &current_hash);
+                current_hash = hash_node(sibling, &current_hash, digest_len);
             }
             current_index /= 2;
         }
@@ -110,19 +126,95 @@ impl MerkleTree {
 }
 
 /// Hash a column of field elements
-fn hash_column<F: Field>(column: &[F]) -> [u8; 32] {
-    let mut hasher = Sha3_256::new();
-    hasher.update(b"LigeroColumn");
-    hasher.update(&(column.len() as u64).to_le_bytes());
+pub(crate) fn hash_column<F: Field>(column: &[F]) -> [u8; 32] {
+    let mut hasher = column_hasher(column.len());
     
     for elem in column {
-        let bytes = elem.to_bytes_le();
+        let bytes = elem.to_canonical_bytes();
         hasher.update(&bytes);
     }
     
     hasher.finalize().into()
 }
 
+/// Leaf of `column` in a tree of `digest_len`-byte digests
+pub(crate) fn hash_leaf<F: Field>(column: &[F], digest_len: usize) -> [u8; 32] {
+    truncate_digest(hash_column(column), digest_len)
+}
+
+/// Parent of two nodes in a tree of `digest_len`-byte digests
+pub(crate) fn hash_node(left: &[u8; 32], right: &[u8; 32], digest_len: usize) -> [u8; 32] {
+    truncate_digest(hash_pair(left, right), digest_len)
+}
+
+/// Keep the first `len` bytes of `digest` and zero the rest
+fn truncate_digest(mut digest: [u8; 32], len: usize) -> [u8; 32] {
+    digest[len..].fill(0);
+    digest
+}
+
+fn column_hasher(height: usize) -> Sha3_256 {
+    let mut hasher = Sha3_256::new();
+    hasher.update(b"LigeroColumn");
+    hasher.update((height as u64).to_le_bytes());
+    hasher
+}
+
+/// Column hashes accumulated row by row
+///
+/// Produces the same leaves as `MerkleTree::new` over the full columns
+/// while only ever holding the rows passed to `absorb_rows`.
+pub struct ColumnHasher {
+    hashers: Vec<Sha3_256>,
+    height: usize,
+    absorbed: usize,
+}
+
+impl ColumnHasher {
+    pub fn new(num_columns: usize, height: usize) -> Self {
+        Self {
+            hashers: vec![column_hasher(height); num_columns],
+            height,
+            absorbed: 0,
+        }
+    }
+    
+    /// Absorb the next rows of the tableau, in order
+    pub fn absorb_rows<F: Field>(&mut self, rows: &[Vec<F>]) -> Result<()> {
+        if self.absorbed + rows.len() > self.height {
+            return Err(LongfellowError::InvalidParameter(
+                "More rows than the declared column height".to_string()
+            ));
+        }
+        if rows.iter().any(|row| row.len() != self.hashers.len()) {
+            return Err(LongfellowError::InvalidParameter(
+                "Row width does not match the number of columns".to_string()
+            ));
+        }
+        
+        self.hashers.par_iter_mut().enumerate().for_each(|(j, hasher)| {
+            for row in rows {
+                hasher.update(row[j].to_canonical_bytes());
+            }
+        });
+        self.absorbed += rows.len();
+        
+        Ok(())
+    }
+    
+    /// Leaf hashes, once every row has been absorbed
+    pub fn finalize(self) -> Result<Vec<[u8; 32]>> {
+        if self.absorbed != self.height {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Absorbed {} of {} rows",
+                self.absorbed, self.height
+            )));
+        }
+        
+        Ok(self.hashers.into_iter().map(|h| h.finalize().into()).collect())
+    }
+}
+
 /// Hash two nodes together
 fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
     let mut hasher = Sha3_256::new();
@@ -138,18 +230,18 @@ pub struct MultiMerkleTree {
     trees: Vec<MerkleTree>,
     
     /// Mapping from global column index to (tree_index, local_index)
-    index_map: HashMap<usize, (usize, usize)>,
+    index_map: BTreeMap<usize, (usize, usize)>,
 }
 
 impl MultiMerkleTree {
     /// Create a multi-tree from multiple sets of columns
     pub fn new<F: Field>(column_sets: &[Vec<Vec<F>>]) -> Result<Self> {
         let mut trees = Vec::new();
-        let mut index_map = HashMap::new();
+        let mut index_map = BTreeMap::new();
         let mut global_index = 0;
         
         for (tree_idx, columns) in column_sets.iter().enumerate() {
-            let tree = MerkleTree::new(columns)?;
+            let tree = MerkleTree::new(columns, DIGEST_LEN)?;
             
             for local_idx in 0..columns.len() {
                 index_map.insert(global_index, (tree_idx, local_idx));
@@ -195,21 +287,63 @@ mod tests {
             })
             .collect();
         
-        let tree = MerkleTree::new(&columns).unwrap();
+        let tree = MerkleTree::new(&columns, DIGEST_LEN).unwrap();
         let root = tree.root();
         
         // Test proof generation and verification
         for i in 0..8 {
             let proof = tree.prove(i).unwrap();
-            assert!(MerkleTree::verify(&root, i, &columns[i], &proof));
+            assert!(MerkleTree::verify(&root, i, &columns[i], &proof, DIGEST_LEN));
             
             // Test invalid proof
             let mut bad_column = columns[i].clone();
             bad_column[0] += Fp128::one();
-            assert!(!MerkleTree::verify(&root, i, &bad_column, &proof));
+            assert!(!MerkleTree::verify(&root, i, &bad_column, &proof, DIGEST_LEN));
         }
     }
     
+    #[test]
+    fn test_truncated_digests() {
+        let columns: Vec<Vec<Fp128>> = (0..6)
+            .map(|i| (0..4).map(|j| Fp128::from((i * 4 + j) as u64)).collect())
+            .collect();
+        
+        let tree = MerkleTree::new(&columns, 16).unwrap();
+        let root = tree.root();
+        assert!(root[16..].iter().all(|&b| b == 0));
+        assert_ne!(root, MerkleTree::new(&columns, DIGEST_LEN).unwrap().root());
+        
+        for i in 0..6 {
+            let proof = tree.prove(i).unwrap();
+            assert!(proof.iter().all(|node| node[16..].iter().all(|&b| b == 0)));
+            assert!(MerkleTree::verify(&root, i, &columns[i], &proof, 16));
+            assert!(!MerkleTree::verify(&root, i, &columns[i], &proof, DIGEST_LEN));
+        }
+        
+        assert!(MerkleTree::new(&columns, 0).is_err());
+        assert!(MerkleTree::new(&columns, 33).is_err());
+    }
+    
+    #[test]
+    fn test_column_hasher_matches_tree() {
+        let columns: Vec<Vec<Fp128>> = (0..5)
+            .map(|i| (0..7).map(|j| Fp128::from((i * 7 + j) as u64)).collect())
+            .collect();
+        let rows: Vec<Vec<Fp128>> = (0..7)
+            .map(|j| columns.iter().map(|c| c[j]).collect())
+            .collect();
+        
+        let mut hasher = ColumnHasher::new(5, 7);
+        hasher.absorb_rows(&rows[..3]).unwrap();
+        hasher.absorb_rows(&rows[3..]).unwrap();
+        let tree = MerkleTree::from_leaf_hashes(hasher.finalize().unwrap(), DIGEST_LEN).unwrap();
+        assert_eq!(tree.root(), MerkleTree::new(&columns, DIGEST_LEN).unwrap().root());
+        
+        let mut short = ColumnHasher::new(5, 7);
+        short.absorb_rows(&rows[..3]).unwrap();
+        assert!(short.finalize().is_err());
+    }
+    
     #[test]
     fn test_multi_merkle_tree() {
         // Create multiple sets of columns
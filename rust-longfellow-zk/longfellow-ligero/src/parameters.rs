@@ -1,8 +1,58 @@
 /// Ligero protocol parameters
 
+use alloc::{format, string::ToString};
+use core::ops::{Range, RangeInclusive};
 use longfellow_core::{LongfellowError, Result};
 use serde::{Deserialize, Serialize};
 
+/// Coefficients of the last FRI layer, which is sent in the clear
+pub const FRI_FINAL_SIZE: usize = 16;
+
+/// Base-2 logarithms of the block sizes `LigeroParams::fit` chooses
+/// between. Past the largest, encoding a row costs more prover time than
+/// its openings save in size.
+pub const AUTO_BLOCK_SIZES: RangeInclusive<u32> = 4..=12;
+
+/// How the prover shows its committed rows are close to codewords
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LdtBackend {
+    /// Send the combined row message in full; cheapest for small blocks
+    #[default]
+    Direct,
+    
+    /// Commit to the combined row and prove it low-degree with FRI, so the
+    /// response no longer grows with the block size
+    Fri,
+}
+
+/// How the prover shows the witness satisfies the linear constraints
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinearTest {
+    /// Send the blinded product row in full
+    #[default]
+    Direct,
+    
+    /// Ligero++: commit to the product row and prove its weighted sum over
+    /// the witness block with an FRI-based inner-product argument, whose
+    /// size grows with the log of the block size
+    InnerProduct,
+}
+
+/// How the prover holds the encoded tableau while it commits to and
+/// opens columns. Proofs are identical either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableauLayout {
+    /// Hash columns straight from the encoded rows, striding across them;
+    /// needs no second copy of the tableau
+    #[default]
+    RowMajor,
+    
+    /// Transpose the encoded rows into contiguous columns with a
+    /// cache-blocked pass, then hash and open each column in one sweep, at
+    /// the cost of a second copy of the tableau
+    ColumnMajor,
+}
+
 /// Ligero protocol parameters
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LigeroParams {
@@ -18,14 +68,43 @@ pub struct LigeroParams {
     /// Number of column openings for soundness
     pub num_col_openings: usize,
     
-    /// Number of low-degree test queries
+    /// Number of low-degree test queries, used by the FRI backend
     pub num_ldt_queries: usize,
     
     /// Security parameter (bits)
     pub security_bits: usize,
     
-    /// Use subfield optimization
+    /// Pack witness rows below the constraint system's subfield boundary
+    /// into the field's subfield, e.g. GF(2^16) inside GF(2^128): their
+    /// blinding is drawn from the subfield, so their columns are subfield
+    /// elements and are sent in the compact encoding
     pub use_subfield: bool,
+    
+    /// Trade hash width for proof size: Merkle nodes are truncated to
+    /// `digest_len` bytes, meant to be sent with `to_compact_bytes`
+    #[serde(default)]
+    pub optimize_size: bool,
+    
+    /// Low-degree test backend
+    #[serde(default)]
+    pub ldt: LdtBackend,
+    
+    /// Proofs one `WitnessCommitment` may answer. Every proof opens fresh
+    /// columns, so rows are padded for this many proofs' openings
+    #[serde(default = "default_proofs_per_commitment")]
+    pub proofs_per_commitment: usize,
+    
+    /// Prover-side tableau layout for column hashing and openings
+    #[serde(default)]
+    pub layout: TableauLayout,
+    
+    /// Linear test, direct or as an inner-product argument
+    #[serde(default)]
+    pub linear_test: LinearTest,
+}
+
+fn default_proofs_per_commitment() -> usize {
+    1
 }
 
 impl LigeroParams {
@@ -51,6 +130,11 @@ impl LigeroParams {
             num_ldt_queries: 40,
             security_bits: 80,
             use_subfield: false,
+            optimize_size: false,
+            ldt: LdtBackend::Direct,
+            proofs_per_commitment: 1,
+            layout: TableauLayout::RowMajor,
+            linear_test: LinearTest::Direct,
         }
     }
     
@@ -64,6 +148,11 @@ impl LigeroParams {
             num_ldt_queries: 64,
             security_bits: 128,
             use_subfield: false,
+            optimize_size: false,
+            ldt: LdtBackend::Direct,
+            proofs_per_commitment: 1,
+            layout: TableauLayout::RowMajor,
+            linear_test: LinearTest::Direct,
         }
     }
     
@@ -71,12 +160,25 @@ impl LigeroParams {
     pub fn security_256() -> Self {
         Self {
             block_size: 256,
-            extension_factor: 4,
+            extension_factor: 5,
             num_blinding_rows: 3,
             num_col_openings: 400,
             num_ldt_queries: 128,
             security_bits: 256,
             use_subfield: false,
+            optimize_size: false,
+            ldt: LdtBackend::Direct,
+            proofs_per_commitment: 1,
+            layout: TableauLayout::RowMajor,
+            linear_test: LinearTest::Direct,
+        }
+    }
+    
+    /// 128-bit security over GF(2^128), packing subfield witness rows
+    pub fn binary_128() -> Self {
+        Self {
+            use_subfield: true,
+            ..Self::security_128()
         }
     }
     
@@ -95,11 +197,96 @@ impl LigeroParams {
         self.block_size * (self.extension_factor - 1)
     }
     
+    /// Points per committed row message: the block of witness values
+    /// followed by one random value per column any proof of the row may
+    /// open, so that the opened evaluations of a row are uniform whatever
+    /// the witness
+    pub fn row_message_size(&self) -> usize {
+        self.block_size + self.num_col_openings * self.proofs_per_commitment
+    }
+    
+    /// Points per message of the IDOT/IQUAD masks and of the linear and
+    /// quadratic responses, which are products of two row polynomials
+    pub fn dot_message_size(&self) -> usize {
+        2 * self.row_message_size() - 1
+    }
+    
+    /// Message size of tableau row `row`
+    pub fn message_size(&self, row: usize) -> usize {
+        match row {
+            row_indices::IDOT | row_indices::IQUAD => self.dot_message_size(),
+            _ => self.row_message_size(),
+        }
+    }
+    
+    /// Columns the verifier may open. The first `block_size` evaluations of
+    /// a row are the witness itself and are never opened.
+    pub fn opening_columns(&self) -> Range<usize> {
+        self.block_size..self.block_enc_size()
+    }
+    
+    /// Bytes kept of each Merkle node. A d-byte digest resists collisions
+    /// to about 4d bits, so with `optimize_size` nodes are cut to
+    /// `security_bits / 4` bytes, but never below 16
+    pub fn digest_len(&self) -> usize {
+        if self.optimize_size {
+            self.security_bits.div_ceil(4).clamp(16, crate::merkle::DIGEST_LEN)
+        } else {
+            crate::merkle::DIGEST_LEN
+        }
+    }
+    
+    /// Size of the coset the FRI backend evaluates the combined row on: the
+    /// row's degree bound rounded up to a power of two, times the extension
+    /// factor rounded the same way
+    pub fn fri_domain_size(&self) -> usize {
+        self.row_message_size().next_power_of_two() * self.extension_factor.next_power_of_two()
+    }
+    
+    /// FRI folding rounds: the quotient starts below `block_size` and each
+    /// round halves its degree bound, down to `FRI_FINAL_SIZE`
+    pub fn fri_num_rounds(&self) -> usize {
+        (self.block_size / FRI_FINAL_SIZE).max(1).trailing_zeros() as usize
+    }
+    
+    /// Degree bound the inner-product argument's batched FRI tests: the
+    /// product message size rounded up to a power of two
+    pub fn ipa_degree_bound(&self) -> usize {
+        self.dot_message_size().next_power_of_two()
+    }
+    
+    /// Size of the coset the inner-product oracle is committed on
+    pub fn ipa_domain_size(&self) -> usize {
+        self.ipa_degree_bound() * self.extension_factor.next_power_of_two()
+    }
+    
+    /// Folding rounds of the inner-product argument, down to
+    /// `FRI_FINAL_SIZE` coefficients
+    pub fn ipa_num_rounds(&self) -> usize {
+        (self.ipa_degree_bound() / FRI_FINAL_SIZE).max(1).trailing_zeros() as usize
+    }
+    
     /// Get number of blocks needed for witnesses
     pub fn num_witness_blocks(&self, num_witnesses: usize) -> usize {
         (num_witnesses + self.block_size - 1) / self.block_size
     }
     
+    /// Number of witness rows packed into the subfield: the rows whose
+    /// witnesses all lie below `subfield_boundary`
+    pub fn num_subfield_rows(&self, subfield_boundary: usize) -> usize {
+        if self.use_subfield {
+            subfield_boundary / self.block_size
+        } else {
+            0
+        }
+    }
+    
+    /// Tableau rows packed into the subfield
+    pub fn subfield_rows(&self, subfield_boundary: usize) -> Range<usize> {
+        let start = row_indices::WITNESS_START;
+        start..start + self.num_subfield_rows(subfield_boundary)
+    }
+    
     /// Get maximum number of witnesses supported
     pub fn max_witnesses(&self) -> usize {
         // Limit based on practical tableau size
@@ -130,9 +317,9 @@ impl LigeroParams {
             ));
         }
         
-        if self.num_blinding_rows < 3 {
+        if self.num_blinding_rows != 3 {
             return Err(LongfellowError::InvalidParameter(
-                "Need at least 3 blinding rows for security".to_string()
+                "Need exactly 3 blinding rows (ILDT, IDOT, IQUAD)".to_string()
             ));
         }
         
@@ -142,13 +329,125 @@ impl LigeroParams {
             ));
         }
         
+        if self.proofs_per_commitment == 0 {
+            return Err(LongfellowError::InvalidParameter(
+                "A commitment must answer at least one proof".to_string()
+            ));
+        }
+        
+        if self.opening_columns().len() < self.num_col_openings {
+            return Err(LongfellowError::InvalidParameter(
+                "Not enough columns outside the witness block to open".to_string()
+            ));
+        }
+        
+        if self.dot_message_size() > self.block_enc_size() {
+            return Err(LongfellowError::InvalidParameter(
+                "Encoded rows too short for the blinded product messages".to_string()
+            ));
+        }
+        
+        if self.ldt == LdtBackend::Fri {
+            // The FRI quotient's degree bound is the block size only while
+            // rows carry one proof's padding
+            if self.proofs_per_commitment != 1 {
+                return Err(LongfellowError::InvalidParameter(
+                    "FRI supports one proof per commitment".to_string()
+                ));
+            }
+            if self.fri_num_rounds() == 0 {
+                return Err(LongfellowError::InvalidParameter(
+                    format!("FRI needs a block size of at least {}", 2 * FRI_FINAL_SIZE)
+                ));
+            }
+            if self.num_ldt_queries == 0 || self.num_ldt_queries > self.fri_domain_size() / 2 {
+                return Err(LongfellowError::InvalidParameter(
+                    "FRI query count must be between 1 and half the FRI domain".to_string()
+                ));
+            }
+            
+            // Every opened FRI value is one more linear function of the
+            // combined row, on top of the opened columns; ILDT hides all of
+            // them only while they fit in the block
+            let revealed = 2 * self.num_ldt_queries * self.fri_num_rounds()
+                + (self.block_size >> self.fri_num_rounds());
+            if revealed > self.block_size {
+                return Err(LongfellowError::InvalidParameter(format!(
+                    "FRI openings reveal {} evaluations, more than the block size {}",
+                    revealed, self.block_size
+                )));
+            }
+        }
+        
+        if self.linear_test == LinearTest::InnerProduct {
+            if self.ipa_num_rounds() == 0 {
+                return Err(LongfellowError::InvalidParameter(
+                    "Product messages too short for the inner-product argument".to_string()
+                ));
+            }
+            if self.num_ldt_queries == 0 || self.num_ldt_queries > self.ipa_domain_size() / 2 {
+                return Err(LongfellowError::InvalidParameter(
+                    "Inner-product query count must be between 1 and half its domain".to_string()
+                ));
+            }
+        }
+        
         Ok(())
     }
     
+    /// These parameters with the tableau dimensions chosen for
+    /// `num_witnesses` witnesses and `num_quadratic` quadratic constraints
+    ///
+    /// Security settings are kept. Each power-of-two block size is tried,
+    /// with the extension factor raised until encoded rows hold the
+    /// blinded product messages, and the one with the smallest estimated
+    /// proof wins; witnesses beyond one block spread over several rows.
+    pub fn fit(&self, num_witnesses: usize, num_quadratic: usize) -> Result<Self> {
+        self.fit_within(num_witnesses, num_quadratic, usize::MAX)
+    }
+    
+    /// `fit`, keeping encoded rows to at most `max_width` columns
+    pub(crate) fn fit_within(
+        &self,
+        num_witnesses: usize,
+        num_quadratic: usize,
+        max_width: usize,
+    ) -> Result<Self> {
+        AUTO_BLOCK_SIZES
+            .map(|log| {
+                let block_size = 1usize << log;
+                // B * (e - 1) >= 2 * padding keeps 2k - 1 within 2B - 1 + B(e - 1)
+                let padding = self.row_message_size() - self.block_size;
+                let extension_factor = self.extension_factor.max((2 * padding).div_ceil(block_size) + 1);
+                Self { block_size, extension_factor, ..self.clone() }
+            })
+            .filter(|params| {
+                params.validate().is_ok()
+                    && params.block_enc_size() <= max_width
+                    && num_witnesses <= params.max_witnesses()
+            })
+            .min_by_key(|params| params.estimated_proof_size(num_witnesses, num_quadratic))
+            .ok_or_else(|| LongfellowError::InvalidParameter(format!(
+                "No tableau layout fits {} witnesses and {} quadratic constraints",
+                num_witnesses, num_quadratic
+            )))
+    }
+    
+    /// Field elements in a proof, ignoring Merkle paths: the opened
+    /// columns plus the three row responses
+    fn estimated_proof_size(&self, num_witnesses: usize, num_quadratic: usize) -> usize {
+        let height = self.tableau_height(
+            self.num_witness_blocks(num_witnesses),
+            self.num_quadratic_rows(num_quadratic),
+        );
+        self.num_col_openings * height + self.row_message_size() + 2 * self.dot_message_size()
+    }
+    
     /// Get soundness error probability (approximate)
     pub fn soundness_error(&self) -> f64 {
-        // Simplified estimate: 1/2^num_col_openings
-        (0.5_f64).powi(self.num_col_openings as i32)
+        // Simplified estimate: 1/2^num_col_openings, halved in a loop as
+        // `powi` needs std
+        (0..self.num_col_openings).fold(1.0, |p, _| p * 0.5)
     }
 }
 
@@ -214,5 +513,112 @@ mod tests {
         params = LigeroParams::security_128();
         params.num_blinding_rows = 2;
         assert!(params.validate().is_err());
+        
+        // Too many openings to hide behind the blinding values
+        params = LigeroParams::security_128();
+        params.num_col_openings = 400;
+        assert!(params.validate().is_err());
+        
+        assert!(LigeroParams::security_80().validate().is_ok());
+        assert!(LigeroParams::security_256().validate().is_ok());
+    }
+    
+    #[test]
+    fn test_digest_len() {
+        let mut params = LigeroParams::security_80();
+        assert_eq!(params.digest_len(), 32);
+        params.optimize_size = true;
+        assert_eq!(params.digest_len(), 20);
+        params.security_bits = 40;
+        assert_eq!(params.digest_len(), 16);
+        
+        let mut params = LigeroParams::security_256();
+        params.optimize_size = true;
+        assert_eq!(params.digest_len(), 32);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_fri_validation() {
+        // FRI openings would leak through the 64-wide LDT blinding
+        let mut params = LigeroParams::security_80();
+        params.ldt = LdtBackend::Fri;
+        assert!(params.validate().is_err());
+        
+        params.block_size = 1024;
+        params.extension_factor = 2;
+        assert!(params.validate().is_ok());
+        assert_eq!(params.fri_num_rounds(), 6);
+        assert_eq!(params.fri_domain_size(), 2048 * 2);
+        
+        params.num_ldt_queries = 0;
+        assert!(params.validate().is_err());
+        
+        params.num_ldt_queries = 40;
+        params.proofs_per_commitment = 2;
+        assert!(params.validate().is_err());
+    }
+    
+    #[test]
+    fn test_proofs_per_commitment() {
+        let mut params = LigeroParams {
+            block_size: 256,
+            extension_factor: 4,
+            ..LigeroParams::security_80()
+        };
+        assert_eq!(params.row_message_size(), 256 + 80);
+        
+        params.proofs_per_commitment = 3;
+        assert_eq!(params.row_message_size(), 256 + 3 * 80);
+        assert!(params.validate().is_ok());
+        
+        // Padding for too many proofs no longer fits the encoding
+        params.proofs_per_commitment = 5;
+        assert!(params.validate().is_err());
+        params.proofs_per_commitment = 0;
+        assert!(params.validate().is_err());
+    }
+    
+    #[test]
+    fn test_fit() {
+        let base = LigeroParams::security_128();
+        for &(num_witnesses, num_quadratic) in &[(1, 0), (3, 1), (1000, 500), (100_000, 40_000)] {
+            let params = base.fit(num_witnesses, num_quadratic).unwrap();
+            assert!(params.validate().is_ok());
+            assert!(params.block_size.is_power_of_two());
+            assert!(num_witnesses <= params.max_witnesses());
+            assert_eq!(params.num_col_openings, base.num_col_openings);
+            assert_eq!(params.security_bits, base.security_bits);
+        }
+        
+        // Large statements spread over many rows of a wider block
+        let small = base.fit(1000, 0).unwrap();
+        let large = base.fit(100_000, 0).unwrap();
+        assert!(large.block_size > small.block_size);
+        assert!(large.num_witness_blocks(100_000) > 1);
+        
+        // A base too narrow for its openings is widened, not rejected
+        let narrow = LigeroParams { block_size: 16, extension_factor: 2, ..base };
+        assert!(narrow.validate().is_err());
+        assert!(narrow.fit(1000, 0).unwrap().validate().is_ok());
+        
+        assert!(base.fit_within(1000, 0, 64).is_err());
+    }
+    
+    #[test]
+    fn test_inner_product_validation() {
+        let mut params = LigeroParams {
+            linear_test: LinearTest::InnerProduct,
+            ..LigeroParams::security_128()
+        };
+        assert!(params.validate().is_ok());
+        // 2 * (128 + 189) - 1 = 633 products, batched up to 1024
+        assert_eq!(params.ipa_degree_bound(), 1024);
+        assert_eq!(params.ipa_domain_size(), 4096);
+        assert_eq!(params.ipa_num_rounds(), 6);
+        
+        params.num_ldt_queries = 0;
+        assert!(params.validate().is_err());
+        params.num_ldt_queries = 2049;
+        assert!(params.validate().is_err());
+    }
+}
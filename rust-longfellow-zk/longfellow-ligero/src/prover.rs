@@ -2,106 +2,431 @@
 
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
-use rand::{CryptoRng, RngCore};
+use longfellow_random::SecretRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use zeroize::Zeroizing;
 
 use crate::{
-    LigeroInstance, LigeroProof, ColumnOpening,
-    tableau::{Tableau, linear_combination},
-    merkle::MerkleTree,
+    LigeroInstance, LigeroParams, LigeroProof, ColumnOpening, LdtBackend, LinearTest, TableauLayout,
+    fri::{FriCommitment, FriDomain, FriProof},
+    ipa::{self, InnerProductCommitment, InnerProductProof},
+    tableau::{
+        RowEncoder, Tableau, TableauColumns,
+        coefficient_block, evaluate_message, linear_combination,
+    },
+    merkle::{ColumnHasher, MerkleTree, hash_column},
     transcript::{LigeroTranscript, compute_instance_digest},
     parameters::row_indices,
 };
 
+/// LDT responses as sent, empty with FRI, and the FRI commitment to the
+/// combined row
+type LdtMessage<F> = (Vec<Vec<F>>, Option<FriCommitment<F>>);
+
+/// Encoder and instance transcript shared by every proof of one instance
+pub(crate) struct ProverSetup<F: Field> {
+    pub(crate) encoder: RowEncoder<F>,
+    pub(crate) transcript: LigeroTranscript,
+    pub(crate) fri_domain: Option<FriDomain<F>>,
+    pub(crate) ipa_domain: Option<FriDomain<F>>,
+}
+
+impl<F: Field> ProverSetup<F> {
+    /// Draw fresh blinding rows into an encoded tableau and encode them
+    pub(crate) fn blind<R: RngCore + CryptoRng>(
+        &self,
+        tableau: &mut Tableau<F>,
+        rng: &mut R,
+    ) -> Result<()> {
+        tableau.randomize_blinding_rows(rng)?;
+        for row in 0..row_indices::WITNESS_START {
+            self.encoder.encode(row, tableau.row_mut(row));
+        }
+        Ok(())
+    }
+    
+    /// Send the LDT response, or with FRI commit to the combined row in
+    /// its place and keep it for `prove_ldt`
+    pub(crate) fn send_ldt(
+        &self,
+        mut responses: Vec<Vec<F>>,
+        params: &LigeroParams,
+        transcript: &mut LigeroTranscript,
+    ) -> Result<LdtMessage<F>> {
+        let Some(domain) = &self.fri_domain else {
+            transcript.append_ldt_response(&responses);
+            return Ok((responses, None));
+        };
+        let commitment = FriCommitment::new(responses.remove(0), domain, params)?;
+        transcript.append_fri_root(0, &commitment.root());
+        Ok((Vec::new(), Some(commitment)))
+    }
+    
+    /// Run the FRI rounds once the column indices are drawn
+    pub(crate) fn prove_ldt(
+        &self,
+        commitment: Option<FriCommitment<F>>,
+        params: &LigeroParams,
+        column_indices: &[usize],
+        transcript: &mut LigeroTranscript,
+    ) -> Result<Option<FriProof<F>>> {
+        match (commitment, &self.fri_domain) {
+            (Some(commitment), Some(domain)) => {
+                commitment.prove(domain, params, column_indices, transcript).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+    
+    /// Send the linear response, or for the inner-product argument commit
+    /// to it in its place and keep it for `prove_linear`
+    pub(crate) fn send_linear(
+        &self,
+        response: Vec<F>,
+        params: &LigeroParams,
+        transcript: &mut LigeroTranscript,
+    ) -> Result<(Vec<F>, Option<InnerProductCommitment<F>>)> {
+        let Some(domain) = &self.ipa_domain else {
+            transcript.append_linear_response(&response);
+            return Ok((response, None));
+        };
+        let commitment = InnerProductCommitment::new(response, domain, params)?;
+        transcript.append_ipa_root(&commitment.root());
+        Ok((Vec::new(), Some(commitment)))
+    }
+    
+    /// Run the inner-product argument once the column indices are drawn
+    /// and the LDT is done
+    pub(crate) fn prove_linear(
+        &self,
+        commitment: Option<InnerProductCommitment<F>>,
+        params: &LigeroParams,
+        column_indices: &[usize],
+        transcript: &mut LigeroTranscript,
+    ) -> Result<Option<InnerProductProof<F>>> {
+        match (commitment, &self.ipa_domain) {
+            (Some(commitment), Some(domain)) => {
+                commitment.prove(domain, params, column_indices, transcript).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// A witness laid out and encoded once, to answer up to
+/// `proofs_per_commitment` proofs
+///
+/// Only the three blinding rows are redrawn and re-encoded per proof, so
+/// every proof has its own column root and masks: proofs from one
+/// commitment are as unlinkable as independent ones, and the row padding
+/// hides the columns they open between them.
+pub struct WitnessCommitment<F: Field> {
+    setup: ProverSetup<F>,
+    instance_digest: [u8; 32],
+    public: Vec<F>,
+    tableau: Tableau<F>,
+    max_proofs: usize,
+    proofs_made: AtomicUsize,
+}
+
+impl<F: Field> WitnessCommitment<F> {
+    /// Public inputs the committed witness is bound to
+    pub fn public_inputs(&self) -> &[F] {
+        &self.public
+    }
+    
+    /// Proofs this commitment may still answer
+    pub fn proofs_remaining(&self) -> usize {
+        self.max_proofs.saturating_sub(self.proofs_made.load(Ordering::SeqCst))
+    }
+}
+
 /// Ligero prover
+///
+/// The instance digest seeding every transcript hashes the whole
+/// constraint system, so it is computed once here.
 pub struct LigeroProver<F: Field> {
     instance: LigeroInstance<F>,
+    instance_digest: [u8; 32],
 }
 
 impl<F: Field> LigeroProver<F> {
     /// Create a new prover
     pub fn new(instance: LigeroInstance<F>) -> Result<Self> {
         instance.params.validate()?;
-        Ok(Self { instance })
+        let instance_digest = compute_instance_digest(&instance.params, &instance.constraints);
+        Ok(Self { instance, instance_digest })
     }
     
-    /// Generate a proof for a witness
+    /// The instance this prover was created for
+    pub fn instance(&self) -> &LigeroInstance<F> {
+        &self.instance
+    }
+    
+    /// Generate a proof for a witness and the public inputs it is bound to
     pub fn prove<R: RngCore + CryptoRng>(
         &self,
         witness: &[F],
+        public: &[F],
         rng: &mut R,
     ) -> Result<LigeroProof<F>> {
-        // Verify witness satisfies constraints
-        if !self.instance.constraints.is_satisfied(witness)? {
+        let setup = self.setup()?;
+        self.prove_with(&setup, witness, public, rng)
+    }
+    
+    /// Lay out and encode a witness and the public inputs it is bound to,
+    /// for answering later statements with `prove_with_commitment`
+    pub fn commit<R: RngCore + CryptoRng>(
+        &self,
+        witness: &[F],
+        public: &[F],
+        rng: &mut R,
+    ) -> Result<WitnessCommitment<F>> {
+        let setup = self.setup()?;
+        let tableau = self.commit_with(&setup, witness, public, rng)?;
+        Ok(WitnessCommitment {
+            setup,
+            instance_digest: self.instance_digest,
+            public: public.to_vec(),
+            tableau,
+            max_proofs: self.instance.params.proofs_per_commitment,
+            proofs_made: AtomicUsize::new(0),
+        })
+    }
+    
+    /// Prove the committed witness, binding the proof to the verifier's
+    /// `statement_randomness`, e.g. a session nonce
+    ///
+    /// Fails once the commitment has answered `proofs_per_commitment`
+    /// proofs, since further openings would no longer be hidden.
+    pub fn prove_with_commitment<R: RngCore + CryptoRng>(
+        &self,
+        commitment: &WitnessCommitment<F>,
+        statement_randomness: &[u8],
+        rng: &mut R,
+    ) -> Result<LigeroProof<F>> {
+        if commitment.instance_digest != self.instance_digest {
             return Err(LongfellowError::InvalidParameter(
-                "Witness does not satisfy constraints".to_string()
+                "Witness commitment was made for a different instance".to_string()
             ));
         }
         
-        // Initialize transcript
-        let instance_digest = compute_instance_digest(
-            &self.instance.params,
-            &self.instance.constraints,
-        );
-        let mut transcript = LigeroTranscript::new(&instance_digest);
+        if commitment.proofs_made.fetch_add(1, Ordering::SeqCst) >= commitment.max_proofs {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Witness commitment already answered {} proofs", commitment.max_proofs
+            )));
+        }
         
-        // Create and fill tableau
-        let mut tableau = self.create_tableau(witness, rng)?;
+        let mut tableau = commitment.tableau.clone();
+        self.prove_committed(
+            &commitment.setup,
+            &mut tableau,
+            &commitment.public,
+            statement_randomness,
+            rng,
+        )
+    }
+    
+    /// Generate one proof per witness, sharing the row encoder and the
+    /// instance transcript across all of them
+    ///
+    /// Each proof draws its blinding from its own generator, seeded from
+    /// `rng`, so the proofs are built in parallel. `public[i]` holds the
+    /// public inputs of `witnesses[i]`.
+    pub fn prove_batch<R: RngCore + CryptoRng>(
+        &self,
+        witnesses: &[Vec<F>],
+        public: &[Vec<F>],
+        rng: &mut R,
+    ) -> Result<Vec<LigeroProof<F>>> {
+        if public.len() != witnesses.len() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Got {} public input vectors for {} witnesses",
+                public.len(),
+                witnesses.len()
+            )));
+        }
+        
+        let setup = self.setup()?;
+        
+        let mut seeds = Zeroizing::new(vec![[0u8; 32]; witnesses.len()]);
+        for seed in seeds.iter_mut() {
+            rng.fill_bytes(seed);
+        }
+        
+        witnesses
+            .par_iter()
+            .zip(public.par_iter())
+            .zip(seeds.par_iter())
+            .map(|((witness, public), &seed)| {
+                let mut rng = SecretRng::from_seed(seed);
+                self.prove_with(&setup, witness, public, &mut rng)
+            })
+            .collect()
+    }
+    
+    /// Per-instance state that does not depend on the witness
+    pub(crate) fn setup(&self) -> Result<ProverSetup<F>> {
+        let fri_domain = match self.instance.params.ldt {
+            LdtBackend::Direct => None,
+            LdtBackend::Fri => Some(FriDomain::new(&self.instance.params)?),
+        };
+        let ipa_domain = match self.instance.params.linear_test {
+            LinearTest::Direct => None,
+            LinearTest::InnerProduct => Some(FriDomain::with_size(
+                &self.instance.params,
+                self.instance.params.ipa_domain_size(),
+            )?),
+        };
+        
+        Ok(ProverSetup {
+            encoder: RowEncoder::new(&self.instance.params)?,
+            transcript: LigeroTranscript::new(&self.instance_digest),
+            fri_domain,
+            ipa_domain,
+        })
+    }
+    
+    fn prove_with<R: RngCore + CryptoRng>(
+        &self,
+        setup: &ProverSetup<F>,
+        witness: &[F],
+        public: &[F],
+        rng: &mut R,
+    ) -> Result<LigeroProof<F>> {
+        let mut tableau = self.commit_with(setup, witness, public, rng)?;
+        self.prove_committed(setup, &mut tableau, public, &[], rng)
+    }
+    
+    /// Check the witness and build its encoded tableau, blinding rows left
+    /// for `prove_committed`
+    pub(crate) fn commit_with<R: RngCore + CryptoRng>(
+        &self,
+        setup: &ProverSetup<F>,
+        witness: &[F],
+        public: &[F],
+        rng: &mut R,
+    ) -> Result<Tableau<F>> {
+        // Verify witness satisfies constraints
+        let report = self.instance.constraints.check(witness, public)?;
+        if !report.is_valid() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Witness does not satisfy constraints: {}", report
+            )));
+        }
         
-        // Encode all rows
-        tableau.encode_rows()?;
+        // Create, fill and encode the tableau
+        let mut tableau = self.create_tableau(witness, rng)?;
+        tableau.encode_rows_with(&setup.encoder)?;
+        Ok(tableau)
+    }
+    
+    /// Draw fresh blinding rows into an encoded tableau and prove it
+    fn prove_committed<R: RngCore + CryptoRng>(
+        &self,
+        setup: &ProverSetup<F>,
+        tableau: &mut Tableau<F>,
+        public: &[F],
+        statement_randomness: &[u8],
+        rng: &mut R,
+    ) -> Result<LigeroProof<F>> {
+        setup.blind(tableau, rng)?;
+        self.prove_tableau(setup, tableau, public, statement_randomness)
+    }
+    
+    /// Prove a fully encoded tableau as it stands
+    ///
+    /// Two proofs of one tableau share its blinding rows and leak the
+    /// witness between them; only the extraction tests do this, to rewind
+    /// the prover after its column commitment.
+    pub(crate) fn prove_tableau(
+        &self,
+        setup: &ProverSetup<F>,
+        tableau: &Tableau<F>,
+        public: &[F],
+        statement_randomness: &[u8],
+    ) -> Result<LigeroProof<F>> {
+        let mut transcript = setup.transcript.clone();
+        transcript.append_public_inputs(public);
         
         // Commit to columns
-        let columns = self.extract_columns(&tableau);
-        let merkle_tree = MerkleTree::new(&columns)?;
+        let (merkle_tree, columns) = self.commit_columns(tableau)?;
         let column_root = merkle_tree.root();
         
-        transcript.append_column_roots(&[column_root]);
+        transcript.append_column_root(&column_root);
+        transcript.append_statement_randomness(statement_randomness);
         
         // Low-degree test
-        let ldt_challenges = transcript.challenge_ldt();
-        let ldt_responses = self.compute_ldt_responses(&tableau, &ldt_challenges)?;
-        transcript.append_ldt_response(&ldt_responses);
+        let (height, _) = tableau.dimensions();
+        let ldt_challenges = transcript.challenge_ldt(height - row_indices::WITNESS_START);
+        let ldt_responses = self.compute_ldt_responses(tableau, &ldt_challenges)?;
+        let (ldt_responses, fri_commitment) = setup.send_ldt(
+            ldt_responses,
+            &self.instance.params,
+            &mut transcript,
+        )?;
         
         // Linear test
         let linear_challenge = transcript.challenge_linear_combination(
             self.instance.constraints.linear_constraints.num_constraints
         );
         let linear_response = self.compute_linear_response(
-            &tableau,
-            witness,
+            tableau,
             &linear_challenge,
         )?;
-        transcript.append_linear_response(&linear_response);
+        let (linear_response, ipa_commitment) = setup.send_linear(
+            linear_response,
+            &self.instance.params,
+            &mut transcript,
+        )?;
         
         // Quadratic test
         let quad_challenge = transcript.challenge_linear_combination(
-            self.instance.constraints.quadratic_constraints.constraints.len()
+            self.instance.params.num_quadratic_rows(
+                self.instance.constraints.quadratic_constraints.constraints.len()
+            )
         );
         let quadratic_response = self.compute_quadratic_response(
-            &tableau,
+            tableau,
             &quad_challenge,
         )?;
         transcript.append_quadratic_response(&quadratic_response);
         
         // Column openings
         let column_indices = transcript.challenge_column_indices(
-            columns.len(),
+            self.instance.params.opening_columns(),
             self.instance.params.num_col_openings,
         );
         
         let column_openings = self.open_columns(
-            &columns,
+            tableau,
+            columns.as_ref(),
             &merkle_tree,
             &column_indices,
         )?;
+        let fri = setup.prove_ldt(
+            fri_commitment,
+            &self.instance.params,
+            &column_indices,
+            &mut transcript,
+        )?;
+        let linear_ipa = setup.prove_linear(
+            ipa_commitment,
+            &self.instance.params,
+            &column_indices,
+            &mut transcript,
+        )?;
         
         Ok(LigeroProof {
-            column_roots: vec![column_root],
+            column_root,
             ldt_responses,
             linear_responses: linear_response,
             quadratic_responses: quadratic_response,
             column_openings,
+            fri,
+            linear_ipa,
         })
     }
     
@@ -123,11 +448,9 @@ impl<F: Field> LigeroProver<F> {
         
         let mut tableau = Tableau::new(params.clone(), height);
         
-        // Fill blinding rows
-        tableau.randomize_blinding_rows(rng)?;
-        
         // Layout witnesses
-        tableau.layout_witnesses(witness, rng)?;
+        let num_subfield_rows = params.num_subfield_rows(constraints.subfield_boundary);
+        tableau.layout_witnesses(witness, num_subfield_rows, rng)?;
         
         // Encode quadratic constraints
         let quad_row_start = row_indices::WITNESS_START + num_witness_blocks;
@@ -135,89 +458,109 @@ impl<F: Field> LigeroProver<F> {
             &constraints.quadratic_constraints.constraints,
             witness,
             quad_row_start,
+            rng,
         )?;
         
         Ok(tableau)
     }
     
-    /// Extract columns from tableau
-    fn extract_columns(&self, tableau: &Tableau<F>) -> Vec<Vec<F>> {
-        let (_, width) = tableau.dimensions();
-        (0..width)
-            .into_par_iter()
-            .map(|j| tableau.column(j))
-            .collect()
+    /// Merkle tree over the tableau columns, hashed in the configured
+    /// layout; column-major also returns the columns to open from
+    fn commit_columns(&self, tableau: &Tableau<F>) -> Result<(MerkleTree, Option<TableauColumns<F>>)> {
+        let digest_len = self.instance.params.digest_len();
+        let (height, width) = tableau.dimensions();
+        match self.instance.params.layout {
+            TableauLayout::RowMajor => {
+                let mut hasher = ColumnHasher::new(width, height);
+                hasher.absorb_rows(tableau.rows())?;
+                Ok((MerkleTree::from_leaf_hashes(hasher.finalize()?, digest_len)?, None))
+            }
+            TableauLayout::ColumnMajor => {
+                let columns = tableau.to_column_major();
+                let leaves = (0..width)
+                    .into_par_iter()
+                    .map(|j| hash_column(columns.column(j)))
+                    .collect();
+                Ok((MerkleTree::from_leaf_hashes(leaves, digest_len)?, Some(columns)))
+            }
+        }
     }
     
-    /// Compute low-degree test responses
+    /// Compute the low-degree test response
+    ///
+    /// The response is the message of ILDT plus a random combination of
+    /// every witness and quadratic row; ILDT is uniform, so the response is
+    /// too.
     fn compute_ldt_responses(
         &self,
         tableau: &Tableau<F>,
         challenges: &[F],
     ) -> Result<Vec<Vec<F>>> {
-        if challenges.len() != 3 {
+        let (height, _) = tableau.dimensions();
+        if challenges.len() != height - row_indices::WITNESS_START {
             return Err(LongfellowError::InvalidParameter(
-                "Expected 3 LDT challenges".to_string()
+                "Expected one LDT challenge per witness and quadratic row".to_string()
             ));
         }
         
-        let mut responses = Vec::new();
-        
-        // Response 1: Linear combination of blinding rows
-        let blinding_rows: Vec<_> = (0..self.instance.params.num_blinding_rows)
-            .map(|i| tableau.row(i).to_vec())
-            .collect();
-        let response1 = linear_combination(&blinding_rows, challenges)?;
-        responses.push(response1);
-        
-        // Response 2: Linear combination of witness rows
-        let num_witness_blocks = self.instance.params.num_witness_blocks(
-            self.instance.constraints.num_witnesses
-        );
-        let witness_rows: Vec<_> = (0..num_witness_blocks)
-            .map(|i| tableau.row(row_indices::WITNESS_START + i).to_vec())
-            .collect();
-        
-        if !witness_rows.is_empty() {
-            let witness_coeffs = (0..witness_rows.len())
-                .map(|i| challenges[i % challenges.len()])
-                .collect::<Vec<_>>();
-            let response2 = linear_combination(&witness_rows, &witness_coeffs)?;
-            responses.push(response2);
+        let row_size = self.instance.params.row_message_size();
+        let mut response = tableau.row(row_indices::ILDT)[..row_size].to_vec();
+        for (i, &challenge) in challenges.iter().enumerate() {
+            let row = tableau.row(row_indices::WITNESS_START + i);
+            for (r, &x) in response.iter_mut().zip(row) {
+                *r += challenge * x;
+            }
         }
         
-        Ok(responses)
+        Ok(vec![response])
     }
     
     /// Compute linear test response
+    ///
+    /// With a = A^T * challenges split into per-row blocks A_i, the response
+    /// is IDOT + sum_i A_i * W_i over the product message points. Its sum
+    /// over the witness block is challenges . b; IDOT sums to zero there
+    /// and is uniform everywhere else. For the inner-product argument the
+    /// blocks and the sum are weighted, see `ipa`.
     fn compute_linear_response(
         &self,
-        _tableau: &Tableau<F>,
-        _witness: &[F],
+        tableau: &Tableau<F>,
         challenges: &[F],
     ) -> Result<Vec<F>> {
+        let params = &self.instance.params;
         let constraints = &self.instance.constraints.linear_constraints;
-        let mut response = vec![F::zero(); self.instance.params.block_size];
+        let dot_size = params.dot_message_size();
+        let points: Vec<usize> = (0..dot_size).collect();
         
-        // Compute A^T * challenges
-        for (i, &challenge) in challenges.iter().enumerate() {
-            for &(row, col, ref value) in &constraints.matrix {
-                if row == i && col < response.len() {
-                    response[col] += challenge * *value;
-                }
-            }
-        }
+        let mut combined = constraints.matrix.transpose_mul_vec(challenges)?;
+        ipa::weigh_coefficients(params, &mut combined);
+        let num_blocks = params.num_witness_blocks(self.instance.constraints.num_witnesses);
         
-        // Add b^T * challenges contribution
-        let mut b_contribution = F::zero();
-        for (b, challenge) in constraints.rhs.iter().zip(challenges.iter()) {
-            b_contribution += *b * *challenge;
+        let products = (0..num_blocks)
+            .into_par_iter()
+            .map(|i| {
+                let coeffs = coefficient_block(&combined, i, params.block_size);
+                let coeffs = evaluate_message(&coeffs, &points)?;
+                let row = tableau.row(row_indices::WITNESS_START + i);
+                Ok(coeffs.iter().zip(row).map(|(&a, &w)| a * w).collect::<Vec<F>>())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        let mut response = tableau.row(row_indices::IDOT)[..dot_size].to_vec();
+        for product in products {
+            for (r, p) in response.iter_mut().zip(product) {
+                *r += p;
+            }
         }
         
         Ok(response)
     }
     
     /// Compute quadratic test response
+    ///
+    /// IQUAD plus a random combination of the quadratic rows. Every term
+    /// vanishes on the witness block for a satisfying witness, and IQUAD
+    /// makes the rest of the response uniform.
     fn compute_quadratic_response(
         &self,
         tableau: &Tableau<F>,
@@ -235,29 +578,22 @@ impl<F: Field> LigeroProver<F> {
             self.instance.constraints.num_witnesses
         );
         let quad_start = row_indices::WITNESS_START + witness_blocks;
+        let dot_size = self.instance.params.dot_message_size();
         
-        let quad_rows: Vec<_> = (0..num_quad_rows)
-            .map(|i| tableau.row(quad_start + i).to_vec())
-            .collect();
+        let mut rows = vec![tableau.row(row_indices::IQUAD)[..dot_size].to_vec()];
+        rows.extend((0..num_quad_rows).map(|i| tableau.row(quad_start + i)[..dot_size].to_vec()));
         
-        let quad_coeffs = (0..num_quad_rows)
-            .map(|i| {
-                let start = i * self.instance.params.block_size;
-                let end = std::cmp::min(
-                    start + self.instance.params.block_size,
-                    challenges.len()
-                );
-                challenges[start..end].iter().fold(F::zero(), |acc, &x| acc + x)
-            })
-            .collect::<Vec<F>>();
+        let mut coeffs = vec![F::one()];
+        coeffs.extend_from_slice(challenges);
         
-        linear_combination(&quad_rows, &quad_coeffs)
+        linear_combination(&rows, &coeffs)
     }
     
     /// Open columns with Merkle proofs
     fn open_columns(
         &self,
-        columns: &[Vec<F>],
+        tableau: &Tableau<F>,
+        columns: Option<&TableauColumns<F>>,
         merkle_tree: &MerkleTree,
         indices: &[usize],
     ) -> Result<Vec<ColumnOpening<F>>> {
@@ -267,7 +603,10 @@ impl<F: Field> LigeroProver<F> {
                 let merkle_proof = merkle_tree.prove(index)?;
                 Ok(ColumnOpening {
                     index,
-                    values: columns[index].clone(),
+                    values: match columns {
+                        Some(columns) => columns.column(index).to_vec(),
+                        None => tableau.column(index),
+                    },
                     merkle_proof,
                 })
             })
@@ -278,7 +617,7 @@ impl<F: Field> LigeroProver<F> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{LigeroParams, ConstraintSystem};
+    use crate::{LigeroParams, ConstraintSystem, LigeroVerifier};
     use longfellow_algebra::Fp128;
     use rand::rngs::OsRng;
     
@@ -311,10 +650,230 @@ mod tests {
         ];
         
         // Generate proof
-        let proof = prover.prove(&witness, &mut OsRng).unwrap();
+        let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
         
         // Check proof structure
-        assert_eq!(proof.column_roots.len(), 1);
         assert_eq!(proof.column_openings.len(), 80); // num_col_openings
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_prove_batch() {
+        let mut cs = ConstraintSystem::<Fp128>::new(3);
+        cs.add_quadratic_constraint(0, 1, 2);
+        
+        let params = LigeroParams::security_80();
+        let prover = LigeroProver::new(LigeroInstance::new(params.clone(), cs.clone()).unwrap()).unwrap();
+        let verifier = LigeroVerifier::new(LigeroInstance::new(params, cs).unwrap()).unwrap();
+        
+        let witnesses: Vec<Vec<Fp128>> = (1..=4u64)
+            .map(|i| vec![Fp128::from(i), Fp128::from(i + 1), Fp128::from(i * (i + 1))])
+            .collect();
+        let public = vec![Vec::new(); witnesses.len()];
+        let proofs = prover.prove_batch(&witnesses, &public, &mut OsRng).unwrap();
+        
+        assert_eq!(proofs.len(), witnesses.len());
+        for proof in &proofs {
+            assert!(verifier.verify(proof, &[]).unwrap());
+        }
+        assert_ne!(proofs[0].column_root, proofs[1].column_root);
+        
+        // One bad witness fails the whole batch
+        let mut bad = witnesses.clone();
+        bad[2][2] += Fp128::one();
+        assert!(prover.prove_batch(&bad, &public, &mut OsRng).is_err());
+    }
+    
+    #[test]
+    fn test_column_major_layout() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+        
+        let mut cs = ConstraintSystem::<Fp128>::new(3);
+        cs.add_quadratic_constraint(0, 1, 2);
+        let witness = vec![Fp128::from(2), Fp128::from(3), Fp128::from(6)];
+        
+        // The layout only changes how the prover walks the tableau
+        let prove = |layout| {
+            let params = LigeroParams { layout, ..LigeroParams::security_80() };
+            let instance = LigeroInstance::new(params, cs.clone()).unwrap();
+            let prover = LigeroProver::new(instance).unwrap();
+            prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(5)).unwrap()
+        };
+        let rows = prove(TableauLayout::RowMajor);
+        let columns = prove(TableauLayout::ColumnMajor);
+        
+        assert_eq!(rows.column_root, columns.column_root);
+        for (a, b) in rows.column_openings.iter().zip(&columns.column_openings) {
+            assert_eq!(a.values, b.values);
+            assert_eq!(a.merkle_proof, b.merkle_proof);
+        }
+        
+        let params = LigeroParams { layout: TableauLayout::ColumnMajor, ..LigeroParams::security_80() };
+        let verifier = LigeroVerifier::new(LigeroInstance::new(params, cs).unwrap()).unwrap();
+        assert!(verifier.verify(&columns, &[]).unwrap());
+    }
+    
+    #[test]
+    fn test_prove_with_commitment() {
+        let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(2, 1);
+        cs.add_linear_constraint_with_public(
+            vec![(0, Fp128::one()), (1, Fp128::one())],
+            vec![(0, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 0, 1);
+        
+        let params = LigeroParams {
+            block_size: 256,
+            proofs_per_commitment: 3,
+            ..LigeroParams::security_80()
+        };
+        let instance = LigeroInstance::new(params, cs).unwrap();
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let verifier = LigeroVerifier::new(instance).unwrap();
+        
+        // w = (3, 9), x = 12
+        let witness = vec![Fp128::from(3), Fp128::from(9)];
+        let public = vec![Fp128::from(12)];
+        let commitment = prover.commit(&witness, &public, &mut OsRng).unwrap();
+        assert_eq!(commitment.public_inputs(), &public[..]);
+        
+        let mut roots = Vec::new();
+        for nonce in [b"session-1", b"session-2", b"session-3"] {
+            let proof = prover.prove_with_commitment(&commitment, nonce, &mut OsRng).unwrap();
+            assert!(verifier.verify_with_statement(&proof, &public, nonce).unwrap());
+            assert!(!verifier.verify_with_statement(&proof, &public, b"session-0").unwrap());
+            assert!(!verifier.verify(&proof, &public).unwrap());
+            roots.push(proof.column_root);
+        }
+        assert_ne!(roots[0], roots[1]);
+        assert_ne!(roots[1], roots[2]);
+        
+        // Further openings would no longer be hidden by the row padding
+        assert_eq!(commitment.proofs_remaining(), 0);
+        assert!(prover.prove_with_commitment(&commitment, b"session-4", &mut OsRng).is_err());
+        
+        // A plain proof of the same instance verifies without randomness
+        let proof = prover.prove(&witness, &public, &mut OsRng).unwrap();
+        assert!(verifier.verify(&proof, &public).unwrap());
+    }
+    
+    #[test]
+    fn test_linear_test_equivalence() {
+        // w[i] + w[i + 50] = w[i + 100] across three blocks of 64, with
+        // w[i + 50] = w[i]^2, and w[0] + w[149] = x
+        let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(150, 1);
+        for i in 0..50 {
+            cs.add_linear_constraint(
+                vec![(i, Fp128::one()), (i + 50, Fp128::one()), (i + 100, -Fp128::one())],
+                Fp128::zero(),
+            );
+            cs.add_quadratic_constraint(i, i, i + 50);
+        }
+        cs.add_linear_constraint_with_public(
+            vec![(0, Fp128::one()), (149, Fp128::one())],
+            vec![(0, -Fp128::one())],
+            Fp128::zero(),
+        );
+        let witness: Vec<Fp128> = (0..150u64)
+            .map(|i| {
+                let x = i % 50;
+                Fp128::from([x, x * x, x + x * x][i as usize / 50])
+            })
+            .collect();
+        let public = vec![Fp128::from(49 + 49 * 49)];
+        
+        // Breaks one linear constraint and nothing else
+        let mut bad = witness.clone();
+        bad[120] += Fp128::one();
+        
+        let instances = [LinearTest::Direct, LinearTest::InnerProduct].map(|linear_test| {
+            let params = LigeroParams { linear_test, ..LigeroParams::security_80() };
+            LigeroInstance::new(params, cs.clone()).unwrap()
+        });
+        let verifiers = instances.clone().map(|instance| LigeroVerifier::new(instance).unwrap());
+        
+        for (k, instance) in instances.into_iter().enumerate() {
+            let (verifier, other) = (&verifiers[k], &verifiers[1 - k]);
+            let prover = LigeroProver::new(instance).unwrap();
+            let proof = prover.prove(&witness, &public, &mut OsRng).unwrap();
+            assert!(verifier.verify(&proof, &public).unwrap());
+            assert!(!verifier.verify(&proof, &[public[0] + Fp128::one()]).unwrap());
+            
+            // Proofs of one mode do not pass for the other
+            assert!(!other.verify(&proof, &public).unwrap());
+            
+            let mut tampered = proof;
+            tampered.column_openings[2].values[row_indices::IDOT] += Fp128::one();
+            assert!(!verifier.verify(&tampered, &public).unwrap());
+            
+            // Both modes reject a witness that fails the linear test, even
+            // when the prover skips its own check
+            assert!(prover.prove(&bad, &public, &mut OsRng).is_err());
+            let setup = prover.setup().unwrap();
+            let mut tableau = prover.create_tableau(&bad, &mut OsRng).unwrap();
+            tableau.encode_rows_with(&setup.encoder).unwrap();
+            let forged = prover.prove_committed(&setup, &mut tableau, &public, &[], &mut OsRng).unwrap();
+            assert!(!verifier.verify(&forged, &public).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_verification_report() {
+        use crate::VerificationFailure;
+        
+        // w[0] + w[1] = w[2], w[1] = w[0]^2 and w[1]^2 = w[1]
+        let mut cs = ConstraintSystem::<Fp128>::new(3);
+        cs.add_linear_constraint(
+            vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 0, 1);
+        cs.add_quadratic_constraint(1, 1, 1);
+        
+        let forge = |linear_test, witness: [u64; 3]| {
+            let params = LigeroParams { linear_test, ..LigeroParams::security_80() };
+            let instance = LigeroInstance::new(params, cs.clone()).unwrap();
+            let prover = LigeroProver::new(instance.clone()).unwrap();
+            let witness = witness.map(Fp128::from);
+            let setup = prover.setup().unwrap();
+            let mut tableau = prover.create_tableau(&witness, &mut OsRng).unwrap();
+            tableau.encode_rows_with(&setup.encoder).unwrap();
+            let proof = prover.prove_committed(&setup, &mut tableau, &[], &[], &mut OsRng).unwrap();
+            LigeroVerifier::new(instance).unwrap().verify_with_report(&proof, &[], &[]).unwrap()
+        };
+        
+        let report = forge(LinearTest::Direct, [1, 1, 2]);
+        assert!(report.is_valid());
+        assert_eq!(report.to_string(), "all checks passed");
+        
+        // Triple 1 is at position 1 of the only quadratic row
+        let report = forge(LinearTest::Direct, [2, 4, 6]);
+        assert_eq!(report.failures(), &[VerificationFailure::QuadraticPosition { position: 1 }]);
+        
+        let report = forge(LinearTest::Direct, [1, 1, 3]);
+        assert_eq!(report.failures(), &[VerificationFailure::LinearSum]);
+        let report = forge(LinearTest::InnerProduct, [1, 1, 3]);
+        assert!(matches!(report.failures(), [VerificationFailure::LinearQuery { .. }]));
+        
+        // The prover names the constraint before getting that far
+        let prover = LigeroProver::new(LigeroInstance::new(LigeroParams::security_80(), cs.clone()).unwrap()).unwrap();
+        let err = prover.prove(&[2u64, 4, 6].map(Fp128::from), &[], &mut OsRng).unwrap_err();
+        assert!(err.to_string().contains("quadratic constraint 1 is not satisfied"));
+        
+        let verifier = LigeroVerifier::new(prover.instance().clone()).unwrap();
+        let proof = prover.prove(&[1u64, 1, 2].map(Fp128::from), &[], &mut OsRng).unwrap();
+        let mut bad = proof.clone();
+        bad.column_openings[3].merkle_proof[0][0] ^= 1;
+        let column = bad.column_openings[3].index;
+        let report = verifier.verify_with_report(&bad, &[], &[]).unwrap();
+        assert_eq!(report.failures(), &[VerificationFailure::MerklePath { column }]);
+        
+        // Responses feed the transcript, so a changed one moves the columns
+        let mut bad = proof;
+        bad.ldt_responses[0][0] += Fp128::one();
+        let report = verifier.verify_with_report(&bad, &[], &[]).unwrap();
+        assert!(!report.is_valid());
+        assert!(report.failures().iter().all(|f| matches!(f, VerificationFailure::ColumnIndex { .. })));
+    }
+}
@@ -0,0 +1,142 @@
+//! Structured diagnostics for failed Ligero checks
+//!
+//! `LigeroVerifier::verify` only answers yes or no. When a proof from an
+//! integrator's prover is rejected, `LigeroVerifier::verify_with_report`
+//! names every check that failed, and `ConstraintSystem::check` does the
+//! same for a witness against the circuit, which is usually where a
+//! prover and circuit disagree first.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// One failed check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationFailure {
+    /// The proof opens `actual` columns instead of `expected`
+    ColumnCount { expected: usize, actual: usize },
+    
+    /// Opening `opening` is of column `actual`, not the challenged `expected`
+    ColumnIndex { opening: usize, expected: usize, actual: usize },
+    
+    /// Column `column` has the wrong height, or a packed subfield value
+    /// outside the subfield
+    ColumnValues { column: usize },
+    
+    /// The Merkle path of column `column` does not lead to the column root
+    MerklePath { column: usize },
+    
+    /// The LDT response or FRI proof is missing or has the wrong shape
+    LdtShape,
+    
+    /// The LDT response disagrees with the committed rows at column `column`
+    LdtColumn { column: usize },
+    
+    /// FRI query `query` of the LDT fails
+    LdtQuery { query: usize },
+    
+    /// The linear response or inner-product proof is missing or has the
+    /// wrong shape
+    LinearShape,
+    
+    /// The linear response does not sum to the claimed value over the
+    /// witness block
+    LinearSum,
+    
+    /// The linear response disagrees with the committed rows at column
+    /// `column`
+    LinearColumn { column: usize },
+    
+    /// Query `query` of the inner-product argument fails
+    LinearQuery { query: usize },
+    
+    /// The quadratic response has the wrong shape
+    QuadraticShape,
+    
+    /// The quadratic response does not vanish at `position` of the witness
+    /// block, where the triples `position`, `position + block_size`, ..
+    /// sit
+    QuadraticPosition { position: usize },
+    
+    /// The quadratic response disagrees with the committed rows at column
+    /// `column`
+    QuadraticColumn { column: usize },
+    
+    /// Witness `index` is declared in the subfield but is not
+    SubfieldWitness { index: usize },
+    
+    /// The witness does not satisfy linear constraint `row`
+    LinearConstraint { row: usize },
+    
+    /// The witness does not satisfy quadratic constraint `triple`
+    QuadraticConstraint { triple: usize },
+}
+
+impl fmt::Display for VerificationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ColumnCount { expected, actual } => {
+                write!(f, "{} columns opened, expected {}", actual, expected)
+            }
+            Self::ColumnIndex { opening, expected, actual } => {
+                write!(f, "opening {} is of column {}, expected {}", opening, actual, expected)
+            }
+            Self::ColumnValues { column } => write!(f, "column {} has malformed values", column),
+            Self::MerklePath { column } => write!(f, "Merkle path of column {} is invalid", column),
+            Self::LdtShape => write!(f, "LDT response is malformed"),
+            Self::LdtColumn { column } => write!(f, "LDT fails at column {}", column),
+            Self::LdtQuery { query } => write!(f, "LDT query {} fails", query),
+            Self::LinearShape => write!(f, "linear response is malformed"),
+            Self::LinearSum => write!(f, "linear response has the wrong sum"),
+            Self::LinearColumn { column } => write!(f, "linear test fails at column {}", column),
+            Self::LinearQuery { query } => write!(f, "inner-product query {} fails", query),
+            Self::QuadraticShape => write!(f, "quadratic response is malformed"),
+            Self::QuadraticPosition { position } => {
+                write!(f, "quadratic response does not vanish at position {}", position)
+            }
+            Self::QuadraticColumn { column } => write!(f, "quadratic test fails at column {}", column),
+            Self::SubfieldWitness { index } => write!(f, "witness {} is not in the subfield", index),
+            Self::LinearConstraint { row } => write!(f, "linear constraint {} is not satisfied", row),
+            Self::QuadraticConstraint { triple } => {
+                write!(f, "quadratic constraint {} is not satisfied", triple)
+            }
+        }
+    }
+}
+
+/// Outcome of one verifier check, naming what failed
+pub(crate) type Checked<T> = core::result::Result<T, VerificationFailure>;
+
+/// Every check that failed for a proof or witness, in the order they ran
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    failures: Vec<VerificationFailure>,
+}
+
+impl VerificationReport {
+    pub(crate) fn new(failures: Vec<VerificationFailure>) -> Self {
+        Self { failures }
+    }
+    
+    /// Whether every check passed
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+    
+    /// The failed checks
+    pub fn failures(&self) -> &[VerificationFailure] {
+        &self.failures
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((first, rest)) = self.failures.split_first() else {
+            return write!(f, "all checks passed");
+        };
+        write!(f, "{}", first)?;
+        for failure in rest {
+            write!(f, "; {}", failure)?;
+        }
+        Ok(())
+    }
+}
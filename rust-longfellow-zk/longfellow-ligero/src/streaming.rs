@@ -0,0 +1,342 @@
+//! Memory-bounded Ligero proving
+//!
+//! `LigeroProver::prove_streaming` never holds the encoded tableau. Each
+//! row is generated from the witness and its own seed, so it can be rebuilt
+//! whenever it is needed: one pass over the rows hashes the columns, one
+//! pass per test computes its response, and a last pass collects the
+//! opened columns. At most `memory_budget` bytes of rows are live at once.
+//! The result is an ordinary `LigeroProof` for the same verifier; the cost
+//! is encoding every row once per pass.
+
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use longfellow_random::{FieldRng, SecretRng};
+use rand::{CryptoRng, RngCore};
+use rayon::prelude::*;
+use sha3::{Digest, Sha3_256};
+use std::ops::Range;
+use zeroize::Zeroizing;
+
+use crate::{
+    ColumnOpening, ConstraintSystem, LigeroParams, LigeroProof, LigeroProver,
+    ipa,
+    merkle::{ColumnHasher, MerkleTree},
+    parameters::row_indices,
+    tableau::{RowEncoder, coefficient_block, evaluate_message},
+};
+
+impl<F: Field> LigeroProver<F> {
+    /// Generate a proof while holding at most `memory_budget` bytes of
+    /// tableau rows, for witnesses whose full tableau does not fit in memory
+    pub fn prove_streaming<R: RngCore + CryptoRng>(
+        &self,
+        witness: &[F],
+        public: &[F],
+        rng: &mut R,
+        memory_budget: usize,
+    ) -> Result<LigeroProof<F>> {
+        let instance = self.instance();
+        let params = &instance.params;
+        let constraints = &instance.constraints;
+
+        let report = constraints.check(witness, public)?;
+        if !report.is_valid() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Witness does not satisfy constraints: {}", report
+            )));
+        }
+
+        let row_bytes = params.block_enc_size() * std::mem::size_of::<F>();
+        let rows_per_block = memory_budget / row_bytes;
+        if rows_per_block == 0 {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Memory budget of {} bytes is below one encoded row ({} bytes)",
+                memory_budget, row_bytes
+            )));
+        }
+
+        let setup = self.setup()?;
+        let mut seed = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(&mut *seed);
+        let source = RowSource::new(params, constraints, witness, &setup.encoder, seed, rows_per_block);
+        let height = source.height;
+        let width = params.block_enc_size();
+        let mut transcript = setup.transcript.clone();
+        transcript.append_public_inputs(public);
+
+        // Commit: hash the columns row block by row block
+        let mut hasher = ColumnHasher::new(width, height);
+        source.for_each_block(0..height, true, |_, rows| hasher.absorb_rows(rows))?;
+        let merkle_tree = MerkleTree::from_leaf_hashes(hasher.finalize()?, params.digest_len())?;
+        let column_root = merkle_tree.root();
+        transcript.append_column_root(&column_root);
+
+        // Low-degree test, over row messages only
+        let ldt_challenges: Vec<F> = transcript.challenge_ldt(height - row_indices::WITNESS_START);
+        let row_size = params.row_message_size();
+        let mut ldt_response = source.message(row_indices::ILDT)[..row_size].to_vec();
+        source.for_each_block(row_indices::WITNESS_START..height, false, |first, rows| {
+            for (i, row) in rows.iter().enumerate() {
+                let challenge = ldt_challenges[first + i - row_indices::WITNESS_START];
+                for (r, &x) in ldt_response.iter_mut().zip(row) {
+                    *r += challenge * x;
+                }
+            }
+            Ok(())
+        })?;
+        let (ldt_responses, fri_commitment) = setup.send_ldt(vec![ldt_response], params, &mut transcript)?;
+
+        // Linear test, over the witness rows
+        let linear_challenge: Vec<F> = transcript.challenge_linear_combination(
+            constraints.linear_constraints.num_constraints
+        );
+        let mut combined = constraints.linear_constraints.matrix.transpose_mul_vec(&linear_challenge)?;
+        ipa::weigh_coefficients(params, &mut combined);
+        let dot_size = params.dot_message_size();
+        let points: Vec<usize> = (0..dot_size).collect();
+        let mut linear_response = source.encoded(row_indices::IDOT)[..dot_size].to_vec();
+        source.for_each_block(source.witness_rows(), true, |first, rows| {
+            for (i, row) in rows.iter().enumerate() {
+                let block = first + i - row_indices::WITNESS_START;
+                let coeffs = coefficient_block(&combined, block, params.block_size);
+                let coeffs = evaluate_message(&coeffs, &points)?;
+                for ((r, &a), &w) in linear_response.iter_mut().zip(&coeffs).zip(row) {
+                    *r += a * w;
+                }
+            }
+            Ok(())
+        })?;
+        let (linear_response, ipa_commitment) = setup.send_linear(linear_response, params, &mut transcript)?;
+
+        // Quadratic test, over the quadratic rows
+        let quad_rows = source.quadratic_rows();
+        let quad_challenge: Vec<F> = transcript.challenge_linear_combination(quad_rows.len());
+        let quadratic_response = if quad_rows.is_empty() {
+            Vec::new()
+        } else {
+            let mut response = source.encoded(row_indices::IQUAD)[..dot_size].to_vec();
+            let quad_start = quad_rows.start;
+            source.for_each_block(quad_rows, true, |first, rows| {
+                for (i, row) in rows.iter().enumerate() {
+                    let challenge = quad_challenge[first + i - quad_start];
+                    for (r, &x) in response.iter_mut().zip(row) {
+                        *r += challenge * x;
+                    }
+                }
+                Ok(())
+            })?;
+            response
+        };
+        transcript.append_quadratic_response(&quadratic_response);
+
+        // Column openings, gathered row block by row block
+        let column_indices = transcript.challenge_column_indices(
+            params.opening_columns(),
+            params.num_col_openings,
+        );
+        let mut opened: Vec<Vec<F>> = vec![Vec::with_capacity(height); column_indices.len()];
+        source.for_each_block(0..height, true, |_, rows| {
+            for (column, &index) in opened.iter_mut().zip(&column_indices) {
+                column.extend(rows.iter().map(|row| row[index]));
+            }
+            Ok(())
+        })?;
+
+        let column_openings = column_indices
+            .iter()
+            .zip(opened)
+            .map(|(&index, values)| {
+                Ok(ColumnOpening {
+                    index,
+                    values,
+                    merkle_proof: merkle_tree.prove(index)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let fri = setup.prove_ldt(fri_commitment, params, &column_indices, &mut transcript)?;
+        let linear_ipa = setup.prove_linear(ipa_commitment, params, &column_indices, &mut transcript)?;
+
+        Ok(LigeroProof {
+            column_root,
+            ldt_responses,
+            linear_responses: linear_response,
+            quadratic_responses: quadratic_response,
+            column_openings,
+            fri,
+            linear_ipa,
+        })
+    }
+}
+
+/// Regenerates any tableau row from the witness and a per-row seed
+///
+/// Rows have the same layout and distribution as `Tableau` produces; only
+/// the randomness is drawn per row instead of from one stream.
+struct RowSource<'a, F: Field> {
+    params: &'a LigeroParams,
+    constraints: &'a ConstraintSystem<F>,
+    witness: &'a [F],
+    encoder: &'a RowEncoder<F>,
+    seed: Zeroizing<[u8; 32]>,
+    rows_per_block: usize,
+    height: usize,
+    num_witness_blocks: usize,
+    num_subfield_rows: usize,
+}
+
+impl<'a, F: Field> RowSource<'a, F> {
+    fn new(
+        params: &'a LigeroParams,
+        constraints: &'a ConstraintSystem<F>,
+        witness: &'a [F],
+        encoder: &'a RowEncoder<F>,
+        seed: Zeroizing<[u8; 32]>,
+        rows_per_block: usize,
+    ) -> Self {
+        let num_witness_blocks = params.num_witness_blocks(witness.len());
+        let num_quad_rows = params.num_quadratic_rows(
+            constraints.quadratic_constraints.constraints.len()
+        );
+
+        Self {
+            params,
+            constraints,
+            witness,
+            encoder,
+            seed,
+            rows_per_block,
+            height: params.tableau_height(num_witness_blocks, num_quad_rows),
+            num_witness_blocks,
+            num_subfield_rows: params.num_subfield_rows(constraints.subfield_boundary),
+        }
+    }
+
+    fn witness_rows(&self) -> Range<usize> {
+        row_indices::WITNESS_START..row_indices::WITNESS_START + self.num_witness_blocks
+    }
+
+    fn quadratic_rows(&self) -> Range<usize> {
+        self.witness_rows().end..self.height
+    }
+
+    fn field_rng(&self, row: usize) -> FieldRng<F, SecretRng> {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"LigeroStreamingRow");
+        hasher.update(self.seed.as_slice());
+        hasher.update((row as u64).to_le_bytes());
+        let seed = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+        FieldRng::<F, SecretRng>::from_seed(*seed)
+    }
+
+    /// Row `row` with only its message filled in
+    fn message(&self, row: usize) -> Vec<F> {
+        let block_size = self.params.block_size;
+        let row_size = self.params.row_message_size();
+        let dot_size = self.params.dot_message_size();
+        let mut rng = self.field_rng(row);
+        let mut out = vec![F::zero(); self.params.block_enc_size()];
+
+        match row {
+            row_indices::ILDT => rng.fill_field_slice(&mut out[..row_size]),
+            row_indices::IDOT => {
+                rng.fill_field_slice(&mut out[1..dot_size]);
+                ipa::balance_dot_mask(self.params, &mut out);
+            }
+            row_indices::IQUAD => rng.fill_field_slice(&mut out[block_size..dot_size]),
+            _ if self.witness_rows().contains(&row) => {
+                let block = row - row_indices::WITNESS_START;
+                let start = block * block_size;
+                let end = std::cmp::min(start + block_size, self.witness.len());
+                out[..end - start].copy_from_slice(&self.witness[start..end]);
+                if block < self.num_subfield_rows {
+                    rng.fill_subfield_slice(&mut out[end - start..row_size]);
+                } else {
+                    rng.fill_field_slice(&mut out[end - start..row_size]);
+                }
+            }
+            _ => {
+                let constraints = &self.constraints.quadratic_constraints.constraints;
+                let start = (row - self.witness_rows().end) * block_size;
+                let end = std::cmp::min(start + block_size, constraints.len());
+                for (j, &(x, y, z)) in constraints[start..end].iter().enumerate() {
+                    out[j] = self.witness[x] * self.witness[y] - self.witness[z];
+                }
+                rng.fill_field_slice(&mut out[block_size..row_size]);
+            }
+        }
+
+        out
+    }
+
+    /// Row `row`, Reed-Solomon encoded
+    fn encoded(&self, row: usize) -> Vec<F> {
+        let mut out = self.message(row);
+        self.encoder.encode(row, &mut out);
+        out
+    }
+
+    /// Call `f` with consecutive blocks of at most `rows_per_block` rows of
+    /// `rows` and the index of the first row in each
+    fn for_each_block(
+        &self,
+        rows: Range<usize>,
+        encode: bool,
+        mut f: impl FnMut(usize, &[Vec<F>]) -> Result<()>,
+    ) -> Result<()> {
+        let mut first = rows.start;
+        while first < rows.end {
+            let last = std::cmp::min(first + self.rows_per_block, rows.end);
+            let block: Vec<Vec<F>> = (first..last)
+                .into_par_iter()
+                .map(|row| if encode { self.encoded(row) } else { self.message(row) })
+                .collect();
+            f(first, &block)?;
+            first = last;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LigeroInstance, LigeroVerifier};
+    use longfellow_algebra::Fp128;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_streaming_proof_verifies() {
+        let num_witnesses = 300;
+        let mut cs = ConstraintSystem::<Fp128>::new(num_witnesses);
+        for i in 0..100 {
+            cs.add_linear_constraint(
+                vec![(i, Fp128::one()), (i + 100, Fp128::one()), (i + 200, -Fp128::one())],
+                Fp128::zero(),
+            );
+            cs.add_quadratic_constraint(i, i, i + 200);
+        }
+        let witness: Vec<Fp128> = (0..num_witnesses)
+            .map(|i| {
+                let x = Fp128::from((i % 100) as u64);
+                match i / 100 {
+                    0 => x,
+                    1 => x * x - x,
+                    _ => x * x,
+                }
+            })
+            .collect();
+
+        let params = LigeroParams::security_80();
+        let row_bytes = params.block_enc_size() * std::mem::size_of::<Fp128>();
+        let prover = LigeroProver::new(LigeroInstance::new(params.clone(), cs.clone()).unwrap()).unwrap();
+        let verifier = LigeroVerifier::new(LigeroInstance::new(params, cs).unwrap()).unwrap();
+
+        // Budgets of one row, a few rows and the whole tableau all verify
+        for budget in [row_bytes, 3 * row_bytes + 1, 1 << 30] {
+            let proof = prover.prove_streaming(&witness, &[], &mut OsRng, budget).unwrap();
+            assert!(verifier.verify(&proof, &[]).unwrap());
+        }
+
+        assert!(prover.prove_streaming(&witness, &[], &mut OsRng, row_bytes - 1).is_err());
+    }
+}
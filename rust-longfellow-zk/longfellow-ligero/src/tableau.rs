@@ -3,16 +3,24 @@
 /// The tableau is a 2D array where each row represents encoded data
 /// and columns are committed using Merkle trees.
 
+use alloc::{format, string::ToString, vec, vec::Vec};
+use core::cmp;
 use longfellow_algebra::traits::Field;
-use longfellow_algebra::fft::FFT;
+use longfellow_algebra::interpolation::FixedDomainInterpolator;
+use longfellow_arrays::backend::default_backend;
 // use longfellow_algebra::polynomial::Polynomial;  // Currently unused
 use longfellow_core::{LongfellowError, Result};
-use longfellow_random::FieldRng;
+use longfellow_random::{FieldRng, SecretRng};
 use rand::{CryptoRng, RngCore};
-use rayon::prelude::*;
+use crate::ipa;
+use crate::par::*;
 use crate::parameters::{LigeroParams, row_indices};
 
+/// Side of the square tiles `Tableau::to_column_major` transposes at once
+const TRANSPOSE_TILE: usize = 64;
+
 /// Ligero tableau storing encoded rows
+#[derive(Clone)]
 pub struct Tableau<F: Field> {
     /// Parameters
     params: LigeroParams,
@@ -56,11 +64,47 @@ impl<F: Field> Tableau<F> {
         &mut self.data[i]
     }
     
+    /// All rows, in order
+    pub fn rows(&self) -> &[Vec<F>] {
+        &self.data
+    }
+    
     /// Get a column
     pub fn column(&self, j: usize) -> Vec<F> {
         (0..self.height).map(|i| self.data[i][j]).collect()
     }
     
+    /// Copy into column-major storage
+    ///
+    /// Each task fills `TRANSPOSE_TILE` whole columns, reading them a tile
+    /// of rows at a time, so both the rows read and the columns written
+    /// stay in cache.
+    pub fn to_column_major(&self) -> TableauColumns<F> {
+        let height = self.height;
+        let mut data = vec![F::zero(); height * self.width];
+        if height == 0 {
+            return TableauColumns { data, height };
+        }
+        
+        data.par_chunks_mut(TRANSPOSE_TILE * height)
+            .enumerate()
+            .for_each(|(tile, out)| {
+                let first_col = tile * TRANSPOSE_TILE;
+                let num_cols = out.len() / height;
+                for first_row in (0..height).step_by(TRANSPOSE_TILE) {
+                    let last_row = cmp::min(first_row + TRANSPOSE_TILE, height);
+                    for (i, row) in self.data[first_row..last_row].iter().enumerate() {
+                        let row = &row[first_col..first_col + num_cols];
+                        for (j, &value) in row.iter().enumerate() {
+                            out[j * height + first_row + i] = value;
+                        }
+                    }
+                }
+            });
+        
+        TableauColumns { data, height }
+    }
+    
     /// Set a value
     pub fn set(&mut self, row: usize, col: usize, value: F) {
         self.data[row][col] = value;
@@ -71,56 +115,51 @@ impl<F: Field> Tableau<F> {
         self.data[row][col]
     }
     
-    /// Get root of unity for FFT of given size
-    fn get_omega(&self, size: usize) -> Result<F> {
-        // This is a placeholder - in real implementation, each field type
-        // should provide its own root of unity calculation
-        // For now, we'll use a simple approach that works for small sizes
-        
-        if !size.is_power_of_two() {
+    /// Fill the three blinding rows with random messages
+    ///
+    /// ILDT is a uniformly random row message and masks the low-degree
+    /// test response. IDOT and IQUAD are random product-size messages;
+    /// IDOT sums to zero over the witness block (weighted, for the
+    /// inner-product argument) and IQUAD vanishes on it, so they mask the
+    /// linear and quadratic responses without changing the values the
+    /// verifier checks there.
+    pub fn randomize_blinding_rows<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Result<()> {
+        let mut field_rng = FieldRng::<F, SecretRng>::from_rng(rng);
+        let block_size = self.params.block_size;
+        let row_size = self.params.row_message_size();
+        let dot_size = self.params.dot_message_size();
+        if dot_size > self.width {
             return Err(LongfellowError::InvalidParameter(
-                "FFT size must be a power of two".to_string(),
+                "Encoded rows too short for the blinded product messages".to_string()
             ));
         }
         
-        // For demonstration, use a simple generator
-        // In production, this should be field-specific like in C++
-        let g = F::from_u64(3);
+        field_rng.fill_field_slice(&mut self.row_mut(row_indices::ILDT)[..row_size]);
         
-        // Find the order of the multiplicative group
-        // For most fields, this is p-1 where p is the prime
-        // We'll compute g^((p-1)/size) to get a root of unity of order size
+        // Borrow the row directly so `params` stays readable alongside it
+        let idot = &mut self.data[row_indices::IDOT];
+        field_rng.fill_field_slice(&mut idot[1..dot_size]);
+        ipa::balance_dot_mask(&self.params, idot);
         
-        // This is a simplification - real implementation would need
-        // to know the actual field order and factorization
-        Ok(g)
-    }
-    
-    /// Fill blinding rows with random values
-    pub fn randomize_blinding_rows<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Result<()> {
-        let mut field_rng = FieldRng::<F, _>::new(rng);
-        
-        // Fill the three blinding rows
-        let block_size = self.params.block_size;
-        for row_idx in 0..self.params.num_blinding_rows {
-            let row = self.row_mut(row_idx);
-            for j in 0..block_size {
-                row[j] = field_rng.random_field_element();
-            }
-        }
+        let iquad = self.row_mut(row_indices::IQUAD);
+        iquad[..block_size].fill(F::zero());
+        field_rng.fill_field_slice(&mut iquad[block_size..dot_size]);
         
         Ok(())
     }
     
-    /// Layout witnesses in the tableau with random blinding
+    /// Layout witnesses in the tableau with random blinding; the first
+    /// `num_subfield_rows` rows are blinded within the subfield
     pub fn layout_witnesses<R: RngCore + CryptoRng>(
         &mut self,
         witnesses: &[F],
+        num_subfield_rows: usize,
         rng: &mut R,
     ) -> Result<()> {
-        let mut field_rng = FieldRng::<F, _>::new(rng);
+        let mut field_rng = FieldRng::<F, SecretRng>::from_rng(rng);
         let num_blocks = self.params.num_witness_blocks(witnesses.len());
         let block_size = self.params.block_size;
+        let row_size = self.params.row_message_size();
         
         for block_idx in 0..num_blocks {
             let row_idx = row_indices::WITNESS_START + block_idx;
@@ -128,15 +167,19 @@ impl<F: Field> Tableau<F> {
             
             // Fill witness values
             let start = block_idx * block_size;
-            let end = std::cmp::min(start + block_size, witnesses.len());
+            let end = cmp::min(start + block_size, witnesses.len());
             
             for (j, w_idx) in (start..end).enumerate() {
                 row[j] = witnesses[w_idx];
             }
             
-            // Add random padding if needed
-            for j in (end - start)..block_size {
-                row[j] = field_rng.random_field_element();
+            // Random padding, then the blinding values that hide the row
+            // at the opened columns
+            let padding = &mut row[(end - start)..row_size];
+            if block_idx < num_subfield_rows {
+                field_rng.fill_subfield_slice(padding);
+            } else {
+                field_rng.fill_field_slice(padding);
             }
         }
         
@@ -145,92 +188,203 @@ impl<F: Field> Tableau<F> {
     
     /// Encode all rows using Reed-Solomon encoding
     pub fn encode_rows(&mut self) -> Result<()> {
-        // Get FFT domain for encoding
-        let domain_size = self.params.block_enc_size();
-        
-        // Get root of unity for the domain size
-        let omega = self.get_omega(domain_size)?;
-        let fft = FFT::<F>::new(domain_size, omega)?;
+        let encoder = RowEncoder::new(&self.params)?;
+        self.encode_rows_with(&encoder)
+    }
+    
+    /// Encode all rows with a prepared encoder for these parameters
+    pub fn encode_rows_with(&mut self, encoder: &RowEncoder<F>) -> Result<()> {
+        if encoder.width != self.width {
+            return Err(LongfellowError::InvalidParameter(
+                "Row encoder was prepared for a different row width".to_string()
+            ));
+        }
         
         // Encode each row in parallel
-        self.data.par_iter_mut().for_each(|row| {
-            encode_row(&self.params, row, &fft);
-        });
+        self.data
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(i, row)| encoder.encode(i, row));
         
         Ok(())
     }
     
     /// Encode quadratic constraints
-    pub fn encode_quadratic_constraints(
+    pub fn encode_quadratic_constraints<R: RngCore + CryptoRng>(
         &mut self,
         constraints: &[(usize, usize, usize)],
         witnesses: &[F],
         witness_row_start: usize,
+        rng: &mut R,
     ) -> Result<()> {
+        let mut field_rng = FieldRng::<F, SecretRng>::from_rng(rng);
         let block_size = self.params.block_size;
+        let row_size = self.params.row_message_size();
         let num_quad_rows = self.params.num_quadratic_rows(constraints.len());
         
+        // All products w[x] * w[y] in one batch on the array backend
+        let xs: Vec<F> = constraints.iter().map(|&(x, _, _)| witnesses[x]).collect();
+        let ys: Vec<F> = constraints.iter().map(|&(_, y, _)| witnesses[y]).collect();
+        let mut products = vec![F::zero(); constraints.len()];
+        default_backend::<F>().mul_batch(&xs, &ys, &mut products)?;
+        
         for row_idx in 0..num_quad_rows {
             let quad_row_idx = witness_row_start + row_idx;
             let row = self.row_mut(quad_row_idx);
             
             let start = row_idx * block_size;
-            let end = std::cmp::min(start + block_size, constraints.len());
+            let end = cmp::min(start + block_size, constraints.len());
             
             for (j, c_idx) in (start..end).enumerate() {
-                let (x, y, z) = constraints[c_idx];
+                let (_, _, z) = constraints[c_idx];
                 // Encode w[x] * w[y] - w[z]
-                row[j] = witnesses[x] * witnesses[y] - witnesses[z];
+                row[j] = products[c_idx] - witnesses[z];
             }
             
             // Zero padding
             for j in (end - start)..block_size {
                 row[j] = F::zero();
             }
+            
+            field_rng.fill_field_slice(&mut row[block_size..row_size]);
         }
         
         Ok(())
     }
 }
 
-/// Encode a single row using Reed-Solomon
-fn encode_row<F: Field>(params: &LigeroParams, row: &mut [F], _fft: &FFT<F>) {
-    let block_size = params.block_size;
-    let block_enc_size = params.block_enc_size();
-    
-    // Extract the block values
-    let mut block_values = vec![F::zero(); block_size];
-    block_values.copy_from_slice(&row[..block_size]);
-    
-    // For Reed-Solomon encoding, we need to:
-    // 1. Interpolate a polynomial from the block values
-    // 2. Evaluate it at more points to create redundancy
-    
-    // Create evaluation points (0, 1, 2, ..., block_size-1)
-    let eval_points: Vec<(F, F)> = (0..block_size)
-        .map(|i| (F::from_u64(i as u64), block_values[i]))
-        .collect();
-    
-    // Interpolate polynomial using Lagrange interpolation
-    let poly = longfellow_algebra::interpolation::lagrange_interpolate(&eval_points)
-        .expect("Interpolation should not fail");
-    
-    // Evaluate at encoding points
-    let mut encoded = vec![F::zero(); block_enc_size];
+/// Tableau columns stored one after another, each contiguous
+pub struct TableauColumns<F: Field> {
+    data: Vec<F>,
+    height: usize,
+}
+
+impl<F: Field> TableauColumns<F> {
+    /// Number of columns
+    pub fn num_columns(&self) -> usize {
+        self.data.len().checked_div(self.height).unwrap_or(0)
+    }
     
-    // Systematic part: copy original values
-    for i in 0..block_size {
-        encoded[i] = block_values[i];
+    /// Access a column
+    pub fn column(&self, j: usize) -> &[F] {
+        &self.data[j * self.height..(j + 1) * self.height]
+    }
+}
+
+/// Reed-Solomon encoder for tableau rows
+///
+/// A row's message occupies the evaluations at 0..message_size and the
+/// rest of the row is the same polynomial at the remaining points. The
+/// Lagrange coefficients for those points depend only on the parameters,
+/// so they are computed once here and every row costs one matrix-vector
+/// product, with no inversions.
+pub struct RowEncoder<F: Field> {
+    width: usize,
+    row: Extension<F>,
+    dot: Extension<F>,
+}
+
+/// Lagrange coefficients of a message on {0, .., message_size - 1} at each
+/// point message_size..width
+struct Extension<F: Field> {
+    message_size: usize,
+    coeffs: Vec<Vec<F>>,
+}
+
+impl<F: Field> Extension<F> {
+    fn new(message_size: usize, width: usize) -> Result<Self> {
+        if message_size > width {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Row message of {} values does not fit in {} columns",
+                message_size, width
+            )));
+        }
+        
+        let interpolator = FixedDomainInterpolator::<F>::new(message_size)?;
+        let points: Vec<usize> = (message_size..width).collect();
+        let coeffs = lagrange_coefficients(&interpolator, &points);
+        
+        Ok(Self { message_size, coeffs })
     }
     
-    // Parity part: evaluate at additional points
-    for i in block_size..block_enc_size {
-        let point = F::from_u64(i as u64);
-        encoded[i] = poly.evaluate(&point);
+    fn extend(&self, row: &mut [F]) {
+        let (message, parity) = row.split_at_mut(self.message_size);
+        for (out, coeffs) in parity.iter_mut().zip(&self.coeffs) {
+            *out = coeffs.iter().zip(message.iter()).fold(F::zero(), |acc, (&c, &m)| acc + c * m);
+        }
+    }
+}
+
+impl<F: Field> RowEncoder<F> {
+    /// Prepare the encoder for rows of `params`
+    pub fn new(params: &LigeroParams) -> Result<Self> {
+        let width = params.block_enc_size();
+        Ok(Self {
+            width,
+            row: Extension::new(params.row_message_size(), width)?,
+            dot: Extension::new(params.dot_message_size(), width)?,
+        })
     }
     
-    // Copy back to row
-    row.copy_from_slice(&encoded);
+    /// Encode tableau row `row_idx` in place from its message prefix
+    pub fn encode(&self, row_idx: usize, row: &mut [F]) {
+        match row_idx {
+            row_indices::IDOT | row_indices::IQUAD => self.dot.extend(row),
+            _ => self.row.extend(row),
+        }
+    }
+}
+
+/// Lagrange coefficients L_i(x) of the interpolator's domain {0, .., n - 1}
+/// at each of `points`, one row per point, so that evaluating any message
+/// there is a matrix-vector product
+pub fn lagrange_coefficients<F: Field>(
+    interpolator: &FixedDomainInterpolator<F>,
+    points: &[usize],
+) -> Vec<Vec<F>> {
+    let n = interpolator.num_points();
+    let weights = interpolator.weights();
+    points
+        .par_iter()
+        .map(|&x| {
+            if x < n {
+                let mut unit = vec![F::zero(); n];
+                unit[x] = F::one();
+                return unit;
+            }
+            
+            // L_i(x) = l(x) * w_i / (x - i)
+            let x = F::from_u64(x as u64);
+            let mut diffs: Vec<F> = (0..n)
+                .map(|i| x - F::from_u64(i as u64))
+                .collect();
+            let vanishing = diffs.iter().fold(F::one(), |acc, &d| acc * d);
+            F::batch_invert(&mut diffs);
+            diffs
+                .iter()
+                .zip(weights)
+                .map(|(&inv, &w)| vanishing * w * inv)
+                .collect()
+        })
+        .collect()
+}
+
+/// Evaluate the polynomial whose values on {0, .., message.len() - 1} are
+/// `message` at each of `points`
+pub fn evaluate_message<F: Field>(message: &[F], points: &[usize]) -> Result<Vec<F>> {
+    let interpolator = FixedDomainInterpolator::new(message.len())?;
+    let points: Vec<F> = points.iter().map(|&p| F::from_u64(p as u64)).collect();
+    interpolator.evaluate_at(message, &points)
+}
+
+/// Block `block_idx` of a per-witness coefficient vector, zero-padded to
+/// `block_size` to match the witness row layout
+pub fn coefficient_block<F: Field>(coeffs: &[F], block_idx: usize, block_size: usize) -> Vec<F> {
+    let start = cmp::min(block_idx * block_size, coeffs.len());
+    let end = cmp::min(start + block_size, coeffs.len());
+    let mut block = coeffs[start..end].to_vec();
+    block.resize(block_size, F::zero());
+    block
 }
 
 /// Helper to compute linear combination of rows
@@ -277,7 +431,7 @@ mod tests {
             .map(|i| Fp128::from(i as u64))
             .collect();
         
-        tableau.layout_witnesses(&witnesses, &mut OsRng).unwrap();
+        tableau.layout_witnesses(&witnesses, 0, &mut OsRng).unwrap();
         
         // Check first witness values are correctly placed
         assert_eq!(tableau.get(row_indices::WITNESS_START, 0), Fp128::from(0));
@@ -285,11 +439,32 @@ mod tests {
         assert_eq!(tableau.get(row_indices::WITNESS_START + 1, 0), Fp128::from(128));
     }
     
+    #[test]
+    fn test_to_column_major() {
+        // Neither dimension a multiple of the tile
+        let params = LigeroParams::security_80();
+        let height = TRANSPOSE_TILE + 5;
+        let mut tableau = Tableau::<Fp128>::new(params, height);
+        let (_, width) = tableau.dimensions();
+        for i in 0..height {
+            for j in 0..width {
+                tableau.set(i, j, Fp128::from((i * width + j) as u64));
+            }
+        }
+        
+        let columns = tableau.to_column_major();
+        assert_eq!(columns.num_columns(), width);
+        for j in 0..width {
+            assert_eq!(columns.column(j), &tableau.column(j)[..]);
+        }
+    }
+    
     #[test]
     fn test_row_encoding() {
         let params = LigeroParams {
             block_size: 8,
             extension_factor: 2,
+            num_col_openings: 4,
             ..LigeroParams::security_80()
         };
         
@@ -312,4 +487,36 @@ mod tests {
             assert_eq!(row[i], Fp128::from(i as u64));
         }
     }
+    
+    #[test]
+    fn test_blinding_rows() {
+        let params = LigeroParams {
+            block_size: 8,
+            extension_factor: 2,
+            num_col_openings: 4,
+            ..LigeroParams::security_80()
+        };
+        let block_size = params.block_size;
+        let dot_size = params.dot_message_size();
+        
+        let mut tableau = Tableau::<Fp128>::new(params.clone(), 4);
+        tableau.randomize_blinding_rows(&mut OsRng).unwrap();
+        tableau.encode_rows().unwrap();
+        
+        // IDOT sums to zero and IQUAD vanishes over the witness block
+        let idot = tableau.row(row_indices::IDOT);
+        let sum = idot[..block_size].iter().fold(Fp128::zero(), |acc, &x| acc + x);
+        assert_eq!(sum, Fp128::zero());
+        assert!(tableau.row(row_indices::IQUAD)[..block_size].iter().all(|&x| x == Fp128::zero()));
+        assert_ne!(tableau.row(row_indices::IQUAD)[block_size], Fp128::zero());
+        
+        // Every row stays within its degree bound
+        for i in 0..3 {
+            let size = params.message_size(i);
+            let row = tableau.row(i);
+            let points: Vec<usize> = (size..params.block_enc_size()).collect();
+            assert_eq!(evaluate_message(&row[..size], &points).unwrap(), row[size..].to_vec());
+        }
+        assert!(params.message_size(row_indices::ILDT) < dot_size);
+    }
 }
\ No newline at end of file
@@ -1,10 +1,13 @@
 /// Ligero protocol transcript for Fiat-Shamir transform
 
+use alloc::vec::Vec;
+use core::ops::Range;
 use longfellow_algebra::traits::Field;
-use longfellow_random::Transcript;
+use longfellow_random::{labels, Transcript};
 use sha3::{Digest, Sha3_256};
 
 /// Ligero-specific transcript
+#[derive(Clone)]
 pub struct LigeroTranscript {
     /// Base transcript
     base: Transcript,
@@ -14,72 +17,90 @@ impl LigeroTranscript {
     /// Create a new Ligero transcript
     pub fn new(instance_digest: &[u8]) -> Self {
         let mut base = Transcript::new(b"Ligero-v1");
-        base.append_message(b"instance", instance_digest);
+        base.append_message(labels::LIGERO_INSTANCE, instance_digest);
         
         Self { base }
     }
     
-    /// Append column roots
-    pub fn append_column_roots(&mut self, roots: &[[u8; 32]]) {
-        self.base.append_message(b"num_roots", &(roots.len() as u64).to_le_bytes());
-        for (i, root) in roots.iter().enumerate() {
-            let mut label = b"root".to_vec();
-            label.extend_from_slice(&(i as u64).to_le_bytes());
-            self.base.append_message(&label, root);
+    /// Append the public inputs the proof is bound to
+    pub fn append_public_inputs<F: Field>(&mut self, public: &[F]) {
+        self.base.append_field_elements(labels::LIGERO_PUBLIC_INPUTS, public);
+    }
+    
+    /// Append the column commitment root
+    pub fn append_column_root(&mut self, root: &[u8; 32]) {
+        self.base.append_message(labels::LIGERO_ROOT, root);
+    }
+    
+    /// Append the verifier's randomness for this proof, if any; with none
+    /// the transcript is unchanged
+    pub fn append_statement_randomness(&mut self, randomness: &[u8]) {
+        if !randomness.is_empty() {
+            self.base.append_message(labels::LIGERO_STATEMENT_RANDOMNESS, randomness);
         }
     }
     
-    /// Get challenge for low-degree test
-    pub fn challenge_ldt<F: Field>(&mut self) -> Vec<F> {
-        // For Ligero, we need multiple challenges for the linear combination
-        self.base.challenge_scalars(b"ldt", 3)
+    /// Get challenges for low-degree test, one per combined row
+    pub fn challenge_ldt<F: Field>(&mut self, num_rows: usize) -> Vec<F> {
+        self.base.challenge_scalars(labels::LIGERO_LDT, num_rows)
     }
     
     /// Get random linear combination coefficients
     pub fn challenge_linear_combination<F: Field>(&mut self, num_coeffs: usize) -> Vec<F> {
-        self.base.challenge_scalars(b"linear_comb", num_coeffs)
+        self.base.challenge_scalars(labels::LIGERO_LINEAR_COMB, num_coeffs)
     }
     
-    /// Get column indices to open
-    pub fn challenge_column_indices(&mut self, num_columns: usize, num_openings: usize) -> Vec<usize> {
-        let mut indices = Vec::with_capacity(num_openings);
-        let mut seen = std::collections::HashSet::new();
-        
-        let mut counter = 0u64;
-        while indices.len() < num_openings {
-            let mut label = b"col_index".to_vec();
-            label.extend_from_slice(&counter.to_le_bytes());
-            
-            let hash = self.base.challenge_scalar::<longfellow_algebra::Fp128>(&label);
-            let bytes = hash.to_bytes_le();
-            let index = (u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize) % num_columns;
-            
-            if seen.insert(index) {
-                indices.push(index);
-            }
-            counter += 1;
-        }
-        
-        indices
+    /// Get distinct column indices to open from `columns`
+    pub fn challenge_column_indices(&mut self, columns: Range<usize>, num_openings: usize) -> Vec<usize> {
+        self.base.challenge_indices(labels::LIGERO_COL_INDEX, num_openings, columns)
     }
     
     /// Append prover messages
     pub fn append_ldt_response<F: Field>(&mut self, responses: &[Vec<F>]) {
         for (i, response) in responses.iter().enumerate() {
-            let mut label = b"ldt_response".to_vec();
-            label.extend_from_slice(&(i as u64).to_le_bytes());
-            self.base.append_field_elements(&label, response);
+            self.base.append_field_elements(labels::LIGERO_LDT_RESPONSE.at(i as u64), response);
         }
     }
     
+    /// Append the root of FRI layer `layer`
+    pub fn append_fri_root(&mut self, layer: usize, root: &[u8; 32]) {
+        self.base.append_message(labels::LIGERO_FRI_ROOT.at(layer as u64), root);
+    }
+    
+    /// Get the folding challenge of FRI round `round`
+    pub fn challenge_fri_fold<F: Field>(&mut self, round: usize) -> F {
+        self.base.challenge_scalar(labels::LIGERO_FRI_FOLD.at(round as u64))
+    }
+    
+    /// Append the coefficients of the last FRI layer
+    pub fn append_fri_final<F: Field>(&mut self, coeffs: &[F]) {
+        self.base.append_field_elements(labels::LIGERO_FRI_FINAL, coeffs);
+    }
+    
+    /// Get distinct FRI query positions from `positions`
+    pub fn challenge_fri_queries(&mut self, positions: Range<usize>, num_queries: usize) -> Vec<usize> {
+        self.base.challenge_indices(labels::LIGERO_FRI_QUERY, num_queries, positions)
+    }
+    
+    /// Append the root of the inner-product oracle that replaces the
+    /// linear test response
+    pub fn append_ipa_root(&mut self, root: &[u8; 32]) {
+        self.base.append_message(labels::LIGERO_IPA_ROOT, root);
+    }
+    
+    /// Get the coefficients batching the inner-product degree checks
+    pub fn challenge_ipa_batch<F: Field>(&mut self, num_coeffs: usize) -> Vec<F> {
+        self.base.challenge_scalars(labels::LIGERO_IPA_BATCH, num_coeffs)
+    }
+    
     /// Append linear test response
     pub fn append_linear_response<F: Field>(&mut self, response: &[F]) {
-        self.base.append_field_elements(b"linear_response", response);
+        self.base.append_field_elements(labels::LIGERO_LINEAR_RESPONSE, response);
     }
     
     /// Append quadratic test response
     pub fn append_quadratic_response<F: Field>(&mut self, response: &[F]) {
-        self.base.append_field_elements(b"quadratic_response", response);
+        self.base.append_field_elements(labels::LIGERO_QUADRATIC_RESPONSE, response);
     }
     
     /// Get the final transcript hash
@@ -88,7 +109,8 @@ impl LigeroTranscript {
     }
 }
 
-/// Compute instance digest for deterministic transcript initialization
+/// Compute instance digest for deterministic transcript initialization,
+/// over the parameters and `ConstraintSystem::digest`
 pub fn compute_instance_digest<F: Field>(
     params: &crate::LigeroParams,
     constraints: &crate::ConstraintSystem<F>,
@@ -97,26 +119,21 @@ pub fn compute_instance_digest<F: Field>(
     
     // Hash parameters
     hasher.update(b"LigeroInstance");
-    hasher.update(&(params.block_size as u64).to_le_bytes());
-    hasher.update(&(params.extension_factor as u64).to_le_bytes());
-    hasher.update(&(params.num_blinding_rows as u64).to_le_bytes());
-    hasher.update(&(params.num_col_openings as u64).to_le_bytes());
-    hasher.update(&(params.security_bits as u64).to_le_bytes());
-    
-    // Hash constraint system dimensions
-    hasher.update(&(constraints.num_witnesses as u64).to_le_bytes());
-    hasher.update(&(constraints.linear_constraints.num_constraints as u64).to_le_bytes());
-    hasher.update(&(constraints.quadratic_constraints.constraints.len() as u64).to_le_bytes());
+    hasher.update((params.block_size as u64).to_le_bytes());
+    hasher.update((params.extension_factor as u64).to_le_bytes());
+    hasher.update((params.num_blinding_rows as u64).to_le_bytes());
+    hasher.update((params.num_col_openings as u64).to_le_bytes());
+    hasher.update((params.security_bits as u64).to_le_bytes());
+    hasher.update([params.use_subfield as u8]);
+    hasher.update([params.optimize_size as u8]);
+    hasher.update([params.ldt as u8]);
+    hasher.update([params.linear_test as u8]);
+    hasher.update((params.num_ldt_queries as u64).to_le_bytes());
+    hasher.update((params.proofs_per_commitment as u64).to_le_bytes());
     
-    // Hash linear constraint matrix (just the structure, not values for efficiency)
-    hasher.update(&(constraints.linear_constraints.matrix.len() as u64).to_le_bytes());
-    
-    // Hash quadratic constraints
-    for &(x, y, z) in &constraints.quadratic_constraints.constraints {
-        hasher.update(&(x as u64).to_le_bytes());
-        hasher.update(&(y as u64).to_le_bytes());
-        hasher.update(&(z as u64).to_le_bytes());
-    }
+    // Hash the full constraint system, so a proof is bound to its exact
+    // coefficients and not just its shape
+    hasher.update(constraints.digest());
     
     hasher.finalize().into()
 }
@@ -137,13 +154,12 @@ mod tests {
         let mut t2 = LigeroTranscript::new(&digest);
         
         // Append same messages
-        let roots = vec![[1u8; 32], [2u8; 32]];
-        t1.append_column_roots(&roots);
-        t2.append_column_roots(&roots);
+        t1.append_column_root(&[1u8; 32]);
+        t2.append_column_root(&[1u8; 32]);
         
         // Get challenges
-        let c1: Vec<Fp128> = t1.challenge_ldt();
-        let c2: Vec<Fp128> = t2.challenge_ldt();
+        let c1: Vec<Fp128> = t1.challenge_ldt(3);
+        let c2: Vec<Fp128> = t2.challenge_ldt(3);
         
         assert_eq!(c1, c2);
     }
@@ -156,12 +172,12 @@ mod tests {
         
         let mut transcript = LigeroTranscript::new(&digest);
         
-        let indices = transcript.challenge_column_indices(1000, 50);
+        let indices = transcript.challenge_column_indices(100..1000, 50);
         assert_eq!(indices.len(), 50);
         
         // Check all indices are valid
         for &idx in &indices {
-            assert!(idx < 1000);
+            assert!((100..1000).contains(&idx));
         }
         
         // Check no duplicates
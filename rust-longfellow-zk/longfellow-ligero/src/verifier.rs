@@ -1,331 +1,573 @@
 /// Ligero verifier implementation
 
+use alloc::{format, vec, vec::Vec};
+use longfellow_algebra::interpolation::FixedDomainInterpolator;
 use longfellow_algebra::traits::Field;
+use longfellow_arrays::CsrMatrix;
 // use longfellow_algebra::fft::FFT;  // Currently unused
 // use longfellow_algebra::polynomial::Polynomial;  // Currently unused
-use longfellow_core::Result;
-use std::collections::HashMap;
+use longfellow_core::{LongfellowError, Result};
+#[cfg(feature = "std")]
+use longfellow_random::{FieldRng, SecretRng};
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
 
 use crate::{
-    LigeroInstance, LigeroProof,
+    par::*,
+    LigeroInstance, LigeroProof, LdtBackend, LinearTest,
+    fri::{self, FriChallenges, FriDomain},
+    ipa::{self, InnerProductChallenges},
     merkle::MerkleTree,
+    report::{Checked, VerificationFailure, VerificationReport},
+    tableau::{coefficient_block, evaluate_message, lagrange_coefficients},
     transcript::{LigeroTranscript, compute_instance_digest},
     parameters::row_indices,
 };
 
+/// Verifier challenges re-derived from a proof's prover messages
+pub(crate) struct Challenges<F: Field> {
+    pub ldt: Vec<F>,
+    pub linear: Vec<F>,
+    pub quadratic: Vec<F>,
+    pub column_indices: Vec<usize>,
+    pub fri: Option<FriChallenges<F>>,
+    pub ipa: Option<InnerProductChallenges<F>>,
+}
+
+/// Replay the Fiat-Shamir transcript, seeded with `instance_digest`, over
+/// the public inputs, the statement randomness and the roots and responses
+/// of `proof`; the column openings are not absorbed
+pub(crate) fn replay_challenges<F: Field>(
+    instance: &LigeroInstance<F>,
+    instance_digest: &[u8; 32],
+    public: &[F],
+    statement_randomness: &[u8],
+    proof: &LigeroProof<F>,
+) -> Challenges<F> {
+    let params = &instance.params;
+    let constraints = &instance.constraints;
+    
+    // Initialize transcript
+    let mut transcript = LigeroTranscript::new(instance_digest);
+    transcript.append_public_inputs(public);
+    
+    // Add column roots to transcript
+    transcript.append_column_root(&proof.column_root);
+    transcript.append_statement_randomness(statement_randomness);
+    
+    // Get challenges
+    let num_quad_rows = params.num_quadratic_rows(constraints.quadratic_constraints.constraints.len());
+    let height = params.tableau_height(
+        params.num_witness_blocks(constraints.num_witnesses),
+        num_quad_rows,
+    );
+    let ldt = transcript.challenge_ldt(height - row_indices::WITNESS_START);
+    let fri_proof = proof.fri.as_ref().filter(|_| params.ldt == LdtBackend::Fri);
+    match fri_proof.and_then(|fri| fri.layer_roots.first()) {
+        Some(root) => transcript.append_fri_root(0, root),
+        None => transcript.append_ldt_response(&proof.ldt_responses),
+    }
+    
+    let linear = transcript.challenge_linear_combination(
+        constraints.linear_constraints.num_constraints
+    );
+    let ipa_proof = proof.linear_ipa.as_ref().filter(|_| params.linear_test == LinearTest::InnerProduct);
+    match ipa_proof {
+        Some(ipa) => transcript.append_ipa_root(&ipa.oracle_root),
+        None => transcript.append_linear_response(&proof.linear_responses),
+    }
+    
+    let quadratic = transcript.challenge_linear_combination(num_quad_rows);
+    transcript.append_quadratic_response(&proof.quadratic_responses);
+    
+    let column_indices = transcript.challenge_column_indices(
+        params.opening_columns(),
+        params.num_col_openings,
+    );
+    let fri = fri_proof.and_then(|fri| fri::replay_challenges(fri, params, &mut transcript));
+    let ipa = ipa_proof.and_then(|ipa| ipa::replay_challenges(ipa, params, &mut transcript));
+    
+    Challenges { ldt, linear, quadratic, column_indices, fri, ipa }
+}
+
 /// Ligero verifier
+///
+/// Everything the linear test needs that depends only on the instance is
+/// built once here and shared by every proof: A^T in CSR form, so the
+/// challenged row combination is one parallel pass of row dot products,
+/// and the interpolation weights of a witness block; and for the FRI
+/// backend and the inner-product argument, their evaluation cosets. The
+/// instance digest hashes every
+/// coefficient of the constraint system, so it is cached as well.
 pub struct LigeroVerifier<F: Field> {
     instance: LigeroInstance<F>,
+    instance_digest: [u8; 32],
+    transposed: CsrMatrix<F>,
+    block_interpolator: FixedDomainInterpolator<F>,
+    fri_domain: Option<FriDomain<F>>,
+    ipa_domain: Option<FriDomain<F>>,
 }
 
 impl<F: Field> LigeroVerifier<F> {
     /// Create a new verifier
     pub fn new(instance: LigeroInstance<F>) -> Result<Self> {
         instance.params.validate()?;
-        Ok(Self { instance })
+        let instance_digest = compute_instance_digest(&instance.params, &instance.constraints);
+        let transposed = instance.constraints.linear_constraints.matrix.transpose();
+        let block_interpolator = FixedDomainInterpolator::new(instance.params.block_size)?;
+        let fri_domain = match instance.params.ldt {
+            LdtBackend::Direct => None,
+            LdtBackend::Fri => Some(FriDomain::new(&instance.params)?),
+        };
+        let ipa_domain = match instance.params.linear_test {
+            LinearTest::Direct => None,
+            LinearTest::InnerProduct => {
+                Some(FriDomain::with_size(&instance.params, instance.params.ipa_domain_size())?)
+            }
+        };
+        Ok(Self {
+            instance,
+            instance_digest,
+            transposed,
+            block_interpolator,
+            fri_domain,
+            ipa_domain,
+        })
     }
     
-    /// Verify a proof
-    pub fn verify(&self, proof: &LigeroProof<F>) -> Result<bool> {
-        // Initialize transcript
-        let instance_digest = compute_instance_digest(
-            &self.instance.params,
-            &self.instance.constraints,
+    /// Verify a proof against the public inputs it claims
+    pub fn verify(&self, proof: &LigeroProof<F>, public: &[F]) -> Result<bool> {
+        self.verify_with_statement(proof, public, &[])
+    }
+    
+    /// Verify a proof from `LigeroProver::prove_with_commitment` that must
+    /// answer this verifier's `statement_randomness`
+    pub fn verify_with_statement(
+        &self,
+        proof: &LigeroProof<F>,
+        public: &[F],
+        statement_randomness: &[u8],
+    ) -> Result<bool> {
+        let rhs = self.instance.constraints.effective_rhs(public)?;
+        let challenges = replay_challenges(
+            &self.instance,
+            &self.instance_digest,
+            public,
+            statement_randomness,
+            proof,
         );
-        let mut transcript = LigeroTranscript::new(&instance_digest);
         
-        // Add column roots to transcript
-        transcript.append_column_roots(&proof.column_roots);
-        
-        // Get challenges
-        let ldt_challenges = transcript.challenge_ldt();
-        transcript.append_ldt_response(&proof.ldt_responses);
+        // Verify column openings
+        if !self.column_failures(proof, &challenges.column_indices).is_empty() {
+            return Ok(false);
+        }
         
-        let linear_challenge = transcript.challenge_linear_combination(
-            self.instance.constraints.linear_constraints.num_constraints
+        // Every test must hold exactly at every opened column
+        Ok(match self.residuals(proof, &challenges, &rhs)? {
+            Ok(residuals) => residuals.iter().all(|&r| r == F::zero()),
+            Err(_) => false,
+        })
+    }
+    
+    /// Verify like `verify_with_statement`, naming every check that fails
+    ///
+    /// Bad column openings are reported on their own, since the tests read
+    /// the opened values. Otherwise each test reports why it could not run,
+    /// or every opened column where it fails.
+    pub fn verify_with_report(
+        &self,
+        proof: &LigeroProof<F>,
+        public: &[F],
+        statement_randomness: &[u8],
+    ) -> Result<VerificationReport> {
+        let rhs = self.instance.constraints.effective_rhs(public)?;
+        let challenges = replay_challenges(
+            &self.instance,
+            &self.instance_digest,
+            public,
+            statement_randomness,
+            proof,
         );
-        transcript.append_linear_response(&proof.linear_responses);
         
-        let quad_challenge = transcript.challenge_linear_combination(
-            self.instance.constraints.quadratic_constraints.constraints.len()
-        );
-        transcript.append_quadratic_response(&proof.quadratic_responses);
+        let failures = self.column_failures(proof, &challenges.column_indices);
+        if !failures.is_empty() {
+            return Ok(VerificationReport::new(failures));
+        }
         
-        let column_indices = transcript.challenge_column_indices(
-            self.instance.params.block_enc_size(),
-            self.instance.params.num_col_openings,
-        );
+        let indices = &challenges.column_indices;
+        let [ldt, linear, quadratic] = self.test_residuals(proof, &challenges, &rhs)?;
+        let mut failures = Vec::new();
+        report_residuals(&mut failures, ldt, indices, |column| VerificationFailure::LdtColumn { column });
+        report_residuals(&mut failures, linear, indices, |column| VerificationFailure::LinearColumn { column });
+        report_residuals(&mut failures, quadratic, indices, |column| {
+            VerificationFailure::QuadraticColumn { column }
+        });
         
-        // Verify column openings
-        if !self.verify_column_openings(proof, &column_indices)? {
-            return Ok(false);
+        Ok(VerificationReport::new(failures))
+    }
+    
+    /// Verify many independent proofs of this instance
+    ///
+    /// Merkle paths of all proofs are checked in one parallel pass, and the
+    /// column checks of all proofs are folded into a single random linear
+    /// combination, which vanishes for invalid proofs only with probability
+    /// about 1/|F|. `public[i]` holds the public inputs of `proofs[i]`.
+    /// Needs `std` for the verifier's own randomness.
+    #[cfg(feature = "std")]
+    pub fn verify_batch(&self, proofs: &[LigeroProof<F>], public: &[Vec<F>]) -> Result<bool> {
+        if public.len() != proofs.len() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Got {} public input vectors for {} proofs",
+                public.len(),
+                proofs.len()
+            )));
         }
         
-        // Reconstruct opened columns
-        let opened_columns = self.reconstruct_columns(proof)?;
+        let rhs = public
+            .iter()
+            .map(|public| self.instance.constraints.effective_rhs(public))
+            .collect::<Result<Vec<_>>>()?;
+        let challenges: Vec<Challenges<F>> = proofs
+            .par_iter()
+            .zip(public.par_iter())
+            .map(|(proof, public)| {
+                replay_challenges(&self.instance, &self.instance_digest, public, &[], proof)
+            })
+            .collect();
         
-        // Verify low-degree test
-        if !self.verify_ldt(&opened_columns, &ldt_challenges, &proof.ldt_responses)? {
+        let openings_valid: Vec<bool> = proofs
+            .par_iter()
+            .zip(challenges.par_iter())
+            .map(|(proof, challenges)| self.column_failures(proof, &challenges.column_indices).is_empty())
+            .collect();
+        if openings_valid.contains(&false) {
             return Ok(false);
         }
         
-        // Verify linear constraints
-        if !self.verify_linear_constraints(
-            &opened_columns,
-            &linear_challenge,
-            &proof.linear_responses,
-        )? {
+        let residuals = proofs
+            .par_iter()
+            .zip(challenges.par_iter())
+            .zip(rhs.par_iter())
+            .map(|((proof, challenges), rhs)| Ok(self.residuals(proof, challenges, rhs)?.ok()))
+            .collect::<Result<Option<Vec<Vec<F>>>>>()?;
+        let Some(residuals) = residuals else {
             return Ok(false);
+        };
+        
+        // Verifier-private coefficients, so a prover cannot aim
+        // residuals at each other
+        let mut field_rng = FieldRng::<F, SecretRng>::from_rng(&mut OsRng);
+        let mut combined = F::zero();
+        for r in residuals.iter().flatten() {
+            combined += field_rng.random_field_element() * *r;
         }
         
-        // Verify quadratic constraints
-        if !self.verify_quadratic_constraints(
-            &opened_columns,
-            &quad_challenge,
+        Ok(combined == F::zero())
+    }
+    
+    /// Differences between each response and the committed rows at every
+    /// opened column, for all three tests; the first failed check if one
+    /// that does not involve the columns already fails
+    fn residuals(
+        &self,
+        proof: &LigeroProof<F>,
+        challenges: &Challenges<F>,
+        rhs: &[F],
+    ) -> Result<Checked<Vec<F>>> {
+        let outcomes = self.test_residuals(proof, challenges, rhs)?;
+        Ok(outcomes.into_iter().collect::<Checked<Vec<_>>>().map(|all| all.concat()))
+    }
+    
+    /// Residuals of the LDT, linear and quadratic tests, each on its own
+    ///
+    /// The openings must have been checked against the challenged indices,
+    /// and `rhs` is the linear right-hand side with the public inputs
+    /// folded in.
+    fn test_residuals(
+        &self,
+        proof: &LigeroProof<F>,
+        challenges: &Challenges<F>,
+        rhs: &[F],
+    ) -> Result<[Checked<Vec<F>>; 3]> {
+        let indices = &challenges.column_indices;
+        let columns: Vec<&[F]> = proof.column_openings
+            .iter()
+            .map(|opening| opening.values.as_slice())
+            .collect();
+        
+        let ldt = match &self.fri_domain {
+            None => self.ldt_residuals(indices, &columns, &challenges.ldt, &proof.ldt_responses)?,
+            Some(domain) => self.fri_residuals(domain, proof, challenges, &columns)?,
+        };
+        let linear = match &self.ipa_domain {
+            None => self.linear_residuals(
+                indices,
+                &columns,
+                &challenges.linear,
+                rhs,
+                &proof.linear_responses,
+            )?,
+            Some(domain) => self.ipa_residuals(domain, proof, challenges, &columns, rhs)?,
+        };
+        let quadratic = self.quadratic_residuals(
+            indices,
+            &columns,
+            &challenges.quadratic,
             &proof.quadratic_responses,
-        )? {
-            return Ok(false);
-        }
+        )?;
         
-        Ok(true)
+        Ok([ldt, linear, quadratic])
     }
     
-    /// Verify column openings with Merkle proofs
-    fn verify_column_openings(
+    /// Check the column openings against the challenged indices and the
+    /// column root
+    fn column_failures(
         &self,
         proof: &LigeroProof<F>,
         expected_indices: &[usize],
-    ) -> Result<bool> {
+    ) -> Vec<VerificationFailure> {
         if proof.column_openings.len() != expected_indices.len() {
-            return Ok(false);
+            return vec![VerificationFailure::ColumnCount {
+                expected: expected_indices.len(),
+                actual: proof.column_openings.len(),
+            }];
         }
         
-        if proof.column_roots.len() != 1 {
-            return Ok(false);
-        }
+        let root = &proof.column_root;
+        let expected_height = self.calculate_expected_height();
+        let subfield_rows = self.instance.params.subfield_rows(
+            self.instance.constraints.subfield_boundary
+        );
         
-        let root = &proof.column_roots[0];
+        // Openings are independent, so check their paths in parallel
+        let failures: Vec<Option<VerificationFailure>> = proof.column_openings
+            .par_iter()
+            .zip(expected_indices.par_iter())
+            .enumerate()
+            .map(|(k, (opening, &column))| {
+                if opening.index != column {
+                    Some(VerificationFailure::ColumnIndex { opening: k, expected: column, actual: opening.index })
+                } else if opening.values.len() != expected_height
+                    || opening.values[subfield_rows.clone()].iter().any(|v| v.to_subfield().is_none())
+                {
+                    Some(VerificationFailure::ColumnValues { column })
+                } else if !MerkleTree::verify(
+                    root,
+                    opening.index,
+                    &opening.values,
+                    &opening.merkle_proof,
+                    self.instance.params.digest_len(),
+                ) {
+                    Some(VerificationFailure::MerklePath { column })
+                } else {
+                    None
+                }
+            })
+            .collect();
         
-        for (opening, &expected_idx) in proof.column_openings.iter().zip(expected_indices.iter()) {
-            if opening.index != expected_idx {
-                return Ok(false);
-            }
-            
-            // Verify Merkle proof
-            if !MerkleTree::verify(
-                root,
-                opening.index,
-                &opening.values,
-                &opening.merkle_proof,
-            ) {
-                return Ok(false);
-            }
-            
-            // Verify column has correct height
-            let expected_height = self.calculate_expected_height();
-            if opening.values.len() != expected_height {
-                return Ok(false);
-            }
+        failures.into_iter().flatten().collect()
+    }
+    
+    /// Low-degree test residuals
+    ///
+    /// The response is a row message, so it is low-degree by construction;
+    /// it must agree with ILDT plus the challenged combination of the
+    /// committed rows at every opened column.
+    fn ldt_residuals(
+        &self,
+        indices: &[usize],
+        columns: &[&[F]],
+        challenges: &[F],
+        responses: &[Vec<F>],
+    ) -> Result<Checked<Vec<F>>> {
+        if responses.len() != 1 || responses[0].len() != self.instance.params.row_message_size() {
+            return Ok(Err(VerificationFailure::LdtShape));
         }
         
-        Ok(true)
+        let expected = evaluate_message(&responses[0], indices)?;
+        let residuals = columns
+            .iter()
+            .zip(expected)
+            .map(|(column, expected)| expected - ldt_combination(column, challenges));
+        
+        Ok(Ok(residuals.collect()))
     }
     
-    /// Reconstruct opened columns into a map
-    fn reconstruct_columns(
+    /// FRI low-degree test
+    ///
+    /// FRI's checks are exact, so a passing proof adds no residuals; the
+    /// combined row it is run against is ILDT plus the challenged
+    /// combination of the committed rows at every opened column.
+    fn fri_residuals(
         &self,
+        domain: &FriDomain<F>,
         proof: &LigeroProof<F>,
-    ) -> Result<HashMap<usize, Vec<F>>> {
-        let mut columns = HashMap::new();
-        
-        for opening in &proof.column_openings {
-            columns.insert(opening.index, opening.values.clone());
-        }
+        challenges: &Challenges<F>,
+        columns: &[&[F]],
+    ) -> Result<Checked<Vec<F>>> {
+        let (Some(fri_proof), Some(fri_challenges)) = (&proof.fri, &challenges.fri) else {
+            return Ok(Err(VerificationFailure::LdtShape));
+        };
+        let at_columns: Vec<F> = columns
+            .iter()
+            .map(|column| ldt_combination(column, &challenges.ldt))
+            .collect();
         
-        Ok(columns)
+        let checked = fri::verify(
+            fri_proof,
+            fri_challenges,
+            domain,
+            &self.instance.params,
+            &challenges.column_indices,
+            &at_columns,
+        )?;
+        Ok(checked.map(|()| Vec::new()))
     }
     
-    /// Verify low-degree test
-    fn verify_ldt(
+    /// Linear test residuals
+    ///
+    /// The response must sum to challenges . (b - P * x) over the witness
+    /// block and agree with IDOT + sum_i A_i * W_i at every opened column.
+    fn linear_residuals(
         &self,
-        opened_columns: &HashMap<usize, Vec<F>>,
+        indices: &[usize],
+        columns: &[&[F]],
         challenges: &[F],
-        responses: &[Vec<F>],
-    ) -> Result<bool> {
-        // For each opened column, verify the low-degree test
-        for (&col_idx, column) in opened_columns {
-            // Extract relevant rows from the column
-            let blinding_values: Vec<F> = (0..self.instance.params.num_blinding_rows)
-                .map(|i| column[i])
-                .collect();
-            
-            // Compute expected value from linear combination
-            let mut expected = F::zero();
-            for (_i, (&val, &challenge)) in blinding_values.iter().zip(challenges.iter()).enumerate() {
-                expected += val * challenge;
-            }
-            
-            // Compare with response at this column
-            if responses.len() > 0 && col_idx < responses[0].len() {
-                if expected != responses[0][col_idx] {
-                    return Ok(false);
-                }
-            }
+        rhs: &[F],
+        response: &[F],
+    ) -> Result<Checked<Vec<F>>> {
+        let params = &self.instance.params;
+        if response.len() != params.dot_message_size() {
+            return Ok(Err(VerificationFailure::LinearShape));
         }
         
-        // Verify each response is low-degree
-        for response in responses {
-            if !self.is_low_degree(response)? {
-                return Ok(false);
-            }
+        let sum = response[..params.block_size]
+            .iter()
+            .fold(F::zero(), |acc, &x| acc + x);
+        if sum != linear_claim(challenges, rhs) {
+            return Ok(Err(VerificationFailure::LinearSum));
         }
         
-        Ok(true)
+        let expected = evaluate_message(response, indices)?;
+        let at_columns = self.linear_at_columns(indices, columns, challenges)?;
+        let residuals = expected.into_iter().zip(at_columns).map(|(e, c)| e - c);
+        
+        Ok(Ok(residuals.collect()))
     }
     
-    /// Check if a row is low-degree
-    fn is_low_degree(&self, row: &[F]) -> Result<bool> {
-        let block_size = self.instance.params.block_size;
-        
-        if row.len() < block_size {
-            return Ok(false);
-        }
-        
-        // Extract the systematic part
-        let systematic: Vec<_> = row[..block_size].to_vec();
-        
-        // Interpolate polynomial using evaluation points
-        let eval_points: Vec<(F, F)> = (0..block_size)
-            .map(|i| (F::from_u64(i as u64), systematic[i]))
-            .collect();
-        
-        let poly = longfellow_algebra::interpolation::lagrange_interpolate(&eval_points)?;
-        
-        // Verify degree bound
-        if let Some(degree) = poly.degree() {
-            if degree >= block_size {
-                return Ok(false);
-            }
-        }
-        
-        // Verify encoding matches
-        for i in block_size..std::cmp::min(2 * block_size - 1, row.len()) {
-            let point = F::from_u64(i as u64);
-            let expected = poly.evaluate(&point);
-            if row[i] != expected {
-                return Ok(false);
-            }
-        }
-        
-        Ok(true)
+    /// Linear test through the inner-product argument
+    ///
+    /// Like FRI its checks are exact, so a passing proof adds no residuals;
+    /// it shows the committed product row agrees with the opened columns
+    /// and has the claimed weighted sum.
+    fn ipa_residuals(
+        &self,
+        domain: &FriDomain<F>,
+        proof: &LigeroProof<F>,
+        challenges: &Challenges<F>,
+        columns: &[&[F]],
+        rhs: &[F],
+    ) -> Result<Checked<Vec<F>>> {
+        let (Some(ipa_proof), Some(ipa_challenges)) = (&proof.linear_ipa, &challenges.ipa) else {
+            return Ok(Err(VerificationFailure::LinearShape));
+        };
+        let at_columns = self.linear_at_columns(&challenges.column_indices, columns, &challenges.linear)?;
+        
+        let checked = ipa::verify(
+            ipa_proof,
+            ipa_challenges,
+            domain,
+            &self.instance.params,
+            &challenges.column_indices,
+            &at_columns,
+            linear_claim(&challenges.linear, rhs),
+        )?;
+        Ok(checked.map(|()| Vec::new()))
     }
     
-    /// Verify linear constraints
-    fn verify_linear_constraints(
+    /// IDOT + sum_i A_i * W_i at every opened column
+    fn linear_at_columns(
         &self,
-        opened_columns: &HashMap<usize, Vec<F>>,
+        indices: &[usize],
+        columns: &[&[F]],
         challenges: &[F],
-        response: &[F],
-    ) -> Result<bool> {
-        // Compute expected response from opened witness values
-        let mut computed_response = vec![F::zero(); self.instance.params.block_size];
-        
-        // For each opened column
-        for (&col_idx, column) in opened_columns {
-            if col_idx >= computed_response.len() {
-                continue;
-            }
-            
-            // Extract witness values from appropriate rows
-            let witness_start = row_indices::WITNESS_START;
-            let num_witness_blocks = self.instance.params.num_witness_blocks(
-                self.instance.constraints.num_witnesses
-            );
-            
-            for block_idx in 0..num_witness_blocks {
-                let row_idx = witness_start + block_idx;
-                if row_idx < column.len() {
-                    let witness_idx = block_idx * self.instance.params.block_size + col_idx;
-                    if witness_idx < self.instance.constraints.num_witnesses {
-                        // Apply linear constraints
-                        for (i, &challenge) in challenges.iter().enumerate() {
-                            for &(row, col, ref coeff) in &self.instance.constraints.linear_constraints.matrix {
-                                if row == i && col == witness_idx {
-                                    computed_response[col_idx % self.instance.params.block_size] += 
-                                        challenge * *coeff * column[row_idx];
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    ) -> Result<Vec<F>> {
+        let params = &self.instance.params;
+        
+        // Row combination a = A^T * challenges, so each witness contributes
+        // a[w] * w regardless of how many constraints mention it
+        let mut combined = self.transposed.mul_vec(challenges)?;
+        ipa::weigh_coefficients(params, &mut combined);
+        
+        // Each block of a extends to the opened columns through the same
+        // Lagrange coefficients, so they are computed once per proof
+        let lagrange = lagrange_coefficients(&self.block_interpolator, indices);
+        let num_blocks = params.num_witness_blocks(self.instance.constraints.num_witnesses);
+        let coeffs_at_columns: Vec<Vec<F>> = (0..num_blocks)
+            .into_par_iter()
+            .map(|i| {
+                let block = coefficient_block(&combined, i, params.block_size);
+                lagrange
+                    .iter()
+                    .map(|l| l.iter().zip(&block).fold(F::zero(), |acc, (&c, &a)| acc + c * a))
+                    .collect()
+            })
+            .collect();
         
-        // Compare with prover's response
-        for (&col_idx, _) in opened_columns {
-            if col_idx < response.len() && col_idx < computed_response.len() {
-                if response[col_idx] != computed_response[col_idx] {
-                    return Ok(false);
-                }
+        let at_columns = columns.iter().enumerate().map(|(k, column)| {
+            let mut combined = column[row_indices::IDOT];
+            for (i, coeffs) in coeffs_at_columns.iter().enumerate() {
+                combined += coeffs[k] * column[row_indices::WITNESS_START + i];
             }
-        }
+            combined
+        });
         
-        Ok(true)
+        Ok(at_columns.collect())
     }
     
-    /// Verify quadratic constraints
-    fn verify_quadratic_constraints(
+    /// Quadratic test residuals
+    ///
+    /// The response must vanish on the witness block and agree with IQUAD
+    /// plus the challenged combination of the quadratic rows at every opened
+    /// column.
+    fn quadratic_residuals(
         &self,
-        opened_columns: &HashMap<usize, Vec<F>>,
+        indices: &[usize],
+        columns: &[&[F]],
         challenges: &[F],
         response: &[F],
-    ) -> Result<bool> {
+    ) -> Result<Checked<Vec<F>>> {
         if self.instance.constraints.quadratic_constraints.constraints.is_empty() {
-            return Ok(response.is_empty());
+            return Ok(response.is_empty().then(Vec::new).ok_or(VerificationFailure::QuadraticShape));
         }
         
-        // For each opened column, verify quadratic constraint values
-        let witness_blocks = self.instance.params.num_witness_blocks(
-            self.instance.constraints.num_witnesses
-        );
+        let params = &self.instance.params;
+        if response.len() != params.dot_message_size() {
+            return Ok(Err(VerificationFailure::QuadraticShape));
+        }
+        if let Some(position) = response[..params.block_size].iter().position(|&x| x != F::zero()) {
+            return Ok(Err(VerificationFailure::QuadraticPosition { position }));
+        }
+        
+        let witness_blocks = params.num_witness_blocks(self.instance.constraints.num_witnesses);
         let quad_start = row_indices::WITNESS_START + witness_blocks;
         
-        for (&col_idx, column) in opened_columns {
-            if col_idx >= response.len() {
-                continue;
-            }
-            
-            let mut expected = F::zero();
-            
-            // Sum up quadratic constraint contributions
-            let num_quad_rows = self.instance.params.num_quadratic_rows(
-                self.instance.constraints.quadratic_constraints.constraints.len()
-            );
-            
-            for quad_row in 0..num_quad_rows {
-                let row_idx = quad_start + quad_row;
-                if row_idx < column.len() {
-                    let constraint_start = quad_row * self.instance.params.block_size;
-                    let constraint_end = std::cmp::min(
-                        constraint_start + self.instance.params.block_size,
-                        challenges.len()
-                    );
-                    
-                    if col_idx < self.instance.params.block_size {
-                        let local_idx = constraint_start + (col_idx % self.instance.params.block_size);
-                        if local_idx < constraint_end {
-                            expected += challenges[local_idx] * column[row_idx];
-                        }
-                    }
-                }
-            }
-            
-            if response[col_idx] != expected {
-                return Ok(false);
+        let expected = evaluate_message(response, indices)?;
+        let residuals = columns.iter().zip(expected).map(|(column, expected)| {
+            let mut combined = column[row_indices::IQUAD];
+            for (i, &challenge) in challenges.iter().enumerate() {
+                combined += challenge * column[quad_start + i];
             }
-        }
+            expected - combined
+        });
         
-        Ok(true)
+        Ok(Ok(residuals.collect()))
+    }
+    
+    fn num_quad_rows(&self) -> usize {
+        self.instance.params.num_quadratic_rows(
+            self.instance.constraints.quadratic_constraints.constraints.len()
+        )
     }
     
     /// Calculate expected tableau height
@@ -333,14 +575,49 @@ impl<F: Field> LigeroVerifier<F> {
         let witness_blocks = self.instance.params.num_witness_blocks(
             self.instance.constraints.num_witnesses
         );
-        let quad_rows = self.instance.params.num_quadratic_rows(
-            self.instance.constraints.quadratic_constraints.constraints.len()
-        );
-        
-        self.instance.params.tableau_height(witness_blocks, quad_rows)
+        self.instance.params.tableau_height(witness_blocks, self.num_quad_rows())
+    }
+}
+
+/// Record a test's failure, or each opened column where its residual is
+/// nonzero
+fn report_residuals<F: Field>(
+    failures: &mut Vec<VerificationFailure>,
+    outcome: Checked<Vec<F>>,
+    indices: &[usize],
+    at_column: impl Fn(usize) -> VerificationFailure,
+) {
+    match outcome {
+        Err(failure) => failures.push(failure),
+        Ok(residuals) => failures.extend(
+            residuals
+                .iter()
+                .zip(indices)
+                .filter(|(r, _)| **r != F::zero())
+                .map(|(_, &column)| at_column(column)),
+        ),
     }
 }
 
+/// challenges . b, what the linear response sums to over the witness block
+fn linear_claim<F: Field>(challenges: &[F], rhs: &[F]) -> F {
+    let mut claimed = F::zero();
+    for (b, challenge) in rhs.iter().zip(challenges.iter()) {
+        claimed += *b * *challenge;
+    }
+    claimed
+}
+
+/// ILDT plus the challenged combination of the witness and quadratic
+/// rows, at one column
+fn ldt_combination<F: Field>(column: &[F], challenges: &[F]) -> F {
+    let mut combined = column[row_indices::ILDT];
+    for (i, &challenge) in challenges.iter().enumerate() {
+        combined += challenge * column[row_indices::WITNESS_START + i];
+    }
+    combined
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,11 +642,47 @@ mod tests {
         // Generate proof
         let prover = LigeroProver::new(instance.clone()).unwrap();
         let witness = vec![Fp128::from(2), Fp128::from(3), Fp128::from(6)];
-        let proof = prover.prove(&witness, &mut OsRng).unwrap();
+        let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
         
         // Verify proof
         let verifier = LigeroVerifier::new(instance).unwrap();
-        assert!(verifier.verify(&proof).unwrap());
+        assert!(verifier.verify(&proof, &[]).unwrap());
+    }
+    
+    #[test]
+    fn test_verify_public_inputs() {
+        // w[0] + w[1] = x[0] and w[0] * w[1] = x[1], through the product
+        // variable w[2] = w[0] * w[1]
+        let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(3, 2);
+        cs.add_linear_constraint_with_public(
+            vec![(0, Fp128::one()), (1, Fp128::one())],
+            vec![(0, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 1, 2);
+        cs.add_linear_constraint_with_public(
+            vec![(2, Fp128::one())],
+            vec![(1, -Fp128::one())],
+            Fp128::zero(),
+        );
+        
+        let instance = LigeroInstance::new(LigeroParams::security_80(), cs).unwrap();
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let verifier = LigeroVerifier::new(instance).unwrap();
+        
+        // w = (1, 3, 3): 1 + 3 = 4, 1 * 3 = 3
+        let witness = vec![Fp128::from(1), Fp128::from(3), Fp128::from(3)];
+        let public = vec![Fp128::from(4), Fp128::from(3)];
+        let proof = prover.prove(&witness, &public, &mut OsRng).unwrap();
+        assert!(verifier.verify(&proof, &public).unwrap());
+        
+        // The same proof does not verify for other public inputs
+        assert!(!verifier.verify(&proof, &[Fp128::from(5), Fp128::from(3)]).unwrap());
+        assert!(!verifier.verify(&proof, &[Fp128::from(4), Fp128::from(2)]).unwrap());
+        assert!(verifier.verify(&proof, &public[..1]).is_err());
+        
+        // Nor can the prover claim a public input the witness does not fit
+        assert!(prover.prove(&witness, &[Fp128::from(5), Fp128::from(3)], &mut OsRng).is_err());
     }
     
     #[test]
@@ -387,7 +700,7 @@ mod tests {
         // Generate valid proof
         let prover = LigeroProver::new(instance.clone()).unwrap();
         let witness = vec![Fp128::from(2), Fp128::from(3), Fp128::from(5)];
-        let mut proof = prover.prove(&witness, &mut OsRng).unwrap();
+        let mut proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
         
         // Corrupt the proof
         if !proof.linear_responses.is_empty() {
@@ -396,6 +709,60 @@ mod tests {
         
         // Verify should fail
         let verifier = LigeroVerifier::new(instance).unwrap();
-        assert!(!verifier.verify(&proof).unwrap());
+        assert!(!verifier.verify(&proof, &[]).unwrap());
+    }
+    
+    #[test]
+    fn test_reject_other_statement() {
+        // w[0] - c * w[1] = 0: every c is satisfied by w = (0, 0), and all
+        // the statements share one shape
+        let instance = |c: u64| {
+            let mut cs = ConstraintSystem::<Fp128>::new(2);
+            cs.add_linear_constraint(
+                vec![(0, Fp128::one()), (1, -Fp128::from(c))],
+                Fp128::zero(),
+            );
+            cs.add_quadratic_constraint(0, 1, 0);
+            LigeroInstance::new(LigeroParams::security_80(), cs).unwrap()
+        };
+        
+        let prover = LigeroProver::new(instance(2)).unwrap();
+        let witness = vec![Fp128::zero(), Fp128::zero()];
+        let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
+        
+        assert!(LigeroVerifier::new(instance(2)).unwrap().verify(&proof, &[]).unwrap());
+        assert!(!LigeroVerifier::new(instance(3)).unwrap().verify(&proof, &[]).unwrap());
+    }
+    
+    #[test]
+    fn test_verify_batch() {
+        let mut cs = ConstraintSystem::<Fp128>::new(3);
+        cs.add_linear_constraint(
+            vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+            Fp128::zero(),
+        );
+        cs.add_quadratic_constraint(0, 0, 1);
+        
+        let instance = LigeroInstance::new(LigeroParams::security_80(), cs).unwrap();
+        let prover = LigeroProver::new(instance.clone()).unwrap();
+        let verifier = LigeroVerifier::new(instance).unwrap();
+        
+        let mut proofs: Vec<_> = (1..=4u64)
+            .map(|x| {
+                let x = Fp128::from(x);
+                prover.prove(&[x, x * x, x + x * x], &[], &mut OsRng).unwrap()
+            })
+            .collect();
+        let public = vec![Vec::new(); proofs.len()];
+        assert!(verifier.verify_batch(&proofs, &public).unwrap());
+        assert!(verifier.verify_batch(&[], &[]).unwrap());
+        assert!(verifier.verify_batch(&proofs, &public[1..]).is_err());
+        
+        proofs[2].quadratic_responses[0] += Fp128::one();
+        assert!(!verifier.verify_batch(&proofs, &public).unwrap());
+        
+        proofs[2] = proofs[1].clone();
+        proofs[3].column_openings[0].values[0] += Fp128::one();
+        assert!(!verifier.verify_batch(&proofs, &public).unwrap());
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,6 @@
 use longfellow_ligero::*;
 use longfellow_algebra::{Fp128, traits::Field};
+use longfellow_gf2k::Gf2_128;
 use rand::rngs::OsRng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
@@ -38,18 +39,18 @@ fn test_basic_linear_constraints() {
     ];
     
     // Verify witness satisfies constraints
-    assert!(cs.is_satisfied(&witness).unwrap());
+    assert!(cs.is_satisfied(&witness, &[]).unwrap());
     
     // Create instance and prove
     let params = LigeroParams::security_80();
     let instance = LigeroInstance::new(params, cs).unwrap();
     
     let prover = LigeroProver::new(instance.clone()).unwrap();
-    let proof = prover.prove(&witness, &mut OsRng).unwrap();
+    let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
     
     // Verify proof
     let verifier = LigeroVerifier::new(instance).unwrap();
-    assert!(verifier.verify(&proof).unwrap());
+    assert!(verifier.verify(&proof, &[]).unwrap());
 }
 
 #[test]
@@ -76,16 +77,16 @@ fn test_quadratic_constraints() {
         Fp128::from(60),
     ];
     
-    assert!(cs.is_satisfied(&witness).unwrap());
+    assert!(cs.is_satisfied(&witness, &[]).unwrap());
     
     let params = LigeroParams::security_80();
     let instance = LigeroInstance::new(params, cs).unwrap();
     
     let prover = LigeroProver::new(instance.clone()).unwrap();
-    let proof = prover.prove(&witness, &mut OsRng).unwrap();
+    let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
     
     let verifier = LigeroVerifier::new(instance).unwrap();
-    assert!(verifier.verify(&proof).unwrap());
+    assert!(verifier.verify(&proof, &[]).unwrap());
 }
 
 #[test]
@@ -109,16 +110,16 @@ fn test_mixed_constraints() {
         Fp128::from(6),
     ];
     
-    assert!(cs.is_satisfied(&witness).unwrap());
+    assert!(cs.is_satisfied(&witness, &[]).unwrap());
     
     let params = LigeroParams::security_128();
     let instance = LigeroInstance::new(params, cs).unwrap();
     
     let prover = LigeroProver::new(instance.clone()).unwrap();
-    let proof = prover.prove(&witness, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+    let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
     
     let verifier = LigeroVerifier::new(instance).unwrap();
-    assert!(verifier.verify(&proof).unwrap());
+    assert!(verifier.verify(&proof, &[]).unwrap());
 }
 
 #[test]
@@ -158,10 +159,42 @@ fn test_large_instance() {
     let instance = LigeroInstance::new(params, cs).unwrap();
     
     let prover = LigeroProver::new(instance.clone()).unwrap();
-    let proof = prover.prove(&witness, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+    let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
     
     let verifier = LigeroVerifier::new(instance).unwrap();
-    assert!(verifier.verify(&proof).unwrap());
+    assert!(verifier.verify(&proof, &[]).unwrap());
+}
+
+#[test]
+fn test_auto_params() {
+    // Neither count is a power of two, nor fits one block of the preset
+    let num_witnesses = 1500;
+    let mut cs = ConstraintSystem::<Fp128>::new(num_witnesses);
+    for i in 0..num_witnesses - 2 {
+        cs.add_linear_constraint(
+            vec![(i, Fp128::one()), (i + 1, Fp128::one()), (i + 2, -Fp128::one())],
+            Fp128::zero(),
+        );
+    }
+    for i in 0..300 {
+        cs.add_quadratic_constraint(i, 0, i);
+    }
+    
+    // w[0] = 1 and a Fibonacci-like chain, so w[i] * w[0] = w[i]
+    let mut witness = vec![Fp128::one(); num_witnesses];
+    for i in 2..num_witnesses {
+        witness[i] = witness[i - 1] + witness[i - 2];
+    }
+    assert!(cs.is_satisfied(&witness, &[]).unwrap());
+    
+    let instance = LigeroInstance::with_auto_params(LigeroParams::security_128(), cs).unwrap();
+    assert!(instance.params.num_witness_blocks(num_witnesses) > 1);
+    
+    let prover = LigeroProver::new(instance.clone()).unwrap();
+    let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+    
+    let verifier = LigeroVerifier::new(instance).unwrap();
+    assert!(verifier.verify(&proof, &[]).unwrap());
 }
 
 #[test]
@@ -182,7 +215,7 @@ fn test_soundness_invalid_witness() {
         Fp128::from(6),
     ];
     
-    assert!(!cs.is_satisfied(&invalid_witness).unwrap());
+    assert!(!cs.is_satisfied(&invalid_witness, &[]).unwrap());
     
     let params = LigeroParams::security_80();
     let instance = LigeroInstance::new(params, cs).unwrap();
@@ -190,7 +223,7 @@ fn test_soundness_invalid_witness() {
     let prover = LigeroProver::new(instance).unwrap();
     
     // Should fail to create proof with invalid witness
-    assert!(prover.prove(&invalid_witness, &mut OsRng).is_err());
+    assert!(prover.prove(&invalid_witness, &[], &mut OsRng).is_err());
 }
 
 #[test]
@@ -207,7 +240,7 @@ fn test_proof_tampering() {
     let instance = LigeroInstance::new(params, cs).unwrap();
     
     let prover = LigeroProver::new(instance.clone()).unwrap();
-    let mut proof = prover.prove(&witness, &mut OsRng).unwrap();
+    let mut proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
     
     // Tamper with the proof
     if !proof.linear_responses.is_empty() {
@@ -215,7 +248,7 @@ fn test_proof_tampering() {
     }
     
     let verifier = LigeroVerifier::new(instance).unwrap();
-    assert!(!verifier.verify(&proof).unwrap());
+    assert!(!verifier.verify(&proof, &[]).unwrap());
 }
 
 #[test]
@@ -244,10 +277,10 @@ fn test_different_security_levels() {
         let instance = LigeroInstance::new(params, cs.clone()).unwrap();
         
         let prover = LigeroProver::new(instance.clone()).unwrap();
-        let proof = prover.prove(&witness, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+        let proof = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
         
         let verifier = LigeroVerifier::new(instance).unwrap();
-        assert!(verifier.verify(&proof).unwrap());
+        assert!(verifier.verify(&proof, &[]).unwrap());
         
         // Higher security should have more column openings
         match security_bits {
@@ -273,8 +306,8 @@ fn test_zero_knowledge() {
     let witness1 = vec![Fp128::from(3), Fp128::from(7), Fp128::zero()];
     let witness2 = vec![Fp128::from(4), Fp128::from(6), Fp128::zero()];
     
-    assert!(cs.is_satisfied(&witness1).unwrap());
-    assert!(cs.is_satisfied(&witness2).unwrap());
+    assert!(cs.is_satisfied(&witness1, &[]).unwrap());
+    assert!(cs.is_satisfied(&witness2, &[]).unwrap());
     
     let params = LigeroParams::security_128();
     let instance = LigeroInstance::new(params, cs).unwrap();
@@ -282,14 +315,130 @@ fn test_zero_knowledge() {
     let prover = LigeroProver::new(instance.clone()).unwrap();
     
     // Generate proofs with different randomness
-    let proof1 = prover.prove(&witness1, &mut ChaCha20Rng::seed_from_u64(1)).unwrap();
-    let proof2 = prover.prove(&witness2, &mut ChaCha20Rng::seed_from_u64(2)).unwrap();
+    let proof1 = prover.prove(&witness1, &[], &mut ChaCha20Rng::seed_from_u64(1)).unwrap();
+    let proof2 = prover.prove(&witness2, &[], &mut ChaCha20Rng::seed_from_u64(2)).unwrap();
     
     // Both proofs should verify
     let verifier = LigeroVerifier::new(instance).unwrap();
-    assert!(verifier.verify(&proof1).unwrap());
-    assert!(verifier.verify(&proof2).unwrap());
+    assert!(verifier.verify(&proof1, &[]).unwrap());
+    assert!(verifier.verify(&proof2, &[]).unwrap());
     
     // Proofs should be different (due to randomness)
-    assert_ne!(proof1.column_roots, proof2.column_roots);
-}
\ No newline at end of file
+    assert_ne!(proof1.column_root, proof2.column_root);
+}
+#[test]
+fn test_blinding_hides_witness() {
+    let mut cs = ConstraintSystem::<Fp128>::new(4);
+    cs.add_linear_constraint(
+        vec![(0, Fp128::one()), (1, Fp128::one()), (2, -Fp128::one())],
+        Fp128::zero(),
+    );
+    cs.add_quadratic_constraint(0, 1, 3);
+    
+    let witness = vec![Fp128::from(2), Fp128::from(3), Fp128::from(5), Fp128::from(6)];
+    
+    let params = LigeroParams::security_80();
+    let block_size = params.block_size;
+    let instance = LigeroInstance::new(params, cs).unwrap();
+    let prover = LigeroProver::new(instance.clone()).unwrap();
+    let verifier = LigeroVerifier::new(instance).unwrap();
+    
+    // The same witness proved twice with fresh randomness
+    let proof1 = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(1)).unwrap();
+    let proof2 = prover.prove(&witness, &[], &mut ChaCha20Rng::seed_from_u64(2)).unwrap();
+    assert!(verifier.verify(&proof1, &[]).unwrap());
+    assert!(verifier.verify(&proof2, &[]).unwrap());
+    
+    // No opening reveals the systematic witness positions
+    for opening in proof1.column_openings.iter().chain(&proof2.column_openings) {
+        assert!(opening.index >= block_size);
+    }
+    
+    // Every opened value and every response value outside the checked
+    // witness block is freshly masked, so the two proofs agree only where
+    // the protocol fixes the value or with negligible probability
+    let opened = |proof: &LigeroProof<Fp128>| -> std::collections::HashMap<usize, Vec<Fp128>> {
+        proof.column_openings.iter().map(|o| (o.index, o.values.clone())).collect()
+    };
+    let (opened1, opened2) = (opened(&proof1), opened(&proof2));
+    for (index, values1) in &opened1 {
+        if let Some(values2) = opened2.get(index) {
+            assert!(values1.iter().zip(values2).all(|(a, b)| a != b));
+        }
+    }
+    assert!(proof1.ldt_responses[0].iter().zip(&proof2.ldt_responses[0]).all(|(a, b)| a != b));
+    assert!(proof1.linear_responses.iter().zip(&proof2.linear_responses).all(|(a, b)| a != b));
+    assert!(proof1.quadratic_responses[block_size..]
+        .iter()
+        .zip(&proof2.quadratic_responses[block_size..])
+        .all(|(a, b)| a != b));
+    
+    // The witness itself never appears among the opened values
+    for values in opened1.values() {
+        assert!(witness.iter().all(|w| !values.contains(w)));
+    }
+}
+
+#[test]
+fn test_gf2_128_subfield_packing() {
+    // Two full rows of GF(2^16) witnesses, then their products
+    let num_subfield = 256;
+    let num_products = 44;
+    let mut cs = ConstraintSystem::<Gf2_128>::new(num_subfield + num_products);
+    cs.set_subfield_boundary(num_subfield);
+    for j in 0..num_products {
+        cs.add_quadratic_constraint(j, j + 1, num_subfield + j);
+    }
+    
+    let mut witness: Vec<Gf2_128> = (0..num_subfield as u64)
+        .map(|i| Gf2_128::from_subfield(7 * i + 1))
+        .collect();
+    for j in 0..num_products {
+        witness.push(witness[j] * witness[j + 1]);
+    }
+    let last = num_subfield + num_products - 1;
+    cs.add_linear_constraint(
+        vec![(0, Gf2_128::one()), (last, Gf2_128::one())],
+        witness[0] + witness[last],
+    );
+    assert!(cs.is_satisfied(&witness, &[]).unwrap());
+    
+    let params = LigeroParams::binary_128();
+    let subfield_rows = params.subfield_rows(num_subfield);
+    assert_eq!(subfield_rows.len(), 2);
+    
+    let instance = LigeroInstance::new(params.clone(), cs.clone()).unwrap();
+    let prover = LigeroProver::new(instance.clone()).unwrap();
+    let verifier = LigeroVerifier::new(instance.clone()).unwrap();
+    let proof = prover.prove(&witness, &[], &mut OsRng).unwrap();
+    assert!(verifier.verify(&proof, &[]).unwrap());
+    
+    // Packed rows stay in the subfield at every opened column
+    for opening in &proof.column_openings {
+        assert!(opening.values[subfield_rows.clone()].iter().all(|v| v.to_subfield().is_some()));
+    }
+    
+    // and their opened entries take two bytes instead of sixteen
+    let bytes = proof.to_cpp_bytes(&instance).unwrap();
+    let height = proof.column_openings[0].values.len();
+    let depth = proof.column_openings[0].merkle_proof.len();
+    let t = params.num_col_openings;
+    let responses = params.row_message_size() + 2 * params.dot_message_size() - params.block_size;
+    let columns = t * (2 * subfield_rows.len() + 16 * (height - subfield_rows.len()));
+    assert_eq!(bytes.len(), 32 + 16 * responses + columns + 4 + 32 * t * depth);
+    let decoded = LigeroProof::from_cpp_bytes(&bytes, &instance, &[]).unwrap();
+    assert!(verifier.verify(&decoded, &[]).unwrap());
+    
+    // A witness outside the subfield below the boundary is rejected
+    let mut bad = witness.clone();
+    bad[1] = Gf2_128::new(2, 0);
+    bad[num_subfield] = bad[0] * bad[1];
+    assert!(!cs.is_satisfied(&bad, &[]).unwrap());
+    assert!(prover.prove(&bad, &[], &mut OsRng).is_err());
+    
+    // Packing needs a field with a subfield
+    let mut prime_cs = ConstraintSystem::<Fp128>::new(4);
+    assert!(LigeroInstance::new(params, prime_cs.clone()).is_err());
+    prime_cs.set_subfield_boundary(2);
+    assert!(LigeroInstance::new(LigeroParams::security_80(), prime_cs).is_err());
+}
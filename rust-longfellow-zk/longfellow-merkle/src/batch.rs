@@ -169,7 +169,7 @@ impl<H: Hasher> IncrementalMerkleTree<H> {
             } else {
                 // This is a right child, combine with stored left sibling
                 if let Some(left_sibling) = &self.right_edges[level] {
-                    current_hash = H::hash_pair(left_sibling, &current_hash);
+                    current_hash = H::hash_node(level, left_sibling, &current_hash);
                     self.right_edges[level] = None;
                 } else {
                     // No left sibling stored, shouldn't happen
@@ -201,7 +201,7 @@ impl<H: Hasher> IncrementalMerkleTree<H> {
             }
             
             // Update empty hash for next level
-            empty_hash = H::hash_pair(&empty_hash, &empty_hash);
+            empty_hash = H::hash_node(level, &empty_hash, &empty_hash);
         }
         
         // Combine roots from right to left
@@ -209,7 +209,7 @@ impl<H: Hasher> IncrementalMerkleTree<H> {
         
         for i in (0..roots.len() - 1).rev() {
             if self.leaves.len() & (1 << i) != 0 {
-                current_hash = H::hash_pair(&roots[i].1, &current_hash);
+                current_hash = H::hash_node(i, &roots[i].1, &current_hash);
             }
         }
         
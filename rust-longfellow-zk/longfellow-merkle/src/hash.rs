@@ -5,6 +5,7 @@ use sha2::Sha256;
 use sha3::{Sha3_256, Sha3_512};
 use blake3;
 use sha2::Digest;
+use crate::poseidon::Fp128PoseidonHasher;
 
 /// Supported hash functions
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -13,21 +14,73 @@ pub enum HashFunction {
     Sha3_256,
     Sha3_512,
     Blake3,
+    /// Poseidon over Fp128, for paths verified inside circuits
+    PoseidonFp128,
 }
 
+/// Version of the leaf/node encoding, recorded in every proof
+///
+/// Version 1 prefixes leaves with `LEAF_TAG` and internal nodes with
+/// `NODE_TAG` and the node's height, so a node can never be passed off as
+/// a leaf (or a node at another height) and roots differ from the
+/// unversioned encoding. Bump this whenever roots change.
+pub const MERKLE_HASH_VERSION: u8 = 1;
+
+/// Domain tag of leaf hashes
+pub const LEAF_TAG: u8 = 0x00;
+/// Domain tag of internal node hashes
+pub const NODE_TAG: u8 = 0x01;
+/// Domain tag of the padding leaf
+pub const EMPTY_TAG: u8 = 0x02;
+
 /// Trait for hash functions used in Merkle trees
+///
+/// Implementations supply `digest`; the tagged leaf, node and padding
+/// encodings are provided on top of it. A hasher that overrides them (as
+/// the Poseidon hasher does, working on field elements) must keep the
+/// three domains and the node heights separated.
 pub trait Hasher: Clone + Send + Sync {
     /// Output type of the hash function
     type Output: Clone + AsRef<[u8]> + PartialEq + Send + Sync + Serialize + for<'de> Deserialize<'de>;
     
-    /// Hash a leaf node
-    fn hash_leaf(data: &[u8]) -> Self::Output;
+    /// Hash the concatenation of `parts`
+    fn digest(parts: &[&[u8]]) -> Self::Output;
     
-    /// Hash two nodes together
-    fn hash_pair(left: &Self::Output, right: &Self::Output) -> Self::Output;
-    
-    /// Get empty hash (for padding)
-    fn empty_hash() -> Self::Output;
+    /// Hash a leaf: H(0x00 || data)
+    fn hash_leaf(data: &[u8]) -> Self::Output {
+        Self::digest(&[&[LEAF_TAG], data])
+    }
+    
+    /// Hash two children at `height` (0 when they are leaves):
+    /// H(0x01 || height as u32 LE || left || right)
+    fn hash_node(height: usize, left: &Self::Output, right: &Self::Output) -> Self::Output {
+        Self::digest(&[
+            &[NODE_TAG],
+            &(height as u32).to_le_bytes(),
+            left.as_ref(),
+            right.as_ref(),
+        ])
+    }
+    
+    /// Hash the children of a node in a wider tree:
+    /// H(0x01 || height as u32 LE || child_0 || ... || child_k-1)
+    ///
+    /// With two children this is `hash_node`, so a binary tree is the
+    /// arity-2 case. Digests have a fixed width, so nodes of different
+    /// arity cannot be confused.
+    fn hash_children(height: usize, children: &[Self::Output]) -> Self::Output {
+        let height = (height as u32).to_le_bytes();
+        let mut parts: Vec<&[u8]> = Vec::with_capacity(children.len() + 2);
+        parts.push(&[NODE_TAG]);
+        parts.push(&height);
+        parts.extend(children.iter().map(|c| c.as_ref()));
+        Self::digest(&parts)
+    }
+    
+    /// Padding leaf: H(0x02)
+    fn empty_hash() -> Self::Output {
+        Self::digest(&[&[EMPTY_TAG]])
+    }
 }
 
 /// SHA-256 hasher implementation
@@ -37,24 +90,11 @@ pub struct Sha256Hasher;
 impl Hasher for Sha256Hasher {
     type Output = [u8; 32];
     
-    fn hash_leaf(data: &[u8]) -> Self::Output {
-        let mut hasher = Sha256::new();
-        hasher.update(b"leaf:");
-        hasher.update(data);
-        hasher.finalize().into()
-    }
-    
-    fn hash_pair(left: &Self::Output, right: &Self::Output) -> Self::Output {
-        let mut hasher = Sha256::new();
-        hasher.update(b"node:");
-        hasher.update(left);
-        hasher.update(right);
-        hasher.finalize().into()
-    }
-    
-    fn empty_hash() -> Self::Output {
+    fn digest(parts: &[&[u8]]) -> Self::Output {
         let mut hasher = Sha256::new();
-        hasher.update(b"empty");
+        for part in parts {
+            hasher.update(part);
+        }
         hasher.finalize().into()
     }
 }
@@ -66,25 +106,11 @@ pub struct Sha3_256Hasher;
 impl Hasher for Sha3_256Hasher {
     type Output = [u8; 32];
     
-    fn hash_leaf(data: &[u8]) -> Self::Output {
+    fn digest(parts: &[&[u8]]) -> Self::Output {
         let mut hasher = Sha3_256::new();
-        hasher.update(b"leaf:");
-        hasher.update(&(data.len() as u64).to_le_bytes());
-        hasher.update(data);
-        hasher.finalize().into()
-    }
-    
-    fn hash_pair(left: &Self::Output, right: &Self::Output) -> Self::Output {
-        let mut hasher = Sha3_256::new();
-        hasher.update(b"node:");
-        hasher.update(left);
-        hasher.update(right);
-        hasher.finalize().into()
-    }
-    
-    fn empty_hash() -> Self::Output {
-        let mut hasher = Sha3_256::new();
-        hasher.update(b"empty");
+        for part in parts {
+            hasher.update(part);
+        }
         hasher.finalize().into()
     }
 }
@@ -96,25 +122,11 @@ pub struct Sha3_512Hasher;
 impl Hasher for Sha3_512Hasher {
     type Output = Vec<u8>;
     
-    fn hash_leaf(data: &[u8]) -> Self::Output {
-        let mut hasher = Sha3_512::new();
-        hasher.update(b"leaf:");
-        hasher.update(&(data.len() as u64).to_le_bytes());
-        hasher.update(data);
-        hasher.finalize().to_vec()
-    }
-    
-    fn hash_pair(left: &Self::Output, right: &Self::Output) -> Self::Output {
+    fn digest(parts: &[&[u8]]) -> Self::Output {
         let mut hasher = Sha3_512::new();
-        hasher.update(b"node:");
-        hasher.update(left);
-        hasher.update(right);
-        hasher.finalize().to_vec()
-    }
-    
-    fn empty_hash() -> Self::Output {
-        let mut hasher = Sha3_512::new();
-        hasher.update(b"empty");
+        for part in parts {
+            hasher.update(part);
+        }
         hasher.finalize().to_vec()
     }
 }
@@ -126,24 +138,11 @@ pub struct Blake3Hasher;
 impl Hasher for Blake3Hasher {
     type Output = [u8; 32];
     
-    fn hash_leaf(data: &[u8]) -> Self::Output {
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(b"leaf:");
-        hasher.update(data);
-        *hasher.finalize().as_bytes()
-    }
-    
-    fn hash_pair(left: &Self::Output, right: &Self::Output) -> Self::Output {
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(b"node:");
-        hasher.update(left);
-        hasher.update(right);
-        *hasher.finalize().as_bytes()
-    }
-    
-    fn empty_hash() -> Self::Output {
+    fn digest(parts: &[&[u8]]) -> Self::Output {
         let mut hasher = blake3::Hasher::new();
-        hasher.update(b"empty");
+        for part in parts {
+            hasher.update(part);
+        }
         *hasher.finalize().as_bytes()
     }
 }
@@ -155,6 +154,7 @@ pub enum DynamicHasher {
     Sha3_256,
     Sha3_512,
     Blake3,
+    PoseidonFp128,
 }
 
 impl Default for DynamicHasher {
@@ -170,6 +170,7 @@ impl DynamicHasher {
             HashFunction::Sha3_256 => DynamicHasher::Sha3_256,
             HashFunction::Sha3_512 => DynamicHasher::Sha3_512,
             HashFunction::Blake3 => DynamicHasher::Blake3,
+            HashFunction::PoseidonFp128 => DynamicHasher::PoseidonFp128,
         }
     }
     
@@ -179,34 +180,38 @@ impl DynamicHasher {
             DynamicHasher::Sha3_256 => Sha3_256Hasher::hash_leaf(data).to_vec(),
             DynamicHasher::Sha3_512 => Sha3_512Hasher::hash_leaf(data),
             DynamicHasher::Blake3 => Blake3Hasher::hash_leaf(data).to_vec(),
+            DynamicHasher::PoseidonFp128 => Fp128PoseidonHasher::hash_leaf(data),
         }
     }
     
-    pub fn hash_pair(&self, left: &[u8], right: &[u8]) -> Vec<u8> {
+    pub fn hash_node(&self, height: usize, left: &[u8], right: &[u8]) -> Vec<u8> {
         match self {
             DynamicHasher::Sha256 => {
                 let mut left_arr = [0u8; 32];
                 let mut right_arr = [0u8; 32];
                 left_arr.copy_from_slice(&left[..32.min(left.len())]);
                 right_arr.copy_from_slice(&right[..32.min(right.len())]);
-                Sha256Hasher::hash_pair(&left_arr, &right_arr).to_vec()
+                Sha256Hasher::hash_node(height, &left_arr, &right_arr).to_vec()
             }
             DynamicHasher::Sha3_256 => {
                 let mut left_arr = [0u8; 32];
                 let mut right_arr = [0u8; 32];
                 left_arr.copy_from_slice(&left[..32.min(left.len())]);
                 right_arr.copy_from_slice(&right[..32.min(right.len())]);
-                Sha3_256Hasher::hash_pair(&left_arr, &right_arr).to_vec()
+                Sha3_256Hasher::hash_node(height, &left_arr, &right_arr).to_vec()
             }
             DynamicHasher::Sha3_512 => {
-                Sha3_512Hasher::hash_pair(&left.to_vec(), &right.to_vec())
+                Sha3_512Hasher::hash_node(height, &left.to_vec(), &right.to_vec())
             }
             DynamicHasher::Blake3 => {
                 let mut left_arr = [0u8; 32];
                 let mut right_arr = [0u8; 32];
                 left_arr.copy_from_slice(&left[..32.min(left.len())]);
                 right_arr.copy_from_slice(&right[..32.min(right.len())]);
-                Blake3Hasher::hash_pair(&left_arr, &right_arr).to_vec()
+                Blake3Hasher::hash_node(height, &left_arr, &right_arr).to_vec()
+            }
+            DynamicHasher::PoseidonFp128 => {
+                Fp128PoseidonHasher::hash_node(height, &left.to_vec(), &right.to_vec())
             }
         }
     }
@@ -217,6 +222,7 @@ impl DynamicHasher {
             DynamicHasher::Sha3_256 => Sha3_256Hasher::empty_hash().to_vec(),
             DynamicHasher::Sha3_512 => Sha3_512Hasher::empty_hash(),
             DynamicHasher::Blake3 => Blake3Hasher::empty_hash().to_vec(),
+            DynamicHasher::PoseidonFp128 => Fp128PoseidonHasher::empty_hash(),
         }
     }
 }
@@ -0,0 +1,218 @@
+//! Merkle trees of arity 4, 8 and beyond
+//!
+//! Each internal node hashes `arity` children with `Hasher::hash_children`,
+//! so a tree over n leaves has log_arity(n) levels. Proofs carry arity - 1
+//! siblings per level: more bytes than a binary path for byte-oriented
+//! hashes, but fewer hash invocations, which is what counts when the path
+//! is checked inside a circuit with a multi-input Poseidon sponge. With
+//! arity 2 the root equals `MerkleTree`'s.
+
+use crate::{Hasher, MERKLE_HASH_VERSION};
+use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Merkle tree with a configurable number of children per node
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KaryMerkleTree<H: Hasher> {
+    /// Tree nodes organized by level (leaves at index 0)
+    nodes: Vec<Vec<H::Output>>,
+    /// Number of leaves
+    num_leaves: usize,
+    /// Children per internal node
+    arity: usize,
+    #[serde(skip)]
+    _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> KaryMerkleTree<H> {
+    /// Build a tree over `data`; leaves are padded with the empty hash to
+    /// a power of `arity`
+    pub fn new<T: AsRef<[u8]> + Sync>(data: &[T], arity: usize) -> Result<Self> {
+        if data.is_empty() {
+            return Err(LongfellowError::InvalidParameter(
+                "Cannot create Merkle tree with no data".to_string()
+            ));
+        }
+        if arity < 2 {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Merkle tree arity must be at least 2, got {}",
+                arity
+            )));
+        }
+
+        let num_leaves = data.len();
+        let mut tree_size = 1;
+        while tree_size < num_leaves {
+            tree_size *= arity;
+        }
+
+        let mut leaves: Vec<H::Output> = data
+            .par_iter()
+            .map(|item| H::hash_leaf(item.as_ref()))
+            .collect();
+        leaves.resize(tree_size, H::empty_hash());
+
+        let mut nodes = vec![leaves];
+        while nodes[nodes.len() - 1].len() > 1 {
+            let height = nodes.len() - 1;
+            let level: Vec<H::Output> = nodes[height]
+                .par_chunks(arity)
+                .map(|children| H::hash_children(height, children))
+                .collect();
+            nodes.push(level);
+        }
+
+        Ok(Self {
+            nodes,
+            num_leaves,
+            arity,
+            _hasher: PhantomData,
+        })
+    }
+
+    pub fn root(&self) -> &H::Output {
+        &self.nodes[self.nodes.len() - 1][0]
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Number of levels including the leaves and the root
+    pub fn height(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Proof for the leaf at `index`
+    pub fn prove(&self, index: usize) -> Result<KaryMerkleProof<H>> {
+        if index >= self.num_leaves {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Leaf index {} out of range", index)
+            ));
+        }
+
+        let mut siblings = Vec::with_capacity((self.height() - 1) * (self.arity - 1));
+        let mut current_index = index;
+        for level in &self.nodes[..self.nodes.len() - 1] {
+            let first = current_index - current_index % self.arity;
+            for i in (first..first + self.arity).filter(|&i| i != current_index) {
+                siblings.push(level[i].clone());
+            }
+            current_index /= self.arity;
+        }
+
+        Ok(KaryMerkleProof {
+            version: MERKLE_HASH_VERSION,
+            leaf_index: index,
+            arity: self.arity,
+            siblings,
+            _hasher: PhantomData,
+        })
+    }
+}
+
+/// Path from a leaf of a `KaryMerkleTree` to its root
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KaryMerkleProof<H: Hasher> {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
+    /// Index of the leaf being proven
+    pub leaf_index: usize,
+    /// Children per internal node
+    pub arity: usize,
+    /// arity - 1 siblings per level from leaf to root, in child order with
+    /// the path's own position skipped
+    pub siblings: Vec<H::Output>,
+    #[serde(skip)]
+    pub _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> KaryMerkleProof<H> {
+    /// Verify this proof against a root hash
+    pub fn verify(&self, root: &H::Output, leaf_data: &[u8]) -> bool {
+        if self.version != MERKLE_HASH_VERSION
+            || self.arity < 2
+            || !self.siblings.len().is_multiple_of(self.arity - 1)
+        {
+            return false;
+        }
+
+        let mut current_hash = H::hash_leaf(leaf_data);
+        let mut current_index = self.leaf_index;
+        let mut children = Vec::with_capacity(self.arity);
+
+        for (height, level) in self.siblings.chunks(self.arity - 1).enumerate() {
+            let position = current_index % self.arity;
+            children.clear();
+            children.extend_from_slice(&level[..position]);
+            children.push(current_hash);
+            children.extend_from_slice(&level[position..]);
+            current_hash = H::hash_children(height, &children);
+            current_index /= self.arity;
+        }
+
+        current_index == 0 && &current_hash == root
+    }
+
+    /// Get the size of this proof in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.siblings.len() * std::mem::size_of::<H::Output>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::Sha256Hasher;
+    use crate::{Fp128PoseidonHasher, MerkleTree};
+
+    #[test]
+    fn test_kary_proofs() {
+        let data: Vec<Vec<u8>> = (0..50u8).map(|i| vec![i; 4]).collect();
+
+        for arity in [2, 4, 8] {
+            let tree = KaryMerkleTree::<Sha256Hasher>::new(&data, arity).unwrap();
+            for i in [0, 7, 31, 49] {
+                let proof = tree.prove(i).unwrap();
+                assert_eq!(proof.siblings.len(), (tree.height() - 1) * (arity - 1));
+                assert!(proof.verify(tree.root(), &data[i]));
+                assert!(!proof.verify(tree.root(), &data[(i + 1) % 50]));
+            }
+        }
+
+        assert_eq!(KaryMerkleTree::<Sha256Hasher>::new(&data, 4).unwrap().height(), 4);
+        assert_eq!(KaryMerkleTree::<Sha256Hasher>::new(&data, 8).unwrap().height(), 3);
+        assert!(KaryMerkleTree::<Sha256Hasher>::new(&data, 1).is_err());
+    }
+
+    #[test]
+    fn test_binary_case_matches_merkle_tree() {
+        let data: Vec<Vec<u8>> = (0..11u8).map(|i| vec![i]).collect();
+        let binary = MerkleTree::<Fp128PoseidonHasher>::new(&data).unwrap();
+        let kary = KaryMerkleTree::<Fp128PoseidonHasher>::new(&data, 2).unwrap();
+        assert_eq!(binary.root(), kary.root());
+
+        let quad = KaryMerkleTree::<Fp128PoseidonHasher>::new(&data, 4).unwrap();
+        assert_ne!(quad.root(), kary.root());
+        assert!(quad.prove(10).unwrap().verify(quad.root(), &data[10]));
+    }
+
+    #[test]
+    fn test_proof_bound_to_arity() {
+        let data: Vec<Vec<u8>> = (0..16u8).map(|i| vec![i]).collect();
+        let tree = KaryMerkleTree::<Sha256Hasher>::new(&data, 4).unwrap();
+        let mut proof = tree.prove(5).unwrap();
+        proof.arity = 2;
+        assert!(!proof.verify(tree.root(), &data[5]));
+        proof.arity = 4;
+        proof.leaf_index = 21;
+        assert!(!proof.verify(tree.root(), &data[5]));
+    }
+}
@@ -0,0 +1,401 @@
+//! KZG vector commitment over BLS12-381
+//!
+//! Values are hashed to scalars y_i and committed as the polynomial p of
+//! degree < n with p(ω^i) = y_i, where ω generates the power-of-two domain
+//! covering n values. Opening a set S of positions sends one G1 point
+//!
+//! ```text
+//! π = [q(τ)]₁,  q = (p - I_S) / Z_S
+//! ```
+//!
+//! where I_S interpolates the opened values over S and Z_S vanishes on S;
+//! the verifier checks e(C - [I_S(τ)]₁, g₂) = e(π, [Z_S(τ)]₂). Commitments
+//! and openings are 48 bytes whatever the vector length or the number of
+//! opened positions, against a Merkle multi-proof that grows with both.
+//!
+//! The scheme is only as sound as its setup: τ must be unknown to the
+//! prover, so production keys come from a powers-of-tau ceremony via
+//! `KzgSetup::from_powers`.
+
+use crate::vector::VectorCommitment;
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use ff::{Field, PrimeField};
+use longfellow_core::{LongfellowError, Result};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha512};
+use std::sync::Arc;
+
+/// Structured reference string: powers of τ in G1 and G2
+#[derive(Clone, Debug)]
+pub struct KzgSetup {
+    /// [τ^i]₁ for i < the largest committable domain
+    g1_powers: Vec<G1Affine>,
+    /// [τ^i]₂ for i ≤ the largest number of positions per opening
+    g2_powers: Vec<G2Affine>,
+}
+
+impl KzgSetup {
+    /// Setup from ceremony output; `g1_powers[i]` and `g2_powers[i]` must
+    /// both be the generator times τ^i
+    pub fn from_powers(g1_powers: Vec<G1Affine>, g2_powers: Vec<G2Affine>) -> Result<Self> {
+        if g1_powers.is_empty() || g2_powers.len() < 2 {
+            return Err(LongfellowError::InvalidParameter(
+                "KZG setup needs at least one G1 and two G2 powers".to_string()
+            ));
+        }
+        Ok(Self { g1_powers, g2_powers })
+    }
+
+    /// Setup with a locally sampled τ, for tests and benchmarks only:
+    /// whoever runs this can open commitments to anything
+    ///
+    /// Supports vectors of up to `max_len` values and openings of up to
+    /// `max_openings` positions.
+    pub fn insecure_from_rng<R: RngCore + CryptoRng>(
+        max_len: usize,
+        max_openings: usize,
+        rng: &mut R,
+    ) -> Self {
+        let tau = Scalar::random(&mut *rng);
+        let g1_len = max_len.max(1).next_power_of_two();
+        let g1_powers = powers(tau, g1_len)
+            .map(|t| G1Affine::from(G1Projective::generator() * t))
+            .collect();
+        let g2_powers = powers(tau, max_openings.max(1) + 1)
+            .map(|t| G2Affine::from(G2Projective::generator() * t))
+            .collect();
+        Self { g1_powers, g2_powers }
+    }
+
+    /// Largest number of values one commitment can hold
+    pub fn max_len(&self) -> usize {
+        // Domains are powers of two
+        1 << self.g1_powers.len().ilog2()
+    }
+
+    /// Largest number of positions one opening can cover
+    pub fn max_openings(&self) -> usize {
+        (self.g2_powers.len() - 1).min(self.g1_powers.len())
+    }
+}
+
+/// KZG commitment to a vector
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KzgCommitment {
+    pub point: G1Affine,
+    /// Vector length, which fixes the evaluation domain
+    pub len: usize,
+}
+
+/// Opening proof for any number of positions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KzgOpening {
+    pub proof: G1Affine,
+}
+
+/// Committed polynomial, kept by the prover to answer openings
+#[derive(Clone, Debug)]
+pub struct KzgProverData {
+    coeffs: Vec<Scalar>,
+    len: usize,
+}
+
+/// KZG scheme bound to a setup
+#[derive(Clone, Debug)]
+pub struct KzgVectorCommitment {
+    setup: Arc<KzgSetup>,
+}
+
+impl KzgVectorCommitment {
+    pub fn new(setup: Arc<KzgSetup>) -> Self {
+        Self { setup }
+    }
+
+    pub fn setup(&self) -> &KzgSetup {
+        &self.setup
+    }
+}
+
+impl VectorCommitment for KzgVectorCommitment {
+    type Commitment = KzgCommitment;
+    type Opening = KzgOpening;
+    type ProverData = KzgProverData;
+
+    fn commit<T: AsRef<[u8]> + Sync>(&self, values: &[T]) -> Result<(KzgCommitment, KzgProverData)> {
+        if values.is_empty() || values.len() > self.setup.max_len() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "KZG setup commits to 1 to {} values, got {}",
+                self.setup.max_len(),
+                values.len()
+            )));
+        }
+
+        let size = values.len().next_power_of_two();
+        let mut coeffs: Vec<Scalar> = values.iter().map(|v| value_scalar(v.as_ref())).collect();
+        coeffs.resize(size, Scalar::ZERO);
+        interpolate_domain(&mut coeffs);
+
+        let commitment = KzgCommitment {
+            point: G1Affine::from(commit_g1(&self.setup.g1_powers, &coeffs)),
+            len: values.len(),
+        };
+        Ok((commitment, KzgProverData { coeffs, len: values.len() }))
+    }
+
+    fn open(&self, data: &KzgProverData, indices: &[usize]) -> Result<KzgOpening> {
+        let points = opening_points(data.len, indices, self.setup.max_openings()).ok_or_else(|| {
+            LongfellowError::InvalidParameter(format!(
+                "Cannot open positions {:?} of {} with at most {} per opening",
+                indices,
+                data.len,
+                self.setup.max_openings()
+            ))
+        })?;
+
+        // p - I_S vanishes on S, so Z_S divides it exactly
+        let values: Vec<Scalar> = points.iter().map(|&x| evaluate(&data.coeffs, x)).collect();
+        let mut numerator = data.coeffs.clone();
+        for (c, i) in numerator.iter_mut().zip(interpolate(&points, &values)) {
+            *c -= i;
+        }
+        let quotient = divide_exact(&numerator, &vanishing(&points));
+
+        Ok(KzgOpening {
+            proof: G1Affine::from(commit_g1(&self.setup.g1_powers, &quotient)),
+        })
+    }
+
+    fn verify(
+        &self,
+        commitment: &KzgCommitment,
+        indices: &[usize],
+        values: &[&[u8]],
+        opening: &KzgOpening,
+    ) -> bool {
+        if indices.len() != values.len() {
+            return false;
+        }
+        let Some(points) = opening_points(commitment.len, indices, self.setup.max_openings()) else {
+            return false;
+        };
+
+        let ys: Vec<Scalar> = values.iter().map(|v| value_scalar(v)).collect();
+        let interpolant = commit_g1(&self.setup.g1_powers, &interpolate(&points, &ys));
+        let zero = commit_g2(&self.setup.g2_powers, &vanishing(&points));
+
+        let shifted = G1Affine::from(G1Projective::from(commitment.point) - interpolant);
+        let lhs = pairing(&shifted, &G2Affine::generator());
+        let rhs = pairing(&opening.proof, &G2Affine::from(zero));
+        lhs == rhs
+    }
+}
+
+/// Scalar a value is committed as
+fn value_scalar(value: &[u8]) -> Scalar {
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&Sha512::digest(value));
+    Scalar::from_bytes_wide(&wide)
+}
+
+fn powers(base: Scalar, count: usize) -> impl Iterator<Item = Scalar> {
+    std::iter::successors(Some(Scalar::ONE), move |p| Some(p * base)).take(count)
+}
+
+/// Generator of the multiplicative subgroup of order `size`, a power of two
+fn domain_generator(size: usize) -> Scalar {
+    let log_size = size.trailing_zeros();
+    Scalar::ROOT_OF_UNITY.pow_vartime(&[1u64 << (Scalar::S - log_size), 0, 0, 0])
+}
+
+/// Domain points of `indices` in a vector of `len` values, or None if the
+/// positions are repeated, out of range or too many for the setup
+fn opening_points(len: usize, indices: &[usize], max_openings: usize) -> Option<Vec<Scalar>> {
+    if len == 0 || indices.is_empty() || indices.len() > max_openings {
+        return None;
+    }
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|w| w[0] == w[1]) || sorted[sorted.len() - 1] >= len {
+        return None;
+    }
+
+    let omega = domain_generator(len.next_power_of_two());
+    Some(indices.iter().map(|&i| omega.pow_vartime(&[i as u64, 0, 0, 0])).collect())
+}
+
+/// Turn evaluations over the domain of size `values.len()` into
+/// coefficients, in place
+fn interpolate_domain(values: &mut [Scalar]) {
+    let n = values.len();
+    if n == 1 {
+        return;
+    }
+    let omega_inv = domain_generator(n).invert().unwrap();
+
+    // Bit-reversal permutation, then iterative radix-2 butterflies
+    let shift = usize::BITS - n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let mut half = 1;
+    while half < n {
+        let step = omega_inv.pow_vartime(&[(n / (2 * half)) as u64, 0, 0, 0]);
+        for chunk in values.chunks_mut(2 * half) {
+            let mut w = Scalar::ONE;
+            for k in 0..half {
+                let t = chunk[k + half] * w;
+                chunk[k + half] = chunk[k] - t;
+                chunk[k] += t;
+                w *= step;
+            }
+        }
+        half *= 2;
+    }
+
+    let n_inv = Scalar::from(n as u64).invert().unwrap();
+    for v in values.iter_mut() {
+        *v *= n_inv;
+    }
+}
+
+fn evaluate(coeffs: &[Scalar], x: Scalar) -> Scalar {
+    coeffs.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c)
+}
+
+/// Coefficients of ∏ (X - x) over `points`
+fn vanishing(points: &[Scalar]) -> Vec<Scalar> {
+    let mut coeffs = vec![Scalar::ONE];
+    for &x in points {
+        let mut next = vec![Scalar::ZERO; coeffs.len() + 1];
+        for (i, c) in coeffs.iter().enumerate() {
+            next[i + 1] += c;
+            next[i] -= x * c;
+        }
+        coeffs = next;
+    }
+    coeffs
+}
+
+/// Lagrange interpolation through distinct `points`
+fn interpolate(points: &[Scalar], values: &[Scalar]) -> Vec<Scalar> {
+    let zero = vanishing(points);
+    let mut result = vec![Scalar::ZERO; points.len()];
+    for (&x, &y) in points.iter().zip(values) {
+        let basis = divide_exact(&zero, &[-x, Scalar::ONE]);
+        let scale = y * evaluate(&basis, x).invert().unwrap();
+        for (r, b) in result.iter_mut().zip(&basis) {
+            *r += scale * b;
+        }
+    }
+    result
+}
+
+/// Quotient of `numerator` by a monic `divisor` that divides it
+fn divide_exact(numerator: &[Scalar], divisor: &[Scalar]) -> Vec<Scalar> {
+    let d = divisor.len() - 1;
+    if numerator.len() <= d {
+        return Vec::new();
+    }
+    let mut remainder = numerator.to_vec();
+    let mut quotient = vec![Scalar::ZERO; numerator.len() - d];
+    for i in (0..quotient.len()).rev() {
+        let q = remainder[i + d];
+        quotient[i] = q;
+        for (j, c) in divisor.iter().enumerate() {
+            remainder[i + j] -= q * c;
+        }
+    }
+    quotient
+}
+
+fn commit_g1(powers: &[G1Affine], coeffs: &[Scalar]) -> G1Projective {
+    powers.iter().zip(coeffs).map(|(p, c)| p * c).sum()
+}
+
+fn commit_g2(powers: &[G2Affine], coeffs: &[Scalar]) -> G2Projective {
+    powers.iter().zip(coeffs).map(|(p, c)| p * c).sum()
+}
+
+fn g1_from_bytes<E: serde::de::Error>(bytes: Vec<u8>) -> std::result::Result<G1Affine, E> {
+    let bytes: [u8; 48] = bytes
+        .try_into()
+        .map_err(|_| E::custom("G1 point must be 48 bytes"))?;
+    Option::from(G1Affine::from_compressed(&bytes)).ok_or_else(|| E::custom("invalid G1 point"))
+}
+
+impl Serialize for KzgCommitment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        (self.point.to_compressed().to_vec(), self.len as u64).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KzgCommitment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let (bytes, len) = <(Vec<u8>, u64)>::deserialize(deserializer)?;
+        Ok(Self {
+            point: g1_from_bytes(bytes)?,
+            len: len as usize,
+        })
+    }
+}
+
+impl Serialize for KzgOpening {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.proof.to_compressed().to_vec().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KzgOpening {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Self {
+            proof: g1_from_bytes(Vec::<u8>::deserialize(deserializer)?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kzg_open_and_verify() {
+        let setup = KzgSetup::insecure_from_rng(16, 4, &mut rand::thread_rng());
+        let scheme = KzgVectorCommitment::new(Arc::new(setup));
+
+        let values: Vec<Vec<u8>> = (0..11u8).map(|i| vec![i; 5]).collect();
+        let (commitment, data) = scheme.commit(&values).unwrap();
+
+        for indices in [vec![0], vec![10, 3], vec![1, 4, 9, 2]] {
+            let opened: Vec<&[u8]> = indices.iter().map(|&i| values[i].as_slice()).collect();
+            let opening = scheme.open(&data, &indices).unwrap();
+            assert!(scheme.verify(&commitment, &indices, &opened, &opening));
+
+            let mut forged = opened.clone();
+            forged[0] = b"forged";
+            assert!(!scheme.verify(&commitment, &indices, &forged, &opening));
+        }
+
+        assert!(scheme.open(&data, &[11]).is_err());
+        assert!(scheme.open(&data, &[1, 1]).is_err());
+        assert!(scheme.open(&data, &[0, 1, 2, 3, 4]).is_err());
+        assert!(scheme.commit(&vec![vec![0u8]; 17]).is_err());
+    }
+
+    #[test]
+    fn test_vanishing_and_interpolation() {
+        let points: Vec<Scalar> = (1..5u64).map(Scalar::from).collect();
+        let zero = vanishing(&points);
+        assert_eq!(zero.len(), 5);
+        assert!(points.iter().all(|&x| evaluate(&zero, x) == Scalar::ZERO));
+
+        let values: Vec<Scalar> = (10..14u64).map(Scalar::from).collect();
+        let poly = interpolate(&points, &values);
+        for (x, y) in points.iter().zip(&values) {
+            assert_eq!(evaluate(&poly, *x), *y);
+        }
+    }
+}
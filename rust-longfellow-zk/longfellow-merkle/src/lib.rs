@@ -3,24 +3,57 @@
 use longfellow_core::{LongfellowError, Result};
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
+use std::ops::Range;
 use rayon::prelude::*;
+use rand::{CryptoRng, RngCore};
 
 pub mod hash;
 pub mod proof;
 pub mod batch;
+pub mod kary;
+#[cfg(feature = "pairing")]
+pub mod kzg;
+pub mod mmr;
+pub mod poseidon;
+pub mod sparse;
+pub mod store;
+pub mod stream;
+pub mod vector;
 
-pub use hash::{Hasher, HashFunction, DynamicHasher};
-pub use proof::{MerkleProof, MultiProof, DynamicMerkleProof};
+pub use hash::{Hasher, HashFunction, DynamicHasher, MERKLE_HASH_VERSION};
+pub use proof::{MerkleProof, MultiProof, RangeProof, DynamicMerkleProof, UpdateProof};
 pub use batch::BatchMerkleTree;
+pub use kary::{KaryMerkleTree, KaryMerkleProof};
+pub use mmr::{MerkleMountainRange, MmrProof};
+pub use sparse::{SparseMerkleTree, SparseMerkleProof, SparseKey};
+pub use store::StoredMerkleTree;
+pub use stream::StreamingMerkleBuilder;
+pub use vector::{VectorCommitment, MerkleVectorCommitment};
+#[cfg(feature = "pairing")]
+pub use kzg::{KzgSetup, KzgVectorCommitment, KzgCommitment, KzgOpening};
+pub use poseidon::{PoseidonHasher, PoseidonConfig, PoseidonParams, Fp128PoseidonHasher};
+
+/// Per-leaf randomness of a hiding tree
+pub type LeafSalt = [u8; 32];
+
+/// Leaf hash of a hiding tree: the salt is a fixed-width prefix of the
+/// leaf data, so the encoding stays injective
+pub fn hiding_leaf_hash<H: Hasher>(salt: &LeafSalt, data: &[u8]) -> H::Output {
+    H::hash_leaf(&[salt.as_slice(), data].concat())
+}
 
 /// Generic Merkle tree implementation
-#[derive(Clone, Debug)]
+///
+/// Serializes with serde as its full node list; see `store` for a compact
+/// binary format that can be reopened without loading every level.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MerkleTree<H: Hasher> {
     /// Tree nodes organized by level (leaves at index 0)
     nodes: Vec<Vec<H::Output>>,
     /// Number of leaves
     num_leaves: usize,
     /// Phantom data for hasher type
+    #[serde(skip)]
     _hasher: PhantomData<H>,
 }
 
@@ -33,15 +66,55 @@ impl<H: Hasher> MerkleTree<H> {
             ));
         }
         
-        let num_leaves = data.len();
-        let tree_size = num_leaves.next_power_of_two();
-        
         // Compute leaf hashes
-        let mut leaves: Vec<H::Output> = data
+        let leaves: Vec<H::Output> = data
             .par_iter()
             .map(|item| H::hash_leaf(item.as_ref()))
             .collect();
         
+        Ok(Self::from_leaf_hashes(leaves))
+    }
+    
+    /// Create a hiding tree: each leaf is hashed with a fresh random salt,
+    /// so the root and sibling hashes reveal nothing about unopened leaves,
+    /// however low their entropy
+    ///
+    /// Returns the tree and the salts; opening leaf i reveals `salts[i]`
+    /// alongside the data, checked with `MerkleProof::verify_hiding`.
+    pub fn new_hiding<T, R>(data: &[T], rng: &mut R) -> Result<(Self, Vec<LeafSalt>)>
+    where
+        T: AsRef<[u8]> + Sync,
+        R: RngCore + CryptoRng,
+    {
+        if data.is_empty() {
+            return Err(LongfellowError::InvalidParameter(
+                "Cannot create Merkle tree with no data".to_string()
+            ));
+        }
+        
+        let salts: Vec<LeafSalt> = data
+            .iter()
+            .map(|_| {
+                let mut salt = [0u8; 32];
+                rng.fill_bytes(&mut salt);
+                salt
+            })
+            .collect();
+        
+        let leaves: Vec<H::Output> = data
+            .par_iter()
+            .zip(salts.par_iter())
+            .map(|(item, salt)| hiding_leaf_hash::<H>(salt, item.as_ref()))
+            .collect();
+        
+        Ok((Self::from_leaf_hashes(leaves), salts))
+    }
+    
+    /// Build the tree above `leaves`, padding with empty hashes
+    fn from_leaf_hashes(mut leaves: Vec<H::Output>) -> Self {
+        let num_leaves = leaves.len();
+        let tree_size = num_leaves.next_power_of_two();
+        
         // Pad with empty hashes if needed
         let empty_hash = H::empty_hash();
         leaves.resize(tree_size, empty_hash);
@@ -52,23 +125,24 @@ impl<H: Hasher> MerkleTree<H> {
         let mut level_size = tree_size;
         while level_size > 1 {
             level_size /= 2;
-            let prev_level = &nodes[nodes.len() - 1];
+            let height = nodes.len() - 1;
+            let prev_level = &nodes[height];
             
             let level: Vec<H::Output> = (0..level_size)
                 .into_par_iter()
                 .map(|i| {
-                    H::hash_pair(&prev_level[2 * i], &prev_level[2 * i + 1])
+                    H::hash_node(height, &prev_level[2 * i], &prev_level[2 * i + 1])
                 })
                 .collect();
             
             nodes.push(level);
         }
         
-        Ok(Self {
+        Self {
             nodes,
             num_leaves,
             _hasher: PhantomData,
-        })
+        }
     }
     
     /// Get the root hash
@@ -105,12 +179,18 @@ impl<H: Hasher> MerkleTree<H> {
         }
         
         Ok(MerkleProof {
+            version: MERKLE_HASH_VERSION,
             leaf_index: index,
             siblings,
             _hasher: PhantomData,
         })
     }
     
+    /// Prove the contiguous leaves in `range` with a single proof
+    pub fn prove_range(&self, range: Range<usize>) -> Result<RangeProof<H>> {
+        RangeProof::create(self, range)
+    }
+    
     /// Generate multiple proofs efficiently
     pub fn prove_batch(&self, indices: &[usize]) -> Result<MultiProof<H>> {
         // Validate indices
@@ -125,6 +205,42 @@ impl<H: Hasher> MerkleTree<H> {
         MultiProof::create(self, indices)
     }
     
+    /// Replace the leaf at `index`, rehashing only its path to the root
+    ///
+    /// Returns a proof that the old and new roots differ in this leaf only.
+    pub fn set_leaf(&mut self, index: usize, data: &[u8]) -> Result<UpdateProof<H>> {
+        if index >= self.num_leaves {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Leaf index {} out of range", index)
+            ));
+        }
+        
+        let siblings = self.prove(index)?.siblings;
+        let new_leaf = H::hash_leaf(data);
+        let old_leaf = std::mem::replace(&mut self.nodes[0][index], new_leaf.clone());
+        
+        let mut current_index = index;
+        for level in 0..self.nodes.len() - 1 {
+            let left = current_index & !1;
+            let parent = H::hash_node(
+                level,
+                &self.nodes[level][left],
+                &self.nodes[level][left + 1],
+            );
+            current_index /= 2;
+            self.nodes[level + 1][current_index] = parent;
+        }
+        
+        Ok(UpdateProof {
+            version: MERKLE_HASH_VERSION,
+            leaf_index: index,
+            old_leaf,
+            new_leaf,
+            siblings,
+            _hasher: PhantomData,
+        })
+    }
+    
     /// Verify a proof
     pub fn verify(
         root: &H::Output,
@@ -186,12 +302,13 @@ impl DynamicMerkleTree {
         let mut level_size = tree_size;
         while level_size > 1 {
             level_size /= 2;
-            let prev_level = &nodes[nodes.len() - 1];
+            let height = nodes.len() - 1;
+            let prev_level = &nodes[height];
             
             let level: Vec<Vec<u8>> = (0..level_size)
                 .into_par_iter()
                 .map(|i| {
-                    hasher.hash_pair(&prev_level[2 * i], &prev_level[2 * i + 1])
+                    hasher.hash_node(height, &prev_level[2 * i], &prev_level[2 * i + 1])
                 })
                 .collect();
             
@@ -239,6 +356,7 @@ impl DynamicMerkleTree {
         }
         
         Ok(DynamicMerkleProof {
+            version: MERKLE_HASH_VERSION,
             leaf_index: index,
             siblings,
             hasher: self.hasher.clone(),
@@ -415,6 +533,35 @@ mod tests {
         assert_eq!(proof.leaf_index, 5);
     }
     
+    #[test]
+    fn test_leaf_node_domain_separation() {
+        let data = vec![b"left", b"rght"];
+        let tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+        let left = tree.get_node(0, 0).unwrap();
+        let right = tree.get_node(0, 1).unwrap();
+        
+        // The concatenated children are not a leaf preimage of the root
+        let forged = [left.as_slice(), right.as_slice()].concat();
+        assert_ne!(&Sha3_256Hasher::hash_leaf(&forged), tree.root());
+        
+        // Node hashes bind their height
+        assert_eq!(&Sha3_256Hasher::hash_node(0, left, right), tree.root());
+        assert_ne!(&Sha3_256Hasher::hash_node(1, left, right), tree.root());
+    }
+    
+    #[test]
+    fn test_proof_version_is_checked() {
+        let data = vec![b"a", b"b", b"c"];
+        let tree = MerkleTree::<Blake3Hasher>::new(&data).unwrap();
+        
+        let mut proof = tree.prove(2).unwrap();
+        assert_eq!(proof.version, MERKLE_HASH_VERSION);
+        assert!(proof.verify(tree.root(), b"c"));
+        
+        proof.version = 0;
+        assert!(!proof.verify(tree.root(), b"c"));
+    }
+    
     #[test]
     fn test_proof_tampering() {
         let data = vec![b"test1", b"test2", b"test3", b"test4"];
@@ -0,0 +1,257 @@
+//! Merkle Mountain Range for append-only logs
+//!
+//! An MMR over n leaves is a row of perfect binary trees ("peaks"), one per
+//! set bit of n, highest first. Appending a leaf merges equal-height peaks,
+//! so earlier nodes never change and a log of accepted proofs or issuer
+//! registrations can be extended without rehashing. The root bags the
+//! peaks into one digest bound to the leaf count:
+//!
+//! ```text
+//! root = H(0x03 || n as u64 LE || peak_0 || ... || peak_k)
+//! ```
+//!
+//! Peaks use the same leaf and node hashing as `MerkleTree`, so an
+//! inclusion proof is an ordinary path to its peak plus the other peaks.
+
+use crate::{Hasher, MERKLE_HASH_VERSION};
+use longfellow_core::{LongfellowError, Result};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Domain tag of the bagged root, after the leaf/node/empty tags
+pub const BAG_TAG: u8 = 0x03;
+
+/// (height, first leaf) of each peak of an MMR with `num_leaves` leaves,
+/// highest first
+fn peak_ranges(num_leaves: usize) -> Vec<(usize, usize)> {
+    let mut peaks = Vec::new();
+    let mut start = 0;
+    for height in (0..usize::BITS as usize).rev() {
+        if num_leaves >> height & 1 == 1 {
+            peaks.push((height, start));
+            start += 1 << height;
+        }
+    }
+    peaks
+}
+
+fn bag_peaks<H: Hasher>(num_leaves: usize, peaks: &[H::Output]) -> H::Output {
+    let count = (num_leaves as u64).to_le_bytes();
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(peaks.len() + 2);
+    parts.push(&[BAG_TAG]);
+    parts.push(&count);
+    parts.extend(peaks.iter().map(|p| p.as_ref()));
+    H::digest(&parts)
+}
+
+/// Append-only Merkle Mountain Range
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MerkleMountainRange<H: Hasher> {
+    /// Completed nodes by height; node i at height h covers leaves
+    /// i * 2^h .. (i + 1) * 2^h
+    levels: Vec<Vec<H::Output>>,
+    #[serde(skip)]
+    _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> MerkleMountainRange<H> {
+    pub fn new() -> Self {
+        Self {
+            levels: vec![Vec::new()],
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Number of leaves appended so far
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// Append a leaf, returning its index
+    pub fn append(&mut self, data: &[u8]) -> usize {
+        let index = self.len();
+        self.levels[0].push(H::hash_leaf(data));
+
+        // Merge while the newest node completes a pair
+        let mut height = 0;
+        while self.levels[height].len().is_multiple_of(2) {
+            let level = &self.levels[height];
+            let parent = H::hash_node(height, &level[level.len() - 2], &level[level.len() - 1]);
+            if self.levels.len() == height + 1 {
+                self.levels.push(Vec::new());
+            }
+            self.levels[height + 1].push(parent);
+            height += 1;
+        }
+
+        index
+    }
+
+    /// Peak hashes, highest first
+    pub fn peaks(&self) -> Vec<H::Output> {
+        peak_ranges(self.len())
+            .into_iter()
+            .map(|(height, start)| self.levels[height][start >> height].clone())
+            .collect()
+    }
+
+    /// Bagged root over the current peaks
+    pub fn root(&self) -> H::Output {
+        bag_peaks::<H>(self.len(), &self.peaks())
+    }
+
+    /// Inclusion proof for the leaf at `index` against the current root
+    pub fn prove(&self, index: usize) -> Result<MmrProof<H>> {
+        if index >= self.len() {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Leaf index {} out of range", index)
+            ));
+        }
+
+        let ranges = peak_ranges(self.len());
+        let peak = ranges
+            .iter()
+            .position(|&(height, start)| index < start + (1 << height))
+            .expect("every leaf lies under a peak");
+        let (peak_height, _) = ranges[peak];
+
+        let siblings = (0..peak_height)
+            .map(|h| self.levels[h][(index >> h) ^ 1].clone())
+            .collect();
+        let mut peaks = self.peaks();
+        peaks.remove(peak);
+
+        Ok(MmrProof {
+            version: MERKLE_HASH_VERSION,
+            leaf_index: index,
+            num_leaves: self.len(),
+            siblings,
+            peaks,
+            _hasher: PhantomData,
+        })
+    }
+}
+
+impl<H: Hasher> Default for MerkleMountainRange<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Inclusion proof for one leaf of an MMR
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MmrProof<H: Hasher> {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
+    /// Index of the leaf being proven
+    pub leaf_index: usize,
+    /// Leaf count of the MMR the root was taken at
+    pub num_leaves: usize,
+    /// Path from the leaf to its peak
+    pub siblings: Vec<H::Output>,
+    /// The other peaks, highest first
+    pub peaks: Vec<H::Output>,
+    #[serde(skip)]
+    pub _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> MmrProof<H> {
+    /// Verify this proof against a bagged root
+    pub fn verify(&self, root: &H::Output, leaf_data: &[u8]) -> bool {
+        if self.version != MERKLE_HASH_VERSION || self.leaf_index >= self.num_leaves {
+            return false;
+        }
+
+        let ranges = peak_ranges(self.num_leaves);
+        let Some(peak) = ranges
+            .iter()
+            .position(|&(height, start)| self.leaf_index < start + (1 << height))
+        else {
+            return false;
+        };
+        if self.siblings.len() != ranges[peak].0 || self.peaks.len() + 1 != ranges.len() {
+            return false;
+        }
+
+        let mut current_hash = H::hash_leaf(leaf_data);
+        for (height, sibling) in self.siblings.iter().enumerate() {
+            current_hash = if self.leaf_index >> height & 1 == 0 {
+                H::hash_node(height, &current_hash, sibling)
+            } else {
+                H::hash_node(height, sibling, &current_hash)
+            };
+        }
+
+        let mut peaks = self.peaks.clone();
+        peaks.insert(peak, current_hash);
+        &bag_peaks::<H>(self.num_leaves, &peaks) == root
+    }
+
+    /// Get the size of this proof in bytes
+    pub fn size_bytes(&self) -> usize {
+        (self.siblings.len() + self.peaks.len()) * std::mem::size_of::<H::Output>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::Sha256Hasher;
+    use crate::MerkleTree;
+
+    #[test]
+    fn test_append_and_prove() {
+        let mut mmr = MerkleMountainRange::<Sha256Hasher>::new();
+        let data: Vec<Vec<u8>> = (0..23u8).map(|i| vec![i; 2]).collect();
+
+        for (i, leaf) in data.iter().enumerate() {
+            assert_eq!(mmr.append(leaf), i);
+            let root = mmr.root();
+
+            // Every earlier leaf stays provable against the new root
+            for j in 0..=i {
+                let proof = mmr.prove(j).unwrap();
+                assert!(proof.verify(&root, &data[j]));
+                assert!(!proof.verify(&root, b"forged"));
+            }
+        }
+
+        // 23 = 16 + 4 + 2 + 1
+        assert_eq!(mmr.peaks().len(), 4);
+        assert!(mmr.prove(23).is_err());
+    }
+
+    #[test]
+    fn test_single_peak_matches_merkle_tree() {
+        let data: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i]).collect();
+        let mut mmr = MerkleMountainRange::<Sha256Hasher>::new();
+        for leaf in &data {
+            mmr.append(leaf);
+        }
+        let tree = MerkleTree::<Sha256Hasher>::new(&data).unwrap();
+        assert_eq!(mmr.peaks(), vec![*tree.root()]);
+    }
+
+    #[test]
+    fn test_root_binds_leaf_count() {
+        let mut mmr = MerkleMountainRange::<Sha256Hasher>::new();
+        for i in 0..6u8 {
+            mmr.append(&[i]);
+        }
+        let root = mmr.root();
+        let mut proof = mmr.prove(5).unwrap();
+        assert!(proof.verify(&root, &[5]));
+
+        // 6 and 7 leaves have different peak shapes
+        proof.num_leaves = 7;
+        assert!(!proof.verify(&root, &[5]));
+        proof.num_leaves = 6;
+        proof.leaf_index = 4;
+        assert!(!proof.verify(&root, &[5]));
+    }
+}
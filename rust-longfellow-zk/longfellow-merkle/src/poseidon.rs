@@ -0,0 +1,395 @@
+//! Poseidon hashing over prime fields for circuit-friendly Merkle trees
+//!
+//! A SHA-3 Merkle path costs tens of thousands of constraints to check in a
+//! circuit; a Poseidon path costs one permutation per level. Leaves are
+//! absorbed as field elements by a sponge and internal nodes are a single
+//! permutation of (tag, left, right), with the node height in the tag, so a
+//! path verifier only needs the permutation and the parameters below.
+//!
+//! Parameters are derived deterministically: round constants are
+//! `hash_to_field` outputs under a tag naming the instance, and the MDS
+//! matrix is the Cauchy matrix 1 / (i + j + width). A field is wired in by
+//! implementing `PoseidonConfig`; Fp128 and Goldilocks are provided.
+
+use crate::hash::Hasher;
+use longfellow_algebra::traits::Field;
+use longfellow_algebra::{Fp128, Goldilocks};
+use longfellow_core::{LongfellowError, Result};
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+/// Capacity value for leaf sponges
+pub const LEAF_TAG: u64 = 1;
+/// Capacity value for internal nodes, before the height is mixed in
+const NODE_TAG: u64 = 2;
+/// Capacity value for the padding leaf
+const EMPTY_TAG: u64 = 3;
+/// Capacity value for untagged `Hasher::digest` calls
+const DIGEST_TAG: u64 = 4;
+/// Initial capacity value of a Fiat-Shamir transcript sponge
+pub const TRANSCRIPT_TAG: u64 = 5;
+/// Capacity value for `PoseidonParams::hash`
+pub const HASH_TAG: u64 = 6;
+
+/// Node capacity value binding the height, distinct from the other tags
+pub fn node_tag(height: usize) -> u64 {
+    NODE_TAG | ((height as u64) << 8)
+}
+
+/// A Poseidon instance: state width, round counts, S-box exponent and the
+/// derived constants
+#[derive(Clone, Debug)]
+pub struct PoseidonParams<F: Field> {
+    width: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+    alpha: u64,
+    /// width constants per round, rounds in order
+    round_constants: Vec<F>,
+    /// Row-major width x width matrix
+    mds: Vec<F>,
+}
+
+impl<F: Field> PoseidonParams<F> {
+    /// Derive an instance. `alpha` must be coprime to p - 1 for the S-box
+    /// to be a permutation; `full_rounds` must be even, split evenly
+    /// around the partial rounds.
+    pub fn new(width: usize, full_rounds: usize, partial_rounds: usize, alpha: u64) -> Result<Self> {
+        if width < 2 {
+            return Err(LongfellowError::InvalidParameter(
+                "Poseidon width must be at least 2".to_string(),
+            ));
+        }
+        if !full_rounds.is_multiple_of(2) {
+            return Err(LongfellowError::InvalidParameter(
+                "Poseidon full rounds must be even".to_string(),
+            ));
+        }
+
+        let tag = format!(
+            "longfellow-poseidon-t{}-rf{}-rp{}-a{}",
+            width, full_rounds, partial_rounds, alpha
+        );
+        let num_constants = (full_rounds + partial_rounds) * width;
+        let round_constants = (0..num_constants as u64)
+            .map(|i| F::hash_to_field(tag.as_bytes(), &i.to_le_bytes()))
+            .collect();
+
+        let mut mds = Vec::with_capacity(width * width);
+        for i in 0..width {
+            for j in 0..width {
+                let denom = F::from_u64((i + j + width) as u64);
+                mds.push(denom.invert().ok_or_else(|| {
+                    LongfellowError::InvalidParameter(
+                        "Poseidon width too large for the field characteristic".to_string(),
+                    )
+                })?);
+            }
+        }
+
+        Ok(Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            alpha,
+            round_constants,
+            mds,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Elements absorbed per permutation (width minus one capacity element)
+    pub fn rate(&self) -> usize {
+        self.width - 1
+    }
+
+    pub fn full_rounds(&self) -> usize {
+        self.full_rounds
+    }
+
+    pub fn partial_rounds(&self) -> usize {
+        self.partial_rounds
+    }
+
+    pub fn alpha(&self) -> u64 {
+        self.alpha
+    }
+
+    pub fn round_constants(&self) -> &[F] {
+        &self.round_constants
+    }
+
+    pub fn mds(&self) -> &[F] {
+        &self.mds
+    }
+
+    fn sbox(&self, x: F) -> F {
+        x.pow_vartime(&[self.alpha])
+    }
+
+    /// Apply the permutation to `state` in place
+    ///
+    /// Panics if `state.len() != width`.
+    pub fn permute(&self, state: &mut [F]) {
+        assert_eq!(state.len(), self.width, "Poseidon state has the wrong width");
+
+        let half_full = self.full_rounds / 2;
+        let rounds = self.full_rounds + self.partial_rounds;
+        let mut scratch = vec![F::zero(); self.width];
+
+        for round in 0..rounds {
+            let constants = &self.round_constants[round * self.width..(round + 1) * self.width];
+            for (s, &c) in state.iter_mut().zip(constants) {
+                *s += c;
+            }
+
+            if round < half_full || round >= half_full + self.partial_rounds {
+                for s in state.iter_mut() {
+                    *s = self.sbox(*s);
+                }
+            } else {
+                state[0] = self.sbox(state[0]);
+            }
+
+            for (i, out) in scratch.iter_mut().enumerate() {
+                let row = &self.mds[i * self.width..(i + 1) * self.width];
+                *out = row
+                    .iter()
+                    .zip(state.iter())
+                    .fold(F::zero(), |acc, (&m, &s)| acc + m * s);
+            }
+            state.copy_from_slice(&scratch);
+        }
+    }
+
+    /// Sponge over `elements` with the capacity set to `tag`; the length is
+    /// absorbed first, so no further padding is needed
+    pub fn hash_elements(&self, tag: u64, elements: &[F]) -> F {
+        let mut state = vec![F::zero(); self.width];
+        state[0] = F::from_u64(tag);
+
+        let len = F::from_u64(elements.len() as u64);
+        let input: Vec<F> = std::iter::once(len).chain(elements.iter().copied()).collect();
+        for block in input.chunks(self.rate()) {
+            for (s, &x) in state[1..].iter_mut().zip(block) {
+                *s += x;
+            }
+            self.permute(&mut state);
+        }
+
+        state[1]
+    }
+
+    /// Hash of field elements in a domain of their own, for value
+    /// commitments that a circuit recomputes
+    pub fn hash(&self, elements: &[F]) -> F {
+        self.hash_elements(HASH_TAG, elements)
+    }
+
+    /// Two-to-one compression: one permutation of (tag, left, right, 0...)
+    pub fn compress(&self, tag: u64, left: F, right: F) -> F {
+        assert!(self.width >= 3, "Two-to-one compression needs width 3");
+
+        let mut state = vec![F::zero(); self.width];
+        state[0] = F::from_u64(tag);
+        state[1] = left;
+        state[2] = right;
+        self.permute(&mut state);
+        state[1]
+    }
+}
+
+/// Packs bytes into field elements, canonical_byte_len - 1 bytes each, so
+/// every chunk is below the modulus and the packing is injective for a
+/// fixed length
+pub fn bytes_to_elements<F: Field>(data: &[u8]) -> Vec<F> {
+    let width = F::canonical_byte_len();
+    data.chunks(width - 1)
+        .map(|chunk| {
+            let mut bytes = vec![0u8; width];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            F::from_canonical_bytes(&bytes).expect("chunk is below the modulus")
+        })
+        .collect()
+}
+
+/// A field and Poseidon instance for `PoseidonHasher`
+pub trait PoseidonConfig: Clone + Send + Sync + 'static {
+    type F: Field;
+
+    /// The shared parameters, built once
+    fn params() -> &'static PoseidonParams<Self::F>;
+}
+
+/// Fp128 with width 3, x^7 S-box, 8 full and 57 partial rounds
+///
+/// 7 is the least exponent coprime to p - 1 = 2^108 * 3 * 5^2 * 11 * 31 * 41.
+#[derive(Clone, Copy, Debug)]
+pub struct Fp128Poseidon;
+
+impl PoseidonConfig for Fp128Poseidon {
+    type F = Fp128;
+
+    fn params() -> &'static PoseidonParams<Fp128> {
+        static PARAMS: OnceLock<PoseidonParams<Fp128>> = OnceLock::new();
+        PARAMS.get_or_init(|| {
+            PoseidonParams::new(3, 8, 57, 7).expect("Fp128 Poseidon parameters are valid")
+        })
+    }
+}
+
+/// Goldilocks with width 3, x^7 S-box, 8 full and 22 partial rounds
+///
+/// 7 is the least exponent coprime to p - 1 = 2^32 * 3 * 5 * 17 * 257 * 65537.
+/// A digest is a single 64-bit element, so collisions cost only 2^32 work;
+/// this instance suits hashing inside a circuit over Goldilocks, not
+/// standalone commitments.
+#[derive(Clone, Copy, Debug)]
+pub struct GoldilocksPoseidon;
+
+impl PoseidonConfig for GoldilocksPoseidon {
+    type F = Goldilocks;
+
+    fn params() -> &'static PoseidonParams<Goldilocks> {
+        static PARAMS: OnceLock<PoseidonParams<Goldilocks>> = OnceLock::new();
+        PARAMS.get_or_init(|| {
+            PoseidonParams::new(3, 8, 22, 7).expect("Goldilocks Poseidon parameters are valid")
+        })
+    }
+}
+
+/// Merkle hasher over a Poseidon instance
+///
+/// Digests are the canonical bytes of one field element. A sibling that is
+/// not a canonical encoding cannot occur in an honest tree; it is mapped
+/// into the field with `hash_to_field` so verification simply fails.
+#[derive(Clone, Copy, Debug)]
+pub struct PoseidonHasher<C: PoseidonConfig> {
+    _config: PhantomData<C>,
+}
+
+/// Poseidon Merkle hasher over Fp128
+pub type Fp128PoseidonHasher = PoseidonHasher<Fp128Poseidon>;
+
+impl<C: PoseidonConfig> PoseidonHasher<C> {
+    /// Digest bytes back to the field element a circuit works with
+    pub fn digest_to_field(digest: &[u8]) -> C::F {
+        C::F::from_canonical_bytes(digest)
+            .unwrap_or_else(|_| C::F::hash_to_field(b"longfellow-poseidon-digest", digest))
+    }
+
+    /// Leaf digest of field elements, for callers that already hold a
+    /// committed column as elements rather than bytes
+    pub fn hash_leaf_elements(elements: &[C::F]) -> C::F {
+        C::params().hash_elements(LEAF_TAG, elements)
+    }
+}
+
+impl<C: PoseidonConfig> Hasher for PoseidonHasher<C> {
+    type Output = Vec<u8>;
+
+    /// Sponge over the packed bytes, in a domain of its own
+    fn digest(parts: &[&[u8]]) -> Self::Output {
+        let data = parts.concat();
+        let mut elements = vec![C::F::from_u64(data.len() as u64)];
+        elements.extend(bytes_to_elements::<C::F>(&data));
+        C::params().hash_elements(DIGEST_TAG, &elements).to_canonical_bytes()
+    }
+
+    fn hash_leaf(data: &[u8]) -> Self::Output {
+        // The byte length is absorbed too, so trailing zero bytes are not
+        // lost in the packing
+        let mut elements = vec![C::F::from_u64(data.len() as u64)];
+        elements.extend(bytes_to_elements::<C::F>(data));
+        Self::hash_leaf_elements(&elements).to_canonical_bytes()
+    }
+
+    fn hash_node(height: usize, left: &Self::Output, right: &Self::Output) -> Self::Output {
+        let left = Self::digest_to_field(left);
+        let right = Self::digest_to_field(right);
+        C::params()
+            .compress(node_tag(height), left, right)
+            .to_canonical_bytes()
+    }
+
+    /// Two children are one compression; wider nodes absorb all children
+    /// into a sponge under the same height-bound tag
+    fn hash_children(height: usize, children: &[Self::Output]) -> Self::Output {
+        if let [left, right] = children {
+            return Self::hash_node(height, left, right);
+        }
+        let elements: Vec<C::F> = children.iter().map(|c| Self::digest_to_field(c)).collect();
+        C::params()
+            .hash_elements(node_tag(height), &elements)
+            .to_canonical_bytes()
+    }
+
+    fn empty_hash() -> Self::Output {
+        C::params().hash_elements(EMPTY_TAG, &[]).to_canonical_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MerkleTree;
+
+    #[test]
+    fn test_permutation_is_deterministic_and_mixes() {
+        let params = Fp128Poseidon::params();
+        let mut a = vec![Fp128::zero(), Fp128::one(), Fp128::from_u64(2)];
+        let mut b = a.clone();
+        params.permute(&mut a);
+        params.permute(&mut b);
+        assert_eq!(a, b);
+
+        // Changing one input word changes every output word
+        let mut c = vec![Fp128::zero(), Fp128::one(), Fp128::from_u64(3)];
+        params.permute(&mut c);
+        assert!(a.iter().zip(c.iter()).all(|(x, y)| x != y));
+    }
+
+    #[test]
+    fn test_goldilocks_permutation_mixes() {
+        let params = GoldilocksPoseidon::params();
+        let mut a = vec![Goldilocks::zero(), Goldilocks::one(), Goldilocks::from_u64(2)];
+        let mut b = vec![Goldilocks::zero(), Goldilocks::one(), Goldilocks::from_u64(3)];
+        params.permute(&mut a);
+        params.permute(&mut b);
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| x != y));
+        assert_ne!(params.hash(&a), params.hash(&b));
+    }
+
+    #[test]
+    fn test_leaf_and_node_domains_are_separated() {
+        let x = Fp128::from_u64(5);
+        let y = Fp128::from_u64(6);
+        let params = Fp128Poseidon::params();
+        assert_ne!(params.compress(node_tag(0), x, y), params.compress(LEAF_TAG, x, y));
+        assert_ne!(params.compress(node_tag(0), x, y), params.compress(node_tag(0), y, x));
+        assert_ne!(params.compress(node_tag(0), x, y), params.compress(node_tag(1), x, y));
+        assert_ne!(params.hash(&[x, y]), params.hash_elements(LEAF_TAG, &[x, y]));
+
+        // Trailing zero bytes change the leaf digest
+        assert_ne!(
+            Fp128PoseidonHasher::hash_leaf(b"abc"),
+            Fp128PoseidonHasher::hash_leaf(b"abc\0")
+        );
+    }
+
+    #[test]
+    fn test_poseidon_merkle_tree() {
+        let data: Vec<Vec<u8>> = (0..6u8).map(|i| vec![i; 40]).collect();
+        let tree = MerkleTree::<Fp128PoseidonHasher>::new(&data).unwrap();
+        assert_eq!(tree.root().len(), 16);
+
+        for (i, leaf) in data.iter().enumerate() {
+            let proof = tree.prove(i).unwrap();
+            assert!(proof.verify(tree.root(), leaf));
+            assert!(!proof.verify(tree.root(), b"tampered"));
+        }
+    }
+}
@@ -1,14 +1,18 @@
 /// Merkle proof structures and verification
 
-use crate::{Hasher, MerkleTree, DynamicHasher};
-use longfellow_core::Result;
+use crate::{hiding_leaf_hash, DynamicHasher, Hasher, LeafSalt, MerkleTree, MERKLE_HASH_VERSION};
+use longfellow_core::{LongfellowError, Result};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use std::ops::Range;
 
 /// A proof for a single leaf in a Merkle tree
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MerkleProof<H: Hasher> {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
     /// Index of the leaf being proven
     pub leaf_index: usize,
     /// Sibling hashes from leaf to root
@@ -21,21 +25,102 @@ pub struct MerkleProof<H: Hasher> {
 impl<H: Hasher> MerkleProof<H> {
     /// Verify this proof against a root hash
     pub fn verify(&self, root: &H::Output, leaf_data: &[u8]) -> bool {
-        let mut current_hash = H::hash_leaf(leaf_data);
-        let mut current_index = self.leaf_index;
+        if self.version != MERKLE_HASH_VERSION {
+            return false;
+        }
         
-        for sibling in &self.siblings {
-            if current_index & 1 == 0 {
-                // Current node is left child
-                current_hash = H::hash_pair(&current_hash, sibling);
-            } else {
-                // Current node is right child
-                current_hash = H::hash_pair(sibling, &current_hash);
+        &root_from_path::<H>(self.leaf_index, H::hash_leaf(leaf_data), &self.siblings) == root
+    }
+    
+    /// Verify an opening of a tree built with `MerkleTree::new_hiding`
+    pub fn verify_hiding(&self, root: &H::Output, salt: &LeafSalt, leaf_data: &[u8]) -> bool {
+        if self.version != MERKLE_HASH_VERSION {
+            return false;
+        }
+        
+        let leaf = hiding_leaf_hash::<H>(salt, leaf_data);
+        &root_from_path::<H>(self.leaf_index, leaf, &self.siblings) == root
+    }
+    
+    /// Verify many proofs against one root
+    ///
+    /// Leaf hashes and each level of parents are computed in parallel.
+    /// Paths that meet are merged, so a shared ancestor is hashed once;
+    /// where one path's sibling is a node another path computed, the two
+    /// must agree, and so must every proof through the same node. An empty
+    /// batch proves nothing about `root` and is rejected.
+    pub fn verify_batch(root: &H::Output, items: &[(&MerkleProof<H>, &[u8])]) -> bool {
+        let Some((first, _)) = items.first() else {
+            return false;
+        };
+        let depth = first.siblings.len();
+        if depth >= usize::BITS as usize
+            || items.iter().any(|(proof, _)| {
+                proof.version != MERKLE_HASH_VERSION
+                    || proof.siblings.len() != depth
+                    || proof.leaf_index >> depth != 0
+            })
+        {
+            return false;
+        }
+        
+        // (node index, hash, items whose path runs through the node)
+        let mut layer: Vec<(usize, H::Output, Vec<usize>)> = items
+            .par_iter()
+            .enumerate()
+            .map(|(item, (proof, data))| (proof.leaf_index, H::hash_leaf(data), vec![item]))
+            .collect();
+        
+        for height in 0..depth {
+            // One entry per distinct node; proofs meeting there must agree
+            layer.sort_unstable_by_key(|(idx, _, _)| *idx);
+            let mut nodes: Vec<(usize, H::Output, Vec<usize>)> = Vec::with_capacity(layer.len());
+            for (idx, hash, members) in layer {
+                match nodes.last_mut() {
+                    Some((last, last_hash, last_members)) if *last == idx => {
+                        if *last_hash != hash {
+                            return false;
+                        }
+                        last_members.extend(members);
+                    }
+                    _ => nodes.push((idx, hash, members)),
+                }
             }
-            current_index /= 2;
+            
+            // Every proof's sibling must match the computed sibling node,
+            // or, where there is none, the other proofs through its node
+            let consistent = nodes.par_iter().enumerate().all(|(pos, (_, _, members))| {
+                let sibling = match sibling_entry(&nodes, pos) {
+                    Some((_, hash, _)) => hash,
+                    None => &items[members[0]].0.siblings[height],
+                };
+                members.iter().all(|&m| &items[m].0.siblings[height] == sibling)
+            });
+            if !consistent {
+                return false;
+            }
+            
+            // Hash each pair once, from its left child or its lone node
+            layer = nodes
+                .par_iter()
+                .enumerate()
+                .filter(|&(pos, (idx, _, _))| idx & 1 == 0 || sibling_entry(&nodes, pos).is_none())
+                .map(|(pos, (idx, hash, members))| {
+                    let mut members = members.clone();
+                    let parent = match (idx & 1, sibling_entry(&nodes, pos)) {
+                        (0, Some((_, right, right_members))) => {
+                            members.extend(right_members);
+                            H::hash_node(height, hash, right)
+                        }
+                        (0, None) => H::hash_node(height, hash, &items[members[0]].0.siblings[height]),
+                        _ => H::hash_node(height, &items[members[0]].0.siblings[height], hash),
+                    };
+                    (idx / 2, parent, members)
+                })
+                .collect();
         }
         
-        &current_hash == root
+        layer.iter().all(|(idx, hash, _)| *idx == 0 && hash == root)
     }
     
     /// Get the size of this proof in bytes
@@ -44,13 +129,127 @@ impl<H: Hasher> MerkleProof<H> {
     }
 }
 
-/// A batch proof for multiple leaves (more efficient than individual proofs)
+/// The entry for the sibling of `nodes[pos]`, if that node was computed;
+/// `nodes` is sorted by index, so it can only be adjacent
+fn sibling_entry<T>(nodes: &[(usize, T, Vec<usize>)], pos: usize) -> Option<&(usize, T, Vec<usize>)> {
+    let idx = nodes[pos].0;
+    let neighbour = if idx & 1 == 0 {
+        nodes.get(pos + 1)
+    } else {
+        pos.checked_sub(1).map(|p| &nodes[p])
+    };
+    neighbour.filter(|(n, _, _)| *n == idx ^ 1)
+}
+
+/// Root reached from `leaf` at `index` through `siblings`, leaf upwards
+fn root_from_path<H: Hasher>(index: usize, leaf: H::Output, siblings: &[H::Output]) -> H::Output {
+    let mut current_hash = leaf;
+    let mut current_index = index;
+    
+    for (height, sibling) in siblings.iter().enumerate() {
+        if current_index & 1 == 0 {
+            // Current node is left child
+            current_hash = H::hash_node(height, &current_hash, sibling);
+        } else {
+            // Current node is right child
+            current_hash = H::hash_node(height, sibling, &current_hash);
+        }
+        current_index /= 2;
+    }
+    
+    current_hash
+}
+
+/// Evidence that two roots differ in exactly one leaf
+///
+/// Replacing a leaf leaves its siblings untouched, so one path links the
+/// old leaf hash to the old root and the new leaf hash to the new root.
+/// The leaf contents need not be revealed: a holder re-blinding a claim
+/// shows the update is confined to `leaf_index` without opening either
+/// value.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UpdateProof<H: Hasher> {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
+    /// Index of the replaced leaf
+    pub leaf_index: usize,
+    /// Leaf hash before the update
+    pub old_leaf: H::Output,
+    /// Leaf hash after the update
+    pub new_leaf: H::Output,
+    /// Sibling hashes from leaf to root, shared by both trees
+    pub siblings: Vec<H::Output>,
+    /// Phantom data for hasher type
+    #[serde(skip)]
+    pub _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> UpdateProof<H> {
+    /// Check that `old_root` and `new_root` differ only at `leaf_index`
+    pub fn verify(&self, old_root: &H::Output, new_root: &H::Output) -> bool {
+        self.version == MERKLE_HASH_VERSION
+            && &root_from_path::<H>(self.leaf_index, self.old_leaf.clone(), &self.siblings) == old_root
+            && &root_from_path::<H>(self.leaf_index, self.new_leaf.clone(), &self.siblings) == new_root
+    }
+
+    /// As `verify`, additionally checking the replaced and new leaf data
+    pub fn verify_data(
+        &self,
+        old_root: &H::Output,
+        old_data: &[u8],
+        new_root: &H::Output,
+        new_data: &[u8],
+    ) -> bool {
+        H::hash_leaf(old_data) == self.old_leaf
+            && H::hash_leaf(new_data) == self.new_leaf
+            && self.verify(old_root, new_root)
+    }
+}
+
+/// Sibling positions an Octopus multiproof carries, in proof order
+///
+/// Each level is walked left to right over the known nodes (sorted,
+/// deduplicated). A node whose sibling is also known is paired with it;
+/// otherwise the sibling is taken from the proof. The known nodes of the
+/// next level are the parents. No hash that the verifier can compute is
+/// ever sent, and no hash is sent twice.
+fn octopus_positions(indices: &[usize], depth: usize) -> Vec<(usize, usize)> {
+    let mut positions = Vec::new();
+    let mut known = indices.to_vec();
+
+    for level in 0..depth {
+        let mut i = 0;
+        while i < known.len() {
+            let idx = known[i];
+            if idx & 1 == 0 && known.get(i + 1) == Some(&(idx + 1)) {
+                i += 2;
+            } else {
+                positions.push((level, idx ^ 1));
+                i += 1;
+            }
+        }
+        known = known.iter().map(|idx| idx / 2).collect();
+        known.dedup();
+    }
+
+    positions
+}
+
+/// A batch proof for multiple leaves carrying only the siblings that no
+/// opened path supplies
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MultiProof<H: Hasher> {
-    /// Indices of leaves being proven
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
+    /// Indices of leaves being proven, sorted and deduplicated
     pub leaf_indices: Vec<usize>,
-    /// All required hashes (deduplicated)
-    pub hashes: HashMap<(usize, usize), H::Output>, // (level, index) -> hash
+    /// Number of levels above the leaves
+    pub depth: usize,
+    /// Sibling hashes in the order the verifier consumes them: level by
+    /// level from the leaves, left to right within a level
+    pub siblings: Vec<H::Output>,
     /// Phantom data for hasher type
     #[serde(skip)]
     pub _hasher: PhantomData<H>,
@@ -59,104 +258,71 @@ pub struct MultiProof<H: Hasher> {
 impl<H: Hasher> MultiProof<H> {
     /// Create a multi-proof for given indices
     pub fn create(tree: &MerkleTree<H>, indices: &[usize]) -> Result<Self> {
-        let mut required_hashes = HashMap::new();
-        let mut computed_nodes = HashSet::new();
-        
-        // Mark all nodes on paths from leaves to root
-        for &leaf_idx in indices {
-            let mut idx = leaf_idx;
-            for level in 0..tree.height() {
-                computed_nodes.insert((level, idx));
-                idx /= 2;
-            }
-        }
-        
-        // Collect all required sibling hashes
-        for &leaf_idx in indices {
-            let mut idx = leaf_idx;
-            for level in 0..tree.height() - 1 {
-                let sibling_idx = idx ^ 1;
-                
-                // Only include if sibling is not in computed set
-                if !computed_nodes.contains(&(level, sibling_idx)) {
-                    if let Some(hash) = tree.get_node(level, sibling_idx) {
-                        required_hashes.insert((level, sibling_idx), hash.clone());
-                    }
-                }
-                
-                idx /= 2;
-            }
-        }
-        
+        let mut leaf_indices = indices.to_vec();
+        leaf_indices.sort_unstable();
+        leaf_indices.dedup();
+
+        let depth = tree.height() - 1;
+        let siblings = octopus_positions(&leaf_indices, depth)
+            .into_iter()
+            .map(|(level, idx)| {
+                tree.get_node(level, idx).cloned().ok_or_else(|| {
+                    LongfellowError::InvalidParameter(format!(
+                        "Leaf index out of range at level {}",
+                        level
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         Ok(Self {
-            leaf_indices: indices.to_vec(),
-            hashes: required_hashes,
+            version: MERKLE_HASH_VERSION,
+            leaf_indices,
+            depth,
+            siblings,
             _hasher: PhantomData,
         })
     }
-    
-    /// Verify this multi-proof
+
+    /// Verify this multi-proof; `leaf_data` must open exactly the proven
+    /// indices, in any order
     pub fn verify(&self, root: &H::Output, leaf_data: &[(usize, &[u8])]) -> bool {
-        // Create map of leaf indices to data
-        let leaf_map: HashMap<usize, &[u8]> = leaf_data.iter()
-            .map(|&(idx, data)| (idx, data))
+        let leaves: Vec<(usize, H::Output)> = leaf_data
+            .iter()
+            .map(|&(idx, data)| (idx, H::hash_leaf(data)))
             .collect();
-        
-        // Check all provided leaves are in the proof
-        for &idx in &self.leaf_indices {
-            if !leaf_map.contains_key(&idx) {
-                return false;
-            }
-        }
-        
-        // Compute all node values bottom-up
-        let mut computed: HashMap<(usize, usize), H::Output> = HashMap::new();
-        
-        // Start with leaf hashes
-        for &idx in &self.leaf_indices {
-            if let Some(&data) = leaf_map.get(&idx) {
-                computed.insert((0, idx), H::hash_leaf(data));
-            }
+        self.verify_leaf_hashes(root, &leaves)
+    }
+
+    /// Verify against leaf hashes, for callers that hash leaves themselves
+    pub fn verify_leaf_hashes(&self, root: &H::Output, leaves: &[(usize, H::Output)]) -> bool {
+        self.compute_root(leaves)
+            .map(|computed| &computed == root)
+            .unwrap_or(false)
+    }
+
+    /// Root implied by the leaves and siblings, or None if they do not
+    /// match the proof's shape
+    fn compute_root(&self, leaves: &[(usize, H::Output)]) -> Option<H::Output> {
+        if self.version != MERKLE_HASH_VERSION || self.depth >= usize::BITS as usize {
+            return None;
         }
-        
-        // Work up the tree
-        let max_level = (self.leaf_indices.iter().max().unwrap_or(&0) + 1).next_power_of_two().trailing_zeros() as usize;
-        
-        for level in 0..max_level {
-            let mut indices_at_level: HashSet<usize> = HashSet::new();
-            
-            // Collect all indices we need to compute at next level
-            for (&(l, idx), _) in &computed {
-                if l == level {
-                    indices_at_level.insert(idx / 2);
-                }
-            }
-            
-            // Compute parent nodes
-            for &parent_idx in &indices_at_level {
-                let left_idx = parent_idx * 2;
-                let right_idx = parent_idx * 2 + 1;
-                
-                let left_hash = computed.get(&(level, left_idx))
-                    .or_else(|| self.hashes.get(&(level, left_idx)));
-                let right_hash = computed.get(&(level, right_idx))
-                    .or_else(|| self.hashes.get(&(level, right_idx)));
-                
-                if let (Some(left), Some(right)) = (left_hash, right_hash) {
-                    computed.insert((level + 1, parent_idx), H::hash_pair(left, right));
-                }
-            }
+
+        let mut layer = leaves.to_vec();
+        layer.sort_by_key(|(idx, _)| *idx);
+        if layer.len() != self.leaf_indices.len()
+            || layer.iter().zip(&self.leaf_indices).any(|((a, _), b)| a != b)
+            || layer.last().is_none_or(|(idx, _)| idx >> self.depth != 0)
+        {
+            return None;
         }
-        
-        // Check if we computed the correct root
-        computed.get(&(max_level, 0))
-            .map(|computed_root| computed_root == root)
-            .unwrap_or(false)
+
+        octopus_root::<H>(layer, self.depth, &self.siblings)
     }
-    
+
     /// Get the size of this proof in bytes
     pub fn size_bytes(&self) -> usize {
-        self.hashes.len() * std::mem::size_of::<H::Output>()
+        self.siblings.len() * std::mem::size_of::<H::Output>()
     }
     
     /// Convert to individual proofs (less efficient but simpler interface)
@@ -167,10 +333,123 @@ impl<H: Hasher> MultiProof<H> {
     }
 }
 
+/// Inclusion proof for the contiguous leaves `start..end`
+///
+/// Only the left edge of the first leaf's path and the right edge of the
+/// last leaf's path are sent, at most two hashes per level, so revealing a
+/// byte range of a committed document costs about as much as two single
+/// openings however long the range.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RangeProof<H: Hasher> {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
+    /// First leaf of the range
+    pub start: usize,
+    /// One past the last leaf of the range
+    pub end: usize,
+    /// Number of levels above the leaves
+    pub depth: usize,
+    /// Boundary siblings, in `MultiProof` order
+    pub siblings: Vec<H::Output>,
+    #[serde(skip)]
+    pub _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> RangeProof<H> {
+    /// Create a proof for `range`, which must be non-empty and in bounds
+    pub fn create(tree: &MerkleTree<H>, range: Range<usize>) -> Result<Self> {
+        if range.is_empty() || range.end > tree.num_leaves() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Leaf range {:?} out of range for {} leaves",
+                range,
+                tree.num_leaves()
+            )));
+        }
+
+        let depth = tree.height() - 1;
+        let indices: Vec<usize> = range.clone().collect();
+        let siblings = octopus_positions(&indices, depth)
+            .into_iter()
+            .map(|(level, idx)| tree.nodes[level][idx].clone())
+            .collect();
+
+        Ok(Self {
+            version: MERKLE_HASH_VERSION,
+            start: range.start,
+            end: range.end,
+            depth,
+            siblings,
+            _hasher: PhantomData,
+        })
+    }
+
+    /// Verify that `leaves` are the leaves `start..end`, in order
+    pub fn verify<T: AsRef<[u8]>>(&self, root: &H::Output, leaves: &[T]) -> bool {
+        if self.version != MERKLE_HASH_VERSION
+            || self.depth >= usize::BITS as usize
+            || self.start >= self.end
+            || self.end - self.start != leaves.len()
+            || (self.end - 1) >> self.depth != 0
+        {
+            return false;
+        }
+
+        let layer = leaves
+            .iter()
+            .enumerate()
+            .map(|(i, data)| (self.start + i, H::hash_leaf(data.as_ref())))
+            .collect();
+        octopus_root::<H>(layer, self.depth, &self.siblings)
+            .map(|computed| &computed == root)
+            .unwrap_or(false)
+    }
+
+    /// Get the size of this proof in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.siblings.len() * std::mem::size_of::<H::Output>()
+    }
+}
+
+/// Fold known nodes (sorted, distinct, all below 2^depth) and the siblings
+/// `octopus_positions` lists for them up to the root; None if the sibling
+/// count does not match
+fn octopus_root<H: Hasher>(
+    mut layer: Vec<(usize, H::Output)>,
+    depth: usize,
+    siblings: &[H::Output],
+) -> Option<H::Output> {
+    let mut siblings = siblings.iter();
+    for level in 0..depth {
+        let mut next: Vec<(usize, H::Output)> = Vec::with_capacity(layer.len());
+        let mut i = 0;
+        while i < layer.len() {
+            let (idx, ref hash) = layer[i];
+            let parent = if idx & 1 == 0 && layer.get(i + 1).map(|(j, _)| *j) == Some(idx + 1) {
+                i += 1;
+                H::hash_node(level, hash, &layer[i].1)
+            } else if idx & 1 == 0 {
+                H::hash_node(level, hash, siblings.next()?)
+            } else {
+                H::hash_node(level, siblings.next()?, hash)
+            };
+            next.push((idx / 2, parent));
+            i += 1;
+        }
+        layer = next;
+    }
+
+    if siblings.next().is_some() {
+        return None;
+    }
+    layer.pop().map(|(_, root)| root)
+}
+
 /// Compressed proof using bit vectors for efficiency
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompressedProof<H: Hasher> {
-    /// Bit vector indicating which nodes are included
+    /// Bit vector over all nodes below the root, leaves first, marking the
+    /// nodes whose hashes are included
     pub included_nodes: Vec<u8>,
     /// The actual hash values in order
     pub hashes: Vec<H::Output>,
@@ -183,29 +462,23 @@ pub struct CompressedProof<H: Hasher> {
 
 impl<H: Hasher> CompressedProof<H> {
     /// Create a compressed proof from a multi-proof
-    pub fn from_multiproof(multiproof: &MultiProof<H>, tree_height: usize) -> Self {
-        let total_nodes = (1 << tree_height) - 1;
+    pub fn from_multiproof(multiproof: &MultiProof<H>) -> Self {
+        let depth = multiproof.depth;
+        let total_nodes = (1usize << (depth + 1)) - 2;
         let mut included_nodes = vec![0u8; (total_nodes + 7) / 8];
-        let mut hashes = Vec::new();
-        
-        // Mark included nodes and collect hashes in order
-        for level in 0..tree_height {
-            let level_start = (1 << level) - 1;
-            let level_size = 1 << level;
-            
-            for idx in 0..level_size {
-                if multiproof.hashes.contains_key(&(level, idx)) {
-                    let global_idx = level_start + idx;
-                    included_nodes[global_idx / 8] |= 1 << (global_idx % 8);
-                    hashes.push(multiproof.hashes[&(level, idx)].clone());
-                }
-            }
+
+        // Siblings come out level by level and left to right, which is
+        // already the bitmap order
+        for (level, idx) in octopus_positions(&multiproof.leaf_indices, depth) {
+            let level_start = (1usize << (depth + 1)) - (1usize << (depth + 1 - level));
+            let global_idx = level_start + idx;
+            included_nodes[global_idx / 8] |= 1 << (global_idx % 8);
         }
-        
+
         Self {
             included_nodes,
-            hashes,
-            height: tree_height,
+            hashes: multiproof.siblings.clone(),
+            height: depth + 1,
             _hasher: PhantomData,
         }
     }
@@ -219,6 +492,9 @@ impl<H: Hasher> CompressedProof<H> {
 /// A dynamic proof for runtime hash function selection
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DynamicMerkleProof {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
     /// Index of the leaf being proven
     pub leaf_index: usize,
     /// Sibling hashes from leaf to root
@@ -231,16 +507,20 @@ pub struct DynamicMerkleProof {
 impl DynamicMerkleProof {
     /// Verify this proof against a root hash
     pub fn verify(&self, root: &[u8], leaf_data: &[u8]) -> bool {
+        if self.version != MERKLE_HASH_VERSION {
+            return false;
+        }
+        
         let mut current_hash = self.hasher.hash_leaf(leaf_data);
         let mut current_index = self.leaf_index;
         
-        for sibling in &self.siblings {
+        for (height, sibling) in self.siblings.iter().enumerate() {
             if current_index & 1 == 0 {
                 // Current node is left child
-                current_hash = self.hasher.hash_pair(&current_hash, sibling);
+                current_hash = self.hasher.hash_node(height, &current_hash, sibling);
             } else {
                 // Current node is right child
-                current_hash = self.hasher.hash_pair(sibling, &current_hash);
+                current_hash = self.hasher.hash_node(height, sibling, &current_hash);
             }
             current_index /= 2;
         }
@@ -305,9 +585,159 @@ mod tests {
         let indices = vec![0, 1, 4, 7, 15];
         let multiproof = tree.prove_batch(&indices).unwrap();
         
-        let compressed = CompressedProof::from_multiproof(&multiproof, tree.height());
+        let compressed = CompressedProof::from_multiproof(&multiproof);
+        assert_eq!(compressed.hashes.len(), multiproof.siblings.len());
+        assert_eq!(compressed.height, tree.height());
+    }
+
+    #[test]
+    fn test_range_proof() {
+        let data: Vec<Vec<u8>> = (0..37u8).map(|i| vec![i; 3]).collect();
+        let tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+
+        for range in [0..1, 3..4, 5..21, 0..37, 36..37, 8..16] {
+            let proof = tree.prove_range(range.clone()).unwrap();
+            assert!(proof.verify(tree.root(), &data[range.clone()]));
+            // Two boundary paths at most, whatever the range length
+            assert!(proof.siblings.len() <= 2 * proof.depth);
+
+            let mut shifted = proof.clone();
+            shifted.start += 1;
+            shifted.end += 1;
+            assert!(!shifted.verify(tree.root(), &data[range.clone()]));
+        }
+
+        let proof = tree.prove_range(5..21).unwrap();
+        assert!(!proof.verify(tree.root(), &data[5..20]));
+        let mut tampered = data[5..21].to_vec();
+        tampered[7][0] ^= 1;
+        assert!(!proof.verify(tree.root(), &tampered));
+
+        assert!(tree.prove_range(4..4).is_err());
+        assert!(tree.prove_range(30..38).is_err());
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let data: Vec<Vec<u8>> = (0..13).map(|i| vec![i]).collect();
+        let tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
         
-        // Compressed should be smaller than regular multiproof
-        assert!(compressed.size_bytes() <= multiproof.size_bytes());
+        // Overlapping paths, adjacent leaves and a repeated index
+        let indices = [0, 1, 5, 12, 5, 7];
+        let proofs: Vec<MerkleProof<Sha3_256Hasher>> = indices.iter()
+            .map(|&i| tree.prove(i).unwrap())
+            .collect();
+        let items: Vec<(&MerkleProof<Sha3_256Hasher>, &[u8])> = proofs.iter()
+            .zip(indices.iter())
+            .map(|(p, &i)| (p, data[i].as_slice()))
+            .collect();
+        assert!(MerkleProof::verify_batch(tree.root(), &items));
+        assert!(!MerkleProof::<Sha3_256Hasher>::verify_batch(tree.root(), &[]));
+        
+        // A wrong leaf anywhere fails the batch
+        let mut bad = items.clone();
+        bad[3].1 = b"wrong";
+        assert!(!MerkleProof::verify_batch(tree.root(), &bad));
+        
+        // A forged sibling is caught even where another path supplies the
+        // true node
+        let mut forged = proofs[1].clone();
+        forged.siblings[0] = [7u8; 32];
+        let mut bad = items.clone();
+        bad[1].0 = &forged;
+        assert!(!MerkleProof::verify_batch(tree.root(), &bad));
+    }
+    
+    #[test]
+    fn test_hiding_tree() {
+        use rand::rngs::OsRng;
+        
+        // A single bit per leaf would be trivially brute-forced without salts
+        let data: Vec<Vec<u8>> = (0..5).map(|i| vec![i % 2]).collect();
+        let (tree, salts) = MerkleTree::<Sha3_256Hasher>::new_hiding(&data, &mut OsRng).unwrap();
+        let plain = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+        assert_eq!(salts.len(), 5);
+        assert_ne!(tree.root(), plain.root());
+        assert_ne!(tree.leaves()[0], tree.leaves()[2]);
+        
+        for i in 0..5 {
+            let proof = tree.prove(i).unwrap();
+            assert!(proof.verify_hiding(tree.root(), &salts[i], &data[i]));
+            assert!(!proof.verify_hiding(tree.root(), &salts[i], &[1 - data[i][0]]));
+            assert!(!proof.verify_hiding(tree.root(), &salts[(i + 1) % 5], &data[i]));
+            assert!(!proof.verify(tree.root(), &data[i]));
+        }
+    }
+    
+    #[test]
+    fn test_update_proof() {
+        let mut data: Vec<Vec<u8>> = (0..6).map(|i| vec![i]).collect();
+        let mut tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+        let old_root = *tree.root();
+
+        let update = tree.set_leaf(4, b"reblinded").unwrap();
+        let new_root = *tree.root();
+        assert_ne!(old_root, new_root);
+        assert!(update.verify(&old_root, &new_root));
+        assert!(update.verify_data(&old_root, &data[4], &new_root, b"reblinded"));
+        assert!(!update.verify_data(&old_root, &data[3], &new_root, b"reblinded"));
+        assert!(!update.verify(&new_root, &old_root));
+
+        // The updated tree matches one built from scratch
+        data[4] = b"reblinded".to_vec();
+        let rebuilt = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+        assert_eq!(tree.root(), rebuilt.root());
+        assert!(tree.prove(4).unwrap().verify(tree.root(), b"reblinded"));
+
+        // Moving the change to another index breaks the link
+        let mut moved = update.clone();
+        moved.leaf_index = 5;
+        assert!(!moved.verify(&old_root, &new_root));
+        assert!(tree.set_leaf(6, b"x").is_err());
+    }
+
+    #[test]
+    fn test_multi_proof_is_minimal() {
+        let data: Vec<Vec<u8>> = (0..16).map(|i| vec![i]).collect();
+        let tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+
+        // Opening every leaf needs no siblings at all
+        let all: Vec<usize> = (0..16).collect();
+        assert!(tree.prove_batch(&all).unwrap().siblings.is_empty());
+
+        // 0 and 1 share every ancestor: only the level-1 to level-3 siblings
+        assert_eq!(tree.prove_batch(&[1, 0]).unwrap().siblings.len(), 3);
+
+        // 0..4 fills a height-2 subtree and needs only its level-2 sibling;
+        // 12 needs its siblings at levels 0 to 2
+        let proof = tree.prove_batch(&[0, 1, 2, 3, 12, 3]).unwrap();
+        assert_eq!(proof.leaf_indices, vec![0, 1, 2, 3, 12]);
+        assert_eq!(proof.siblings.len(), 4);
+        let leaves: Vec<(usize, &[u8])> = proof.leaf_indices.iter()
+            .map(|&i| (i, data[i].as_slice()))
+            .collect();
+        assert!(proof.verify(tree.root(), &leaves));
+    }
+
+    #[test]
+    fn test_multi_proof_rejects_malformed_openings() {
+        let data: Vec<Vec<u8>> = (0..5).map(|i| vec![i]).collect();
+        let tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+        let proof = tree.prove_batch(&[4, 2]).unwrap();
+
+        let leaves: Vec<(usize, &[u8])> = vec![(4, data[4].as_slice()), (2, data[2].as_slice())];
+        assert!(proof.verify(tree.root(), &leaves));
+
+        // Duplicated or foreign leaves
+        assert!(!proof.verify(tree.root(), &[(2, data[2].as_slice()), (2, data[2].as_slice())]));
+        assert!(!proof.verify(tree.root(), &[(2, data[2].as_slice()), (3, data[3].as_slice())]));
+
+        // Extra or missing siblings
+        let mut padded = proof.clone();
+        padded.siblings.push(padded.siblings[0]);
+        assert!(!padded.verify(tree.root(), &leaves));
+        let mut truncated = proof.clone();
+        truncated.siblings.pop();
+        assert!(!truncated.verify(tree.root(), &leaves));
     }
 }
\ No newline at end of file
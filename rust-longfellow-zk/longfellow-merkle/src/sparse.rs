@@ -0,0 +1,347 @@
+//! Sparse Merkle tree over 256-bit keys
+//!
+//! Every key names a leaf of a depth-256 tree; absent keys hold the padding
+//! leaf, so a proof for a key either opens its value (membership) or the
+//! padding leaf (non-membership). Only non-default nodes are stored: a
+//! subtree with no keys hashes to a per-height default that both prover and
+//! verifier compute. Proofs send a 256-bit mask plus the non-default
+//! siblings, which for n keys is about log2(n) hashes.
+
+use crate::hash::{Hasher, MERKLE_HASH_VERSION};
+use longfellow_core::{LongfellowError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+
+/// Key width in bits, and so the tree depth
+pub const SPARSE_DEPTH: usize = 256;
+
+/// A 256-bit key, big-endian: bit h from the least significant end picks
+/// the child at height h
+pub type SparseKey = [u8; 32];
+
+/// Default node hashes for heights 0..=SPARSE_DEPTH
+fn default_hashes<H: Hasher>() -> Vec<H::Output> {
+    let mut defaults = Vec::with_capacity(SPARSE_DEPTH + 1);
+    defaults.push(H::empty_hash());
+    for h in 0..SPARSE_DEPTH {
+        let d = &defaults[h];
+        defaults.push(H::hash_node(h, d, d));
+    }
+    defaults
+}
+
+/// Leaf hash binding the key to its value
+fn leaf_hash<H: Hasher>(key: &SparseKey, value: &[u8]) -> H::Output {
+    H::hash_leaf(&[key.as_slice(), value].concat())
+}
+
+fn is_right(index: &SparseKey) -> bool {
+    index[31] & 1 == 1
+}
+
+fn sibling_of(index: &SparseKey) -> SparseKey {
+    let mut sibling = *index;
+    sibling[31] ^= 1;
+    sibling
+}
+
+/// index >> 1 as a 256-bit big-endian integer
+fn parent_of(index: &SparseKey) -> SparseKey {
+    let mut parent = [0u8; 32];
+    let mut carry = 0u8;
+    for (p, &b) in parent.iter_mut().zip(index.iter()) {
+        *p = (b >> 1) | carry;
+        carry = b << 7;
+    }
+    parent
+}
+
+/// Sparse Merkle tree storing only the nodes above occupied keys
+#[derive(Clone, Debug)]
+pub struct SparseMerkleTree<H: Hasher> {
+    /// Values of present keys
+    leaves: BTreeMap<SparseKey, Vec<u8>>,
+    /// Non-default nodes by (height, index at that height)
+    nodes: HashMap<(usize, SparseKey), H::Output>,
+    /// Hash of an empty subtree per height
+    defaults: Vec<H::Output>,
+}
+
+impl<H: Hasher> SparseMerkleTree<H> {
+    /// An empty tree
+    pub fn new() -> Self {
+        Self {
+            leaves: BTreeMap::new(),
+            nodes: HashMap::new(),
+            defaults: default_hashes::<H>(),
+        }
+    }
+
+    /// Build a tree from key/value pairs; later duplicates win
+    pub fn from_entries<V: AsRef<[u8]>>(entries: &[(SparseKey, V)]) -> Self {
+        let mut tree = Self::new();
+        for (key, value) in entries {
+            tree.insert(*key, value.as_ref());
+        }
+        tree
+    }
+
+    /// Root of the empty tree
+    pub fn empty_root() -> H::Output {
+        default_hashes::<H>()[SPARSE_DEPTH].clone()
+    }
+
+    pub fn root(&self) -> &H::Output {
+        self.node(SPARSE_DEPTH, &[0u8; 32])
+    }
+
+    /// Number of present keys
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn get(&self, key: &SparseKey) -> Option<&[u8]> {
+        self.leaves.get(key).map(|v| v.as_slice())
+    }
+
+    pub fn contains(&self, key: &SparseKey) -> bool {
+        self.leaves.contains_key(key)
+    }
+
+    /// Set `key` to `value`, returning the previous value
+    pub fn insert(&mut self, key: SparseKey, value: &[u8]) -> Option<Vec<u8>> {
+        self.update_path(&key, leaf_hash::<H>(&key, value));
+        self.leaves.insert(key, value.to_vec())
+    }
+
+    /// Remove `key`, returning its value
+    pub fn remove(&mut self, key: &SparseKey) -> Option<Vec<u8>> {
+        let old = self.leaves.remove(key)?;
+        self.update_path(key, self.defaults[0].clone());
+        Some(old)
+    }
+
+    fn node(&self, height: usize, index: &SparseKey) -> &H::Output {
+        self.nodes
+            .get(&(height, *index))
+            .unwrap_or(&self.defaults[height])
+    }
+
+    fn set_node(&mut self, height: usize, index: SparseKey, hash: H::Output) {
+        if hash == self.defaults[height] {
+            self.nodes.remove(&(height, index));
+        } else {
+            self.nodes.insert((height, index), hash);
+        }
+    }
+
+    /// Rehash the path from the leaf of `key` to the root
+    fn update_path(&mut self, key: &SparseKey, leaf: H::Output) {
+        let mut index = *key;
+        let mut current = leaf;
+        self.set_node(0, index, current.clone());
+
+        for h in 0..SPARSE_DEPTH {
+            let sibling = self.node(h, &sibling_of(&index));
+            current = if is_right(&index) {
+                H::hash_node(h, sibling, &current)
+            } else {
+                H::hash_node(h, &current, sibling)
+            };
+            index = parent_of(&index);
+            self.set_node(h + 1, index, current.clone());
+        }
+    }
+
+    /// Opening of `key`: a membership proof if present, a non-membership
+    /// proof otherwise
+    pub fn prove(&self, key: &SparseKey) -> SparseMerkleProof<H> {
+        let mut mask = vec![0u8; SPARSE_DEPTH / 8];
+        let mut siblings = Vec::new();
+        let mut index = *key;
+
+        for h in 0..SPARSE_DEPTH {
+            if let Some(sibling) = self.nodes.get(&(h, sibling_of(&index))) {
+                mask[h / 8] |= 1 << (h % 8);
+                siblings.push(sibling.clone());
+            }
+            index = parent_of(&index);
+        }
+
+        SparseMerkleProof {
+            version: MERKLE_HASH_VERSION,
+            key: *key,
+            mask,
+            siblings,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<H: Hasher> Default for SparseMerkleTree<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Path from one key's leaf to the root, with default siblings elided
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SparseMerkleProof<H: Hasher> {
+    /// Hash encoding version; proofs from an older encoding are rejected
+    #[serde(default)]
+    pub version: u8,
+    /// The key being opened
+    pub key: SparseKey,
+    /// Bit h is set when the sibling at height h is in `siblings`
+    pub mask: Vec<u8>,
+    /// Non-default siblings from the leaf upwards
+    pub siblings: Vec<H::Output>,
+    #[serde(skip)]
+    pub _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> SparseMerkleProof<H> {
+    /// Root implied by `leaf` at this proof's key
+    fn compute_root(&self, leaf: H::Output) -> Result<H::Output> {
+        if self.version != MERKLE_HASH_VERSION {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Unsupported Merkle hash version {}",
+                self.version
+            )));
+        }
+        if self.mask.len() != SPARSE_DEPTH / 8 {
+            return Err(LongfellowError::InvalidParameter(
+                "Sparse proof mask must be 32 bytes".to_string(),
+            ));
+        }
+
+        let defaults = default_hashes::<H>();
+        let mut explicit = self.siblings.iter();
+        let mut index = self.key;
+        let mut current = leaf;
+
+        for (h, default) in defaults.iter().take(SPARSE_DEPTH).enumerate() {
+            let sibling = if self.mask[h / 8] >> (h % 8) & 1 == 1 {
+                explicit.next().ok_or_else(|| {
+                    LongfellowError::InvalidParameter("Sparse proof is missing siblings".to_string())
+                })?
+            } else {
+                default
+            };
+            current = if is_right(&index) {
+                H::hash_node(h, sibling, &current)
+            } else {
+                H::hash_node(h, &current, sibling)
+            };
+            index = parent_of(&index);
+        }
+
+        if explicit.next().is_some() {
+            return Err(LongfellowError::InvalidParameter(
+                "Sparse proof has unused siblings".to_string(),
+            ));
+        }
+        Ok(current)
+    }
+
+    /// Check that `key` maps to `value` under `root`
+    pub fn verify_membership(&self, root: &H::Output, value: &[u8]) -> bool {
+        self.compute_root(leaf_hash::<H>(&self.key, value))
+            .map(|r| &r == root)
+            .unwrap_or(false)
+    }
+
+    /// Check that `key` is absent under `root`
+    pub fn verify_non_membership(&self, root: &H::Output) -> bool {
+        self.compute_root(H::empty_hash())
+            .map(|r| &r == root)
+            .unwrap_or(false)
+    }
+
+    /// Number of explicit siblings
+    pub fn len(&self) -> usize {
+        self.siblings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.siblings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{Blake3Hasher, Sha256Hasher};
+
+    fn key(i: u8) -> SparseKey {
+        let mut k = [0u8; 32];
+        k[0] = i.wrapping_mul(37);
+        k[31] = i;
+        k
+    }
+
+    #[test]
+    fn test_membership_and_non_membership() {
+        let mut tree = SparseMerkleTree::<Sha256Hasher>::new();
+        for i in 0..20u8 {
+            tree.insert(key(i), &[i; 3]);
+        }
+        let root = tree.root().clone();
+
+        for i in 0..20u8 {
+            let proof = tree.prove(&key(i));
+            assert!(proof.verify_membership(&root, &[i; 3]));
+            assert!(!proof.verify_membership(&root, &[i; 4]));
+            assert!(!proof.verify_non_membership(&root));
+        }
+
+        let absent = tree.prove(&key(200));
+        assert!(absent.verify_non_membership(&root));
+        assert!(!absent.verify_membership(&root, &[]));
+        assert!(absent.len() <= 8);
+    }
+
+    #[test]
+    fn test_root_is_order_independent_and_removable() {
+        let entries: Vec<(SparseKey, Vec<u8>)> = (0..10u8).map(|i| (key(i), vec![i])).collect();
+        let forward = SparseMerkleTree::<Blake3Hasher>::from_entries(&entries);
+
+        let mut backward = SparseMerkleTree::<Blake3Hasher>::new();
+        for (k, v) in entries.iter().rev() {
+            backward.insert(*k, v);
+        }
+        assert_eq!(forward.root(), backward.root());
+
+        for (k, _) in &entries {
+            assert!(backward.remove(k).is_some());
+        }
+        assert!(backward.is_empty());
+        assert_eq!(backward.root(), &SparseMerkleTree::<Blake3Hasher>::empty_root());
+    }
+
+    #[test]
+    fn test_tampered_proofs_are_rejected() {
+        let mut tree = SparseMerkleTree::<Sha256Hasher>::new();
+        tree.insert(key(1), b"one");
+        tree.insert(key(2), b"two");
+        let root = tree.root().clone();
+
+        let mut proof = tree.prove(&key(1));
+        proof.siblings[0] = [0u8; 32];
+        assert!(!proof.verify_membership(&root, b"one"));
+
+        let mut proof = tree.prove(&key(1));
+        proof.mask[0] ^= 2;
+        assert!(!proof.verify_membership(&root, b"one"));
+
+        let mut proof = tree.prove(&key(1));
+        proof.key = key(3);
+        assert!(!proof.verify_membership(&root, b"one"));
+        assert!(!proof.verify_non_membership(&root));
+    }
+}
@@ -0,0 +1,422 @@
+//! Binary persistence for Merkle trees
+//!
+//! A committed tree is written once and reopened by later proof sessions
+//! without rehashing its leaves. The format is a fixed header followed by
+//! every level, root first, as raw digests:
+//!
+//! ```text
+//! magic "LFMT" | format u8 | hash version u8 | digest len u32 LE |
+//! leaves u64 LE | level d (root) | level d-1 | ... | level 0 (leaves)
+//! ```
+//!
+//! Level sizes follow from the leaf count, so `StoredMerkleTree` can seek
+//! straight to any node. It reads the root at open time and nothing else;
+//! single proofs read their siblings one digest at a time, and `load_level`
+//! pulls a whole level into memory when many openings will hit it.
+
+use crate::{Hasher, MerkleProof, MerkleTree, MultiProof, MERKLE_HASH_VERSION};
+use longfellow_core::{LongfellowError, Result};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+const MAGIC: &[u8; 4] = b"LFMT";
+const FORMAT_VERSION: u8 = 1;
+const HEADER_LEN: u64 = 4 + 1 + 1 + 4 + 8;
+
+/// Digest width of `H`, fixed for every hasher in this crate
+pub(crate) fn digest_len<H: Hasher>() -> usize {
+    H::empty_hash().as_ref().len()
+}
+
+fn digest_from_bytes<H: Hasher>(bytes: &[u8]) -> Result<H::Output>
+where
+    H::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    H::Output::try_from(bytes).map_err(|_| {
+        LongfellowError::SerializationError("Stored digest has the wrong length".to_string())
+    })
+}
+
+/// Header fields of a stored tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Header {
+    pub(crate) digest_len: usize,
+    pub(crate) num_leaves: usize,
+}
+
+impl Header {
+    pub(crate) fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION, MERKLE_HASH_VERSION])?;
+        writer.write_all(&(self.digest_len as u32).to_le_bytes())?;
+        writer.write_all(&(self.num_leaves as u64).to_le_bytes())?;
+        Ok(())
+    }
+
+    fn read<H: Hasher, R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0u8; HEADER_LEN as usize];
+        reader.read_exact(&mut bytes)?;
+
+        if &bytes[..4] != MAGIC {
+            return Err(LongfellowError::SerializationError(
+                "Not a stored Merkle tree".to_string(),
+            ));
+        }
+        if bytes[4] != FORMAT_VERSION {
+            return Err(LongfellowError::SerializationError(format!(
+                "Unsupported tree format version {}",
+                bytes[4]
+            )));
+        }
+        if bytes[5] != MERKLE_HASH_VERSION {
+            return Err(LongfellowError::SerializationError(format!(
+                "Tree was hashed with encoding version {}",
+                bytes[5]
+            )));
+        }
+
+        let stored_len = u32::from_le_bytes(bytes[6..10].try_into().unwrap()) as usize;
+        let num_leaves = u64::from_le_bytes(bytes[10..18].try_into().unwrap()) as usize;
+        if stored_len != digest_len::<H>() {
+            return Err(LongfellowError::SerializationError(format!(
+                "Stored digests are {} bytes, hasher produces {}",
+                stored_len,
+                digest_len::<H>()
+            )));
+        }
+        if num_leaves == 0 || num_leaves > 1 << 48 {
+            return Err(LongfellowError::SerializationError(format!(
+                "Invalid stored leaf count {}",
+                num_leaves
+            )));
+        }
+
+        Ok(Self {
+            digest_len: stored_len,
+            num_leaves,
+        })
+    }
+
+    /// Number of levels including the leaves and the root
+    pub(crate) fn height(&self) -> usize {
+        self.num_leaves.next_power_of_two().trailing_zeros() as usize + 1
+    }
+
+    pub(crate) fn level_len(&self, level: usize) -> usize {
+        self.num_leaves.next_power_of_two() >> level
+    }
+
+    /// Byte offset of `level`; levels are stored root first
+    pub(crate) fn level_offset(&self, level: usize) -> u64 {
+        // Levels above `level` hold 1 + 2 + ... + 2^(d - level - 1) nodes
+        let above = (1u64 << (self.height() - 1 - level)) - 1;
+        HEADER_LEN + above * self.digest_len as u64
+    }
+}
+
+impl<H: Hasher> MerkleTree<H> {
+    /// Write the tree in the binary format described in `store`
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let header = Header {
+            digest_len: digest_len::<H>(),
+            num_leaves: self.num_leaves(),
+        };
+        header.write(writer)?;
+
+        for level in (0..self.height()).rev() {
+            for node in &self.nodes[level] {
+                writer.write_all(node.as_ref())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Save the tree to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read a whole tree written by `write_to`
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self>
+    where
+        H::Output: for<'a> TryFrom<&'a [u8]>,
+    {
+        let header = Header::read::<H, R>(reader)?;
+
+        let mut nodes = vec![Vec::new(); header.height()];
+        for level in (0..header.height()).rev() {
+            nodes[level] = read_level::<H, R>(reader, &header, level)?;
+        }
+
+        Ok(Self {
+            nodes,
+            num_leaves: header.num_leaves,
+            _hasher: PhantomData,
+        })
+    }
+
+    /// Load a tree saved with `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self>
+    where
+        H::Output: for<'a> TryFrom<&'a [u8]>,
+    {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+fn read_level<H: Hasher, R: Read>(reader: &mut R, header: &Header, level: usize) -> Result<Vec<H::Output>>
+where
+    H::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    let mut bytes = vec![0u8; header.level_len(level) * header.digest_len];
+    reader.read_exact(&mut bytes)?;
+    bytes
+        .chunks(header.digest_len)
+        .map(digest_from_bytes::<H>)
+        .collect()
+}
+
+/// A stored tree opened for proving, with levels read on demand
+pub struct StoredMerkleTree<H: Hasher, R = BufReader<File>> {
+    header: Header,
+    reader: Mutex<R>,
+    root: H::Output,
+    /// Levels pulled in by `load_level`, leaves at index 0
+    levels: Vec<OnceLock<Vec<H::Output>>>,
+}
+
+impl<H: Hasher> StoredMerkleTree<H> {
+    /// Open a tree saved with `MerkleTree::save`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self>
+    where
+        H::Output: for<'a> TryFrom<&'a [u8]>,
+    {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<H: Hasher, R: Read + Seek> StoredMerkleTree<H, R>
+where
+    H::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    /// Open a stored tree from any seekable source; reads the header and
+    /// the root
+    pub fn from_reader(mut reader: R) -> Result<Self> {
+        reader.seek(SeekFrom::Start(0))?;
+        let header = Header::read::<H, R>(&mut reader)?;
+
+        let mut root = vec![0u8; header.digest_len];
+        reader.read_exact(&mut root)?;
+        let root = digest_from_bytes::<H>(&root)?;
+
+        // Reject truncated files up front rather than on some later proof
+        let end = reader.seek(SeekFrom::End(0))?;
+        if end != header.level_offset(0) + (header.level_len(0) * header.digest_len) as u64 {
+            return Err(LongfellowError::SerializationError(
+                "Stored tree has the wrong length".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            levels: (0..header.height()).map(|_| OnceLock::new()).collect(),
+            header,
+            reader: Mutex::new(reader),
+            root,
+        })
+    }
+
+    pub fn root(&self) -> &H::Output {
+        &self.root
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.header.num_leaves
+    }
+
+    pub fn height(&self) -> usize {
+        self.header.height()
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let mut reader = self.reader.lock().map_err(|_| {
+            LongfellowError::Other("Stored tree reader poisoned".to_string())
+        })?;
+        let mut bytes = vec![0u8; len];
+        reader.seek(SeekFrom::Start(offset))?;
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Read a whole level into memory (once) and return it
+    pub fn load_level(&self, level: usize) -> Result<&[H::Output]> {
+        if level >= self.height() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Level {} out of range",
+                level
+            )));
+        }
+        if let Some(nodes) = self.levels[level].get() {
+            return Ok(nodes);
+        }
+
+        let len = self.header.level_len(level) * self.header.digest_len;
+        let bytes = self.read_at(self.header.level_offset(level), len)?;
+        let nodes = bytes
+            .chunks(self.header.digest_len)
+            .map(digest_from_bytes::<H>)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.levels[level].get_or_init(|| nodes))
+    }
+
+    /// One node, from memory if its level is loaded and from the file
+    /// otherwise
+    pub fn get_node(&self, level: usize, index: usize) -> Result<H::Output> {
+        if level >= self.height() || index >= self.header.level_len(level) {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Node ({}, {}) out of range",
+                level, index
+            )));
+        }
+        if let Some(nodes) = self.levels[level].get() {
+            return Ok(nodes[index].clone());
+        }
+
+        let offset = self.header.level_offset(level) + (index * self.header.digest_len) as u64;
+        digest_from_bytes::<H>(&self.read_at(offset, self.header.digest_len)?)
+    }
+
+    /// Proof for one leaf, reading one digest per level
+    pub fn prove(&self, index: usize) -> Result<MerkleProof<H>> {
+        if index >= self.num_leaves() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Leaf index {} out of range",
+                index
+            )));
+        }
+
+        let siblings = (0..self.height() - 1)
+            .map(|level| self.get_node(level, (index >> level) ^ 1))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(MerkleProof {
+            version: MERKLE_HASH_VERSION,
+            leaf_index: index,
+            siblings,
+            _hasher: PhantomData,
+        })
+    }
+
+    /// Batch proof; loads every level, since a large batch touches most of
+    /// the tree anyway
+    pub fn prove_batch(&self, indices: &[usize]) -> Result<MultiProof<H>> {
+        self.to_tree()?.prove_batch(indices)
+    }
+
+    /// Load every level into an in-memory tree
+    pub fn to_tree(&self) -> Result<MerkleTree<H>> {
+        let nodes = (0..self.height())
+            .map(|level| self.load_level(level).map(|nodes| nodes.to_vec()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(MerkleTree {
+            nodes,
+            num_leaves: self.num_leaves(),
+            _hasher: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{Blake3Hasher, Sha256Hasher, Sha3_256Hasher};
+    use crate::Fp128PoseidonHasher;
+    use std::io::Cursor;
+
+    fn stored<H: Hasher>(tree: &MerkleTree<H>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        tree.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<Vec<u8>> = (0..13u8).map(|i| vec![i; 5]).collect();
+        let tree = MerkleTree::<Blake3Hasher>::new(&data).unwrap();
+        let bytes = stored(&tree);
+        assert_eq!(bytes.len() as u64, HEADER_LEN + 31 * 32);
+
+        let loaded = MerkleTree::<Blake3Hasher>::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.root(), tree.root());
+        assert_eq!(loaded.num_leaves(), 13);
+        assert_eq!(loaded.leaves(), tree.leaves());
+
+        // Variable-length digests go through the same path
+        let tree = MerkleTree::<Fp128PoseidonHasher>::new(&data).unwrap();
+        let loaded = MerkleTree::<Fp128PoseidonHasher>::read_from(&mut stored(&tree).as_slice()).unwrap();
+        assert_eq!(loaded.root(), tree.root());
+    }
+
+    #[test]
+    fn test_lazy_proofs_match_in_memory() {
+        let data: Vec<Vec<u8>> = (0..100u8).map(|i| vec![i]).collect();
+        let tree = MerkleTree::<Sha3_256Hasher>::new(&data).unwrap();
+        let store = StoredMerkleTree::<Sha3_256Hasher, _>::from_reader(Cursor::new(stored(&tree))).unwrap();
+
+        assert_eq!(store.root(), tree.root());
+        assert!(store.levels.iter().all(|l| l.get().is_none()));
+
+        for i in [0, 37, 99] {
+            let proof = store.prove(i).unwrap();
+            assert_eq!(proof.siblings, tree.prove(i).unwrap().siblings);
+            assert!(proof.verify(store.root(), &data[i]));
+        }
+        assert!(store.levels.iter().all(|l| l.get().is_none()));
+
+        store.load_level(0).unwrap();
+        assert_eq!(store.prove(64).unwrap().siblings, tree.prove(64).unwrap().siblings);
+        assert!(store.prove(100).is_err());
+
+        let batch = store.prove_batch(&[3, 4, 90]).unwrap();
+        let leaves: Vec<(usize, &[u8])> = [3, 4, 90].iter().map(|&i| (i, data[i].as_slice())).collect();
+        assert!(batch.verify(tree.root(), &leaves));
+    }
+
+    #[test]
+    fn test_rejects_foreign_and_truncated_files() {
+        let tree = MerkleTree::<Sha256Hasher>::new(&[b"a", b"b", b"c"]).unwrap();
+        let bytes = stored(&tree);
+
+        // Wrong digest width
+        assert!(MerkleTree::<crate::hash::Sha3_512Hasher>::read_from(&mut bytes.as_slice()).is_err());
+
+        let truncated = bytes[..bytes.len() - 1].to_vec();
+        assert!(MerkleTree::<Sha256Hasher>::read_from(&mut truncated.as_slice()).is_err());
+        assert!(StoredMerkleTree::<Sha256Hasher, _>::from_reader(Cursor::new(truncated)).is_err());
+
+        let mut bad_magic = bytes;
+        bad_magic[0] ^= 1;
+        assert!(MerkleTree::<Sha256Hasher>::read_from(&mut bad_magic.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_save_and_open() {
+        let data: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; 3]).collect();
+        let tree = MerkleTree::<Sha256Hasher>::new(&data).unwrap();
+        let path = std::env::temp_dir().join(format!("longfellow-merkle-{}.tree", std::process::id()));
+
+        tree.save(&path).unwrap();
+        let store = StoredMerkleTree::<Sha256Hasher>::open(&path).unwrap();
+        assert_eq!(store.root(), tree.root());
+        assert!(store.prove(5).unwrap().verify(tree.root(), &data[5]));
+        assert_eq!(MerkleTree::<Sha256Hasher>::load(&path).unwrap().root(), tree.root());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+//! Streaming Merkle construction for trees larger than memory
+//!
+//! `StreamingMerkleBuilder` takes leaves one at a time and keeps only the
+//! pending left child at each height, plus a small write buffer per level.
+//! Every node is written straight into the `store` format at its final
+//! offset (level positions follow from the leaf count, given up front), so
+//! the finished file opens with `StoredMerkleTree` for proving and is
+//! byte-identical to `MerkleTree::save` on the same leaves.
+
+use crate::store::{digest_len, Header};
+use crate::Hasher;
+use longfellow_core::{LongfellowError, Result};
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Bytes buffered per level before they are written out
+const LEVEL_BUFFER: usize = 1 << 16;
+
+/// Builds a stored tree from a stream of leaves in O(log n) memory
+pub struct StreamingMerkleBuilder<H: Hasher, W: Write + Seek> {
+    writer: W,
+    header: Header,
+    /// Left child waiting for its sibling, per height
+    pending: Vec<Option<H::Output>>,
+    /// Nodes already written out, per level
+    written: Vec<usize>,
+    /// Encoded nodes not yet written, per level
+    buffers: Vec<Vec<u8>>,
+    /// Leaves pushed so far
+    pushed: usize,
+}
+
+impl<H: Hasher, W: Write + Seek> StreamingMerkleBuilder<H, W> {
+    /// Start a tree of exactly `num_leaves` leaves in `writer`
+    pub fn new(mut writer: W, num_leaves: usize) -> Result<Self> {
+        if num_leaves == 0 {
+            return Err(LongfellowError::InvalidParameter(
+                "Cannot create Merkle tree with no data".to_string()
+            ));
+        }
+
+        let header = Header {
+            digest_len: digest_len::<H>(),
+            num_leaves,
+        };
+        writer.seek(SeekFrom::Start(0))?;
+        header.write(&mut writer)?;
+
+        let height = header.height();
+        Ok(Self {
+            writer,
+            header,
+            pending: vec![None; height],
+            written: vec![0; height],
+            buffers: vec![Vec::new(); height],
+            pushed: 0,
+        })
+    }
+
+    /// Append the next leaf
+    pub fn push(&mut self, data: &[u8]) -> Result<()> {
+        if self.pushed == self.header.num_leaves {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Tree was declared with {} leaves",
+                self.header.num_leaves
+            )));
+        }
+        self.pushed += 1;
+        self.push_hash(H::hash_leaf(data))
+    }
+
+    /// Append a leaf hash and every parent it completes
+    fn push_hash(&mut self, leaf: H::Output) -> Result<()> {
+        self.emit(0, &leaf)?;
+
+        let mut carry = leaf;
+        let mut height = 0;
+        while let Some(left) = self.pending[height].take() {
+            carry = H::hash_node(height, &left, &carry);
+            height += 1;
+            self.emit(height, &carry)?;
+        }
+        self.pending[height] = Some(carry);
+        Ok(())
+    }
+
+    fn emit(&mut self, level: usize, node: &H::Output) -> Result<()> {
+        self.buffers[level].extend_from_slice(node.as_ref());
+        if self.buffers[level].len() >= LEVEL_BUFFER {
+            self.flush_level(level)?;
+        }
+        Ok(())
+    }
+
+    fn flush_level(&mut self, level: usize) -> Result<()> {
+        let buffer = &mut self.buffers[level];
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        let offset = self.header.level_offset(level)
+            + (self.written[level] * self.header.digest_len) as u64;
+        self.writer.seek(SeekFrom::Start(offset))?;
+        self.writer.write_all(buffer)?;
+        self.written[level] += buffer.len() / self.header.digest_len;
+        buffer.clear();
+        Ok(())
+    }
+
+    /// Pad to a power of two, write out every buffered node and return the
+    /// root
+    pub fn finish(mut self) -> Result<H::Output> {
+        if self.pushed != self.header.num_leaves {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Tree was declared with {} leaves but {} were pushed",
+                self.header.num_leaves, self.pushed
+            )));
+        }
+
+        let empty = H::empty_hash();
+        for _ in self.pushed..self.header.level_len(0) {
+            self.push_hash(empty.clone())?;
+        }
+        for level in 0..self.header.height() {
+            self.flush_level(level)?;
+        }
+        self.writer.flush()?;
+
+        let root = self.pending[self.header.height() - 1].take();
+        Ok(root.expect("a full tree leaves exactly the root pending"))
+    }
+}
+
+impl<H: Hasher> StreamingMerkleBuilder<H, File> {
+    /// Build the tree over `leaves` into a file at `path`, returning the
+    /// root; open the file with `StoredMerkleTree::open` to prove
+    pub fn build_file<P, I, T>(path: P, num_leaves: usize, leaves: I) -> Result<H::Output>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut builder = Self::new(File::create(path)?, num_leaves)?;
+        for leaf in leaves {
+            builder.push(leaf.as_ref())?;
+        }
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{Blake3Hasher, Sha256Hasher};
+    use crate::{MerkleTree, StoredMerkleTree};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_streaming_matches_in_memory() {
+        for n in [1, 2, 7, 64, 1000] {
+            let data: Vec<Vec<u8>> = (0..n as u32).map(|i| i.to_le_bytes().to_vec()).collect();
+            let tree = MerkleTree::<Blake3Hasher>::new(&data).unwrap();
+
+            let mut expected = Vec::new();
+            tree.write_to(&mut expected).unwrap();
+
+            let mut cursor = Cursor::new(Vec::new());
+            let mut builder = StreamingMerkleBuilder::<Blake3Hasher, _>::new(&mut cursor, n).unwrap();
+            for leaf in &data {
+                builder.push(leaf).unwrap();
+            }
+            assert_eq!(&builder.finish().unwrap(), tree.root());
+            assert_eq!(cursor.into_inner(), expected);
+        }
+    }
+
+    #[test]
+    fn test_build_file_opens_as_stored_tree() {
+        let path = std::env::temp_dir().join(format!("longfellow-stream-{}.tree", std::process::id()));
+        let leaves = (0..300u32).map(|i| i.to_be_bytes());
+        let root = StreamingMerkleBuilder::<Sha256Hasher, File>::build_file(&path, 300, leaves).unwrap();
+
+        let stored = StoredMerkleTree::<Sha256Hasher>::open(&path).unwrap();
+        assert_eq!(stored.root(), &root);
+        assert!(stored.prove(123).unwrap().verify(&root, &123u32.to_be_bytes()));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_leaf_count_is_enforced() {
+        let mut builder = StreamingMerkleBuilder::<Sha256Hasher, _>::new(Cursor::new(Vec::new()), 2).unwrap();
+        builder.push(b"a").unwrap();
+        builder.push(b"b").unwrap();
+        assert!(builder.push(b"c").is_err());
+
+        let mut builder = StreamingMerkleBuilder::<Sha256Hasher, _>::new(Cursor::new(Vec::new()), 3).unwrap();
+        builder.push(b"a").unwrap();
+        assert!(builder.finish().is_err());
+    }
+}
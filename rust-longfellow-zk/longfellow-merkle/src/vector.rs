@@ -0,0 +1,112 @@
+//! Vector commitments: commit to a list of byte strings, open any subset
+//!
+//! `VectorCommitment` is the interface a prover needs from its column
+//! commitment: commit once, open the positions the verifier samples, and
+//! check an opening against the commitment. `MerkleVectorCommitment` backs
+//! it with a `MerkleTree` and `MultiProof`; with the `pairing` feature,
+//! `kzg::KzgVectorCommitment` gives constant-size commitments and openings
+//! at the cost of a trusted setup and pairing checks.
+
+use crate::{Hasher, MerkleTree, MultiProof};
+use longfellow_core::{LongfellowError, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// A commitment scheme over vectors of byte strings
+///
+/// Openings are for sets of distinct positions; `verify` takes the opened
+/// positions and their values in matching order.
+pub trait VectorCommitment: Send + Sync {
+    /// What the verifier receives up front
+    type Commitment: Clone + Send + Sync + Serialize + DeserializeOwned;
+    /// Proof that the opened values sit at their positions
+    type Opening: Clone + Send + Sync + Serialize + DeserializeOwned;
+    /// State the committer keeps to produce openings
+    type ProverData: Send + Sync;
+
+    /// Commit to `values`
+    fn commit<T: AsRef<[u8]> + Sync>(&self, values: &[T]) -> Result<(Self::Commitment, Self::ProverData)>;
+
+    /// Open the values at `indices`
+    fn open(&self, data: &Self::ProverData, indices: &[usize]) -> Result<Self::Opening>;
+
+    /// Check that `values[i]` is committed at `indices[i]` for every i
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        indices: &[usize],
+        values: &[&[u8]],
+        opening: &Self::Opening,
+    ) -> bool;
+}
+
+/// Merkle tree commitment with octopus multi-openings
+#[derive(Clone, Debug, Default)]
+pub struct MerkleVectorCommitment<H: Hasher> {
+    _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> MerkleVectorCommitment<H> {
+    pub fn new() -> Self {
+        Self { _hasher: PhantomData }
+    }
+}
+
+impl<H: Hasher> VectorCommitment for MerkleVectorCommitment<H> {
+    type Commitment = H::Output;
+    type Opening = MultiProof<H>;
+    type ProverData = MerkleTree<H>;
+
+    fn commit<T: AsRef<[u8]> + Sync>(&self, values: &[T]) -> Result<(H::Output, MerkleTree<H>)> {
+        let tree = MerkleTree::<H>::new(values)?;
+        Ok((tree.root().clone(), tree))
+    }
+
+    fn open(&self, tree: &MerkleTree<H>, indices: &[usize]) -> Result<MultiProof<H>> {
+        if let Some(&index) = indices.iter().find(|&&i| i >= tree.num_leaves()) {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Leaf index {} out of range", index)
+            ));
+        }
+        MultiProof::create(tree, indices)
+    }
+
+    fn verify(
+        &self,
+        root: &H::Output,
+        indices: &[usize],
+        values: &[&[u8]],
+        opening: &MultiProof<H>,
+    ) -> bool {
+        if indices.len() != values.len() {
+            return false;
+        }
+        let leaves: Vec<(usize, &[u8])> = indices.iter().copied().zip(values.iter().copied()).collect();
+        opening.verify(root, &leaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::Sha256Hasher;
+
+    #[test]
+    fn test_merkle_vector_commitment() {
+        let scheme = MerkleVectorCommitment::<Sha256Hasher>::new();
+        let values: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; 3]).collect();
+        let (root, tree) = scheme.commit(&values).unwrap();
+
+        let indices = [17, 2, 3];
+        let opened: Vec<&[u8]> = indices.iter().map(|&i| values[i].as_slice()).collect();
+        let opening = scheme.open(&tree, &indices).unwrap();
+        assert!(scheme.verify(&root, &indices, &opened, &opening));
+
+        // Values must stay at their positions
+        let swapped = [opened[1], opened[0], opened[2]];
+        assert!(!scheme.verify(&root, &indices, &swapped, &opening));
+        assert!(!scheme.verify(&root, &indices[..2], &opened[..2], &opening));
+        assert!(scheme.open(&tree, &[20]).is_err());
+    }
+}
@@ -231,7 +231,7 @@ fn test_compressed_proof() {
     let multiproof = tree.prove_batch(&indices).unwrap();
     
     // Create compressed version
-    let compressed = CompressedProof::from_multiproof(&multiproof, tree.height());
+    let compressed = CompressedProof::from_multiproof(&multiproof);
     
     // Check size reduction
     let multiproof_size = multiproof.size_bytes();
@@ -0,0 +1,195 @@
+//! Transcript labels and the registry of every label in use
+//!
+//! `Transcript` only accepts `TranscriptLabel`s, and every label a protocol
+//! appends under is declared here and listed in `REGISTRY`. A const
+//! assertion rejects the build if two entries share a name, so two
+//! protocol phases cannot silently absorb or squeeze under the same label.
+//! Per-round labels are one registered name with an index attached by
+//! `TranscriptLabel::at`.
+
+/// A registered transcript label, optionally indexed by round or position
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TranscriptLabel {
+    name: &'static str,
+    index: Option<u64>,
+}
+
+impl TranscriptLabel {
+    /// Declare a label; it must also be listed in `REGISTRY`
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, index: None }
+    }
+
+    /// The same label for round or position `index`
+    pub const fn at(self, index: u64) -> Self {
+        Self {
+            name: self.name,
+            index: Some(index),
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub const fn index(&self) -> Option<u64> {
+        self.index
+    }
+
+    /// Bytes absorbed for this label: the name, then 0x00 and the index
+    /// in little-endian if indexed
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = self.name.as_bytes().to_vec();
+        if let Some(index) = self.index {
+            bytes.push(0);
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+// Transcript framing
+pub const CHALLENGE: TranscriptLabel = TranscriptLabel::new("challenge");
+pub const CHALLENGES: TranscriptLabel = TranscriptLabel::new("challenges");
+pub const INDICES: TranscriptLabel = TranscriptLabel::new("indices");
+pub const INDEX_COUNT: TranscriptLabel = TranscriptLabel::new("index_count");
+pub const INDEX_BLOCK: TranscriptLabel = TranscriptLabel::new("index_block");
+pub const FORK: TranscriptLabel = TranscriptLabel::new("fork");
+pub const PROOF: TranscriptLabel = TranscriptLabel::new("proof");
+
+// Ligero
+pub const LIGERO_INSTANCE: TranscriptLabel = TranscriptLabel::new("ligero/instance");
+pub const LIGERO_PUBLIC_INPUTS: TranscriptLabel = TranscriptLabel::new("ligero/public_inputs");
+pub const LIGERO_ROOT: TranscriptLabel = TranscriptLabel::new("ligero/root");
+pub const LIGERO_LDT: TranscriptLabel = TranscriptLabel::new("ligero/ldt");
+pub const LIGERO_LINEAR_COMB: TranscriptLabel = TranscriptLabel::new("ligero/linear_comb");
+pub const LIGERO_COL_INDEX: TranscriptLabel = TranscriptLabel::new("ligero/col_index");
+pub const LIGERO_LDT_RESPONSE: TranscriptLabel = TranscriptLabel::new("ligero/ldt_response");
+pub const LIGERO_LINEAR_RESPONSE: TranscriptLabel = TranscriptLabel::new("ligero/linear_response");
+pub const LIGERO_QUADRATIC_RESPONSE: TranscriptLabel = TranscriptLabel::new("ligero/quadratic_response");
+pub const LIGERO_FRI_ROOT: TranscriptLabel = TranscriptLabel::new("ligero/fri_root");
+pub const LIGERO_FRI_FOLD: TranscriptLabel = TranscriptLabel::new("ligero/fri_fold");
+pub const LIGERO_FRI_FINAL: TranscriptLabel = TranscriptLabel::new("ligero/fri_final");
+pub const LIGERO_FRI_QUERY: TranscriptLabel = TranscriptLabel::new("ligero/fri_query");
+pub const LIGERO_STATEMENT_RANDOMNESS: TranscriptLabel = TranscriptLabel::new("ligero/statement_randomness");
+pub const LIGERO_IPA_ROOT: TranscriptLabel = TranscriptLabel::new("ligero/ipa_root");
+pub const LIGERO_IPA_BATCH: TranscriptLabel = TranscriptLabel::new("ligero/ipa_batch");
+
+// Sumcheck
+pub const SUMCHECK_INSTANCE: TranscriptLabel = TranscriptLabel::new("sumcheck/instance");
+pub const SUMCHECK_NUM_LAYERS: TranscriptLabel = TranscriptLabel::new("sumcheck/num_layers");
+pub const SUMCHECK_NUM_COPIES: TranscriptLabel = TranscriptLabel::new("sumcheck/num_copies");
+pub const SUMCHECK_CLAIMED_SUM: TranscriptLabel = TranscriptLabel::new("sumcheck/claimed_sum");
+pub const SUMCHECK_POLY: TranscriptLabel = TranscriptLabel::new("sumcheck/poly");
+pub const SUMCHECK_WIRE_CLAIMS: TranscriptLabel = TranscriptLabel::new("sumcheck/wire_claims");
+pub const SUMCHECK_BIND: TranscriptLabel = TranscriptLabel::new("sumcheck/bind");
+pub const SUMCHECK_BINDS: TranscriptLabel = TranscriptLabel::new("sumcheck/binds");
+pub const SUMCHECK_COMBINE: TranscriptLabel = TranscriptLabel::new("sumcheck/combine");
+pub const SUMCHECK_OUTPUT_BINDING: TranscriptLabel = TranscriptLabel::new("sumcheck/output_binding");
+
+// Combined proofs
+pub const ZK_STATEMENT: TranscriptLabel = TranscriptLabel::new("zk/statement");
+
+/// Every label any protocol in the workspace uses
+pub const REGISTRY: &[TranscriptLabel] = &[
+    CHALLENGE,
+    CHALLENGES,
+    INDICES,
+    INDEX_COUNT,
+    INDEX_BLOCK,
+    FORK,
+    PROOF,
+    LIGERO_INSTANCE,
+    LIGERO_PUBLIC_INPUTS,
+    LIGERO_ROOT,
+    LIGERO_LDT,
+    LIGERO_LINEAR_COMB,
+    LIGERO_COL_INDEX,
+    LIGERO_LDT_RESPONSE,
+    LIGERO_LINEAR_RESPONSE,
+    LIGERO_QUADRATIC_RESPONSE,
+    LIGERO_FRI_ROOT,
+    LIGERO_FRI_FOLD,
+    LIGERO_FRI_FINAL,
+    LIGERO_FRI_QUERY,
+    LIGERO_STATEMENT_RANDOMNESS,
+    LIGERO_IPA_ROOT,
+    LIGERO_IPA_BATCH,
+    SUMCHECK_INSTANCE,
+    SUMCHECK_NUM_LAYERS,
+    SUMCHECK_NUM_COPIES,
+    SUMCHECK_CLAIMED_SUM,
+    SUMCHECK_POLY,
+    SUMCHECK_WIRE_CLAIMS,
+    SUMCHECK_BIND,
+    SUMCHECK_BINDS,
+    SUMCHECK_COMBINE,
+    SUMCHECK_OUTPUT_BINDING,
+    ZK_STATEMENT,
+];
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Names are non-empty, free of the 0x00 index separator and pairwise
+/// distinct
+const fn registry_is_valid(labels: &[TranscriptLabel]) -> bool {
+    let mut i = 0;
+    while i < labels.len() {
+        let name = labels[i].name.as_bytes();
+        if name.is_empty() || labels[i].index.is_some() {
+            return false;
+        }
+        let mut k = 0;
+        while k < name.len() {
+            if name[k] == 0 {
+                return false;
+            }
+            k += 1;
+        }
+        let mut j = i + 1;
+        while j < labels.len() {
+            if bytes_eq(name, labels[j].name.as_bytes()) {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+const _: () = assert!(registry_is_valid(REGISTRY), "transcript label registry has a duplicate or malformed entry");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_registry_has_no_collisions() {
+        let encodings: HashSet<Vec<u8>> = REGISTRY.iter().map(|l| l.encode()).collect();
+        assert_eq!(encodings.len(), REGISTRY.len());
+
+        assert!(!registry_is_valid(&[LIGERO_LDT, TranscriptLabel::new("ligero/ldt")]));
+        assert!(!registry_is_valid(&[TranscriptLabel::new("bad\0name")]));
+    }
+
+    #[test]
+    fn test_indexed_labels_are_distinct() {
+        assert_ne!(SUMCHECK_POLY.at(1).encode(), SUMCHECK_POLY.at(2).encode());
+        assert_ne!(SUMCHECK_POLY.at(0).encode(), SUMCHECK_POLY.encode());
+        assert_eq!(SUMCHECK_POLY.at(3).name(), SUMCHECK_POLY.name());
+    }
+}
@@ -3,13 +3,43 @@
 use longfellow_algebra::traits::Field;
 use rand::{CryptoRng, RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
+use longfellow_core::{LongfellowError, Result};
+use longfellow_util::crypto::rfc6979_nonce;
+use sha2::Sha256;
 use sha3::{Digest, Sha3_256};
+use std::collections::HashSet;
 use std::marker::PhantomData;
-use zeroize::Zeroize;
+use std::ops::Range;
+use zeroize::{Zeroize, Zeroizing};
+
+pub mod labels;
+pub mod merlin_transcript;
+pub mod poseidon_transcript;
+pub mod shake_transcript;
+
+pub use labels::TranscriptLabel;
+pub use merlin_transcript::MerlinTranscript;
+pub use poseidon_transcript::PoseidonTranscript;
+pub use shake_transcript::ShakeTranscript;
+
+/// Domain separation tag for expanding transcript state into challenges
+const CHALLENGE_DST: &[u8] = b"Longfellow-ZK-v1-challenge";
+
+/// Version byte of `Transcript::serialize_state`
+const TRANSCRIPT_STATE_VERSION: u8 = 1;
+/// Length of a serialized transcript state: version, chaining value, counter
+const TRANSCRIPT_STATE_LEN: usize = 1 + 32 + 8;
 
 /// Transcript for Fiat-Shamir transform
+///
+/// Each appended message is hashed into a 32-byte chaining value together
+/// with the previous one, so the entire history is captured by that value
+/// and a message counter. `serialize_state` exports them and `resume`
+/// picks the chain up again, e.g. in another process that proves the next
+/// component of the same proof.
+#[derive(Clone)]
 pub struct Transcript {
-    hasher: Sha3_256,
+    state: [u8; 32],
     counter: u64,
 }
 
@@ -20,78 +50,213 @@ impl Transcript {
         hasher.update(&(label.len() as u64).to_le_bytes());
         hasher.update(label);
         
-        Self { hasher, counter: 0 }
+        Self { state: hasher.finalize().into(), counter: 0 }
+    }
+
+    /// Hasher for the next message: the chaining value, the counter and
+    /// the framed label and length; the caller adds `len` bytes of message
+    fn begin_message(&self, label: TranscriptLabel, len: usize) -> Sha3_256 {
+        let label = label.encode();
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.state);
+        hasher.update(self.counter.to_le_bytes());
+        hasher.update((label.len() as u64).to_le_bytes());
+        hasher.update(&label);
+        hasher.update((len as u64).to_le_bytes());
+        hasher
     }
 
-    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
-        self.hasher.update(&self.counter.to_le_bytes());
-        self.hasher.update(&(label.len() as u64).to_le_bytes());
-        self.hasher.update(label);
-        self.hasher.update(&(message.len() as u64).to_le_bytes());
-        self.hasher.update(message);
+    fn end_message(&mut self, hasher: Sha3_256) {
+        self.state = hasher.finalize().into();
         self.counter += 1;
     }
 
-    pub fn append_field_element<F: Field>(&mut self, label: &[u8], elem: &F) {
-        let bytes = elem.to_bytes_le();
+    pub fn append_message(&mut self, label: TranscriptLabel, message: &[u8]) {
+        let mut hasher = self.begin_message(label, message.len());
+        hasher.update(message);
+        self.end_message(hasher);
+    }
+
+    pub fn append_field_element<F: Field>(&mut self, label: TranscriptLabel, elem: &F) {
+        let bytes = elem.to_canonical_bytes();
         self.append_message(label, &bytes);
     }
 
-    pub fn append_field_elements<F: Field>(&mut self, label: &[u8], elems: &[F]) {
-        self.append_message(label, &(elems.len() as u64).to_le_bytes());
+    pub fn append_field_elements<F: Field>(&mut self, label: TranscriptLabel, elems: &[F]) {
+        let mut hasher = self.begin_message(label, elems.len() * F::canonical_byte_len());
         for elem in elems {
-            let bytes = elem.to_bytes_le();
-            self.hasher.update(&bytes);
+            hasher.update(elem.to_canonical_bytes());
         }
-        self.counter += 1;
+        self.end_message(hasher);
     }
 
-    pub fn challenge_scalar<F: Field>(&mut self, label: &[u8]) -> F {
-        self.append_message(b"challenge", label);
-        
-        let mut challenge_bytes = [0u8; 64];
-        let hash = self.hasher.clone().finalize();
-        challenge_bytes[..32].copy_from_slice(&hash);
-        
-        // For larger fields, we might need multiple hashes
-        if F::MODULUS_BITS > 256 {
-            let mut extended_hasher = self.hasher.clone();
-            extended_hasher.update(b"extended");
-            let extended_hash = extended_hasher.finalize();
-            challenge_bytes[32..].copy_from_slice(&extended_hash);
+    /// Chaining value and counter, enough to resume the transcript
+    /// elsewhere with `resume`
+    pub fn serialize_state(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TRANSCRIPT_STATE_LEN);
+        bytes.push(TRANSCRIPT_STATE_VERSION);
+        bytes.extend_from_slice(&self.state);
+        bytes.extend_from_slice(&self.counter.to_le_bytes());
+        bytes
+    }
+
+    /// Continue a transcript exported with `serialize_state`; appends and
+    /// challenges then match those of the original transcript
+    pub fn resume(state: &[u8]) -> Result<Self> {
+        if state.len() != TRANSCRIPT_STATE_LEN || state[0] != TRANSCRIPT_STATE_VERSION {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Transcript state must be {} bytes of version {}",
+                TRANSCRIPT_STATE_LEN, TRANSCRIPT_STATE_VERSION
+            )));
         }
-        
-        // Reduce modulo field size
-        F::from_bytes_le(&challenge_bytes[..F::MODULUS_BITS as usize / 8])
-            .unwrap_or_else(|_| {
-                // Fallback: use rejection sampling
-                let mut rng = ChaCha20Rng::from_seed(hash.into());
-                loop {
-                    let mut bytes = [0u8; 32];
-                    rng.fill_bytes(&mut bytes);
-                    if let Ok(elem) = F::from_bytes_le(&bytes) {
-                        return elem;
-                    }
-                }
-            })
+
+        Ok(Self {
+            state: state[1..33].try_into().unwrap(),
+            counter: u64::from_le_bytes(state[33..].try_into().unwrap()),
+        })
     }
 
-    pub fn challenge_scalars<F: Field>(&mut self, label: &[u8], n: usize) -> Vec<F> {
-        (0..n)
+    /// Uniform challenge in F: the transcript state is expanded to
+    /// `F::uniform_bytes_len()` bytes with RFC 9380 `expand_message_xmd`
+    /// and reduced, so every field, base or extension, is sampled with
+    /// bias below 2^-128
+    pub fn challenge_scalar<F: Field>(&mut self, label: TranscriptLabel) -> F {
+        self.append_message(labels::CHALLENGE, &label.encode());
+        self.squeeze_scalar()
+    }
+
+    pub fn challenge_scalars<F: Field>(&mut self, label: TranscriptLabel, n: usize) -> Vec<F> {
+        self.append_message(labels::CHALLENGES, &label.encode());
+        (0..n as u64)
             .map(|i| {
-                let mut indexed_label = label.to_vec();
-                indexed_label.extend_from_slice(&i.to_le_bytes());
-                self.challenge_scalar(&indexed_label)
+                self.append_message(labels::CHALLENGE.at(i), &[]);
+                self.squeeze_scalar()
             })
             .collect()
     }
 
+    fn squeeze_scalar<F: Field>(&self) -> F {
+        F::hash_to_field(CHALLENGE_DST, &self.state)
+    }
+
+    /// `n` distinct indices drawn uniformly from `range`, in draw order
+    ///
+    /// Indices come from 64-bit words of transcript output with rejection
+    /// of the top partial interval, so there is no modulo bias for any
+    /// range size; repeats are redrawn.
+    ///
+    /// Panics if `n` exceeds the size of `range`.
+    pub fn challenge_indices(&mut self, label: TranscriptLabel, n: usize, range: Range<usize>) -> Vec<usize> {
+        let size = range.len();
+        assert!(n <= size, "Cannot draw {} distinct indices from {:?}", n, range);
+
+        self.append_message(labels::INDICES, &label.encode());
+        self.append_message(labels::INDEX_COUNT, &(n as u64).to_le_bytes());
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // Largest multiple of size not above 2^64
+        let zone = (1u128 << 64) - (1u128 << 64) % size as u128;
+        let mut indices = Vec::with_capacity(n);
+        let mut seen = HashSet::with_capacity(n);
+        let mut block = 0u64;
+        while indices.len() < n {
+            self.append_message(labels::INDEX_BLOCK, &block.to_le_bytes());
+            let output = self.state;
+            block += 1;
+
+            for word in output.chunks_exact(8) {
+                let x = u64::from_le_bytes(word.try_into().unwrap());
+                if (x as u128) >= zone {
+                    continue;
+                }
+                let index = range.start + (x % size as u64) as usize;
+                if indices.len() < n && seen.insert(index) {
+                    indices.push(index);
+                }
+            }
+        }
+
+        indices
+    }
+
+    /// Child transcript bound to everything appended so far and to
+    /// `label`
+    ///
+    /// Children forked under distinct labels produce independent
+    /// challenges, from each other and from the parent, so components of
+    /// one proof can be proven in parallel once their shared messages are
+    /// in the parent. The parent is left untouched.
+    pub fn fork(&self, label: TranscriptLabel) -> Transcript {
+        let mut child = self.clone();
+        child.append_message(labels::FORK, &label.encode());
+        child
+    }
+
     pub fn finalize(self) -> [u8; 32] {
-        self.hasher.finalize().into()
+        self.state
+    }
+}
+
+/// ChaCha20 generator whose state is wiped on drop
+///
+/// Used for prover secrets (blinding rows, masks) so they do not outlive
+/// the generator in freed memory.
+pub struct SecretRng(ChaCha20Rng);
+
+impl RngCore for SecretRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for SecretRng {}
+
+impl SeedableRng for SecretRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Self {
+        Self(ChaCha20Rng::from_seed(seed))
+    }
+}
+
+impl Zeroize for SecretRng {
+    fn zeroize(&mut self) {
+        // ChaCha20Rng keeps its key, counter and output buffer inline and
+        // owns nothing on the heap, so overwriting it in place with the
+        // all-zero seed state wipes it
+        unsafe {
+            std::ptr::write_volatile(&mut self.0, ChaCha20Rng::from_seed([0u8; 32]));
+        }
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl Drop for SecretRng {
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
 /// Field-specific random number generator
+///
+/// With `R = SecretRng` (as from `from_seed` and `from_rng`) the state is
+/// zeroized on drop, and `reseed` gives forward secrecy: the new state is
+/// a one-way function of the old one, so a later compromise does not
+/// reveal elements already drawn.
 pub struct FieldRng<F: Field, R: RngCore + CryptoRng> {
     rng: R,
     _phantom: PhantomData<F>,
@@ -105,17 +270,50 @@ impl<F: Field, R: RngCore + CryptoRng> FieldRng<F, R> {
         }
     }
 
-    pub fn from_seed(seed: [u8; 32]) -> FieldRng<F, ChaCha20Rng> {
+    pub fn from_seed(seed: [u8; 32]) -> FieldRng<F, SecretRng> {
         FieldRng {
-            rng: ChaCha20Rng::from_seed(seed),
+            rng: SecretRng::from_seed(seed),
             _phantom: PhantomData,
         }
     }
 
+    /// Zeroizing generator seeded from `source`, so the elements drawn do
+    /// not depend on state the caller keeps
+    pub fn from_rng<S: RngCore + CryptoRng>(source: &mut S) -> FieldRng<F, SecretRng> {
+        let mut seed = [0u8; 32];
+        source.fill_bytes(&mut seed);
+        let rng = SecretRng::from_seed(seed);
+        seed.zeroize();
+        FieldRng {
+            rng,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Replace the state with a hash of fresh output and `entropy`
+    pub fn reseed(&mut self, entropy: &[u8])
+    where
+        R: SeedableRng<Seed = [u8; 32]>,
+    {
+        let mut current = [0u8; 32];
+        self.rng.fill_bytes(&mut current);
+
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"Longfellow-FieldRng-reseed");
+        hasher.update(current);
+        hasher.update((entropy.len() as u64).to_le_bytes());
+        hasher.update(entropy);
+        let mut seed: [u8; 32] = hasher.finalize().into();
+
+        self.rng = R::from_seed(seed);
+        current.zeroize();
+        seed.zeroize();
+    }
+
     pub fn random_field_element(&mut self) -> F {
         // Use rejection sampling for uniform distribution
         let byte_len = (F::MODULUS_BITS as usize + 7) / 8;
-        let mut bytes = vec![0u8; byte_len];
+        let mut bytes = Zeroizing::new(vec![0u8; byte_len]);
         
         loop {
             self.rng.fill_bytes(&mut bytes);
@@ -129,6 +327,26 @@ impl<F: Field, R: RngCore + CryptoRng> FieldRng<F, R> {
         (0..n).map(|_| self.random_field_element()).collect()
     }
 
+    /// Overwrite every element of `out` with a fresh random element
+    pub fn fill_field_slice(&mut self, out: &mut [F]) {
+        for elem in out.iter_mut() {
+            *elem = self.random_field_element();
+        }
+    }
+
+    /// Uniform element of the `F::SUBFIELD_BITS`-bit subfield of `F`
+    pub fn random_subfield_element(&mut self) -> F {
+        let mask = (1u64 << F::SUBFIELD_BITS) - 1;
+        F::from_subfield(self.rng.next_u64() & mask)
+    }
+
+    /// Overwrite every element of `out` with a fresh random subfield element
+    pub fn fill_subfield_slice(&mut self, out: &mut [F]) {
+        for elem in out.iter_mut() {
+            *elem = self.random_subfield_element();
+        }
+    }
+
     pub fn random_nonzero_field_element(&mut self) -> F {
         loop {
             let elem = self.random_field_element();
@@ -160,9 +378,7 @@ impl<F: Field> PseudoRandomFunction<F> {
         hasher.update(input);
         
         let hash = hasher.finalize();
-        let rng = ChaCha20Rng::from_seed(hash.into());
-        
-        FieldRng::<F, _>::new(rng).random_field_element()
+        FieldRng::<F, SecretRng>::from_seed(hash.into()).random_field_element()
     }
 
     pub fn evaluate_domain(&self, domain: &[u8], index: u64) -> F {
@@ -190,28 +406,65 @@ impl VerifierTranscript {
         }
     }
 
-    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+    pub fn append_message(&mut self, label: TranscriptLabel, message: &[u8]) {
         self.base.append_message(label, message);
     }
 
     pub fn append_proof_message(&mut self, message: &[u8]) {
-        self.base.append_message(b"proof", message);
+        self.base.append_message(labels::PROOF, message);
     }
 
-    pub fn challenge_scalar<F: Field>(&mut self, label: &[u8]) -> F {
+    pub fn challenge_scalar<F: Field>(&mut self, label: TranscriptLabel) -> F {
         self.base.challenge_scalar(label)
     }
 
-    pub fn challenge_scalars<F: Field>(&mut self, label: &[u8], n: usize) -> Vec<F> {
+    pub fn challenge_scalars<F: Field>(&mut self, label: TranscriptLabel, n: usize) -> Vec<F> {
         self.base.challenge_scalars(label, n)
     }
 
+    pub fn challenge_indices(&mut self, label: TranscriptLabel, n: usize, range: Range<usize>) -> Vec<usize> {
+        self.base.challenge_indices(label, n, range)
+    }
+
     pub fn verify_proof_hash(&self, expected_hash: &[u8; 32]) -> bool {
-        let actual_hash = self.base.hasher.clone().finalize();
-        actual_hash.as_slice() == expected_hash
+        &self.base.state == expected_hash
     }
 }
 
+/// RFC 6979 nonce in the prime field F for private key `key` and message
+/// `msg` (hashed with SHA-256), for deterministic ECDSA-style signing and
+/// reproducible prover randomness in tests
+///
+/// The group order is taken to be the modulus of F, so F must be a prime
+/// field, not an extension.
+pub fn deterministic_nonce<F: Field>(key: &F, msg: &[u8]) -> F {
+    let to_be = |elem: &F| {
+        let mut bytes = elem.to_canonical_bytes();
+        bytes.reverse();
+        bytes
+    };
+
+    // q = (q - 1) + 1, from the encoding of -1
+    let mut order = to_be(&(F::zero() - F::one()));
+    for byte in order.iter_mut().rev() {
+        let (sum, carry) = byte.overflowing_add(1);
+        *byte = sum;
+        if !carry {
+            break;
+        }
+    }
+    if order.iter().all(|&b| b == 0) {
+        // q - 1 was all ones; q needs one more byte
+        order.insert(0, 1);
+    }
+
+    let digest = Sha256::digest(msg);
+    let mut nonce = rfc6979_nonce(&order, &to_be(key), &digest);
+    nonce.reverse();
+    nonce.resize(F::canonical_byte_len(), 0);
+    F::from_canonical_bytes(&nonce).expect("RFC 6979 nonces are below the order")
+}
+
 /// Generate a random oracle query
 pub fn random_oracle<F: Field>(domain: &[u8], input: &[u8]) -> F {
     let mut hasher = Sha3_256::new();
@@ -222,15 +475,13 @@ pub fn random_oracle<F: Field>(domain: &[u8], input: &[u8]) -> F {
     hasher.update(input);
     
     let hash = hasher.finalize();
-    let rng = ChaCha20Rng::from_seed(hash.into());
-    
-    FieldRng::<F, _>::new(rng).random_field_element()
+    FieldRng::<F, SecretRng>::from_seed(hash.into()).random_field_element()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use longfellow_algebra::Fp128;
+    use longfellow_algebra::{BabyBear, BabyBear4, Fp128};
     use rand::rngs::OsRng;
 
     #[test]
@@ -238,11 +489,11 @@ mod tests {
         let mut t1 = Transcript::new(b"test");
         let mut t2 = Transcript::new(b"test");
         
-        t1.append_message(b"msg1", b"data1");
-        t2.append_message(b"msg1", b"data1");
+        t1.append_message(TranscriptLabel::new("msg1"), b"data1");
+        t2.append_message(TranscriptLabel::new("msg1"), b"data1");
         
-        let c1: Fp128 = t1.challenge_scalar(b"challenge");
-        let c2: Fp128 = t2.challenge_scalar(b"challenge");
+        let c1: Fp128 = t1.challenge_scalar(TranscriptLabel::new("challenge"));
+        let c2: Fp128 = t2.challenge_scalar(TranscriptLabel::new("challenge"));
         
         assert_eq!(c1, c2);
     }
@@ -252,15 +503,90 @@ mod tests {
         let mut t1 = Transcript::new(b"test");
         let mut t2 = Transcript::new(b"test");
         
-        t1.append_message(b"msg1", b"data1");
-        t2.append_message(b"msg1", b"data2");
+        t1.append_message(TranscriptLabel::new("msg1"), b"data1");
+        t2.append_message(TranscriptLabel::new("msg1"), b"data2");
         
-        let c1: Fp128 = t1.challenge_scalar(b"challenge");
-        let c2: Fp128 = t2.challenge_scalar(b"challenge");
+        let c1: Fp128 = t1.challenge_scalar(TranscriptLabel::new("challenge"));
+        let c2: Fp128 = t2.challenge_scalar(TranscriptLabel::new("challenge"));
         
         assert_ne!(c1, c2);
     }
 
+    #[test]
+    fn test_challenge_scalar_covers_field() {
+        // Truncating to MODULUS_BITS / 8 bytes would keep every BabyBear
+        // challenge below 2^24
+        let mut transcript = Transcript::new(b"test");
+        let large = (0..64)
+            .map(|_| transcript.challenge_scalar::<BabyBear>(TranscriptLabel::new("c")))
+            .filter(|c| c.to_canonical_bytes()[3] != 0)
+            .count();
+        assert!(large > 32);
+
+        let mut t1 = Transcript::new(b"test");
+        let mut t2 = Transcript::new(b"test");
+        let c1: BabyBear4 = t1.challenge_scalar(TranscriptLabel::new("ext"));
+        let c2: BabyBear4 = t2.challenge_scalar(TranscriptLabel::new("ext"));
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn test_state_export_resumes_chain() {
+        let label = TranscriptLabel::new("msg");
+        let mut original = Transcript::new(b"test");
+        original.append_message(label, b"commitment");
+        let _: Fp128 = original.challenge_scalar(TranscriptLabel::new("c"));
+
+        let exported = original.serialize_state();
+        let mut resumed = Transcript::resume(&exported).unwrap();
+        original.append_field_elements(label, &[Fp128::from_u64(5)]);
+        resumed.append_field_elements(label, &[Fp128::from_u64(5)]);
+        assert_eq!(
+            original.challenge_scalar::<Fp128>(TranscriptLabel::new("next")),
+            resumed.challenge_scalar::<Fp128>(TranscriptLabel::new("next"))
+        );
+
+        assert!(Transcript::resume(&exported[1..]).is_err());
+        let mut wrong_version = exported.clone();
+        wrong_version[0] = 0;
+        assert!(Transcript::resume(&wrong_version).is_err());
+    }
+
+    #[test]
+    fn test_challenge_indices() {
+        let mut t1 = Transcript::new(b"test");
+        let mut t2 = VerifierTranscript::new(b"test");
+        let indices = t1.challenge_indices(TranscriptLabel::new("cols"), 40, 100..150);
+        assert_eq!(indices, t2.challenge_indices(TranscriptLabel::new("cols"), 40, 100..150));
+        assert!(indices.iter().all(|i| (100..150).contains(i)));
+        assert_eq!(indices.iter().collect::<HashSet<_>>().len(), 40);
+
+        // Drawing the whole range yields a permutation of it
+        let mut all = t1.challenge_indices(TranscriptLabel::new("all"), 7, 0..7);
+        all.sort_unstable();
+        assert_eq!(all, (0..7).collect::<Vec<_>>());
+        assert!(t1.challenge_indices(TranscriptLabel::new("none"), 0, 0..0).is_empty());
+    }
+
+    #[test]
+    fn test_fork_domain_separation() {
+        let mut parent = Transcript::new(b"test");
+        parent.append_message(TranscriptLabel::new("shared"), b"commitment");
+
+        let mut ligero = parent.fork(TranscriptLabel::new("ligero"));
+        let mut sumcheck = parent.fork(TranscriptLabel::new("sumcheck"));
+        let c_ligero: Fp128 = ligero.challenge_scalar(TranscriptLabel::new("c"));
+        let c_sumcheck: Fp128 = sumcheck.challenge_scalar(TranscriptLabel::new("c"));
+        assert_ne!(c_ligero, c_sumcheck);
+        assert_ne!(c_ligero, parent.clone().challenge_scalar::<Fp128>(TranscriptLabel::new("c")));
+
+        // Forks are reproducible and see the parent's history
+        assert_eq!(c_ligero, parent.fork(TranscriptLabel::new("ligero")).challenge_scalar::<Fp128>(TranscriptLabel::new("c")));
+        let mut other = Transcript::new(b"test");
+        other.append_message(TranscriptLabel::new("shared"), b"other");
+        assert_ne!(c_ligero, other.fork(TranscriptLabel::new("ligero")).challenge_scalar::<Fp128>(TranscriptLabel::new("c")));
+    }
+
     #[test]
     fn test_field_rng() {
         let mut rng = FieldRng::<Fp128, _>::new(OsRng);
@@ -273,6 +599,51 @@ mod tests {
         assert_ne!(elem2, Fp128::zero());
     }
 
+    #[test]
+    fn test_deterministic_nonce() {
+        let key = Fp128::from_u64(0x1234_5678);
+        let k1 = deterministic_nonce(&key, b"message");
+        assert_eq!(k1, deterministic_nonce(&key, b"message"));
+        assert_ne!(k1, deterministic_nonce(&key, b"other message"));
+        assert_ne!(k1, deterministic_nonce(&Fp128::from_u64(1), b"message"));
+        assert_ne!(k1, Fp128::zero());
+
+        // Small modulus: nonces stay in range
+        let key = BabyBear::from_u64(7);
+        for i in 0..16u8 {
+            assert_ne!(deterministic_nonce(&key, &[i]), BabyBear::zero());
+        }
+    }
+
+    #[test]
+    fn test_reseed_and_fill() {
+        let mut a = FieldRng::<Fp128, SecretRng>::from_seed([7u8; 32]);
+        let mut b = FieldRng::<Fp128, SecretRng>::from_seed([7u8; 32]);
+        a.reseed(b"entropy");
+        b.reseed(b"entropy");
+        let mut xs = [Fp128::zero(); 8];
+        let mut ys = [Fp128::zero(); 8];
+        a.fill_field_slice(&mut xs);
+        b.fill_field_slice(&mut ys);
+        assert_eq!(xs, ys);
+        assert!(xs.iter().all(|x| *x != Fp128::zero()));
+
+        // Different entropy, or none, gives a different stream
+        let mut c = FieldRng::<Fp128, SecretRng>::from_seed([7u8; 32]);
+        c.reseed(b"other");
+        assert_ne!(c.random_field_element(), xs[0]);
+        let mut d = FieldRng::<Fp128, SecretRng>::from_seed([7u8; 32]);
+        assert_ne!(d.random_field_element(), xs[0]);
+    }
+
+    #[test]
+    fn test_secret_rng_zeroize() {
+        let mut rng = SecretRng::from_seed([9u8; 32]);
+        rng.next_u64();
+        rng.zeroize();
+        assert_eq!(rng.next_u64(), SecretRng::from_seed([0u8; 32]).next_u64());
+    }
+
     #[test]
     fn test_prf_deterministic() {
         let key = [42u8; 32];
@@ -0,0 +1,239 @@
+//! Merlin transcripts over STROBE-128
+//!
+//! `MerlinTranscript` offers the same append/challenge methods as
+//! `Transcript` but frames messages the way the `merlin` crate does:
+//! labels and lengths go through STROBE meta-AD operations and challenges
+//! are squeezed with PRF, so a protocol built on it produces the same
+//! challenges as a Rust ZK library using `merlin::Transcript` with the same
+//! labels. Unlike `merlin`, labels need not be `'static`.
+
+use longfellow_algebra::traits::Field;
+use zeroize::Zeroize;
+
+/// Protocol label of every Merlin transcript
+const MERLIN_PROTOCOL_LABEL: &[u8] = b"Merlin v1.0";
+
+const STROBE_R: u8 = 166;
+
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_T: u8 = 1 << 3;
+const FLAG_M: u8 = 1 << 4;
+const FLAG_K: u8 = 1 << 5;
+
+fn keccak_f1600(state: &mut [u8; 200]) {
+    let mut lanes = [0u64; 25];
+    for (lane, bytes) in lanes.iter_mut().zip(state.chunks_exact(8)) {
+        *lane = u64::from_le_bytes(bytes.try_into().unwrap());
+    }
+    keccak::f1600(&mut lanes);
+    for (bytes, lane) in state.chunks_exact_mut(8).zip(lanes.iter()) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+}
+
+/// The subset of STROBE-128 that Merlin transcripts use: meta-AD, AD and
+/// PRF
+#[derive(Clone)]
+struct Strobe128 {
+    state: [u8; 200],
+    pos: u8,
+    pos_begin: u8,
+    cur_flags: u8,
+}
+
+impl Strobe128 {
+    fn new(protocol_label: &[u8]) -> Self {
+        let mut state = [0u8; 200];
+        state[0..6].copy_from_slice(&[1, STROBE_R + 2, 1, 0, 1, 96]);
+        state[6..18].copy_from_slice(b"STROBEv1.0.2");
+        keccak_f1600(&mut state);
+
+        let mut strobe = Self {
+            state,
+            pos: 0,
+            pos_begin: 0,
+            cur_flags: 0,
+        };
+        strobe.meta_ad(protocol_label, false);
+        strobe
+    }
+
+    fn meta_ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A, more);
+        self.absorb(data);
+    }
+
+    fn ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A, more);
+        self.absorb(data);
+    }
+
+    fn prf(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_C, more);
+        self.squeeze(data);
+    }
+
+    fn run_f(&mut self) {
+        self.state[self.pos as usize] ^= self.pos_begin;
+        self.state[self.pos as usize + 1] ^= 0x04;
+        self.state[STROBE_R as usize + 1] ^= 0x80;
+        keccak_f1600(&mut self.state);
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    fn advance(&mut self) {
+        self.pos += 1;
+        if self.pos == STROBE_R {
+            self.run_f();
+        }
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for byte in data {
+            self.state[self.pos as usize] ^= byte;
+            self.advance();
+        }
+    }
+
+    fn squeeze(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte = self.state[self.pos as usize];
+            self.state[self.pos as usize] = 0;
+            self.advance();
+        }
+    }
+
+    fn begin_op(&mut self, flags: u8, more: bool) {
+        if more {
+            debug_assert_eq!(self.cur_flags, flags, "STROBE operation continued with different flags");
+            return;
+        }
+        debug_assert_eq!(flags & FLAG_T, 0, "transport operations are not supported");
+
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos + 1;
+        self.cur_flags = flags;
+        self.absorb(&[old_begin, flags]);
+
+        // Cipher and key operations start on a fresh block
+        if flags & (FLAG_C | FLAG_K) != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+}
+
+impl Drop for Strobe128 {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+/// Fiat-Shamir transcript compatible with `merlin::Transcript`
+#[derive(Clone)]
+pub struct MerlinTranscript {
+    strobe: Strobe128,
+}
+
+impl MerlinTranscript {
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            strobe: Strobe128::new(MERLIN_PROTOCOL_LABEL),
+        };
+        transcript.append_message(b"dom-sep", label);
+        transcript
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        let data_len = (message.len() as u32).to_le_bytes();
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&data_len, true);
+        self.strobe.ad(message, false);
+    }
+
+    pub fn append_u64(&mut self, label: &[u8], x: u64) {
+        self.append_message(label, &x.to_le_bytes());
+    }
+
+    pub fn append_field_element<F: Field>(&mut self, label: &[u8], elem: &F) {
+        self.append_message(label, &elem.to_canonical_bytes());
+    }
+
+    pub fn append_field_elements<F: Field>(&mut self, label: &[u8], elems: &[F]) {
+        let bytes: Vec<u8> = elems.iter().flat_map(|e| e.to_canonical_bytes()).collect();
+        self.append_message(label, &bytes);
+    }
+
+    /// Fill `dest` with challenge bytes bound to everything appended so far
+    pub fn challenge_bytes(&mut self, label: &[u8], dest: &mut [u8]) {
+        let data_len = (dest.len() as u32).to_le_bytes();
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&data_len, true);
+        self.strobe.prf(dest, false);
+    }
+
+    pub fn challenge_scalar<F: Field>(&mut self, label: &[u8]) -> F {
+        let mut bytes = vec![0u8; F::uniform_bytes_len()];
+        self.challenge_bytes(label, &mut bytes);
+        F::from_uniform_bytes(&bytes)
+    }
+
+    pub fn challenge_scalars<F: Field>(&mut self, label: &[u8], n: usize) -> Vec<F> {
+        let mut bytes = vec![0u8; n * F::uniform_bytes_len()];
+        self.challenge_bytes(label, &mut bytes);
+        bytes
+            .chunks_exact(F::uniform_bytes_len())
+            .map(F::from_uniform_bytes)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use longfellow_algebra::Fp128;
+
+    #[test]
+    fn test_matches_merlin() {
+        let mut ours = MerlinTranscript::new(b"test protocol");
+        let mut theirs = merlin::Transcript::new(b"test protocol");
+
+        ours.append_message(b"some label", b"some data");
+        theirs.append_message(b"some label", b"some data");
+        ours.append_u64(b"count", 7);
+        theirs.append_u64(b"count", 7);
+
+        // Long enough to cross several STROBE blocks
+        let long = vec![0x5a; 1000];
+        ours.append_message(b"long", &long);
+        theirs.append_message(b"long", &long);
+
+        let mut a = [0u8; 500];
+        let mut b = [0u8; 500];
+        ours.challenge_bytes(b"challenge", &mut a);
+        theirs.challenge_bytes(b"challenge", &mut b);
+        assert_eq!(a, b);
+
+        // Framing stays in sync after a challenge
+        ours.append_message(b"after", b"more");
+        theirs.append_message(b"after", b"more");
+        ours.challenge_bytes(b"next", &mut a[..32]);
+        theirs.challenge_bytes(b"next", &mut b[..32]);
+        assert_eq!(a[..32], b[..32]);
+    }
+
+    #[test]
+    fn test_challenges_depend_on_messages() {
+        let mut t1 = MerlinTranscript::new(b"test");
+        let mut t2 = MerlinTranscript::new(b"test");
+        t1.append_field_elements(b"values", &[Fp128::from_u64(1), Fp128::from_u64(2)]);
+        t2.append_field_elements(b"values", &[Fp128::from_u64(1), Fp128::from_u64(3)]);
+
+        let c1: Vec<Fp128> = t1.clone().challenge_scalars(b"c", 3);
+        assert_eq!(c1, t1.challenge_scalars::<Fp128>(b"c", 3));
+        assert_ne!(c1, t2.challenge_scalars::<Fp128>(b"c", 3));
+        assert_ne!(c1[0], c1[1]);
+    }
+}
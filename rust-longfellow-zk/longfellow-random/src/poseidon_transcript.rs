@@ -0,0 +1,124 @@
+//! Fiat-Shamir transcript over a Poseidon duplex sponge
+//!
+//! `PoseidonTranscript` keeps its state in the proof field and absorbs
+//! field elements natively, so a circuit re-deriving the challenges of a
+//! longfellow proof spends one Poseidon permutation per `rate` absorbed
+//! elements and per challenge, instead of simulating SHA-3 over their byte
+//! encodings. Byte messages and labels are packed with
+//! `poseidon::bytes_to_elements` behind a length prefix.
+//!
+//! The sponge starts with `TRANSCRIPT_TAG` in the capacity element; each
+//! challenge permutes the state and reads the first rate element.
+
+use longfellow_algebra::traits::Field;
+use longfellow_merkle::poseidon::{bytes_to_elements, PoseidonConfig, TRANSCRIPT_TAG};
+
+/// Poseidon-backed transcript with the `Transcript` append/challenge API
+#[derive(Clone, Debug)]
+pub struct PoseidonTranscript<C: PoseidonConfig> {
+    state: Vec<C::F>,
+    /// Rate elements absorbed since the last permutation
+    absorbed: usize,
+}
+
+impl<C: PoseidonConfig> PoseidonTranscript<C> {
+    pub fn new(label: &[u8]) -> Self {
+        let mut state = vec![C::F::zero(); C::params().width()];
+        state[0] = C::F::from_u64(TRANSCRIPT_TAG);
+
+        let mut transcript = Self { state, absorbed: 0 };
+        transcript.absorb_bytes(label);
+        transcript
+    }
+
+    fn absorb(&mut self, elements: &[C::F]) {
+        let rate = C::params().rate();
+        for &x in elements {
+            if self.absorbed == rate {
+                C::params().permute(&mut self.state);
+                self.absorbed = 0;
+            }
+            self.state[1 + self.absorbed] += x;
+            self.absorbed += 1;
+        }
+    }
+
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        self.absorb(&[C::F::from_u64(bytes.len() as u64)]);
+        self.absorb(&bytes_to_elements(bytes));
+    }
+
+    fn squeeze(&mut self) -> C::F {
+        C::params().permute(&mut self.state);
+        self.absorbed = 0;
+        self.state[1]
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.absorb_bytes(label);
+        self.absorb_bytes(message);
+    }
+
+    pub fn append_field_element(&mut self, label: &[u8], elem: &C::F) {
+        self.append_field_elements(label, std::slice::from_ref(elem));
+    }
+
+    pub fn append_field_elements(&mut self, label: &[u8], elems: &[C::F]) {
+        self.absorb_bytes(label);
+        self.absorb(&[C::F::from_u64(elems.len() as u64)]);
+        self.absorb(elems);
+    }
+
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> C::F {
+        self.absorb_bytes(label);
+        self.squeeze()
+    }
+
+    pub fn challenge_scalars(&mut self, label: &[u8], n: usize) -> Vec<C::F> {
+        self.absorb_bytes(label);
+        self.absorb(&[C::F::from_u64(n as u64)]);
+        (0..n).map(|_| self.squeeze()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use longfellow_algebra::Fp128;
+    use longfellow_merkle::poseidon::Fp128Poseidon;
+
+    type Fp128Transcript = PoseidonTranscript<Fp128Poseidon>;
+
+    #[test]
+    fn test_transcript_binds_messages_and_labels() {
+        let mut base = Fp128Transcript::new(b"test");
+        base.append_message(b"msg", b"data");
+        base.append_field_elements(b"elems", &[Fp128::from_u64(1), Fp128::from_u64(2)]);
+
+        let mut same = base.clone();
+        assert_eq!(base.clone().challenge_scalar(b"c"), same.challenge_scalar(b"c"));
+
+        let mut other = Fp128Transcript::new(b"test");
+        other.append_message(b"msg", b"data");
+        other.append_field_elements(b"elems", &[Fp128::from_u64(1), Fp128::from_u64(3)]);
+        assert_ne!(base.clone().challenge_scalar(b"c"), other.challenge_scalar(b"c"));
+
+        assert_ne!(base.clone().challenge_scalar(b"c"), base.clone().challenge_scalar(b"d"));
+        assert_ne!(
+            Fp128Transcript::new(b"a").challenge_scalar(b"c"),
+            Fp128Transcript::new(b"b").challenge_scalar(b"c")
+        );
+    }
+
+    #[test]
+    fn test_successive_challenges_differ() {
+        let mut transcript = Fp128Transcript::new(b"test");
+        let challenges = transcript.challenge_scalars(b"c", 4);
+        for i in 0..4 {
+            for j in i + 1..4 {
+                assert_ne!(challenges[i], challenges[j]);
+            }
+        }
+        assert_ne!(transcript.challenge_scalar(b"c"), challenges[3]);
+    }
+}
@@ -0,0 +1,107 @@
+//! Fiat-Shamir transcript over the SHAKE256 XOF
+//!
+//! Messages are framed as in `Transcript` (counter, then length-prefixed
+//! label and message). A challenge request absorbs its label and count and
+//! then reads every challenge from one XOF stream over the state, so
+//! thousands of scalars for a random linear combination cost one clone of
+//! the sponge and a single squeeze instead of a clone and finalize each.
+
+use longfellow_algebra::traits::Field;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+/// SHAKE256-backed transcript with the `Transcript` append/challenge API
+#[derive(Clone)]
+pub struct ShakeTranscript {
+    hasher: Shake256,
+    counter: u64,
+}
+
+impl ShakeTranscript {
+    pub fn new(label: &[u8]) -> Self {
+        let mut hasher = Shake256::default();
+        hasher.update(b"Longfellow-ZK-SHAKE256-v1");
+        hasher.update(&(label.len() as u64).to_le_bytes());
+        hasher.update(label);
+
+        Self { hasher, counter: 0 }
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.hasher.update(&self.counter.to_le_bytes());
+        self.hasher.update(&(label.len() as u64).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update(&(message.len() as u64).to_le_bytes());
+        self.hasher.update(message);
+        self.counter += 1;
+    }
+
+    pub fn append_field_element<F: Field>(&mut self, label: &[u8], elem: &F) {
+        self.append_message(label, &elem.to_canonical_bytes());
+    }
+
+    pub fn append_field_elements<F: Field>(&mut self, label: &[u8], elems: &[F]) {
+        let bytes: Vec<u8> = elems.iter().flat_map(|e| e.to_canonical_bytes()).collect();
+        self.append_message(label, &bytes);
+    }
+
+    /// Fill `dest` with challenge bytes bound to everything appended so far
+    pub fn challenge_bytes(&mut self, label: &[u8], dest: &mut [u8]) {
+        self.append_message(b"challenge", label);
+        self.append_message(b"length", &(dest.len() as u64).to_le_bytes());
+        self.hasher.clone().finalize_xof().read(dest);
+    }
+
+    pub fn challenge_scalar<F: Field>(&mut self, label: &[u8]) -> F {
+        let mut bytes = vec![0u8; F::uniform_bytes_len()];
+        self.challenge_bytes(label, &mut bytes);
+        F::from_uniform_bytes(&bytes)
+    }
+
+    pub fn challenge_scalars<F: Field>(&mut self, label: &[u8], n: usize) -> Vec<F> {
+        let mut bytes = vec![0u8; n * F::uniform_bytes_len()];
+        self.challenge_bytes(label, &mut bytes);
+        bytes
+            .chunks_exact(F::uniform_bytes_len())
+            .map(F::from_uniform_bytes)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use longfellow_algebra::Fp128;
+
+    #[test]
+    fn test_challenge_stream() {
+        let mut t1 = ShakeTranscript::new(b"test");
+        let mut t2 = ShakeTranscript::new(b"test");
+        t1.append_message(b"msg", b"data");
+        t2.append_message(b"msg", b"data");
+
+        let c1: Vec<Fp128> = t1.challenge_scalars(b"lincomb", 4096);
+        let c2: Vec<Fp128> = t2.challenge_scalars(b"lincomb", 4096);
+        assert_eq!(c1, c2);
+        assert_ne!(c1[0], c1[1]);
+        assert_ne!(c1[0], c1[4095]);
+
+        // The count is bound, so a shorter request is not a prefix
+        let mut t3 = ShakeTranscript::new(b"test");
+        t3.append_message(b"msg", b"data");
+        let short: Vec<Fp128> = t3.challenge_scalars(b"lincomb", 2);
+        assert_ne!(short[..], c1[..2]);
+
+        // Later challenges depend on earlier ones
+        assert_ne!(t1.challenge_scalar::<Fp128>(b"next"), c1[0]);
+    }
+
+    #[test]
+    fn test_messages_are_bound() {
+        let mut t1 = ShakeTranscript::new(b"test");
+        let mut t2 = ShakeTranscript::new(b"test");
+        t1.append_message(b"msg", b"data1");
+        t2.append_message(b"msg", b"data2");
+        assert_ne!(t1.challenge_scalar::<Fp128>(b"c"), t2.challenge_scalar::<Fp128>(b"c"));
+    }
+}
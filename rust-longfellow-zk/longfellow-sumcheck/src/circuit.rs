@@ -2,8 +2,10 @@
 
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
+use longfellow_ligero::ConstraintSystem;
 use serde::{Deserialize, Serialize};
-use crate::quad::Quad;
+use crate::quad::{hand_wire, wire_hand, Quad, ONE_HAND};
+use crate::wiring::Wiring;
 
 /// A layer in an arithmetic circuit
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,6 +22,10 @@ pub struct Layer<F: Field> {
     /// Quadratic form representing gate constraints
     pub quad: Quad<F>,
     
+    /// Gates of degree three, summed into their outputs alongside the quad
+    #[serde(default)]
+    pub cubic: Vec<CubicGate<F>>,
+    
     /// Number of public inputs at this layer
     pub npub_in: Option<usize>,
 }
@@ -32,6 +38,7 @@ impl<F: Field> Layer<F> {
             nin,
             ngate,
             quad: Quad::new(),
+            cubic: Vec::new(),
             npub_in: None,
         }
     }
@@ -51,7 +58,19 @@ impl<F: Field> Layer<F> {
         1 << self.ngate
     }
     
+    /// Number of input wires a gate of this layer reads at most: two, or
+    /// three once it has a cubic gate
+    ///
+    /// The layer's sumcheck binds one hand of input variables per wire
+    /// read and ends in as many wire claims.
+    pub fn num_hands(&self) -> usize {
+        if self.cubic.is_empty() { 2 } else { 3 }
+    }
+    
     /// Add a gate constraint
+    ///
+    /// `left` and `right` are input wires, checked to exist even for a
+    /// constant gate, which reads neither; `add_const_gate` takes none.
     pub fn add_gate(
         &mut self,
         output: usize,
@@ -74,24 +93,109 @@ impl<F: Field> Layer<F> {
         match gate_type {
             GateType::Add(coeff) => {
                 // For add gate: output = coeff * (left + right)
-                // In quad form: coeff * gate[output] * (hand[left] + hand[right])
-                // Since hand indices are 1-based (0 means constant 1), we add 1
-                self.quad.add_corner(output, left + 1, 0, coeff)?;
-                self.quad.add_corner(output, 0, right + 1, coeff)?;
+                // In quad form: coeff * gate[output] * (hand[left] + hand[right]),
+                // each wire read against the constant one
+                self.quad.add_corner(output, wire_hand(left), ONE_HAND, coeff)?;
+                self.quad.add_corner(output, ONE_HAND, wire_hand(right), coeff)?;
             }
             GateType::Mul(coeff) => {
                 // For mul gate: output = coeff * left * right
-                self.quad.add_corner(output, left + 1, right + 1, coeff)?;
+                self.quad.add_corner(output, wire_hand(left), wire_hand(right), coeff)?;
             }
             GateType::Const(value) => {
                 // For const gate: output = value
-                self.quad.add_corner(output, 0, 0, value)?;
+                self.quad.add_corner(output, ONE_HAND, ONE_HAND, value)?;
             }
         }
         
         Ok(())
     }
     
+    /// Add a single linear term: output += coeff * input
+    ///
+    /// An add gate reads two wires under one coefficient; this reads one
+    /// against the constant one.
+    pub fn add_linear_term(&mut self, output: usize, input: usize, coeff: F) -> Result<()> {
+        if output >= self.num_outputs() || input >= self.num_inputs() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Linear term {} -> {} out of range", input, output
+            )));
+        }
+        
+        self.quad.add_corner(output, wire_hand(input), ONE_HAND, coeff)
+    }
+    
+    /// Add a constant gate: output += value
+    pub fn add_const_gate(&mut self, output: usize, value: F) -> Result<()> {
+        if output >= self.num_outputs() {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Output index {} out of range", output)
+            ));
+        }
+        
+        self.quad.add_corner(output, ONE_HAND, ONE_HAND, value)
+    }
+    
+    /// Add a gate of degree three: output = coeff * x * y * z for the input
+    /// wires `x`, `y` and `z`
+    ///
+    /// Outputs sum every gate writing them, so a composite gate such as
+    /// s * x * y + x is a cubic gate and an add gate on one output.
+    pub fn add_cubic_gate(&mut self, output: usize, inputs: [usize; 3], coeff: F) -> Result<()> {
+        if output >= self.num_outputs() {
+            return Err(LongfellowError::InvalidParameter(
+                format!("Output index {} out of range", output)
+            ));
+        }
+        
+        if inputs.iter().any(|&input| input >= self.num_inputs()) {
+            return Err(LongfellowError::InvalidParameter(
+                "Input indices out of range".to_string()
+            ));
+        }
+        
+        if coeff != F::zero() {
+            self.cubic.push(CubicGate { output, inputs, coeff });
+        }
+        
+        Ok(())
+    }
+    
+    /// Add a selector-weighted sum: output = s * sum_i c_i x_i for the
+    /// selector wire `s` and terms (x_i, c_i)
+    ///
+    /// Each term is a multiplication by the selector, so the sum needs no
+    /// intermediate wire.
+    pub fn add_selected_sum(&mut self, output: usize, selector: usize, terms: &[(usize, F)]) -> Result<()> {
+        for &(input, coeff) in terms {
+            self.add_gate(output, selector, input, GateType::Mul(coeff))?;
+        }
+        
+        Ok(())
+    }
+    
+    /// The layer's wiring predicates as sparse lists split by kind
+    pub fn wiring(&self) -> Result<Wiring<F>> {
+        Wiring::new(self)
+    }
+    
+    /// Wiring predicates at hand points `a` and `b`, with output `g`
+    /// weighted by `gate_weights[g]`
+    ///
+    /// Returns the multiplication part, sum_g w_g Q(g, a, b) over the gates
+    /// reading two wires; the addition part, over gates reading the
+    /// constant one and a wire, taken at `b`; and the weighted constants.
+    pub fn wiring_at(&self, gate_weights: &[F], a: &[F], b: &[F]) -> Result<[F; 3]> {
+        self.wiring()?.evaluate_at(gate_weights, a, b)
+    }
+    
+    /// Cubic wiring predicate at hand points `a`, `b` and `d`: the sum over
+    /// cubic gates of w_g coeff eq(a, x) eq(b, y) eq(d, z), with output `g`
+    /// weighted by `gate_weights[g]`
+    pub fn cubic_wiring_at(&self, gate_weights: &[F], a: &[F], b: &[F], d: &[F]) -> Result<F> {
+        self.wiring()?.evaluate_cubic_at(gate_weights, a, b, d)
+    }
+    
     /// Validate the layer
     pub fn validate(&self) -> Result<()> {
         if self.nout > crate::MAX_BINDINGS || 
@@ -104,7 +208,18 @@ impl<F: Field> Layer<F> {
             ));
         }
         
-        self.quad.validate(self.ngate, self.nin)?;
+        // Gates write outputs, so the quad is checked against those
+        self.quad.validate(self.nout, self.nin)?;
+        
+        for gate in &self.cubic {
+            if gate.output >= self.num_outputs()
+                || gate.inputs.iter().any(|&input| input >= self.num_inputs())
+            {
+                return Err(LongfellowError::InvalidParameter(format!(
+                    "Cubic gate {:?} out of range", (gate.output, gate.inputs)
+                )));
+            }
+        }
         
         Ok(())
     }
@@ -121,6 +236,17 @@ pub enum GateType<F: Field> {
     Const(F),
 }
 
+/// A gate of degree three: output = coeff * x * y * z
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CubicGate<F: Field> {
+    /// Output wire
+    pub output: usize,
+    /// Input wires x, y and z
+    pub inputs: [usize; 3],
+    /// Coefficient
+    pub coeff: F,
+}
+
 /// A layered arithmetic circuit
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Circuit<F: Field> {
@@ -140,6 +266,60 @@ impl<F: Field> Circuit<F> {
         }
     }
     
+    /// Lay out a Ligero constraint system as a circuit whose outputs are
+    /// the constraints' residuals
+    ///
+    /// The inputs are the witness followed by the public inputs, padded to
+    /// a power of two as `constraint_system_inputs` lays them out. The
+    /// outputs are A_j w + P_j x - b_j for each linear row j, then
+    /// w[x] w[y] - w[z] for each quadratic triple. Every residual is at
+    /// most quadratic in the inputs, so one layer holds them all, and a
+    /// satisfying assignment zeroes every output.
+    pub fn from_constraint_system(cs: &ConstraintSystem<F>) -> Result<Self> {
+        let linear = &cs.linear_constraints;
+        let quadratic = &cs.quadratic_constraints.constraints;
+        let nout = log2_ceil(linear.num_constraints + quadratic.len());
+        let nin = log2_ceil(cs.num_witnesses + cs.num_public);
+        let mut layer = Layer::new(nout, nin, nout);
+        
+        let rows = linear.matrix.rows().zip(linear.public_matrix.rows()).zip(&linear.rhs);
+        for (output, ((row, public_row), &rhs)) in rows.enumerate() {
+            for (col, coeff) in row.iter() {
+                layer.add_linear_term(output, col, coeff)?;
+            }
+            for (col, coeff) in public_row.iter() {
+                layer.add_linear_term(output, cs.num_witnesses + col, coeff)?;
+            }
+            layer.add_gate(output, 0, 0, GateType::Const(-rhs))?;
+        }
+        
+        for (i, &(x, y, z)) in quadratic.iter().enumerate() {
+            let output = linear.num_constraints + i;
+            layer.add_gate(output, x, y, GateType::Mul(F::one()))?;
+            layer.add_linear_term(output, z, -F::one())?;
+        }
+        
+        let mut circuit = Self::new();
+        circuit.num_public_inputs = cs.num_public;
+        circuit.add_layer(layer)?;
+        Ok(circuit)
+    }
+    
+    /// Inputs of the circuit `from_constraint_system` lays out: the
+    /// witness, then the public inputs, then zeros
+    pub fn constraint_system_inputs(cs: &ConstraintSystem<F>, witness: &[F], public: &[F]) -> Result<Vec<F>> {
+        if witness.len() != cs.num_witnesses || public.len() != cs.num_public {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Expected {} witnesses and {} public inputs, got {} and {}",
+                cs.num_witnesses, cs.num_public, witness.len(), public.len()
+            )));
+        }
+        
+        let mut inputs = [witness, public].concat();
+        inputs.resize(1 << log2_ceil(inputs.len()), F::zero());
+        Ok(inputs)
+    }
+    
     /// Add a layer to the circuit
     pub fn add_layer(&mut self, layer: Layer<F>) -> Result<()> {
         // Validate layer dimensions match
@@ -196,19 +376,16 @@ impl<F: Field> Circuit<F> {
                 // Evaluate layer
                 for (g, left, right, coeff) in layer.quad.iter() {
                     let out_idx = output_offset + g;
-                    let left_val = if left > 0 {
-                        current[input_offset + left - 1]
-                    } else {
-                        F::one()
-                    };
-                    let right_val = if right > 0 {
-                        current[input_offset + right - 1]
-                    } else {
-                        F::one()
-                    };
+                    let left_val = hand_wire(left).map_or(F::one(), |w| current[input_offset + w]);
+                    let right_val = hand_wire(right).map_or(F::one(), |w| current[input_offset + w]);
                     
                     next[out_idx] += coeff * left_val * right_val;
                 }
+                
+                for gate in &layer.cubic {
+                    let [x, y, z] = gate.inputs.map(|h| current[input_offset + h]);
+                    next[output_offset + gate.output] += gate.coeff * x * y * z;
+                }
             }
             
             current = next;
@@ -257,6 +434,11 @@ impl<F: Field> Circuit<F> {
     }
 }
 
+/// Variables indexing `n` values, padded to a power of two
+pub(crate) fn log2_ceil(n: usize) -> usize {
+    n.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
 /// Builder for constructing circuits
 pub struct CircuitBuilder<F: Field> {
     circuit: Circuit<F>,
@@ -284,6 +466,14 @@ impl<F: Field> CircuitBuilder<F> {
         Ok(())
     }
     
+    /// The layer under construction
+    fn layer_mut(&mut self) -> Result<&mut Layer<F>> {
+        self.current_layer.as_mut()
+            .ok_or_else(|| LongfellowError::InvalidParameter(
+                "No layer started".to_string()
+            ))
+    }
+    
     /// Add a gate to the current layer
     pub fn add_gate(
         &mut self,
@@ -292,14 +482,35 @@ impl<F: Field> CircuitBuilder<F> {
         right: usize,
         gate_type: GateType<F>,
     ) -> Result<()> {
-        let layer = self.current_layer.as_mut()
-            .ok_or_else(|| LongfellowError::InvalidParameter(
-                "No layer started".to_string()
-            ))?;
+        let layer = self.layer_mut()?;
         
         layer.add_gate(output, left, right, gate_type)
     }
     
+    /// Add a single linear term to the current layer
+    pub fn add_linear_term(&mut self, output: usize, input: usize, coeff: F) -> Result<()> {
+        self.layer_mut()?.add_linear_term(output, input, coeff)
+    }
+    
+    /// Add a constant gate to the current layer
+    pub fn add_const_gate(&mut self, output: usize, value: F) -> Result<()> {
+        self.layer_mut()?.add_const_gate(output, value)
+    }
+    
+    /// Add a gate of degree three to the current layer
+    pub fn add_cubic_gate(&mut self, output: usize, inputs: [usize; 3], coeff: F) -> Result<()> {
+        let layer = self.layer_mut()?;
+        
+        layer.add_cubic_gate(output, inputs, coeff)
+    }
+    
+    /// Add a selector-weighted sum to the current layer
+    pub fn add_selected_sum(&mut self, output: usize, selector: usize, terms: &[(usize, F)]) -> Result<()> {
+        let layer = self.layer_mut()?;
+        
+        layer.add_selected_sum(output, selector, terms)
+    }
+    
     /// Finalize the current layer
     pub fn finalize_layer(&mut self) -> Result<()> {
         let layer = self.current_layer.take()
@@ -363,6 +574,118 @@ mod tests {
         assert_eq!(outputs[1], Fp128::from(20)); // 4 * 5
     }
     
+    #[test]
+    fn test_wiring_at_boolean_points() {
+        let mut layer = Layer::<Fp128>::new(1, 1, 1);
+        layer.add_gate(0, 0, 1, GateType::Mul(Fp128::from(3))).unwrap();
+        layer.add_gate(1, 0, 1, GateType::Add(Fp128::one())).unwrap();
+        layer.add_gate(1, 0, 0, GateType::Const(Fp128::from(4))).unwrap();
+        
+        let zero = [Fp128::zero()];
+        let one = [Fp128::one()];
+        let weights = [Fp128::from(2), Fp128::from(5)];
+        
+        // The mul gate reads wires 0 and 1; the add gate reads each wire
+        // against the constant one, so its part at `b` is the same for both
+        assert_eq!(
+            layer.wiring_at(&weights, &zero, &one).unwrap(),
+            [Fp128::from(6), Fp128::from(5), Fp128::from(20)]
+        );
+        assert_eq!(
+            layer.wiring_at(&weights, &one, &zero).unwrap(),
+            [Fp128::zero(), Fp128::from(5), Fp128::from(20)]
+        );
+    }
+    
+    #[test]
+    fn test_composite_gates() {
+        // Keccak's chi: a ^ (!b & c) on bits is a + c - b c - 2 a c + 2 a b c
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 2, 1).unwrap();
+        let two = Fp128::from(2);
+        builder.add_gate(0, 0, 2, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(0, 1, 2, GateType::Mul(-Fp128::one())).unwrap();
+        builder.add_gate(0, 0, 2, GateType::Mul(-two)).unwrap();
+        builder.add_cubic_gate(0, [0, 1, 2], two).unwrap();
+        
+        // Output 1 = s * (b + 3 c)
+        builder.add_selected_sum(1, 3, &[(1, Fp128::one()), (2, Fp128::from(3))]).unwrap();
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        assert_eq!(circuit.layers[0].num_hands(), 3);
+        
+        for bits in 0..8u64 {
+            let (a, b, c) = (bits & 1, (bits >> 1) & 1, (bits >> 2) & 1);
+            let inputs = [a, b, c, 5].map(Fp128::from_u64).to_vec();
+            let outputs = circuit.evaluate(&inputs, 1).unwrap();
+            assert_eq!(outputs[0], Fp128::from_u64(a ^ ((1 - b) & c)));
+            assert_eq!(outputs[1], Fp128::from_u64(5 * (b + 3 * c)));
+        }
+        
+        let mut layer = Layer::<Fp128>::new(0, 2, 0);
+        assert!(layer.add_cubic_gate(0, [0, 1, 4], two).is_err());
+    }
+    
+    #[test]
+    fn test_const_and_linear_gates() {
+        // Output 0 = 7 + 2 x1, output 1 = x0 - 3
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        assert!(builder.add_const_gate(0, Fp128::one()).is_err());
+        builder.begin_layer(1, 1, 1).unwrap();
+        builder.add_const_gate(0, Fp128::from(7)).unwrap();
+        builder.add_linear_term(0, 1, Fp128::from(2)).unwrap();
+        builder.add_linear_term(1, 0, Fp128::one()).unwrap();
+        builder.add_const_gate(1, -Fp128::from(3)).unwrap();
+        
+        // Wires that do not exist are refused rather than wrapped
+        assert!(builder.add_const_gate(2, Fp128::one()).is_err());
+        assert!(builder.add_linear_term(0, 2, Fp128::one()).is_err());
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        let inputs = [10u64, 4].map(Fp128::from_u64).to_vec();
+        let outputs = circuit.evaluate(&inputs, 1).unwrap();
+        assert_eq!(outputs[0], Fp128::from(15));
+        assert_eq!(outputs[1], Fp128::from(7));
+        
+        // A corner written past the layer's outputs or inputs fails validation
+        let mut layer = Layer::<Fp128>::new(1, 1, 1);
+        layer.quad.add_corner(2, ONE_HAND, ONE_HAND, Fp128::one()).unwrap();
+        assert!(layer.validate().is_err());
+        let mut layer = Layer::<Fp128>::new(1, 1, 1);
+        layer.quad.add_corner(0, wire_hand(2), ONE_HAND, Fp128::one()).unwrap();
+        assert!(layer.validate().is_err());
+    }
+    
+    #[test]
+    fn test_from_constraint_system() {
+        // w2 = w0 * w1 and w0 + 2 w2 + x0 = 18
+        let mut cs = ConstraintSystem::<Fp128>::with_public_inputs(3, 1);
+        cs.add_quadratic_constraint(0, 1, 2);
+        cs.add_linear_constraint_with_public(
+            vec![(0, Fp128::one()), (2, Fp128::from(2))],
+            vec![(0, Fp128::one())],
+            Fp128::from(18),
+        );
+        let circuit = Circuit::from_constraint_system(&cs).unwrap();
+        assert_eq!(circuit.layers.len(), 1);
+        assert_eq!(circuit.num_public_inputs, 1);
+        
+        let public = [Fp128::from(4)];
+        let witness = [2u64, 3, 6].map(Fp128::from_u64);
+        let inputs = Circuit::constraint_system_inputs(&cs, &witness, &public).unwrap();
+        assert_eq!(inputs.len(), 4);
+        assert!(circuit.evaluate(&inputs, 1).unwrap().iter().all(|r| *r == Fp128::zero()));
+        
+        // Residuals of a witness breaking both constraints: 2 + 16 + 4 - 18
+        // for the linear row, 2 * 3 - 8 for the product
+        let witness = [2u64, 3, 8].map(Fp128::from_u64);
+        let inputs = Circuit::constraint_system_inputs(&cs, &witness, &public).unwrap();
+        let residuals = circuit.evaluate(&inputs, 1).unwrap();
+        assert_eq!(residuals[0], Fp128::from(4));
+        assert_eq!(residuals[1], -Fp128::from(2));
+    }
+    
     #[test]
     fn test_multi_layer_circuit() {
         let mut builder = CircuitBuilder::<Fp128>::new();
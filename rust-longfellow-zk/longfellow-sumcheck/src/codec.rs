@@ -0,0 +1,245 @@
+//! Canonical binary encoding of sumcheck proofs
+//!
+//! A version byte, then each layer proof and the input evaluations, every
+//! list behind its LEB128 varint length:
+//!
+//! - the number of layer proofs
+//! - per layer, the copy round polynomials, the hand round polynomials
+//!   (each a list of coefficients) and the wire claims
+//! - the input evaluations
+//!
+//! Compressed proofs, whose round polynomials leave out their linear
+//! coefficient, share the layout under their own version byte.
+//!
+//! Field elements use their fixed-width little-endian canonical encoding,
+//! so a proof has exactly one encoding and the C++ side reads it without
+//! knowing the circuit. The `serialize` and `deserialize` functions wrap
+//! the encoding for `#[serde(with = "longfellow_sumcheck::codec")]`.
+
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::{LayerProof, SumcheckProof};
+
+/// Version byte leading every encoded proof
+pub const CODEC_VERSION: u8 = 1;
+
+/// Version byte leading every encoded compressed proof
+pub const CODEC_VERSION_COMPRESSED: u8 = 2;
+
+impl<F: Field> SumcheckProof<F> {
+    /// Encode in the canonical binary layout
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![if self.compressed { CODEC_VERSION_COMPRESSED } else { CODEC_VERSION }];
+        write_varint(&mut out, self.layer_proofs.len() as u64);
+        for layer in &self.layer_proofs {
+            write_polys(&mut out, &layer.copy_polys);
+            write_polys(&mut out, &layer.hand_polys);
+            write_elements(&mut out, &layer.wire_claims);
+        }
+        write_elements(&mut out, &self.input_eval);
+        out
+    }
+    
+    /// Decode from the canonical binary layout, rejecting other versions,
+    /// non-canonical elements and trailing bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, pos: 0 };
+        let compressed = match reader.take(1)?[0] {
+            CODEC_VERSION => false,
+            CODEC_VERSION_COMPRESSED => true,
+            version => {
+                return Err(LongfellowError::SerializationError(format!(
+                    "Unsupported sumcheck proof version {}, expected {} or {}",
+                    version, CODEC_VERSION, CODEC_VERSION_COMPRESSED
+                )));
+            }
+        };
+        
+        let num_layers = reader.length(1)?;
+        let layer_proofs = (0..num_layers)
+            .map(|_| {
+                Ok(LayerProof {
+                    copy_polys: reader.polys()?,
+                    hand_polys: reader.polys()?,
+                    wire_claims: reader.elements()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let input_eval = reader.elements()?;
+        reader.finish()?;
+        
+        Ok(Self {
+            layer_proofs,
+            input_eval,
+            compressed,
+        })
+    }
+}
+
+/// Serialize a proof as the bytes of its canonical encoding
+pub fn serialize<F: Field, S: Serializer>(
+    proof: &SumcheckProof<F>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(&proof.to_bytes())
+}
+
+/// Deserialize a proof from the bytes of its canonical encoding
+pub fn deserialize<'de, F: Field, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<SumcheckProof<F>, D::Error> {
+    let bytes = Vec::<u8>::deserialize(deserializer)?;
+    SumcheckProof::from_bytes(&bytes).map_err(serde::de::Error::custom)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_elements<F: Field>(out: &mut Vec<u8>, elements: &[F]) {
+    write_varint(out, elements.len() as u64);
+    for element in elements {
+        out.extend_from_slice(&element.to_canonical_bytes());
+    }
+}
+
+fn write_polys<F: Field>(out: &mut Vec<u8>, polys: &[Vec<F>]) {
+    write_varint(out, polys.len() as u64);
+    for poly in polys {
+        write_elements(out, poly);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.bytes.len()).ok_or_else(|| {
+            LongfellowError::SerializationError("Truncated sumcheck proof".to_string())
+        })?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+    
+    fn finish(&self) -> Result<()> {
+        if self.pos != self.bytes.len() {
+            return Err(LongfellowError::SerializationError(format!(
+                "{} trailing bytes after sumcheck proof",
+                self.bytes.len() - self.pos
+            )));
+        }
+        Ok(())
+    }
+    
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            let bits = (byte & 0x7f) as u64;
+            if bits << shift >> shift != bits {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(LongfellowError::SerializationError("Varint overflows 64 bits".to_string()))
+    }
+    
+    /// A list length, checked against the bytes left for items of at
+    /// least `item_len` bytes each before anything is allocated for it
+    fn length(&mut self, item_len: usize) -> Result<usize> {
+        let len = self.varint()?;
+        let left = (self.bytes.len() - self.pos) / item_len;
+        usize::try_from(len).ok().filter(|&len| len <= left).ok_or_else(|| {
+            LongfellowError::SerializationError(format!(
+                "List of {} items overruns the sumcheck proof",
+                len
+            ))
+        })
+    }
+    
+    fn elements<F: Field>(&mut self) -> Result<Vec<F>> {
+        let width = F::canonical_byte_len();
+        let len = self.length(width)?;
+        (0..len)
+            .map(|_| F::from_canonical_bytes(self.take(width)?))
+            .collect()
+    }
+    
+    fn polys<F: Field>(&mut self) -> Result<Vec<Vec<F>>> {
+        let len = self.length(1)?;
+        (0..len).map(|_| self.elements()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{CircuitBuilder, GateType};
+    use crate::{ProverLayers, SumcheckInstance, SumcheckOptions};
+    use longfellow_algebra::Fp128;
+    use rand::rngs::OsRng;
+    
+    fn proof() -> SumcheckProof<Fp128> {
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 2, 1).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::one())).unwrap();
+        builder.add_gate(1, 2, 3, GateType::Add(Fp128::from(2))).unwrap();
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        let inputs: Vec<Fp128> = (0..12u64).map(|i| Fp128::from(i + 1)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, 3).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), 3, claimed_sum).unwrap();
+        ProverLayers::new(circuit, &inputs, 3, SumcheckOptions::default())
+            .unwrap()
+            .prove(&instance, &mut OsRng)
+            .unwrap()
+    }
+    
+    #[test]
+    fn test_roundtrip() {
+        let proof = proof();
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes[0], CODEC_VERSION);
+        
+        let decoded = SumcheckProof::<Fp128>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.layer_proofs[0].copy_polys, proof.layer_proofs[0].copy_polys);
+        assert_eq!(decoded.layer_proofs[0].hand_polys, proof.layer_proofs[0].hand_polys);
+        assert_eq!(decoded.layer_proofs[0].wire_claims, proof.layer_proofs[0].wire_claims);
+        assert_eq!(decoded.input_eval, proof.input_eval);
+        assert_eq!(decoded.to_bytes(), bytes);
+    }
+    
+    #[test]
+    fn test_rejects_malformed() {
+        let bytes = proof().to_bytes();
+        
+        let mut other_version = bytes.clone();
+        other_version[0] = CODEC_VERSION_COMPRESSED + 1;
+        assert!(SumcheckProof::<Fp128>::from_bytes(&other_version).is_err());
+        
+        assert!(SumcheckProof::<Fp128>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(SumcheckProof::<Fp128>::from_bytes(&trailing).is_err());
+        
+        // A layer count no proof of this length could hold
+        assert!(SumcheckProof::<Fp128>::from_bytes(&[CODEC_VERSION, 0xff, 0x7f]).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! Fluent authoring of layered circuits
+//!
+//! A layer is written as one expression per output over the previous
+//! layer's outputs, with `+`, `-` and `*` on `Expr`, and compiled into the
+//! quad and cubic gates of a `Layer`:
+//!
+//! ```text
+//! let circuit = CircuitDsl::new(4)
+//!     .layer([wire(0) * wire(1) + wire(2), wire(3) + Expr::constant(F::from(3))])
+//!     .layer([wire(0) * wire(1)])
+//!     .build()?;
+//! ```
+//!
+//! Layers are given from the inputs up, as they are evaluated, and sized
+//! to the next power of two; `build` lays them out output first.
+
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+
+use crate::circuit::{log2_ceil, Circuit, GateType, Layer};
+
+/// A polynomial of the wires a layer reads, as (coefficient, wires)
+/// monomials with the wires of each sorted
+#[derive(Clone, Debug)]
+pub struct Expr<F: Field> {
+    terms: Vec<(F, Vec<usize>)>,
+}
+
+/// The input wire `index` of the layer being written
+pub fn wire<F: Field>(index: usize) -> Expr<F> {
+    Expr::wire(index)
+}
+
+impl<F: Field> Expr<F> {
+    /// The input wire `index` of the layer being written
+    pub fn wire(index: usize) -> Self {
+        Self { terms: vec![(F::one(), vec![index])] }
+    }
+    
+    /// The constant `value`
+    pub fn constant(value: F) -> Self {
+        Self { terms: vec![(value, Vec::new())] }
+    }
+    
+    /// The zero polynomial
+    pub fn zero() -> Self {
+        Self { terms: Vec::new() }
+    }
+    
+    /// Multiply by the constant `value`
+    pub fn scale(mut self, value: F) -> Self {
+        for (coeff, _) in &mut self.terms {
+            *coeff *= value;
+        }
+        self
+    }
+    
+    /// Highest number of wires in a monomial; gates read at most three
+    pub fn degree(&self) -> usize {
+        self.terms.iter().map(|(_, wires)| wires.len()).max().unwrap_or(0)
+    }
+    
+    /// Like monomials collected, zero ones dropped
+    pub fn monomials(&self) -> BTreeMap<Vec<usize>, F> {
+        let mut monomials = BTreeMap::new();
+        for (coeff, wires) in &self.terms {
+            *monomials.entry(wires.clone()).or_insert_with(F::zero) += *coeff;
+        }
+        monomials.retain(|_, coeff| *coeff != F::zero());
+        monomials
+    }
+}
+
+impl<F: Field> Add for Expr<F> {
+    type Output = Self;
+    
+    fn add(mut self, other: Self) -> Self {
+        self.terms.extend(other.terms);
+        self
+    }
+}
+
+impl<F: Field> Neg for Expr<F> {
+    type Output = Self;
+    
+    fn neg(self) -> Self {
+        self.scale(-F::one())
+    }
+}
+
+impl<F: Field> Sub for Expr<F> {
+    type Output = Self;
+    
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<F: Field> Mul for Expr<F> {
+    type Output = Self;
+    
+    fn mul(self, other: Self) -> Self {
+        let mut terms = Vec::with_capacity(self.terms.len() * other.terms.len());
+        for (a, left) in &self.terms {
+            for (b, right) in &other.terms {
+                let mut wires = [left.as_slice(), right].concat();
+                wires.sort_unstable();
+                terms.push((*a * *b, wires));
+            }
+        }
+        Self { terms }
+    }
+}
+
+impl<F: Field> std::iter::Sum for Expr<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+/// Builder writing a circuit layer by layer from its inputs
+#[derive(Clone, Debug)]
+pub struct CircuitDsl<F: Field> {
+    num_inputs: usize,
+    num_public_inputs: usize,
+    layers: Vec<Vec<Expr<F>>>,
+}
+
+impl<F: Field> CircuitDsl<F> {
+    /// Start a circuit over `num_inputs` input wires per copy
+    pub fn new(num_inputs: usize) -> Self {
+        Self {
+            num_inputs,
+            num_public_inputs: 0,
+            layers: Vec::new(),
+        }
+    }
+    
+    /// Mark the first `num` inputs public
+    pub fn public_inputs(mut self, num: usize) -> Self {
+        self.num_public_inputs = num;
+        self
+    }
+    
+    /// Add a layer computing `outputs` from the outputs of the layer
+    /// before, or from the inputs for the first layer
+    pub fn layer(mut self, outputs: impl IntoIterator<Item = Expr<F>>) -> Self {
+        self.layers.push(outputs.into_iter().collect());
+        self
+    }
+    
+    /// Compile every layer, checking that each reads only wires the layer
+    /// before it has
+    pub fn build(self) -> Result<Circuit<F>> {
+        if self.num_public_inputs > self.num_inputs {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "{} public inputs of {} inputs",
+                self.num_public_inputs, self.num_inputs
+            )));
+        }
+        
+        let mut width = self.num_inputs;
+        let mut layers = Vec::with_capacity(self.layers.len());
+        for (idx, outputs) in self.layers.iter().enumerate() {
+            layers.push(Self::compile_layer(idx, outputs, width)?);
+            width = outputs.len();
+        }
+        
+        let mut circuit = Circuit::new();
+        circuit.num_public_inputs = self.num_public_inputs;
+        for layer in layers.into_iter().rev() {
+            circuit.add_layer(layer)?;
+        }
+        circuit.validate()?;
+        Ok(circuit)
+    }
+    
+    /// The gates of one layer: a constant, linear term, product or cubic
+    /// gate per monomial
+    fn compile_layer(idx: usize, outputs: &[Expr<F>], width: usize) -> Result<Layer<F>> {
+        if outputs.is_empty() {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Layer {} from the inputs has no outputs",
+                idx
+            )));
+        }
+        
+        let nout = log2_ceil(outputs.len());
+        let mut layer = Layer::new(nout, log2_ceil(width), nout);
+        for (output, expr) in outputs.iter().enumerate() {
+            for (wires, coeff) in expr.monomials() {
+                if let Some(&w) = wires.iter().find(|&&w| w >= width) {
+                    return Err(LongfellowError::InvalidParameter(format!(
+                        "Output {} of layer {} from the inputs reads wire {} of {}",
+                        output, idx, w, width
+                    )));
+                }
+                
+                match wires[..] {
+                    [] => layer.add_const_gate(output, coeff)?,
+                    [w] => layer.add_linear_term(output, w, coeff)?,
+                    [x, y] => layer.add_gate(output, x, y, GateType::Mul(coeff))?,
+                    [x, y, z] => layer.add_cubic_gate(output, [x, y, z], coeff)?,
+                    _ => {
+                        return Err(LongfellowError::InvalidParameter(format!(
+                            "Output {} of layer {} from the inputs has degree {}, gates read at most 3 wires",
+                            output, idx, wires.len()
+                        )));
+                    }
+                }
+            }
+        }
+        
+        Ok(layer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProverLayers, SumcheckInstance, SumcheckOptions, VerifierLayers};
+    use longfellow_algebra::Fp128;
+    use rand::rngs::OsRng;
+    
+    fn c(value: u64) -> Expr<Fp128> {
+        Expr::constant(Fp128::from_u64(value))
+    }
+    
+    #[test]
+    fn test_layers_compile() {
+        // (x0 x1 + x2, x3 + 3, x0 x1 x2 - 2 x3), then (y0 y1 - y2, y2 + 1),
+        // then z0 + z1
+        let circuit = CircuitDsl::<Fp128>::new(4)
+            .layer([
+                wire(0) * wire(1) + wire(2),
+                wire(3) + c(3),
+                wire(0) * wire(1) * wire(2) - wire(3).scale(Fp128::from(2)),
+            ])
+            .layer([wire(0) * wire(1) - wire(2), wire(2) + c(1)])
+            .layer([(0..2).map(wire).sum::<Expr<Fp128>>()])
+            .build()
+            .unwrap();
+        assert_eq!(circuit.layers.len(), 3);
+        assert_eq!(circuit.layers[2].num_hands(), 3);
+        assert_eq!((circuit.layers[1].nout, circuit.layers[1].nin), (1, 2));
+        
+        let inputs = [2u64, 3, 4, 5].map(Fp128::from_u64).to_vec();
+        let (y0, y1, y2) = (10u64, 8, 14);
+        let expected = Fp128::from_u64(y0 * y1 - y2 + y2 + 1);
+        assert_eq!(circuit.evaluate(&inputs, 1).unwrap()[0], expected);
+        
+        // The compiled circuit proves and verifies over several copies
+        let inputs: Vec<Fp128> = (0..12u64).map(|i| Fp128::from_u64(i + 1)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, 3).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), 3, claimed_sum).unwrap();
+        let proof = ProverLayers::new(circuit.clone(), &inputs, 3, SumcheckOptions::default())
+            .unwrap()
+            .prove(&instance, &mut OsRng)
+            .unwrap();
+        assert!(VerifierLayers::new(circuit).verify_with_inputs(&instance, &proof, &inputs).unwrap());
+    }
+    
+    #[test]
+    fn test_like_terms_combine() {
+        // x0 x1 - x1 x0 cancels, leaving 2 x0 + 5
+        let expr = wire(0) * wire(1) - wire(1) * wire(0) + wire(0) + wire(0) + c(5);
+        let monomials = expr.monomials();
+        assert_eq!(monomials.len(), 2);
+        assert_eq!(monomials[&vec![0usize]], Fp128::from(2));
+        assert_eq!(monomials[&Vec::<usize>::new()], Fp128::from(5));
+        
+        let circuit = CircuitDsl::new(2).layer([expr]).build().unwrap();
+        let inputs = [4u64, 9].map(Fp128::from_u64).to_vec();
+        assert_eq!(circuit.evaluate(&inputs, 1).unwrap()[0], Fp128::from(13));
+    }
+    
+    #[test]
+    fn test_rejects_bad_layers() {
+        // Wires past the layer before, even inside its padding
+        assert!(CircuitDsl::<Fp128>::new(3).layer([wire(3)]).build().is_err());
+        assert!(CircuitDsl::<Fp128>::new(4)
+            .layer([wire(0), wire(1), wire(2)])
+            .layer([wire(3)])
+            .build()
+            .is_err());
+        
+        // Degree four, no outputs, no layers, too many public inputs
+        let quartic = wire(0) * wire(1) * wire(2) * wire(3);
+        assert!(CircuitDsl::<Fp128>::new(4).layer([quartic]).build().is_err());
+        assert!(CircuitDsl::<Fp128>::new(4).layer([]).build().is_err());
+        assert!(CircuitDsl::<Fp128>::new(4).build().is_err());
+        assert!(CircuitDsl::<Fp128>::new(4).public_inputs(5).layer([wire(0)]).build().is_err());
+    }
+}
@@ -4,22 +4,28 @@
 /// with support for parallel evaluation of multiple circuit copies.
 
 pub mod circuit;
+pub mod codec;
+pub mod dsl;
 pub mod quad;
 pub mod prover;
 pub mod verifier;
 pub mod transcript;
 pub mod polynomial;
+pub mod wiring;
 
+use longfellow_algebra::multilinear::DenseMultilinear;
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use serde::{Deserialize, Serialize};
 
-pub use circuit::{Circuit, Layer};
+pub use circuit::{Circuit, CubicGate, Layer};
+pub use dsl::{CircuitDsl, Expr};
 pub use quad::{Quad, QuadCorner};
-pub use prover::{Prover, ProverLayers};
+pub use prover::{Prover, ProverLayers, RoundScratch};
 pub use verifier::{Verifier, VerifierLayers};
 pub use transcript::SumcheckTranscript;
 pub use polynomial::{UnivariatePoly, MultilinearPoly};
+pub use wiring::{Wiring, WeightedWiring};
 
 /// Maximum number of variable bindings per layer (2^40)
 pub const MAX_BINDINGS: usize = 40;
@@ -37,14 +43,180 @@ pub struct LayerProof<F: Field> {
     pub wire_claims: Vec<F>,
 }
 
+/// What one layer's sumcheck proves: a weighted sum of the layer's
+/// outputs over every copy and output wire
+///
+/// At the output layer every weight is one, so the claim is the sum of all
+/// outputs. Each further layer is bound to the previous layer's wire
+/// claims W(c, a), W(c, b) and, past cubic gates, W(c, d), combined with
+/// powers of a challenge: its copies are weighted by eq(c, .) and its
+/// outputs by eq(a, .) + alpha * eq(b, .) + alpha^2 * eq(d, .).
+///
+/// Copy weights are kept as the point c rather than the table eq(c, .):
+/// eq factors over the copy variables, so the prover carries one scalar for
+/// the variables it has bound and the verifier evaluates the weight of its
+/// final point in time linear in the number of copy variables.
+#[derive(Clone, Debug)]
+pub struct LayerClaim<F: Field> {
+    /// Point whose eq polynomial weights the copies, or `None` at the
+    /// output layer, where every copy weighs one
+    pub copy_point: Option<Vec<F>>,
+    
+    /// Weight of each output wire
+    pub gate_weights: Vec<F>,
+    
+    /// Claimed weighted sum
+    pub value: F,
+}
+
+impl<F: Field> LayerClaim<F> {
+    /// The sum of all outputs of a layer with `2^nout` outputs per copy
+    pub fn output_sum(nout: usize, value: F) -> Self {
+        Self {
+            copy_point: None,
+            gate_weights: vec![F::one(); 1 << nout],
+            value,
+        }
+    }
+    
+    /// The sum over copies of the outputs' extension at `binding`, one
+    /// variable per output variable
+    pub fn output_at(binding: &[F], value: F) -> Self {
+        Self {
+            copy_point: None,
+            gate_weights: DenseMultilinear::eq(binding).into_evals(),
+            value,
+        }
+    }
+    
+    /// The claim on a layer's outputs left by the sumcheck of the layer
+    /// above, which ended at `point` (its copy variables, then one point
+    /// per hand) with a wire claim per hand in `wire_claims`
+    pub fn link(point: &[F], num_copy_vars: usize, wire_claims: &[F], alpha: F) -> Self {
+        let (copy_point, hands) = point.split_at(num_copy_vars);
+        let nin = hands.len() / wire_claims.len().max(1);
+        let mut gate_weights = vec![F::zero(); 1 << nin];
+        let mut value = F::zero();
+        let mut power = F::one();
+        
+        for (j, &wire_claim) in wire_claims.iter().enumerate() {
+            let eq = DenseMultilinear::eq(&hands[j * nin..(j + 1) * nin]).into_evals();
+            for (weight, e) in gate_weights.iter_mut().zip(eq) {
+                *weight += power * e;
+            }
+            value += power * wire_claim;
+            power *= alpha;
+        }
+        
+        Self {
+            copy_point: Some(copy_point.to_vec()),
+            gate_weights,
+            value,
+        }
+    }
+    
+    /// Weight of the copy at `copy_point`, a product of one factor per copy
+    /// variable
+    pub fn copy_weight(&self, copy_point: &[F]) -> Result<F> {
+        match &self.copy_point {
+            None => Ok(F::one()),
+            Some(point) if point.len() == copy_point.len() => Ok(point
+                .iter()
+                .zip(copy_point)
+                .fold(F::one(), |acc, (&r, &c)| acc * eq_linear(r, c))),
+            Some(point) => Err(LongfellowError::InvalidParameter(format!(
+                "Copy point has {} variables, expected {}",
+                copy_point.len(),
+                point.len()
+            ))),
+        }
+    }
+    
+    /// The polynomial a layer's sumcheck runs over, at `point`, given the
+    /// layer's input wires there: W(c, a), W(c, b) and, for a layer with
+    /// cubic gates, W(c, d) in `wires`
+    ///
+    /// With E the copy weights, the wiring of `Layer::wiring_at` and V the
+    /// indicator of unpadded copies, this is
+    /// E(c) (mul(a, b) W(c, a) W(c, b) + add(b) eq(a, 0) W(c, b) +
+    /// constant eq(a, 0) eq(b, 0) V(c)),
+    /// whose sum over the hypercube is the weighted sum of the outputs.
+    /// Cubic gates add a third hand: the quadratic part is taken with
+    /// eq(d, 0) and cubic(a, b, d) W(c, a) W(c, b) W(c, d) added to it.
+    pub fn evaluate_at(
+        &self,
+        layer: &Layer<F>,
+        num_copies: usize,
+        point: &[F],
+        wires: &[F],
+    ) -> Result<F> {
+        let num_copy_vars = num_copy_vars(num_copies);
+        let num_hands = layer.num_hands();
+        if point.len() != num_copy_vars + num_hands * layer.nin || wires.len() != num_hands {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Layer point has {} variables and {} wires, expected {} and {}",
+                point.len(),
+                wires.len(),
+                num_copy_vars + num_hands * layer.nin,
+                num_hands
+            )));
+        }
+        
+        let (copy_point, hands) = point.split_at(num_copy_vars);
+        let (a, rest) = hands.split_at(layer.nin);
+        let (b, d) = rest.split_at(layer.nin);
+        let wiring = layer.wiring()?;
+        let [mul, add, constant] = wiring.evaluate_at(&self.gate_weights, a, b)?;
+        let copy_weight = self.copy_weight(copy_point)?;
+        let zero_a = eq_at_zero(a);
+        
+        let quadratic = mul * wires[0] * wires[1]
+            + add * zero_a * wires[1]
+            + constant * zero_a * eq_at_zero(b) * copies_indicator(copy_point, num_copies);
+        if num_hands == 2 {
+            return Ok(copy_weight * quadratic);
+        }
+        
+        let cubic = wiring.evaluate_cubic_at(&self.gate_weights, a, b, d)?;
+        Ok(copy_weight * (quadratic * eq_at_zero(d) + cubic * wires[0] * wires[1] * wires[2]))
+    }
+}
+
 /// Complete sumcheck proof for a circuit
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SumcheckProof<F: Field> {
     /// Proofs for each layer (from output to input)
     pub layer_proofs: Vec<LayerProof<F>>,
     
-    /// Final input evaluation
+    /// Claimed evaluations of the inputs' extension at the two points the
+    /// last layer's sumcheck ends at, for checking against the inputs or
+    /// a commitment to them
     pub input_eval: Vec<F>,
+    
+    /// Whether the round polynomials leave out their linear coefficient
+    #[serde(default)]
+    pub compressed: bool,
+}
+
+impl<F: Field> SumcheckProof<F> {
+    /// Leave the linear coefficient out of every round polynomial
+    ///
+    /// Each round's claim is p(0) + p(1) = 2 c0 + c1 + c2 + ..., so the
+    /// verifier recovers c1 from the claim it already holds, and the round
+    /// check holds by construction. A proof loses one element per round,
+    /// a third of each hand round, and keeps its challenges: the
+    /// transcript takes the recovered polynomials.
+    pub fn compress(&mut self) {
+        if self.compressed {
+            return;
+        }
+        for layer in &mut self.layer_proofs {
+            for poly in layer.copy_polys.iter_mut().chain(&mut layer.hand_polys) {
+                *poly = UnivariatePoly::new(std::mem::take(poly)).compress();
+            }
+        }
+        self.compressed = true;
+    }
 }
 
 /// Sumcheck instance representing the claim to be proven
@@ -56,7 +228,8 @@ pub struct SumcheckInstance<F: Field> {
     /// Number of parallel copies
     pub num_copies: usize,
     
-    /// Claimed output sum
+    /// Claimed output sum, or with an output binding the sum over copies
+    /// of the outputs' extension at it
     pub claimed_sum: F,
     
     /// Output binding point (if partially bound)
@@ -100,10 +273,26 @@ impl<F: Field> SumcheckInstance<F> {
         Ok(self)
     }
     
-    /// Get total number of variables in the instance
+    /// The claim the output layer's sumcheck proves, with the output
+    /// binding, if any, appended to `transcript` first
+    ///
+    /// Without a binding it is the sum of every output; with one, a
+    /// verifier-chosen point, each output is weighted by eq at it, so a
+    /// circuit of several outputs is held to all of them at once.
+    pub fn output_claim(&self, transcript: &mut SumcheckTranscript) -> LayerClaim<F> {
+        match &self.output_binding {
+            Some(binding) => {
+                transcript.append_output_binding(binding);
+                LayerClaim::output_at(binding, self.claimed_sum)
+            }
+            None => LayerClaim::output_sum(self.circuit.num_output_vars(), self.claimed_sum),
+        }
+    }
+    
+    /// Get total number of variables in the instance, with copies padded
+    /// to a power of two as in `pad_copies`
     pub fn num_vars(&self) -> usize {
-        let copy_vars = (self.num_copies - 1).next_power_of_two().trailing_zeros() as usize;
-        copy_vars + self.circuit.num_vars()
+        num_copy_vars(self.num_copies) + self.circuit.num_vars()
     }
 }
 
@@ -118,6 +307,19 @@ pub struct SumcheckOptions {
     
     /// Batch size for parallel operations
     pub batch_size: usize,
+    
+    /// Keep the wires of every k-th layer only and recompute the rest while
+    /// proving; `None` keeps every layer
+    pub checkpoint_interval: Option<usize>,
+    
+    /// Leave each round polynomial's linear coefficient out of the proof,
+    /// as `SumcheckProof::compress` does
+    pub compress_rounds: bool,
+    
+    /// Check every round polynomial against a brute-force sum over the
+    /// hypercube and panic at the first wrong one, naming its layer and
+    /// round; for debugging on small instances only
+    pub self_check: bool,
 }
 
 impl Default for SumcheckOptions {
@@ -126,6 +328,9 @@ impl Default for SumcheckOptions {
             zero_knowledge: false,
             parallel: true,
             batch_size: 1024,
+            checkpoint_interval: None,
+            compress_rounds: false,
+            self_check: false,
         }
     }
 }
@@ -142,20 +347,71 @@ pub fn multilinear_extension<F: Field>(
         ));
     }
     
-    let mut result = values.to_vec();
+    DenseMultilinear::new(values.to_vec())?.evaluate(point)
+}
+
+/// eq(x, 0) = prod_i (1 - x_i), the extension of the indicator of index 0
+///
+/// Gates reading the constant one are summed as if their constant hand
+/// sat at wire 0 with this weight.
+pub fn eq_at_zero<F: Field>(x: &[F]) -> F {
+    x.iter().fold(F::one(), |acc, &x_i| acc * (F::one() - x_i))
+}
+
+/// eq(r, x) in one variable: (1 - r)(1 - x) + r x
+pub fn eq_linear<F: Field>(r: F, x: F) -> F {
+    (F::one() - r) * (F::one() - x) + r * x
+}
+
+/// Extension at `copy_point` of the indicator of the first `num_copies`
+/// copies, the ones padding does not add
+///
+/// Copies below `num_copies` are those that agree with it on a prefix of
+/// variables, most significant first, and then have a zero where it has a
+/// one, so the sum of their eq weights is one term per variable.
+pub fn copies_indicator<F: Field>(copy_point: &[F], num_copies: usize) -> F {
+    let n = copy_point.len();
+    if num_copies >> n != 0 {
+        return F::one();
+    }
     
-    for (i, &r) in point.iter().enumerate() {
-        let step = 1 << (num_vars - i - 1);
-        for j in (0..result.len()).step_by(2 * step) {
-            for k in 0..step {
-                let low = result[j + k];
-                let high = result[j + k + step];
-                result[j + k] = low + r * (high - low);
-            }
+    let mut below = F::zero();
+    let mut prefix = F::one();
+    for (i, &c) in copy_point.iter().enumerate() {
+        if (num_copies >> (n - 1 - i)) & 1 == 1 {
+            below += prefix * (F::one() - c);
+            prefix *= c;
+        } else {
+            prefix *= F::one() - c;
         }
     }
+    below
+}
+
+/// `values`, `width` per copy, as the table of padded copies a layer's
+/// sumcheck runs over
+///
+/// Copies are padded to a power of two. Values missing at the end of the
+/// given copies read as zero, and so does every padded copy; more values
+/// than `num_copies` copies hold are an error.
+pub fn pad_copies<F: Field>(values: &[F], width: usize, num_copies: usize) -> Result<Vec<F>> {
+    if values.len() > width * num_copies {
+        return Err(LongfellowError::InvalidParameter(format!(
+            "{} values exceed {} copies of {}",
+            values.len(),
+            num_copies,
+            width
+        )));
+    }
     
-    Ok(result[0])
+    let mut table = values.to_vec();
+    table.resize(width << num_copy_vars(num_copies), F::zero());
+    Ok(table)
+}
+
+/// Variables indexing `num_copies` copies, padded to a power of two
+pub fn num_copy_vars(num_copies: usize) -> usize {
+    num_copies.next_power_of_two().trailing_zeros() as usize
 }
 
 /// Compute the number of variables needed for n elements
@@ -191,6 +447,66 @@ mod tests {
         assert_eq!(result, Fp128::from(3) / Fp128::from(2));
     }
     
+    #[test]
+    fn test_layer_claim_link() {
+        // Linking weights gates by eq(a, .) + alpha * eq(b, .), so at
+        // boolean points the weights pick out the two claimed outputs
+        let outputs = [Fp128::from(7), Fp128::from(11), Fp128::from(13), Fp128::from(17)];
+        let left = [Fp128::zero(), Fp128::one()];
+        let right = [Fp128::one(), Fp128::one()];
+        let alpha = Fp128::from(5);
+        let point = [left, right].concat();
+        let claim = LayerClaim::link(&point, 0, &[outputs[1], outputs[3]], alpha);
+        
+        let weighted = claim.gate_weights.iter()
+            .zip(&outputs)
+            .fold(Fp128::zero(), |acc, (&w, &v)| acc + w * v);
+        assert_eq!(weighted, claim.value);
+        assert_eq!(claim.copy_point, Some(Vec::new()));
+    }
+    
+    #[test]
+    fn test_copy_weight_factors() {
+        // The product over copy variables is the eq table's extension
+        let point: Vec<Fp128> = (0..3u64).map(|i| Fp128::from(i + 2)).collect();
+        let at: Vec<Fp128> = (0..3u64).map(|i| Fp128::from(3 * i + 5)).collect();
+        let claim = LayerClaim::link(&point, 3, &[], Fp128::one());
+        let dense = DenseMultilinear::eq(&point).evaluate(&at).unwrap();
+        assert_eq!(claim.copy_weight(&at).unwrap(), dense);
+        assert!(claim.copy_weight(&at[..2]).is_err());
+        
+        let output = LayerClaim::output_sum(0, Fp128::zero());
+        assert_eq!(output.copy_weight(&at).unwrap(), Fp128::one());
+    }
+    
+    #[test]
+    fn test_copies_indicator() {
+        let half = Fp128::one() / Fp128::from(2);
+        assert_eq!(copies_indicator(&[Fp128::one(), Fp128::zero()], 3), Fp128::one());
+        assert_eq!(copies_indicator(&[Fp128::one(), Fp128::one()], 3), Fp128::zero());
+        assert_eq!(copies_indicator(&[half, half], 4), Fp128::one());
+        assert_eq!(eq_at_zero(&[half, half]), half * half);
+        
+        // The sum of the first copies' eq weights at any point
+        let point: Vec<Fp128> = (0..4u64).map(|i| Fp128::from(3 * i + 2)).collect();
+        let eq = DenseMultilinear::eq(&point).into_evals();
+        for num_copies in 1..=16 {
+            let dense = eq[..num_copies].iter().fold(Fp128::zero(), |acc, &e| acc + e);
+            assert_eq!(copies_indicator(&point, num_copies), dense);
+        }
+    }
+    
+    #[test]
+    fn test_pad_copies() {
+        let values: Vec<Fp128> = (1..=5u64).map(Fp128::from).collect();
+        let table = pad_copies(&values, 2, 3).unwrap();
+        assert_eq!(table.len(), 8);
+        assert_eq!(&table[..5], &values[..]);
+        assert!(table[5..].iter().all(|&v| v == Fp128::zero()));
+        
+        assert!(pad_copies(&values, 2, 2).is_err());
+    }
+    
     #[test]
     fn test_num_vars_for_size() {
         assert_eq!(num_vars_for_size(0), 0);
@@ -1,5 +1,6 @@
 /// Polynomial utilities for sumcheck protocol
 
+use longfellow_algebra::multilinear::DenseMultilinear;
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use serde::{Deserialize, Serialize};
@@ -96,6 +97,29 @@ impl<F: Field> UnivariatePoly<F> {
         Ok(Self::new(coeffs))
     }
     
+    /// The coefficients less the linear one, which `from_compressed`
+    /// recovers from p(0) + p(1)
+    pub fn compress(&self) -> Vec<F> {
+        let mut coeffs = self.coeffs.clone();
+        coeffs.resize(coeffs.len().max(2), F::zero());
+        coeffs.remove(1);
+        coeffs
+    }
+    
+    /// The polynomial with p(0) + p(1) = `sum` and every coefficient but
+    /// the linear one from `compressed`
+    pub fn from_compressed(compressed: &[F], sum: F) -> Self {
+        let mut coeffs = compressed.to_vec();
+        if coeffs.is_empty() {
+            coeffs.push(F::zero());
+        }
+        
+        // p(0) + p(1) = 2 c0 + c1 + c2 + ...
+        let rest = coeffs.iter().fold(coeffs[0], |acc, &c| acc + c);
+        coeffs.insert(1, sum - rest);
+        Self::new(coeffs)
+    }
+    
     /// Add two polynomials
     pub fn add(&self, other: &Self) -> Self {
         let max_len = self.coeffs.len().max(other.coeffs.len());
@@ -115,6 +139,16 @@ impl<F: Field> UnivariatePoly<F> {
     pub fn scale(&self, s: F) -> Self {
         Self::new(self.coeffs.iter().map(|&c| c * s).collect())
     }
+    
+    /// Multiply by the linear polynomial c0 + c1 x
+    pub fn mul_linear(&self, c0: F, c1: F) -> Self {
+        let mut result = vec![F::zero(); self.coeffs.len() + 1];
+        for (i, &c) in self.coeffs.iter().enumerate() {
+            result[i] += c * c0;
+            result[i + 1] += c * c1;
+        }
+        Self::new(result)
+    }
 }
 
 /// Multilinear polynomial representation
@@ -160,17 +194,11 @@ impl<F: Field> MultilinearPoly<F> {
             ));
         }
         
-        let new_size = self.evals.len() / 2;
-        let mut new_evals = vec![F::zero(); new_size];
-        
-        for i in 0..new_size {
-            let low = self.evals[i];
-            let high = self.evals[i + new_size];
-            new_evals[i] = low + value * (high - low);
-        }
+        let mut dense = DenseMultilinear::new(self.evals.clone())?;
+        dense.bind_var(0, value)?;
         
         Ok(Self {
-            evals: new_evals,
+            evals: dense.into_evals(),
             num_vars: self.num_vars - 1,
         })
     }
@@ -228,24 +256,10 @@ impl PolyHelper {
     
     /// Create eq polynomial: eq(x, r) = prod_i (x_i * r_i + (1-x_i) * (1-r_i))
     pub fn eq_polynomial<F: Field>(r: &[F]) -> Result<MultilinearPoly<F>> {
-        let num_vars = r.len();
-        let size = 1 << num_vars;
-        let mut evals = vec![F::zero(); size];
-        
-        for i in 0..size {
-            let mut prod = F::one();
-            for j in 0..num_vars {
-                let bit = (i >> j) & 1;
-                if bit == 1 {
-                    prod *= r[j];
-                } else {
-                    prod *= F::one() - r[j];
-                }
-            }
-            evals[i] = prod;
-        }
-        
-        MultilinearPoly::new(evals)
+        // Bit j of the index pairs with r[j], so r is reversed into the
+        // most-significant-first variable order of DenseMultilinear
+        let reversed: Vec<F> = r.iter().rev().copied().collect();
+        MultilinearPoly::new(DenseMultilinear::eq(&reversed).into_evals())
     }
     
     /// Combine polynomials for parallel sumcheck (degree 3)
@@ -302,6 +316,20 @@ mod tests {
         assert_eq!(poly.evaluate(Fp128::from(3)), Fp128::from(13)); // 9 + 3 + 1
     }
     
+    #[test]
+    fn test_compress() {
+        // p(x) = 2x^3 + 2x^2 + 3x + 1, with p(0) + p(1) = 1 + 8 = 9
+        let poly = UnivariatePoly::new([1u64, 3, 2, 2].map(Fp128::from_u64).to_vec());
+        let compressed = poly.compress();
+        assert_eq!(compressed, [1u64, 2, 2].map(Fp128::from_u64).to_vec());
+        assert_eq!(UnivariatePoly::from_compressed(&compressed, Fp128::from(9)).coeffs, poly.coeffs);
+        
+        // A constant keeps a zero linear coefficient
+        let constant = UnivariatePoly::constant(Fp128::from(4));
+        let recovered = UnivariatePoly::from_compressed(&constant.compress(), Fp128::from(8));
+        assert_eq!(recovered.coeffs, vec![Fp128::from(4), Fp128::zero()]);
+    }
+    
     #[test]
     fn test_multilinear_poly() {
         // f(x,y) = 2xy + x + y
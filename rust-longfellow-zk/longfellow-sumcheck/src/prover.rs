@@ -1,26 +1,40 @@
 /// Sumcheck prover implementation
 
+use longfellow_algebra::multilinear::DenseMultilinear;
 use longfellow_algebra::traits::Field;
+use longfellow_arrays::backend::{default_backend, ArrayBackend};
 use longfellow_arrays::dense::Dense;
 use longfellow_core::{LongfellowError, Result};
+use longfellow_random::Transcript;
 use rand::{CryptoRng, RngCore};
-// use rayon::prelude::*;  // Currently unused
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
 
 use crate::{
-    SumcheckInstance, SumcheckProof, LayerProof, SumcheckOptions,
+    SumcheckInstance, SumcheckProof, LayerClaim, LayerProof, SumcheckOptions, multilinear_extension,
+    num_copy_vars, pad_copies,
     circuit::{Circuit, Layer},
     polynomial::UnivariatePoly,
+    quad::hand_wire,
     transcript::SumcheckTranscript,
+    wiring::WeightedWiring,
 };
 
+/// Most variables a layer may have under `SumcheckOptions::self_check`,
+/// whose rounds each sum over the hypercube of the layer
+const SELF_CHECK_MAX_VARS: usize = 12;
+
 /// Sumcheck prover for a single layer
 pub struct Prover<F: Field> {
-    /// Wire values for this layer
+    /// Input wires of this layer, `2^nin` per copy
     wires: Dense<F>,
     /// Number of copies
     num_copies: usize,
     /// Options
-    _options: SumcheckOptions,
+    options: SumcheckOptions,
+    /// Index of the layer within its circuit, for self-check reports
+    layer_index: usize,
 }
 
 impl<F: Field> Prover<F> {
@@ -33,34 +47,93 @@ impl<F: Field> Prover<F> {
         Self {
             wires,
             num_copies,
-            _options: options,
+            options,
+            layer_index: 0,
         }
     }
     
+    /// Number the layer this prover proves in self-check reports
+    pub fn with_layer_index(mut self, layer_index: usize) -> Self {
+        self.layer_index = layer_index;
+        self
+    }
+    
     /// Prove sumcheck for a layer
+    ///
+    /// Returns the proof and the point the rounds end at: the copy
+    /// variables, then the left hand, then the right hand, then for a layer
+    /// with cubic gates the third hand. The wire claims are the layer's
+    /// inputs at the copy point and each hand.
+    ///
+    /// Every round reads half-tables of the values still unbound and folds
+    /// them with the round's challenge, so the rounds of each phase cost
+    /// time linear in its table together.
     pub fn prove_layer<R: RngCore + CryptoRng>(
         &self,
         layer: &Layer<F>,
-        claim: F,
+        claim: &LayerClaim<F>,
+        transcript: &mut SumcheckTranscript,
+        rng: &mut R,
+    ) -> Result<(LayerProof<F>, Vec<F>)> {
+        self.prove_layer_with(layer, claim, transcript, &mut RoundScratch::new(), rng)
+    }
+    
+    /// Prove sumcheck for a layer as `prove_layer`, in buffers kept in
+    /// `scratch` from earlier layers
+    pub fn prove_layer_with<R: RngCore + CryptoRng>(
+        &self,
+        layer: &Layer<F>,
+        claim: &LayerClaim<F>,
         transcript: &mut SumcheckTranscript,
+        scratch: &mut RoundScratch<F>,
         _rng: &mut R,
-    ) -> Result<LayerProof<F>> {
+    ) -> Result<(LayerProof<F>, Vec<F>)> {
         let mut copy_polys = Vec::new();
         let mut hand_polys = Vec::new();
-        let mut current_claim = claim;
+        let mut current_claim = claim.value;
+        let mut bindings = Vec::new();
         
-        // Bind copy variables first
+        // Gates weighted by the claim, down to their hands
+        let gates = layer.wiring()?.weighted(&claim.gate_weights)?;
+        let num_hands = layer.num_hands();
+        
+        // Copies are rows of the wire table, so binding a copy variable
+        // folds rows; padded copies weigh nothing and have no constants.
+        // Copy weights factor over the copy variables: the bound ones fold
+        // into one scalar, the one being bound is a linear factor, and the
+        // rest weigh each pair of rows by eq over the remaining variables
         let copy_vars = self.num_copy_vars();
-        let mut copy_bindings = Vec::new();
+        let num_rows = 1 << copy_vars;
+        let mut wires = self.padded_wires(layer)?;
+        let mut copy_weight = F::one();
+        if claim.copy_point.as_ref().is_some_and(|point| point.len() != copy_vars) {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Claim copy point does not have {} variables",
+                copy_vars
+            )));
+        }
+        let mut copies = Dense::from_vec(
+            num_rows,
+            1,
+            (0..num_rows).map(|c| if c < self.num_copies { F::one() } else { F::zero() }).collect(),
+        )?;
         
+        // Bind copy variables first
+        if copy_vars > 0 {
+            scratch.begin_rest(claim.copy_point.as_deref().map(|point| &point[1..]), copy_vars - 1);
+        }
         for round in 0..copy_vars {
-            let poly = self.compute_copy_poly(layer, round, &copy_bindings)?;
+            let factor = match &claim.copy_point {
+                Some(point) => [F::one() - point[round], point[round].double() - F::one()],
+                None => [F::one(), F::zero()],
+            };
+            let evals = self.compute_copy_poly(&gates, num_hands, &wires, &scratch.rest, &copies);
+            let poly = scratch.interpolate(&evals[..num_hands + 1])?
+                .mul_linear(copy_weight * factor[0], copy_weight * factor[1]);
+            self.check_round(layer, claim, &bindings, round, &poly)?;
             
             // Verify sum
-            let sum = (0..=poly.degree())
-                .map(|i| poly.evaluate(F::from_u64(i as u64)))
-                .fold(F::zero(), |acc, x| acc + x);
-            
+            let sum = poly.evaluate(F::zero()) + poly.evaluate(F::one());
             if sum != current_claim {
                 return Err(LongfellowError::VerificationError(
                     format!("Copy poly sum mismatch: {:?} != {:?}", sum, current_claim)
@@ -70,439 +143,441 @@ impl<F: Field> Prover<F> {
             transcript.append_polynomial(round, &poly);
             let challenge = transcript.challenge_binding::<F>(round);
             
-            copy_bindings.push(challenge);
+            wires.bind_in_place(challenge);
+            copy_weight *= factor[0] + factor[1] * challenge;
+            scratch.fold_rest(claim.copy_point.is_some());
+            copies.bind_in_place(challenge);
+            
+            bindings.push(challenge);
             current_claim = poly.evaluate(challenge);
             copy_polys.push(poly.coeffs);
         }
         
-        // Bind hand variables
-        let hand_vars = layer.nin;
-        let mut hand_bindings = Vec::new();
+        // One copy left: its wires become a table over either hand
+        let copy_valid = copies.scalar()?;
+        wires.reshape(layer.num_inputs())?;
         
-        for round in 0..hand_vars * 2 {
-            let is_left = round < hand_vars;
-            let var_idx = if is_left { round } else { round - hand_vars };
+        // Left hand: summed over the other hands, the layer is
+        // E (W(a) H(a) + eq(a, 0) C), with
+        // H(a) = sum_b mul(a, b) W(b) + sum_{b, d} cubic(a, b, d) W(b) W(d)
+        // and C = sum_b add(b) W(b) + constant
+        let mut left = wires.clone();
+        let mut mul_right = Dense::new(layer.num_inputs(), 1);
+        let w = wires.as_slice();
+        let mut constant = gates.constant * copy_valid;
+        for &(h, weight) in &gates.add {
+            constant += weight * w[h];
+        }
+        for &(l, r, weight) in &gates.mul {
+            mul_right.as_mut_slice()[l] += weight * w[r];
+        }
+        for &([x, y, z], weight) in &gates.cubic {
+            mul_right.as_mut_slice()[x] += weight * w[y] * w[z];
+        }
+        let mut zero = Self::zero_indicator(layer);
+        
+        for round in copy_vars..copy_vars + layer.nin {
+            let poly = self.compute_hand_poly(&[
+                (copy_weight, &[&left, &mul_right]),
+                (copy_weight * constant, &[&zero]),
+            ], scratch)?;
+            self.check_round(layer, claim, &bindings, round, &poly)?;
+            let challenge = Self::bind_hand_round(poly, round, &mut current_claim, transcript, &mut hand_polys)?;
             
-            let poly = self.compute_hand_poly(
-                layer,
-                var_idx,
-                is_left,
-                &copy_bindings,
-                &hand_bindings,
-            )?;
+            left.bind_in_place(challenge);
+            mul_right.bind_in_place(challenge);
+            zero.bind_in_place(challenge);
+            bindings.push(challenge);
+        }
+        
+        // Right hand: with the left hand bound to a and summed over the
+        // third, the layer is
+        // E ((W(a) mul(a, b) + eq(a, 0) add(b) + W(a) T(b)) W(b)
+        // + eq(a, 0) eq(b, 0) K V), with T(b) = sum_d cubic(a, b, d) W(d)
+        let left_claim = left.scalar()?;
+        let left_zero = zero.scalar()?;
+        let eq_left = DenseMultilinear::eq(&bindings[copy_vars..]).into_evals();
+        let mut right = wires.clone();
+        let mut wiring = Dense::new(layer.num_inputs(), 1);
+        for &(h, weight) in &gates.add {
+            wiring.as_mut_slice()[h] += weight * left_zero;
+        }
+        for &(l, r, weight) in &gates.mul {
+            wiring.as_mut_slice()[r] += weight * left_claim * eq_left[l];
+        }
+        for &([x, y, z], weight) in &gates.cubic {
+            wiring.as_mut_slice()[y] += weight * left_claim * eq_left[x] * wires.as_slice()[z];
+        }
+        let mut zero = Self::zero_indicator(layer);
+        let constant = copy_weight * gates.constant * left_zero * copy_valid;
+        
+        for round in copy_vars + layer.nin..copy_vars + 2 * layer.nin {
+            let poly = self.compute_hand_poly(&[
+                (copy_weight, &[&wiring, &right]),
+                (constant, &[&zero]),
+            ], scratch)?;
+            self.check_round(layer, claim, &bindings, round, &poly)?;
+            let challenge = Self::bind_hand_round(poly, round, &mut current_claim, transcript, &mut hand_polys)?;
             
-            // Verify sum
-            let sum = poly.evaluate(F::zero()) + poly.evaluate(F::one());
-            if sum != current_claim {
-                return Err(LongfellowError::VerificationError(
-                    "Hand poly sum mismatch".to_string()
-                ));
+            wiring.bind_in_place(challenge);
+            right.bind_in_place(challenge);
+            zero.bind_in_place(challenge);
+            bindings.push(challenge);
+        }
+        
+        let right_claim = right.scalar()?;
+        let mut wire_claims = vec![left_claim, right_claim];
+        
+        // Third hand: with a and b bound, the layer is
+        // E (Q eq(d, 0) + W(a) W(b) cubic(a, b, d) W(d)), Q the quadratic
+        // part at (a, b)
+        if num_hands == 3 {
+            let right_zero = zero.scalar()?;
+            let eq_right = DenseMultilinear::eq(&bindings[copy_vars + layer.nin..]).into_evals();
+            let mut quadratic = gates.constant * left_zero * right_zero * copy_valid;
+            for &(h, weight) in &gates.add {
+                quadratic += weight * left_zero * eq_right[h] * right_claim;
+            }
+            for &(l, r, weight) in &gates.mul {
+                quadratic += weight * eq_left[l] * eq_right[r] * left_claim * right_claim;
             }
+            let mut third = wires;
+            let mut wiring = Dense::new(layer.num_inputs(), 1);
+            for &([x, y, z], weight) in &gates.cubic {
+                wiring.as_mut_slice()[z] += weight * eq_left[x] * eq_right[y] * left_claim * right_claim;
+            }
+            let mut zero = Self::zero_indicator(layer);
             
-            transcript.append_polynomial(copy_vars + round, &poly);
-            let challenge = transcript.challenge_binding::<F>(copy_vars + round);
+            for round in copy_vars + 2 * layer.nin..copy_vars + 3 * layer.nin {
+                let poly = self.compute_hand_poly(&[
+                    (copy_weight, &[&wiring, &third]),
+                    (copy_weight * quadratic, &[&zero]),
+                ], scratch)?;
+                self.check_round(layer, claim, &bindings, round, &poly)?;
+                let challenge = Self::bind_hand_round(poly, round, &mut current_claim, transcript, &mut hand_polys)?;
+                
+                wiring.bind_in_place(challenge);
+                third.bind_in_place(challenge);
+                zero.bind_in_place(challenge);
+                bindings.push(challenge);
+            }
             
-            hand_bindings.push(challenge);
-            current_claim = poly.evaluate(challenge);
-            hand_polys.push(poly.coeffs);
+            wire_claims.push(third.scalar()?);
         }
         
-        // Compute wire claims
-        let wire_claims = self.compute_wire_claims(&copy_bindings, &hand_bindings)?;
         transcript.append_wire_claims(0, &wire_claims);
         
-        Ok(LayerProof {
-            copy_polys,
-            hand_polys,
-            wire_claims,
-        })
+        Ok((
+            LayerProof {
+                copy_polys,
+                hand_polys,
+                wire_claims,
+            },
+            bindings,
+        ))
+    }
+    
+    /// With `SumcheckOptions::self_check`, panic unless `poly` is the
+    /// layer polynomial summed over the hypercube, with `bindings` bound
+    /// and the round's variable free
+    ///
+    /// Each of the polynomial's coefficients is checked at a point of its
+    /// own: the layer polynomial is evaluated as the verifier does, at
+    /// every completion of the round's point, on wires read from the
+    /// multilinear extension of the whole input table. None of the
+    /// prover's folded tables enter, so a wrong round shows here as the
+    /// round at fault rather than as a sum mismatch further on.
+    fn check_round(
+        &self,
+        layer: &Layer<F>,
+        claim: &LayerClaim<F>,
+        bindings: &[F],
+        round: usize,
+        poly: &UnivariatePoly<F>,
+    ) -> Result<()> {
+        if !self.options.self_check {
+            return Ok(());
+        }
+        
+        let copy_vars = self.num_copy_vars();
+        let num_hands = layer.num_hands();
+        let num_vars = copy_vars + num_hands * layer.nin;
+        if num_vars > SELF_CHECK_MAX_VARS {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Self-check takes layers of at most {} variables, layer {} has {}",
+                SELF_CHECK_MAX_VARS, self.layer_index, num_vars
+            )));
+        }
+        
+        let table = pad_copies(self.wires.as_slice(), layer.num_inputs(), self.num_copies)?;
+        let free = num_vars - bindings.len() - 1;
+        for t in 0..poly.coeffs.len().max(1) {
+            let t = F::from_u64(t as u64);
+            let mut sum = F::zero();
+            for x in 0..1usize << free {
+                let mut point = bindings.to_vec();
+                point.push(t);
+                point.extend((0..free).map(|k| F::from_u64((x >> k & 1) as u64)));
+                
+                let (copy_point, hands) = point.split_at(copy_vars);
+                let wires = (0..num_hands)
+                    .map(|j| {
+                        let hand = &hands[j * layer.nin..(j + 1) * layer.nin];
+                        multilinear_extension(&table, &[copy_point, hand].concat())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                sum += claim.evaluate_at(layer, self.num_copies, &point, &wires)?;
+            }
+            
+            if poly.evaluate(t) != sum {
+                panic!(
+                    "Sumcheck self-check failed at layer {}, round {}: p({:?}) = {:?}, hypercube sum {:?}",
+                    self.layer_index,
+                    round,
+                    t,
+                    poly.evaluate(t),
+                    sum
+                );
+            }
+        }
+        
+        Ok(())
     }
     
     /// Get number of copy variables
     fn num_copy_vars(&self) -> usize {
-        if self.num_copies <= 1 {
-            0
-        } else {
-            (self.num_copies - 1).next_power_of_two().trailing_zeros() as usize
-        }
+        num_copy_vars(self.num_copies)
+    }
+    
+    /// The input wires as a table with a row per copy, padded copies zero
+    fn padded_wires(&self, layer: &Layer<F>) -> Result<Dense<F>> {
+        let width = layer.num_inputs();
+        let table = pad_copies(self.wires.as_slice(), width, self.num_copies)?;
+        Dense::from_vec(1 << self.num_copy_vars(), width, table)
+    }
+    
+    /// eq(x, 0) over one hand, where gates read the constant one
+    fn zero_indicator(layer: &Layer<F>) -> Dense<F> {
+        let mut zero = Dense::new(layer.num_inputs(), 1);
+        zero.as_mut_slice()[0] = F::one();
+        zero
     }
     
-    /// Compute polynomial for copy variable binding
+    /// Values at 0, 1, ..., up to the number of hands, of the polynomial
+    /// for copy variable binding without its linear copy weight factor:
+    /// every wire a gate reads is linear in each copy variable
+    ///
+    /// At each pair of rows folded together, the wires along the line
+    /// between them are interpolated once per evaluation point and the
+    /// weighted gates summed over them, scaled by the pair's weight in
+    /// `rest`.
     fn compute_copy_poly(
         &self,
-        layer: &Layer<F>,
-        round: usize,
-        bindings: &[F],
-    ) -> Result<UnivariatePoly<F>> {
-        // For degree-3 polynomial, evaluate at 0, 1, 2, 3
-        let mut evals = vec![F::zero(); 4];
+        gates: &WeightedWiring<F>,
+        num_hands: usize,
+        wires: &Dense<F>,
+        rest: &[F],
+        copies: &Dense<F>,
+    ) -> [F; 4] {
+        let half = wires.n0() / 2;
+        let wires = wires.view();
+        let at = |table: &Dense<F>, i: usize, t: F| {
+            let (lo, hi) = (table.as_slice()[i], table.as_slice()[i + half]);
+            lo + t * (hi - lo)
+        };
         
-        for eval_point in 0..4 {
-            let point_val = F::from_u64(eval_point as u64);
-            let mut sum = F::zero();
-            
-            // Sum over all unbound copy indices
-            let unbound_copies = 1 << (self.num_copy_vars() - bindings.len());
-            
-            for copy_idx in 0..unbound_copies {
-                // Compute full copy index with bindings
-                let mut full_idx = 0;
-                let _bit_pos = 0;
-                
-                for (i, &binding) in bindings.iter().enumerate() {
-                    if binding == F::one() {
-                        full_idx |= 1 << i;
+        self.sum_chunks(half, |rows| {
+            let mut row = vec![F::zero(); wires.n1()];
+            let mut evals = [F::zero(); 4];
+            for i in rows {
+                let (lo, hi) = (wires.row(i), wires.row(i + half));
+                for (t, eval) in evals[..num_hands + 1].iter_mut().enumerate() {
+                    let t = F::from_u64(t as u64);
+                    for (w, (&l, &h)) in row.iter_mut().zip(lo.iter().zip(hi)) {
+                        *w = l + t * (h - l);
                     }
-                }
-                
-                // Add current round bit
-                if round < self.num_copy_vars() {
-                    if point_val == F::one() || point_val == F::from_u64(3) {
-                        full_idx |= 1 << round;
+                    
+                    let mut outputs = gates.constant * at(copies, i, t);
+                    for &(h, weight) in &gates.add {
+                        outputs += weight * row[h];
                     }
-                }
-                
-                // Add remaining unbound bits
-                for i in 0..self.num_copy_vars() - bindings.len() - 1 {
-                    if (copy_idx >> i) & 1 == 1 {
-                        full_idx |= 1 << (bindings.len() + 1 + i);
+                    for &(l, r, weight) in &gates.mul {
+                        outputs += weight * row[l] * row[r];
                     }
-                }
-                
-                if full_idx < self.num_copies {
-                    // Evaluate layer at this copy
-                    let copy_sum = self.evaluate_layer_copy(layer, full_idx)?;
-                    sum += copy_sum;
+                    for &([x, y, z], weight) in &gates.cubic {
+                        outputs += weight * row[x] * row[y] * row[z];
+                    }
+                    *eval += rest[i] * outputs;
                 }
             }
-            
-            evals[eval_point] = sum;
-        }
-        
-        UnivariatePoly::interpolate(&evals)
+            evals
+        })
     }
     
-    /// Compute polynomial for hand variable binding
+    /// Compute polynomial for hand variable binding, of degree 2: a sum of
+    /// scaled products of two tables, each linear in the variable bound
     fn compute_hand_poly(
         &self,
-        layer: &Layer<F>,
-        var_idx: usize,
-        is_left: bool,
-        copy_bindings: &[F],
-        hand_bindings: &[F],
+        terms: &[(F, &[&Dense<F>])],
+        scratch: &mut RoundScratch<F>,
     ) -> Result<UnivariatePoly<F>> {
-        // For degree-2 polynomial, evaluate at 0, 1, 2
-        let mut evals = vec![F::zero(); 3];
-        
-        // For the simple test case, let me trace through what should happen
-        // We have an add gate: output = input[0] + input[1]
-        // With wire values [3, 5], the output is 8
-        
-        // In sumcheck, we're working with a polynomial over boolean indices
-        // The polynomial evaluates to the wire value at that index
-        // For 2 inputs, we have indices 00, 01, 10, 11 mapping to positions 0, 1, 2, 3
-        // But we only have 2 wires, so only indices 0 and 1 are valid
-        
-        // The sumcheck polynomial for this layer is:
-        // P(x0, x1) = wire[x0*2 + x1] if the index is valid, 0 otherwise
-        // But that's not right either...
-        
-        // Actually, for an add gate, the constraint is:
-        // output = input[0] + input[1]
-        // In the quadratic form, this is represented as:
-        // gate[0] * (input[0] * 1 + 1 * input[1])
-        
-        // Let me implement based on the actual quadratic form
-        for eval_point in 0..3 {
-            let point_val = F::from_u64(eval_point as u64);
-            
-            // Apply the current binding to the quadratic form
-            let mut quad = layer.quad.clone();
-            
-            // Apply existing hand bindings
-            for (i, &binding) in hand_bindings.iter().enumerate() {
-                if i < layer.nin {
-                    quad = quad.bind_hand(i, binding, true)?;
-                } else {
-                    quad = quad.bind_hand(i - layer.nin, binding, false)?;
+        let half = terms[0].1[0].len() / 2;
+        let evals = self.sum_chunks(half, |entries| {
+            let mut evals = [F::zero(); 3];
+            for &(scale, tables) in terms {
+                for i in entries.clone() {
+                    for (t, eval) in evals.iter_mut().enumerate() {
+                        let t = F::from_u64(t as u64);
+                        *eval += tables.iter().fold(scale, |acc, table| {
+                            let (lo, hi) = (table.as_slice()[i], table.as_slice()[i + half]);
+                            acc * (lo + t * (hi - lo))
+                        });
+                    }
                 }
             }
-            
-            // Apply current variable binding
-            quad = quad.bind_hand(var_idx, point_val, is_left)?;
-            
-            // Sum over remaining variables
-            evals[eval_point] = self.sum_quad_with_bindings(&quad, copy_bindings, layer)?;
-        }
+            evals
+        });
         
-        UnivariatePoly::interpolate(&evals)
+        scratch.interpolate(&evals)
     }
     
-    /// Evaluate the polynomial at a specific point
-    fn evaluate_at_point(
+    /// Sum `evals` over `0..len`, split into chunks of `batch_size` summed
+    /// in parallel when the options ask for it
+    fn sum_chunks<const N: usize>(
         &self,
-        layer: &Layer<F>,
-        var_idx: usize,
-        is_left: bool,
-        point_val: F,
-        copy_bindings: &[F],
-        hand_bindings: &[F],
-    ) -> Result<F> {
-        let mut sum = F::zero();
-        
-        // We're in the hand polynomial phase, so gate variables have been bound
-        // We need to sum over all remaining unbound hand variables
-        
-        // For simplicity, let's handle the case where we have a single copy first
-        if self.num_copies == 1 {
-            // Sum over all possible assignments to unbound variables
-            let num_left_vars = layer.nin;
-            let num_right_vars = layer.nin;
-            let total_hand_vars = num_left_vars + num_right_vars;
-            let num_bound = hand_bindings.len();
-            let current_var_bound = num_bound + 1; // Including the current variable
-            
-            // We need to sum over 2^(total_hand_vars - current_var_bound) assignments
-            let unbound_vars = total_hand_vars - current_var_bound;
-            let num_assignments = 1 << unbound_vars;
-            
-            for assignment in 0..num_assignments {
-                // Build the full assignment including bound variables
-                let mut left_assignment = vec![F::zero(); num_left_vars];
-                let mut right_assignment = vec![F::zero(); num_right_vars];
-                
-                // Apply existing bindings
-                let mut binding_idx = 0;
-                for i in 0..num_left_vars {
-                    if binding_idx < hand_bindings.len() && binding_idx < num_left_vars {
-                        left_assignment[i] = hand_bindings[binding_idx];
-                        binding_idx += 1;
-                    } else if i == var_idx && is_left {
-                        left_assignment[i] = point_val;
-                    } else {
-                        // Unbound variable - use assignment
-                        let unbound_idx = if i < var_idx || !is_left { i } else { i - 1 };
-                        let bit = (assignment >> unbound_idx) & 1;
-                        left_assignment[i] = F::from_u64(bit as u64);
-                    }
-                }
-                
-                // Similar for right assignment
-                binding_idx = num_left_vars;
-                for i in 0..num_right_vars {
-                    if binding_idx < hand_bindings.len() {
-                        right_assignment[i] = hand_bindings[binding_idx];
-                        binding_idx += 1;
-                    } else if i == var_idx && !is_left {
-                        right_assignment[i] = point_val;
-                    } else {
-                        // Unbound variable
-                        let unbound_idx = if i < var_idx || is_left { i } else { i - 1 };
-                        let bit = (assignment >> unbound_idx) & 1;
-                        right_assignment[i] = F::from_u64(bit as u64);
-                    }
-                }
-                
-                // Evaluate the layer at this assignment
-                sum += self.evaluate_layer_at_assignment(
-                    layer,
-                    &left_assignment,
-                    &right_assignment,
-                )?;
-            }
-        } else {
-            // Handle multiple copies with copy bindings
-            // For now, return zero to avoid the error
-            sum = F::zero();
+        len: usize,
+        evals: impl Fn(Range<usize>) -> [F; N] + Sync,
+    ) -> [F; N] {
+        let batch = self.options.batch_size.max(1);
+        if !self.options.parallel || len <= batch {
+            return evals(0..len);
         }
         
-        Ok(sum)
+        (0..len.div_ceil(batch))
+            .into_par_iter()
+            .map(|chunk| evals(chunk * batch..((chunk + 1) * batch).min(len)))
+            .reduce(
+                || [F::zero(); N],
+                |mut acc, part| {
+                    for (a, p) in acc.iter_mut().zip(part) {
+                        *a += p;
+                    }
+                    acc
+                },
+            )
     }
     
-    /// Evaluate layer at specific hand assignments
-    fn evaluate_layer_at_assignment(
-        &self,
-        layer: &Layer<F>,
-        left_assignment: &[F],
-        right_assignment: &[F],
+    /// Check, send and bind one hand round, returning its challenge
+    fn bind_hand_round(
+        poly: UnivariatePoly<F>,
+        round: usize,
+        current_claim: &mut F,
+        transcript: &mut SumcheckTranscript,
+        hand_polys: &mut Vec<Vec<F>>,
     ) -> Result<F> {
-        let mut sum = F::zero();
-        
-        // For each gate in the quadratic form
-        for (g, h0, h1, coeff) in layer.quad.iter() {
-            // Get the wire values based on the assignment
-            let left_idx = self.assignment_to_index(left_assignment);
-            let right_idx = self.assignment_to_index(right_assignment);
-            
-            let left_val = if h0 == 0 {
-                F::one()
-            } else if left_idx < self.wires.len() {
-                *self.wires.as_slice().get(left_idx)
-                    .ok_or_else(|| LongfellowError::InvalidParameter(format!("Wire index {} out of bounds", left_idx)))?
-            } else {
-                F::zero()
-            };
-            
-            let right_val = if h1 == 0 {
-                F::one()
-            } else if right_idx < self.wires.len() {
-                *self.wires.as_slice().get(right_idx)
-                    .ok_or_else(|| LongfellowError::InvalidParameter(format!("Wire index {} out of bounds", right_idx)))?
-            } else {
-                F::zero()
-            };
-            
-            sum += coeff * left_val * right_val;
+        // Verify sum
+        let sum = poly.evaluate(F::zero()) + poly.evaluate(F::one());
+        if sum != *current_claim {
+            return Err(LongfellowError::VerificationError(
+                "Hand poly sum mismatch".to_string()
+            ));
         }
         
-        Ok(sum)
+        transcript.append_polynomial(round, &poly);
+        let challenge = transcript.challenge_binding::<F>(round);
+        
+        *current_claim = poly.evaluate(challenge);
+        hand_polys.push(poly.coeffs);
+        Ok(challenge)
     }
-    
-    /// Convert boolean assignment to wire index
-    fn assignment_to_index(&self, assignment: &[F]) -> usize {
-        let mut idx = 0;
-        for (i, &val) in assignment.iter().enumerate() {
-            if val == F::one() {
-                idx |= 1 << i;
-            }
+}
+
+/// Buffers the sumcheck rounds reuse across rounds and layers
+///
+/// Round polynomials are interpolated from their values at 0, 1, ...
+/// through Lagrange bases computed once per number of points, instead of
+/// building and inverting a basis every round, and the eq table weighting
+/// the copy variables not yet bound is folded in place rather than rebuilt.
+pub struct RoundScratch<F: Field> {
+    /// Lagrange bases over 0..n, indexed by n, coefficients of the i-th
+    /// basis polynomial at i * n
+    bases: Vec<Vec<F>>,
+    /// eq over the copy variables after the one being bound
+    rest: Vec<F>,
+}
+
+impl<F: Field> Default for RoundScratch<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> RoundScratch<F> {
+    /// Empty buffers, filled as rounds need them
+    pub fn new() -> Self {
+        Self {
+            bases: Vec::new(),
+            rest: Vec::new(),
         }
-        idx
     }
     
-    /// Evaluate layer for a specific copy
-    fn evaluate_layer_copy(&self, layer: &Layer<F>, copy_idx: usize) -> Result<F> {
-        let offset = copy_idx * (1 << layer.nin);
-        let mut sum = F::zero();
-        
-        // Sum over all gate evaluations
-        for (_g, h0, h1, coeff) in layer.quad.iter() {
-            let left_val = if h0 == 0 {
-                F::one()
-            } else {
-                *self.wires.as_slice().get(offset + h0 - 1).ok_or(LongfellowError::InvalidParameter("Wire index out of bounds".to_string()))?
-            };
-            
-            let right_val = if h1 == 0 {
-                F::one()
-            } else {
-                *self.wires.as_slice().get(offset + h1 - 1).ok_or(LongfellowError::InvalidParameter("Wire index out of bounds".to_string()))?
-            };
-            
-            sum += coeff * left_val * right_val;
+    /// The polynomial through `evals` at 0, 1, ...
+    fn interpolate(&mut self, evals: &[F]) -> Result<UnivariatePoly<F>> {
+        let n = evals.len();
+        if self.bases.len() <= n {
+            self.bases.resize(n + 1, Vec::new());
+        }
+        if self.bases[n].is_empty() {
+            let mut unit = vec![F::zero(); n];
+            for i in 0..n {
+                unit[i] = F::one();
+                self.bases[n].extend(UnivariatePoly::interpolate(&unit)?.coeffs);
+                unit[i] = F::zero();
+            }
         }
         
-        Ok(sum)
+        let basis = &self.bases[n];
+        let mut coeffs = vec![F::zero(); n];
+        for (i, &e) in evals.iter().enumerate() {
+            for (c, &b) in coeffs.iter_mut().zip(&basis[i * n..(i + 1) * n]) {
+                *c += e * b;
+            }
+        }
+        Ok(UnivariatePoly::new(coeffs))
     }
     
-    /// Sum quadratic form with partial bindings
-    fn sum_quad_with_bindings(
-        &self,
-        quad: &crate::quad::Quad<F>,
-        _copy_bindings: &[F],
-        layer: &Layer<F>,
-    ) -> Result<F> {
-        // After partial bindings, we need to sum over all remaining unbound variables
-        
-        // The key insight: after binding k variables out of n total,
-        // we need to sum over 2^(n-k) assignments to the remaining variables
-        
-        let mut total_sum = F::zero();
-        
-        // For the test case: we have 2 input variables (layer.nin = 1 means 2^1 = 2)
-        // After binding some variables, the indices in the quad are reduced
-        
-        // Since gate variables should be bound in the hand phase,
-        // we're only summing over remaining hand variables
+    /// Start the weights of `num_vars` copy variables: eq at `point`, or
+    /// one everywhere without a point
+    fn begin_rest(&mut self, point: Option<&[F]>, num_vars: usize) {
+        self.rest.clear();
+        let Some(point) = point else {
+            self.rest.resize(1 << num_vars, F::one());
+            return;
+        };
         
-        // The indices h0, h1 after binding represent bit-packed indices
-        // with some bits already fixed by the binding
-        
-        // For now, let's handle the simple case
-        // After all hand variables are bound, we should just evaluate
-        
-        for (g, h0, h1, coeff) in quad.iter() {
-            // g should be 0 for single gate
-            // h0, h1 are indices (0 = constant 1)
-            
-            // The multilinear extension of wire values
-            // For wires [3, 5]:
-            // MLE(0) = 3, MLE(1) = 5
-            // MLE(x) = 3*(1-x) + 5*x
-            
-            // But after binding, the indices are transformed
-            // We need to understand how many variables remain unbound
-            
-            // For the simple test case, let's directly evaluate
-            let gate_contrib = if g == 0 { F::one() } else { F::zero() };
-            
-            let left_contrib = if h0 == 0 {
-                F::one()
-            } else {
-                // h0 encodes which wire to use
-                // After binding, this should be a specific wire
-                let wire_idx = (h0 as usize).saturating_sub(1);
-                if wire_idx < self.wires.len() {
-                    *self.wires.as_slice().get(wire_idx)
-                        .ok_or_else(|| LongfellowError::InvalidParameter(format!("Wire index {} out of bounds", wire_idx)))?
-                } else {
-                    F::zero()
-                }
-            };
-            
-            let right_contrib = if h1 == 0 {
-                F::one()
-            } else {
-                let wire_idx = (h1 as usize).saturating_sub(1);
-                if wire_idx < self.wires.len() {
-                    *self.wires.as_slice().get(wire_idx)
-                        .ok_or_else(|| LongfellowError::InvalidParameter(format!("Wire index {} out of bounds", wire_idx)))?
-                } else {
-                    F::zero()
-                }
-            };
-            
-            total_sum += coeff * gate_contrib * left_contrib * right_contrib;
+        // Each variable doubles the table, most significant first
+        self.rest.resize(1 << point.len(), F::zero());
+        self.rest[0] = F::one();
+        for (k, &r) in point.iter().enumerate() {
+            for i in (0..1 << k).rev() {
+                let v = self.rest[i];
+                self.rest[2 * i + 1] = v * r;
+                self.rest[2 * i] = v - v * r;
+            }
         }
-        
-        Ok(total_sum)
     }
     
-    /// Compute wire claims after all bindings
-    fn compute_wire_claims(
-        &self,
-        copy_bindings: &[F],
-        hand_bindings: &[F],
-    ) -> Result<Vec<F>> {
-        // Evaluate wires at binding point
-        let mut claims = Vec::new();
-        
-        // Determine which copy we're looking at based on bindings
-        let mut copy_idx = 0;
-        for (i, &b) in copy_bindings.iter().enumerate() {
-            if b == F::one() {
-                copy_idx |= 1 << i;
+    /// Drop the most significant variable from the weights: eq factors
+    /// sum to one over it, and ones stay ones
+    fn fold_rest(&mut self, is_eq: bool) {
+        let half = self.rest.len() / 2;
+        if is_eq {
+            for i in 0..half {
+                let hi = self.rest[i + half];
+                self.rest[i] += hi;
             }
         }
-        
-        if copy_idx < self.num_copies {
-            let offset = copy_idx * self.wires.len() / self.num_copies;
-            
-            // Get wire values at hand binding points
-            for i in 0..2 {
-                let mut wire_idx = 0;
-                for (j, &b) in hand_bindings[i * hand_bindings.len() / 2..(i + 1) * hand_bindings.len() / 2].iter().enumerate() {
-                    if b == F::one() {
-                        wire_idx |= 1 << j;
-                    }
-                }
-                
-                if offset + wire_idx < self.wires.len() {
-                    claims.push(*self.wires.as_slice().get(offset + wire_idx).ok_or(LongfellowError::InvalidParameter("Wire index out of bounds".to_string()))?);
-                }
-            }
-        }
-        
-        Ok(claims)
+        self.rest.truncate(half);
     }
 }
 
@@ -510,127 +585,310 @@ impl<F: Field> Prover<F> {
 pub struct ProverLayers<F: Field> {
     /// The circuit
     circuit: Circuit<F>,
-    /// Wire values for all layers
-    all_wires: Vec<Dense<F>>,
+    /// Circuit inputs, `2^nin` per copy
+    inputs: Dense<F>,
+    /// Output wires of each layer, or with `checkpoint_interval` set of
+    /// every k-th layer only
+    all_wires: Vec<Option<Dense<F>>>,
     /// Number of copies
     num_copies: usize,
     /// Options
     options: SumcheckOptions,
+    /// Kernels for the bulk array work
+    backend: Arc<dyn ArrayBackend<F>>,
 }
 
 impl<F: Field> ProverLayers<F> {
-    /// Create a new layered prover
+    /// Create a new layered prover on the default array backend
     pub fn new(
         circuit: Circuit<F>,
         inputs: &[F],
         num_copies: usize,
         options: SumcheckOptions,
     ) -> Result<Self> {
+        Self::with_backend(circuit, inputs, num_copies, options, default_backend())
+    }
+    
+    /// Create a new layered prover that runs array kernels on `backend`
+    pub fn with_backend(
+        circuit: Circuit<F>,
+        inputs: &[F],
+        num_copies: usize,
+        options: SumcheckOptions,
+        backend: Arc<dyn ArrayBackend<F>>,
+    ) -> Result<Self> {
+        // Inputs missing at the end read as zero, as in `pad_copies`
+        let width = circuit.num_inputs() * num_copies;
+        if num_copies == 0 || inputs.len() > width {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "{} inputs do not fit {} copies of {}",
+                inputs.len(),
+                num_copies,
+                circuit.num_inputs()
+            )));
+        }
+        let mut inputs = inputs.to_vec();
+        inputs.resize(width, F::zero());
+        
         // Evaluate circuit to get all wire values
-        let all_wires = Self::evaluate_all_layers(&circuit, inputs, num_copies)?;
+        let inputs = Dense::from_vec(1, width, inputs)?;
+        let all_wires = Self::evaluate_all_layers(
+            &circuit,
+            &inputs,
+            num_copies,
+            options.checkpoint_interval,
+            backend.as_ref(),
+        )?;
         
         Ok(Self {
             circuit,
+            inputs,
             all_wires,
             num_copies,
             options,
+            backend,
         })
     }
     
+    /// The backend running this prover's array kernels
+    pub fn backend(&self) -> &dyn ArrayBackend<F> {
+        self.backend.as_ref()
+    }
+    
     /// Generate complete sumcheck proof
+    ///
+    /// Layers are proven from the output down. Each layer's sumcheck ends
+    /// in claims on its inputs at a point per hand, sharing a copy point; a
+    /// challenge combines them into the claim the next layer proves, and
+    /// the last layer's claims are left on the circuit inputs.
     pub fn prove<R: RngCore + CryptoRng>(
         &self,
         instance: &SumcheckInstance<F>,
-rng: &mut R,
+        rng: &mut R,
+    ) -> Result<SumcheckProof<F>> {
+        self.prove_on(instance, &mut SumcheckTranscript::new(b"sumcheck"), rng)
+    }
+    
+    /// Generate complete sumcheck proof on the transcript of a larger
+    /// proof, which is left holding every sumcheck message
+    ///
+    /// Only verifiable by `VerifierLayers::verify_with_transcript` from
+    /// the same transcript state.
+    pub fn prove_with_transcript<R: RngCore + CryptoRng>(
+        &self,
+        instance: &SumcheckInstance<F>,
+        transcript: &mut Transcript,
+        rng: &mut R,
     ) -> Result<SumcheckProof<F>> {
-        let mut transcript = SumcheckTranscript::new(b"sumcheck");
+        let mut sumcheck = SumcheckTranscript::from_transcript(transcript.clone(), b"sumcheck");
+        let proof = self.prove_on(instance, &mut sumcheck, rng)?;
+        *transcript = sumcheck.into_inner();
+        Ok(proof)
+    }
+    
+    fn prove_on<R: RngCore + CryptoRng>(
+        &self,
+        instance: &SumcheckInstance<F>,
+        transcript: &mut SumcheckTranscript,
+        rng: &mut R,
+    ) -> Result<SumcheckProof<F>> {
+        if instance.num_copies != self.num_copies {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Instance has {} copies, prover {}",
+                instance.num_copies, self.num_copies
+            )));
+        }
+        
         transcript.append_circuit_info(
             self.circuit.layers.len(),
             self.num_copies,
-            &instance.claimed_sum.to_bytes_le(),
+            &instance.claimed_sum.to_canonical_bytes(),
         );
         
+        let copy_vars = num_copy_vars(self.num_copies);
         let mut layer_proofs = Vec::new();
-        let mut current_claim = instance.claimed_sum;
+        let mut claim = instance.output_claim(transcript);
+        let mut input_eval = Vec::new();
+        let mut segment = Vec::new();
+        let mut scratch = RoundScratch::new();
         
         // Process each layer from output to input
         for (layer_idx, layer) in self.circuit.layers.iter().enumerate() {
-            let wires = &self.all_wires[layer_idx];
-            let prover = Prover::new(wires.clone(), self.num_copies, self.options.clone());
+            let wires = match self.all_wires.get(layer_idx + 1) {
+                None => &self.inputs,
+                Some(Some(wires)) => wires,
+                Some(None) => {
+                    if !segment.iter().any(|(idx, _)| *idx == layer_idx + 1) {
+                        segment = self.recompute_segment(layer_idx + 1)?;
+                    }
+                    &segment.iter().find(|(idx, _)| *idx == layer_idx + 1).unwrap().1
+                }
+            };
+            let prover = Prover::new(wires.clone(), self.num_copies, self.options.clone())
+                .with_layer_index(layer_idx);
             
-            let layer_proof = prover.prove_layer(
+            let (layer_proof, point) = prover.prove_layer_with(
                 layer,
-                current_claim,
-                &mut transcript,
+                &claim,
+                transcript,
+                &mut scratch,
                 rng,
             )?;
             
-            // Update claim for next layer
-            current_claim = layer_proof.wire_claims.iter().fold(F::zero(), |acc, &x| acc + x);
+            // Combine the wire claims into the next layer's claim
+            if layer_idx + 1 < self.circuit.layers.len() {
+                let alpha = transcript.challenge_combination::<F>(layer_idx);
+                claim = LayerClaim::link(&point, copy_vars, &layer_proof.wire_claims, alpha);
+            } else {
+                input_eval = layer_proof.wire_claims.clone();
+            }
             layer_proofs.push(layer_proof);
         }
         
-        // Final input evaluation
-        let input_eval = self.evaluate_inputs_at_binding(&transcript)?;
-        
-        Ok(SumcheckProof {
+        let mut proof = SumcheckProof {
             layer_proofs,
             input_eval,
-        })
+            compressed: false,
+        };
+        if self.options.compress_rounds {
+            proof.compress();
+        }
+        Ok(proof)
+    }
+    
+    /// Recompute the output wires of layer `idx` and of the layers between
+    /// it and the next checkpoint toward the inputs, as (layer, wires)
+    ///
+    /// Layers are proven from the output down, so the first layer of a
+    /// segment asked for is the one nearest the output and the rest are
+    /// asked for right after it.
+    fn recompute_segment(&self, idx: usize) -> Result<Vec<(usize, Dense<F>)>> {
+        let source = (idx + 1..self.all_wires.len())
+            .find(|&i| self.all_wires[i].is_some())
+            .unwrap_or(self.circuit.layers.len());
+        
+        let mut segment: Vec<(usize, Dense<F>)> = Vec::with_capacity(source - idx);
+        for i in (idx..source).rev() {
+            let input = match segment.last() {
+                Some((_, wires)) => wires,
+                None => self.all_wires.get(source).and_then(Option::as_ref).unwrap_or(&self.inputs),
+            };
+            let wires = Self::evaluate_layer(
+                &self.circuit.layers[i],
+                input,
+                self.num_copies,
+                self.backend.as_ref(),
+            )?;
+            segment.push((i, wires));
+        }
+        
+        Ok(segment)
     }
     
     /// Evaluate all layers of the circuit
+    ///
+    /// With a checkpoint interval k only every k-th layer's wires are kept;
+    /// the prover recomputes the layers between a segment at a time, so a
+    /// deep circuit holds about depth / k + k layers instead of all of
+    /// them, for one more evaluation of the circuit.
     fn evaluate_all_layers(
         circuit: &Circuit<F>,
-        inputs: &[F],
+        inputs: &Dense<F>,
         num_copies: usize,
-    ) -> Result<Vec<Dense<F>>> {
-        let mut all_wires = Vec::new();
-        let mut current = Dense::from_vec(1, inputs.len(), inputs.to_vec())?;
+        checkpoint_interval: Option<usize>,
+        backend: &dyn ArrayBackend<F>,
+    ) -> Result<Vec<Option<Dense<F>>>> {
+        let num_layers = circuit.layers.len();
+        let mut all_wires: Vec<Option<Dense<F>>> = Vec::with_capacity(num_layers);
+        // The last layer evaluated, while it is not kept
+        let mut previous: Option<Dense<F>> = None;
         
         // Process layers in reverse (input to output)
-        for layer in circuit.layers.iter().rev() {
-            let next_size = (1 << layer.nout) * num_copies;
-            let mut next_wires = vec![F::zero(); next_size];
+        for (idx, layer) in circuit.layers.iter().enumerate().rev() {
+            let input = previous
+                .as_ref()
+                .or_else(|| all_wires.last().and_then(Option::as_ref))
+                .unwrap_or(inputs);
+            let next = Self::evaluate_layer(layer, input, num_copies, backend)?;
             
-            // Evaluate layer
-            for copy in 0..num_copies {
-                let in_offset = copy * (1 << layer.nin);
-                let out_offset = copy * (1 << layer.nout);
-                
-                for (g, h0, h1, coeff) in layer.quad.iter() {
-                    let left = if h0 == 0 {
-                        F::one()
-                    } else {
-                        *current.as_slice().get(in_offset + h0 - 1).ok_or(LongfellowError::InvalidParameter("Wire index out of bounds".to_string()))?
-                    };
-                    
-                    let right = if h1 == 0 {
-                        F::one()
-                    } else {
-                        *current.as_slice().get(in_offset + h1 - 1).ok_or(LongfellowError::InvalidParameter("Wire index out of bounds".to_string()))?
-                    };
-                    
-                    next_wires[out_offset + g] += coeff * left * right;
-                }
+            if checkpoint_interval.is_none_or(|k| idx % k.max(1) == 0) {
+                all_wires.push(Some(next));
+                previous = None;
+            } else {
+                all_wires.push(None);
+                previous = Some(next);
             }
-            
-            current = Dense::from_vec(1, next_wires.len(), next_wires)?;
-            all_wires.push(current.clone());
         }
         
         all_wires.reverse(); // Back to output-to-input order
         Ok(all_wires)
     }
     
-    /// Evaluate inputs at the binding point from transcript
-    fn evaluate_inputs_at_binding(
-        &self,
-        _transcript: &SumcheckTranscript,
-    ) -> Result<Vec<F>> {
-        // This would extract bindings from transcript and evaluate
-        // For now, return placeholder
-        Ok(vec![F::zero()])
+    /// Evaluate one layer on its input wires
+    ///
+    /// Each copy reads its inputs through a view of the previous layer and
+    /// writes its outputs through a view of the next, so no wire vector is
+    /// cloned along the way. Gate products are computed in batches on
+    /// `backend`.
+    fn evaluate_layer(
+        layer: &Layer<F>,
+        input: &Dense<F>,
+        num_copies: usize,
+        backend: &dyn ArrayBackend<F>,
+    ) -> Result<Dense<F>> {
+        let out_of_bounds =
+            || LongfellowError::InvalidParameter("Wire index out of bounds".to_string());
+        let current = input.view();
+        let in_width = 1 << layer.nin;
+        let out_width = 1 << layer.nout;
+        let next_size = out_width * num_copies;
+        let mut next = Dense::from_vec(1, next_size, vec![F::zero(); next_size])?;
+        
+        // Gather both operands of every gate in every copy, then form
+        // coeff * left * right with two batched products on `backend`
+        let terms = layer.quad.num_corners() * num_copies;
+        let mut lefts = Vec::with_capacity(terms);
+        let mut rights = Vec::with_capacity(terms);
+        let mut coeffs = Vec::with_capacity(terms);
+        for copy in 0..num_copies {
+            let copy_in = current.slice_cols(copy * in_width..(copy + 1) * in_width);
+            
+            for (_g, h0, h1, coeff) in layer.quad.iter() {
+                for (hand, operands) in [(h0, &mut lefts), (h1, &mut rights)] {
+                    operands.push(match hand_wire(hand) {
+                        None => F::one(),
+                        Some(w) => *copy_in.get(0, w).ok_or_else(out_of_bounds)?,
+                    });
+                }
+                coeffs.push(coeff);
+            }
+        }
+        
+        let mut products = vec![F::zero(); terms];
+        backend.mul_batch(&lefts, &rights, &mut products)?;
+        backend.mul_batch(&products, &coeffs, &mut lefts)?;
+        
+        let mut values = lefts.into_iter();
+        for copy in 0..num_copies {
+            let mut copy_out = next
+                .view_mut()
+                .slice_cols_mut(copy * out_width..(copy + 1) * out_width);
+            for ((g, _, _, _), value) in layer.quad.iter().zip(values.by_ref()) {
+                *copy_out.get_mut(0, g).ok_or_else(out_of_bounds)? += value;
+            }
+            
+            // Cubic gates are few next to the quad; they read wires directly
+            let copy_in = current.slice_cols(copy * in_width..(copy + 1) * in_width);
+            for gate in &layer.cubic {
+                let mut value = gate.coeff;
+                for h in gate.inputs {
+                    value *= *copy_in.get(0, h).ok_or_else(out_of_bounds)?;
+                }
+                *copy_out.get_mut(0, gate.output).ok_or_else(out_of_bounds)? += value;
+            }
+        }
+        
+        Ok(next)
     }
 }
 
@@ -652,12 +910,288 @@ mod tests {
         let prover = Prover::new(wires, 1, crate::SumcheckOptions::default());
         
         // Expected claim: 3 + 5 = 8
-        let claim = Fp128::from_u64(8);
+        let claim = LayerClaim::output_sum(0, Fp128::from_u64(8));
         let mut transcript = SumcheckTranscript::new(b"test");
         
-        let proof = prover.prove_layer(&layer, claim, &mut transcript, &mut OsRng).unwrap();
+        let (proof, point) = prover.prove_layer(&layer, &claim, &mut transcript, &mut OsRng).unwrap();
+        
+        // One round per hand variable, ending in the wires at either hand
+        assert_eq!(proof.hand_polys.len(), 2);
+        assert_eq!(point.len(), 2);
+        let wires = [Fp128::from_u64(3), Fp128::from_u64(5)];
+        assert_eq!(
+            proof.wire_claims,
+            vec![
+                crate::multilinear_extension(&wires, &point[..1]).unwrap(),
+                crate::multilinear_extension(&wires, &point[1..]).unwrap(),
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_round_scratch() {
+        let mut scratch = RoundScratch::<Fp128>::new();
+        for n in [3, 4, 3, 5] {
+            let evals: Vec<Fp128> = (0..n as u64).map(|i| Fp128::from_u64(i * i + 7)).collect();
+            assert_eq!(
+                scratch.interpolate(&evals).unwrap().coeffs,
+                UnivariatePoly::interpolate(&evals).unwrap().coeffs
+            );
+        }
+        
+        // Folding the leading variable away leaves eq over the rest
+        let point: Vec<Fp128> = (0..4u64).map(|i| Fp128::from_u64(3 * i + 2)).collect();
+        scratch.begin_rest(Some(&point), point.len());
+        for k in 0..=point.len() {
+            assert_eq!(scratch.rest, DenseMultilinear::eq(&point[k..]).into_evals());
+            scratch.fold_rest(true);
+        }
+        scratch.begin_rest(None, 3);
+        scratch.fold_rest(false);
+        assert_eq!(scratch.rest, vec![Fp128::one(); 4]);
+    }
+    
+    #[test]
+    fn test_self_check() {
+        // Constants, a cubic gate and padded copies all enter the sums
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 1, 1).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::from(2))).unwrap();
+        builder.add_gate(1, 0, 0, GateType::Const(Fp128::from(5))).unwrap();
+        builder.add_gate(1, 1, 1, GateType::Add(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        builder.begin_layer(1, 2, 1).unwrap();
+        builder.add_cubic_gate(0, [0, 1, 2], Fp128::from(3)).unwrap();
+        builder.add_gate(1, 2, 3, GateType::Add(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        let num_copies = 3;
+        let inputs: Vec<Fp128> = (0..4 * num_copies as u64).map(|i| Fp128::from_u64(i + 2)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+        let options = SumcheckOptions { self_check: true, ..SumcheckOptions::default() };
+        let proof = ProverLayers::new(circuit.clone(), &inputs, num_copies, options.clone())
+            .unwrap()
+            .prove(&instance, &mut OsRng)
+            .unwrap();
+        assert!(crate::VerifierLayers::new(circuit).verify_with_inputs(&instance, &proof, &inputs).unwrap());
+        
+        // Layers too wide to sum over are refused
+        let wide = Layer::<Fp128>::new(7, 7, 7);
+        let prover = Prover::new(Dense::new(1, 128), 1, options);
+        let claim = LayerClaim::output_sum(7, Fp128::zero());
+        let mut transcript = SumcheckTranscript::new(b"test");
+        assert!(prover.prove_layer(&wide, &claim, &mut transcript, &mut OsRng).is_err());
+    }
+    
+    #[test]
+    #[should_panic(expected = "layer 2, round 1")]
+    fn test_self_check_names_round() {
+        let mut layer = Layer::<Fp128>::new(0, 1, 0);
+        layer.add_gate(0, 0, 1, GateType::Mul(Fp128::one())).unwrap();
+        let wires = Dense::from_vec(1, 2, vec![Fp128::from_u64(3), Fp128::from_u64(5)]).unwrap();
+        let options = SumcheckOptions { self_check: true, ..SumcheckOptions::default() };
+        let prover = Prover::new(wires, 1, options).with_layer_index(2);
+        let claim = LayerClaim::output_sum(0, Fp128::from_u64(15));
+        
+        // With a = 0 bound, the round over b is eq(b, 1) W(0) W(b) = 9 b + 6 b^2
+        let bindings = [Fp128::zero()];
+        let right = UnivariatePoly::new([0u64, 9, 6].map(Fp128::from_u64).to_vec());
+        prover.check_round(&layer, &claim, &bindings, 1, &right).unwrap();
+        let wrong = UnivariatePoly::new([1u64, 9, 6].map(Fp128::from_u64).to_vec());
+        prover.check_round(&layer, &claim, &bindings, 1, &wrong).unwrap();
+    }
+    
+    #[test]
+    fn test_wide_layer() {
+        // 2^10 outputs over 2^10 inputs per copy: enumerating assignments
+        // per round would take hours, folding tables takes milliseconds
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(10, 10, 10).unwrap();
+        for g in 0..512 {
+            builder.add_gate(2 * g, g, (7 * g + 3) % 1024, GateType::Mul(Fp128::one())).unwrap();
+            builder.add_gate(2 * g + 1, 1023 - g, g, GateType::Add(Fp128::from(2))).unwrap();
+        }
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        let num_copies = 4;
+        let inputs: Vec<Fp128> = (0..1024 * num_copies as u64).map(Fp128::from_u64).collect();
+        let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+        
+        let prover = ProverLayers::new(
+            circuit.clone(),
+            &inputs,
+            num_copies,
+            SumcheckOptions::default(),
+        ).unwrap();
+        let proof = prover.prove(&instance, &mut OsRng).unwrap();
+        
+        let verifier = crate::VerifierLayers::new(circuit);
+        assert!(verifier.verify_with_inputs(&instance, &proof, &inputs).unwrap());
+    }
+    
+    #[test]
+    fn test_non_power_of_two_copies() {
+        // Constants on both layers: padded copies must not add them
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 1, 1).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::from(2))).unwrap();
+        builder.add_gate(1, 1, 1, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 0, GateType::Const(Fp128::from(5))).unwrap();
+        builder.finalize_layer().unwrap();
+        builder.begin_layer(1, 2, 1).unwrap();
+        builder.add_gate(0, 0, 3, GateType::Mul(Fp128::one())).unwrap();
+        builder.add_gate(1, 1, 2, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 0, GateType::Const(Fp128::from(7))).unwrap();
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        let verifier = crate::VerifierLayers::new(circuit.clone());
+        
+        for num_copies in [3, 5, 12] {
+            let inputs: Vec<Fp128> = (0..4 * num_copies as u64).map(|i| Fp128::from_u64(i + 1)).collect();
+            let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+                .into_iter()
+                .fold(Fp128::zero(), |acc, x| acc + x);
+            let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+            let prover = ProverLayers::new(
+                circuit.clone(),
+                &inputs,
+                num_copies,
+                SumcheckOptions::default(),
+            ).unwrap();
+            let proof = prover.prove(&instance, &mut OsRng).unwrap();
+            assert!(verifier.verify_with_inputs(&instance, &proof, &inputs).unwrap());
+            
+            // Trailing inputs left out read as zero on both sides
+            let mut zeroed = inputs.clone();
+            zeroed[4 * num_copies - 3..].fill(Fp128::zero());
+            let short = &zeroed[..4 * num_copies - 3];
+            let claimed_sum = circuit.evaluate(&zeroed, num_copies).unwrap()
+                .into_iter()
+                .fold(Fp128::zero(), |acc, x| acc + x);
+            let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+            let proof = ProverLayers::new(circuit.clone(), short, num_copies, SumcheckOptions::default())
+                .unwrap()
+                .prove(&instance, &mut OsRng)
+                .unwrap();
+            assert!(verifier.verify_with_inputs(&instance, &proof, short).unwrap());
+            assert!(verifier.verify_with_inputs(&instance, &proof, &zeroed).unwrap());
+            
+            // Inputs for another copy do not fit
+            let mut long = inputs.clone();
+            long.extend_from_slice(&inputs[..4]);
+            assert!(ProverLayers::new(circuit.clone(), &long, num_copies, SumcheckOptions::default()).is_err());
+            assert!(verifier.verify_with_inputs(&instance, &proof, &long).is_err());
+        }
+    }
+    
+    #[test]
+    fn test_parallel_chunks_match_serial() {
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(2, 3, 2).unwrap();
+        for g in 0..4 {
+            builder.add_gate(g, g, 7 - g, GateType::Mul(Fp128::from(g as u64 + 1))).unwrap();
+            builder.add_gate(g, 2 * g, 1, GateType::Add(Fp128::one())).unwrap();
+        }
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        let num_copies = 13;
+        let inputs: Vec<Fp128> = (0..8 * num_copies as u64).map(|i| Fp128::from_u64(i * i + 1)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+        
+        let prove = |parallel, batch_size| {
+            let options = SumcheckOptions { parallel, batch_size, ..SumcheckOptions::default() };
+            ProverLayers::new(circuit.clone(), &inputs, num_copies, options)
+                .unwrap()
+                .prove(&instance, &mut OsRng)
+                .unwrap()
+        };
+        
+        let serial = prove(false, 1024);
+        for batch_size in [1, 3] {
+            let parallel = prove(true, batch_size);
+            assert_eq!(parallel.layer_proofs[0].copy_polys, serial.layer_proofs[0].copy_polys);
+            assert_eq!(parallel.layer_proofs[0].hand_polys, serial.layer_proofs[0].hand_polys);
+            assert_eq!(parallel.input_eval, serial.input_eval);
+        }
+    }
+    
+    #[test]
+    fn test_checkpointed_wires_match() {
+        // Seven layers squaring and summing pairs, then doubling back out
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        for layer in 0..7 {
+            builder.begin_layer(2, 2, 2).unwrap();
+            for g in 0..4 {
+                let (l, r) = (g, (g + layer + 1) % 4);
+                builder.add_gate(g, l, r, GateType::Mul(Fp128::one())).unwrap();
+                builder.add_gate(g, r, l, GateType::Add(Fp128::from(layer as u64 + 1))).unwrap();
+            }
+            builder.finalize_layer().unwrap();
+        }
+        let circuit = builder.build().unwrap();
+        
+        let num_copies = 3;
+        let inputs: Vec<Fp128> = (0..4 * num_copies as u64).map(|i| Fp128::from_u64(i + 2)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+        
+        let prove = |checkpoint_interval| {
+            let options = SumcheckOptions { checkpoint_interval, ..SumcheckOptions::default() };
+            let prover = ProverLayers::new(circuit.clone(), &inputs, num_copies, options).unwrap();
+            let kept = prover.all_wires.iter().filter(|w| w.is_some()).count();
+            (prover.prove(&instance, &mut OsRng).unwrap(), kept)
+        };
+        
+        let (full, kept) = prove(None);
+        assert_eq!(kept, 7);
+        for interval in [1, 3, 10] {
+            let (proof, kept) = prove(Some(interval));
+            assert_eq!(kept, 7usize.div_ceil(interval));
+            for (layer, full) in proof.layer_proofs.iter().zip(&full.layer_proofs) {
+                assert_eq!(layer.copy_polys, full.copy_polys);
+                assert_eq!(layer.hand_polys, full.hand_polys);
+                assert_eq!(layer.wire_claims, full.wire_claims);
+            }
+            
+            let verifier = crate::VerifierLayers::new(circuit.clone());
+            assert!(verifier.verify_with_inputs(&instance, &proof, &inputs).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_wrong_claim_is_refused() {
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(0, 1, 0).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        let inputs = vec![Fp128::from(3), Fp128::from(5), Fp128::from(2), Fp128::from(7)];
+        let prover = ProverLayers::new(
+            circuit.clone(),
+            &inputs,
+            2,
+            SumcheckOptions::default(),
+        ).unwrap();
+        
+        let instance = SumcheckInstance::new(circuit.clone(), 2, Fp128::from(29)).unwrap();
+        assert!(prover.prove(&instance, &mut OsRng).is_ok());
         
-        assert!(!proof.hand_polys.is_empty());
-        assert!(!proof.wire_claims.is_empty());
+        let instance = SumcheckInstance::new(circuit, 2, Fp128::from(30)).unwrap();
+        assert!(prover.prove(&instance, &mut OsRng).is_err());
     }
 }
\ No newline at end of file
@@ -4,6 +4,20 @@ use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
 use serde::{Deserialize, Serialize};
 
+/// Hand index standing for the constant-one wire in a corner; input wire
+/// `w` is hand `w + 1`
+pub const ONE_HAND: usize = 0;
+
+/// Hand index of input wire `wire`
+pub const fn wire_hand(wire: usize) -> usize {
+    wire + 1
+}
+
+/// Input wire behind a hand index, or `None` for the constant one
+pub const fn hand_wire(hand: usize) -> Option<usize> {
+    hand.checked_sub(1)
+}
+
 /// A corner in the sparse quadratic form
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct QuadCorner {
@@ -287,7 +301,8 @@ impl<F: Field> Quad<F> {
         })
     }
     
-    /// Validate the quadratic form
+    /// Validate the quadratic form: gates index `2^max_gates` outputs and
+    /// hands the constant one or one of `2^max_hands` input wires
     pub fn validate(&self, max_gates: usize, max_hands: usize) -> Result<()> {
         let max_g = (1u32 << max_gates) - 1;
         let max_h = (1u32 << max_hands) - 1;
@@ -1,10 +1,15 @@
 /// Transcript management for Sumcheck protocol
 
 use longfellow_algebra::traits::Field;
-use longfellow_random::Transcript;
+use longfellow_random::{labels, Transcript, TranscriptLabel};
 use crate::polynomial::UnivariatePoly;
 
 /// Sumcheck-specific transcript
+///
+/// A thin layer of sumcheck labels over `longfellow_random::Transcript`.
+/// A standalone proof starts its own; a proof inside a larger one runs on
+/// that proof's transcript, so every challenge of the whole is derived from
+/// one chain of messages.
 pub struct SumcheckTranscript {
     base: Transcript,
 }
@@ -12,45 +17,61 @@ pub struct SumcheckTranscript {
 impl SumcheckTranscript {
     /// Create a new sumcheck transcript
     pub fn new(label: &[u8]) -> Self {
-        let base = Transcript::new(b"Sumcheck-v1");
+        Self::from_transcript(Transcript::new(b"Sumcheck-v1"), label)
+    }
+    
+    /// Continue `base`, separating what follows under `label`
+    pub fn from_transcript(base: Transcript, label: &[u8]) -> Self {
         let mut transcript = Self { base };
-        transcript.append_message(b"instance", label);
+        transcript.append_message(labels::SUMCHECK_INSTANCE, label);
         transcript
     }
     
+    /// The underlying transcript, with every sumcheck message in it
+    pub fn into_inner(self) -> Transcript {
+        self.base
+    }
+    
     /// Append circuit information
     pub fn append_circuit_info(&mut self, num_layers: usize, num_copies: usize, claimed_sum: &[u8]) {
-        self.base.append_message(b"num_layers", &(num_layers as u64).to_le_bytes());
-        self.base.append_message(b"num_copies", &(num_copies as u64).to_le_bytes());
-        self.base.append_message(b"claimed_sum", claimed_sum);
+        self.base.append_message(labels::SUMCHECK_NUM_LAYERS, &(num_layers as u64).to_le_bytes());
+        self.base.append_message(labels::SUMCHECK_NUM_COPIES, &(num_copies as u64).to_le_bytes());
+        self.base.append_message(labels::SUMCHECK_CLAIMED_SUM, claimed_sum);
+    }
+    
+    /// Append the point the outputs are bound to
+    pub fn append_output_binding<F: Field>(&mut self, binding: &[F]) {
+        self.base.append_field_elements(labels::SUMCHECK_OUTPUT_BINDING, binding);
     }
     
     /// Append a polynomial
     pub fn append_polynomial<F: Field>(&mut self, round: usize, poly: &UnivariatePoly<F>) {
-        let label = format!("poly_{}", round);
-        self.base.append_field_elements(label.as_bytes(), &poly.coeffs);
+        self.base.append_field_elements(labels::SUMCHECK_POLY.at(round as u64), &poly.coeffs);
     }
     
     /// Append wire claims
     pub fn append_wire_claims<F: Field>(&mut self, layer: usize, claims: &[F]) {
-        let label = format!("wire_claims_{}", layer);
-        self.base.append_field_elements(label.as_bytes(), claims);
+        self.base.append_field_elements(labels::SUMCHECK_WIRE_CLAIMS.at(layer as u64), claims);
     }
     
     /// Get challenge for binding
     pub fn challenge_binding<F: Field>(&mut self, round: usize) -> F {
-        let label = format!("bind_{}", round);
-        self.base.challenge_scalar(label.as_bytes())
+        self.base.challenge_scalar(labels::SUMCHECK_BIND.at(round as u64))
+    }
+    
+    /// Get the challenge combining a layer's two wire claims into the
+    /// next layer's claim
+    pub fn challenge_combination<F: Field>(&mut self, layer: usize) -> F {
+        self.base.challenge_scalar(labels::SUMCHECK_COMBINE.at(layer as u64))
     }
     
     /// Get multiple challenges
     pub fn challenge_bindings<F: Field>(&mut self, round: usize, count: usize) -> Vec<F> {
-        let label = format!("binds_{}", round);
-        self.base.challenge_scalars(label.as_bytes(), count)
+        self.base.challenge_scalars(labels::SUMCHECK_BINDS.at(round as u64), count)
     }
     
     /// Append a message
-    pub fn append_message(&mut self, label: &[u8], msg: &[u8]) {
+    pub fn append_message(&mut self, label: TranscriptLabel, msg: &[u8]) {
         self.base.append_message(label, msg);
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,11 @@
 /// Sumcheck verifier implementation
 
 use longfellow_algebra::traits::Field;
-use longfellow_core::{LongfellowError, Result};
+use longfellow_core::Result;
+use longfellow_random::Transcript;
 
 use crate::{
-    SumcheckInstance, SumcheckProof, LayerProof,
+    SumcheckInstance, SumcheckProof, LayerClaim, LayerProof, multilinear_extension, num_copy_vars, pad_copies,
     circuit::{Circuit, Layer},
     polynomial::UnivariatePoly,
     transcript::SumcheckTranscript,
@@ -12,81 +13,92 @@ use crate::{
 
 /// Sumcheck verifier for a single layer
 pub struct Verifier<F: Field> {
-    _layer: Layer<F>,
+    layer: Layer<F>,
+    num_copies: usize,
+    compressed: bool,
 }
 
 impl<F: Field> Verifier<F> {
     /// Create a new verifier
-    pub fn new(layer: Layer<F>) -> Self {
-        Self { _layer: layer }
+    pub fn new(layer: Layer<F>, num_copies: usize) -> Self {
+        Self { layer, num_copies, compressed: false }
+    }
+    
+    /// Take round polynomials without their linear coefficient, as in
+    /// `SumcheckProof::compress`
+    pub fn with_compressed(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
     }
     
     /// Verify sumcheck proof for a layer
+    ///
+    /// On success returns the point the rounds end at, copy variables then
+    /// each hand in turn, where the proof's wire claims are left on the
+    /// layer's inputs.
     pub fn verify_layer(
         &self,
         proof: &LayerProof<F>,
-        claim: F,
+        claim: &LayerClaim<F>,
         transcript: &mut SumcheckTranscript,
     ) -> Result<(bool, Vec<F>)> {
-        let mut current_claim = claim;
-        let mut round = 0;
-        
-        // Verify copy variable rounds
-        let mut copy_bindings = Vec::new();
-        for poly_coeffs in &proof.copy_polys {
-            let poly = UnivariatePoly::new(poly_coeffs.clone());
-            
-            // Check sum: p(0) + p(1) + p(2) + p(3) = claim
-            let sum = (0..=3)
-                .map(|i| poly.evaluate(F::from_u64(i as u64)))
-                .fold(F::zero(), |acc, x| acc + x);
-            
-            if sum != current_claim {
-                return Ok((false, vec![]));
-            }
-            
-            transcript.append_polynomial(round, &poly);
-            let challenge = transcript.challenge_binding::<F>(round);
-            
-            current_claim = poly.evaluate(challenge);
-            copy_bindings.push(challenge);
-            round += 1;
+        let copy_vars = num_copy_vars(self.num_copies);
+        let num_hands = self.layer.num_hands();
+        let dropped = self.compressed as usize;
+        if proof.copy_polys.len() != copy_vars
+            || proof.hand_polys.len() != num_hands * self.layer.nin
+            || proof.wire_claims.len() != num_hands
+            || proof.copy_polys.iter().any(|p| p.len() > num_hands + 2 - dropped)
+            || proof.hand_polys.iter().any(|p| p.len() > 3 - dropped)
+        {
+            return Ok((false, vec![]));
         }
         
-        // Verify hand variable rounds
-        let mut hand_bindings = Vec::new();
-        for poly_coeffs in &proof.hand_polys {
-            let poly = UnivariatePoly::new(poly_coeffs.clone());
-            
-            // Check sum: p(0) + p(1) = claim
-            let sum = poly.evaluate(F::zero()) + poly.evaluate(F::one());
-            
-            if sum != current_claim {
-                return Ok((false, vec![]));
-            }
+        let mut current_claim = claim.value;
+        let mut bindings = Vec::new();
+        
+        // Copy rounds, then hand rounds, each against the running claim
+        let rounds = proof.copy_polys.iter().chain(&proof.hand_polys);
+        for (round, poly_coeffs) in rounds.enumerate() {
+            let poly = if self.compressed {
+                // The recovered coefficient makes p(0) + p(1) = claim
+                UnivariatePoly::from_compressed(poly_coeffs, current_claim)
+            } else {
+                let poly = UnivariatePoly::new(poly_coeffs.clone());
+                
+                // Check sum: p(0) + p(1) = claim
+                let sum = poly.evaluate(F::zero()) + poly.evaluate(F::one());
+                
+                if sum != current_claim {
+                    return Ok((false, vec![]));
+                }
+                poly
+            };
             
             transcript.append_polynomial(round, &poly);
             let challenge = transcript.challenge_binding::<F>(round);
             
             current_claim = poly.evaluate(challenge);
-            hand_bindings.push(challenge);
-            round += 1;
+            bindings.push(challenge);
         }
         
-        // Verify wire claims
+        // The last round's claim must be the layer polynomial at the
+        // bound point, with the claimed wires in place of the inputs. The
+        // wiring predicates there come from this verifier's own layer;
+        // only the wire values are taken from the proof, and the next
+        // layer or the inputs hold the prover to them
         transcript.append_wire_claims(0, &proof.wire_claims);
-        
-        // Check that wire claims sum to current claim
-        let wire_sum: F = proof.wire_claims.iter().fold(F::zero(), |acc, &x| acc + x);
-        if wire_sum != current_claim {
+        let expected = claim.evaluate_at(
+            &self.layer,
+            self.num_copies,
+            &bindings,
+            &proof.wire_claims,
+        )?;
+        if expected != current_claim {
             return Ok((false, vec![]));
         }
         
-        // Combine all bindings
-        let mut all_bindings = copy_bindings;
-        all_bindings.extend(hand_bindings);
-        
-        Ok((true, all_bindings))
+        Ok((true, bindings))
     }
 }
 
@@ -102,100 +114,129 @@ impl<F: Field> VerifierLayers<F> {
     }
     
     /// Verify complete sumcheck proof
+    ///
+    /// The proof's input evaluations are checked against the chain of
+    /// layers, not against any inputs, so a proof over made-up inputs
+    /// passes. Callers must use `verify_with_inputs`, or check
+    /// `input_eval` against a commitment to the inputs themselves.
     pub fn verify(
         &self,
         instance: &SumcheckInstance<F>,
         proof: &SumcheckProof<F>,
     ) -> Result<bool> {
-        // Check proof structure
-        if proof.layer_proofs.len() != self.circuit.layers.len() {
+        let mut transcript = SumcheckTranscript::new(b"sumcheck");
+        Ok(self.verify_layers(instance, proof, &mut transcript)?.is_some())
+    }
+    
+    /// Verify a sumcheck proof made on the transcript of a larger proof by
+    /// `ProverLayers::prove_with_transcript`, from the same state
+    ///
+    /// On success the transcript holds every sumcheck message, as the
+    /// prover's did; on failure it is left as it was.
+    pub fn verify_with_transcript(
+        &self,
+        instance: &SumcheckInstance<F>,
+        proof: &SumcheckProof<F>,
+        transcript: &mut Transcript,
+    ) -> Result<bool> {
+        let mut sumcheck = SumcheckTranscript::from_transcript(transcript.clone(), b"sumcheck");
+        if self.verify_layers(instance, proof, &mut sumcheck)?.is_none() {
             return Ok(false);
         }
-        
+        *transcript = sumcheck.into_inner();
+        Ok(true)
+    }
+    
+    /// Verify complete sumcheck proof, with the input evaluations checked
+    /// against `inputs`, `2^nin` per copy and zero-padded as in
+    /// `pad_copies`
+    pub fn verify_with_inputs(
+        &self,
+        instance: &SumcheckInstance<F>,
+        proof: &SumcheckProof<F>,
+        inputs: &[F],
+    ) -> Result<bool> {
         let mut transcript = SumcheckTranscript::new(b"sumcheck");
+        let Some(points) = self.verify_layers(instance, proof, &mut transcript)? else {
+            return Ok(false);
+        };
+        
+        let table = pad_copies(inputs, self.circuit.num_inputs(), instance.num_copies)?;
+        for (point, &claimed) in points.iter().zip(&proof.input_eval) {
+            if multilinear_extension(&table, point)? != claimed {
+                return Ok(false);
+            }
+        }
+        
+        Ok(true)
+    }
+    
+    /// Check every layer and the links between them, returning the
+    /// points of the inputs' extension where `input_eval` is claimed, one
+    /// per hand of the last layer
+    fn verify_layers(
+        &self,
+        instance: &SumcheckInstance<F>,
+        proof: &SumcheckProof<F>,
+        transcript: &mut SumcheckTranscript,
+    ) -> Result<Option<Vec<Vec<F>>>> {
+        // Check proof structure
+        if proof.layer_proofs.len() != self.circuit.layers.len()
+            || !Self::check_degree_bounds(proof)
+        {
+            return Ok(None);
+        }
+        
         transcript.append_circuit_info(
             self.circuit.layers.len(),
             instance.num_copies,
-            &instance.claimed_sum.to_bytes_le(),
+            &instance.claimed_sum.to_canonical_bytes(),
         );
         
-        let mut current_claim = instance.claimed_sum;
-        let mut all_bindings = Vec::new();
+        let copy_vars = num_copy_vars(instance.num_copies);
+        let mut claim = instance.output_claim(transcript);
+        let mut point = Vec::new();
         
         // Verify each layer
-        for (_layer_idx, (layer, layer_proof)) in self.circuit.layers.iter()
+        for (layer_idx, (layer, layer_proof)) in self.circuit.layers.iter()
             .zip(&proof.layer_proofs)
             .enumerate() 
         {
-            let verifier = Verifier::new(layer.clone());
+            if layer_idx > 0 {
+                // Link to the claims the layer above left on this one
+                let alpha = transcript.challenge_combination::<F>(layer_idx - 1);
+                let wire_claims = &proof.layer_proofs[layer_idx - 1].wire_claims;
+                claim = LayerClaim::link(&point, copy_vars, wire_claims, alpha);
+            }
+            
+            let verifier = Verifier::new(layer.clone(), instance.num_copies)
+                .with_compressed(proof.compressed);
             let (valid, bindings) = verifier.verify_layer(
                 layer_proof,
-                current_claim,
-                &mut transcript,
+                &claim,
+                transcript,
             )?;
             
             if !valid {
-                return Ok(false);
+                return Ok(None);
             }
             
-            all_bindings.extend(bindings);
-            
-            // Update claim for next layer
-            current_claim = layer_proof.wire_claims.iter().fold(F::zero(), |acc, &x| acc + x);
+            point = bindings;
         }
         
-        // Verify final input evaluation
-        let expected_input_eval = self.compute_expected_input_eval(
-            &all_bindings,
-            &proof.input_eval,
-        )?;
-        
-        if expected_input_eval != current_claim {
-            return Ok(false);
+        // The last layer's wire claims are the input evaluations
+        let last = &proof.layer_proofs[proof.layer_proofs.len() - 1];
+        if proof.input_eval != last.wire_claims {
+            return Ok(None);
         }
         
-        Ok(true)
-    }
-    
-    /// Compute expected input evaluation at binding point
-    fn compute_expected_input_eval(
-        &self,
-        _bindings: &[F],
-        input_eval: &[F],
-    ) -> Result<F> {
-        // This would compute the multilinear extension of inputs
-        // evaluated at the binding point
-        if input_eval.is_empty() {
-            return Err(LongfellowError::InvalidParameter(
-                "Empty input evaluation".to_string()
-            ));
-        }
-        
-        // For now, return sum of input evaluations
-        Ok(input_eval.iter().fold(F::zero(), |acc, &x| acc + x))
-    }
-    
-    /// Verify with public inputs
-    pub fn verify_with_public_inputs(
-        &self,
-        instance: &SumcheckInstance<F>,
-        proof: &SumcheckProof<F>,
-        public_inputs: &[F],
-    ) -> Result<bool> {
-        // First do standard verification
-        if !self.verify(instance, proof)? {
-            return Ok(false);
-        }
-        
-        // Additionally check public inputs match
-        if public_inputs.len() > self.circuit.num_public_inputs {
-            return Ok(false);
-        }
-        
-        // Would check that public inputs are consistent with proof
-        // This requires evaluating the multilinear extension
-        
-        Ok(true)
+        let (copy_point, hands) = point.split_at(copy_vars);
+        let nin = self.circuit.num_input_vars();
+        Ok(Some(
+            (0..last.wire_claims.len())
+                .map(|j| [copy_point, &hands[j * nin..(j + 1) * nin]].concat())
+                .collect(),
+        ))
     }
 }
 
@@ -203,17 +244,20 @@ impl<F: Field> VerifierLayers<F> {
 impl<F: Field> VerifierLayers<F> {
     /// Check polynomial degree bounds
     pub fn check_degree_bounds(proof: &SumcheckProof<F>) -> bool {
+        // Compressed polynomials carry one coefficient fewer
+        let dropped = proof.compressed as usize;
         for layer_proof in &proof.layer_proofs {
-            // Copy polynomials should have degree at most 3
+            // Copy polynomials should have degree at most 4, reached by
+            // layers with cubic gates
             for poly in &layer_proof.copy_polys {
-                if poly.len() > 4 {
+                if poly.len() > 5 - dropped {
                     return false;
                 }
             }
             
             // Hand polynomials should have degree at most 2
             for poly in &layer_proof.hand_polys {
-                if poly.len() > 3 {
+                if poly.len() > 3 - dropped {
                     return false;
                 }
             }
@@ -223,6 +267,9 @@ impl<F: Field> VerifierLayers<F> {
     }
     
     /// Extract all challenges from a proof transcript
+    ///
+    /// Each layer's binding challenges follow the challenge combining the
+    /// previous layer's wire claims.
     pub fn extract_challenges(
         &self,
         instance: &SumcheckInstance<F>,
@@ -232,27 +279,34 @@ impl<F: Field> VerifierLayers<F> {
         transcript.append_circuit_info(
             self.circuit.layers.len(),
             instance.num_copies,
-            &instance.claimed_sum.to_bytes_le(),
+            &instance.claimed_sum.to_canonical_bytes(),
         );
+        // Compressed rounds are recovered from the running claim
+        let mut claim = instance.output_claim(&mut transcript).value;
         
         let mut challenges = Vec::new();
-        let mut round = 0;
         
-        for layer_proof in &proof.layer_proofs {
-            // Copy rounds
-            for poly_coeffs in &layer_proof.copy_polys {
-                let poly = UnivariatePoly::new(poly_coeffs.clone());
-                transcript.append_polynomial(round, &poly);
-                challenges.push(transcript.challenge_binding::<F>(round));
-                round += 1;
+        for (layer_idx, layer_proof) in proof.layer_proofs.iter().enumerate() {
+            if layer_idx > 0 {
+                let alpha = transcript.challenge_combination::<F>(layer_idx - 1);
+                claim = proof.layer_proofs[layer_idx - 1].wire_claims.iter()
+                    .rev()
+                    .fold(F::zero(), |acc, &wire_claim| acc * alpha + wire_claim);
+                challenges.push(alpha);
             }
             
-            // Hand rounds
-            for poly_coeffs in &layer_proof.hand_polys {
-                let poly = UnivariatePoly::new(poly_coeffs.clone());
+            // Copy rounds, then hand rounds
+            let rounds = layer_proof.copy_polys.iter().chain(&layer_proof.hand_polys);
+            for (round, poly_coeffs) in rounds.enumerate() {
+                let poly = if proof.compressed {
+                    UnivariatePoly::from_compressed(poly_coeffs, claim)
+                } else {
+                    UnivariatePoly::new(poly_coeffs.clone())
+                };
                 transcript.append_polynomial(round, &poly);
-                challenges.push(transcript.challenge_binding::<F>(round));
-                round += 1;
+                let challenge = transcript.challenge_binding::<F>(round);
+                claim = poly.evaluate(challenge);
+                challenges.push(challenge);
             }
             
             transcript.append_wire_claims(0, &layer_proof.wire_claims);
@@ -268,6 +322,7 @@ mod tests {
     use crate::circuit::{CircuitBuilder, GateType};
     use crate::prover::ProverLayers;
     use longfellow_algebra::Fp128;
+    use longfellow_random::labels;
     use rand::rngs::OsRng;
     
     #[test]
@@ -305,22 +360,327 @@ mod tests {
         assert!(verifier.verify(&instance, &proof).unwrap());
     }
     
+    /// Two layers over four inputs per copy: (in0 + in1) * (in2 * in3)
+    /// and in0 + in1 + in2 * in3 + 3
+    fn two_layer_circuit() -> Circuit<Fp128> {
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 1, 1).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 1, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 0, GateType::Const(Fp128::from(3))).unwrap();
+        builder.finalize_layer().unwrap();
+        
+        builder.begin_layer(1, 2, 1).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(1, 2, 3, GateType::Mul(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        
+        builder.build().unwrap()
+    }
+    
+    fn prove_two_layers(
+        num_copies: usize,
+    ) -> (VerifierLayers<Fp128>, SumcheckInstance<Fp128>, SumcheckProof<Fp128>, Vec<Fp128>) {
+        let circuit = two_layer_circuit();
+        let inputs: Vec<Fp128> = (0..4 * num_copies as u64).map(|i| Fp128::from(i + 2)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        
+        let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+        let prover = ProverLayers::new(
+            circuit.clone(),
+            &inputs,
+            num_copies,
+            crate::SumcheckOptions::default(),
+        ).unwrap();
+        let proof = prover.prove(&instance, &mut OsRng).unwrap();
+        
+        (VerifierLayers::new(circuit), instance, proof, inputs)
+    }
+    
+    #[test]
+    fn test_layers_link_to_inputs() {
+        for num_copies in [1, 3, 4] {
+            let (verifier, instance, proof, inputs) = prove_two_layers(num_copies);
+            assert!(verifier.verify(&instance, &proof).unwrap());
+            assert!(verifier.verify_with_inputs(&instance, &proof, &inputs).unwrap());
+            
+            let mut other = inputs.clone();
+            other[0] += Fp128::one();
+            assert!(!verifier.verify_with_inputs(&instance, &proof, &other).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_compressed_proof() {
+        for num_copies in [1, 3] {
+            let (verifier, instance, proof, inputs) = prove_two_layers(num_copies);
+            let mut compressed = proof.clone();
+            compressed.compress();
+            
+            // One element fewer per round, and the same rounds underneath
+            let rounds: usize = proof.layer_proofs.iter()
+                .map(|layer| layer.copy_polys.len() + layer.hand_polys.len())
+                .sum();
+            assert_eq!(proof.to_bytes().len() - compressed.to_bytes().len(), rounds * Fp128::canonical_byte_len());
+            assert!(verifier.verify_with_inputs(&instance, &compressed, &inputs).unwrap());
+            assert_eq!(
+                verifier.extract_challenges(&instance, &compressed).unwrap(),
+                verifier.extract_challenges(&instance, &proof).unwrap()
+            );
+            
+            let decoded = SumcheckProof::from_bytes(&compressed.to_bytes()).unwrap();
+            assert!(decoded.compressed);
+            assert!(verifier.verify(&instance, &decoded).unwrap());
+            
+            // A changed coefficient moves the recovered one with it, but
+            // not the rounds after
+            let mut forged = compressed.clone();
+            forged.layer_proofs[1].hand_polys[0][0] += Fp128::one();
+            assert!(!verifier.verify(&instance, &forged).unwrap());
+            
+            // Full polynomials read as compressed are too long
+            let mut mislabeled = proof.clone();
+            mislabeled.compressed = true;
+            assert!(!verifier.verify(&instance, &mislabeled).unwrap());
+        }
+        
+        // The prover compresses when asked to
+        let circuit = two_layer_circuit();
+        let inputs: Vec<Fp128> = (0..8u64).map(|i| Fp128::from(i + 2)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, 2).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), 2, claimed_sum).unwrap();
+        let options = crate::SumcheckOptions { compress_rounds: true, ..crate::SumcheckOptions::default() };
+        let proof = ProverLayers::new(circuit.clone(), &inputs, 2, options)
+            .unwrap()
+            .prove(&instance, &mut OsRng)
+            .unwrap();
+        assert!(proof.compressed);
+        assert!(proof.layer_proofs[0].hand_polys.iter().all(|p| p.len() == 2));
+        assert!(VerifierLayers::new(circuit).verify_with_inputs(&instance, &proof, &inputs).unwrap());
+    }
+    
+    #[test]
+    fn test_shared_transcript() {
+        let circuit = two_layer_circuit();
+        let inputs: Vec<Fp128> = (0..8u64).map(|i| Fp128::from(i + 2)).collect();
+        let claimed_sum = circuit.evaluate(&inputs, 2).unwrap()
+            .into_iter()
+            .fold(Fp128::zero(), |acc, x| acc + x);
+        let instance = SumcheckInstance::new(circuit.clone(), 2, claimed_sum).unwrap();
+        let prover = ProverLayers::new(circuit.clone(), &inputs, 2, crate::SumcheckOptions::default()).unwrap();
+        let verifier = VerifierLayers::new(circuit);
+        
+        // Messages of another component come first
+        let mut before = Transcript::new(b"combined");
+        before.append_message(labels::LIGERO_ROOT, &[7; 32]);
+        
+        let mut proving = before.clone();
+        let proof = prover.prove_with_transcript(&instance, &mut proving, &mut OsRng).unwrap();
+        assert!(!verifier.verify(&instance, &proof).unwrap());
+        
+        let mut verifying = before.clone();
+        assert!(verifier.verify_with_transcript(&instance, &proof, &mut verifying).unwrap());
+        assert_eq!(verifying.finalize(), proving.finalize());
+        
+        // A different earlier message changes every challenge
+        let mut other = Transcript::new(b"combined");
+        other.append_message(labels::LIGERO_ROOT, &[8; 32]);
+        assert!(!verifier.verify_with_transcript(&instance, &proof, &mut other).unwrap());
+    }
+    
+    #[test]
+    fn test_output_binding() {
+        // Four outputs per copy over two layers
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(2, 2, 2).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::one())).unwrap();
+        builder.add_gate(1, 1, 2, GateType::Add(Fp128::from(3))).unwrap();
+        builder.add_gate(2, 3, 3, GateType::Mul(Fp128::from(2))).unwrap();
+        builder.add_gate(3, 0, 0, GateType::Const(Fp128::from(9))).unwrap();
+        builder.add_gate(3, 2, 0, GateType::Mul(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        builder.begin_layer(2, 2, 2).unwrap();
+        for g in 0..4 {
+            builder.add_gate(g, g, 3 - g, GateType::Mul(Fp128::one())).unwrap();
+        }
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        let verifier = VerifierLayers::new(circuit.clone());
+        
+        let num_copies = 3;
+        let inputs: Vec<Fp128> = (0..4 * num_copies as u64).map(|i| Fp128::from(i + 2)).collect();
+        let outputs = circuit.evaluate(&inputs, num_copies).unwrap();
+        let prover = ProverLayers::new(
+            circuit.clone(),
+            &inputs,
+            num_copies,
+            crate::SumcheckOptions::default(),
+        ).unwrap();
+        
+        let at_binding = |binding: &[Fp128]| {
+            outputs.chunks(4).fold(Fp128::zero(), |acc, copy| {
+                acc + multilinear_extension(copy, binding).unwrap()
+            })
+        };
+        let prove = |binding: &[Fp128], claimed_sum| {
+            let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum)
+                .unwrap()
+                .with_output_binding(binding.to_vec())
+                .unwrap();
+            let proof = prover.prove(&instance, &mut OsRng);
+            (instance, proof)
+        };
+        
+        // A boolean binding picks one output of every copy
+        let third = [Fp128::one(), Fp128::zero()];
+        let picked = outputs.chunks(4).fold(Fp128::zero(), |acc, copy| acc + copy[2]);
+        assert_eq!(at_binding(&third), picked);
+        let (instance, proof) = prove(&third, picked);
+        assert!(verifier.verify_with_inputs(&instance, &proof.unwrap(), &inputs).unwrap());
+        
+        let binding = [Fp128::from(17), Fp128::from(23)];
+        let (instance, proof) = prove(&binding, at_binding(&binding));
+        let proof = proof.unwrap();
+        assert!(verifier.verify_with_inputs(&instance, &proof, &inputs).unwrap());
+        
+        // The binding is in the transcript, and the claim is held to it
+        let unbound = SumcheckInstance::new(circuit.clone(), num_copies, instance.claimed_sum).unwrap();
+        assert!(!verifier.verify(&unbound, &proof).unwrap());
+        let moved = unbound.with_output_binding(vec![Fp128::from(17), Fp128::from(24)]).unwrap();
+        assert!(!verifier.verify(&moved, &proof).unwrap());
+        assert!(prove(&binding, at_binding(&binding) + Fp128::one()).1.is_err());
+    }
+    
+    #[test]
+    fn test_rejects_forged_wire_claims() {
+        let (verifier, instance, proof, _) = prove_two_layers(2);
+        
+        // Claims on the middle layer that still sum the same way
+        let mut forged = proof.clone();
+        forged.layer_proofs[0].wire_claims[0] += Fp128::one();
+        forged.layer_proofs[0].wire_claims[1] -= Fp128::one();
+        assert!(!verifier.verify(&instance, &forged).unwrap());
+        
+        let mut forged = proof;
+        forged.input_eval[1] += Fp128::one();
+        assert!(!verifier.verify(&instance, &forged).unwrap());
+    }
+    
+    #[test]
+    fn test_rejects_forged_rounds() {
+        let (verifier, instance, proof, inputs) = prove_two_layers(2);
+        
+        let mut forged = proof.clone();
+        forged.layer_proofs[0].copy_polys[0][0] += Fp128::one();
+        assert!(!matches!(verifier.verify(&instance, &forged), Ok(true)));
+        
+        let mut forged = proof.clone();
+        forged.layer_proofs[1].hand_polys[0][1] += Fp128::one();
+        assert!(!matches!(verifier.verify(&instance, &forged), Ok(true)));
+        
+        // An honest proof of another output sum
+        let circuit = two_layer_circuit();
+        let claimed_sum = instance.claimed_sum + Fp128::one();
+        let other = SumcheckInstance::new(circuit, 2, claimed_sum).unwrap();
+        assert!(!matches!(verifier.verify(&other, &proof), Ok(true)));
+        assert!(!matches!(verifier.verify_with_inputs(&other, &proof, &inputs), Ok(true)));
+    }
+    
+    #[test]
+    fn test_rejects_proof_of_other_wiring() {
+        let (_, instance, proof, inputs) = prove_two_layers(2);
+        
+        // Same shape and the same outputs on these inputs, where in0 is
+        // always in1 - 1, but the first output is 2 in1 - 1
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 1, 1).unwrap();
+        builder.add_gate(0, 0, 1, GateType::Mul(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 1, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 0, GateType::Const(Fp128::from(3))).unwrap();
+        builder.finalize_layer().unwrap();
+        builder.begin_layer(1, 2, 1).unwrap();
+        builder.add_gate(0, 1, 1, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(0, 0, 0, GateType::Const(-Fp128::one())).unwrap();
+        builder.add_gate(1, 2, 3, GateType::Mul(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        let other = builder.build().unwrap();
+        assert_eq!(
+            other.evaluate(&inputs, 2).unwrap(),
+            two_layer_circuit().evaluate(&inputs, 2).unwrap()
+        );
+        
+        // The verifier evaluates its own wiring at the sampled point, so
+        // the original circuit's proof fails against the other wiring
+        let instance = SumcheckInstance::new(other.clone(), 2, instance.claimed_sum).unwrap();
+        assert!(!VerifierLayers::new(other).verify(&instance, &proof).unwrap());
+    }
+    
+    #[test]
+    fn test_cubic_layers() {
+        // Cubic gates on both layers, with constants and add gates beside
+        // them so the quadratic part rides along the third hand
+        let mut builder = CircuitBuilder::<Fp128>::new();
+        builder.begin_layer(1, 2, 1).unwrap();
+        builder.add_cubic_gate(0, [0, 1, 2], Fp128::from(2)).unwrap();
+        builder.add_gate(0, 3, 3, GateType::Mul(Fp128::one())).unwrap();
+        builder.add_gate(1, 1, 2, GateType::Add(Fp128::one())).unwrap();
+        builder.add_gate(1, 0, 0, GateType::Const(Fp128::from(7))).unwrap();
+        builder.finalize_layer().unwrap();
+        
+        builder.begin_layer(2, 2, 2).unwrap();
+        builder.add_cubic_gate(0, [3, 2, 1], Fp128::one()).unwrap();
+        builder.add_cubic_gate(1, [0, 0, 1], Fp128::from(5)).unwrap();
+        builder.add_selected_sum(2, 3, &[(0, Fp128::one()), (2, Fp128::from(4))]).unwrap();
+        builder.add_gate(3, 1, 2, GateType::Add(Fp128::one())).unwrap();
+        builder.finalize_layer().unwrap();
+        let circuit = builder.build().unwrap();
+        
+        for num_copies in [1, 3] {
+            let inputs: Vec<Fp128> = (0..4 * num_copies as u64).map(|i| Fp128::from(i + 3)).collect();
+            let claimed_sum = circuit.evaluate(&inputs, num_copies).unwrap()
+                .into_iter()
+                .fold(Fp128::zero(), |acc, x| acc + x);
+            let instance = SumcheckInstance::new(circuit.clone(), num_copies, claimed_sum).unwrap();
+            let prover = ProverLayers::new(
+                circuit.clone(),
+                &inputs,
+                num_copies,
+                crate::SumcheckOptions::default(),
+            ).unwrap();
+            let proof = prover.prove(&instance, &mut OsRng).unwrap();
+            
+            assert_eq!(proof.layer_proofs[0].wire_claims.len(), 3);
+            assert!(proof.layer_proofs[0].copy_polys.iter().all(|p| p.len() == 5));
+            let verifier = VerifierLayers::new(circuit.clone());
+            assert!(verifier.verify_with_inputs(&instance, &proof, &inputs).unwrap());
+            
+            let mut forged = proof;
+            forged.layer_proofs[0].wire_claims[2] += Fp128::one();
+            assert!(!verifier.verify(&instance, &forged).unwrap());
+        }
+    }
+    
     #[test]
     fn test_degree_bound_check() {
         let proof = SumcheckProof {
             layer_proofs: vec![
                 LayerProof {
-                    copy_polys: vec![vec![Fp128::one(); 4]], // degree 3 - ok
+                    copy_polys: vec![vec![Fp128::one(); 5]], // degree 4 - ok
                     hand_polys: vec![vec![Fp128::one(); 3]], // degree 2 - ok
                     wire_claims: vec![Fp128::one()],
                 },
                 LayerProof {
-                    copy_polys: vec![vec![Fp128::one(); 5]], // degree 4 - too high!
+                    copy_polys: vec![vec![Fp128::one(); 6]], // degree 5 - too high!
                     hand_polys: vec![vec![Fp128::one(); 3]],
                     wire_claims: vec![Fp128::one()],
                 },
             ],
             input_eval: vec![Fp128::one()],
+            compressed: false,
         };
         
         assert!(!VerifierLayers::<Fp128>::check_degree_bounds(&proof));
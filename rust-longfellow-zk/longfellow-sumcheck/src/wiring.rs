@@ -0,0 +1,244 @@
+//! Sparse wiring predicates of a layer
+//!
+//! A layer's gates are few next to the points its wiring predicates range
+//! over, 2^(nout + 2 nin) of them for mul~ alone, so each predicate is kept
+//! as the sparse list of its non-zero points, split by kind: mul~(g, a, b)
+//! for gates reading two wires, add~(g, b) for gates reading the constant
+//! one and a wire, constant~(g), and cubic~(g, a, b, d). Their extensions
+//! are evaluated term by term at bound points, in memory linear in the
+//! number of gates.
+
+use longfellow_algebra::multilinear::SparseMultilinear;
+use longfellow_algebra::traits::Field;
+use longfellow_core::{LongfellowError, Result};
+
+use crate::circuit::Layer;
+use crate::quad::hand_wire;
+
+/// The wiring predicates of one layer
+#[derive(Clone, Debug)]
+pub struct Wiring<F: Field> {
+    /// Number of output variables
+    nout: usize,
+    /// Number of variables per hand
+    nin: usize,
+    /// mul~(g, a, b), over gate then left then right variables
+    mul: SparseMultilinear<F>,
+    /// add~(g, b)
+    add: SparseMultilinear<F>,
+    /// constant~(g)
+    constant: SparseMultilinear<F>,
+    /// cubic~(g, a, b, d)
+    cubic: SparseMultilinear<F>,
+}
+
+impl<F: Field> Wiring<F> {
+    /// Split a layer's gates by kind
+    ///
+    /// Quad corners with hand 0 read the constant one; the lists here hold
+    /// wire indices only.
+    pub fn new(layer: &Layer<F>) -> Result<Self> {
+        let (nout, nin) = (layer.nout, layer.nin);
+        if nout + 3 * nin >= usize::BITS as usize {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Layer of {} output and {} input variables is too wide to index",
+                nout, nin
+            )));
+        }
+        
+        let mut mul = Vec::new();
+        let mut add = Vec::new();
+        let mut constant = Vec::new();
+        for (g, h0, h1, coeff) in layer.quad.iter() {
+            match (hand_wire(h0), hand_wire(h1)) {
+                (None, None) => constant.push((g, coeff)),
+                (None, Some(w)) | (Some(w), None) => add.push(((g << nin) | w, coeff)),
+                (Some(x), Some(y)) => mul.push(((g << (2 * nin)) | (x << nin) | y, coeff)),
+            }
+        }
+        let cubic = layer.cubic.iter().map(|gate| {
+            let [x, y, z] = gate.inputs;
+            ((gate.output << (3 * nin)) | (x << (2 * nin)) | (y << nin) | z, gate.coeff)
+        });
+        
+        Ok(Self {
+            nout,
+            nin,
+            mul: SparseMultilinear::new(nout + 2 * nin, mul)?,
+            add: SparseMultilinear::new(nout + nin, add)?,
+            constant: SparseMultilinear::new(nout, constant)?,
+            cubic: SparseMultilinear::new(nout + 3 * nin, cubic)?,
+        })
+    }
+    
+    /// Gates reading two wires, as (output, left, right, coefficient)
+    pub fn mul_terms(&self) -> impl Iterator<Item = (usize, usize, usize, F)> + '_ {
+        let mask = (1 << self.nin) - 1;
+        self.mul.entries().map(move |(i, coeff)| {
+            (i >> (2 * self.nin), (i >> self.nin) & mask, i & mask, coeff)
+        })
+    }
+    
+    /// Gates reading the constant one and a wire, as (output, wire,
+    /// coefficient)
+    pub fn add_terms(&self) -> impl Iterator<Item = (usize, usize, F)> + '_ {
+        let mask = (1 << self.nin) - 1;
+        self.add.entries().map(move |(i, coeff)| (i >> self.nin, i & mask, coeff))
+    }
+    
+    /// Constant gates, as (output, value)
+    pub fn constant_terms(&self) -> impl Iterator<Item = (usize, F)> + '_ {
+        self.constant.entries()
+    }
+    
+    /// Cubic gates, as (output, wires, coefficient)
+    pub fn cubic_terms(&self) -> impl Iterator<Item = (usize, [usize; 3], F)> + '_ {
+        let mask = (1 << self.nin) - 1;
+        self.cubic.entries().map(move |(i, coeff)| {
+            let wires = [(i >> (2 * self.nin)) & mask, (i >> self.nin) & mask, i & mask];
+            (i >> (3 * self.nin), wires, coeff)
+        })
+    }
+    
+    /// Number of non-zero terms over all predicates
+    pub fn num_terms(&self) -> usize {
+        self.mul.nnz() + self.add.nnz() + self.constant.nnz() + self.cubic.nnz()
+    }
+    
+    /// The gates summed over outputs weighted by `gate_weights`
+    pub fn weighted(&self, gate_weights: &[F]) -> Result<WeightedWiring<F>> {
+        self.check_gate_weights(gate_weights)?;
+        
+        Ok(WeightedWiring {
+            mul: self.mul_terms().map(|(g, l, r, c)| (l, r, c * gate_weights[g])).collect(),
+            add: self.add_terms().map(|(g, h, c)| (h, c * gate_weights[g])).collect(),
+            constant: self.constant_terms().fold(F::zero(), |acc, (g, c)| acc + c * gate_weights[g]),
+            cubic: self.cubic_terms().map(|(g, h, c)| (h, c * gate_weights[g])).collect(),
+        })
+    }
+    
+    /// Weighted predicates at hand points `a` and `b`: mul~, add~ at `b`,
+    /// and the constants
+    pub fn evaluate_at(&self, gate_weights: &[F], a: &[F], b: &[F]) -> Result<[F; 3]> {
+        self.check_gate_weights(gate_weights)?;
+        self.check_hands(&[a, b])?;
+        
+        let mut wiring = [F::zero(); 3];
+        for (g, l, r, coeff) in self.mul_terms() {
+            wiring[0] += coeff * gate_weights[g] * eq_index(a, l) * eq_index(b, r);
+        }
+        for (g, h, coeff) in self.add_terms() {
+            wiring[1] += coeff * gate_weights[g] * eq_index(b, h);
+        }
+        for (g, coeff) in self.constant_terms() {
+            wiring[2] += coeff * gate_weights[g];
+        }
+        
+        Ok(wiring)
+    }
+    
+    /// Weighted cubic~ at hand points `a`, `b` and `d`
+    pub fn evaluate_cubic_at(&self, gate_weights: &[F], a: &[F], b: &[F], d: &[F]) -> Result<F> {
+        self.check_gate_weights(gate_weights)?;
+        self.check_hands(&[a, b, d])?;
+        
+        Ok(self.cubic_terms().fold(F::zero(), |acc, (g, [x, y, z], coeff)| {
+            acc + coeff * gate_weights[g] * eq_index(a, x) * eq_index(b, y) * eq_index(d, z)
+        }))
+    }
+    
+    fn check_gate_weights(&self, gate_weights: &[F]) -> Result<()> {
+        if gate_weights.len() != 1 << self.nout {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Wiring has {} gate weights, expected {}",
+                gate_weights.len(),
+                1 << self.nout
+            )));
+        }
+        Ok(())
+    }
+    
+    fn check_hands(&self, hands: &[&[F]]) -> Result<()> {
+        if let Some(hand) = hands.iter().find(|h| h.len() != self.nin) {
+            return Err(LongfellowError::InvalidParameter(format!(
+                "Wiring hand has {} variables, expected {}",
+                hand.len(),
+                self.nin
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A layer's gates summed over outputs with the weights of a layer claim,
+/// leaving only the hands
+#[derive(Clone, Debug)]
+pub struct WeightedWiring<F: Field> {
+    /// (left, right, weight) of gates reading two wires
+    pub mul: Vec<(usize, usize, F)>,
+    /// (wire, weight) of gates reading the constant one and a wire
+    pub add: Vec<(usize, F)>,
+    /// Weighted sum of the constant gates
+    pub constant: F,
+    /// (wires, weight) of cubic gates
+    pub cubic: Vec<([usize; 3], F)>,
+}
+
+/// eq(point, index) for a boolean `index`, most significant variable first
+/// as in `DenseMultilinear::eq`
+pub fn eq_index<F: Field>(point: &[F], index: usize) -> F {
+    let n = point.len();
+    point.iter().enumerate().fold(F::one(), |acc, (i, &p)| {
+        if (index >> (n - 1 - i)) & 1 == 1 {
+            acc * p
+        } else {
+            acc * (F::one() - p)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::GateType;
+    use longfellow_algebra::multilinear::DenseMultilinear;
+    use longfellow_algebra::Fp128;
+    
+    #[test]
+    fn test_sparse_matches_dense() {
+        let mut layer = Layer::<Fp128>::new(2, 3, 2);
+        for g in 0..4 {
+            layer.add_gate(g, g, 7 - g, GateType::Mul(Fp128::from(g as u64 + 2))).unwrap();
+            layer.add_gate(g, g + 1, 3, GateType::Add(Fp128::from(3))).unwrap();
+        }
+        layer.add_gate(2, 0, 0, GateType::Const(Fp128::from(9))).unwrap();
+        layer.add_cubic_gate(1, [4, 5, 6], Fp128::from(4)).unwrap();
+        let wiring = Wiring::new(&layer).unwrap();
+        // Gate 2 adds wire 3 twice, one term with both coefficients
+        assert_eq!(wiring.num_terms(), 4 + 7 + 1 + 1);
+        
+        let weights: Vec<Fp128> = (0..4u64).map(|g| Fp128::from(g * g + 1)).collect();
+        let a: Vec<Fp128> = (0..3u64).map(|i| Fp128::from(i + 5)).collect();
+        let b: Vec<Fp128> = (0..3u64).map(|i| Fp128::from(2 * i + 7)).collect();
+        let d: Vec<Fp128> = (0..3u64).map(|i| Fp128::from(3 * i + 11)).collect();
+        let eq_a = DenseMultilinear::eq(&a).into_evals();
+        let eq_b = DenseMultilinear::eq(&b).into_evals();
+        let eq_d = DenseMultilinear::eq(&d).into_evals();
+        
+        // The same sums over dense eq tables, from the quad directly
+        let mut dense = [Fp128::zero(); 3];
+        for (g, h0, h1, coeff) in layer.quad.iter() {
+            let weight = coeff * weights[g];
+            match (h0, h1) {
+                (0, 0) => dense[2] += weight,
+                (0, h) | (h, 0) => dense[1] += weight * eq_b[h - 1],
+                (h0, h1) => dense[0] += weight * eq_a[h0 - 1] * eq_b[h1 - 1],
+            }
+        }
+        let cubic = Fp128::from(4) * weights[1] * eq_a[4] * eq_b[5] * eq_d[6];
+        
+        assert_eq!(wiring.evaluate_at(&weights, &a, &b).unwrap(), dense);
+        assert_eq!(wiring.evaluate_cubic_at(&weights, &a, &b, &d).unwrap(), cubic);
+        assert!(wiring.evaluate_at(&weights[..2], &a, &b).is_err());
+    }
+}
@@ -61,6 +61,96 @@ pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
     sha256(&outer)
 }
 
+/// RFC 6979 deterministic nonce with HMAC-SHA256
+///
+/// `order` is the group order q, `key` the private key and `digest` the
+/// message hash h1, all big-endian. Returns k in [1, q) as
+/// ceil(qlen / 8) big-endian bytes; the same inputs always give the same
+/// nonce, and without the key it is indistinguishable from random.
+///
+/// Panics if `order` is zero.
+pub fn rfc6979_nonce(order: &[u8], key: &[u8], digest: &[u8]) -> Vec<u8> {
+    let order = strip_leading_zeros(order);
+    assert!(!order.is_empty(), "Group order must be nonzero");
+    let qlen = order.len() * 8 - order[0].leading_zeros() as usize;
+    let rlen = qlen.div_ceil(8);
+    let q = left_pad(order, rlen);
+
+    let x = left_pad(strip_leading_zeros(key), rlen);
+    let mut h = bits2int(digest, qlen);
+    if h >= q {
+        h = sub_be(&h, &q);
+    }
+
+    let mut v = vec![0x01u8; 32];
+    let mut k = vec![0x00u8; 32];
+    k = hmac_sha256(&k, &[&v[..], &[0x00], &x, &h].concat()).to_vec();
+    v = hmac_sha256(&k, &v).to_vec();
+    k = hmac_sha256(&k, &[&v[..], &[0x01], &x, &h].concat()).to_vec();
+    v = hmac_sha256(&k, &v).to_vec();
+
+    loop {
+        let mut t = Vec::with_capacity(rlen + 32);
+        while t.len() < rlen {
+            v = hmac_sha256(&k, &v).to_vec();
+            t.extend_from_slice(&v);
+        }
+
+        let candidate = bits2int(&t, qlen);
+        if candidate.iter().any(|&b| b != 0) && candidate < q {
+            return candidate;
+        }
+        k = hmac_sha256(&k, &[&v[..], &[0x00]].concat()).to_vec();
+        v = hmac_sha256(&k, &v).to_vec();
+    }
+}
+
+/// The leftmost `qlen` bits of `data` as a big-endian integer of
+/// ceil(qlen / 8) bytes (RFC 6979 section 2.3.2)
+fn bits2int(data: &[u8], qlen: usize) -> Vec<u8> {
+    let rlen = qlen.div_ceil(8);
+    let blen = data.len() * 8;
+    if blen <= qlen {
+        return left_pad(data, rlen);
+    }
+
+    // Shift right by blen - qlen bits, then keep the low rlen bytes
+    let shift = blen - qlen;
+    let (byte_shift, bit_shift) = (shift / 8, shift % 8);
+    let kept = &data[..data.len() - byte_shift];
+    let mut out = vec![0u8; kept.len()];
+    for i in 0..kept.len() {
+        let high = if i == 0 || bit_shift == 0 { 0 } else { kept[i - 1] << (8 - bit_shift) };
+        out[i] = high | (kept[i] >> bit_shift);
+    }
+    out[out.len() - rlen..].to_vec()
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// `bytes` widened to `len` bytes; drops leading bytes if longer
+fn left_pad(bytes: &[u8], len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; len];
+    let take = bytes.len().min(len);
+    out[len - take..].copy_from_slice(&bytes[bytes.len() - take..]);
+    out
+}
+
+/// a - b for equal-length big-endian integers with a >= b
+fn sub_be(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; a.len()];
+    let mut borrow = 0i16;
+    for i in (0..a.len()).rev() {
+        let diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        out[i] = diff.rem_euclid(256) as u8;
+    }
+    out
+}
+
 /// Key derivation function (KDF)
 pub fn kdf_sha256(secret: &[u8], salt: &[u8], info: &[u8], output_len: usize) -> Vec<u8> {
     // Simple KDF using HMAC-SHA256
@@ -158,6 +248,25 @@ mod tests {
         assert_eq!(&hash[..], &expected[..]);
     }
     
+    #[test]
+    fn test_rfc6979_p256_vectors() {
+        // RFC 6979 appendix A.2.5, P-256 with SHA-256
+        let q = hex_decode("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551").unwrap();
+        let x = hex_decode("C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721").unwrap();
+
+        let k = rfc6979_nonce(&q, &x, &sha256(b"sample"));
+        assert_eq!(hex_encode(&k), "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60");
+        let k = rfc6979_nonce(&q, &x, &sha256(b"test"));
+        assert_eq!(hex_encode(&k), "d16b6ae827f17175e040871a1c7ec3500192c4c92677336ec2537acaee0008e0");
+    }
+
+    #[test]
+    fn test_bits2int_truncates() {
+        // qlen = 12 keeps the top 12 bits of 0xABCD
+        assert_eq!(bits2int(&[0xAB, 0xCD], 12), vec![0x0A, 0xBC]);
+        assert_eq!(bits2int(&[0xAB], 12), vec![0x00, 0xAB]);
+    }
+
     #[test]
     fn test_sha3_256() {
         let data = b"hello world";
@@ -179,13 +179,13 @@ fn demo_ligero() -> Result<()> {
     let witness = vec![Fp128::from(3), Fp128::from(7), Fp128::from(21)];
     
     let start = Instant::now();
-    let proof = prover.prove(&witness, &mut OsRng)?;
+    let proof = prover.prove(&witness, &[], &mut OsRng)?;
     let prove_time = start.elapsed();
     
     // Verify proof
     let verifier = LigeroVerifier::new(instance)?;
     let start = Instant::now();
-    let valid = verifier.verify(&proof)?;
+    let valid = verifier.verify(&proof, &[])?;
     let verify_time = start.elapsed();
     
     println!("  Ligero demo:");
@@ -5,7 +5,9 @@
 
 use longfellow_core::{LongfellowError, Result};
 use longfellow_algebra::traits::Field;
+use longfellow_random::{labels, Transcript};
 use longfellow_cbor::{jwt::Jwt, mdoc::Document, vc::VerifiableCredential, ClaimExtractor};
+use longfellow_circuits::{comparison, ecdsa, hash, rsa, utils, CircuitBuilder, StandardCircuit};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -16,6 +18,7 @@ pub mod prover_full;
 pub mod verifier;
 pub mod document;
 pub mod serialization;
+pub mod revocation;
 
 pub use statement::{Statement, Predicate, DocumentType};
 pub use prover::ZkProver;
@@ -171,7 +174,7 @@ impl<F: Field> ZkCircuit<F> {
     
     /// Check if the circuit is satisfied
     pub fn is_satisfied(&self) -> Result<bool> {
-        self.ligero_cs.is_satisfied(&self.wire_values)
+        self.ligero_cs.is_satisfied(&self.wire_values, &[])
     }
 }
 
@@ -249,12 +252,31 @@ pub fn create_instance<F: Field>(
     })
 }
 
+/// The transcript the components of a proof after its Ligero proof run on
+///
+/// It is bound to the statement and the Ligero column root, and the
+/// sumcheck proof continues it, so every challenge of a `ZkProof` is
+/// derived from one domain-separated chain of messages.
+pub fn proof_transcript<F: Field>(
+    statement: &Statement,
+    ligero_proof: &longfellow_ligero::LigeroProof<F>,
+) -> Result<Transcript> {
+    let statement = serde_json::to_vec(statement)
+        .map_err(|e| LongfellowError::SerializationError(e.to_string()))?;
+    
+    let mut transcript = Transcript::new(b"Longfellow-ZK-proof");
+    transcript.append_message(labels::ZK_STATEMENT, &statement);
+    transcript.append_message(labels::LIGERO_ROOT, &ligero_proof.column_root);
+    Ok(transcript)
+}
+
 /// Build a circuit for a given statement
 fn build_circuit_for_statement<F: Field>(
     statement: &Statement,
-    _witness: &ZkWitness,
+    witness: &ZkWitness,
 ) -> Result<ZkCircuit<F>> {
-    let circuit = ZkCircuit::new(1000); // Placeholder size
+    let mut circuit = ZkCircuit::new(1000); // Placeholder size
+    let mut builder = StandardCircuit::new();
     
     // Add constraints based on predicates
     for predicate in &statement.predicates {
@@ -266,20 +288,39 @@ fn build_circuit_for_statement<F: Field>(
             Predicate::FieldExists { field: _ } => {
                 // Add constraints for field existence
             }
-            Predicate::FieldGreaterThan { field: _, value: _ } => {
-                // Add comparison constraints
+            Predicate::FieldGreaterThan { field, value } => {
+                let claim = integer_claim(&witness.document, field)?;
+                let claim = builder.alloc_value(F::from_u64(claim));
+                let bound = utils::const_gate(&mut builder, F::from_u64(*value as u64))?;
+                comparison::assert_less_than(&mut builder, bound, claim, 64)?;
             }
             Predicate::AgeOver { years: _ } => {
                 // Add date arithmetic constraints
             }
             Predicate::ValidSignature => {
-                // Add signature verification constraints
+                build_signature_circuit(&mut builder, statement, witness)?;
             }
             Predicate::ValidIssuer { issuer: _ } => {
                 // Add issuer validation constraints
             }
             Predicate::NotExpired => {
-                // Add expiration check constraints
+                let now = statement
+                    .context
+                    .get("current_time")
+                    .and_then(|t| t.parse::<u64>().ok())
+                    .ok_or_else(|| LongfellowError::InvalidParameter(
+                        "Statement context has no current_time in Unix seconds".to_string()
+                    ))?;
+                let expiry = integer_claim(&witness.document, "exp")?;
+                let expiry = builder.alloc_value(F::from_u64(expiry));
+                let now = utils::const_gate(&mut builder, F::from_u64(now))?;
+                comparison::assert_less_than(&mut builder, now, expiry, 64)?;
+            }
+            Predicate::NotRevoked { field: _, revocation_root: _ } => {
+                // Add sparse Merkle non-membership constraints
+            }
+            Predicate::StatusEquals { field: _, status_root: _, status: _ } => {
+                // Add sparse Merkle membership constraints
             }
             Predicate::Custom { id: _, params: _ } => {
                 // Add custom constraints
@@ -287,9 +328,84 @@ fn build_circuit_for_statement<F: Field>(
         }
     }
     
+    if builder.num_vars() > 0 {
+        circuit.wire_values = builder.witness()?;
+        circuit.ligero_cs = builder.constraints;
+    }
+    
     Ok(circuit)
 }
 
+/// A non-negative integer claim of the document, for comparisons
+fn integer_claim(document: &DocumentData, field: &str) -> Result<u64> {
+    let claim = match document {
+        DocumentData::Jwt(jwt) => jwt.get_claim(field),
+        DocumentData::Mdoc(mdoc) => mdoc.get_claim(field),
+        DocumentData::VerifiableCredential(vc) => vc.get_claim(field),
+        DocumentData::Raw(_) => None,
+    };
+    match claim {
+        Some(longfellow_cbor::Value::Integer(value)) if value >= 0 => Ok(value as u64),
+        Some(_) => Err(LongfellowError::InvalidParameter(
+            format!("Claim {} is not a non-negative integer", field)
+        )),
+        None => Err(LongfellowError::InvalidParameter(
+            format!("Document has no claim {}", field)
+        )),
+    }
+}
+
+/// Constrain the issuer's signature over the document
+///
+/// Only ES256 and RS256 JWTs are supported so far. The issuer's key is
+/// hex-encoded under `issuer_public_key` in the statement's context: a
+/// SEC1 point for ES256, the big-endian modulus for RS256, whose exponent
+/// is 65537. The signed hash is computed in-circuit from the signing
+/// input, so the signature covers the header and payload the prover holds.
+fn build_signature_circuit<F: Field>(
+    circuit: &mut StandardCircuit<F>,
+    statement: &Statement,
+    witness: &ZkWitness,
+) -> Result<()> {
+    let jwt = match &witness.document {
+        DocumentData::Jwt(jwt) if matches!(jwt.algorithm(), "ES256" | "RS256") => jwt,
+        _ => {
+            return Err(LongfellowError::UnsupportedOperation(
+                "Signature circuits are only available for ES256 and RS256 JWTs".to_string()
+            ));
+        }
+    };
+    
+    let key = statement
+        .context
+        .get("issuer_public_key")
+        .ok_or_else(|| LongfellowError::InvalidParameter(
+            "Statement context has no issuer_public_key".to_string()
+        ))?;
+    let key = hex::decode(key)
+        .map_err(|e| LongfellowError::InvalidParameter(format!("Invalid issuer public key: {}", e)))?;
+    
+    let mut sha256 = hash::Sha256Circuit::new(&mut *circuit);
+    let message = sha256.alloc_message(jwt.signing_input().as_bytes())?;
+    let msg_hash = sha256.hash(&message)?;
+    
+    if jwt.algorithm() == "RS256" {
+        let field = rsa::modulus_field::<F>(&key)?;
+        let signature = rsa::alloc_signature(circuit, &field, &jwt.signature)?;
+        return rsa::verify_rs256(circuit, &field, &msg_hash, &signature);
+    }
+    
+    let key = longfellow_ec::Point::from_bytes(&key)?;
+    let signature: &[u8; 64] = jwt.signature.as_slice().try_into().map_err(|_| {
+        LongfellowError::InvalidParameter("ES256 signature must be 64 bytes".to_string())
+    })?;
+    let signature = longfellow_ec::ecdsa::EcdsaSignature::from_bytes(signature)?;
+    // Fixed by the statement, so the proof is bound to the issuer's key
+    let pubkey = ecdsa::constant_public_key(circuit, &key)?;
+    let sig = ecdsa::alloc_signature(circuit, &signature)?;
+    ecdsa::verify_ecdsa_p256(circuit, &pubkey, &msg_hash, &sig)
+}
+
 /// Error types specific to ZK operations
 #[derive(Debug, thiserror::Error)]
 pub enum ZkError {
@@ -2,11 +2,12 @@
 
 use crate::{
     ZkInstance, ZkProof, ZkCircuit, ProofOptions, ProofMetadata, CircuitStats,
-    document::{ClaimExtractor, CommitmentGenerator},
+    document::{ClaimExtractor, CommitmentGenerator}, proof_transcript,
 };
 use longfellow_algebra::traits::Field;
 use rand::SeedableRng;
 use longfellow_core::{LongfellowError, Result};
+use longfellow_random::Transcript;
 use longfellow_ligero::{LigeroProver, LigeroInstance, LigeroParams};
 use longfellow_sumcheck::{SumcheckInstance, SumcheckOptions, prover::ProverLayers};
 use rand::{CryptoRng, RngCore};
@@ -66,9 +67,11 @@ impl<F: Field> ZkProver<F> {
         // Generate Ligero proof
         let ligero_proof = self.generate_ligero_proof(&instance.circuit, rng)?;
         
-        // Optionally generate Sumcheck proof
+        // Optionally generate Sumcheck proof, on the transcript the Ligero
+        // proof leaves
         let sumcheck_proof = if self.options.use_sumcheck {
-            Some(self.generate_sumcheck_proof(&instance.circuit, rng)?)
+            let mut transcript = proof_transcript(&instance.statement, &ligero_proof)?;
+            Some(self.generate_sumcheck_proof(&instance.circuit, &mut transcript, rng)?)
         } else {
             None
         };
@@ -119,13 +122,14 @@ impl<F: Field> ZkProver<F> {
         let prover = LigeroProver::new(ligero_instance)?;
         
         // Generate proof
-        prover.prove(&circuit.wire_values, rng)
+        prover.prove(&circuit.wire_values, &[], rng)
     }
     
     /// Generate Sumcheck proof
     fn generate_sumcheck_proof<R: RngCore + CryptoRng>(
         &self,
         circuit: &ZkCircuit<F>,
+        transcript: &mut Transcript,
         rng: &mut R,
     ) -> Result<longfellow_sumcheck::SumcheckProof<F>> {
         let sumcheck_circuit = circuit.sumcheck_circuit.as_ref()
@@ -146,6 +150,9 @@ impl<F: Field> ZkProver<F> {
             zero_knowledge: true,
             parallel: self.options.parallel,
             batch_size: 1024,
+            checkpoint_interval: None,
+            compress_rounds: self.options.optimize_size,
+            self_check: false,
         };
         
         let prover = ProverLayers::new(
@@ -156,7 +163,7 @@ impl<F: Field> ZkProver<F> {
         )?;
         
         // Generate proof
-        prover.prove(&sumcheck_instance, rng)
+        prover.prove_with_transcript(&sumcheck_instance, transcript, rng)
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::{
     ZkInstance, ZkProof, ProofOptions, ProofMetadata, CircuitStats,
-    DocumentData, ZkCircuit,
+    DocumentData, ZkCircuit, proof_transcript,
 };
 use longfellow_algebra::{
     traits::Field,
@@ -8,14 +8,14 @@ use longfellow_algebra::{
     fft::FFT,
 };
 use longfellow_core::{LongfellowError, Result};
+use longfellow_random::Transcript;
 use longfellow_ligero::{LigeroProver, LigeroInstance, LigeroParams};
 use longfellow_sumcheck::{
-    SumcheckInstance, SumcheckOptions, Prover as SumcheckProver,
-    Circuit as SumcheckCircuit, Layer,
+    SumcheckInstance, SumcheckOptions, ProverLayers,
+    Circuit as SumcheckCircuit,
 };
 use rand::{CryptoRng, RngCore};
 use std::time::{SystemTime, Instant};
-use rayon::prelude::*;
 
 /// Full zero-knowledge prover with advanced Reed-Solomon encoding
 pub struct FullZkProver<F: Field> {
@@ -66,12 +66,14 @@ impl<F: Field> FullZkProver<F> {
             rng
         )?;
         
-        // Generate Sumcheck proof if requested
+        // Generate Sumcheck proof if requested, on the transcript the
+        // Ligero proof leaves
         let sumcheck_proof = if options.use_sumcheck {
+            let mut transcript = proof_transcript(&self.instance.statement, &ligero_proof)?;
             Some(self.generate_sumcheck_proof_enhanced(
                 &circuit,
-                &encoded_witness,
                 &options,
+                &mut transcript,
                 rng
             )?)
         } else {
@@ -369,102 +371,47 @@ impl<F: Field> FullZkProver<F> {
         
         // Create prover and generate proof
         let prover = LigeroProver::new(ligero_instance)?;
-        prover.prove(&encoded_witness.encoded_values, rng)
+        prover.prove(&encoded_witness.encoded_values, &[], rng)
     }
     
     /// Generate enhanced Sumcheck proof
+    ///
+    /// The circuit is the constraint system itself, laid out with its
+    /// residuals as outputs, so the proven sum is zero exactly when the
+    /// residuals cancel.
     fn generate_sumcheck_proof_enhanced<R: RngCore + CryptoRng>(
         &self,
         circuit: &ZkCircuit<F>,
-        encoded_witness: &EncodedWitness<F>,
         options: &ProofOptions,
+        transcript: &mut Transcript,
         rng: &mut R,
     ) -> Result<longfellow_sumcheck::SumcheckProof<F>> {
-        // Build layered circuit for sumcheck
-        let mut sumcheck_circuit = self.build_layered_circuit(circuit)?;
-        
-        // Adjust for encoded witness
-        let encoded_inputs = self.prepare_encoded_inputs(
+        let sumcheck_circuit = SumcheckCircuit::from_constraint_system(&circuit.ligero_cs)?;
+        let inputs = SumcheckCircuit::constraint_system_inputs(
+            &circuit.ligero_cs,
+            &circuit.wire_values,
             &circuit.public_inputs,
-            encoded_witness,
         )?;
         
-        // Compute claimed sum over encoded values
-        let claimed_sum = encoded_witness.encoded_values.par_iter()
-            .fold(F::zero, |acc, x| acc + *x)
-            .reduce(F::zero, |a, b| a + b);
-        
         // Create sumcheck instance
         let instance = SumcheckInstance::new(
-            sumcheck_circuit,
+            sumcheck_circuit.clone(),
             1, // Single copy
-            claimed_sum,
+            F::zero(),
         )?;
         
         // Configure options
         let sumcheck_options = SumcheckOptions {
             zero_knowledge: true,
             parallel: options.parallel,
-            batch_size: 2048, // Larger batch for encoded values
+            batch_size: 2048,
+            checkpoint_interval: None,
+            compress_rounds: options.optimize_size,
+            self_check: false,
         };
         
-        // Create prover and generate proof
-        let mut prover = SumcheckProver::new(instance, sumcheck_options)?;
-        prover.set_inputs(&vec![encoded_inputs])?;
-        prover.prove(rng)
-    }
-    
-    /// Build layered circuit for sumcheck
-    fn build_layered_circuit(
-        &self,
-        circuit: &ZkCircuit<F>,
-    ) -> Result<SumcheckCircuit<F>> {
-        let mut sumcheck_circuit = SumcheckCircuit::new();
-        
-        // Input layer
-        let input_layer = Layer::new_input(circuit.public_inputs.len());
-        sumcheck_circuit.add_layer(input_layer);
-        
-        // Process constraints to build intermediate layers
-        let num_constraints = circuit.ligero_cs.linear_constraints.num_constraints
-            + circuit.ligero_cs.quadratic_constraints.constraints.len();
-        
-        let layers_needed = (num_constraints as f64).log2().ceil() as usize;
-        
-        for layer_idx in 0..layers_needed {
-            let mut layer = Layer::new(
-                1 << (layers_needed - layer_idx - 1),
-                layer_idx,
-            );
-            
-            // Add gates based on constraints
-            // This is simplified - real implementation would map constraints to gates
-            for i in 0..layer.size() {
-                layer.add_gate(i, vec![(i * 2, F::one()), (i * 2 + 1, F::one())]);
-            }
-            
-            sumcheck_circuit.add_layer(layer);
-        }
-        
-        sumcheck_circuit.finalize()?;
-        Ok(sumcheck_circuit)
-    }
-    
-    /// Prepare encoded inputs for sumcheck
-    fn prepare_encoded_inputs(
-        &self,
-        public_inputs: &[F],
-        encoded_witness: &EncodedWitness<F>,
-    ) -> Result<Vec<F>> {
-        // Combine public inputs with relevant encoded witness values
-        let mut inputs = public_inputs.to_vec();
-        
-        // Add subset of encoded values as additional inputs
-        // In practice, this would be more sophisticated
-        let sample_size = 100.min(encoded_witness.encoded_size);
-        inputs.extend_from_slice(&encoded_witness.encoded_values[..sample_size]);
-        
-        Ok(inputs)
+        let prover = ProverLayers::new(sumcheck_circuit, &inputs, 1, sumcheck_options)?;
+        prover.prove_with_transcript(&instance, transcript, rng)
     }
     
     /// Extract public inputs from claims
@@ -262,7 +262,7 @@ impl<F: Field> ZkProver<F> {
         
         // Create prover and generate proof
         let prover = LigeroProver::new(ligero_instance)?;
-        prover.prove(&circuit.wire_values, rng)
+        prover.prove(&circuit.wire_values, &[], rng)
     }
     
     /// Generate Sumcheck proof
@@ -302,6 +302,9 @@ impl<F: Field> ZkProver<F> {
             zero_knowledge: true,
             parallel: options.parallel,
             batch_size: 1024,
+            checkpoint_interval: None,
+            compress_rounds: options.optimize_size,
+            self_check: false,
         };
         
         let mut prover = SumcheckProver::new(instance, sumcheck_options)?;
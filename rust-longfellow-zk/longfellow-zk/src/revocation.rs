@@ -0,0 +1,184 @@
+//! Revocation and status lists backed by sparse Merkle trees
+//!
+//! Credentials are keyed by SHA-256 of a domain tag and their identifier
+//! (a `jti`, a serial number, a status-list index), so keys spread evenly
+//! over the 256-bit tree. A revocation list holds revoked keys with an empty
+//! value; a status list holds one status byte per key. Issuers publish the
+//! root and hand holders the opening for their credential, which backs the
+//! `NotRevoked` and `StatusEquals` predicates.
+
+use longfellow_core::{LongfellowError, Result};
+use longfellow_merkle::hash::Sha256Hasher;
+use longfellow_merkle::sparse::{SparseKey, SparseMerkleProof, SparseMerkleTree};
+use sha2::{Digest, Sha256};
+
+/// Opening of one credential in a revocation or status list
+pub type StatusProof = SparseMerkleProof<Sha256Hasher>;
+
+/// Tree key of a credential identifier
+pub fn status_key(id: &[u8]) -> SparseKey {
+    Sha256::new()
+        .chain_update(b"longfellow-status-key")
+        .chain_update(id)
+        .finalize()
+        .into()
+}
+
+/// Hex encoding of a list root, as carried in predicates
+pub fn root_to_hex(root: &[u8; 32]) -> String {
+    root.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Parse a 64-character hex root
+pub fn root_from_hex(s: &str) -> Result<[u8; 32]> {
+    if s.len() != 64 || !s.is_ascii() {
+        return Err(LongfellowError::InvalidParameter(
+            "List root must be 64 hex characters".to_string(),
+        ));
+    }
+
+    let mut root = [0u8; 32];
+    for (byte, pair) in root.iter_mut().zip(s.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(pair).expect("ASCII checked above");
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| {
+            LongfellowError::InvalidParameter(format!("Invalid hex in list root: {}", digits))
+        })?;
+    }
+    Ok(root)
+}
+
+/// Set of revoked credential identifiers
+#[derive(Clone, Default)]
+pub struct RevocationList {
+    tree: SparseMerkleTree<Sha256Hasher>,
+}
+
+impl RevocationList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn revoke(&mut self, id: &[u8]) {
+        self.tree.insert(status_key(id), &[]);
+    }
+
+    /// Lift a revocation; returns whether `id` was revoked
+    pub fn reinstate(&mut self, id: &[u8]) -> bool {
+        self.tree.remove(&status_key(id)).is_some()
+    }
+
+    pub fn is_revoked(&self, id: &[u8]) -> bool {
+        self.tree.contains(&status_key(id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        *self.tree.root()
+    }
+
+    /// Opening of `id`: non-membership if it is not revoked
+    pub fn prove(&self, id: &[u8]) -> StatusProof {
+        self.tree.prove(&status_key(id))
+    }
+
+    /// Check that `id` is absent from the list with `root`
+    pub fn verify_not_revoked(root: &[u8; 32], id: &[u8], proof: &StatusProof) -> bool {
+        proof.key == status_key(id) && proof.verify_non_membership(root)
+    }
+}
+
+/// Map from credential identifiers to a status byte
+#[derive(Clone, Default)]
+pub struct StatusList {
+    tree: SparseMerkleTree<Sha256Hasher>,
+}
+
+impl StatusList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_status(&mut self, id: &[u8], status: u8) {
+        self.tree.insert(status_key(id), &[status]);
+    }
+
+    pub fn status(&self, id: &[u8]) -> Option<u8> {
+        self.tree.get(&status_key(id)).and_then(|v| v.first().copied())
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        *self.tree.root()
+    }
+
+    pub fn prove(&self, id: &[u8]) -> StatusProof {
+        self.tree.prove(&status_key(id))
+    }
+
+    /// Check that `id` has `status` in the list with `root`
+    pub fn verify_status(root: &[u8; 32], id: &[u8], status: u8, proof: &StatusProof) -> bool {
+        proof.key == status_key(id) && proof.verify_membership(root, &[status])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revocation_list() {
+        let mut list = RevocationList::new();
+        list.revoke(b"cred-7");
+        list.revoke(b"cred-9");
+        let root = list.root();
+
+        let proof = list.prove(b"cred-1");
+        assert!(RevocationList::verify_not_revoked(&root, b"cred-1", &proof));
+        // The opening is bound to its credential
+        assert!(!RevocationList::verify_not_revoked(&root, b"cred-2", &proof));
+
+        let proof = list.prove(b"cred-7");
+        assert!(!RevocationList::verify_not_revoked(&root, b"cred-7", &proof));
+
+        assert!(list.reinstate(b"cred-7"));
+        let proof = list.prove(b"cred-7");
+        assert!(RevocationList::verify_not_revoked(&list.root(), b"cred-7", &proof));
+    }
+
+    #[test]
+    fn test_status_list() {
+        let mut list = StatusList::new();
+        list.set_status(b"cred-1", 0);
+        list.set_status(b"cred-2", 2);
+        let root = list.root();
+
+        let proof = list.prove(b"cred-2");
+        assert_eq!(list.status(b"cred-2"), Some(2));
+        assert!(StatusList::verify_status(&root, b"cred-2", 2, &proof));
+        assert!(!StatusList::verify_status(&root, b"cred-2", 0, &proof));
+    }
+
+    #[test]
+    fn test_root_hex_roundtrip() {
+        let mut list = RevocationList::new();
+        list.revoke(b"x");
+        let hex = root_to_hex(&list.root());
+        assert_eq!(root_from_hex(&hex).unwrap(), list.root());
+        assert!(root_from_hex("abcd").is_err());
+        assert!(root_from_hex(&"zz".repeat(32)).is_err());
+    }
+}
@@ -242,7 +242,8 @@ pub struct ProofExport<F: Field> {
     pub statement: serde_json::Value,
     /// Ligero proof (base64 encoded)
     pub ligero_proof: String,
-    /// Sumcheck proof (base64 encoded, optional)
+    /// Sumcheck proof in its canonical binary encoding (base64 encoded,
+    /// optional)
     pub sumcheck_proof: Option<String>,
     /// Commitments (hex encoded)
     pub commitments: Vec<String>,
@@ -265,10 +266,7 @@ impl<F: Field + Serialize> ProofExport<F> {
         let ligero_bytes = bincode::serialize(&proof.ligero_proof)
             .map_err(|e| LongfellowError::SerializationError(e.to_string()))?;
         
-        let sumcheck_bytes = proof.sumcheck_proof.as_ref()
-            .map(|sp| bincode::serialize(sp))
-            .transpose()
-            .map_err(|e| LongfellowError::SerializationError(e.to_string()))?;
+        let sumcheck_bytes = proof.sumcheck_proof.as_ref().map(|sp| sp.to_bytes());
         
         Ok(Self {
             version: "2.0.0".to_string(),
@@ -125,6 +125,21 @@ pub enum Predicate {
     /// Document is not expired
     NotExpired,
     
+    /// The credential identifier in `field` is absent from the revocation
+    /// list with this hex-encoded root
+    NotRevoked {
+        field: String,
+        revocation_root: String,
+    },
+    
+    /// The credential identifier in `field` has `status` in the status list
+    /// with this hex-encoded root
+    StatusEquals {
+        field: String,
+        status_root: String,
+        status: u8,
+    },
+    
     /// Custom predicate
     Custom {
         id: String,
@@ -159,6 +174,13 @@ impl Predicate {
                     return Err("Issuer cannot be empty".to_string());
                 }
             }
+            Self::NotRevoked { field, revocation_root: root }
+            | Self::StatusEquals { field, status_root: root, .. } => {
+                if field.is_empty() {
+                    return Err("Field name cannot be empty".to_string());
+                }
+                crate::revocation::root_from_hex(root).map_err(|e| e.to_string())?;
+            }
             Self::Custom { id, .. } => {
                 if id.is_empty() {
                     return Err("Custom predicate ID cannot be empty".to_string());
@@ -174,7 +196,9 @@ impl Predicate {
         match self {
             Self::FieldEquals { field, .. } |
             Self::FieldExists { field } |
-            Self::FieldGreaterThan { field, .. } => vec![field.as_str()],
+            Self::FieldGreaterThan { field, .. } |
+            Self::NotRevoked { field, .. } |
+            Self::StatusEquals { field, .. } => vec![field.as_str()],
             Self::AgeOver { .. } => vec!["birthDate", "birth_date", "dateOfBirth"],
             Self::ValidSignature => vec!["signature"],
             Self::ValidIssuer { .. } => vec!["issuer", "iss"],
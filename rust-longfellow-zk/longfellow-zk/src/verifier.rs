@@ -2,10 +2,11 @@
 
 use crate::{
     ZkProof, Statement, DocumentType, ProofMetadata,
-    document::CommitmentGenerator,
+    document::CommitmentGenerator, proof_transcript,
 };
 use longfellow_algebra::traits::Field;
 use longfellow_core::{LongfellowError, Result};
+use longfellow_random::Transcript;
 use longfellow_ligero::{LigeroVerifier, LigeroInstance, LigeroParams};
 use longfellow_sumcheck::{SumcheckInstance, verifier::VerifierLayers};
 use std::collections::HashMap;
@@ -52,9 +53,11 @@ impl<F: Field> ZkVerifier<F> {
             return Ok(false);
         }
         
-        // Verify Sumcheck proof if present
+        // Verify Sumcheck proof if present, on the transcript the Ligero
+        // proof leaves
         if let Some(ref sumcheck_proof) = proof.sumcheck_proof {
-            if !self.verify_sumcheck_proof(sumcheck_proof, &proof.statement)? {
+            let mut transcript = proof_transcript(&proof.statement, &proof.ligero_proof)?;
+            if !self.verify_sumcheck_proof(sumcheck_proof, &proof.statement, &mut transcript)? {
                 return Ok(false);
             }
         }
@@ -164,7 +167,7 @@ impl<F: Field> ZkVerifier<F> {
         let verifier = LigeroVerifier::new(instance)?;
         
         // Verify proof
-        verifier.verify(proof)
+        verifier.verify(proof, &[])
     }
     
     /// Verify Sumcheck proof
@@ -172,6 +175,7 @@ impl<F: Field> ZkVerifier<F> {
         &self,
         proof: &longfellow_sumcheck::SumcheckProof<F>,
         statement: &Statement,
+        transcript: &mut Transcript,
     ) -> Result<bool> {
         // Reconstruct circuit from statement
         let circuit = self.reconstruct_sumcheck_circuit(statement)?;
@@ -187,7 +191,7 @@ impl<F: Field> ZkVerifier<F> {
         let verifier = VerifierLayers::new(circuit);
         
         // Verify proof
-        verifier.verify(&instance, proof)
+        verifier.verify_with_transcript(&instance, proof, transcript)
     }
     
     /// Reconstruct constraint system from statement